@@ -0,0 +1,364 @@
+//! Scheduled Audits
+//!
+//! `ground audit --all` is meant for a nightly cron: it runs the check suite
+//! over every root listed under `audit` in `.ground.yml`, scores each root's
+//! health, drops expired entries from its baseline, and records the lot in
+//! the registry with when, which Ground, and which commit. Over time the
+//! registry becomes a history of the codebase rather than a cache of the
+//! last thing someone asked about.
+//!
+//! A finding is "new" when the previous audit of the same root and check
+//! didn't have its fingerprint. The run's digest (counts, new findings,
+//! health deltas) is POSTed to `audit.webhook` as JSON, with the readable
+//! version in `text` so chat webhooks can take it as-is.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Arc;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::check::{run_check, CheckError, CheckKind, CheckOptions, Finding, Scope};
+use crate::computations::SymbolGraph;
+use crate::config::GroundConfig;
+use crate::fingerprint::{Baseline, BASELINE_FILE};
+use crate::registry::RegistryError;
+use crate::VerifiedTriad;
+
+#[derive(Error, Debug)]
+pub enum AuditError {
+    #[error("Registry error: {0}")]
+    Registry(#[from] RegistryError),
+    
+    #[error("Check error: {0}")]
+    Check(#[from] CheckError),
+    
+    #[error("Audit root {0} doesn't exist")]
+    MissingRoot(PathBuf),
+}
+
+/// What an audit covers
+#[derive(Debug, Clone)]
+pub struct AuditOptions {
+    pub roots: Vec<PathBuf>,
+    pub checks: Vec<CheckKind>,
+    /// Where to POST the digest
+    pub webhook: Option<String>,
+}
+
+impl AuditOptions {
+    /// Audit one directory with every directory-wide check
+    pub fn for_root(root: impl Into<PathBuf>) -> Self {
+        Self {
+            roots: vec![root.into()],
+            checks: default_checks(),
+            webhook: None,
+        }
+    }
+    
+    /// What the `audit` section of the config governing `dir` asks for
+    ///
+    /// Without a config, `dir` is audited alone.
+    pub fn from_config(dir: &Path) -> Result<Self, AuditError> {
+        let Some((config, config_dir)) = GroundConfig::find_with_root(dir) else {
+            return Ok(Self::for_root(dir));
+        };
+        
+        let roots = if config.audit.roots.is_empty() {
+            vec![config_dir]
+        } else {
+            config.audit.roots.iter().map(|root| config_dir.join(root)).collect()
+        };
+        let checks = if config.audit.checks.is_empty() {
+            default_checks()
+        } else {
+            config.audit.checks.iter().map(|c| c.parse()).collect::<Result<_, _>>()?
+        };
+        
+        Ok(Self { roots, checks, webhook: config.audit.webhook })
+    }
+}
+
+/// Every check that runs over a whole directory (environment needs an entry point)
+pub fn default_checks() -> Vec<CheckKind> {
+    CheckKind::ALL.into_iter().filter(|k| *k != CheckKind::Environment).collect()
+}
+
+/// One check over one root
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditCheck {
+    pub kind: CheckKind,
+    pub findings: Vec<Finding>,
+    /// Findings the previous audit of this root didn't have (none on the first audit)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub new_findings: Option<usize>,
+    pub suppressed: usize,
+    pub files_scanned: usize,
+    /// Why the check couldn't run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Everything an audit found under one root
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditRoot {
+    pub root: PathBuf,
+    pub checks: Vec<AuditCheck>,
+    pub health: Option<f64>,
+    /// Change since the previous health run of this root
+    pub health_delta: Option<f64>,
+    /// Baseline fingerprints dropped because they expired
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pruned_exceptions: Vec<String>,
+    /// Problems outside the checks (health, baseline)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<String>,
+}
+
+impl AuditRoot {
+    pub fn total_findings(&self) -> usize {
+        self.checks.iter().map(|c| c.findings.len()).sum()
+    }
+    
+    pub fn total_new(&self) -> usize {
+        self.checks.iter().filter_map(|c| c.new_findings).sum()
+    }
+}
+
+/// A recorded audit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditRun {
+    pub id: String,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub ground_version: String,
+    /// HEAD of the first root's repository
+    pub git_commit: Option<String>,
+    pub roots: Vec<AuditRoot>,
+    /// Why the digest webhook failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook_error: Option<String>,
+}
+
+impl AuditRun {
+    /// Whether any check, health score, or the webhook failed
+    pub fn has_errors(&self) -> bool {
+        self.webhook_error.is_some()
+            || self.roots.iter().any(|r| !r.errors.is_empty() || r.checks.iter().any(|c| c.error.is_some()))
+    }
+    
+    /// Human-readable summary of the run
+    pub fn digest(&self) -> String {
+        let mut lines = vec![format!(
+            "Ground audit {}{}",
+            self.finished_at.format("%Y-%m-%d %H:%M UTC"),
+            self.git_commit.as_deref().map(|c| format!(" @ {}", &c[..c.len().min(10)])).unwrap_or_default()
+        )];
+        
+        for root in &self.roots {
+            let health = match (root.health, root.health_delta) {
+                (Some(score), Some(delta)) => format!("health {:.1} ({:+.1})", score, delta),
+                (Some(score), None) => format!("health {:.1}", score),
+                _ => "health n/a".to_string(),
+            };
+            lines.push(format!(
+                "{}: {}, {} finding(s), {} new",
+                root.root.display(), health, root.total_findings(), root.total_new()
+            ));
+            for check in &root.checks {
+                match &check.error {
+                    Some(error) => lines.push(format!("  {}: error: {}", check.kind.as_str(), error)),
+                    None if !check.findings.is_empty() => lines.push(format!(
+                        "  {}: {}{}",
+                        check.kind.as_str(),
+                        check.findings.len(),
+                        check.new_findings.filter(|n| *n > 0).map(|n| format!(" (+{} new)", n)).unwrap_or_default()
+                    )),
+                    None => {}
+                }
+            }
+            if !root.pruned_exceptions.is_empty() {
+                lines.push(format!("  {} expired baseline entr(ies) pruned", root.pruned_exceptions.len()));
+            }
+            for error in &root.errors {
+                lines.push(format!("  error: {}", error));
+            }
+        }
+        
+        lines.join("\n")
+    }
+}
+
+/// Run an audit, post its digest, and record it
+pub fn run_audit(vt: &mut VerifiedTriad, options: &AuditOptions) -> Result<AuditRun, AuditError> {
+    let started_at = Utc::now();
+    let roots = options.roots.iter()
+        .map(|root| root.canonicalize().map_err(|_| AuditError::MissingRoot(root.clone())))
+        .collect::<Result<Vec<_>, _>>()?;
+    
+    let mut audited = Vec::new();
+    for root in &roots {
+        audited.push(audit_root(vt, root, &options.checks)?);
+    }
+    
+    let mut run = AuditRun {
+        id: uuid::Uuid::new_v4().to_string(),
+        started_at,
+        finished_at: Utc::now(),
+        ground_version: env!("CARGO_PKG_VERSION").to_string(),
+        git_commit: roots.first().and_then(|root| git_head(root)),
+        roots: audited,
+        webhook_error: None,
+    };
+    
+    if let Some(url) = &options.webhook {
+        run.webhook_error = post_digest(url, &run).err();
+    }
+    
+    vt.registry.record_audit(&run)?;
+    Ok(run)
+}
+
+fn audit_root(vt: &mut VerifiedTriad, root: &Path, checks: &[CheckKind]) -> Result<AuditRoot, AuditError> {
+    let mut errors = Vec::new();
+    
+    let baseline_path = root.join(BASELINE_FILE);
+    let mut pruned_exceptions = Vec::new();
+    let baseline = match Baseline::find_in(root) {
+        Some(mut baseline) => {
+            pruned_exceptions = baseline.prune_expired(Utc::now());
+            if !pruned_exceptions.is_empty() {
+                if let Err(e) = baseline.save(&baseline_path) {
+                    errors.push(format!("couldn't save pruned baseline: {}", e));
+                }
+            }
+            Some(baseline)
+        }
+        None => None,
+    };
+    
+    // One graph for every check that needs it
+    let graph = SymbolGraph::build(root, None).ok().map(Arc::new);
+    let options = CheckOptions { baseline, graph, ..Default::default() };
+    
+    let mut audited = Vec::new();
+    for &kind in checks {
+        let previous = vt.registry.previous_audit_fingerprints(root, kind)?;
+        audited.push(match run_check(kind, Scope::Directory(root.to_path_buf()), &options) {
+            Ok(report) => AuditCheck {
+                kind,
+                new_findings: previous.map(|seen: HashSet<String>| {
+                    report.findings.iter().filter(|f| !seen.contains(&f.fingerprint)).count()
+                }),
+                suppressed: report.suppressed,
+                files_scanned: report.files_scanned,
+                findings: report.findings,
+                error: None,
+            },
+            Err(e) => AuditCheck {
+                kind,
+                findings: Vec::new(),
+                new_findings: None,
+                suppressed: 0,
+                files_scanned: 0,
+                error: Some(e.to_string()),
+            },
+        });
+    }
+    
+    let (health, health_delta) = match vt.compute_health(root) {
+        Ok((report, trend)) => (Some(report.score), trend.map(|t| t.score_delta)),
+        Err(e) => {
+            errors.push(format!("health: {}", e));
+            (None, None)
+        }
+    };
+    
+    Ok(AuditRoot {
+        root: root.to_path_buf(),
+        checks: audited,
+        health,
+        health_delta,
+        pruned_exceptions,
+        errors,
+    })
+}
+
+fn git_head(dir: &Path) -> Option<String> {
+    let output = Command::new("git").arg("-C").arg(dir).args(["rev-parse", "HEAD"]).output().ok()?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// POST the digest, giving up after 30s
+fn post_digest(url: &str, run: &AuditRun) -> Result<(), String> {
+    let payload = serde_json::json!({
+        "text": run.digest(),
+        "run_id": run.id,
+        "git_commit": run.git_commit,
+        "roots": run.roots.iter().map(|r| serde_json::json!({
+            "root": r.root,
+            "health": r.health,
+            "health_delta": r.health_delta,
+            "findings": r.total_findings(),
+            "new_findings": r.total_new(),
+        })).collect::<Vec<_>>(),
+    });
+    
+    ureq::post(url)
+        .timeout(std::time::Duration::from_secs(30))
+        .set("Content-Type", "application/json")
+        .send_string(&payload.to_string())
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::VerificationRegistry;
+    
+    #[test]
+    fn test_audit_records_runs_and_new_findings() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src");
+        std::fs::create_dir_all(&src).unwrap();
+        std::fs::write(src.join("index.ts"), "import { used } from './used';\nused();\n").unwrap();
+        std::fs::write(src.join("used.ts"), "export function used() { return 1; }\n").unwrap();
+        std::fs::write(src.join("stray.ts"), "export function stray() { return 2; }\n").unwrap();
+        
+        // An expired baseline entry is pruned on the way in
+        let baseline_path = dir.path().join(BASELINE_FILE);
+        Baseline::new(["stale".to_string()])
+            .expiring(Utc::now() - chrono::Duration::hours(1))
+            .save(&baseline_path)
+            .unwrap();
+        
+        let mut vt = VerifiedTriad { registry: VerificationRegistry::in_memory().unwrap(), thresholds: Default::default() };
+        let options = AuditOptions {
+            checks: vec![CheckKind::Orphans, CheckKind::DeadExports],
+            ..AuditOptions::for_root(dir.path())
+        };
+        
+        let first = run_audit(&mut vt, &options).unwrap();
+        let root = &first.roots[0];
+        assert_eq!(root.pruned_exceptions, vec!["stale".to_string()]);
+        assert!(Baseline::load(&baseline_path).unwrap().fingerprints.is_empty());
+        assert!(root.health.is_some());
+        assert!(root.checks.iter().all(|c| c.error.is_none() && c.new_findings.is_none()));
+        assert!(root.total_findings() > 0);
+        
+        // A new stray module shows up as new, and health now has a delta
+        std::fs::write(src.join("another.ts"), "export const another = 3;\n").unwrap();
+        let second = run_audit(&mut vt, &options).unwrap();
+        let root = &second.roots[0];
+        assert!(root.total_new() > 0);
+        assert!(root.health_delta.is_some());
+        assert!(second.digest().contains("new"));
+        
+        let history = vt.registry.audit_history(10).unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].id, second.id);
+    }
+}
@@ -11,7 +11,14 @@
 //!
 //! This server supports MCP Apps extension for interactive UIs:
 //! - `ui://ground/duplicate-explorer` - Visual duplicate function explorer
+//!
+//! ## Progress
+//!
+//! When a `tools/call` request carries `_meta.progressToken`, long-running
+//! tools emit `notifications/progress` with the current phase and files
+//! processed.
 
+use std::cell::Cell;
 use std::io::{self, BufRead, Write};
 use std::path::PathBuf;
 use std::time::Instant;
@@ -122,10 +129,19 @@ fn summarize_args(tool_name: &str, args: &Value) -> String {
             let b = args.get("file_b").and_then(|v| v.as_str()).unwrap_or("?");
             format!(" ({} vs {})", shorten_path(a), shorten_path(b))
         }
+        "ground_compare_snippets" => {
+            let lang = args.get("language").and_then(|v| v.as_str()).unwrap_or("?");
+            let lines = |key: &str| args.get(key).and_then(|v| v.as_str()).map(|s| s.lines().count()).unwrap_or(0);
+            format!(" ({}, {} vs {} lines)", lang, lines("code_a"), lines("code_b"))
+        }
         "ground_find_orphans" => {
             let dir = args.get("directory").and_then(|v| v.as_str()).unwrap_or(".");
             format!(" (dir={})", shorten_path(dir))
         }
+        "ground_health" => {
+            let dir = args.get("directory").and_then(|v| v.as_str()).unwrap_or(".");
+            format!(" (dir={})", shorten_path(dir))
+        }
         "ground_check_environment" => {
             let entry = args.get("entry_point").and_then(|v| v.as_str()).unwrap_or("?");
             format!(" (entry={})", shorten_path(entry))
@@ -138,6 +154,14 @@ fn summarize_args(tool_name: &str, args: &Value) -> String {
             let file = args.get("file").and_then(|v| v.as_str()).unwrap_or("?");
             format!(" (file={})", shorten_path(file))
         }
+        "ground_trace_feature" => {
+            let entry = args.get("entry").and_then(|v| v.as_str()).unwrap_or("?");
+            format!(" (entry={})", shorten_path(entry))
+        }
+        "ground_record_feedback" => {
+            let outcome = args.get("outcome").and_then(|v| v.as_str()).unwrap_or("?");
+            format!(" ({})", outcome)
+        }
         _ => String::new()
     }
 }
@@ -152,6 +176,22 @@ fn shorten_path(path: &str) -> String {
     }
 }
 
+/// Send an MCP progress notification for an in-flight request
+fn send_progress(token: &Value, progress: u64, message: &str) {
+    let notification = json!({
+        "jsonrpc": "2.0",
+        "method": "notifications/progress",
+        "params": {
+            "progressToken": token,
+            "progress": progress,
+            "message": message
+        }
+    });
+    let mut stdout = io::stdout();
+    let _ = writeln!(stdout, "{}", notification);
+    let _ = stdout.flush();
+}
+
 fn main() {
     let cli = Cli::parse();
     
@@ -307,7 +347,23 @@ fn main() {
                 log!("{} starting{}", tool_name, args_summary);
                 
                 let start = Instant::now();
-                let result = mcp::handle_tool_call(&mut g, tool_name, &args);
+                let progress_token = request.params.get("_meta")
+                    .and_then(|m| m.get("progressToken"))
+                    .cloned();
+                let result = match progress_token {
+                    Some(token) => {
+                        let tool = tool_name.to_string();
+                        let sent = Cell::new(0u64);
+                        mcp::handle_tool_call_with_progress(&mut g, tool_name, &args, move |p| {
+                            // MCP requires progress to increase with every notification,
+                            // and phases restart their counts, so send a running step
+                            // number and carry the phase counts in the message
+                            sent.set(sent.get() + 1);
+                            send_progress(&token, sent.get(), &format!("{} {}", tool, p.message()));
+                        })
+                    }
+                    None => mcp::handle_tool_call(&mut g, tool_name, &args),
+                };
                 let elapsed = start.elapsed();
                 
                 // Log completion with timing
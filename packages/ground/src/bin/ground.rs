@@ -6,23 +6,37 @@
 //!   ground compare <a> <b>              Compare two files for similarity
 //!   ground count uses <symbol> [path]   Count how many times something is used
 //!   ground check connections <module>   Check if a module is connected
+//!   ground check rename <symbol> [path] Check what renaming a symbol would touch
+//!   ground check run <check> [path]     Run a named check (exits 1 on findings, for CI)
 //!
 //!   ground find duplicates [path]       Find duplicate code
 //!   ground find duplicate-functions [path]  Find duplicate functions
 //!   ground find dead-code <symbol>      Find unused code
 //!   ground find orphans [path]          Find disconnected modules
+//!   ground find barrel-bloat [path]     Find barrels bundling heavy modules most consumers skip
+//!   ground find signature-clusters [path]  Find the same API exported under different names
+//!   ground find feature <entry>         Plan removing a feature (code only its entry reaches)
 //!
 //!   ground claim duplicate <a> <b>      Claim files are duplicates (needs compare first)
 //!   ground claim dead-code <symbol>     Claim code is dead (needs count first)
 //!   ground claim orphan <module>        Claim module is orphaned (needs check first)
 //!
 //!   ground status                       Show what's been checked
+//!   ground health [path]                Score repo health (per package, with trend)
+//!   ground quick-scan [path]            Estimate duplication and adoption from a sample
+//!   ground audit --all                  Audit every configured root into the registry (nightly cron)
+//!   ground feedback <fingerprint> <accepted|rejected>  Record whether a finding's fix went in
+//!   ground calibration                  Compare predicted confidence with observed precision
 
 use std::path::{Path, PathBuf};
 use std::fs;
 use std::collections::HashMap;
 use clap::{Parser, Subcommand};
-use ground::VerifiedTriad;
+use ground::{VerifiedTriad, run_check, AuditOptions, Baseline, CheckKind, CheckOptions, FixOutcome, Scope};
+use ground::fingerprint::BASELINE_FILE;
+use ground::computations::{ExternalUsageReport, PublicApiManifest, SymbolGraph};
+use ground::computations::public_api::find_usage_reports;
+use ground::computations::walk::source_files;
 use ground::exceptions::{check_exception, load_config, smart_threshold};
 use ground::monorepo::{detect_monorepo, suggest_refactoring, generate_loom_command};
 
@@ -68,9 +82,97 @@ enum Commands {
     #[command(subcommand)]
     Claim(ClaimCommands),
     
+    /// Public API manifest (exports other repos consume)
+    #[command(subcommand)]
+    PublicApi(PublicApiCommands),
+    
     /// Show what's been checked
     Status,
     
+    /// Score repo health (duplication, dead exports, orphans, adoption, cycles)
+    Health {
+        /// Repository or package root
+        #[arg(default_value = ".")]
+        path: PathBuf,
+    },
+    
+    /// Estimate duplication and token adoption from a seeded sample of files
+    ///
+    /// Answers whether a full audit is worth running: each estimate comes with
+    /// a 95% confidence interval, compared against a budget.
+    QuickScan {
+        /// Repository or package root
+        #[arg(default_value = ".")]
+        path: PathBuf,
+        /// Percent of files to sample
+        #[arg(long, default_value = "5")]
+        sample: f64,
+        /// Same seed, same sample
+        #[arg(long, default_value = "0")]
+        seed: u64,
+        /// Sample at least this many files
+        #[arg(long, default_value = "30")]
+        min_files: usize,
+        /// Full audit warranted above this duplication density (percent)
+        #[arg(long, default_value = "10")]
+        duplication_budget: f64,
+        /// Full audit warranted below this token adoption (percent)
+        #[arg(long, default_value = "70")]
+        adoption_floor: f64,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    
+    /// Run the check suite and health score, recording findings in the registry
+    ///
+    /// Meant for a nightly cron. Prunes expired baseline entries and POSTs a
+    /// digest to the webhook; exits non-zero if a check or the webhook failed.
+    Audit {
+        /// Root to audit
+        #[arg(default_value = ".")]
+        path: PathBuf,
+        /// Audit every root (and run the checks) listed under `audit` in .ground.yml
+        #[arg(long)]
+        all: bool,
+        /// POST the digest here (overrides `audit.webhook`)
+        #[arg(long)]
+        webhook: Option<String>,
+        /// Print the run as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    
+    /// Record whether the fix for a finding was accepted or rejected
+    ///
+    /// The check and confidence come from the last audit that found the
+    /// fingerprint, unless given.
+    Feedback {
+        /// Finding fingerprint
+        fingerprint: String,
+        /// accepted or rejected
+        outcome: String,
+        /// Check that reported the finding
+        #[arg(long)]
+        check: Option<String>,
+        /// Confidence the check gave the finding
+        #[arg(long)]
+        confidence: Option<f64>,
+        /// Why, for the record
+        #[arg(long)]
+        note: Option<String>,
+    },
+    
+    /// Compare predicted confidence with observed precision per check
+    Calibration {
+        /// Precision an auto-fix threshold should deliver (default: 0.9)
+        #[arg(long)]
+        target: Option<f64>,
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    
     /// Suggest how to fix a duplication
     Suggest {
         /// First file
@@ -99,11 +201,86 @@ enum CheckCommands {
         /// Module path
         module: PathBuf,
     },
+    /// Check what renaming a symbol would touch (strings, dynamic uses, other packages)
+    Rename {
+        /// Symbol to rename
+        symbol: String,
+        /// File defining it, or a directory to search
+        #[arg(default_value = ".")]
+        path: PathBuf,
+    },
     /// Check for environment safety issues (Workers APIs in Node.js or vice versa)
     EnvironmentSafety {
         /// Entry point to analyze (CLI script, Worker index.ts, etc.)
         entry_point: PathBuf,
     },
+    /// Check a package's package.json dependencies against its imports
+    Dependencies {
+        /// Package directory
+        #[arg(default_value = ".")]
+        package: PathBuf,
+    },
+    /// Run a named check (duplicate-functions, dead-exports, orphans, environment, drift, dependencies, conventions, barrel-bloat, signature-clusters)
+    ///
+    /// Exits non-zero when findings exceed --max-findings, for use in CI.
+    Run {
+        /// Check to run
+        check: String,
+        /// Directory to check
+        #[arg(default_value = ".")]
+        path: PathBuf,
+        /// Limit to one file (module for dead-exports, entry point for environment)
+        #[arg(long)]
+        file: Option<PathBuf>,
+        /// Findings allowed before failing
+        #[arg(long, default_value = "0")]
+        max_findings: usize,
+        /// Similarity threshold for duplicate-functions (0.0-1.0)
+        #[arg(long, default_value = "0.8")]
+        threshold: f64,
+        /// Include test files
+        #[arg(long)]
+        include_tests: bool,
+        /// Leave out findings in this baseline (default: <path>/.ground/baseline.json, if present)
+        #[arg(long)]
+        baseline: Option<PathBuf>,
+        /// Record the current findings as the baseline instead of failing on them
+        #[arg(long)]
+        write_baseline: bool,
+        /// With --write-baseline, stop suppressing the recorded findings after this many days
+        #[arg(long, requires = "write_baseline")]
+        expires_in_days: Option<i64>,
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum PublicApiCommands {
+    /// Report what this repo imports from a package, for the package's repo
+    ///
+    /// Drop the output into the package repo's .ground/external-usage/ so its
+    /// dead export analysis knows these symbols are used.
+    Report {
+        /// Repo to scan for imports
+        #[arg(default_value = ".")]
+        path: PathBuf,
+        /// Package to report on (repeatable)
+        #[arg(long = "package", required = true)]
+        packages: Vec<String>,
+        /// Name of this repo, as the package repo will see it
+        #[arg(long)]
+        source: String,
+        /// Write the report here instead of stdout
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// List the externally used symbols Ground knows about for a directory
+    Show {
+        #[arg(default_value = ".")]
+        path: PathBuf,
+    },
 }
 
 #[derive(Subcommand)]
@@ -164,6 +341,26 @@ enum FindCommands {
         #[arg(default_value = ".")]
         path: PathBuf,
     },
+    /// Find barrels re-exporting heavy modules most consumers don't use
+    BarrelBloat {
+        /// Path to search for barrels
+        #[arg(default_value = ".")]
+        path: PathBuf,
+    },
+    /// Find exported functions in different packages that look like the same API
+    SignatureClusters {
+        /// Path to search (usually the monorepo root)
+        #[arg(default_value = ".")]
+        path: PathBuf,
+    },
+    /// Plan removing a feature: the code only its entry reaches
+    Feature {
+        /// Entry file (a route or module), or file#symbol for one export
+        entry: String,
+        /// Print the plan as JSON
+        #[arg(long)]
+        json: bool,
+    },
     /// Find exports that are never imported elsewhere
     DeadExports {
         /// Module to scan for dead exports
@@ -285,6 +482,15 @@ enum ClaimCommands {
         /// Why you're claiming this
         reason: String,
     },
+    /// Claim that a package.json dependency is unused
+    UnusedDependency {
+        /// Package directory
+        package: PathBuf,
+        /// Dependency name
+        dependency: String,
+        /// Why you're claiming this
+        reason: String,
+    },
 }
 
 fn main() {
@@ -323,6 +529,23 @@ fn run(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
             }
             println!("  Evidence ID: {}", evidence.id);
             
+            let explanation = &evidence.explanation;
+            if !explanation.regions.is_empty() {
+                println!();
+                println!("  Aligned regions ({:.0}% of A, {:.0}% of B):",
+                    explanation.coverage_a * 100.0, explanation.coverage_b * 100.0);
+                for region in &explanation.regions {
+                    let shared = region.shared_text();
+                    let shared = match shared.char_indices().nth(80) {
+                        Some((end, _)) => format!("{}…", &shared[..end]),
+                        None => shared,
+                    };
+                    println!("    A:{}-{} ↔ B:{}-{} ({:.0}%)  {}",
+                        region.lines_a.0, region.lines_a.1, region.lines_b.0, region.lines_b.1,
+                        region.similarity * 100.0, shared);
+                }
+            }
+            
             let threshold = vt.thresholds().dry_similarity;
             println!();
             if evidence.similarity >= threshold {
@@ -365,6 +588,60 @@ fn run(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
             Ok(())
         }
         
+        Commands::Check(CheckCommands::Rename { symbol, path }) => {
+            let mut vt = VerifiedTriad::new(&cli.db)?;
+            let impact = vt.rename_impact(&symbol, &path, None)?;
+            
+            println!("Checked what renaming '{}' would touch", symbol);
+            println!();
+            for file in &impact.defined_in {
+                println!("  Defined in: {}", file.display());
+            }
+            for file in &impact.reexported_by {
+                println!("  Re-exported by: {}", file.display());
+            }
+            println!("  Sites: {} ({} an editor rename won't update)", impact.sites.len(), impact.textual_sites().count());
+            println!("  Evidence ID: {}", impact.usage.id);
+            
+            let textual: Vec<_> = impact.textual_sites().collect();
+            if !textual.is_empty() {
+                println!();
+                println!("  Update by hand:");
+                for site in textual.iter().take(10) {
+                    println!("    {}:{} ({:?})  {}", site.file.display(), site.line, site.kind, site.context);
+                }
+                if textual.len() > 10 {
+                    println!("    ... and {} more", textual.len() - 10);
+                }
+            }
+            
+            if !impact.dynamic.is_empty() {
+                println!();
+                println!("  Dynamic uses:");
+                for usage in &impact.dynamic {
+                    let line = usage.line.map(|l| format!(":{}", l)).unwrap_or_default();
+                    println!("    {}{}  {}", usage.file.display(), line, usage.message);
+                }
+            }
+            
+            if !impact.consumers.is_empty() {
+                println!();
+                println!("  Imported by:");
+                for group in &impact.consumers {
+                    let name = group.package.as_deref().unwrap_or("(no package)");
+                    let marker = if group.cross_package { " [other package]" } else { "" };
+                    println!("    {}{}: {} file(s)", name, marker, group.files.len());
+                }
+            }
+            
+            if impact.is_mechanical() {
+                println!();
+                println!("  Safe to rename mechanically.");
+            }
+            
+            Ok(())
+        }
+        
         Commands::Check(CheckCommands::Connections { module }) => {
             let mut vt = VerifiedTriad::new(&cli.db)?;
             let evidence = vt.analyze_connectivity(&module)?;
@@ -373,6 +650,13 @@ fn run(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
             println!();
             println!("  Incoming: {} (files that import this)", evidence.incoming_connections);
             println!("  Outgoing: {} (files this imports)", evidence.outgoing_connections);
+            for state in &evidence.state {
+                let via = match state.kind {
+                    ground::computations::StateConnectionKind::StoreSubscription => "store",
+                    ground::computations::StateConnectionKind::ContextKey => "context key",
+                };
+                println!("  Shared {} '{}' with {} file(s)", via, state.name, state.files.len());
+            }
             println!("  Evidence ID: {}", evidence.id);
             
             if let Some(arch) = &evidence.architectural {
@@ -398,6 +682,108 @@ fn run(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
             Ok(())
         }
         
+        Commands::Check(CheckCommands::Dependencies { package }) => {
+            let mut vt = VerifiedTriad::new(&cli.db)?;
+            let evidence = vt.analyze_dependencies(&package)?;
+            
+            println!("Checked dependencies for {}", evidence.package_name.as_deref().unwrap_or(&package.display().to_string()));
+            println!();
+            println!("  Declared: {}", evidence.declared.len());
+            println!("  Files analyzed: {}", evidence.files_analyzed);
+            println!("  Evidence ID: {}", evidence.id);
+            
+            if !evidence.unused.is_empty() {
+                println!();
+                println!("  Unused ({}):", evidence.unused.len());
+                for dep in &evidence.unused {
+                    println!("    {} ({})", dep.name, dep.kind.as_str());
+                }
+                println!();
+                println!("  You can now run: ground claim unused-dependency {} <name> \"reason\"", package.display());
+            }
+            
+            if !evidence.missing.is_empty() {
+                println!();
+                println!("  Imported but not declared ({}):", evidence.missing.len());
+                for dep in &evidence.missing {
+                    println!("    {} ← {}", dep.name, dep.imported_by[0].display());
+                }
+            }
+            
+            Ok(())
+        }
+        
+        Commands::Check(CheckCommands::Run { check, path, file, max_findings, threshold, include_tests, baseline, write_baseline, expires_in_days, json }) => {
+            let kind: CheckKind = check.parse()?;
+            let baseline_path = baseline.unwrap_or_else(|| path.join(BASELINE_FILE));
+            let scope = match file {
+                Some(file) => Scope::File { path: file, within: path },
+                None => Scope::Directory(path),
+            };
+            let options = CheckOptions {
+                threshold,
+                include_tests,
+                max_findings,
+                baseline: if write_baseline || !baseline_path.exists() {
+                    None
+                } else {
+                    Some(Baseline::load(&baseline_path)?)
+                },
+                ..Default::default()
+            };
+            
+            let report = run_check(kind, scope, &options)?;
+            
+            if write_baseline {
+                let mut written = Baseline::new(report.findings.iter().map(|f| f.fingerprint.clone()));
+                if let Some(days) = expires_in_days {
+                    written = written.expiring(chrono::Utc::now() + chrono::Duration::days(days));
+                }
+                written.save(&baseline_path)?;
+                println!("Wrote {} fingerprint(s) to {}", report.findings.len(), baseline_path.display());
+                return Ok(());
+            }
+            
+            if json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                println!("{} ({} files, {}ms)", kind.as_str(), report.files_scanned, report.duration_ms);
+                println!();
+                for finding in &report.findings {
+                    let location = match finding.line {
+                        Some(line) => format!("{}:{}", finding.file.display(), line),
+                        None => finding.file.display().to_string(),
+                    };
+                    println!("  {:?} {} [{}]", finding.severity, location, finding.fingerprint);
+                    println!("    {}", finding.message);
+                    if let Some(path) = &finding.suggested_path {
+                        println!("    suggested: {}", path.display());
+                    }
+                }
+                if !report.findings.is_empty() {
+                    println!();
+                }
+                if report.suppressed > 0 {
+                    println!("  {} known finding(s) suppressed by baseline/config", report.suppressed);
+                }
+                if !report.externally_used.is_empty() {
+                    println!("  {} export(s) not flagged: externally_used (public API manifest)", report.externally_used.len());
+                }
+                println!(
+                    "  {} finding(s), {} allowed: {}",
+                    report.findings.len(),
+                    max_findings,
+                    if report.passed { "passed" } else { "FAILED" }
+                );
+            }
+            
+            if !report.passed {
+                std::process::exit(1);
+            }
+            
+            Ok(())
+        }
+        
         Commands::Check(CheckCommands::EnvironmentSafety { entry_point }) => {
             use ground::computations::environment::{analyze_environment_safety, WarningSeverity, RuntimeEnvironment};
             
@@ -522,6 +908,64 @@ fn run(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
                         }
                     }
                 }
+                
+                ClaimCommands::UnusedDependency { package, dependency, reason } => {
+                    match vt.claim_unused_dependency(&package, &dependency, &reason) {
+                        Ok(claim) => {
+                            println!("✓ Claim recorded (grounded in evidence)");
+                            println!();
+                            println!("  Dependency: {} ({})", claim.dependency, claim.kind.as_str());
+                            println!("  Package: {}", claim.package_dir.display());
+                            println!("  Reason: {}", claim.reason);
+                            println!("  Claim ID: {}", claim.id);
+                        }
+                        Err(e) => {
+                            println!("✗ Claim blocked");
+                            println!();
+                            println!("  {}", e);
+                            println!();
+                            println!("  You need to check dependencies first:");
+                            println!("  ground check dependencies {}", package.display());
+                            std::process::exit(1);
+                        }
+                    }
+                }
+            }
+            
+            Ok(())
+        }
+        
+        Commands::PublicApi(PublicApiCommands::Report { path, packages, source, output }) => {
+            let graph = SymbolGraph::build(&path, None)?;
+            let report = ExternalUsageReport::from_graph(&source, &graph, &packages);
+            let json = serde_json::to_string_pretty(&report)?;
+            
+            match output {
+                Some(output) => {
+                    std::fs::write(&output, json)?;
+                    println!("Wrote {} symbol(s) used from {} to {}", report.symbols.len(), packages.join(", "), output.display());
+                }
+                None => println!("{}", json),
+            }
+            
+            Ok(())
+        }
+        
+        Commands::PublicApi(PublicApiCommands::Show { path }) => {
+            let config = ground::config::GroundConfig::find_in_ancestors(&path).unwrap_or_default();
+            let reports = find_usage_reports(&path);
+            
+            println!("Public API for {}", path.display());
+            println!();
+            println!("  .ground.yml: {} symbol(s)", config.public_api.symbols.len());
+            for symbol in &config.public_api.symbols {
+                println!("    {}{}", symbol.name, symbol.module.as_ref().map(|m| format!(" ({})", m)).unwrap_or_default());
+            }
+            for report in &reports {
+                println!("  {}: {} symbol(s)", report.source, report.symbols.len());
+                for symbol in &report.symbols {
+                    println!("    {}{}", symbol.name, symbol.module.as_ref().map(|m| format!(" ({})", m)).unwrap_or_default());
+                }
             }
             
             Ok(())
@@ -543,6 +987,173 @@ fn run(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
             Ok(())
         }
         
+        Commands::Health { path } => {
+            let mut vt = VerifiedTriad::new(&cli.db)?;
+            let (report, trend) = vt.compute_health(&path)?;
+            
+            println!("Health of {}", path.display());
+            println!();
+            match &trend {
+                Some(t) => println!("  Score: {:.1}/100 ({:+.1} since {})", report.score, t.score_delta, t.previous_at.format("%Y-%m-%d %H:%M")),
+                None => println!("  Score: {:.1}/100", report.score),
+            }
+            println!();
+            println!("  Duplication density: {:.1}%", report.metrics.duplication_density() * 100.0);
+            println!("  Dead exports:        {} of {}", report.metrics.dead_exports, report.metrics.total_exports);
+            println!("  Orphans:             {}", report.metrics.orphans);
+            if let Some(adoption) = report.metrics.adoption_ratio() {
+                println!("  Token adoption:      {:.1}%", adoption * 100.0);
+            }
+            println!("  Import cycles:       {}", report.metrics.cycles);
+            
+            if report.packages.len() > 1 {
+                println!();
+                println!("  Packages (worst first):");
+                for package in &report.packages {
+                    let delta = trend.as_ref()
+                        .and_then(|t| t.packages.iter().find(|d| d.name == package.name))
+                        .and_then(|d| d.delta)
+                        .map(|d| format!(" ({:+.1})", d))
+                        .unwrap_or_default();
+                    println!("    {:5.1}{}  {}", package.score, delta, package.name);
+                }
+            }
+            
+            Ok(())
+        }
+        
+        Commands::QuickScan { path, sample, seed, min_files, duplication_budget, adoption_floor, json } => {
+            let options = ground::computations::QuickScanOptions {
+                sample_rate: sample / 100.0,
+                seed,
+                min_files,
+                duplication_budget: duplication_budget / 100.0,
+                adoption_floor: adoption_floor / 100.0,
+                ..Default::default()
+            };
+            let report = ground::computations::quick_scan(&path, &options)?;
+            
+            if json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+                return Ok(());
+            }
+            
+            let pct = |x: f64| format!("{:.1}%", x * 100.0);
+            println!("Quick scan of {} (seed {})", path.display(), seed);
+            println!();
+            println!("  Sampled {} of {} files ({} functions), parsed {} in {}ms",
+                report.files_sampled, report.files_total, report.functions_sampled, report.files_parsed, report.duration_ms);
+            println!();
+            let d = &report.duplication;
+            let (files, low, high) = d.extrapolate();
+            println!("  Duplication density: {} (95% CI {} - {}), ~{:.0} files ({:.0} - {:.0})",
+                pct(d.value), pct(d.low), pct(d.high), files, low, high);
+            if let Some(a) = &report.adoption {
+                println!("  Token adoption:      {} (95% CI {} - {}), from {} files",
+                    pct(a.value), pct(a.low), pct(a.high), a.sampled);
+            }
+            println!();
+            let verdict = match report.verdict {
+                ground::computations::AuditVerdict::Warranted => "Full audit warranted",
+                ground::computations::AuditVerdict::NotWarranted => "Full audit not needed",
+                ground::computations::AuditVerdict::Inconclusive => "Inconclusive; sample more (--sample) to decide",
+            };
+            println!("  {}:", verdict);
+            for reason in &report.reasons {
+                println!("    - {}", reason);
+            }
+            
+            Ok(())
+        }
+        
+        Commands::Audit { path, all, webhook, json } => {
+            let mut options = AuditOptions::from_config(&path)?;
+            if !all {
+                options.roots = vec![path];
+            }
+            if webhook.is_some() {
+                options.webhook = webhook;
+            }
+            
+            let mut vt = VerifiedTriad::new(&cli.db)?;
+            let run = vt.audit(&options)?;
+            
+            if json {
+                println!("{}", serde_json::to_string_pretty(&run)?);
+            } else {
+                println!("{}", run.digest());
+                println!();
+                println!("Recorded audit {} ({}s)", run.id, (run.finished_at - run.started_at).num_seconds());
+                if let Some(error) = &run.webhook_error {
+                    eprintln!("Webhook failed: {}", error);
+                }
+            }
+            
+            if run.has_errors() {
+                std::process::exit(1);
+            }
+            
+            Ok(())
+        }
+        
+        Commands::Feedback { fingerprint, outcome, check, confidence, note } => {
+            let outcome: FixOutcome = outcome.parse()?;
+            let check = check.map(|c| c.parse::<CheckKind>()).transpose()?;
+            let mut vt = VerifiedTriad::new(&cli.db)?;
+            let feedback = vt.record_feedback(&fingerprint, outcome, check, confidence, note)?;
+            
+            println!(
+                "✓ Recorded {} for {} ({}, confidence {:.0}%)",
+                feedback.outcome.as_str(),
+                feedback.fingerprint,
+                feedback.check.as_str(),
+                feedback.confidence * 100.0,
+            );
+            Ok(())
+        }
+        
+        Commands::Calibration { target, json } => {
+            let vt = VerifiedTriad::new(&cli.db)?;
+            let report = vt.calibration(target)?;
+            
+            if json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+                return Ok(());
+            }
+            if report.checks.is_empty() {
+                println!("No fix outcomes recorded yet. Record them with: ground feedback <fingerprint> accepted");
+                return Ok(());
+            }
+            
+            println!("Confidence calibration (target precision {:.0}%)", report.target_precision * 100.0);
+            for check in &report.checks {
+                println!();
+                println!(
+                    "  {}: {} outcomes, predicted {:.0}%, observed {:.0}%, calibration error {:.2}",
+                    check.check.as_str(),
+                    check.findings,
+                    check.predicted * 100.0,
+                    check.observed * 100.0,
+                    check.calibration_error,
+                );
+                for bucket in &check.buckets {
+                    println!(
+                        "    {:.1}-{:.1}  {:>4} findings  predicted {:>3.0}%  observed {:>3.0}%",
+                        bucket.lower,
+                        bucket.upper,
+                        bucket.findings,
+                        bucket.predicted * 100.0,
+                        bucket.observed * 100.0,
+                    );
+                }
+                match check.suggested_auto_fix {
+                    Some(threshold) => println!("    Suggested auto-fix threshold: {:.2}", threshold),
+                    None => println!("    Not enough evidence for an auto-fix threshold"),
+                }
+            }
+            Ok(())
+        }
+        
         Commands::Suggest { file_a, file_b } => {
             let mut vt = VerifiedTriad::new(&cli.db)?;
             
@@ -609,6 +1220,15 @@ fn run_find(cmd: FindCommands, db: &Path) -> Result<(), Box<dyn std::error::Erro
         FindCommands::Orphans { path } => {
             find_orphans(&path)
         }
+        FindCommands::BarrelBloat { path } => {
+            find_barrel_bloat(&path)
+        }
+        FindCommands::SignatureClusters { path } => {
+            find_signature_clusters(&path)
+        }
+        FindCommands::Feature { entry, json } => {
+            trace_feature_cmd(&entry, json)
+        }
         FindCommands::DeadExports { module, scope } => {
             find_dead_exports_cmd(&module, &scope)
         }
@@ -644,17 +1264,27 @@ fn find_dead_exports_cmd(module: &Path, scope: &Path) -> Result<(), Box<dyn std:
     println!();
     
     let report = find_dead_exports(module, scope)?;
+    let (dead, externally_used) = PublicApiManifest::load(scope)
+        .partition(report.dead_exports, |d| (&d.name, &d.file, d.line));
     
     println!("Found {} exports in module", report.total_exports);
     println!();
     
-    if report.dead_exports.is_empty() {
+    if !externally_used.is_empty() {
+        println!("Externally used (public API manifest):");
+        for export in &externally_used {
+            println!("  '{}' (line {}) ← {}", export.name, export.line, export.consumers.join(", "));
+        }
+        println!();
+    }
+    
+    if dead.is_empty() {
         println!("✓ All exports are used somewhere in the codebase.");
     } else {
-        println!("⚠ Found {} unused exports:", report.dead_exports.len());
+        println!("⚠ Found {} unused exports:", dead.len());
         println!();
         
-        for (i, dead) in report.dead_exports.iter().enumerate() {
+        for (i, dead) in dead.iter().enumerate() {
             println!("  {}. '{}' (line {})", i + 1, dead.name, dead.line);
             println!("     {}", truncate(&dead.context, 60));
         }
@@ -892,7 +1522,8 @@ fn find_duplicate_functions(
         }
         
         for (i, (name, dups)) in by_name.iter().enumerate() {
-            println!("{}. Function '{}' ({:.0}% similar)", i + 1, name, dups[0].similarity * 100.0);
+            let clone_type = dups.iter().map(|d| d.clone_type).max_by_key(|t| *t as u8).unwrap_or_default();
+            println!("{}. Function '{}' ({:.0}% similar, {} clone)", i + 1, name, dups[0].similarity * 100.0, clone_type);
             
             let mut files_set = std::collections::HashSet::new();
             for dup in dups {
@@ -906,7 +1537,8 @@ fn find_duplicate_functions(
             println!();
         }
         
-        println!("Consider extracting these to a shared module.");
+        println!("Consider extracting these to a shared module. Exact and renamed clones can be merged as-is;");
+        println!("gapped clones have added or removed statements and need review first.");
         std::process::exit(1);
     }
     
@@ -914,36 +1546,8 @@ fn find_duplicate_functions(
 }
 
 fn collect_files(dir: &Path, extensions: &[&str], min_size: u64, files: &mut Vec<PathBuf>) {
-    let entries = match fs::read_dir(dir) {
-        Ok(e) => e,
-        Err(_) => return,
-    };
-    
-    for entry in entries.filter_map(|e| e.ok()) {
-        let path = entry.path();
-        
-        if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-            if name.starts_with('.') || 
-               matches!(name, "node_modules" | "target" | "dist" | "build" | ".svelte-kit" | "__pycache__") {
-                continue;
-            }
-        }
-        
-        if path.is_dir() {
-            collect_files(&path, extensions, min_size, files);
-        } else if path.is_file() {
-            let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
-            if !extensions.contains(&ext) {
-                continue;
-            }
-            
-            if let Ok(meta) = fs::metadata(&path) {
-                if meta.len() >= min_size {
-                    files.push(path);
-                }
-            }
-        }
-    }
+    files.extend(source_files(dir, extensions).into_iter()
+        .filter(|path| fs::metadata(path).is_ok_and(|meta| meta.len() >= min_size)));
 }
 
 fn group_by_size(files: &[PathBuf]) -> HashMap<u64, Vec<PathBuf>> {
@@ -961,72 +1565,32 @@ fn group_by_size(files: &[PathBuf]) -> HashMap<u64, Vec<PathBuf>> {
 }
 
 fn find_orphans(path: &Path) -> Result<(), Box<dyn std::error::Error>> {
-    use ground::computations::analyze_connectivity;
-    
     println!("Finding orphaned modules in {}", path.display());
     println!();
     
-    let mut files: Vec<PathBuf> = Vec::new();
-    collect_files(path, &["ts", "tsx", "js", "jsx"], 0, &mut files);
-    
-    // Filter out test files, index files, and type declaration files
-    let files: Vec<_> = files.into_iter()
-        .filter(|f| {
-            let name = f.file_name().and_then(|n| n.to_str()).unwrap_or("");
-            !name.contains(".test.") && 
-            !name.contains(".spec.") &&
-            !name.ends_with(".d.ts") &&
-            name != "index.ts" &&
-            name != "index.js" &&
-            !name.starts_with("+")  // SvelteKit route files
-        })
-        .collect();
+    let report = run_check(CheckKind::Orphans, Scope::Directory(path.to_path_buf()), &CheckOptions::default())?;
+    let connected = report.files_scanned - report.findings.len();
     
-    println!("Checking {} files...", files.len());
+    println!("Checked {} files", report.files_scanned);
     println!();
     
-    let mut orphans = Vec::new();
-    let mut connected = 0;
-    let mut errors = 0;
-    
-    for file in &files {
-        match analyze_connectivity(file) {
-            Ok(evidence) => {
-                if evidence.total_connections() == 0 && evidence.architectural.is_none() {
-                    orphans.push((file.clone(), evidence));
-                } else {
-                    connected += 1;
-                }
-            }
-            Err(_) => {
-                errors += 1;
-            }
-        }
-    }
-    
-    if orphans.is_empty() {
+    if report.findings.is_empty() {
         println!("No orphaned modules found.");
         println!("  {} modules are connected", connected);
-        if errors > 0 {
-            println!("  {} modules had errors during analysis", errors);
-        }
     } else {
-        println!("Found {} orphaned modules:", orphans.len());
+        println!("Found {} orphaned modules:", report.findings.len());
         println!();
         
-        for (i, (file, _evidence)) in orphans.iter().enumerate() {
+        for (i, finding) in report.findings.iter().enumerate() {
             // Try to show relative path
-            let display_path = file.strip_prefix(path).unwrap_or(file);
+            let display_path = finding.file.strip_prefix(path).unwrap_or(&finding.file);
             println!("  {}. {}", i + 1, display_path.display());
         }
         
         println!();
         println!("Summary:");
-        println!("  {} orphaned (nothing imports them)", orphans.len());
+        println!("  {} orphaned (nothing imports them)", report.findings.len());
         println!("  {} connected", connected);
-        if errors > 0 {
-            println!("  {} errors", errors);
-        }
         
         println!();
         println!("To verify an individual module:");
@@ -1038,6 +1602,123 @@ fn find_orphans(path: &Path) -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+fn find_barrel_bloat(path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    println!("Finding barrel bloat in {}", path.display());
+    println!();
+    
+    let report = run_check(CheckKind::BarrelBloat, Scope::Directory(path.to_path_buf()), &CheckOptions::default())?;
+    
+    if report.findings.is_empty() {
+        println!("No barrels re-export heavy modules that most consumers skip.");
+        return Ok(());
+    }
+    
+    println!("Found {} heavy re-export(s):", report.findings.len());
+    println!();
+    for (i, finding) in report.findings.iter().enumerate() {
+        let display_path = finding.file.strip_prefix(path).unwrap_or(&finding.file);
+        println!("  {}. {}:{}", i + 1, display_path.display(), finding.line.unwrap_or(0));
+        println!("     {}", finding.message);
+    }
+    
+    println!();
+    println!("Full breakdown (utilization, per-module weight and share): ground_find_barrel_bloat over MCP");
+    
+    std::process::exit(1);
+}
+
+fn trace_feature_cmd(entry: &str, json: bool) -> Result<(), Box<dyn std::error::Error>> {
+    use ground::computations::{rename::workspace_root, trace_feature};
+    
+    let file = entry.rsplit_once('#').map_or(entry, |(file, _)| file);
+    let file = Path::new(file).canonicalize()
+        .map_err(|_| format!("Entry not found: {}", file))?;
+    let workspace = workspace_root(&file);
+    let graph = SymbolGraph::build(&workspace, None)?;
+    let trace = trace_feature(entry, &graph)?;
+    
+    if json {
+        println!("{}", serde_json::to_string_pretty(&trace)?);
+        return Ok(());
+    }
+    
+    let display = |path: &Path| path.strip_prefix(&workspace).unwrap_or(path).display().to_string();
+    println!("Feature trace from {}", entry);
+    println!();
+    
+    if trace.entry_shared {
+        println!("Other code imports {} for more than this symbol; only the export goes.", display(&trace.entry));
+        println!();
+    }
+    if !trace.files.is_empty() {
+        println!("Delete {} file(s):", trace.files.len());
+        for file in &trace.files {
+            println!("  {} ({} lines)", display(&file.path), file.lines);
+        }
+        println!();
+    }
+    if !trace.tests.is_empty() {
+        println!("Delete {} test file(s):", trace.tests.len());
+        for test in &trace.tests {
+            println!("  {}", display(test));
+        }
+        println!();
+    }
+    if !trace.exports.is_empty() {
+        println!("Remove {} export(s) only the feature uses:", trace.exports.len());
+        for export in &trace.exports {
+            println!("  '{}' in {}:{}", export.name, display(&export.file), export.line);
+        }
+        println!();
+    }
+    if !trace.references.is_empty() {
+        println!("Edit {} reference(s) in code that stays:", trace.references.len());
+        for reference in &trace.references {
+            let names = if reference.names.is_empty() { "*".to_string() } else { reference.names.join(", ") };
+            println!("  {}:{} imports {} from {}", display(&reference.file), reference.line.unwrap_or(0), names, display(&reference.target));
+        }
+        println!();
+    }
+    
+    println!(
+        "{} removal(s), {} lines; {} file(s) the feature uses stay (other code needs them)",
+        trace.removals(), trace.lines, trace.shared.len()
+    );
+    Ok(())
+}
+
+fn find_signature_clusters(path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    use ground::computations::{analyze_signature_clusters, walk::source_files, SignatureOptions};
+    
+    println!("Finding near-miss APIs in {}", path.display());
+    println!();
+    
+    let files = source_files(path, &["ts", "tsx", "js", "jsx"]);
+    let report = analyze_signature_clusters(&files, &SignatureOptions::default());
+    
+    if report.clusters.is_empty() {
+        println!("No exported functions look like the same API across packages ({} checked).", report.exported_functions);
+        return Ok(());
+    }
+    
+    println!("Found {} API(s) reinvented across packages:", report.clusters.len());
+    for (i, cluster) in report.clusters.iter().enumerate() {
+        println!();
+        println!("  {}. {}", i + 1, cluster.names.join(" / "));
+        for (j, member) in cluster.members.iter().enumerate() {
+            let display_path = member.file.strip_prefix(path).unwrap_or(&member.file);
+            let marker = if j == cluster.canonical { "*" } else { " " };
+            println!("    {} {}  {}:{}", marker, member.signature(), display_path.display(), member.line);
+        }
+        println!("     {}", cluster.suggestion);
+    }
+    
+    println!();
+    println!("* suggested canonical API");
+    
+    std::process::exit(1);
+}
+
 fn find_drift(path: &Path, category: &str, below_threshold: Option<f64>, extensions: Option<&str>, format: &str) -> Result<(), Box<dyn std::error::Error>> {
     use ground::computations::patterns::{analyze_patterns, PatternConfig};
     
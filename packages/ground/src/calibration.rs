@@ -0,0 +1,234 @@
+//! Confidence Calibration
+//!
+//! Findings carry a confidence score and agents act on it: auto-fix above
+//! 0.9, review above 0.5. That only works if a 0.9 finding is right about
+//! nine times in ten. Whether a fix for a finding was accepted or rejected
+//! is recorded against its fingerprint, and the calibration report compares
+//! predicted confidence with observed precision for each check, bucket by
+//! bucket. It also suggests the lowest auto-fix threshold the outcomes
+//! support, so thresholds follow evidence rather than guesses.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::check::{CheckError, CheckKind};
+
+/// Width of a calibration bucket (0.0-0.1, 0.1-0.2, ...)
+const BUCKET_WIDTH: f64 = 0.1;
+
+/// Outcomes needed at or above a threshold before it's suggested
+pub const MIN_SAMPLES: usize = 5;
+
+/// What happened to the fix for a finding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FixOutcome {
+    /// The finding was real and the fix went in
+    Accepted,
+    /// The finding was a false positive, or the fix was wrong
+    Rejected,
+}
+
+impl FixOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FixOutcome::Accepted => "accepted",
+            FixOutcome::Rejected => "rejected",
+        }
+    }
+}
+
+impl std::str::FromStr for FixOutcome {
+    type Err = CheckError;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "accepted" | "accept" => Ok(FixOutcome::Accepted),
+            "rejected" | "reject" => Ok(FixOutcome::Rejected),
+            _ => Err(CheckError::Analysis(format!("Unknown outcome: {} (expected accepted or rejected)", s))),
+        }
+    }
+}
+
+/// A recorded outcome for one finding
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Feedback {
+    pub fingerprint: String,
+    pub check: CheckKind,
+    /// Confidence the check gave the finding
+    pub confidence: f64,
+    pub outcome: FixOutcome,
+    pub note: Option<String>,
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// Outcomes whose predicted confidence fell in one range
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalibrationBucket {
+    pub lower: f64,
+    pub upper: f64,
+    pub findings: usize,
+    pub accepted: usize,
+    /// Average predicted confidence
+    pub predicted: f64,
+    /// Share of fixes accepted
+    pub observed: f64,
+}
+
+/// How well one check's confidence matches reality
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckCalibration {
+    pub check: CheckKind,
+    pub findings: usize,
+    pub accepted: usize,
+    pub predicted: f64,
+    pub observed: f64,
+    /// Weighted average gap between predicted and observed across buckets
+    /// (0 is perfectly calibrated)
+    pub calibration_error: f64,
+    /// Non-empty buckets, lowest confidence first
+    pub buckets: Vec<CalibrationBucket>,
+    /// Lowest confidence at which findings at or above it met the target
+    /// precision, over at least `MIN_SAMPLES` outcomes
+    pub suggested_auto_fix: Option<f64>,
+}
+
+/// Calibration across every check with recorded outcomes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalibrationReport {
+    /// Precision an auto-fix threshold has to deliver
+    pub target_precision: f64,
+    pub checks: Vec<CheckCalibration>,
+    pub generated_at: DateTime<Utc>,
+}
+
+/// Compare predicted confidence with observed precision, per check
+pub fn calibrate(feedback: &[Feedback], target_precision: f64) -> CalibrationReport {
+    let checks = CheckKind::ALL.iter()
+        .filter_map(|kind| {
+            let outcomes: Vec<&Feedback> = feedback.iter().filter(|f| f.check == *kind).collect();
+            (!outcomes.is_empty()).then(|| calibrate_check(*kind, &outcomes, target_precision))
+        })
+        .collect();
+    
+    CalibrationReport {
+        target_precision,
+        checks,
+        generated_at: Utc::now(),
+    }
+}
+
+fn calibrate_check(check: CheckKind, outcomes: &[&Feedback], target_precision: f64) -> CheckCalibration {
+    let total = outcomes.len();
+    let bucket_count = (1.0 / BUCKET_WIDTH).round() as usize;
+    
+    let buckets: Vec<CalibrationBucket> = (0..bucket_count)
+        .filter_map(|i| {
+            let in_bucket: Vec<&&Feedback> = outcomes.iter()
+                .filter(|f| bucket_index(f.confidence, bucket_count) == i)
+                .collect();
+            if in_bucket.is_empty() {
+                return None;
+            }
+            let accepted = in_bucket.iter().filter(|f| f.outcome == FixOutcome::Accepted).count();
+            Some(CalibrationBucket {
+                lower: i as f64 * BUCKET_WIDTH,
+                upper: (i + 1) as f64 * BUCKET_WIDTH,
+                findings: in_bucket.len(),
+                accepted,
+                predicted: in_bucket.iter().map(|f| f.confidence).sum::<f64>() / in_bucket.len() as f64,
+                observed: accepted as f64 / in_bucket.len() as f64,
+            })
+        })
+        .collect();
+    
+    let accepted = outcomes.iter().filter(|f| f.outcome == FixOutcome::Accepted).count();
+    let calibration_error = buckets.iter()
+        .map(|b| b.findings as f64 / total as f64 * (b.predicted - b.observed).abs())
+        .sum();
+    
+    CheckCalibration {
+        check,
+        findings: total,
+        accepted,
+        predicted: outcomes.iter().map(|f| f.confidence).sum::<f64>() / total as f64,
+        observed: accepted as f64 / total as f64,
+        calibration_error,
+        buckets,
+        suggested_auto_fix: suggest_threshold(outcomes, target_precision),
+    }
+}
+
+fn bucket_index(confidence: f64, bucket_count: usize) -> usize {
+    ((confidence.clamp(0.0, 1.0) / BUCKET_WIDTH) as usize).min(bucket_count - 1)
+}
+
+/// Walk down from the most confident outcome, keeping the lowest cut-off at
+/// which everything above it still meets the target
+fn suggest_threshold(outcomes: &[&Feedback], target_precision: f64) -> Option<f64> {
+    let mut sorted: Vec<&&Feedback> = outcomes.iter().collect();
+    sorted.sort_by(|a, b| b.confidence.partial_cmp(&a.confidence).unwrap_or(std::cmp::Ordering::Equal));
+    
+    let mut suggested = None;
+    let (mut seen, mut accepted) = (0, 0);
+    for (i, feedback) in sorted.iter().enumerate() {
+        seen += 1;
+        if feedback.outcome == FixOutcome::Accepted {
+            accepted += 1;
+        }
+        // Only cut between distinct confidences
+        if sorted.get(i + 1).is_some_and(|next| next.confidence == feedback.confidence) {
+            continue;
+        }
+        if seen >= MIN_SAMPLES && accepted as f64 / seen as f64 >= target_precision {
+            suggested = Some(feedback.confidence);
+        }
+    }
+    suggested
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn outcome(check: CheckKind, confidence: f64, accepted: bool) -> Feedback {
+        Feedback {
+            fingerprint: format!("{}-{}-{}", check.as_str(), confidence, accepted),
+            check,
+            confidence,
+            outcome: if accepted { FixOutcome::Accepted } else { FixOutcome::Rejected },
+            note: None,
+            recorded_at: Utc::now(),
+        }
+    }
+    
+    #[test]
+    fn test_calibrate_per_check() {
+        let mut feedback = Vec::new();
+        // Orphans: 0.95 findings are right 6 times in 6, 0.85 ones 3 in 4,
+        // 0.55 ones 1 in 4
+        feedback.extend((0..6).map(|_| outcome(CheckKind::Orphans, 0.95, true)));
+        feedback.extend([true, true, true, false].map(|ok| outcome(CheckKind::Orphans, 0.85, ok)));
+        feedback.extend([true, false, false, false].map(|ok| outcome(CheckKind::Orphans, 0.55, ok)));
+        feedback.push(outcome(CheckKind::DeadExports, 0.9, false));
+        
+        let report = calibrate(&feedback, 0.9);
+        assert_eq!(report.checks.len(), 2);
+        
+        let orphans = &report.checks[report.checks.iter().position(|c| c.check == CheckKind::Orphans).unwrap()];
+        assert_eq!(orphans.findings, 14);
+        assert_eq!(orphans.accepted, 10);
+        assert_eq!(orphans.buckets.len(), 3);
+        let top = orphans.buckets.last().unwrap();
+        assert_eq!((top.findings, top.observed), (6, 1.0));
+        assert!((orphans.buckets[0].observed - 0.25).abs() < 1e-9);
+        // 9/10 at or above 0.85 meets 90%; adding the 0.55s doesn't
+        assert_eq!(orphans.suggested_auto_fix, Some(0.85));
+        assert!(orphans.calibration_error > 0.0);
+        
+        // One outcome isn't enough to suggest anything
+        let dead = report.checks.iter().find(|c| c.check == CheckKind::DeadExports).unwrap();
+        assert_eq!(dead.suggested_auto_fix, None);
+        assert_eq!(dead.observed, 0.0);
+    }
+}
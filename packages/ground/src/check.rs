@@ -0,0 +1,760 @@
+//! Named Checks
+//!
+//! Typed entry point for running a single Ground check:
+//!
+//! ```ignore
+//! let report = ground::run_check(CheckKind::Orphans, Scope::Directory("src".into()), &CheckOptions::default())?;
+//! if !report.passed { ... }
+//! ```
+//!
+//! The CLI (`ground check run`), the MCP handlers, and Loom's verifier (via
+//! `ground check run --json`) all go through `run_check`, so a check means
+//! the same thing wherever it's run.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Instant;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::computations::{
+    analyze_barrels, analyze_connectivity_batch, analyze_conventions, analyze_dependencies, analyze_function_dry_with_options, analyze_patterns,
+    find_dead_exports, find_packages, find_project_root, ComputationError, ExternallyUsedExport, FunctionDryOptions,
+    BarrelOptions, PatternConfig, SignatureOptions, analyze_signature_clusters, PublicApiManifest, SymbolGraph, ViolationSeverity,
+};
+use crate::computations::confidence::{orphan_confidence, ConfidenceScore};
+use crate::computations::environment::{analyze_environment_safety, WarningSeverity};
+use crate::computations::framework::{detect_framework, is_implicit_entry};
+use crate::computations::function_dry::is_test_file;
+use crate::computations::pagerank::{rank_modules, ImportGraph};
+use crate::config::GroundConfig;
+use crate::fingerprint::{content_hash, fingerprint, fingerprint_location, Baseline};
+
+#[derive(Error, Debug)]
+pub enum CheckError {
+    #[error("Unknown check: {0}")]
+    UnknownCheck(String),
+    
+    #[error("{kind} needs a {expected} scope")]
+    InvalidScope { kind: &'static str, expected: &'static str },
+    
+    #[error("Computation error: {0}")]
+    Computation(#[from] ComputationError),
+    
+    #[error("Analysis failed: {0}")]
+    Analysis(String),
+}
+
+/// A named check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckKind {
+    /// Functions duplicated across files
+    DuplicateFunctions,
+    /// Exports nothing imports
+    DeadExports,
+    /// Modules nothing imports
+    Orphans,
+    /// Workers/Node.js API misuse reachable from an entry point
+    Environment,
+    /// Design token violations
+    Drift,
+    /// package.json dependencies nothing imports, and imports nothing declares
+    Dependencies,
+    /// Files and directories that break the `.ground.yml` layout conventions
+    Conventions,
+    /// Barrels re-exporting heavy modules most of their consumers don't use
+    BarrelBloat,
+    /// Exported functions in different packages that look like the same API
+    SignatureClusters,
+}
+
+impl CheckKind {
+    pub const ALL: [CheckKind; 9] = [
+        CheckKind::DuplicateFunctions,
+        CheckKind::DeadExports,
+        CheckKind::Orphans,
+        CheckKind::Environment,
+        CheckKind::Drift,
+        CheckKind::Dependencies,
+        CheckKind::Conventions,
+        CheckKind::BarrelBloat,
+        CheckKind::SignatureClusters,
+    ];
+    
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CheckKind::DuplicateFunctions => "duplicate_functions",
+            CheckKind::DeadExports => "dead_exports",
+            CheckKind::Orphans => "orphans",
+            CheckKind::Environment => "environment",
+            CheckKind::Drift => "drift",
+            CheckKind::Dependencies => "dependencies",
+            CheckKind::Conventions => "conventions",
+            CheckKind::BarrelBloat => "barrel_bloat",
+            CheckKind::SignatureClusters => "signature_clusters",
+        }
+    }
+}
+
+impl std::str::FromStr for CheckKind {
+    type Err = CheckError;
+    
+    /// Accepts snake_case or kebab-case, plus the older `duplicates` and
+    /// `dead_code` names Loom labels map to
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.replace('-', "_").as_str() {
+            "duplicate_functions" | "duplicates" => Ok(CheckKind::DuplicateFunctions),
+            "dead_exports" | "dead_code" => Ok(CheckKind::DeadExports),
+            "orphans" => Ok(CheckKind::Orphans),
+            "environment" => Ok(CheckKind::Environment),
+            "drift" => Ok(CheckKind::Drift),
+            "dependencies" | "unused_dependencies" | "deps" => Ok(CheckKind::Dependencies),
+            "conventions" | "layout" => Ok(CheckKind::Conventions),
+            "barrel_bloat" | "barrels" => Ok(CheckKind::BarrelBloat),
+            "signature_clusters" | "signatures" | "near_miss_apis" => Ok(CheckKind::SignatureClusters),
+            _ => Err(CheckError::UnknownCheck(s.to_string())),
+        }
+    }
+}
+
+/// What a check runs over
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Scope {
+    /// Everything under a directory
+    Directory(PathBuf),
+    /// One file, resolved against a directory (a module's exports, an
+    /// environment entry point)
+    File { path: PathBuf, within: PathBuf },
+}
+
+impl Scope {
+    /// Directory the check searches
+    pub fn root(&self) -> &Path {
+        match self {
+            Scope::Directory(dir) => dir,
+            Scope::File { within, .. } => within,
+        }
+    }
+}
+
+/// Called as `(phase, files done, total files)` while a check builds a graph
+pub type CheckProgress = Arc<dyn Fn(&str, usize, usize) + Send + Sync>;
+
+/// Check options (defaults match the CLI and MCP defaults)
+#[derive(Clone)]
+pub struct CheckOptions {
+    /// Similarity threshold for duplicate functions (0.0 - 1.0)
+    pub threshold: f64,
+    /// Include test files
+    pub include_tests: bool,
+    /// Ignore functions shorter than this
+    pub min_function_lines: Option<usize>,
+    /// Findings allowed before the check fails (CI assert mode)
+    pub max_findings: usize,
+    /// Known findings to leave out of the report
+    pub baseline: Option<Baseline>,
+    /// Prebuilt symbol graph to reuse when it covers the scope (orphans, dependencies)
+    pub graph: Option<Arc<SymbolGraph>>,
+    /// Progress while building a graph the caller didn't supply
+    pub progress: Option<CheckProgress>,
+}
+
+impl Default for CheckOptions {
+    fn default() -> Self {
+        Self {
+            threshold: 0.8,
+            include_tests: false,
+            min_function_lines: None,
+            max_findings: 0,
+            baseline: None,
+            graph: None,
+            progress: None,
+        }
+    }
+}
+
+/// How serious a finding is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+/// A single problem found by a check
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Finding {
+    pub file: PathBuf,
+    pub line: Option<usize>,
+    pub severity: Severity,
+    pub message: String,
+    /// Symbol involved (function, export, API)
+    pub symbol: Option<String>,
+    /// Other file involved (the duplicate's twin)
+    pub related: Option<PathBuf>,
+    /// Content-derived identity, stable across file moves
+    #[serde(default)]
+    pub fingerprint: String,
+    /// How sure the check is, with the factors behind it (orphans)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<ConfidenceScore>,
+    /// Where to move the file, or the file to create (conventions)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suggested_path: Option<PathBuf>,
+}
+
+/// Result of running a check
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckReport {
+    pub kind: CheckKind,
+    pub scope: Scope,
+    pub findings: Vec<Finding>,
+    /// Findings left out because the baseline or `ignore.fingerprints` has them
+    #[serde(default)]
+    pub suppressed: usize,
+    /// Dead exports left out because other repos consume them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub externally_used: Vec<ExternallyUsedExport>,
+    pub files_scanned: usize,
+    /// Findings within `max_findings`
+    pub passed: bool,
+    pub duration_ms: u64,
+    pub computed_at: DateTime<Utc>,
+}
+
+/// Run a single named check
+pub fn run_check(kind: CheckKind, scope: Scope, options: &CheckOptions) -> Result<CheckReport, CheckError> {
+    let start = Instant::now();
+    
+    let mut externally_used = Vec::new();
+    let (mut findings, files_scanned) = match kind {
+        CheckKind::DuplicateFunctions => check_duplicate_functions(&scope, options)?,
+        CheckKind::DeadExports => check_dead_exports(&scope, &mut externally_used)?,
+        CheckKind::Orphans => check_orphans(&scope, options)?,
+        CheckKind::Environment => check_environment(&scope)?,
+        CheckKind::Drift => check_drift(&scope)?,
+        CheckKind::Dependencies => check_dependencies(&scope, options)?,
+        CheckKind::Conventions => check_conventions(&scope)?,
+        CheckKind::BarrelBloat => check_barrel_bloat(&scope, options)?,
+        CheckKind::SignatureClusters => check_signature_clusters(&scope, options)?,
+    };
+    
+    for finding in findings.iter_mut().filter(|f| f.fingerprint.is_empty()) {
+        finding.fingerprint = fingerprint_location(kind.as_str(), finding.symbol.as_deref(), &finding.file, finding.line);
+    }
+    
+    let config = GroundConfig::find_in_ancestors(scope.root()).unwrap_or_default();
+    let before = findings.len();
+    findings.retain(|f| {
+        !config.should_ignore_fingerprint(&f.fingerprint)
+            && !options.baseline.as_ref().is_some_and(|b| b.contains(&f.fingerprint))
+    });
+    
+    Ok(CheckReport {
+        kind,
+        passed: findings.len() <= options.max_findings,
+        suppressed: before - findings.len(),
+        externally_used,
+        scope,
+        findings,
+        files_scanned,
+        duration_ms: start.elapsed().as_millis() as u64,
+        computed_at: Utc::now(),
+    })
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Checks
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn check_duplicate_functions(scope: &Scope, options: &CheckOptions) -> Result<(Vec<Finding>, usize), CheckError> {
+    let files = source_files(scope.root(), &["ts", "tsx", "js", "jsx", "svelte"]);
+    let dry_options = FunctionDryOptions {
+        exclude_tests: !options.include_tests,
+        min_function_lines: options.min_function_lines,
+        ..Default::default()
+    };
+    let report = analyze_function_dry_with_options(&files, options.threshold, &dry_options)?;
+    
+    let findings = report.duplicates.iter()
+        .filter(|d| match scope {
+            Scope::File { path, .. } => &d.file_a == path || &d.file_b == path,
+            Scope::Directory(_) => true,
+        })
+        .map(|d| Finding {
+            file: d.file_a.clone(),
+            line: Some(d.function_a.start_line),
+            severity: Severity::Warning,
+            message: format!(
+                "{} is {:.0}% similar to {} in {} ({} clone)",
+                d.function_a.name,
+                d.similarity * 100.0,
+                d.function_b.name,
+                d.file_b.display(),
+                d.clone_type
+            ),
+            symbol: Some(d.function_name.clone()),
+            related: Some(d.file_b.clone()),
+            fingerprint: fingerprint(
+                CheckKind::DuplicateFunctions.as_str(),
+                Some(&d.function_name),
+                &[&content_hash(&d.function_a.source), &content_hash(&d.function_b.source)],
+            ),
+            confidence: None,
+            suggested_path: None,
+        })
+        .collect();
+    
+    Ok((findings, report.files.len()))
+}
+
+fn check_dead_exports(scope: &Scope, externally_used: &mut Vec<ExternallyUsedExport>) -> Result<(Vec<Finding>, usize), CheckError> {
+    let manifest = PublicApiManifest::load(scope.root());
+    
+    match scope {
+        Scope::File { path, within } => {
+            let report = find_dead_exports(path, within)?;
+            let (dead, external) = manifest.partition(report.dead_exports, |d| (&d.name, &d.file, d.line));
+            *externally_used = external;
+            let findings = dead.iter()
+                .map(|d| Finding {
+                    file: d.file.clone(),
+                    line: Some(d.line as usize),
+                    severity: Severity::Warning,
+                    message: format!("'{}' is exported but never imported in {}", d.name, within.display()),
+                    symbol: Some(d.name.clone()),
+                    related: None,
+                    fingerprint: String::new(),
+                    confidence: None,
+                    suggested_path: None,
+                })
+                .collect();
+            Ok((findings, 1))
+        }
+        Scope::Directory(dir) => {
+            let graph = SymbolGraph::build(dir, None).map_err(CheckError::Analysis)?;
+            let report = graph.find_dead_exports();
+            let (dead, external) = manifest.partition(report.dead_exports, |d| (&d.name, &d.file, d.line));
+            *externally_used = external;
+            let findings = dead.iter()
+                .map(|d| Finding {
+                    file: d.file.clone(),
+                    line: Some(d.line as usize),
+                    severity: Severity::Warning,
+                    message: format!("'{}' is exported but never imported", d.name),
+                    symbol: Some(d.name.clone()),
+                    related: None,
+                    fingerprint: String::new(),
+                    confidence: None,
+                    suggested_path: None,
+                })
+                .collect();
+            Ok((findings, graph.files.len()))
+        }
+    }
+}
+
+fn check_orphans(scope: &Scope, options: &CheckOptions) -> Result<(Vec<Finding>, usize), CheckError> {
+    let Scope::Directory(dir) = scope else {
+        return Err(CheckError::InvalidScope { kind: "orphans", expected: "directory" });
+    };
+    
+    let config = GroundConfig::find_in_ancestors(dir).unwrap_or_default();
+    let files: Vec<PathBuf> = source_files(dir, &["ts", "tsx", "js", "jsx"])
+        .into_iter()
+        .filter(|f| !config.should_ignore_path(f))
+        .filter(|f| options.include_tests || !is_test_file(f))
+        .filter(|f| {
+            // Index files, type declarations, and SvelteKit route files
+            // are imported implicitly
+            let name = f.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            !name.ends_with(".d.ts") &&
+            name != "index.ts" &&
+            name != "index.js" &&
+            !name.starts_with('+')
+        })
+        .collect();
+    
+    // One graph for the whole project (importers can live outside `dir`),
+    // instead of walking the project once per file
+    let root = dir.canonicalize().unwrap_or_else(|_| dir.clone());
+    let graph = project_graph(&root, options, "orphans")?;
+    
+    let evidence = analyze_connectivity_batch(&graph, &files);
+    let orphans: Vec<_> = evidence.iter()
+        .zip(&files)
+        .filter(|(e, _)| e.total_connections() == 0 && e.architectural.is_none())
+        .collect();
+    if orphans.is_empty() {
+        return Ok((Vec::new(), files.len()));
+    }
+    
+    // Graph properties behind each orphan's confidence
+    let mut import_graph = ImportGraph::new();
+    for (file, links) in graph.module_links() {
+        import_graph.add_node(file.clone());
+        for target in &links.imports {
+            import_graph.add_edge(&file, target);
+        }
+    }
+    let percentiles: HashMap<PathBuf, f64> = rank_modules(&import_graph).into_iter()
+        .map(|r| (r.path, r.percentile))
+        .collect();
+    let framework = detect_framework(&root);
+    
+    let findings = orphans.into_iter()
+        .map(|(evidence, file)| {
+            let name = file.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            let confidence = orphan_confidence(
+                evidence.incoming_connections,
+                evidence.outgoing_connections,
+                false, // package entry points are architectural connections, never orphans
+                is_test_file(file),
+                name.contains(".config."),
+                evidence.has_architectural_connections(),
+                percentiles.get(&evidence.module_path).copied(),
+                is_implicit_entry(file, &framework.patterns),
+            );
+            Finding {
+                file: file.clone(),
+                line: None,
+                severity: Severity::Warning,
+                message: "Nothing imports this module".to_string(),
+                symbol: None,
+                related: None,
+                fingerprint: String::new(),
+                confidence: Some(confidence),
+                suggested_path: None,
+            }
+        })
+        .collect();
+    
+    Ok((findings, files.len()))
+}
+
+fn check_environment(scope: &Scope) -> Result<(Vec<Finding>, usize), CheckError> {
+    let Scope::File { path, .. } = scope else {
+        return Err(CheckError::InvalidScope { kind: "environment", expected: "file (entry point)" });
+    };
+    
+    let evidence = analyze_environment_safety(path)?;
+    let findings = evidence.warnings.iter()
+        .map(|w| Finding {
+            file: w.import_chain.last().cloned().unwrap_or_else(|| path.clone()),
+            line: None,
+            severity: match w.severity {
+                WarningSeverity::Error => Severity::Error,
+                WarningSeverity::Warning => Severity::Warning,
+                WarningSeverity::Info => Severity::Info,
+            },
+            message: w.message.clone(),
+            symbol: Some(w.api.clone()),
+            related: Some(path.clone()),
+            fingerprint: String::new(),
+            confidence: None,
+            suggested_path: None,
+        })
+        .collect();
+    
+    Ok((findings, evidence.reachable_modules.len()))
+}
+
+fn check_dependencies(scope: &Scope, options: &CheckOptions) -> Result<(Vec<Finding>, usize), CheckError> {
+    let Scope::Directory(dir) = scope else {
+        return Err(CheckError::InvalidScope { kind: "dependencies", expected: "directory" });
+    };
+    
+    let graph = project_graph(dir, options, "dependencies")?;
+    let mut packages = find_packages(dir);
+    if packages.is_empty() && graph.root_dir.join("package.json").is_file() {
+        packages.push(graph.root_dir.clone());
+    }
+    
+    let mut findings = Vec::new();
+    let mut files_scanned = 0;
+    for package in &packages {
+        let evidence = analyze_dependencies(package, Some(&graph))?;
+        let manifest = evidence.package_dir.join("package.json");
+        files_scanned += evidence.files_analyzed;
+        
+        findings.extend(evidence.unused.iter().map(|d| Finding {
+            file: manifest.clone(),
+            line: d.line,
+            severity: Severity::Warning,
+            message: format!("'{}' is in {} but nothing imports, runs, or configures it", d.name, d.kind.as_str()),
+            symbol: Some(d.name.clone()),
+            related: None,
+            fingerprint: String::new(),
+            confidence: None,
+            suggested_path: None,
+        }));
+        findings.extend(evidence.missing.iter().map(|m| {
+            let file = m.imported_by[0].clone();
+            Finding {
+                line: import_line(&file, &m.name),
+                file,
+                severity: Severity::Error,
+                message: format!(
+                    "'{}' is imported by {} file(s) but not declared in {}",
+                    m.name,
+                    m.imported_by.len(),
+                    manifest.display()
+                ),
+                symbol: Some(m.name.clone()),
+                related: Some(manifest.clone()),
+                fingerprint: String::new(),
+                confidence: None,
+                suggested_path: None,
+            }
+        }));
+    }
+    
+    Ok((findings, files_scanned))
+}
+
+fn check_conventions(scope: &Scope) -> Result<(Vec<Finding>, usize), CheckError> {
+    let Scope::Directory(dir) = scope else {
+        return Err(CheckError::InvalidScope { kind: "conventions", expected: "directory" });
+    };
+    
+    let dir = dir.canonicalize().unwrap_or_else(|_| dir.clone());
+    let Some((config, root)) = GroundConfig::find_with_root(&dir) else {
+        return Ok((Vec::new(), 0));
+    };
+    
+    let report = analyze_conventions(&root, &dir, &config.conventions)?;
+    let findings = report.violations.into_iter()
+        .map(|v| Finding {
+            file: v.path,
+            line: None,
+            severity: Severity::Warning,
+            message: v.message,
+            symbol: Some(v.rule),
+            related: None,
+            fingerprint: String::new(),
+            confidence: None,
+            suggested_path: v.suggested_path,
+        })
+        .collect();
+    
+    Ok((findings, report.files_checked))
+}
+
+fn check_barrel_bloat(scope: &Scope, options: &CheckOptions) -> Result<(Vec<Finding>, usize), CheckError> {
+    let Scope::Directory(dir) = scope else {
+        return Err(CheckError::InvalidScope { kind: "barrel_bloat", expected: "directory" });
+    };
+    
+    // Consumers live all over the project, not just under `dir`
+    let root = dir.canonicalize().unwrap_or_else(|_| dir.clone());
+    let graph = project_graph(&root, options, "barrel_bloat")?;
+    let report = analyze_barrels(&graph, &root, &BarrelOptions::default());
+    
+    let mut findings = Vec::new();
+    for barrel in &report.barrels {
+        for module in barrel.bloated() {
+            let weight = if module.exclusive_packages.is_empty() {
+                format!("{} KB of source", module.source_bytes / 1024)
+            } else {
+                module.exclusive_packages.join(", ")
+            };
+            findings.push(Finding {
+                file: barrel.path.clone(),
+                line: Some(module.line),
+                severity: Severity::Warning,
+                message: format!(
+                    "Re-exports '{}', which pulls in {}, but only {} of {} consumers use it ({:.0}% of the barrel's exports are used). {}",
+                    module.specifier,
+                    weight,
+                    module.consumers,
+                    barrel.consumers.len(),
+                    barrel.utilization * 100.0,
+                    module.suggestion.as_deref().unwrap_or_default()
+                ),
+                symbol: Some(module.specifier.clone()),
+                related: Some(module.path.clone()),
+                fingerprint: String::new(),
+                confidence: None,
+                suggested_path: None,
+            });
+        }
+    }
+    
+    Ok((findings, report.files_analyzed))
+}
+
+fn check_signature_clusters(scope: &Scope, options: &CheckOptions) -> Result<(Vec<Finding>, usize), CheckError> {
+    let files = source_files(scope.root(), &["ts", "tsx", "js", "jsx"]);
+    let signature_options = SignatureOptions { include_tests: options.include_tests, ..Default::default() };
+    let report = analyze_signature_clusters(&files, &signature_options);
+    
+    let mut findings = Vec::new();
+    for cluster in &report.clusters {
+        let canonical = cluster.canonical();
+        for variant in cluster.variants() {
+            if let Scope::File { path, .. } = scope {
+                if &variant.file != path {
+                    continue;
+                }
+            }
+            findings.push(Finding {
+                file: variant.file.clone(),
+                line: Some(variant.line),
+                severity: Severity::Info,
+                message: format!(
+                    "{} in {} looks like {} in {}; {} packages have their own version ({})",
+                    variant.signature(),
+                    variant.package,
+                    canonical.signature(),
+                    canonical.package,
+                    cluster.packages.len(),
+                    cluster.names.join(", ")
+                ),
+                symbol: Some(variant.name.clone()),
+                related: Some(canonical.file.clone()),
+                fingerprint: fingerprint(
+                    CheckKind::SignatureClusters.as_str(),
+                    Some(&variant.name),
+                    &[&variant.body_hash, &canonical.name],
+                ),
+                confidence: None,
+                suggested_path: None,
+            });
+        }
+    }
+    
+    Ok((findings, report.files_analyzed))
+}
+
+fn check_drift(scope: &Scope) -> Result<(Vec<Finding>, usize), CheckError> {
+    let report = analyze_patterns(scope.root(), &PatternConfig::default())
+        .map_err(|e| CheckError::Analysis(e.to_string()))?;
+    
+    let findings = report.file_evidence.iter()
+        .filter(|e| match scope {
+            Scope::File { path, .. } => &e.file == path,
+            Scope::Directory(_) => true,
+        })
+        .flat_map(|e| e.violations.iter().map(move |v| Finding {
+            file: e.file.clone(),
+            line: Some(v.line),
+            severity: match v.severity {
+                ViolationSeverity::Error => Severity::Error,
+                ViolationSeverity::Warning => Severity::Warning,
+                ViolationSeverity::Info => Severity::Info,
+            },
+            message: v.message.clone(),
+            symbol: Some(v.property.clone()),
+            related: None,
+            fingerprint: String::new(),
+            confidence: None,
+            suggested_path: None,
+        }))
+        .collect();
+    
+    Ok((findings, report.files_analyzed))
+}
+
+/// The caller's graph if it covers `dir`, otherwise one built for the
+/// project `dir` belongs to
+fn project_graph(dir: &Path, options: &CheckOptions, label: &'static str) -> Result<Arc<SymbolGraph>, CheckError> {
+    let root = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+    match &options.graph {
+        Some(graph) if graph.root_dir.canonicalize().is_ok_and(|r| root.starts_with(r)) => Ok(graph.clone()),
+        _ => {
+            let project_root = find_project_root(&root.join("_")).unwrap_or_else(|_| root.clone());
+            let progress = options.progress.as_ref().map(|report| move |done: usize, total: usize| report(label, done, total));
+            let progress = progress.as_ref().map(|p| p as &dyn Fn(usize, usize));
+            Ok(Arc::new(SymbolGraph::build(&project_root, progress).map_err(CheckError::Analysis)?))
+        }
+    }
+}
+
+/// First line of `file` importing `package` (or a subpath of it)
+fn import_line(file: &Path, package: &str) -> Option<usize> {
+    let content = std::fs::read_to_string(file).ok()?;
+    content.lines()
+        .position(|l| ['"', '\''].iter().any(|q| l.contains(&format!("{q}{package}{q}")) || l.contains(&format!("{q}{package}/"))))
+        .map(|i| i + 1)
+}
+
+/// Source files under a directory, skipping hidden, ignored, and generated code
+fn source_files(dir: &Path, extensions: &[&str]) -> Vec<PathBuf> {
+    crate::computations::walk::source_files(dir, extensions)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+    
+    #[test]
+    fn test_run_check_orphans() {
+        let dir = tempdir().unwrap();
+        let src = dir.path().join("src");
+        std::fs::create_dir_all(&src).unwrap();
+        std::fs::write(src.join("index.ts"), "import { used } from './used';\nused();\n").unwrap();
+        std::fs::write(src.join("used.ts"), "export function used() {}\n").unwrap();
+        std::fs::write(src.join("stray.ts"), "export function stray() {}\n").unwrap();
+        
+        let report = run_check(CheckKind::Orphans, Scope::Directory(src.clone()), &CheckOptions::default()).unwrap();
+        assert!(!report.passed);
+        assert_eq!(report.findings.len(), 1);
+        assert!(report.findings[0].file.ends_with("stray.ts"));
+        
+        let lenient = CheckOptions { max_findings: 1, ..Default::default() };
+        assert!(run_check(CheckKind::Orphans, Scope::Directory(src), &lenient).unwrap().passed);
+        
+        assert_eq!("dead-code".parse::<CheckKind>().unwrap(), CheckKind::DeadExports);
+        assert_eq!("unused-dependencies".parse::<CheckKind>().unwrap(), CheckKind::Dependencies);
+        assert_eq!("barrel-bloat".parse::<CheckKind>().unwrap(), CheckKind::BarrelBloat);
+        assert_eq!("near-miss-apis".parse::<CheckKind>().unwrap(), CheckKind::SignatureClusters);
+        assert!("nonsense".parse::<CheckKind>().is_err());
+    }
+    
+    #[test]
+    fn test_run_check_dependencies() {
+        let dir = tempdir().unwrap();
+        let pkg = dir.path().join("packages/app");
+        std::fs::create_dir_all(pkg.join("src")).unwrap();
+        std::fs::write(pkg.join("package.json"), "{\n  \"dependencies\": {\n    \"lodash\": \"^4.0.0\"\n  }\n}\n").unwrap();
+        std::fs::write(pkg.join("src/index.ts"), "// entry\nimport { z } from 'zod';\nexport const s = z.string();\n").unwrap();
+        
+        let report = run_check(CheckKind::Dependencies, Scope::Directory(dir.path().join("packages")), &CheckOptions::default()).unwrap();
+        assert_eq!(report.findings.len(), 2);
+        let unused = report.findings.iter().find(|f| f.symbol.as_deref() == Some("lodash")).unwrap();
+        assert_eq!((unused.line, unused.severity), (Some(3), Severity::Warning));
+        let missing = report.findings.iter().find(|f| f.symbol.as_deref() == Some("zod")).unwrap();
+        assert_eq!((missing.line, missing.severity), (Some(2), Severity::Error));
+        assert!(missing.file.ends_with("src/index.ts"));
+    }
+    
+    #[test]
+    fn test_baseline_follows_moved_file() {
+        let dir = tempdir().unwrap();
+        let src = dir.path().join("src");
+        std::fs::create_dir_all(src.join("lib")).unwrap();
+        std::fs::write(src.join("stray.ts"), "export function stray() {}\n").unwrap();
+        
+        let report = run_check(CheckKind::Orphans, Scope::Directory(src.clone()), &CheckOptions::default()).unwrap();
+        let baseline = Baseline::new(report.findings.iter().map(|f| f.fingerprint.clone()));
+        
+        // Moving the file doesn't make the finding new again
+        std::fs::rename(src.join("stray.ts"), src.join("lib/renamed.ts")).unwrap();
+        let options = CheckOptions { baseline: Some(baseline), ..Default::default() };
+        let report = run_check(CheckKind::Orphans, Scope::Directory(src.clone()), &options).unwrap();
+        assert!(report.passed);
+        assert_eq!(report.suppressed, 1);
+        
+        // Changing it does
+        std::fs::write(src.join("lib/renamed.ts"), "export function stray() { return 1; }\n").unwrap();
+        let report = run_check(CheckKind::Orphans, Scope::Directory(src), &options).unwrap();
+        assert_eq!((report.findings.len(), report.suppressed), (1, 0));
+    }
+}
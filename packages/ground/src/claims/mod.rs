@@ -11,7 +11,7 @@ use thiserror::Error;
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 
-use crate::computations::{SimilarityEvidence, UsageEvidence, ConnectivityEvidence};
+use crate::computations::{SimilarityEvidence, UsageEvidence, ConnectivityEvidence, DependencyEvidence, DependencyKind};
 
 /// Reasons a claim can be rejected
 #[derive(Error, Debug, Clone, Serialize, Deserialize)]
@@ -223,6 +223,74 @@ impl ConnectivityClaim {
     }
 }
 
+// =============================================================================
+// Unused Dependency Claim
+// =============================================================================
+
+/// A validated claim that a package.json dependency is unused
+///
+/// CANNOT be constructed without DependencyEvidence listing it as unused.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnusedDependencyClaim {
+    /// Unique claim identifier
+    pub id: Uuid,
+    
+    /// Directory containing the package.json
+    pub package_dir: PathBuf,
+    
+    /// The unused dependency
+    pub dependency: String,
+    
+    /// Section it's declared in (from evidence)
+    pub kind: DependencyKind,
+    
+    /// Human-provided reason for the claim
+    pub reason: String,
+    
+    /// ID of the evidence supporting this claim
+    pub evidence_id: Uuid,
+    
+    /// When this claim was made
+    pub claimed_at: DateTime<Utc>,
+}
+
+impl UnusedDependencyClaim {
+    /// Create an unused dependency claim from computed evidence
+    ///
+    /// Rejected if the package doesn't declare it, or declares and uses it
+    pub fn from_evidence(
+        evidence: DependencyEvidence,
+        dependency: &str,
+        reason: String,
+    ) -> Result<Self, ClaimRejected> {
+        let Some(declared) = evidence.declared.iter().find(|d| d.name == dependency) else {
+            return Err(ClaimRejected::EvidenceContradicts {
+                reason: format!("'{}' is not declared in {}/package.json", dependency, evidence.package_dir.display()),
+            });
+        };
+        
+        if !evidence.is_unused(dependency) {
+            return Err(ClaimRejected::EvidenceContradicts {
+                reason: format!(
+                    "'{}' is used by {} (imported, run by a script, configured, or a peer/@types package)",
+                    dependency,
+                    evidence.package_name.as_deref().unwrap_or("the package")
+                ),
+            });
+        }
+        
+        Ok(Self {
+            id: Uuid::new_v4(),
+            package_dir: evidence.package_dir,
+            dependency: dependency.to_string(),
+            kind: declared.kind,
+            reason,
+            evidence_id: evidence.id,
+            claimed_at: Utc::now(),
+        })
+    }
+}
+
 // =============================================================================
 // Claim Report (for output)
 // =============================================================================
@@ -292,6 +360,7 @@ mod tests {
             ast_similarity: Some(similarity),
             hash_a: "hash_a".to_string(),
             hash_b: "hash_b".to_string(),
+            explanation: Default::default(),
             computed_at: Utc::now(),
         }
     }
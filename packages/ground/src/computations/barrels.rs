@@ -0,0 +1,447 @@
+//! Barrel Bloat
+//!
+//! A barrel (an `index.ts` re-exporting a directory) makes every consumer
+//! pay for whatever its re-exported modules pull in, unless the bundler can
+//! prove those modules are side-effect free. Worker bundles mostly can't,
+//! so one heavy module behind a popular barrel ends up in every Worker that
+//! imports anything from it.
+//!
+//! For each barrel this measures:
+//!
+//! - **Utilization**: how much of its exported surface consumers import
+//! - **Weight** of each re-exported module: the packages and local source it
+//!   transitively pulls in
+//! - **Share** of each re-exported module: how many consumers use it
+//!
+//! A module that's heavy (brings in packages no sibling does, or a lot of
+//! source) but used by a minority of consumers is flagged, with a
+//! suggestion to split it out of the barrel.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::fs;
+use serde::{Serialize, Deserialize};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::dependencies::package_of;
+use super::graph::SymbolGraph;
+use super::imports::extract_exports;
+
+/// Thresholds for barrel analysis
+#[derive(Debug, Clone)]
+pub struct BarrelOptions {
+    /// Re-exports a file needs to count as a barrel
+    pub min_reexports: usize,
+    /// Consumers a barrel needs before "most consumers" means anything
+    pub min_consumers: usize,
+    /// A heavy module used by less than this share of consumers is bloat
+    pub max_share: f64,
+    /// Local source (bytes, transitively) that makes a module heavy
+    pub heavy_bytes: u64,
+}
+
+impl Default for BarrelOptions {
+    fn default() -> Self {
+        Self {
+            min_reexports: 3,
+            min_consumers: 2,
+            max_share: 0.5,
+            heavy_bytes: 32 * 1024,
+        }
+    }
+}
+
+/// A module a barrel re-exports
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BarrelModule {
+    pub path: PathBuf,
+    /// Specifier the barrel re-exports it by
+    pub specifier: String,
+    /// First line of the barrel re-exporting it
+    pub line: usize,
+    /// Names the barrel exposes from it
+    pub symbols: Vec<String>,
+    /// Barrel consumers that import at least one of those names
+    pub consumers: usize,
+    /// `consumers` over all of the barrel's consumers
+    pub share: f64,
+    /// Packages it pulls in, directly or through local imports
+    pub packages: Vec<String>,
+    /// Packages no other module of the barrel pulls in
+    pub exclusive_packages: Vec<String>,
+    /// Local source it pulls in, itself included
+    pub source_bytes: u64,
+    /// Heavy, and used by a minority of consumers
+    pub bloat: bool,
+    /// How to split it out, when it's bloat
+    pub suggestion: Option<String>,
+}
+
+/// A barrel file and how its surface is consumed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BarrelEvidence {
+    pub path: PathBuf,
+    /// Names the barrel exports, its own and re-exported
+    pub exported: usize,
+    /// Of those, names some consumer imports
+    pub consumed: usize,
+    /// `consumed` over `exported`
+    pub utilization: f64,
+    /// Files importing the barrel
+    pub consumers: Vec<PathBuf>,
+    pub modules: Vec<BarrelModule>,
+}
+
+impl BarrelEvidence {
+    /// Re-exported modules flagged as bloat
+    pub fn bloated(&self) -> impl Iterator<Item = &BarrelModule> {
+        self.modules.iter().filter(|m| m.bloat)
+    }
+}
+
+/// Barrels under a directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BarrelBloatReport {
+    pub id: Uuid,
+    pub root: PathBuf,
+    /// Every barrel found, most bloated first
+    pub barrels: Vec<BarrelEvidence>,
+    pub files_analyzed: usize,
+    pub computed_at: DateTime<Utc>,
+}
+
+/// What a consumer takes from a barrel
+enum Usage {
+    /// Namespace, side-effect, or dynamic import: everything
+    All,
+    Names(HashSet<String>),
+}
+
+/// Find the barrels under `within` and measure how their surface is used
+///
+/// `graph` should cover the barrels' consumers (build it at the project
+/// root), otherwise consumers outside it are missed.
+pub fn analyze_barrels(graph: &SymbolGraph, within: &Path, options: &BarrelOptions) -> BarrelBloatReport {
+    let files: HashSet<PathBuf> = graph.files.iter().cloned().collect();
+    let links = graph.module_links();
+    let mut weights: HashMap<PathBuf, (BTreeSet<String>, u64)> = HashMap::new();
+    
+    let mut barrels = Vec::new();
+    let mut files_analyzed = 0;
+    for file in graph.files.iter().filter(|f| f.starts_with(within)) {
+        files_analyzed += 1;
+        let Some(surface) = barrel_surface(graph, file, &files) else { continue };
+        if surface.iter().filter(|e| e.module.is_some()).count() < options.min_reexports {
+            continue;
+        }
+        
+        let usages = consumers(graph, file, &files);
+        let exported: BTreeSet<&str> = surface.iter().map(|e| e.name.as_str()).collect();
+        let consumed: BTreeSet<&str> = exported.iter()
+            .copied()
+            .filter(|name| usages.values().any(|u| uses(u, name)))
+            .collect();
+        
+        // Group the re-exported names by module, in barrel order
+        let mut modules: Vec<BarrelModule> = Vec::new();
+        for entry in &surface {
+            let Some(path) = &entry.module else { continue };
+            match modules.iter_mut().find(|m| &m.path == path) {
+                Some(module) => module.symbols.push(entry.name.clone()),
+                None => modules.push(BarrelModule {
+                    path: path.clone(),
+                    specifier: entry.specifier.clone(),
+                    line: entry.line,
+                    symbols: vec![entry.name.clone()],
+                    consumers: 0,
+                    share: 0.0,
+                    packages: Vec::new(),
+                    exclusive_packages: Vec::new(),
+                    source_bytes: 0,
+                    bloat: false,
+                    suggestion: None,
+                }),
+            }
+        }
+        
+        for module in &mut modules {
+            let (packages, bytes) = weights.entry(module.path.clone())
+                .or_insert_with(|| module_weight(graph, &links, &module.path))
+                .clone();
+            module.packages = packages.into_iter().collect();
+            module.source_bytes = bytes;
+            module.consumers = usages.values()
+                .filter(|u| module.symbols.iter().any(|s| uses(u, s)))
+                .count();
+            module.share = if usages.is_empty() { 0.0 } else { module.consumers as f64 / usages.len() as f64 };
+        }
+        
+        for i in 0..modules.len() {
+            let others: HashSet<&String> = modules.iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .flat_map(|(_, m)| &m.packages)
+                .collect();
+            let exclusive: Vec<String> = modules[i].packages.iter()
+                .filter(|p| !others.contains(p))
+                .cloned()
+                .collect();
+            
+            let module = &mut modules[i];
+            let heavy = !exclusive.is_empty() || module.source_bytes >= options.heavy_bytes;
+            module.exclusive_packages = exclusive;
+            module.bloat = heavy && usages.len() >= options.min_consumers && module.share < options.max_share;
+            if module.bloat {
+                module.suggestion = Some(format!(
+                    "Split {} out of the barrel: the {} consumer(s) that use it can import '{}' directly (or a dedicated entry point), and the other {} stop bundling it",
+                    module.symbols.join(", "),
+                    module.consumers,
+                    module.specifier,
+                    usages.len() - module.consumers
+                ));
+            }
+        }
+        
+        let mut consumers: Vec<PathBuf> = usages.into_keys().collect();
+        consumers.sort();
+        barrels.push(BarrelEvidence {
+            path: file.clone(),
+            exported: exported.len(),
+            consumed: consumed.len(),
+            utilization: if exported.is_empty() { 1.0 } else { consumed.len() as f64 / exported.len() as f64 },
+            consumers,
+            modules,
+        });
+    }
+    
+    barrels.sort_by(|a, b| {
+        b.bloated().count().cmp(&a.bloated().count())
+            .then(a.utilization.total_cmp(&b.utilization))
+            .then(a.path.cmp(&b.path))
+    });
+    
+    BarrelBloatReport {
+        id: Uuid::new_v4(),
+        root: within.to_path_buf(),
+        barrels,
+        files_analyzed,
+        computed_at: Utc::now(),
+    }
+}
+
+/// A name a barrel exports
+struct SurfaceEntry {
+    name: String,
+    /// Re-exported module, resolved in the graph (None for the barrel's own
+    /// declarations and re-exports from packages)
+    module: Option<PathBuf>,
+    specifier: String,
+    line: usize,
+}
+
+/// Everything `file` exports, or None if it re-exports nothing
+fn barrel_surface(graph: &SymbolGraph, file: &Path, files: &HashSet<PathBuf>) -> Option<Vec<SurfaceEntry>> {
+    let content = fs::read_to_string(file).ok()?;
+    let stars = star_reexports(&content);
+    let has_named = graph.exports.get(file).is_some_and(|e| e.iter().any(|s| s.is_reexport));
+    if stars.is_empty() && !has_named {
+        return None;
+    }
+    
+    let mut surface = Vec::new();
+    for export in extract_exports(file).ok()? {
+        let specifier = export.source.clone().unwrap_or_default();
+        surface.push(SurfaceEntry {
+            name: export.alias.unwrap_or(export.name),
+            module: export.source.as_deref().and_then(|s| graph.resolve_module(s, file, files)),
+            specifier,
+            line: export.line,
+        });
+    }
+    for (namespace, specifier, line) in stars {
+        let Some(target) = graph.resolve_module(&specifier, file, files) else { continue };
+        let names: Vec<String> = match namespace {
+            Some(namespace) => vec![namespace],
+            None => graph.exports.get(&target)
+                .map(|e| e.iter().map(|s| s.name.clone()).collect())
+                .unwrap_or_default(),
+        };
+        surface.extend(names.into_iter().map(|name| SurfaceEntry {
+            name,
+            module: Some(target.clone()),
+            specifier: specifier.clone(),
+            line,
+        }));
+    }
+    Some(surface)
+}
+
+/// `export * from '...'` and `export * as ns from '...'`, which the export
+/// extractor doesn't name: (namespace, specifier, line)
+pub(super) fn star_reexports(content: &str) -> Vec<(Option<String>, String, usize)> {
+    content.lines()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            let rest = line.trim().strip_prefix("export *")?;
+            let (head, specifier) = rest.split_once(" from ")?;
+            let namespace = head.trim().strip_prefix("as ").map(|n| n.trim().to_string());
+            let specifier = specifier.trim().trim_end_matches(';').trim_matches(|c| c == '\'' || c == '"');
+            Some((namespace, specifier.to_string(), i + 1))
+        })
+        .collect()
+}
+
+/// Files importing `barrel`, and what each takes from it
+fn consumers(graph: &SymbolGraph, barrel: &Path, files: &HashSet<PathBuf>) -> HashMap<PathBuf, Usage> {
+    let package = package_entry_name(barrel);
+    let resolves = |spec: &str, importer: &Path| {
+        package.as_deref() == Some(spec) || graph.resolve_module(spec, importer, files).as_deref() == Some(barrel)
+    };
+    
+    let mut usages = HashMap::new();
+    for (importer, specifiers) in &graph.module_imports {
+        if importer == barrel || !specifiers.iter().any(|s| resolves(s, importer)) {
+            continue;
+        }
+        
+        let names: HashSet<String> = graph.imports.get(importer)
+            .into_iter()
+            .flatten()
+            .filter(|i| resolves(&i.from_module, importer))
+            .map(|i| i.name.clone())
+            .collect();
+        let usage = if names.is_empty() { Usage::All } else { Usage::Names(names) };
+        usages.insert(importer.clone(), usage);
+    }
+    usages
+}
+
+fn uses(usage: &Usage, name: &str) -> bool {
+    match usage {
+        Usage::All => true,
+        Usage::Names(names) => names.contains(name),
+    }
+}
+
+/// The package name consumers import `barrel` by, when it's its package's
+/// entry point (so `@create-something/components` counts as importing
+/// `packages/components/src/index.ts`)
+fn package_entry_name(barrel: &Path) -> Option<String> {
+    let package_dir = barrel.ancestors().skip(1).find(|d| d.join("package.json").is_file())?;
+    let manifest: serde_json::Value = serde_json::from_str(&fs::read_to_string(package_dir.join("package.json")).ok()?).ok()?;
+    let name = manifest.get("name")?.as_str()?;
+    
+    let rel = barrel.strip_prefix(package_dir).ok()?.with_extension("");
+    let mut entries: Vec<String> = vec!["index".into(), "src/index".into(), "src/lib/index".into()];
+    let root_export = manifest.get("exports").map(|e| e.get(".").unwrap_or(e));
+    for value in [manifest.get("main"), manifest.get("module"), manifest.get("svelte"), root_export].into_iter().flatten() {
+        let targets = match value {
+            serde_json::Value::String(s) => vec![s.as_str()],
+            serde_json::Value::Object(conditions) => conditions.values().filter_map(|v| v.as_str()).collect(),
+            _ => Vec::new(),
+        };
+        entries.extend(targets.into_iter().map(|t| {
+            let t = t.trim_start_matches("./");
+            Path::new(t).with_extension("").to_string_lossy().into_owned()
+        }));
+    }
+    
+    entries.iter().any(|e| Path::new(e) == rel).then(|| name.to_string())
+}
+
+/// Packages `module` pulls in and the local source it reaches, itself
+/// included
+fn module_weight(
+    graph: &SymbolGraph,
+    links: &HashMap<PathBuf, super::graph::ModuleLinks>,
+    module: &Path,
+) -> (BTreeSet<String>, u64) {
+    let mut packages = BTreeSet::new();
+    let mut bytes = 0;
+    let mut seen = HashSet::new();
+    let mut stack = vec![module.to_path_buf()];
+    while let Some(file) = stack.pop() {
+        if !seen.insert(file.clone()) {
+            continue;
+        }
+        bytes += fs::metadata(&file).map(|m| m.len()).unwrap_or(0);
+        for spec in graph.module_imports.get(&file).into_iter().flatten() {
+            if let Some(package) = package_of(spec) {
+                packages.insert(package.to_string());
+            }
+        }
+        if let Some(links) = links.get(&file) {
+            stack.extend(links.imports.iter().cloned());
+        }
+    }
+    (packages, bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+    
+    #[test]
+    fn test_barrel_bloat() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        for (file, content) in [
+            ("lib/index.ts", "export { formatDate } from './dates';\nexport { slugify } from './strings';\nexport * from './pdf';\nexport { default as Chart } from './chart';\n"),
+            ("lib/dates.ts", "export function formatDate(d: Date) { return d.toISOString(); }\n"),
+            ("lib/strings.ts", "export function slugify(s: string) { return s.toLowerCase(); }\n"),
+            ("lib/pdf.ts", "import { PDFDocument } from 'pdf-lib';\nexport function renderPdf() { return PDFDocument.create(); }\nexport const PAGE = 'a4';\n"),
+            ("lib/chart.ts", "import * as d3 from 'd3';\nexport default function chart() { return d3; }\n"),
+            ("workers/a.ts", "import { formatDate, slugify } from '../lib';\n"),
+            ("workers/b.ts", "import { formatDate } from '../lib/index';\n"),
+            ("workers/c.ts", "import { slugify } from '../lib';\n"),
+            ("workers/d.ts", "import { renderPdf, Chart } from '../lib';\n"),
+        ] {
+            let path = root.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        
+        let graph = SymbolGraph::build(&root, None).unwrap();
+        let report = analyze_barrels(&graph, &root, &BarrelOptions::default());
+        assert_eq!(report.barrels.len(), 1);
+        
+        let barrel = &report.barrels[0];
+        assert!(barrel.path.ends_with("lib/index.ts"));
+        assert_eq!(barrel.consumers.len(), 4);
+        assert_eq!(barrel.exported, 5);
+        assert_eq!(barrel.consumed, 4); // PAGE is never imported
+        
+        let module = |name: &str| barrel.modules.iter().find(|m| m.path.ends_with(name)).unwrap();
+        let pdf = module("lib/pdf.ts");
+        assert_eq!(pdf.symbols, vec!["renderPdf", "PAGE"]);
+        assert_eq!(pdf.exclusive_packages, vec!["pdf-lib"]);
+        assert_eq!(pdf.consumers, 1);
+        assert!(pdf.bloat);
+        assert!(pdf.suggestion.as_ref().unwrap().contains("'./pdf'"));
+        
+        // Aliased default re-export, matched by the name consumers import
+        let chart = module("lib/chart.ts");
+        assert_eq!(chart.symbols, vec!["Chart"]);
+        assert!(chart.bloat);
+        
+        // Light modules aren't bloat, however few consumers use them
+        assert!(!module("lib/dates.ts").bloat);
+        assert!(!module("lib/strings.ts").bloat);
+        assert_eq!(barrel.bloated().count(), 2);
+        
+        // Too few consumers to call anything a minority
+        let strict = BarrelOptions { min_consumers: 5, ..Default::default() };
+        assert_eq!(analyze_barrels(&graph, &root, &strict).barrels[0].bloated().count(), 0);
+    }
+    
+    #[test]
+    fn test_star_reexports() {
+        let stars = star_reexports("export * from './a';\nexport * as b from \"./b\"\nexport { c } from './c';\n");
+        assert_eq!(stars, vec![
+            (None, "./a".to_string(), 1),
+            (Some("b".to_string()), "./b".to_string(), 2),
+        ]);
+    }
+}
@@ -11,6 +11,10 @@
 //!
 //! A Worker with no import connections but valid deployment configuration
 //! IS connected to the whole - through deployment topology, not code imports.
+//!
+//! Svelte stores and context keys connect modules too (see `svelte_state`):
+//! a store module components subscribe to serves the whole even when the
+//! import graph can't see how the store reached them.
 
 use std::path::{Path, PathBuf};
 use std::fs;
@@ -20,6 +24,10 @@ use uuid::Uuid;
 use chrono::{DateTime, Utc};
 
 use super::ComputationError;
+use super::generated::{is_generated_dir, is_generated_file};
+use super::graph::SymbolGraph;
+use super::svelte_state::{shares_state, StateConnection, StateIndex};
+use super::walk::source_files;
 
 /// Evidence of computed connectivity for a module
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,6 +56,10 @@ pub struct ConnectivityEvidence {
     /// Architectural connections (non-import based)
     pub architectural: Option<ArchitecturalConnections>,
     
+    /// Store subscriptions and context keys shared with other files
+    #[serde(default)]
+    pub state: Vec<StateConnection>,
+    
     /// When this computation was performed
     pub computed_at: DateTime<Utc>,
 }
@@ -93,7 +105,7 @@ pub struct ServiceBinding {
 impl ConnectivityEvidence {
     /// Check if this module "serves the whole" (has minimum connections)
     pub fn serves_whole(&self, min_connections: u32) -> bool {
-        let code_connections = self.incoming_connections + self.outgoing_connections;
+        let code_connections = self.incoming_connections + self.outgoing_connections + self.state.len() as u32;
         let arch_connections = self.architectural.as_ref()
             .map(|a| a.total_connections)
             .unwrap_or(0);
@@ -101,9 +113,9 @@ impl ConnectivityEvidence {
         code_connections >= min_connections || arch_connections >= min_connections
     }
     
-    /// Total connections (code + architectural)
+    /// Total connections (code + state + architectural)
     pub fn total_connections(&self) -> u32 {
-        let code = self.incoming_connections + self.outgoing_connections;
+        let code = self.incoming_connections + self.outgoing_connections + self.state.len() as u32;
         let arch = self.architectural.as_ref()
             .map(|a| a.total_connections)
             .unwrap_or(0);
@@ -136,14 +148,21 @@ pub fn analyze_connectivity(module_path: &Path) -> Result<ConnectivityEvidence,
     // Check for architectural connections (Workers, serverless)
     let architectural = detect_architectural_connections(&module_path);
     
+    // Stores and context keys shared with the rest of the project
+    let state = if shares_state(&module_path) {
+        StateIndex::build(&source_files(&project_root, &["svelte", "ts", "js"])).connections(&module_path)
+    } else {
+        Vec::new()
+    };
+    
     let incoming_connections = imported_by.len() as u32;
     let outgoing_connections = imports.len() as u32;
     
-    // Connected if has code connections OR architectural connections
+    // Connected if has code, state, OR architectural connections
     let arch_connections = architectural.as_ref()
         .map(|a| a.total_connections)
         .unwrap_or(0);
-    let is_connected = incoming_connections > 0 || outgoing_connections > 0 || arch_connections > 0;
+    let is_connected = incoming_connections > 0 || outgoing_connections > 0 || !state.is_empty() || arch_connections > 0;
     
     Ok(ConnectivityEvidence {
         id: Uuid::new_v4(),
@@ -154,10 +173,59 @@ pub fn analyze_connectivity(module_path: &Path) -> Result<ConnectivityEvidence,
         imported_by,
         imports,
         architectural,
+        state,
         computed_at: Utc::now(),
     })
 }
 
+/// Analyze connectivity of many modules from one symbol graph
+///
+/// Same evidence as `analyze_connectivity`, but importers come from a graph
+/// built once for the project rather than a walk of the project per module,
+/// which is what makes scanning a whole package for orphans take seconds.
+/// Modules outside the graph come back unconnected apart from architecture.
+pub fn analyze_connectivity_batch(graph: &SymbolGraph, modules: &[PathBuf]) -> Vec<ConnectivityEvidence> {
+    // Keyed canonically, so relative and absolute paths meet
+    let links: std::collections::HashMap<PathBuf, _> = graph.module_links().into_iter()
+        .map(|(file, links)| (file.canonicalize().unwrap_or(file), links))
+        .collect();
+    // Only read the project for state when some module shares any
+    let mut state_index: Option<StateIndex> = None;
+    
+    modules.iter()
+        .map(|module| {
+            let module_path = module.canonicalize().unwrap_or_else(|_| module.clone());
+            let link = links.get(&module_path).cloned().unwrap_or_default();
+            let architectural = detect_architectural_connections(&module_path);
+            // Import links already connect it; no need to look further
+            let state = if link.imported_by.is_empty() && shares_state(&module_path) {
+                state_index.get_or_insert_with(|| StateIndex::build(&graph.files)).connections(&module_path)
+            } else {
+                Vec::new()
+            };
+            
+            let incoming_connections = link.imported_by.len() as u32;
+            let outgoing_connections = link.local_specifiers as u32;
+            let arch_connections = architectural.as_ref()
+                .map(|a| a.total_connections)
+                .unwrap_or(0);
+            
+            ConnectivityEvidence {
+                id: Uuid::new_v4(),
+                is_connected: incoming_connections > 0 || outgoing_connections > 0 || !state.is_empty() || arch_connections > 0,
+                module_path,
+                incoming_connections,
+                outgoing_connections,
+                imported_by: link.imported_by,
+                imports: link.imports,
+                architectural,
+                state,
+                computed_at: Utc::now(),
+            }
+        })
+        .collect()
+}
+
 /// Detect architectural connections from deployment configuration
 fn detect_architectural_connections(module_path: &Path) -> Option<ArchitecturalConnections> {
     // First check for Cloudflare Worker (wrangler.toml)
@@ -890,7 +958,7 @@ fn parse_wrangler_toml(path: &Path) -> Option<ArchitecturalConnections> {
     })
 }
 
-fn find_project_root(start: &Path) -> Result<PathBuf, ComputationError> {
+pub(crate) fn find_project_root(start: &Path) -> Result<PathBuf, ComputationError> {
     let mut current = start.parent().unwrap_or(start);
     
     loop {
@@ -1012,7 +1080,7 @@ fn find_importers_recursive(
         
         // Skip hidden and generated directories
         if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-            if name.starts_with('.') || matches!(name, "node_modules" | "target" | "dist" | "build") {
+            if name.starts_with('.') || is_generated_dir(name) {
                 continue;
             }
         }
@@ -1027,7 +1095,7 @@ fn find_importers_recursive(
             let _ = find_importers_recursive(target, module_name, &path, importers);
         } else if path.is_file() && path != target {
             let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
-            if matches!(ext, "ts" | "tsx" | "js" | "jsx") {
+            if matches!(ext, "ts" | "tsx" | "js" | "jsx") && !is_generated_file(&path) {
                 if let Ok(content) = fs::read_to_string(&path) {
                     // Check if this file imports our target module
                     // Handle ESM-style .js imports pointing to .ts files
@@ -1199,6 +1267,45 @@ mod tests {
         assert!(evidence.architectural.is_none());
     }
     
+    #[test]
+    fn test_batch_matches_per_file() {
+        let dir = tempdir().unwrap();
+        File::create(dir.path().join("package.json")).unwrap()
+            .write_all(b"{}").unwrap();
+        std::fs::create_dir_all(dir.path().join("lib")).unwrap();
+        
+        let utils = dir.path().join("lib/utils.ts");
+        File::create(&utils).unwrap()
+            .write_all(b"export function validate() {}").unwrap();
+        let lazy = dir.path().join("lib/lazy.ts");
+        File::create(&lazy).unwrap()
+            .write_all(b"export default 1;").unwrap();
+        let orphan = dir.path().join("orphan.ts");
+        File::create(&orphan).unwrap()
+            .write_all(b"export function unused() {}").unwrap();
+        
+        // ESM-style .js specifier and a dynamic import
+        let main = dir.path().join("main.ts");
+        File::create(&main).unwrap()
+            .write_all(b"import { validate } from './lib/utils.js';
+const m = import('./lib/lazy');
+validate();").unwrap();
+        
+        let graph = SymbolGraph::build(dir.path(), None).unwrap();
+        let evidence = analyze_connectivity_batch(&graph, &[utils.clone(), lazy, orphan.clone(), main]);
+        
+        assert_eq!(evidence[0].incoming_connections, 1);
+        assert!(evidence[0].imported_by[0].ends_with("main.ts"));
+        assert_eq!(evidence[1].incoming_connections, 1);
+        assert_eq!(evidence[2].incoming_connections, 0);
+        assert!(!evidence[2].is_connected);
+        assert_eq!(evidence[3].outgoing_connections, 2);
+        
+        // Same answer as the per-file analysis for the simple cases
+        assert_eq!(analyze_connectivity(&utils).unwrap().is_connected, evidence[0].is_connected);
+        assert_eq!(analyze_connectivity(&orphan).unwrap().is_connected, evidence[2].is_connected);
+    }
+    
     #[test]
     fn test_worker_with_architectural_connections() {
         let dir = tempdir().unwrap();
@@ -0,0 +1,291 @@
+//! Directory Conventions
+//!
+//! Validates the layout of a repo against the `conventions` rules declared
+//! in `.ground.yml`, e.g. "workers live under workers/*/src with an
+//! index.ts" or "shared code must not live under apps/":
+//!
+//! - **Misplaced**: a file matching a rule isn't under any `allow` glob
+//! - **Forbidden**: a file matching a rule is under a `deny` glob
+//! - **Missing**: a directory matching a rule lacks a `require`d path
+//!
+//! Each violation carries a suggested path: where to move the file, or the
+//! file to create.
+
+use std::path::{Component, Path, PathBuf};
+use serde::{Serialize, Deserialize};
+use chrono::{DateTime, Utc};
+use glob::{MatchOptions, Pattern};
+use uuid::Uuid;
+
+use super::ComputationError;
+use super::walk::source_files;
+use crate::config::ConventionRule;
+
+/// `*` stays within one path segment; `**` crosses them
+const MATCH: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+/// How a path breaks a convention
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConventionViolationKind {
+    /// Outside every `allow` glob
+    Misplaced,
+    /// Inside a `deny` glob
+    Forbidden,
+    /// A directory without a `require`d path
+    Missing,
+}
+
+/// A path that breaks a convention
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConventionViolation {
+    /// Name of the broken rule
+    pub rule: String,
+    pub kind: ConventionViolationKind,
+    /// Offending file, or the directory missing a required path
+    pub path: PathBuf,
+    pub message: String,
+    /// Where to move the file, or the file to create
+    pub suggested_path: Option<PathBuf>,
+}
+
+/// Result of checking a directory against the conventions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConventionsReport {
+    pub id: Uuid,
+    /// Directory rule paths are relative to
+    pub root: PathBuf,
+    pub violations: Vec<ConventionViolation>,
+    pub files_checked: usize,
+    pub computed_at: DateTime<Utc>,
+}
+
+struct CompiledRule<'a> {
+    rule: &'a ConventionRule,
+    files: Option<Pattern>,
+    dirs: Option<Pattern>,
+    allow: Vec<Pattern>,
+    deny: Vec<Pattern>,
+}
+
+impl<'a> CompiledRule<'a> {
+    fn new(rule: &'a ConventionRule) -> Result<Self, ComputationError> {
+        let compile = |glob: &str| {
+            Pattern::new(glob).map_err(|e| {
+                ComputationError::InvalidConfig(format!("convention '{}': bad pattern '{}': {}", rule.name, glob, e))
+            })
+        };
+        if rule.files.is_none() && rule.dirs.is_none() {
+            return Err(ComputationError::InvalidConfig(format!(
+                "convention '{}' needs `files` or `dirs`",
+                rule.name
+            )));
+        }
+        
+        Ok(Self {
+            rule,
+            files: rule.files.as_deref().map(compile).transpose()?,
+            dirs: rule.dirs.as_deref().map(compile).transpose()?,
+            allow: rule.allow.iter().map(|g| compile(g)).collect::<Result<_, _>>()?,
+            deny: rule.deny.iter().map(|g| compile(g)).collect::<Result<_, _>>()?,
+        })
+    }
+    
+    fn description(&self) -> String {
+        match &self.rule.description {
+            Some(description) => format!("'{}' ({})", self.rule.name, description),
+            None => format!("'{}'", self.rule.name),
+        }
+    }
+}
+
+/// Check the files and directories under `within` against `rules`, whose
+/// paths are relative to `root` (where the config lives)
+pub fn analyze_conventions(
+    root: &Path,
+    within: &Path,
+    rules: &[ConventionRule],
+) -> Result<ConventionsReport, ComputationError> {
+    let rules: Vec<CompiledRule> = rules.iter().map(CompiledRule::new).collect::<Result<_, _>>()?;
+    
+    let (files, dirs) = collect_entries(within);
+    
+    let mut violations = Vec::new();
+    for file in &files {
+        let rel = relative(root, file);
+        for compiled in rules.iter().filter(|r| r.files.as_ref().is_some_and(|p| p.matches_with(&rel, MATCH))) {
+            let kind = if compiled.deny.iter().any(|p| p.matches_with(&rel, MATCH)) {
+                ConventionViolationKind::Forbidden
+            } else if !compiled.allow.is_empty() && !compiled.allow.iter().any(|p| p.matches_with(&rel, MATCH)) {
+                ConventionViolationKind::Misplaced
+            } else {
+                continue;
+            };
+            
+            let suggested_path = relocation(compiled.rule, &rel).map(|p| root.join(p));
+            let mut message = match kind {
+                ConventionViolationKind::Forbidden => format!("{} breaks {}: not allowed here", rel, compiled.description()),
+                _ => format!(
+                    "{} breaks {}: must be under {}",
+                    rel,
+                    compiled.description(),
+                    compiled.rule.allow.join(" or ")
+                ),
+            };
+            if let Some(target) = &suggested_path {
+                message.push_str(&format!("; move to {}", relative(root, target)));
+            }
+            violations.push(ConventionViolation {
+                rule: compiled.rule.name.clone(),
+                kind,
+                path: file.clone(),
+                message,
+                suggested_path,
+            });
+        }
+    }
+    
+    for dir in &dirs {
+        let rel = relative(root, dir);
+        for compiled in rules.iter().filter(|r| r.dirs.as_ref().is_some_and(|p| p.matches_with(&rel, MATCH))) {
+            for required in compiled.rule.require.iter().filter(|r| !dir.join(r).exists()) {
+                violations.push(ConventionViolation {
+                    rule: compiled.rule.name.clone(),
+                    kind: ConventionViolationKind::Missing,
+                    path: dir.clone(),
+                    message: format!("{} breaks {}: missing {}", rel, compiled.description(), required),
+                    suggested_path: Some(dir.join(required)),
+                });
+            }
+        }
+    }
+    
+    Ok(ConventionsReport {
+        id: Uuid::new_v4(),
+        root: root.to_path_buf(),
+        violations,
+        files_checked: files.len(),
+        computed_at: Utc::now(),
+    })
+}
+
+/// Where an offending file should go: the rule's `relocate` template, or
+/// its first `allow` glob with `*` filled by the file's stem and `**` dropped
+fn relocation(rule: &ConventionRule, rel: &str) -> Option<String> {
+    let path = Path::new(rel);
+    let file = path.file_name()?.to_str()?;
+    let stem = file.split('.').next().unwrap_or(file);
+    let dir = path.parent()
+        .and_then(|p| p.file_name())
+        .and_then(|n| n.to_str())
+        .unwrap_or("");
+    
+    if let Some(template) = &rule.relocate {
+        return Some(template.replace("{file}", file).replace("{stem}", stem).replace("{dir}", dir));
+    }
+    
+    let allow = rule.allow.first()?;
+    let mut segments: Vec<String> = allow.split('/')
+        .filter(|s| *s != "**")
+        .map(|s| s.replace('*', stem))
+        .collect();
+    segments.push(file.to_string());
+    Some(segments.join("/"))
+}
+
+/// `path` relative to `root`, with `/` separators
+fn relative(root: &Path, path: &Path) -> String {
+    let rel = path.strip_prefix(root).unwrap_or(path);
+    rel.components()
+        .filter_map(|c| match c {
+            Component::Normal(s) => s.to_str(),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Source files under `dir`, and `dir` plus every directory holding them
+fn collect_entries(dir: &Path) -> (Vec<PathBuf>, Vec<PathBuf>) {
+    let files = source_files(dir, &[]);
+    let mut dirs: Vec<PathBuf> = files.iter()
+        .flat_map(|f| f.ancestors().skip(1).take_while(|a| a.starts_with(dir)))
+        .map(Path::to_path_buf)
+        .collect();
+    dirs.push(dir.to_path_buf());
+    dirs.sort();
+    dirs.dedup();
+    (files, dirs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+    
+    fn rules() -> Vec<ConventionRule> {
+        serde_yaml::from_str(r#"
+- name: worker-layout
+  files: "**/*.worker.ts"
+  allow: ["workers/*/src/**"]
+- name: worker-entry
+  dirs: "workers/*"
+  require: ["src/index.ts"]
+- name: no-shared-in-apps
+  description: Shared code must not live under apps/
+  files: "**/shared/**"
+  deny: ["apps/**"]
+  relocate: "packages/shared/src/{file}"
+"#).unwrap()
+    }
+    
+    #[test]
+    fn test_conventions() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        for file in [
+            "workers/email/src/index.ts",
+            "workers/email/src/send.worker.ts",
+            "workers/billing/src/charge.ts",
+            "apps/web/src/resize.worker.ts",
+            "apps/web/src/shared/format.ts",
+            "packages/shared/src/dates.ts",
+        ] {
+            let path = root.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "export {};\n").unwrap();
+        }
+        
+        let report = analyze_conventions(root, root, &rules()).unwrap();
+        assert_eq!(report.files_checked, 6);
+        assert_eq!(report.violations.len(), 3);
+        
+        let find = |rule: &str| report.violations.iter().find(|v| v.rule == rule).unwrap();
+        let misplaced = find("worker-layout");
+        assert_eq!(misplaced.kind, ConventionViolationKind::Misplaced);
+        assert!(misplaced.path.ends_with("apps/web/src/resize.worker.ts"));
+        assert_eq!(misplaced.suggested_path, Some(root.join("workers/resize/src/resize.worker.ts")));
+        
+        let missing = find("worker-entry");
+        assert_eq!(missing.kind, ConventionViolationKind::Missing);
+        assert!(missing.path.ends_with("workers/billing"));
+        assert_eq!(missing.suggested_path, Some(root.join("workers/billing/src/index.ts")));
+        
+        let forbidden = find("no-shared-in-apps");
+        assert_eq!(forbidden.kind, ConventionViolationKind::Forbidden);
+        assert_eq!(forbidden.suggested_path, Some(root.join("packages/shared/src/format.ts")));
+        assert!(forbidden.message.contains("Shared code must not live under apps/"));
+        
+        // Scoped to a subdirectory, paths still resolve against the root
+        let report = analyze_conventions(root, &root.join("workers"), &rules()).unwrap();
+        assert_eq!(report.violations.len(), 1);
+        
+        let bad = vec![ConventionRule { name: "empty".to_string(), ..Default::default() }];
+        assert!(matches!(analyze_conventions(root, root, &bad), Err(ComputationError::InvalidConfig(_))));
+    }
+}
@@ -0,0 +1,424 @@
+//! Package Dependencies
+//!
+//! Cross-references what a package.json declares against what the package
+//! actually imports, using the symbol graph's module specifiers:
+//!
+//! - **Unused**: declared, but nothing imports it
+//! - **Missing**: imported, but not declared (works until hoisting changes)
+//!
+//! Imports aren't the only way a dependency gets used, so a declared
+//! package also counts as used when:
+//!
+//! - a package.json script runs it (by package name or its `bin` entries)
+//! - a config file at the package root mentions it (`svelte.config.js`,
+//!   `.eslintrc.json`, `tsconfig.json`, ...)
+//! - it's a `@types/*` package (consumed by the compiler, not imports)
+//! - it's a peer dependency (declared for consumers, not for itself)
+//!
+//! `ignore.dependencies` in `.ground.yml` covers whatever this misses.
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::fs;
+use serde::{Serialize, Deserialize};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::ComputationError;
+use super::generated::is_generated_dir;
+use super::graph::SymbolGraph;
+use crate::config::GroundConfig;
+
+/// Node built-in modules, importable without a dependency
+const NODE_BUILTINS: &[&str] = &[
+    "assert", "async_hooks", "buffer", "child_process", "cluster", "console", "constants",
+    "crypto", "dgram", "diagnostics_channel", "dns", "domain", "events", "fs", "http", "http2",
+    "https", "inspector", "module", "net", "os", "path", "perf_hooks", "process", "punycode",
+    "querystring", "readline", "repl", "stream", "string_decoder", "sys", "timers", "tls",
+    "trace_events", "tty", "url", "util", "v8", "vm", "wasi", "worker_threads", "zlib",
+];
+
+/// Specifier prefixes provided by a runtime or bundler, not a package
+const VIRTUAL_PREFIXES: &[&str] = &[
+    "node:", "cloudflare:", "bun:", "virtual:", "$app/", "$env/", "$service-worker", "~icons/",
+];
+
+/// Commands whose name differs from their package, for when node_modules
+/// isn't installed to read `bin` from
+const KNOWN_BINS: &[(&str, &[&str])] = &[
+    ("typescript", &["tsc", "tsserver"]),
+    ("@sveltejs/kit", &["svelte-kit"]),
+    ("@playwright/test", &["playwright"]),
+    ("@biomejs/biome", &["biome"]),
+    ("@changesets/cli", &["changeset"]),
+    ("npm-run-all", &["run-s", "run-p"]),
+];
+
+/// Which package.json section a dependency is declared in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DependencyKind {
+    Dependencies,
+    DevDependencies,
+    PeerDependencies,
+    OptionalDependencies,
+}
+
+impl DependencyKind {
+    const ALL: [DependencyKind; 4] = [
+        DependencyKind::Dependencies,
+        DependencyKind::DevDependencies,
+        DependencyKind::PeerDependencies,
+        DependencyKind::OptionalDependencies,
+    ];
+    
+    /// The package.json key
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DependencyKind::Dependencies => "dependencies",
+            DependencyKind::DevDependencies => "devDependencies",
+            DependencyKind::PeerDependencies => "peerDependencies",
+            DependencyKind::OptionalDependencies => "optionalDependencies",
+        }
+    }
+}
+
+/// A dependency declared in package.json
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeclaredDependency {
+    pub name: String,
+    pub kind: DependencyKind,
+    /// Line of the declaration in package.json
+    pub line: Option<usize>,
+}
+
+/// A package imported without being declared
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MissingDependency {
+    pub name: String,
+    /// Files that import it
+    pub imported_by: Vec<PathBuf>,
+}
+
+/// Evidence of which declared dependencies a package uses
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyEvidence {
+    pub id: Uuid,
+    /// Directory containing the package.json
+    pub package_dir: PathBuf,
+    pub package_name: Option<String>,
+    pub declared: Vec<DeclaredDependency>,
+    /// Declared but never imported, run, or configured
+    pub unused: Vec<DeclaredDependency>,
+    /// Imported but not declared
+    pub missing: Vec<MissingDependency>,
+    /// Source files in the package that were checked
+    pub files_analyzed: usize,
+    pub computed_at: DateTime<Utc>,
+}
+
+impl DependencyEvidence {
+    pub fn is_declared(&self, name: &str) -> bool {
+        self.declared.iter().any(|d| d.name == name)
+    }
+    
+    pub fn is_unused(&self, name: &str) -> bool {
+        self.unused.iter().any(|d| d.name == name)
+    }
+}
+
+/// Analyze the package whose package.json is in `package_dir`
+///
+/// Reuses `graph` when given (it should cover the package); otherwise builds
+/// one for the package.
+pub fn analyze_dependencies(package_dir: &Path, graph: Option<&SymbolGraph>) -> Result<DependencyEvidence, ComputationError> {
+    let package_dir = package_dir.canonicalize()
+        .map_err(|_| ComputationError::FileNotFound(package_dir.to_path_buf()))?;
+    let manifest_path = package_dir.join("package.json");
+    let content = fs::read_to_string(&manifest_path)
+        .map_err(|_| ComputationError::FileNotFound(manifest_path.clone()))?;
+    let manifest: serde_json::Value = serde_json::from_str(&content)
+        .map_err(|e| ComputationError::ParseError { file: manifest_path.clone(), message: e.to_string() })?;
+    
+    let built;
+    let graph = match graph {
+        Some(graph) => graph,
+        None => {
+            built = SymbolGraph::build(&package_dir, None)
+                .map_err(|message| ComputationError::ParseError { file: package_dir.clone(), message })?;
+            &built
+        }
+    };
+    
+    let package_name = manifest.get("name").and_then(|n| n.as_str()).map(String::from);
+    let declared = declared_dependencies(&manifest, &content);
+    let config = GroundConfig::find_in_ancestors(&package_dir).unwrap_or_default();
+    
+    // Bare package imports from files that belong to this package
+    let nested = nested_packages(&package_dir);
+    let mut imported: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+    let mut files_analyzed = 0;
+    for (file, specifiers) in &graph.module_imports {
+        let file = file.canonicalize().unwrap_or_else(|_| file.clone());
+        if !file.starts_with(&package_dir) || nested.iter().any(|n| file.starts_with(n)) {
+            continue;
+        }
+        files_analyzed += 1;
+        for spec in specifiers {
+            if graph.resolve_alias(spec).is_some() {
+                continue;
+            }
+            if let Some(name) = package_of(spec) {
+                imported.entry(name.to_string()).or_default().push(file.clone());
+            }
+        }
+    }
+    
+    let scripts = scripts_text(&manifest);
+    let configs = root_config_text(&package_dir);
+    
+    let unused = declared.iter()
+        .filter(|d| d.kind != DependencyKind::PeerDependencies)
+        .filter(|d| !d.name.starts_with("@types/"))
+        .filter(|d| !imported.contains_key(&d.name))
+        .filter(|d| !runs_in_scripts(&d.name, &scripts, &package_dir))
+        .filter(|d| !mentions(&configs, &d.name))
+        .filter(|d| !config.should_ignore_dependency(&d.name))
+        .cloned()
+        .collect();
+    
+    let declared_names: HashSet<&str> = declared.iter().map(|d| d.name.as_str()).collect();
+    let missing = imported.into_iter()
+        .filter(|(name, _)| !declared_names.contains(name.as_str()))
+        .filter(|(name, _)| package_name.as_deref() != Some(name.as_str()))
+        .filter(|(name, _)| !config.should_ignore_dependency(name))
+        .map(|(name, mut imported_by)| {
+            imported_by.sort();
+            imported_by.dedup();
+            MissingDependency { name, imported_by }
+        })
+        .collect();
+    
+    Ok(DependencyEvidence {
+        id: Uuid::new_v4(),
+        package_dir,
+        package_name,
+        declared,
+        unused,
+        missing,
+        files_analyzed,
+        computed_at: Utc::now(),
+    })
+}
+
+/// Package directories under `dir` (including `dir` itself), skipping
+/// `node_modules` and other generated directories
+pub fn find_packages(dir: &Path) -> Vec<PathBuf> {
+    let mut packages = Vec::new();
+    collect_packages(dir, &mut packages, 0);
+    packages.sort();
+    packages
+}
+
+fn collect_packages(dir: &Path, packages: &mut Vec<PathBuf>, depth: usize) {
+    if depth > 6 {
+        return;
+    }
+    if dir.join("package.json").is_file() {
+        packages.push(dir.to_path_buf());
+    }
+    let Ok(entries) = fs::read_dir(dir) else { return };
+    for path in entries.filter_map(|e| e.ok()).map(|e| e.path()).filter(|p| p.is_dir()) {
+        let skip = path.file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n.starts_with('.') || is_generated_dir(n));
+        if !skip {
+            collect_packages(&path, packages, depth + 1);
+        }
+    }
+}
+
+/// The package a bare specifier imports (`@scope/pkg/sub` → `@scope/pkg`)
+///
+/// `None` for relative paths, Node built-ins, and runtime/bundler virtual
+/// modules.
+pub fn package_of(spec: &str) -> Option<&str> {
+    if spec.is_empty()
+        || spec.starts_with('.')
+        || spec.starts_with('/')
+        || spec.starts_with('#')
+        || spec.starts_with("@/")
+        || spec.starts_with("~/")
+        || spec.starts_with("$lib")
+        || spec.contains("://")
+        || VIRTUAL_PREFIXES.iter().any(|p| spec.starts_with(p))
+    {
+        return None;
+    }
+    
+    let mut segments = spec.splitn(3, '/');
+    let first = segments.next()?;
+    let name = if first.starts_with('@') {
+        let second = segments.next()?;
+        &spec[..first.len() + 1 + second.len()]
+    } else {
+        first
+    };
+    
+    (!NODE_BUILTINS.contains(&name)).then_some(name)
+}
+
+/// Declarations from every dependency section, with their package.json lines
+fn declared_dependencies(manifest: &serde_json::Value, content: &str) -> Vec<DeclaredDependency> {
+    let mut declared = Vec::new();
+    for kind in DependencyKind::ALL {
+        let Some(deps) = manifest.get(kind.as_str()).and_then(|d| d.as_object()) else { continue };
+        let section_line = content.lines().position(|l| l.contains(&format!("\"{}\"", kind.as_str())));
+        for name in deps.keys() {
+            let key = format!("\"{}\"", name);
+            let line = section_line.and_then(|start| {
+                content.lines().enumerate().skip(start).find(|(_, l)| l.contains(&key)).map(|(i, _)| i + 1)
+            });
+            declared.push(DeclaredDependency { name: name.clone(), kind, line });
+        }
+    }
+    declared
+}
+
+/// Packages nested inside `package_dir` (their files aren't this package's)
+fn nested_packages(package_dir: &Path) -> Vec<PathBuf> {
+    find_packages(package_dir).into_iter()
+        .filter(|p| p != package_dir)
+        .collect()
+}
+
+fn scripts_text(manifest: &serde_json::Value) -> String {
+    manifest.get("scripts")
+        .and_then(|s| s.as_object())
+        .map(|s| s.values().filter_map(|v| v.as_str()).collect::<Vec<_>>().join("\n"))
+        .unwrap_or_default()
+}
+
+/// Whether a script runs the package, by its `bin` names when it's installed
+/// or by its (unscoped) name otherwise
+fn runs_in_scripts(name: &str, scripts: &str, package_dir: &Path) -> bool {
+    if scripts.is_empty() {
+        return false;
+    }
+    
+    let unscoped = name.rsplit('/').next().unwrap_or(name);
+    let mut commands = vec![name.to_string(), unscoped.to_string()];
+    commands.extend(installed_bins(name, package_dir));
+    if let Some((_, bins)) = KNOWN_BINS.iter().find(|(package, _)| *package == name) {
+        commands.extend(bins.iter().map(|b| b.to_string()));
+    }
+    
+    scripts.split(|c: char| c.is_whitespace() || matches!(c, '&' | '|' | ';' | '(' | ')' | '"' | '\''))
+        .any(|word| commands.iter().any(|c| c == word))
+}
+
+/// `bin` names from the installed package, looking up through hoisted
+/// node_modules
+fn installed_bins(name: &str, package_dir: &Path) -> Vec<String> {
+    let Some(manifest) = package_dir.ancestors()
+        .take(6)
+        .map(|d| d.join("node_modules").join(name).join("package.json"))
+        .find(|p| p.is_file())
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok())
+    else {
+        return Vec::new();
+    };
+    
+    match manifest.get("bin") {
+        Some(serde_json::Value::String(_)) => vec![name.rsplit('/').next().unwrap_or(name).to_string()],
+        Some(serde_json::Value::Object(bins)) => bins.keys().cloned().collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Text of config files at the package root (tool configs, dotfiles, tsconfig)
+fn root_config_text(package_dir: &Path) -> String {
+    let Ok(entries) = fs::read_dir(package_dir) else { return String::new() };
+    entries.filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_file())
+        .filter(|p| {
+            let name = p.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            name != "package.json"
+                && !name.ends_with(".lock")
+                && !name.ends_with("-lock.json")
+                && !name.ends_with("-lock.yaml")
+                && (name.contains("config") || name.starts_with('.'))
+        })
+        .filter_map(|p| fs::read_to_string(p).ok())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Whether quoted text mentions the package or a subpath of it
+fn mentions(text: &str, name: &str) -> bool {
+    ['"', '\''].iter().any(|q| {
+        text.contains(&format!("{q}{name}{q}")) || text.contains(&format!("{q}{name}/"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+    
+    #[test]
+    fn test_package_of() {
+        assert_eq!(package_of("lodash/fp"), Some("lodash"));
+        assert_eq!(package_of("@sveltejs/kit/vite"), Some("@sveltejs/kit"));
+        assert_eq!(package_of("./utils"), None);
+        assert_eq!(package_of("fs/promises"), None);
+        assert_eq!(package_of("node:path"), None);
+        assert_eq!(package_of("$app/navigation"), None);
+    }
+    
+    #[test]
+    fn test_unused_and_missing_dependencies() {
+        let dir = tempdir().unwrap();
+        let pkg = dir.path();
+        fs::write(pkg.join("package.json"), r#"{
+  "name": "@create-something/example",
+  "scripts": { "build": "vite build && tsc --noEmit" },
+  "dependencies": {
+    "zod": "^3.0.0",
+    "lodash": "^4.0.0",
+    "d3": "^7.0.0"
+  },
+  "devDependencies": {
+    "vite": "^5.0.0",
+    "prettier-plugin-svelte": "^3.0.0",
+    "@types/node": "^20.0.0"
+  },
+  "peerDependencies": { "svelte": "^5.0.0" }
+}"#).unwrap();
+        fs::write(pkg.join(".prettierrc"), r#"{ "plugins": ["prettier-plugin-svelte"] }"#).unwrap();
+        fs::create_dir_all(pkg.join("src")).unwrap();
+        fs::write(pkg.join("src/index.ts"), r#"
+import { z } from 'zod';
+import { readFile } from 'node:fs/promises';
+import path from 'path';
+import { format } from 'date-fns';
+import { helper } from './helper';
+export * from '@create-something/example/sub';
+const chart = await import('d3');
+"#).unwrap();
+        fs::write(pkg.join("src/helper.ts"), "export const helper = 1;\n").unwrap();
+        
+        let evidence = analyze_dependencies(pkg, None).unwrap();
+        
+        let unused: Vec<&str> = evidence.unused.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(unused, vec!["lodash"]);
+        assert_eq!(evidence.unused[0].line, Some(6));
+        
+        let missing: Vec<&str> = evidence.missing.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(missing, vec!["date-fns"]);
+        assert!(evidence.missing[0].imported_by[0].ends_with("src/index.ts"));
+        assert!(evidence.is_declared("vite") && !evidence.is_unused("vite"));
+    }
+}
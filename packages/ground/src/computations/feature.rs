@@ -0,0 +1,307 @@
+//! Feature Trace
+//!
+//! Removing a deprecated feature one dead-export finding at a time takes
+//! several rounds: each deletion makes the next layer dead. Tracing the
+//! feature from its entry (a route, a module, or one exported symbol) gives
+//! the whole plan up front:
+//!
+//! - **Files**: the exclusive closure, code reachable only through the entry.
+//!   Everything the rest of the codebase reaches (from files nothing imports,
+//!   tests aside) without passing through the entry stays.
+//! - **Exports**: exports of files that stay, used only from the closure
+//! - **Tests**: test files that only import the closure
+//! - **References**: imports of the closure from code that stays, which
+//!   have to be edited out (links to the route, uses of the symbol)
+//!
+//! A symbol entry is cut at its file when everything importing the file
+//! only takes that symbol; otherwise only the symbol goes, and the closure
+//! is empty.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::fs;
+use serde::{Serialize, Deserialize};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::ComputationError;
+use super::function_dry::is_test_file;
+use super::graph::SymbolGraph;
+
+/// A file only the feature reaches
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeatureFile {
+    pub path: PathBuf,
+    pub lines: usize,
+    /// Import hops from the entry
+    pub distance: usize,
+}
+
+/// An export that loses its last consumer with the feature
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeatureExport {
+    pub file: PathBuf,
+    pub name: String,
+    pub line: u32,
+    /// Closure files importing it
+    pub used_by: Vec<PathBuf>,
+}
+
+/// An import of the feature from code that stays
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeatureReference {
+    pub file: PathBuf,
+    pub line: Option<u32>,
+    /// Closure file (or, for a symbol entry, the entry file) it imports
+    pub target: PathBuf,
+    /// Names it imports (empty for side-effect, namespace, or dynamic imports)
+    pub names: Vec<String>,
+}
+
+/// The removal plan for a feature
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeatureTrace {
+    pub id: Uuid,
+    pub entry: PathBuf,
+    pub symbol: Option<String>,
+    /// Files to delete, entry first, then by distance
+    pub files: Vec<FeatureFile>,
+    pub exports: Vec<FeatureExport>,
+    pub tests: Vec<PathBuf>,
+    pub references: Vec<FeatureReference>,
+    /// Files the feature uses that other code keeps alive
+    pub shared: Vec<PathBuf>,
+    /// A symbol entry whose file other code imports for something else
+    pub entry_shared: bool,
+    /// Lines across `files` and `tests`
+    pub lines: usize,
+    pub computed_at: DateTime<Utc>,
+}
+
+impl FeatureTrace {
+    /// Files, exports and tests to remove
+    pub fn removals(&self) -> usize {
+        self.files.len() + self.exports.len() + self.tests.len() + usize::from(self.entry_shared)
+    }
+}
+
+/// Trace the code only `entry` reaches
+///
+/// `entry` is a file, optionally with `#symbol` for one of its exports.
+/// `graph` has to cover everything that might use the feature (build it at
+/// the workspace root).
+pub fn trace_feature(entry: &str, graph: &SymbolGraph) -> Result<FeatureTrace, ComputationError> {
+    let (path, symbol) = match entry.rsplit_once('#') {
+        Some((path, symbol)) if !symbol.is_empty() => (path, Some(symbol.to_string())),
+        _ => (entry, None),
+    };
+    let entry_path = Path::new(path).canonicalize()
+        .map_err(|_| ComputationError::FileNotFound(PathBuf::from(path)))?;
+    
+    let links = graph.module_links();
+    if !links.contains_key(&entry_path) {
+        return Err(ComputationError::FileNotFound(entry_path));
+    }
+    let files: HashSet<PathBuf> = links.keys().cloned().collect();
+    let imports_of = |target: &Path| named_imports(graph, target, &files);
+    
+    // A symbol entry cuts at its file only if that's all anyone takes from it
+    let entry_imports = imports_of(&entry_path);
+    let entry_shared = symbol.as_ref().is_some_and(|symbol| {
+        entry_imports.values().any(|(_, names)| names.is_empty() || names.iter().any(|n| n != symbol))
+    });
+    
+    let tests: HashSet<&PathBuf> = files.iter().filter(|f| is_test_file(f)).collect();
+    let roots: Vec<&PathBuf> = links.iter()
+        .filter(|(path, l)| l.imported_by.is_empty() && **path != entry_path && !tests.contains(path))
+        .map(|(path, _)| path)
+        .collect();
+    let kept = reach(&roots, &links, Some(&entry_path));
+    
+    let (closure, distances) = if entry_shared {
+        (HashSet::new(), HashMap::new())
+    } else {
+        let distances = distances_from(&entry_path, &links);
+        let closure: HashSet<PathBuf> = distances.keys().filter(|f| !kept.contains(*f)).cloned().collect();
+        (closure, distances)
+    };
+    let mut shared: Vec<PathBuf> = distances.keys().filter(|f| kept.contains(*f)).cloned().collect();
+    shared.sort();
+    
+    // Tests that only exercise the feature go with it
+    let mut removed_tests: Vec<PathBuf> = tests.iter()
+        .filter(|t| !closure.contains(**t))
+        .filter(|t| {
+            let imports = &links[**t].imports;
+            !imports.is_empty() && imports.iter().all(|i| closure.contains(i))
+        })
+        .map(|t| (*t).clone())
+        .collect();
+    removed_tests.sort();
+    let gone = |f: &PathBuf| closure.contains(f) || removed_tests.contains(f);
+    
+    // Imports of the feature from code that stays
+    let targets: Vec<&PathBuf> = if entry_shared { vec![&entry_path] } else { closure.iter().collect() };
+    let mut references = Vec::new();
+    for target in targets {
+        for (importer, (line, names)) in imports_of(target) {
+            if gone(&importer) {
+                continue;
+            }
+            // For a shared entry, only uses of the symbol
+            if entry_shared && !names.iter().any(|n| Some(n) == symbol.as_ref()) {
+                continue;
+            }
+            references.push(FeatureReference { file: importer, line, target: target.clone(), names });
+        }
+    }
+    references.sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
+    
+    // Exports of kept files whose only consumers are going
+    let mut exports = Vec::new();
+    for file in &shared {
+        let consumers = imports_of(file);
+        if consumers.values().any(|(_, names)| names.is_empty()) {
+            continue;
+        }
+        for export in graph.exports.get(file).into_iter().flatten().filter(|e| !e.is_reexport) {
+            let used_by: Vec<PathBuf> = consumers.iter()
+                .filter(|(_, (_, names))| names.contains(&export.name))
+                .map(|(importer, _)| importer.clone())
+                .collect();
+            if !used_by.is_empty() && used_by.iter().all(&gone) {
+                let mut used_by = used_by;
+                used_by.sort();
+                exports.push(FeatureExport { file: file.clone(), name: export.name.clone(), line: export.line, used_by });
+            }
+        }
+    }
+    
+    let mut feature_files: Vec<FeatureFile> = closure.iter()
+        .map(|path| FeatureFile { path: path.clone(), lines: count_lines(path), distance: distances[path] })
+        .collect();
+    feature_files.sort_by(|a, b| a.distance.cmp(&b.distance).then(a.path.cmp(&b.path)));
+    let lines = feature_files.iter().map(|f| f.lines).sum::<usize>()
+        + removed_tests.iter().map(|t| count_lines(t)).sum::<usize>();
+    
+    Ok(FeatureTrace {
+        id: Uuid::new_v4(),
+        entry: entry_path,
+        symbol,
+        files: feature_files,
+        exports,
+        tests: removed_tests,
+        references,
+        shared,
+        entry_shared,
+        lines,
+        computed_at: Utc::now(),
+    })
+}
+
+/// Files reachable from `roots`, never entering `avoid`
+fn reach(roots: &[&PathBuf], links: &HashMap<PathBuf, super::ModuleLinks>, avoid: Option<&PathBuf>) -> HashSet<PathBuf> {
+    let mut seen: HashSet<PathBuf> = roots.iter().map(|r| (*r).clone()).collect();
+    let mut queue: VecDeque<PathBuf> = seen.iter().cloned().collect();
+    while let Some(file) = queue.pop_front() {
+        for next in links.get(&file).map(|l| l.imports.as_slice()).unwrap_or_default() {
+            if Some(next) != avoid && seen.insert(next.clone()) {
+                queue.push_back(next.clone());
+            }
+        }
+    }
+    seen
+}
+
+/// Import hops from `entry` to everything it reaches
+fn distances_from(entry: &Path, links: &HashMap<PathBuf, super::ModuleLinks>) -> HashMap<PathBuf, usize> {
+    let mut distances = HashMap::from([(entry.to_path_buf(), 0)]);
+    let mut queue = VecDeque::from([entry.to_path_buf()]);
+    while let Some(file) = queue.pop_front() {
+        let distance = distances[&file] + 1;
+        for next in links.get(&file).map(|l| l.imports.as_slice()).unwrap_or_default() {
+            if !distances.contains_key(next) {
+                distances.insert(next.clone(), distance);
+                queue.push_back(next.clone());
+            }
+        }
+    }
+    distances
+}
+
+/// Files importing `target`, with the first import line and the names they
+/// take (empty when they take everything)
+fn named_imports(graph: &SymbolGraph, target: &Path, files: &HashSet<PathBuf>) -> HashMap<PathBuf, (Option<u32>, Vec<String>)> {
+    let mut importers = HashMap::new();
+    for (importer, specifiers) in &graph.module_imports {
+        if importer == target || !specifiers.iter().any(|s| graph.resolve_module(s, importer, files).as_deref() == Some(target)) {
+            continue;
+        }
+        let named: Vec<_> = graph.imports.get(importer)
+            .into_iter()
+            .flatten()
+            .filter(|i| graph.resolve_module(&i.from_module, importer, files).as_deref() == Some(target))
+            .collect();
+        let line = named.iter().map(|i| i.line).min();
+        let mut names: Vec<String> = if named.iter().any(|i| i.name == "*" || i.name == "default") {
+            Vec::new()
+        } else {
+            named.iter().map(|i| i.name.clone()).collect()
+        };
+        names.sort();
+        names.dedup();
+        importers.insert(importer.clone(), (line, names));
+    }
+    importers
+}
+
+fn count_lines(path: &Path) -> usize {
+    fs::read_to_string(path).map(|c| c.lines().count()).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+    
+    #[test]
+    fn test_trace_feature_exclusive_closure() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        let write = |path: &str, content: &str| {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        };
+        
+        write("src/app.ts", "import { nav } from './nav';\nimport { format } from './lib/format';\nexport const app = nav + format;\n");
+        write("src/nav.ts", "import { legacyLink } from './legacy/page';\nexport const nav = legacyLink;\n");
+        write("src/legacy/page.ts", "import { widget } from './widget';\nimport { format, legacyFormat } from '../lib/format';\nexport const legacyLink = widget + format + legacyFormat;\n");
+        write("src/legacy/widget.ts", "export const widget = 1;\n");
+        write("src/legacy/widget.test.ts", "import { widget } from './widget';\ntest(widget);\n");
+        write("src/lib/format.ts", "export const format = 1;\nexport const legacyFormat = 2;\n");
+        
+        let graph = SymbolGraph::build(&root, None).unwrap();
+        let trace = trace_feature(&root.join("src/legacy/page.ts").to_string_lossy(), &graph).unwrap();
+        
+        let files: Vec<_> = trace.files.iter().map(|f| f.path.strip_prefix(&root).unwrap().to_path_buf()).collect();
+        assert_eq!(files, vec![PathBuf::from("src/legacy/page.ts"), PathBuf::from("src/legacy/widget.ts")]);
+        assert_eq!(trace.tests, vec![root.join("src/legacy/widget.test.ts")]);
+        // format.ts stays (the app uses it) but loses legacyFormat
+        assert_eq!(trace.shared, vec![root.join("src/lib/format.ts")]);
+        assert_eq!(trace.exports.len(), 1);
+        assert_eq!(trace.exports[0].name, "legacyFormat");
+        // nav.ts links to the feature and has to be edited
+        assert_eq!(trace.references.len(), 1);
+        assert_eq!(trace.references[0].file, root.join("src/nav.ts"));
+        assert_eq!(trace.references[0].names, vec!["legacyLink".to_string()]);
+        assert_eq!(trace.removals(), 4);
+        
+        // format.ts is imported for more than legacyFormat, so only the symbol goes
+        let trace = trace_feature(&format!("{}#legacyFormat", root.join("src/lib/format.ts").display()), &graph).unwrap();
+        assert!(trace.entry_shared);
+        assert!(trace.files.is_empty());
+        assert_eq!(trace.references.len(), 1);
+        assert_eq!(trace.references[0].file, root.join("src/legacy/page.ts"));
+    }
+}
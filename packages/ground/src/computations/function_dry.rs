@@ -53,6 +53,7 @@
 //! - Reduce propagated bugs (same-file clones have ~18% higher bug rate)
 //! - Apply the Subtractive Triad: DRY at implementation level
 
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::fs;
 use serde::{Serialize, Deserialize};
@@ -61,6 +62,9 @@ use chrono::{DateTime, Utc};
 use tree_sitter::{Parser, Node};
 
 use super::ComputationError;
+use super::generated::is_generated_file;
+use super::similarity::token_similarity;
+use crate::fingerprint::content_hash;
 
 /// An extracted function from source code
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -111,6 +115,14 @@ pub struct FunctionDryEvidence {
     /// Similarity between the functions (0.0 - 1.0)
     pub similarity: f64,
     
+    /// Exact, renamed, or gapped clone
+    #[serde(default)]
+    pub clone_type: CloneType,
+    
+    /// Token alignment of the bodies with identifiers abstracted (0.0 - 1.0)
+    #[serde(default)]
+    pub alignment: f64,
+    
     /// Function details from file A
     pub function_a: ExtractedFunction,
     
@@ -143,6 +155,14 @@ pub struct IntraFileDryEvidence {
     /// Similarity between the functions (0.0 - 1.0)
     pub similarity: f64,
     
+    /// Exact, renamed, or gapped clone
+    #[serde(default)]
+    pub clone_type: CloneType,
+    
+    /// Token alignment of the bodies with identifiers abstracted (0.0 - 1.0)
+    #[serde(default)]
+    pub alignment: f64,
+    
     /// Function details for first function
     pub function_a: ExtractedFunction,
     
@@ -156,6 +176,45 @@ pub struct IntraFileDryEvidence {
     pub computed_at: DateTime<Utc>,
 }
 
+/// Which kind of clone a duplicate pair is
+///
+/// The fix differs: exact and renamed clones can be merged mechanically
+/// (renamed ones by turning the differing names and literals into
+/// parameters), while gapped clones have statements added or removed and
+/// need a human to decide what the shared function is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CloneType {
+    /// Type-1: identical apart from whitespace and comments
+    Exact,
+    /// Type-2: identical token structure with identifiers or literals renamed
+    Renamed,
+    /// Type-3: aligned with gaps, where statements were added, removed, or changed
+    #[default]
+    Gapped,
+}
+
+impl CloneType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CloneType::Exact => "exact",
+            CloneType::Renamed => "renamed",
+            CloneType::Gapped => "gapped",
+        }
+    }
+    
+    /// Whether the pair can be consolidated without human review
+    pub fn is_safe_to_consolidate(&self) -> bool {
+        !matches!(self, CloneType::Gapped)
+    }
+}
+
+impl std::fmt::Display for CloneType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// Result of function-level DRY analysis
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FunctionDryReport {
@@ -243,6 +302,10 @@ impl FunctionDryOptions {
     
     /// Check if a path should be excluded
     pub fn should_exclude(&self, path: &Path) -> bool {
+        if is_generated_file(path) {
+            return true;
+        }
+        
         if self.exclude_tests {
             if is_test_file(path) {
                 return true;
@@ -491,28 +554,60 @@ fn extract_parameters(node: Node, source: &str) -> Vec<String> {
     params
 }
 
-fn normalize_function_body(source: &str) -> String {
-    // Remove comments, normalize whitespace
-    source
-        .lines()
-        .map(|line| {
-            // Remove single-line comments
-            let without_comment = line.split("//").next().unwrap_or(line);
-            without_comment.trim()
-        })
-        .filter(|line| !line.is_empty())
-        .collect::<Vec<_>>()
-        .join("\n")
-        // Remove block comments
-        .split("/*")
-        .map(|part| part.split("*/").last().unwrap_or(part))
-        .collect::<String>()
-        // Normalize whitespace
+pub(crate) fn normalize_function_body(source: &str) -> String {
+    strip_comments(source)
         .split_whitespace()
         .collect::<Vec<_>>()
         .join(" ")
 }
 
+/// `source` with `//` and `/* */` comments removed, skipping comment markers
+/// inside string and template literals. Newlines are kept, so line numbers
+/// still line up.
+pub(crate) fn strip_comments(source: &str) -> String {
+    let mut out = String::with_capacity(source.len());
+    let mut chars = source.chars().peekable();
+    let mut quote: Option<char> = None;
+    let mut in_block_comment = false;
+    while let Some(c) = chars.next() {
+        if in_block_comment {
+            if c == '*' && chars.peek() == Some(&'/') {
+                chars.next();
+                in_block_comment = false;
+            } else if c == '\n' {
+                out.push(c);
+            }
+            continue;
+        }
+        match quote {
+            Some(_) if c == '\\' => {
+                out.push(c);
+                if let Some(escaped) = chars.next() {
+                    out.push(escaped);
+                }
+                continue;
+            }
+            Some(q) if c == q => quote = None,
+            // Only template literals span lines
+            Some(q) if c == '\n' && q != '`' => quote = None,
+            Some(_) => {}
+            None if c == '/' && chars.peek() == Some(&'/') => {
+                while chars.next_if(|&next| next != '\n').is_some() {}
+                continue;
+            }
+            None if c == '/' && chars.peek() == Some(&'*') => {
+                chars.next();
+                in_block_comment = true;
+                continue;
+            }
+            None if matches!(c, '\'' | '"' | '`') => quote = Some(c),
+            None => {}
+        }
+        out.push(c);
+    }
+    out
+}
+
 /// Suggest an extraction name for intra-file duplicates
 /// 
 /// Attempts to find a common pattern or meaningful name for the shared logic
@@ -624,6 +719,96 @@ pub fn compare_functions(a: &ExtractedFunction, b: &ExtractedFunction) -> f64 {
     token_sim * 0.7 + len_penalty * 0.3
 }
 
+/// Classify a duplicate pair, returning its clone type and token alignment
+///
+/// Each check runs separately: an exact content hash match of the bodies
+/// with the function's own name left out (Type-1), an equal token stream once identifiers are consistently renamed and
+/// literals abstracted (Type-2), and otherwise a gapped alignment of the
+/// abstracted tokens (Type-3). Property names after `.` are kept, since
+/// `a.length` and `b.size` are different code, not a rename.
+pub fn classify_clone(a: &ExtractedFunction, b: &ExtractedFunction) -> (CloneType, f64) {
+    let alignment = token_similarity(&abstract_tokens(&a.normalized_body, false), &abstract_tokens(&b.normalized_body, false));
+    
+    let clone_type = if content_hash(&unnamed_body(a)) == content_hash(&unnamed_body(b)) {
+        CloneType::Exact
+    } else if abstract_tokens(&a.normalized_body, true) == abstract_tokens(&b.normalized_body, true) {
+        CloneType::Renamed
+    } else {
+        CloneType::Gapped
+    };
+    
+    (clone_type, alignment)
+}
+
+/// The normalized body with the first whole-word mention of the function's
+/// name, the one in its signature, removed
+fn unnamed_body(f: &ExtractedFunction) -> String {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_' || c == '$';
+    let body = &f.normalized_body;
+    let signature = body.match_indices(f.name.as_str()).find(|(pos, _)| {
+        !body[..*pos].chars().next_back().is_some_and(is_ident)
+            && !body[pos + f.name.len()..].chars().next().is_some_and(is_ident)
+    });
+    match signature {
+        Some((pos, _)) => format!("{}{}", &body[..pos], &body[pos + f.name.len()..]),
+        None => body.clone(),
+    }
+}
+
+const KEYWORDS: &[&str] = &[
+    "async", "await", "break", "case", "catch", "class", "const", "continue", "default", "delete",
+    "do", "else", "export", "extends", "false", "finally", "for", "from", "function", "if", "import",
+    "in", "instanceof", "let", "new", "null", "of", "return", "static", "super", "switch", "this",
+    "throw", "true", "try", "typeof", "undefined", "var", "void", "while", "yield",
+];
+
+/// Tokens with literals replaced by `LIT` and identifiers by `ID`, or by
+/// their order of first appearance (`$0`, `$1`, ...) when `number_identifiers`
+/// is set, so consistently renamed code produces the same stream
+fn abstract_tokens(body: &str, number_identifiers: bool) -> Vec<String> {
+    let mut tokens: Vec<String> = Vec::new();
+    let mut names: HashMap<String, usize> = HashMap::new();
+    let mut chars = body.chars().peekable();
+    
+    while let Some(c) = chars.next() {
+        if c.is_whitespace() {
+            continue;
+        }
+        
+        if matches!(c, '"' | '\'' | '`') {
+            let mut escaped = false;
+            for next in chars.by_ref() {
+                if next == c && !escaped {
+                    break;
+                }
+                escaped = next == '\\' && !escaped;
+            }
+            tokens.push("LIT".to_string());
+        } else if c.is_ascii_digit() {
+            while chars.next_if(|n| n.is_alphanumeric() || *n == '.' || *n == '_').is_some() {}
+            tokens.push("LIT".to_string());
+        } else if c.is_alphabetic() || c == '_' || c == '$' {
+            let mut word = c.to_string();
+            while let Some(n) = chars.next_if(|n| n.is_alphanumeric() || *n == '_' || *n == '$') {
+                word.push(n);
+            }
+            let is_member = tokens.last().is_some_and(|t| t == ".");
+            if KEYWORDS.contains(&word.as_str()) || is_member {
+                tokens.push(word);
+            } else if number_identifiers {
+                let next = names.len();
+                tokens.push(format!("${}", names.entry(word).or_insert(next)));
+            } else {
+                tokens.push("ID".to_string());
+            }
+        } else {
+            tokens.push(c.to_string());
+        }
+    }
+    
+    tokens
+}
+
 /// Analyze functions across multiple files for duplicates
 pub fn analyze_function_dry(
     files: &[PathBuf],
@@ -688,12 +873,15 @@ pub fn analyze_function_dry_with_options(
                 let similarity = compare_functions(func_a, func_b);
                 
                 if similarity >= threshold {
+                    let (clone_type, alignment) = classify_clone(func_a, func_b);
                     duplicates.push(FunctionDryEvidence {
                         id: Uuid::new_v4(),
                         file_a: path_a.clone(),
                         file_b: path_b.clone(),
                         function_name: func_a.name.clone(),
                         similarity,
+                        clone_type,
+                        alignment,
                         function_a: func_a.clone(),
                         function_b: func_b.clone(),
                         computed_at: Utc::now(),
@@ -707,12 +895,15 @@ pub fn analyze_function_dry_with_options(
                 let similarity = compare_functions(func_a, func_b);
                 
                 if similarity >= intra_threshold {
+                    let (clone_type, alignment) = classify_clone(func_a, func_b);
                     intra_file_duplicates.push(IntraFileDryEvidence {
                         id: Uuid::new_v4(),
                         file: path_a.clone(),
                         function_a_name: func_a.name.clone(),
                         function_b_name: func_b.name.clone(),
                         similarity,
+                        clone_type,
+                        alignment,
                         function_a: func_a.clone(),
                         function_b: func_b.clone(),
                         suggested_extraction: suggest_extraction_name(&func_a.name, &func_b.name),
@@ -1090,4 +1281,67 @@ function bd(path: string) {
             .find(|d| d.file == file_a);
         assert!(intra_dup.is_some(), "Should detect intra-file duplicate in file_a");
     }
+    
+    #[test]
+    fn test_classify_clone_types() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("clones.ts");
+        let mut file = File::create(&path).unwrap();
+        writeln!(file, r#"
+function total(items: Item[]) {{
+    let sum = 0;
+    for (const item of items) {{ sum += item.price; }}
+    return sum;
+}}
+
+function totalCopy(items: Item[]) {{
+    // copied
+    let sum = 0;
+    for (const item of items) {{
+        sum += item.price;
+    }}
+    return sum;
+}}
+
+function subtotal(lines: Line[]) {{
+    let acc = 1;
+    for (const line of lines) {{ acc += line.price; }}
+    return acc;
+}}
+
+function swapped(items: Item[]) {{
+    let sum = 0;
+    for (const item of items) {{ item += sum.price; }}
+    return sum;
+}}
+
+function discounted(items: Item[]) {{
+    let sum = 0;
+    for (const item of items) {{
+        if (item.onSale) {{ continue; }}
+        sum += item.price;
+    }}
+    return sum;
+}}
+"#).unwrap();
+        
+        let functions = extract_functions(&path).unwrap();
+        let get = |name: &str| functions.iter().find(|f| f.name == name).unwrap();
+        
+        // Type-1 ignores whitespace, comments, and the function's own name
+        assert_eq!(classify_clone(get("total"), get("totalCopy")), (CloneType::Exact, 1.0));
+        
+        let (clone_type, alignment) = classify_clone(get("total"), get("subtotal"));
+        assert_eq!(clone_type, CloneType::Renamed);
+        assert_eq!(alignment, 1.0);
+        assert!(clone_type.is_safe_to_consolidate());
+        
+        // Same tokens, but the renaming isn't consistent
+        assert_eq!(classify_clone(get("total"), get("swapped")).0, CloneType::Gapped);
+        
+        let (clone_type, alignment) = classify_clone(get("total"), get("discounted"));
+        assert_eq!(clone_type, CloneType::Gapped);
+        assert!(alignment > 0.7 && alignment < 1.0);
+        assert!(!clone_type.is_safe_to_consolidate());
+    }
 }
@@ -0,0 +1,273 @@
+//! Generated Code Detection
+//!
+//! Build output, vendored packages, and codegen output are not code anyone
+//! maintains, so findings in them are noise. Every analyzer walks the tree
+//! through these checks so they agree on what is "generated":
+//!
+//! 1. **Directories** - `node_modules`, `dist`, `.svelte-kit`, `vendor`, ...
+//!    anywhere below the repository root (so passing `.svelte-kit/output`
+//!    explicitly doesn't bypass the skip list)
+//! 2. **Config globs** - `generated.paths` in `.ground.yml`
+//! 3. **Markers** - `@generated` or Go-style `Code generated ... DO NOT EDIT`
+//!    header comments, plus any `generated.markers` from `.ground.yml`
+//! 4. **Build artifacts** - `sourceMappingURL` references and `.min.*` files
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+use glob::Pattern;
+use regex_lite::Regex;
+
+use crate::config::GroundConfig;
+
+/// Directory names that only ever contain generated or vendored code
+pub const GENERATED_DIRS: &[&str] = &[
+    "node_modules", "vendor", "target", "dist", "build", "coverage",
+    ".svelte-kit", ".next", ".nuxt", ".output", ".turbo", ".vercel", ".wrangler",
+    "__generated__", "__pycache__", "venv", ".venv",
+];
+
+lazy_static::lazy_static! {
+    /// Header comments that mark a file as generated: the `@generated` tag,
+    /// or Go's `Code generated <by> DO NOT EDIT.` line. Prose like "ids are
+    /// generated by the database" doesn't match.
+    static ref MARKER: Regex =
+        Regex::new(r"(?i)(?:^|\s)@generated\b|\bcode generated\b.*\bdo not edit\b").unwrap();
+}
+
+/// Bytes read from the start of a file when looking for markers
+const HEAD_BYTES: u64 = 1024;
+
+/// Bytes read from the end of a file when looking for sourcemap references
+const TAIL_BYTES: u64 = 512;
+
+/// Why a file was treated as generated
+#[derive(Debug, Clone, PartialEq)]
+pub enum GeneratedReason {
+    /// Lives under a generated/vendored directory
+    Directory(String),
+    /// Matches a `generated.paths` glob in .ground.yml
+    ConfigGlob(String),
+    /// Header contains a generated-code marker
+    Marker(String),
+    /// References a sourcemap (bundler output)
+    SourceMap,
+    /// Minified file
+    Minified,
+}
+
+/// Whether a directory name should never be descended into
+pub fn is_generated_dir(name: &str) -> bool {
+    GENERATED_DIRS.contains(&name)
+}
+
+/// Whether a file is generated, vendored, or build output
+pub fn is_generated_file(path: &Path) -> bool {
+    generated_reason(path).is_some()
+}
+
+/// Why a file is considered generated (None if it's source)
+pub fn generated_reason(path: &Path) -> Option<GeneratedReason> {
+    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+    if name.contains(".min.") {
+        return Some(GeneratedReason::Minified);
+    }
+    
+    let rules = rules_for(path.parent().unwrap_or(Path::new(".")));
+    let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let relative = canonical.strip_prefix(&rules.base).unwrap_or(&canonical);
+    
+    for component in relative.parent().into_iter().flat_map(|p| p.components()) {
+        let component = component.as_os_str().to_string_lossy();
+        if is_generated_dir(&component) {
+            return Some(GeneratedReason::Directory(component.to_string()));
+        }
+    }
+    
+    let relative_str = relative.to_string_lossy();
+    if let Some(glob) = rules.globs.iter().find(|g| g.matches(&relative_str)) {
+        return Some(GeneratedReason::ConfigGlob(glob.as_str().to_string()));
+    }
+    
+    let (head, tail) = read_head_tail(path)?;
+    if let Some(marker) = find_marker(&head, &rules.markers) {
+        return Some(GeneratedReason::Marker(marker));
+    }
+    if tail.contains("sourceMappingURL=") {
+        return Some(GeneratedReason::SourceMap);
+    }
+    
+    None
+}
+
+/// The repository root a directory belongs to (see `find_base`) and the
+/// `generated.paths` globs from its config, relative to that root
+pub(crate) fn generated_globs(dir: &Path) -> (PathBuf, Vec<String>) {
+    let rules = rules_for(dir);
+    (rules.base.clone(), rules.globs.iter().map(|g| g.as_str().to_string()).collect())
+}
+
+/// Find a generated-code marker in the leading comments of a file
+pub fn find_marker(content: &str, extra_markers: &[String]) -> Option<String> {
+    for line in content.lines().take(10) {
+        let trimmed = line.trim_start();
+        let is_comment = ["//", "/*", "*", "#", "<!--"].iter().any(|c| trimmed.starts_with(c));
+        if !is_comment {
+            continue;
+        }
+        
+        if let Some(found) = MARKER.find(trimmed) {
+            return Some(found.as_str().trim().to_string());
+        }
+        let lower = trimmed.to_lowercase();
+        if let Some(marker) = extra_markers.iter().find(|m| lower.contains(&m.to_lowercase())) {
+            return Some(marker.clone());
+        }
+    }
+    None
+}
+
+fn read_head_tail(path: &Path) -> Option<(String, String)> {
+    let mut file = File::open(path).ok()?;
+    let len = file.metadata().ok()?.len();
+    
+    let mut head = Vec::new();
+    (&mut file).take(HEAD_BYTES).read_to_end(&mut head).ok()?;
+    
+    let mut tail = Vec::new();
+    if len > HEAD_BYTES {
+        file.seek(SeekFrom::Start(len.saturating_sub(TAIL_BYTES).max(HEAD_BYTES))).ok()?;
+        file.read_to_end(&mut tail).ok()?;
+    } else {
+        tail = head.clone();
+    }
+    
+    Some((
+        String::from_utf8_lossy(&head).into_owned(),
+        String::from_utf8_lossy(&tail).into_owned(),
+    ))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Per-Repository Rules
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Generated-code rules for one repository
+struct GeneratedRules {
+    /// Repository root; directory checks only apply below it
+    base: PathBuf,
+    /// `generated.paths` globs (relative to base)
+    globs: Vec<Pattern>,
+    /// `generated.markers` from config
+    markers: Vec<String>,
+    /// When `.ground.yml` was last modified, as of reading it
+    modified: Option<SystemTime>,
+}
+
+lazy_static::lazy_static! {
+    /// Repository root by directory, so each directory resolves it once
+    static ref BASES: Mutex<HashMap<PathBuf, PathBuf>> = Mutex::new(HashMap::new());
+    /// Rules by repository root, reloaded when `.ground.yml` changes so a
+    /// long-running server sees config edits
+    static ref RULES: Mutex<HashMap<PathBuf, Arc<GeneratedRules>>> = Mutex::new(HashMap::new());
+}
+
+fn rules_for(dir: &Path) -> Arc<GeneratedRules> {
+    let cached = BASES.lock().unwrap().get(dir).cloned();
+    let base = cached.unwrap_or_else(|| {
+        let base = find_base(dir);
+        BASES.lock().unwrap().insert(dir.to_path_buf(), base.clone());
+        base
+    });
+    
+    let config_path = base.join(".ground.yml");
+    let modified = std::fs::metadata(&config_path).and_then(|m| m.modified()).ok();
+    if let Some(rules) = RULES.lock().unwrap().get(&base) {
+        if rules.modified == modified {
+            return rules.clone();
+        }
+    }
+    
+    let config = GroundConfig::load(&config_path).unwrap_or_default();
+    let rules = Arc::new(GeneratedRules {
+        globs: config.generated.paths.iter()
+            .filter_map(|p| Pattern::new(p).ok())
+            .collect(),
+        markers: config.generated.markers,
+        base: base.clone(),
+        modified,
+    });
+    
+    RULES.lock().unwrap().insert(base, rules.clone());
+    rules
+}
+
+/// Find the repository root for a directory
+///
+/// Prefers the nearest `.git`/`.ground.yml`/`pnpm-workspace.yaml`, then the
+/// outermost `package.json` (so `node_modules/foo/package.json` doesn't win),
+/// then the directory itself.
+fn find_base(dir: &Path) -> PathBuf {
+    let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+    
+    if let Some(root) = dir.ancestors().find(|a| {
+        a.join(".git").exists() || a.join(".ground.yml").exists() || a.join("pnpm-workspace.yaml").exists()
+    }) {
+        return root.to_path_buf();
+    }
+    
+    dir.ancestors()
+        .filter(|a| a.join("package.json").exists())
+        .last()
+        .unwrap_or(&dir)
+        .to_path_buf()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+    
+    #[test]
+    fn test_detects_generated_code() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        std::fs::create_dir(root.join(".git")).unwrap();
+        std::fs::write(root.join(".ground.yml"), "generated:\n  paths:\n    - \"src/gql/**\"\n  markers:\n    - \"Prisma Client\"\n").unwrap();
+        
+        let output = root.join(".svelte-kit/output/server");
+        let gql = root.join("src/gql");
+        std::fs::create_dir_all(&output).unwrap();
+        std::fs::create_dir_all(&gql).unwrap();
+        
+        let cases = [
+            (output.join("chunk.js"), "export const a = 1;\n"),
+            (gql.join("types.ts"), "export type A = string;\n"),
+            (root.join("src/api.ts"), "// @generated by openapi-typescript\nexport {};\n"),
+            (root.join("src/pb.ts"), "// Code generated by protoc-gen-ts. DO NOT EDIT.\nexport {};\n"),
+            (root.join("src/prisma.ts"), "/* Prisma Client (v5) */\nexport {};\n"),
+            (root.join("src/bundle.js"), "var a=1;\n//# sourceMappingURL=bundle.js.map\n"),
+            (root.join("src/app.min.js"), "var a=1;"),
+        ];
+        for (path, content) in &cases {
+            std::fs::write(path, content).unwrap();
+            assert!(is_generated_file(path), "{:?} should be generated", path);
+        }
+        
+        // Prose that mentions generation or editing isn't a marker
+        let source = root.join("src/ids.ts");
+        std::fs::write(&source, "// ids are generated by the database\n// do not edit the seed by hand\nexport const id = 1;\n").unwrap();
+        assert!(!is_generated_file(&source));
+        assert_eq!(generated_reason(&cases[4].0), Some(GeneratedReason::Marker("Prisma Client".to_string())));
+        
+        // Config edits are picked up without restarting
+        std::fs::write(root.join(".ground.yml"), "generated:\n  paths:\n    - \"src/ids.ts\"\n").unwrap();
+        let later = std::time::SystemTime::now() + std::time::Duration::from_secs(5);
+        std::fs::File::options().write(true).open(root.join(".ground.yml")).unwrap().set_modified(later).unwrap();
+        assert!(is_generated_file(&source));
+        assert_eq!(generated_reason(&cases[0].0), Some(GeneratedReason::Directory(".svelte-kit".to_string())));
+    }
+}
@@ -7,6 +7,7 @@
 //! - Repo-wide dead export detection (O(1) per export after graph build)
 //! - Finding all consumers of a symbol
 //! - Dependency analysis
+//! - Batch orphan detection: module-level links for every file at once
 //!
 //! ## Performance
 //! - Build: O(files) - parse each file once
@@ -25,6 +26,7 @@ use serde::{Serialize, Deserialize};
 use chrono::{DateTime, Utc};
 
 use super::imports::{extract_imports, extract_exports};
+use super::walk::source_files;
 
 /// Path alias configuration (e.g., $lib → src/lib)
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -91,6 +93,11 @@ pub struct SymbolGraph {
     /// Used to trace if an export is used through re-export chains
     pub reexport_chains: HashMap<String, Vec<(PathBuf, PathBuf)>>,
     
+    /// Every module specifier each file references: imports (including
+    /// side-effect and namespace imports), re-exports, require() and import()
+    #[serde(default)]
+    pub module_imports: HashMap<PathBuf, Vec<String>>,
+    
     /// When the graph was built
     pub built_at: DateTime<Utc>,
     
@@ -145,6 +152,7 @@ impl SymbolGraph {
             module_resolution: HashMap::new(),
             path_aliases,
             reexport_chains: HashMap::new(),
+            module_imports: HashMap::new(),
             built_at: Utc::now(),
             root_dir: root_dir.to_path_buf(),
             files_scanned: 0,
@@ -186,6 +194,10 @@ impl SymbolGraph {
                         // Track re-export chains
                         if e.is_reexport {
                             if let Some(ref source) = e.source {
+                                graph.module_imports
+                                    .entry(file.clone())
+                                    .or_default()
+                                    .push(source.clone());
                                 // Record that this file re-exports from source
                                 graph.reexport_chains
                                     .entry(e.name.clone())
@@ -207,6 +219,14 @@ impl SymbolGraph {
             // Extract imports
             match extract_imports(file) {
                 Ok(imports) => {
+                    let specifiers = graph.module_imports.entry(file.clone()).or_default();
+                    specifiers.extend(imports.iter().map(|i| i.source.clone()));
+                    if let Ok(content) = fs::read_to_string(file) {
+                        specifiers.extend(call_specifiers(&content));
+                    }
+                    specifiers.sort();
+                    specifiers.dedup();
+                    
                     let imported: Vec<ImportedSymbol> = imports.iter().flat_map(|i| {
                         i.symbols.iter().map(|s| {
                             let symbol = ImportedSymbol {
@@ -423,7 +443,7 @@ impl SymbolGraph {
     }
     
     /// Resolve a path alias to its target path
-    fn resolve_alias(&self, module_spec: &str) -> Option<String> {
+    pub(crate) fn resolve_alias(&self, module_spec: &str) -> Option<String> {
         for alias in &self.path_aliases {
             // Handle exact match (e.g., "$lib" -> "src/lib")
             if alias.pattern == module_spec {
@@ -500,6 +520,95 @@ impl SymbolGraph {
         false
     }
     
+    /// Resolve a module specifier to a file in the graph
+    ///
+    /// Handles relative paths, path aliases, extensionless and ESM-style
+    /// `.js` specifiers, and directory index files. Package imports return
+    /// `None`.
+    pub fn resolve_module(&self, module_spec: &str, importer: &Path, files: &HashSet<PathBuf>) -> Option<PathBuf> {
+        let resolved_spec = self.resolve_alias(module_spec);
+        let (spec, base) = match &resolved_spec {
+            Some(spec) => (spec.as_str(), self.root_dir.as_path()),
+            None if module_spec.starts_with("./") || module_spec.starts_with("../") => {
+                (module_spec, importer.parent()?)
+            }
+            None => return None,
+        };
+        
+        let joined = normalize_path(&base.join(spec));
+        let stem = match joined.extension().and_then(|e| e.to_str()) {
+            Some("js" | "jsx" | "mjs") => joined.with_extension(""),
+            _ => joined.clone(),
+        };
+        
+        let mut candidates = vec![joined.clone()];
+        for ext in ["ts", "tsx", "js", "jsx", "svelte"] {
+            candidates.push(PathBuf::from(format!("{}.{}", stem.display(), ext)));
+        }
+        for index in ["index.ts", "index.tsx", "index.js", "index.jsx"] {
+            candidates.push(joined.join(index));
+        }
+        
+        candidates.into_iter().find(|c| files.contains(c))
+    }
+    
+    /// Module-level links for every file: who imports it and what it imports
+    ///
+    /// One pass over `module_imports`, so it's cheap to ask about every file
+    /// in a package. Package specifiers that don't resolve (self-imports by
+    /// package name, unknown aliases) fall back to matching file stems, so a
+    /// module is never called unimported just because resolution gave up.
+    pub fn module_links(&self) -> HashMap<PathBuf, ModuleLinks> {
+        let files: HashSet<PathBuf> = self.files.iter().map(|f| normalize_path(f)).collect();
+        let mut by_stem: HashMap<&str, Vec<&PathBuf>> = HashMap::new();
+        for file in &files {
+            if let Some(stem) = file.file_stem().and_then(|s| s.to_str()) {
+                by_stem.entry(stem).or_default().push(file);
+            }
+        }
+        
+        let mut links: HashMap<PathBuf, ModuleLinks> = files.iter()
+            .map(|f| (f.clone(), ModuleLinks::default()))
+            .collect();
+        
+        for (importer, specifiers) in &self.module_imports {
+            let importer = normalize_path(importer);
+            for spec in specifiers {
+                let is_local = spec.starts_with('.') || self.resolve_alias(spec).is_some();
+                let targets: Vec<PathBuf> = match self.resolve_module(spec, &importer, &files) {
+                    Some(target) => vec![target],
+                    None if is_local => Vec::new(),
+                    None => {
+                        // Last path segment, for package self-imports
+                        let stem = spec.rsplit('/').next().unwrap_or(spec);
+                        let stem = stem.split('.').next().unwrap_or(stem);
+                        by_stem.get(stem).map(|f| f.iter().map(|p| (*p).clone()).collect()).unwrap_or_default()
+                    }
+                };
+                
+                if is_local {
+                    if let Some(entry) = links.get_mut(&importer) {
+                        entry.imports.extend(targets.iter().filter(|t| **t != importer).cloned());
+                        entry.local_specifiers += 1;
+                    }
+                }
+                for target in targets.into_iter().filter(|t| *t != importer) {
+                    if let Some(entry) = links.get_mut(&target) {
+                        entry.imported_by.push(importer.clone());
+                    }
+                }
+            }
+        }
+        
+        for entry in links.values_mut() {
+            entry.imported_by.sort();
+            entry.imported_by.dedup();
+            entry.imports.sort();
+            entry.imports.dedup();
+        }
+        links
+    }
+    
     /// Get statistics about the graph
     pub fn stats(&self) -> GraphStats {
         let total_exports: usize = self.exports.values().map(|e| e.len()).sum();
@@ -515,6 +624,17 @@ impl SymbolGraph {
     }
 }
 
+/// A file's module-level connections
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ModuleLinks {
+    /// Files that import this one
+    pub imported_by: Vec<PathBuf>,
+    /// Files in the graph this one imports
+    pub imports: Vec<PathBuf>,
+    /// Relative or aliased specifiers, resolved or not (assets, JSON, ...)
+    pub local_specifiers: usize,
+}
+
 /// Statistics about a symbol graph
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GraphStats {
@@ -525,33 +645,49 @@ pub struct GraphStats {
     pub parse_errors: usize,
 }
 
-/// Collect all TypeScript/JavaScript/Svelte files recursively
-fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) {
-    let entries = match fs::read_dir(dir) {
-        Ok(e) => e,
-        Err(_) => return,
-    };
-    
-    for entry in entries.filter_map(|e| e.ok()) {
-        let path = entry.path();
-        
-        if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-            // Skip hidden and common non-source directories
-            if name.starts_with('.') || 
-               matches!(name, "node_modules" | "target" | "dist" | "build" | ".svelte-kit" | "coverage") {
+/// Specifiers passed to require() and dynamic import(), plus `export * from`
+/// (which the export extractor doesn't name)
+fn call_specifiers(content: &str) -> Vec<String> {
+    let mut specifiers: Vec<String> = content.lines()
+        .map(|l| l.trim())
+        .filter(|l| l.starts_with("export *"))
+        .filter_map(|l| l.split(" from ").nth(1))
+        .map(|s| s.trim().trim_end_matches(';').trim_matches(|c| c == '\'' || c == '"').to_string())
+        .collect();
+    for call in ["require(", "import("] {
+        for (pos, _) in content.match_indices(call) {
+            let rest = content[pos + call.len()..].trim_start();
+            let Some(quote) = rest.chars().next().filter(|c| matches!(c, '\'' | '"' | '`')) else {
                 continue;
+            };
+            if let Some(end) = rest[1..].find(quote) {
+                specifiers.push(rest[1..end + 1].to_string());
             }
         }
-        
-        if path.is_dir() {
-            collect_files(&path, files);
-        } else if path.is_file() {
-            let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
-            if matches!(ext, "ts" | "tsx" | "js" | "jsx" | "svelte") {
-                files.push(path);
+    }
+    specifiers
+}
+
+/// Resolve `.` and `..` components without touching the filesystem
+fn normalize_path(path: &Path) -> PathBuf {
+    use std::path::Component;
+    
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
             }
+            other => normalized.push(other),
         }
     }
+    normalized
+}
+
+/// Collect all TypeScript/JavaScript/Svelte files recursively
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    files.extend(source_files(dir, &["ts", "tsx", "js", "jsx", "svelte"]));
 }
 
 /// Detect path aliases from config files in the directory hierarchy
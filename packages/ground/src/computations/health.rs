@@ -0,0 +1,445 @@
+//! Health Score
+//!
+//! One number instead of four reports. Combines duplication density, dead
+//! export ratio, orphan ratio, design token adoption, and import cycles into
+//! a 0-100 score, computed per package and for the whole repository.
+//!
+//! Each run is stored in the registry so scores can be compared over time.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use serde::{Serialize, Deserialize};
+use chrono::{DateTime, Utc};
+
+use super::ComputationError;
+use super::function_dry::{analyze_function_dry_with_options, is_test_file, FunctionDryOptions};
+use super::framework::{detect_framework, is_implicit_entry, FrameworkPatterns};
+use super::graph::SymbolGraph;
+use super::pagerank::{build_import_graph, ImportGraph};
+use super::patterns::{analyze_patterns, PatternConfig};
+use super::public_api::PublicApiManifest;
+
+/// Component weights (sum to 1.0)
+const WEIGHT_DUPLICATION: f64 = 0.25;
+const WEIGHT_DEAD_EXPORTS: f64 = 0.25;
+const WEIGHT_ORPHANS: f64 = 0.20;
+const WEIGHT_ADOPTION: f64 = 0.15;
+const WEIGHT_CYCLES: f64 = 0.15;
+
+/// Raw counts behind a health score
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HealthMetrics {
+    /// Source files in scope
+    pub files: usize,
+    /// Duplicate function pairs touching this scope
+    pub duplicate_pairs: usize,
+    /// Files containing at least one duplicated function
+    pub files_with_duplicates: usize,
+    /// Exports defined in this scope
+    pub total_exports: usize,
+    /// Exports nothing imports
+    pub dead_exports: usize,
+    /// Modules nothing imports (excluding entry points, tests, config)
+    pub orphans: usize,
+    /// Style declarations that should use design tokens
+    pub style_declarations: usize,
+    /// Style declarations that do use design tokens
+    pub compliant_declarations: usize,
+    /// Import cycles (strongly connected groups of modules)
+    pub cycles: usize,
+    /// Files participating in a cycle
+    pub files_in_cycles: usize,
+}
+
+impl HealthMetrics {
+    /// Fraction of files containing a duplicated function
+    pub fn duplication_density(&self) -> f64 {
+        ratio(self.files_with_duplicates, self.files)
+    }
+    
+    /// Fraction of exports that are never imported
+    pub fn dead_export_ratio(&self) -> f64 {
+        ratio(self.dead_exports, self.total_exports)
+    }
+    
+    /// Fraction of files that are orphaned
+    pub fn orphan_ratio(&self) -> f64 {
+        ratio(self.orphans, self.files)
+    }
+    
+    /// Design token adoption (0.0 - 1.0), if there is any styling to measure
+    pub fn adoption_ratio(&self) -> Option<f64> {
+        (self.style_declarations > 0)
+            .then(|| ratio(self.compliant_declarations, self.style_declarations))
+    }
+    
+    /// Fraction of files caught in an import cycle
+    pub fn cycle_ratio(&self) -> f64 {
+        ratio(self.files_in_cycles, self.files)
+    }
+    
+    /// Composite score (0 - 100, higher is healthier)
+    ///
+    /// Each component scores `1 - ratio`; adoption scores its ratio directly
+    /// and is left out (weights renormalized) when there is nothing to adopt.
+    pub fn score(&self) -> f64 {
+        let mut components = vec![
+            (1.0 - self.duplication_density(), WEIGHT_DUPLICATION),
+            (1.0 - self.dead_export_ratio(), WEIGHT_DEAD_EXPORTS),
+            (1.0 - self.orphan_ratio(), WEIGHT_ORPHANS),
+            (1.0 - self.cycle_ratio(), WEIGHT_CYCLES),
+        ];
+        if let Some(adoption) = self.adoption_ratio() {
+            components.push((adoption, WEIGHT_ADOPTION));
+        }
+        
+        let total_weight: f64 = components.iter().map(|(_, w)| w).sum();
+        let weighted: f64 = components.iter().map(|(s, w)| s * w).sum();
+        (weighted / total_weight * 100.0).clamp(0.0, 100.0)
+    }
+}
+
+fn ratio(part: usize, whole: usize) -> f64 {
+    if whole == 0 {
+        0.0
+    } else {
+        part as f64 / whole as f64
+    }
+}
+
+/// Health of a single package
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackageHealth {
+    pub name: String,
+    pub path: PathBuf,
+    pub score: f64,
+    pub metrics: HealthMetrics,
+}
+
+/// Health of a repository (overall and per package)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthReport {
+    pub directory: PathBuf,
+    /// Overall score (0 - 100)
+    pub score: f64,
+    pub metrics: HealthMetrics,
+    /// Per-package scores, worst first
+    pub packages: Vec<PackageHealth>,
+    pub computed_at: DateTime<Utc>,
+}
+
+/// Change in a package's score between runs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackageDelta {
+    pub name: String,
+    pub score: f64,
+    /// None when the package is new since the previous run
+    pub delta: Option<f64>,
+}
+
+/// Change since the previous run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthTrend {
+    pub previous_at: DateTime<Utc>,
+    pub previous_score: f64,
+    pub score_delta: f64,
+    pub duplication_density_delta: f64,
+    pub dead_export_ratio_delta: f64,
+    pub orphans_delta: i64,
+    pub adoption_ratio_delta: Option<f64>,
+    pub cycles_delta: i64,
+    pub packages: Vec<PackageDelta>,
+}
+
+impl HealthReport {
+    /// Compare against a previous run of the same directory
+    pub fn trend_since(&self, previous: &HealthReport) -> HealthTrend {
+        let prev_packages: HashMap<&str, f64> = previous.packages.iter()
+            .map(|p| (p.name.as_str(), p.score))
+            .collect();
+        
+        let (now, before) = (&self.metrics, &previous.metrics);
+        
+        HealthTrend {
+            previous_at: previous.computed_at,
+            previous_score: previous.score,
+            score_delta: self.score - previous.score,
+            duplication_density_delta: now.duplication_density() - before.duplication_density(),
+            dead_export_ratio_delta: now.dead_export_ratio() - before.dead_export_ratio(),
+            orphans_delta: now.orphans as i64 - before.orphans as i64,
+            adoption_ratio_delta: now.adoption_ratio().zip(before.adoption_ratio()).map(|(a, b)| a - b),
+            cycles_delta: now.cycles as i64 - before.cycles as i64,
+            packages: self.packages.iter()
+                .map(|p| PackageDelta {
+                    name: p.name.clone(),
+                    score: p.score,
+                    delta: prev_packages.get(p.name.as_str()).map(|prev| p.score - prev),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Compute the health score for a repository or package
+pub fn compute_health(root: &Path, similarity_threshold: f64) -> Result<HealthReport, ComputationError> {
+    if !root.is_dir() {
+        return Err(ComputationError::FileNotFound(root.to_path_buf()));
+    }
+    
+    let packages = discover_packages(root);
+    
+    // Exports and the file universe
+    let graph = SymbolGraph::build(root, None)
+        .map_err(|message| ComputationError::ParseError { file: root.to_path_buf(), message })?;
+    let (dead, _) = PublicApiManifest::load(root)
+        .partition(graph.find_dead_exports().dead_exports, |d| (&d.name, &d.file, d.line));
+    let dead: HashMap<PathBuf, usize> = dead.iter()
+        .fold(HashMap::new(), |mut acc, d| {
+            *acc.entry(d.file.clone()).or_insert(0) += 1;
+            acc
+        });
+    
+    // Orphans and cycles from the relative import graph
+    let imports = build_import_graph(root)?;
+    let cycles = find_cycles(&imports);
+    let frameworks: Vec<(PathBuf, FrameworkPatterns)> = packages.iter()
+        .map(|(_, path)| (path.clone(), detect_framework(path).patterns))
+        .collect();
+    let orphans: HashSet<PathBuf> = (0..imports.node_count())
+        .filter(|&i| imports.in_degree(i) == 0)
+        .map(|i| imports.index_to_path[i].clone())
+        .filter(|path| {
+            let patterns = frameworks.iter()
+                .filter(|(pkg, _)| path.starts_with(pkg))
+                .max_by_key(|(pkg, _)| pkg.components().count())
+                .map(|(_, p)| p);
+            !is_expected_root(path, patterns)
+        })
+        .collect();
+    
+    // Duplicated functions
+    let options = FunctionDryOptions { exclude_tests: true, ..Default::default() };
+    let dry = analyze_function_dry_with_options(&graph.files, similarity_threshold, &options)?;
+    
+    // Design token adoption (only meaningful where there is styling)
+    let style: Vec<(PathBuf, usize, usize)> = analyze_patterns(root, &PatternConfig::default())
+        .map(|report| report.file_evidence.into_iter()
+            .map(|e| (e.file, e.metrics.total_declarations, e.metrics.compliant_declarations))
+            .collect())
+        .unwrap_or_default();
+    
+    let measure = |scope: &Path| -> HealthMetrics {
+        let in_scope = |p: &Path| p.starts_with(scope);
+        let mut metrics = HealthMetrics {
+            files: graph.files.iter().filter(|f| in_scope(f)).count(),
+            ..Default::default()
+        };
+        
+        let mut dup_files = HashSet::new();
+        for d in &dry.duplicates {
+            if in_scope(&d.file_a) || in_scope(&d.file_b) {
+                metrics.duplicate_pairs += 1;
+            }
+            for f in [&d.file_a, &d.file_b] {
+                if in_scope(f) {
+                    dup_files.insert(f.clone());
+                }
+            }
+        }
+        metrics.files_with_duplicates = dup_files.len();
+        
+        for (file, exports) in &graph.exports {
+            if in_scope(file) {
+                metrics.total_exports += exports.iter().filter(|e| !e.is_reexport).count();
+                metrics.dead_exports += dead.get(file).copied().unwrap_or(0);
+            }
+        }
+        
+        metrics.orphans = orphans.iter().filter(|f| in_scope(f)).count();
+        
+        for (file, total, compliant) in &style {
+            if in_scope(file) {
+                metrics.style_declarations += total;
+                metrics.compliant_declarations += compliant;
+            }
+        }
+        
+        for cycle in &cycles {
+            let members = cycle.iter().filter(|f| in_scope(f)).count();
+            if members > 0 {
+                metrics.cycles += 1;
+                metrics.files_in_cycles += members;
+            }
+        }
+        
+        metrics
+    };
+    
+    let mut package_health: Vec<PackageHealth> = packages.iter()
+        .map(|(name, path)| {
+            let metrics = measure(path);
+            PackageHealth {
+                name: name.clone(),
+                path: path.clone(),
+                score: metrics.score(),
+                metrics,
+            }
+        })
+        .collect();
+    package_health.sort_by(|a, b| a.score.partial_cmp(&b.score).unwrap_or(std::cmp::Ordering::Equal));
+    
+    let metrics = measure(root);
+    Ok(HealthReport {
+        directory: root.to_path_buf(),
+        score: metrics.score(),
+        metrics,
+        packages: package_health,
+        computed_at: Utc::now(),
+    })
+}
+
+/// Packages under `packages/` and `apps/`, or the root itself
+fn discover_packages(root: &Path) -> Vec<(String, PathBuf)> {
+    let mut packages = Vec::new();
+    
+    for group in ["packages", "apps"] {
+        let Ok(entries) = std::fs::read_dir(root.join(group)) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            if path.is_dir() && !name.starts_with('.') {
+                packages.push((name, path));
+            }
+        }
+    }
+    
+    if packages.is_empty() {
+        let name = root.file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or(".")
+            .to_string();
+        packages.push((name, root.to_path_buf()));
+    }
+    
+    packages.sort();
+    packages
+}
+
+/// Modules that are expected to have no importers
+fn is_expected_root(path: &Path, patterns: Option<&FrameworkPatterns>) -> bool {
+    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+    let stem = name.split('.').next().unwrap_or("");
+    
+    matches!(stem, "index" | "main" | "mod" | "server" | "worker")
+        || name.ends_with(".d.ts")
+        || name.contains(".config.")
+        || name.starts_with('+')
+        || is_test_file(path)
+        || patterns.map(|p| is_implicit_entry(path, p)).unwrap_or(false)
+}
+
+/// Import cycles: strongly connected components with more than one module
+/// (or a module importing itself), via Tarjan's algorithm
+fn find_cycles(graph: &ImportGraph) -> Vec<Vec<PathBuf>> {
+    struct Tarjan<'a> {
+        graph: &'a ImportGraph,
+        index: usize,
+        indices: Vec<Option<usize>>,
+        lowlink: Vec<usize>,
+        on_stack: Vec<bool>,
+        stack: Vec<usize>,
+        cycles: Vec<Vec<PathBuf>>,
+    }
+    
+    impl Tarjan<'_> {
+        fn visit(&mut self, v: usize) {
+            self.indices[v] = Some(self.index);
+            self.lowlink[v] = self.index;
+            self.index += 1;
+            self.stack.push(v);
+            self.on_stack[v] = true;
+            
+            for &w in &self.graph.outgoing[v] {
+                match self.indices[w] {
+                    None => {
+                        self.visit(w);
+                        self.lowlink[v] = self.lowlink[v].min(self.lowlink[w]);
+                    }
+                    Some(w_index) if self.on_stack[w] => {
+                        self.lowlink[v] = self.lowlink[v].min(w_index);
+                    }
+                    _ => {}
+                }
+            }
+            
+            if Some(self.lowlink[v]) == self.indices[v] {
+                let mut component = Vec::new();
+                while let Some(w) = self.stack.pop() {
+                    self.on_stack[w] = false;
+                    component.push(w);
+                    if w == v {
+                        break;
+                    }
+                }
+                if component.len() > 1 || self.graph.outgoing[v].contains(&v) {
+                    self.cycles.push(component.into_iter()
+                        .map(|i| self.graph.index_to_path[i].clone())
+                        .collect());
+                }
+            }
+        }
+    }
+    
+    let n = graph.node_count();
+    let mut tarjan = Tarjan {
+        graph,
+        index: 0,
+        indices: vec![None; n],
+        lowlink: vec![0; n],
+        on_stack: vec![false; n],
+        stack: Vec::new(),
+        cycles: Vec::new(),
+    };
+    for v in 0..n {
+        if tarjan.indices[v].is_none() {
+            tarjan.visit(v);
+        }
+    }
+    tarjan.cycles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+    
+    #[test]
+    fn test_cycles_and_orphans_lower_the_score() {
+        let dir = tempdir().unwrap();
+        let src = dir.path().join("src");
+        std::fs::create_dir_all(&src).unwrap();
+        std::fs::write(src.join("index.ts"), "import { a } from './a';\nexport const run = () => a();\n").unwrap();
+        std::fs::write(src.join("a.ts"), "import { b } from './b';\nexport const a = () => b();\n").unwrap();
+        std::fs::write(src.join("b.ts"), "import { a } from './a';\nexport const b = () => 1;\n").unwrap();
+        std::fs::write(src.join("stray.ts"), "export const unused = 1;\n").unwrap();
+        
+        let report = compute_health(dir.path(), 0.8).unwrap();
+        assert_eq!(report.metrics.files, 4);
+        assert_eq!(report.metrics.cycles, 1);
+        assert_eq!(report.metrics.files_in_cycles, 2);
+        assert_eq!(report.metrics.orphans, 1);
+        assert!(report.score < 100.0);
+        assert_eq!(report.packages.len(), 1);
+        
+        // Trend against a healthier previous run
+        let mut previous = report.clone();
+        previous.metrics.cycles = 0;
+        previous.metrics.files_in_cycles = 0;
+        previous.score = previous.metrics.score();
+        let trend = report.trend_since(&previous);
+        assert_eq!(trend.cycles_delta, 1);
+        assert!(trend.score_delta < 0.0);
+    }
+}
@@ -73,6 +73,8 @@ pub struct ExtractedImport {
 pub struct ExtractedExport {
     /// Symbol name being exported
     pub name: String,
+    /// Name consumers import it by, when aliased (`export { x as y }`)
+    pub alias: Option<String>,
     /// Whether this is a re-export (export { x } from './y')
     pub is_reexport: bool,
     /// Source path if re-export
@@ -284,11 +286,15 @@ fn parse_export_statement(node: Node, source: &str, exports: &mut Vec<ExtractedE
                 let mut inner_cursor = child.walk();
                 for inner in child.children(&mut inner_cursor) {
                     if inner.kind() == "export_specifier" {
+                        let alias = inner.child_by_field_name("alias")
+                            .and_then(|a| a.utf8_text(source.as_bytes()).ok())
+                            .map(|a| a.to_string());
                         // Get the local name (what's being exported)
                         if let Some(name_node) = inner.child_by_field_name("name") {
                             if let Ok(name) = name_node.utf8_text(source.as_bytes()) {
                                 exports.push(ExtractedExport {
                                     name: name.to_string(),
+                                    alias: alias.clone(),
                                     is_reexport: export_source.is_some(),
                                     source: export_source.clone(),
                                     line,
@@ -302,6 +308,7 @@ fn parse_export_statement(node: Node, source: &str, exports: &mut Vec<ExtractedE
                                     if let Ok(name) = spec_child.utf8_text(source.as_bytes()) {
                                         exports.push(ExtractedExport {
                                             name: name.to_string(),
+                                            alias: None,
                                             is_reexport: export_source.is_some(),
                                             source: export_source.clone(),
                                             line,
@@ -320,6 +327,7 @@ fn parse_export_statement(node: Node, source: &str, exports: &mut Vec<ExtractedE
                     if let Ok(name) = name_node.utf8_text(source.as_bytes()) {
                         exports.push(ExtractedExport {
                             name: name.to_string(),
+                            alias: None,
                             is_reexport: false,
                             source: None,
                             line,
@@ -336,6 +344,7 @@ fn parse_export_statement(node: Node, source: &str, exports: &mut Vec<ExtractedE
                             if let Ok(name) = name_node.utf8_text(source.as_bytes()) {
                                 exports.push(ExtractedExport {
                                     name: name.to_string(),
+                                    alias: None,
                                     is_reexport: false,
                                     source: None,
                                     line,
@@ -351,6 +360,7 @@ fn parse_export_statement(node: Node, source: &str, exports: &mut Vec<ExtractedE
                     if let Ok(name) = name_node.utf8_text(source.as_bytes()) {
                         exports.push(ExtractedExport {
                             name: name.to_string(),
+                            alias: None,
                             is_reexport: false,
                             source: None,
                             line,
@@ -364,6 +374,7 @@ fn parse_export_statement(node: Node, source: &str, exports: &mut Vec<ExtractedE
                     if let Ok(name) = name_node.utf8_text(source.as_bytes()) {
                         exports.push(ExtractedExport {
                             name: name.to_string(),
+                            alias: None,
                             is_reexport: false,
                             source: None,
                             line,
@@ -377,6 +388,7 @@ fn parse_export_statement(node: Node, source: &str, exports: &mut Vec<ExtractedE
                     if let Ok(name) = name_node.utf8_text(source.as_bytes()) {
                         exports.push(ExtractedExport {
                             name: name.to_string(),
+                            alias: None,
                             is_reexport: false,
                             source: None,
                             line,
@@ -390,6 +402,7 @@ fn parse_export_statement(node: Node, source: &str, exports: &mut Vec<ExtractedE
                     if let Ok(name) = name_node.utf8_text(source.as_bytes()) {
                         exports.push(ExtractedExport {
                             name: name.to_string(),
+                            alias: None,
                             is_reexport: false,
                             source: None,
                             line,
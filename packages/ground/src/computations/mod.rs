@@ -7,6 +7,10 @@
 //! - Similarity: Compare files for duplicates
 //! - Usage: Count symbol usages, find dead exports
 //! - Connectivity: Analyze module connections
+//! - Dependencies: Declared package.json dependencies vs. imports
+//! - Barrels: How much of a barrel's re-exported surface is consumed
+//! - Signatures: Exported functions across packages that look like the same API
+//! - Rename: Everything renaming a symbol would touch, including dynamic uses
 //!
 //! ## Advanced Algorithms (v2.0)
 //! - PageRank: Import graph importance scoring
@@ -14,6 +18,7 @@
 //! - Reachability: Entry point analysis
 //! - Framework: Auto-detect SvelteKit/Next.js/Cloudflare
 //! - Confidence: Bayesian scoring for AI-native decisions
+//! - Quick scan: Sampled duplication and adoption estimates for huge repos
 //!
 //! ## Pattern Analysis (v2.1)
 //! - Patterns: Design system drift detection and token adoption
@@ -41,13 +46,47 @@ pub mod patterns;
 // Symbol graph (v2.2)
 pub mod graph;
 
-pub use similarity::{compute_similarity, SimilarityEvidence};
+// Repo health score (v2.4)
+pub mod health;
+pub mod quick_scan;
+
+// Generated/vendored code detection
+pub mod generated;
+pub mod walk;
+
+// Stores and context keys as connections
+pub mod svelte_state;
+
+// Exports consumed outside the repo
+pub mod public_api;
+
+// package.json dependencies vs. imports
+pub mod dependencies;
+pub mod conventions;
+
+// Re-export surface consumers actually use
+pub mod barrels;
+pub mod rename;
+
+// Convergent reinvention: same API, different names and bodies
+pub mod signatures;
+pub mod feature;
+
+pub use similarity::{
+    compute_similarity, compute_snippet_similarity, explain_similarity, snippet_key, AlignedRegion,
+    SimilarityEvidence, SimilarityExplanation,
+};
 pub use usage::{count_usages, find_dead_exports, UsageEvidence, UsageLocation, UsageType, DeadExport, DeadExportsReport};
-pub use connectivity::{analyze_connectivity, ConnectivityEvidence, ArchitecturalConnections, ServiceBinding};
+pub(crate) use connectivity::find_project_root;
+pub use svelte_state::{StateConnection, StateConnectionKind};
+pub use connectivity::{
+    analyze_connectivity, analyze_connectivity_batch, ConnectivityEvidence, ArchitecturalConnections,
+    ServiceBinding,
+};
 pub use ast_similarity::{extract_fingerprint, compare_fingerprints, AstFingerprint, AstSimilarity};
 pub use function_dry::{
     extract_functions, analyze_function_dry, analyze_function_dry_with_options, compare_functions,
-    ExtractedFunction, FunctionDryEvidence, FunctionDryReport, FunctionDryOptions, is_test_file,
+    classify_clone, CloneType, ExtractedFunction, FunctionDryEvidence, FunctionDryReport, FunctionDryOptions, is_test_file,
     IntraFileDryEvidence, DEFAULT_INTRA_FILE_THRESHOLD,
 };
 pub use environment::{
@@ -91,9 +130,23 @@ pub use confidence::{
     AUTO_FIX_THRESHOLD, REVIEW_THRESHOLD,
 };
 pub use graph::{
-    SymbolGraph, ExportedSymbol, ImportedSymbol, 
+    SymbolGraph, ExportedSymbol, ImportedSymbol, ModuleLinks,
     GraphDeadExport, GraphDeadExportsReport, GraphStats,
 };
+pub use public_api::{ExternalUsageReport, ExternallyUsedExport, PublicApiManifest};
+pub use dependencies::{
+    analyze_dependencies, find_packages, DependencyEvidence, DependencyKind, DeclaredDependency,
+    MissingDependency,
+};
+pub use conventions::{analyze_conventions, ConventionViolation, ConventionViolationKind, ConventionsReport};
+pub use barrels::{analyze_barrels, BarrelBloatReport, BarrelEvidence, BarrelModule, BarrelOptions};
+pub use signatures::{
+    analyze_signature_clusters, name_words, ExportedSignature, SignatureCluster, SignatureClusterReport, SignatureOptions,
+};
+pub use feature::{trace_feature, FeatureExport, FeatureFile, FeatureReference, FeatureTrace};
+pub use rename::{
+    analyze_rename_impact, DynamicUsage, DynamicUsageKind, PackageConsumers, RenameImpact, RenameSite, SiteKind,
+};
 
 // Pattern analysis exports (v2.1+)
 pub use patterns::{
@@ -113,6 +166,14 @@ pub use patterns::{
     PatternRegistry, FileContext,
 };
 
+pub use generated::{is_generated_dir, is_generated_file, generated_reason, GeneratedReason};
+
+// Health score exports (v2.4)
+pub use health::{
+    compute_health, HealthMetrics, HealthReport, HealthTrend, PackageHealth, PackageDelta,
+};
+pub use quick_scan::{quick_scan, AuditVerdict, Estimate, QuickScanOptions, QuickScanReport};
+
 use thiserror::Error;
 use std::path::PathBuf;
 
@@ -129,4 +190,7 @@ pub enum ComputationError {
     
     #[error("Unsupported language: {0}")]
     UnsupportedLanguage(String),
+    
+    #[error("Invalid config: {0}")]
+    InvalidConfig(String),
 }
@@ -22,6 +22,8 @@ use std::path::{Path, PathBuf};
 use std::fs;
 use serde::{Serialize, Deserialize};
 
+use super::walk::source_files;
+
 /// Default damping factor for PageRank
 pub const DEFAULT_DAMPING: f64 = 0.85;
 
@@ -270,41 +272,14 @@ fn build_graph_recursive(
     dir: &Path,
     graph: &mut ImportGraph,
 ) -> std::io::Result<()> {
-    let entries = match fs::read_dir(dir) {
-        Ok(e) => e,
-        Err(_) => return Ok(()), // Skip unreadable directories
-    };
-    
-    for entry in entries.flatten() {
-        let path = entry.path();
-        
-        // Skip hidden and generated directories
-        if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-            if name.starts_with('.') || 
-               matches!(name, "node_modules" | "target" | "dist" | "build" | ".svelte-kit" | "venv" | ".venv") {
-                continue;
-            }
-        }
-        
-        if !path.exists() {
-            continue;
-        }
-        
-        if path.is_dir() {
-            build_graph_recursive(root, &path, graph)?;
-        } else if path.is_file() {
-            let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
-            if matches!(ext, "ts" | "tsx" | "js" | "jsx") {
-                // Add node
-                graph.add_node(path.clone());
-                
-                // Parse imports
-                if let Ok(content) = fs::read_to_string(&path) {
-                    for import_path in extract_imports(&content) {
-                        if let Some(resolved) = resolve_import(&path, &import_path, root) {
-                            graph.add_edge(&path, &resolved);
-                        }
-                    }
+    for path in source_files(dir, &["ts", "tsx", "js", "jsx"]) {
+        graph.add_node(path.clone());
+        
+        // Parse imports
+        if let Ok(content) = fs::read_to_string(&path) {
+            for import_path in extract_imports(&content) {
+                if let Some(resolved) = resolve_import(&path, &import_path, root) {
+                    graph.add_edge(&path, &resolved);
                 }
             }
         }
@@ -40,6 +40,8 @@ use std::path::{Path, PathBuf};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use super::walk::source_files;
+
 // =============================================================================
 // ERRORS
 // =============================================================================
@@ -814,32 +816,13 @@ fn discover_svelte_files(root: &Path, extensions: Option<&[String]>) -> Result<V
 }
 
 fn discover_files_recursive(dir: &Path, files: &mut Vec<PathBuf>, extensions: Option<&[String]>) -> Result<(), PatternError> {
-    if dir.is_dir() {
-        for entry in std::fs::read_dir(dir)? {
-            let entry = entry?;
-            let path = entry.path();
-            
-            // Skip node_modules, build artifacts, and hidden directories
-            if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                if name.starts_with('.') || name == "node_modules" || name == "dist" 
-                   || name == "venv" || name == "build" || name == "target" {
-                    continue;
-                }
-            }
-            
-            if path.is_dir() {
-                discover_files_recursive(&path, files, extensions)?;
-            } else if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
-                // Check against extension filter if provided
-                let should_include = match extensions {
-                    Some(allowed) => allowed.iter().any(|a| a == ext),
-                    None => matches!(ext, "svelte" | "tsx" | "ts" | "css"),
-                };
-                if should_include {
-                    files.push(path);
-                }
-            }
-        }
+    let extensions: Vec<&str> = match extensions {
+        Some(allowed) => allowed.iter().map(String::as_str).collect(),
+        None => vec!["svelte", "tsx", "ts", "css"],
+    };
+    // An empty filter would mean every file
+    if !extensions.is_empty() && dir.is_dir() {
+        files.extend(source_files(dir, &extensions));
     }
     Ok(())
 }
@@ -0,0 +1,255 @@
+//! Public API Manifest
+//!
+//! An export that only other repositories import looks dead from inside the
+//! monorepo. The public API manifest says which exports are consumed
+//! externally, from two sources:
+//!
+//! - `public_api.symbols` in `.ground.yml`, maintained by hand
+//! - Usage reports generated in downstream repos (`ground public-api report`)
+//!   and dropped into `.ground/external-usage/*.json`
+//!
+//! Dead export results leave matching exports out of their findings and list
+//! them under `externally_used` instead, with who consumes them, so they stay
+//! visible without being flagged for removal.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Utc};
+use glob::Pattern;
+use serde::{Deserialize, Serialize};
+
+use super::graph::SymbolGraph;
+use crate::config::{GroundConfig, PublicSymbol};
+
+/// Where downstream usage reports are read from, relative to a directory
+/// being analyzed or any of its ancestors
+pub const EXTERNAL_USAGE_DIR: &str = ".ground/external-usage";
+
+/// What one downstream repo imports from this one
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExternalUsageReport {
+    /// The consuming repo (e.g. `createsomethingtoday/agency`)
+    pub source: String,
+    #[serde(default)]
+    pub generated_at: Option<DateTime<Utc>>,
+    pub symbols: Vec<PublicSymbol>,
+}
+
+impl ExternalUsageReport {
+    /// Build a report of what `graph` imports from the given packages
+    ///
+    /// Run in the downstream repo; each import of `package` or a subpath
+    /// of it becomes a symbol pinned to that module.
+    pub fn from_graph(source: &str, graph: &SymbolGraph, packages: &[String]) -> Self {
+        let mut symbols: Vec<PublicSymbol> = graph.imports.values()
+            .flatten()
+            .filter(|i| i.name != "*" && packages.iter().any(|p| is_package_or_subpath(&i.from_module, p)))
+            .map(|i| PublicSymbol { name: i.name.clone(), module: Some(i.from_module.clone()) })
+            .collect();
+        symbols.sort_by(|a, b| (&a.name, &a.module).cmp(&(&b.name, &b.module)));
+        symbols.dedup();
+        
+        Self {
+            source: source.to_string(),
+            generated_at: Some(Utc::now()),
+            symbols,
+        }
+    }
+}
+
+/// An export left out of dead export findings because something outside
+/// the repo uses it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExternallyUsedExport {
+    pub name: String,
+    pub file: PathBuf,
+    pub line: u32,
+    /// Who declared it: `.ground.yml` or a usage report's source
+    pub consumers: Vec<String>,
+}
+
+/// Externally consumed symbols, with where each declaration came from
+#[derive(Debug, Clone, Default)]
+pub struct PublicApiManifest {
+    entries: Vec<(PublicSymbol, String)>,
+}
+
+impl PublicApiManifest {
+    /// Manifest from `.ground.yml` and the usage reports found from `dir` upward
+    pub fn load(dir: &Path) -> Self {
+        let config = GroundConfig::find_in_ancestors(dir).unwrap_or_default();
+        let mut manifest = Self::from_config(&config);
+        for report in find_usage_reports(dir) {
+            manifest.add_report(report);
+        }
+        manifest
+    }
+    
+    pub fn from_config(config: &GroundConfig) -> Self {
+        Self {
+            entries: config.public_api.symbols.iter()
+                .map(|s| (s.clone(), ".ground.yml".to_string()))
+                .collect(),
+        }
+    }
+    
+    pub fn add_report(&mut self, report: ExternalUsageReport) {
+        let source = report.source;
+        self.entries.extend(report.symbols.into_iter().map(|s| (s, source.clone())));
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+    
+    /// Who consumes `name` as exported from `file` (empty if nobody declared it)
+    pub fn consumers(&self, name: &str, file: &Path) -> Vec<String> {
+        let mut package_names = HashMap::new();
+        let mut consumers: Vec<String> = self.entries.iter()
+            .filter(|(symbol, _)| name_matches(&symbol.name, name))
+            .filter(|(symbol, _)| match &symbol.module {
+                Some(module) => module_matches(module, file, &mut package_names),
+                None => true,
+            })
+            .map(|(_, source)| source.clone())
+            .collect();
+        consumers.sort();
+        consumers.dedup();
+        consumers
+    }
+    
+    /// Split dead exports into (still dead, externally used)
+    pub fn partition<T>(
+        &self,
+        dead: impl IntoIterator<Item = T>,
+        describe: impl Fn(&T) -> (&str, &Path, u32),
+    ) -> (Vec<T>, Vec<ExternallyUsedExport>) {
+        let mut still_dead = Vec::new();
+        let mut external = Vec::new();
+        for export in dead {
+            let (name, file, line) = describe(&export);
+            let consumers = self.consumers(name, file);
+            if consumers.is_empty() {
+                still_dead.push(export);
+            } else {
+                external.push(ExternallyUsedExport {
+                    name: name.to_string(),
+                    file: file.to_path_buf(),
+                    line,
+                    consumers,
+                });
+            }
+        }
+        (still_dead, external)
+    }
+}
+
+/// Usage reports in `.ground/external-usage/` of `dir` and its ancestors
+///
+/// Unreadable or malformed reports are skipped rather than failing analysis.
+pub fn find_usage_reports(dir: &Path) -> Vec<ExternalUsageReport> {
+    let mut current = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+    let mut reports = Vec::new();
+    
+    for _ in 0..10 {
+        if let Ok(entries) = std::fs::read_dir(current.join(EXTERNAL_USAGE_DIR)) {
+            let mut paths: Vec<PathBuf> = entries.filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| p.extension().is_some_and(|e| e == "json"))
+                .collect();
+            paths.sort();
+            reports.extend(paths.iter()
+                .filter_map(|p| std::fs::read_to_string(p).ok())
+                .filter_map(|c| serde_json::from_str(&c).ok()));
+        }
+        
+        match current.parent() {
+            Some(parent) if parent != current => current = parent.to_path_buf(),
+            _ => break,
+        }
+    }
+    
+    reports
+}
+
+fn name_matches(pattern: &str, name: &str) -> bool {
+    if pattern.contains('*') {
+        Pattern::new(pattern).map(|p| p.matches(name)).unwrap_or(false)
+    } else {
+        pattern == name
+    }
+}
+
+/// A module is a path glob, a path suffix, or a package name (or subpath)
+fn module_matches(module: &str, file: &Path, package_names: &mut HashMap<PathBuf, Option<String>>) -> bool {
+    let path = file.to_string_lossy();
+    if path.ends_with(module) || Pattern::new(module).map(|p| p.matches(&path)).unwrap_or(false) {
+        return true;
+    }
+    
+    let dir = file.parent().unwrap_or(file).to_path_buf();
+    let package = package_names.entry(dir.clone()).or_insert_with(|| package_name(&dir));
+    package.as_deref().is_some_and(|p| is_package_or_subpath(module, p))
+}
+
+fn is_package_or_subpath(module: &str, package: &str) -> bool {
+    module == package || module.strip_prefix(package).is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// Name from the nearest package.json
+fn package_name(dir: &Path) -> Option<String> {
+    dir.ancestors()
+        .take(8)
+        .map(|d| d.join("package.json"))
+        .find(|p| p.exists())
+        .and_then(|p| std::fs::read_to_string(p).ok())
+        .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok())
+        .and_then(|v| v.get("name").and_then(|n| n.as_str()).map(String::from))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+    
+    #[test]
+    fn test_public_api_manifest() {
+        let dir = tempdir().unwrap();
+        let pkg = dir.path().join("packages/components");
+        std::fs::create_dir_all(pkg.join("src")).unwrap();
+        std::fs::write(pkg.join("package.json"), r#"{ "name": "@create-something/components" }"#).unwrap();
+        std::fs::write(dir.path().join(".ground.yml"), r#"
+public_api:
+  symbols:
+    - formatDate
+    - name: "Canon*"
+      module: "@create-something/other"
+"#).unwrap();
+        
+        let reports = dir.path().join(EXTERNAL_USAGE_DIR);
+        std::fs::create_dir_all(&reports).unwrap();
+        let report = ExternalUsageReport {
+            source: "agency".to_string(),
+            generated_at: None,
+            symbols: vec![PublicSymbol {
+                name: "Button".to_string(),
+                module: Some("@create-something/components/ui".to_string()),
+            }],
+        };
+        std::fs::write(reports.join("agency.json"), serde_json::to_string(&report).unwrap()).unwrap();
+        
+        let manifest = PublicApiManifest::load(&pkg.join("src"));
+        let file = pkg.join("src/index.ts");
+        assert_eq!(manifest.consumers("formatDate", &file), vec![".ground.yml"]);
+        assert_eq!(manifest.consumers("Button", &file), vec!["agency"]);
+        // Pinned to a different package
+        assert!(manifest.consumers("CanonCard", &file).is_empty());
+        assert!(manifest.consumers("internalHelper", &file).is_empty());
+        
+        let dead: Vec<(&str, u32)> = vec![("formatDate", 3), ("internalHelper", 9)];
+        let (still_dead, external) = manifest.partition(dead, |(name, line)| (*name, file.as_path(), *line));
+        assert_eq!(still_dead, vec![("internalHelper", 9)]);
+        assert_eq!(external.len(), 1);
+        assert_eq!(external[0].line, 3);
+    }
+}
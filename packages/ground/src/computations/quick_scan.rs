@@ -0,0 +1,414 @@
+//! Quick Scan
+//!
+//! A full duplication and adoption audit of a large monorepo parses every
+//! file and compares every same-named function pair, which can take an hour.
+//! A quick scan looks at a seeded sample of files instead and extrapolates:
+//!
+//! - **Duplication density** - the share of files containing a function
+//!   duplicated elsewhere (as in the health score). Each sampled function is
+//!   checked against the whole repository, not just the sample, but only
+//!   files that mention its name are parsed for that.
+//! - **Token adoption** - compliant style declarations over all of them,
+//!   estimated from the sampled files' declarations.
+//!
+//! Both come with 95% confidence intervals, and the scan says whether a full
+//! audit is warranted: when an interval sits clear of its budget the answer
+//! is yes or no; when it straddles it the answer is "sample more".
+//!
+//! Files are ranked by a hash of the seed and their path relative to the
+//! root, and the top of the ranking is taken. The same seed picks the same
+//! files on any machine, and a file stays in (or out of) the sample as the
+//! repository grows around it.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::ComputationError;
+use super::function_dry::{compare_functions, extract_functions, is_test_file, ExtractedFunction};
+use super::patterns::{analyze_file, PatternConfig};
+use super::walk::source_files;
+
+/// Files functions are extracted from (as for the symbol graph)
+const FUNCTION_EXTENSIONS: &[&str] = &["ts", "tsx", "js", "jsx", "svelte"];
+
+/// Files with style declarations to measure adoption in
+const STYLE_EXTENSIONS: &[&str] = &["svelte", "tsx", "ts", "css"];
+
+/// z for a two-sided 95% interval
+const Z_95: f64 = 1.96;
+
+/// Sampling and decision parameters for a quick scan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuickScanOptions {
+    /// Fraction of files to sample (0.0 - 1.0)
+    pub sample_rate: f64,
+    /// Same seed, same sample
+    pub seed: u64,
+    /// Sample at least this many files (or all of them, if fewer)
+    pub min_files: usize,
+    /// Function similarity that counts as a duplicate
+    pub similarity_threshold: f64,
+    /// A full audit is warranted above this duplication density (0.0 - 1.0)
+    pub duplication_budget: f64,
+    /// A full audit is warranted below this token adoption (0.0 - 1.0)
+    pub adoption_floor: f64,
+    /// Sample test files too
+    pub include_tests: bool,
+}
+
+impl Default for QuickScanOptions {
+    fn default() -> Self {
+        Self {
+            sample_rate: 0.05,
+            seed: 0,
+            min_files: 30,
+            similarity_threshold: 0.80,
+            duplication_budget: 0.10,
+            adoption_floor: 0.70,
+            include_tests: false,
+        }
+    }
+}
+
+/// A sampled proportion with its 95% confidence interval
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Estimate {
+    /// Point estimate (0.0 - 1.0)
+    pub value: f64,
+    pub low: f64,
+    pub high: f64,
+    /// Files the estimate was made from
+    pub sampled: usize,
+    /// Files it extrapolates to
+    pub population: usize,
+}
+
+impl Estimate {
+    /// The estimate scaled to a count over the population, e.g. files with
+    /// duplicates, as (value, low, high)
+    pub fn extrapolate(&self) -> (f64, f64, f64) {
+        let n = self.population as f64;
+        (self.value * n, self.low * n, self.high * n)
+    }
+    
+    /// Whether the whole interval is above, below, or around `limit`
+    fn against(&self, limit: f64) -> std::cmp::Ordering {
+        if self.low > limit {
+            std::cmp::Ordering::Greater
+        } else if self.high < limit {
+            std::cmp::Ordering::Less
+        } else {
+            std::cmp::Ordering::Equal
+        }
+    }
+}
+
+/// Whether the sample justifies a full audit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditVerdict {
+    /// An interval is clear of its budget on the wrong side
+    Warranted,
+    /// Every interval is clear of its budget on the right side
+    NotWarranted,
+    /// An interval straddles its budget; sample more to decide
+    Inconclusive,
+}
+
+impl AuditVerdict {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditVerdict::Warranted => "warranted",
+            AuditVerdict::NotWarranted => "not_warranted",
+            AuditVerdict::Inconclusive => "inconclusive",
+        }
+    }
+}
+
+/// Result of a quick scan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuickScanReport {
+    pub directory: PathBuf,
+    pub options: QuickScanOptions,
+    /// Source files functions could be extracted from
+    pub files_total: usize,
+    pub files_sampled: usize,
+    /// Files parsed, sampled or not: sampled ones, plus any mentioning the
+    /// name of a sampled function
+    pub files_parsed: usize,
+    pub functions_sampled: usize,
+    pub duplication: Estimate,
+    /// None when the sample has no style declarations
+    pub adoption: Option<Estimate>,
+    pub verdict: AuditVerdict,
+    /// Why, one line per metric
+    pub reasons: Vec<String>,
+    pub duration_ms: u64,
+    pub computed_at: DateTime<Utc>,
+}
+
+/// Sample a directory and estimate its duplication density and token
+/// adoption
+pub fn quick_scan(root: &Path, options: &QuickScanOptions) -> Result<QuickScanReport, ComputationError> {
+    if !root.is_dir() {
+        return Err(ComputationError::FileNotFound(root.to_path_buf()));
+    }
+    if !(options.sample_rate > 0.0 && options.sample_rate <= 1.0) {
+        return Err(ComputationError::InvalidConfig(format!(
+            "sample rate must be in (0, 1], got {}", options.sample_rate
+        )));
+    }
+    let started = Instant::now();
+    let in_scope = |files: Vec<PathBuf>| -> Vec<PathBuf> {
+        files.into_iter().filter(|f| options.include_tests || !is_test_file(f)).collect()
+    };
+    
+    // Duplication: is each sampled file home to a function duplicated anywhere?
+    let population = in_scope(source_files(root, FUNCTION_EXTENSIONS));
+    let sample = sample_files(root, &population, options);
+    let sampled: HashSet<&PathBuf> = sample.iter().collect();
+    
+    let mut parsed: HashMap<PathBuf, Vec<ExtractedFunction>> = HashMap::new();
+    for file in &sample {
+        parsed.insert(file.clone(), extract_functions(file).unwrap_or_default());
+    }
+    let names: HashSet<String> = parsed.values().flatten().map(|f| f.name.clone()).collect();
+    let functions_sampled = parsed.values().map(Vec::len).sum();
+    
+    // Only files mentioning a sampled name can hold its duplicate
+    let mut candidates: HashMap<&str, Vec<&PathBuf>> = HashMap::new();
+    for file in population.iter().filter(|f| !sampled.contains(f)) {
+        let Ok(source) = fs::read_to_string(file) else { continue };
+        let mentioned: HashSet<&str> = identifiers(&source).filter(|id| names.contains(*id)).collect();
+        for name in mentioned {
+            let name = names.get(name).expect("filtered to sampled names");
+            candidates.entry(name.as_str()).or_default().push(file);
+        }
+    }
+    for file in candidates.values().flatten() {
+        if !parsed.contains_key(*file) {
+            parsed.insert((*file).clone(), extract_functions(file).unwrap_or_default());
+        }
+    }
+    
+    let has_duplicate = |file: &PathBuf, function: &ExtractedFunction| -> bool {
+        let others = sample.iter()
+            .chain(candidates.get(function.name.as_str()).into_iter().flatten().copied())
+            .filter(|other| *other != file);
+        others
+            .flat_map(|other| parsed.get(other).into_iter().flatten())
+            .any(|f| f.name == function.name && compare_functions(function, f) >= options.similarity_threshold)
+    };
+    let duplicated = sample.iter()
+        .filter(|file| parsed[*file].iter().any(|function| has_duplicate(file, function)))
+        .count();
+    let duplication = proportion(duplicated, sample.len(), population.len());
+    
+    // Adoption: compliant over total declarations in the sampled style files
+    let style_population = in_scope(source_files(root, STYLE_EXTENSIONS));
+    let config = PatternConfig::default();
+    let declarations: Vec<(f64, f64)> = sample_files(root, &style_population, options).iter()
+        .filter_map(|file| analyze_file(file, &config).ok())
+        .map(|e| (e.metrics.compliant_declarations as f64, e.metrics.total_declarations as f64))
+        .collect();
+    let adoption = ratio(&declarations, style_population.len());
+    
+    let (verdict, reasons) = decide(&duplication, adoption.as_ref(), options);
+    
+    Ok(QuickScanReport {
+        directory: root.to_path_buf(),
+        options: options.clone(),
+        files_total: population.len(),
+        files_sampled: sample.len(),
+        files_parsed: parsed.len(),
+        functions_sampled,
+        duplication,
+        adoption,
+        verdict,
+        reasons,
+        duration_ms: started.elapsed().as_millis() as u64,
+        computed_at: Utc::now(),
+    })
+}
+
+/// The files ranked first by their seeded hash, `sample_rate` of them (but
+/// at least `min_files`)
+fn sample_files(root: &Path, files: &[PathBuf], options: &QuickScanOptions) -> Vec<PathBuf> {
+    let size = ((files.len() as f64 * options.sample_rate).ceil() as usize)
+        .max(options.min_files)
+        .min(files.len());
+    let mut ranked: Vec<(u64, &PathBuf)> = files.iter()
+        .map(|file| (sample_rank(options.seed, file.strip_prefix(root).unwrap_or(file)), file))
+        .collect();
+    ranked.sort();
+    let mut sample: Vec<PathBuf> = ranked.into_iter().take(size).map(|(_, f)| f.clone()).collect();
+    sample.sort();
+    sample
+}
+
+fn sample_rank(seed: u64, relative: &Path) -> u64 {
+    let key = format!("{}:{}", seed, relative.to_string_lossy().replace('\\', "/"));
+    let digest = Sha256::digest(key.as_bytes());
+    u64::from_be_bytes(digest[..8].try_into().expect("digest is 32 bytes"))
+}
+
+fn identifiers(source: &str) -> impl Iterator<Item = &str> {
+    source
+        .split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+        .filter(|s| !s.is_empty())
+}
+
+/// `hits` of `n` files sampled without replacement from `population`, with
+/// a Wilson score interval shrunk by the finite population correction
+fn proportion(hits: usize, n: usize, population: usize) -> Estimate {
+    if n == 0 {
+        return Estimate { value: 0.0, low: 0.0, high: 0.0, sampled: 0, population };
+    }
+    let (k, n_f) = (hits as f64, n as f64);
+    let p = k / n_f;
+    let z2 = Z_95 * Z_95 * fpc(n, population);
+    let denominator = 1.0 + z2 / n_f;
+    let center = (p + z2 / (2.0 * n_f)) / denominator;
+    let half = (z2 * (p * (1.0 - p) / n_f + z2 / (4.0 * n_f * n_f))).sqrt() / denominator;
+    Estimate {
+        value: p,
+        low: (center - half).clamp(0.0, p),
+        high: (center + half).clamp(p, 1.0),
+        sampled: n,
+        population,
+    }
+}
+
+/// Ratio estimate of Σ part / Σ whole over sampled files, given as
+/// (part, whole) pairs; files are clusters of declarations, so the interval
+/// comes from the spread between files rather than between declarations
+fn ratio(samples: &[(f64, f64)], population: usize) -> Option<Estimate> {
+    let n = samples.len();
+    let whole: f64 = samples.iter().map(|(_, w)| w).sum();
+    if whole == 0.0 {
+        return None;
+    }
+    let r = samples.iter().map(|(p, _)| p).sum::<f64>() / whole;
+    let half = if n > 1 {
+        let mean_whole = whole / n as f64;
+        let spread = samples.iter().map(|(p, w)| (p - r * w).powi(2)).sum::<f64>() / (n - 1) as f64;
+        Z_95 * (fpc(n, population) * spread / n as f64).sqrt() / mean_whole
+    } else {
+        0.0
+    };
+    Some(Estimate {
+        value: r,
+        low: (r - half).clamp(0.0, 1.0),
+        high: (r + half).clamp(0.0, 1.0),
+        sampled: n,
+        population,
+    })
+}
+
+/// Finite population correction: no uncertainty left once everything is sampled
+fn fpc(n: usize, population: usize) -> f64 {
+    if population <= 1 || n >= population {
+        0.0
+    } else {
+        (population - n) as f64 / (population - 1) as f64
+    }
+}
+
+fn decide(
+    duplication: &Estimate,
+    adoption: Option<&Estimate>,
+    options: &QuickScanOptions,
+) -> (AuditVerdict, Vec<String>) {
+    use std::cmp::Ordering::*;
+    let pct = |x: f64| format!("{:.1}%", x * 100.0);
+    let range = |e: &Estimate| format!("{} ({}-{})", pct(e.value), pct(e.low), pct(e.high));
+    
+    let mut verdicts = Vec::new();
+    let mut reasons = Vec::new();
+    
+    let budget = pct(options.duplication_budget);
+    let (verdict, reason) = match duplication.against(options.duplication_budget) {
+        Greater => (AuditVerdict::Warranted, format!("duplication density {} is above the {} budget", range(duplication), budget)),
+        Less => (AuditVerdict::NotWarranted, format!("duplication density {} is under the {} budget", range(duplication), budget)),
+        Equal => (AuditVerdict::Inconclusive, format!("duplication density {} straddles the {} budget", range(duplication), budget)),
+    };
+    verdicts.push(verdict);
+    reasons.push(reason);
+    
+    if let Some(adoption) = adoption {
+        let floor = pct(options.adoption_floor);
+        let (verdict, reason) = match adoption.against(options.adoption_floor) {
+            Less => (AuditVerdict::Warranted, format!("token adoption {} is below the {} floor", range(adoption), floor)),
+            Greater => (AuditVerdict::NotWarranted, format!("token adoption {} is above the {} floor", range(adoption), floor)),
+            Equal => (AuditVerdict::Inconclusive, format!("token adoption {} straddles the {} floor", range(adoption), floor)),
+        };
+        verdicts.push(verdict);
+        reasons.push(reason);
+    }
+    
+    let verdict = if verdicts.contains(&AuditVerdict::Warranted) {
+        AuditVerdict::Warranted
+    } else if verdicts.contains(&AuditVerdict::Inconclusive) {
+        AuditVerdict::Inconclusive
+    } else {
+        AuditVerdict::NotWarranted
+    };
+    (verdict, reasons)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+    
+    #[test]
+    fn test_quick_scan_samples_reproducibly_and_finds_duplicates_outside_the_sample() {
+        let dir = tempdir().unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir_all(&src).unwrap();
+        let body = "export function formatPrice(amount: number) {\n  const rounded = Math.round(amount * 100) / 100;\n  return `$${rounded.toFixed(2)}`;\n}\n";
+        for i in 0..40 {
+            let source = if i % 2 == 0 {
+                body.to_string()
+            } else {
+                format!("export function unique{i}(x: number) {{\n  return x * {i} + {i};\n}}\n")
+            };
+            fs::write(src.join(format!("m{i:02}.ts")), source).unwrap();
+        }
+        
+        let options = QuickScanOptions { sample_rate: 0.25, min_files: 4, seed: 7, ..Default::default() };
+        let report = quick_scan(dir.path(), &options).unwrap();
+        assert_eq!(report.files_total, 40);
+        assert_eq!(report.files_sampled, 10);
+        
+        // Same seed, same files and estimate
+        let again = quick_scan(dir.path(), &options).unwrap();
+        assert_eq!(again.duplication.value, report.duplication.value);
+        let other = QuickScanOptions { seed: 8, ..options.clone() };
+        assert_ne!(
+            sample_files(dir.path(), &source_files(dir.path(), FUNCTION_EXTENSIONS), &options),
+            sample_files(dir.path(), &source_files(dir.path(), FUNCTION_EXTENSIONS), &other),
+        );
+        
+        // Every sampled formatPrice has twins, sampled or not
+        let sample = sample_files(dir.path(), &source_files(dir.path(), FUNCTION_EXTENSIONS), &options);
+        let expected = sample.iter()
+            .filter(|f| fs::read_to_string(f).unwrap().contains("formatPrice"))
+            .count() as f64 / sample.len() as f64;
+        assert_eq!(report.duplication.value, expected);
+        assert!(report.duplication.low <= 0.5 && 0.5 <= report.duplication.high);
+        assert!(report.duplication.low > 0.0 && report.duplication.high < 1.0);
+        assert_eq!(report.verdict, AuditVerdict::Warranted);
+        
+        // Sampling everything leaves no uncertainty
+        let full = quick_scan(dir.path(), &QuickScanOptions { sample_rate: 1.0, ..options }).unwrap();
+        assert_eq!(full.duplication.value, 0.5);
+        assert_eq!(full.duplication.low, full.duplication.high);
+        assert_eq!(full.duplication.extrapolate().0, 20.0);
+    }
+}
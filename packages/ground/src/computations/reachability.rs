@@ -21,6 +21,9 @@ use std::path::{Path, PathBuf};
 use std::fs;
 use serde::{Serialize, Deserialize};
 
+use super::generated::{is_generated_dir, is_generated_file};
+use super::walk::source_files;
+
 /// Entry point types
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum EntryPointType {
@@ -248,8 +251,7 @@ fn find_entries_recursive(
         
         if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
             // Skip hidden and generated
-            if name.starts_with('.') || 
-               matches!(name, "node_modules" | "dist" | "build" | ".svelte-kit" | "target") {
+            if name.starts_with('.') || is_generated_dir(name) {
                 continue;
             }
         }
@@ -260,7 +262,7 @@ fn find_entries_recursive(
         
         if path.is_dir() {
             find_entries_recursive(root, &path, entries)?;
-        } else if path.is_file() {
+        } else if path.is_file() && !is_generated_file(&path) {
             if let Some(entry) = detect_entry_point(&path) {
                 entries.push(entry);
             }
@@ -436,35 +438,7 @@ fn find_wrangler_entry(wrangler_path: &Path, directory: &Path) -> std::io::Resul
 
 /// Collect all TypeScript/JavaScript modules in a directory
 fn collect_modules(dir: &Path, modules: &mut HashSet<PathBuf>) -> std::io::Result<()> {
-    let entries = match fs::read_dir(dir) {
-        Ok(e) => e,
-        Err(_) => return Ok(()),
-    };
-    
-    for entry in entries.flatten() {
-        let path = entry.path();
-        
-        if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-            if name.starts_with('.') ||
-               matches!(name, "node_modules" | "dist" | "build" | ".svelte-kit" | "target" | "venv" | ".venv") {
-                continue;
-            }
-        }
-        
-        if !path.exists() {
-            continue;
-        }
-        
-        if path.is_dir() {
-            collect_modules(&path, modules)?;
-        } else if path.is_file() {
-            let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
-            if matches!(ext, "ts" | "tsx" | "js" | "jsx" | "svelte") {
-                modules.insert(path);
-            }
-        }
-    }
-    
+    modules.extend(source_files(dir, &["ts", "tsx", "js", "jsx", "svelte"]));
     Ok(())
 }
 
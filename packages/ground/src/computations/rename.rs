@@ -0,0 +1,538 @@
+//! Rename Impact
+//!
+//! What an agent needs to know before renaming an exported symbol:
+//!
+//! - **Sites**: every line mentioning it, classified as a definition,
+//!   import, reference, type-only use, string, comment, or config entry.
+//!   Editor renames cover the first four; the rest break silently.
+//! - **Dynamic usage**: places the name is looked up at runtime
+//!   (`handlers['name']`, `ns[key]` on a namespace import, `export *`
+//!   re-exports) that no text search can fully enumerate
+//! - **Consumers**: files importing it, directly or through barrels,
+//!   grouped by package, with packages other than the defining one flagged
+//!
+//! The usage counts come from `count_usages` and are kept on the result,
+//! so the caller can record them as evidence for later claims.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::fs;
+use serde::{Serialize, Deserialize};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::ComputationError;
+use super::barrels::star_reexports;
+use super::dependencies::package_of;
+use super::generated::{is_generated_dir, is_generated_file};
+use super::graph::SymbolGraph;
+use super::usage::{count_usages, UsageEvidence, UsageType};
+
+/// Non-code files that can name a symbol (routes, handler maps, fixtures)
+const CONFIG_EXTENSIONS: &[&str] = &["json", "yaml", "yml", "toml", "html"];
+
+/// How a line mentions the symbol
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SiteKind {
+    Definition,
+    Import,
+    Reference,
+    TypeOnly,
+    /// Inside a string literal only
+    String,
+    /// Inside a comment only
+    Comment,
+    /// In a JSON/YAML/TOML/HTML file
+    Config,
+}
+
+impl SiteKind {
+    /// Whether an editor's rename refactoring would update it
+    pub fn is_semantic(&self) -> bool {
+        matches!(self, SiteKind::Definition | SiteKind::Import | SiteKind::Reference | SiteKind::TypeOnly)
+    }
+}
+
+/// A line that mentions the symbol
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenameSite {
+    pub file: PathBuf,
+    pub line: u32,
+    pub column: u32,
+    pub kind: SiteKind,
+    pub context: String,
+}
+
+/// Why a rename might miss a use
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DynamicUsageKind {
+    /// A string that is exactly the name (a lookup key, an event name)
+    NameString,
+    /// `obj['name']`
+    ComputedAccess,
+    /// `ns[key]` on a namespace import of the defining module
+    NamespaceLookup,
+    /// `export *` from the defining module
+    StarReexport,
+}
+
+/// A use a rename can't follow statically
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DynamicUsage {
+    pub file: PathBuf,
+    pub line: Option<u32>,
+    pub kind: DynamicUsageKind,
+    pub message: String,
+}
+
+/// Files in one package that import the symbol
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackageConsumers {
+    /// `name` from the package's package.json
+    pub package: Option<String>,
+    pub package_dir: Option<PathBuf>,
+    pub files: Vec<PathBuf>,
+    /// A different package from the one defining the symbol
+    pub cross_package: bool,
+}
+
+/// Everything a rename of `symbol` would touch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenameImpact {
+    pub id: Uuid,
+    pub symbol: String,
+    pub search_path: PathBuf,
+    /// Files exporting it (not re-exporting)
+    pub defined_in: Vec<PathBuf>,
+    /// Barrels passing it on, by name or `export *`
+    pub reexported_by: Vec<PathBuf>,
+    pub sites: Vec<RenameSite>,
+    pub dynamic: Vec<DynamicUsage>,
+    pub consumers: Vec<PackageConsumers>,
+    /// Usage counts over the same search path
+    pub usage: UsageEvidence,
+    pub computed_at: DateTime<Utc>,
+}
+
+impl RenameImpact {
+    /// Sites an editor rename wouldn't update
+    pub fn textual_sites(&self) -> impl Iterator<Item = &RenameSite> {
+        self.sites.iter().filter(|s| !s.kind.is_semantic())
+    }
+    
+    /// Packages other than the defining one that import it
+    pub fn cross_package_consumers(&self) -> impl Iterator<Item = &PackageConsumers> {
+        self.consumers.iter().filter(|c| c.cross_package)
+    }
+    
+    /// No dynamic uses, strings, comments, or config entries: an editor
+    /// rename plus updating the consumers covers everything
+    pub fn is_mechanical(&self) -> bool {
+        self.dynamic.is_empty() && self.textual_sites().next().is_none()
+    }
+}
+
+/// Find everything renaming `symbol` would touch
+///
+/// `path` is the defining file or a directory to search. Consumers come from
+/// `graph`, which should cover the whole workspace (a symbol's importers
+/// can live in any package); one is built at the workspace root when not
+/// given.
+pub fn analyze_rename_impact(symbol: &str, path: &Path, graph: Option<&SymbolGraph>) -> Result<RenameImpact, ComputationError> {
+    let path = path.canonicalize().map_err(|_| ComputationError::FileNotFound(path.to_path_buf()))?;
+    let built;
+    let graph = match graph {
+        Some(graph) => graph,
+        None => {
+            built = SymbolGraph::build(&workspace_root(&path), None)
+                .map_err(|message| ComputationError::ParseError { file: path.clone(), message })?;
+            &built
+        }
+    };
+    let search_path = if path.is_file() { graph.root_dir.clone() } else { path.clone() };
+    
+    let usage = count_usages(symbol, &search_path)?;
+    let mut sites = Vec::new();
+    let mut dynamic = Vec::new();
+    for location in &usage.locations {
+        let Some(kind) = classify_line(&location.context, symbol, location.usage_type, &location.file, location.line, &mut dynamic) else {
+            continue;
+        };
+        sites.push(RenameSite {
+            file: location.file.clone(),
+            line: location.line,
+            column: location.column,
+            kind,
+            context: location.context.clone(),
+        });
+    }
+    collect_config_sites(symbol, &search_path, &mut sites);
+    
+    // Who exports it: the defining files, then anything re-exporting them
+    let files: HashSet<PathBuf> = graph.files.iter().cloned().collect();
+    let defined_in: Vec<PathBuf> = graph.exports.iter()
+        .filter(|(file, exports)| {
+            (!path.is_file() || **file == path) && exports.iter().any(|e| e.name == symbol && !e.is_reexport)
+        })
+        .map(|(file, _)| file.clone())
+        .collect();
+    let stars: Vec<(PathBuf, PathBuf, usize)> = graph.files.iter()
+        .filter_map(|file| Some((file, fs::read_to_string(file).ok()?)))
+        .flat_map(|(file, content)| {
+            star_reexports(&content).into_iter()
+                .filter(|(namespace, _, _)| namespace.is_none())
+                .filter_map(|(_, spec, line)| Some((file.clone(), graph.resolve_module(&spec, file, &files)?, line)))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    
+    let mut exporters: HashSet<PathBuf> = defined_in.iter().cloned().collect();
+    loop {
+        let before = exporters.len();
+        for (file, exports) in &graph.exports {
+            let named = exports.iter()
+                .filter(|e| e.is_reexport && e.name == symbol)
+                .filter_map(|e| e.source_module.as_deref())
+                .any(|spec| graph.resolve_module(spec, file, &files).is_some_and(|t| exporters.contains(&t)));
+            if named {
+                exporters.insert(file.clone());
+            }
+        }
+        for (file, target, _) in &stars {
+            if exporters.contains(target) {
+                exporters.insert(file.clone());
+            }
+        }
+        if exporters.len() == before {
+            break;
+        }
+    }
+    for (file, target, line) in stars.iter().filter(|(_, t, _)| exporters.contains(t)) {
+        dynamic.push(DynamicUsage {
+            file: file.clone(),
+            line: Some(*line as u32),
+            kind: DynamicUsageKind::StarReexport,
+            message: format!(
+                "Re-exports {} with `export *`, so the new name becomes part of this file's API without it changing",
+                target.display()
+            ),
+        });
+    }
+    
+    let mut packages = PackageLookup::default();
+    let exporter_packages: HashSet<String> = exporters.iter()
+        .filter_map(|f| packages.of(f).1)
+        .collect();
+    let imports_exporter = |spec: &str, importer: &Path| match graph.resolve_module(spec, importer, &files) {
+        Some(target) => exporters.contains(&target),
+        None => package_of(spec).is_some_and(|p| exporter_packages.contains(p)),
+    };
+    
+    let mut consumer_files: HashSet<PathBuf> = HashSet::new();
+    for (file, imports) in &graph.imports {
+        if !exporters.contains(file) && imports.iter().any(|i| i.name == symbol && imports_exporter(&i.from_module, file)) {
+            consumer_files.insert(file.clone());
+        }
+    }
+    
+    // Namespace imports (`import * as ns`) aren't named imports
+    for (file, specifiers) in &graph.module_imports {
+        if exporters.contains(file) || !specifiers.iter().any(|s| imports_exporter(s, file)) {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(file) else { continue };
+        for namespace in namespace_imports(&content).into_iter().filter(|(_, spec)| imports_exporter(spec, file)).map(|(ns, _)| ns) {
+            if content.contains(&format!("{}.{}", namespace, symbol)) {
+                consumer_files.insert(file.clone());
+            }
+            if let Some(line) = content.lines().position(|l| l.contains(&format!("{}[", namespace))) {
+                consumer_files.insert(file.clone());
+                dynamic.push(DynamicUsage {
+                    file: file.clone(),
+                    line: Some(line as u32 + 1),
+                    kind: DynamicUsageKind::NamespaceLookup,
+                    message: format!("Indexes the namespace import '{}' dynamically; it may reach {} by name", namespace, symbol),
+                });
+            }
+        }
+    }
+    
+    let defining_packages: HashSet<Option<PathBuf>> = defined_in.iter().map(|f| packages.of(f).0).collect();
+    let mut grouped: BTreeMap<Option<PathBuf>, PackageConsumers> = BTreeMap::new();
+    for file in consumer_files {
+        let (package_dir, package) = packages.of(&file);
+        grouped.entry(package_dir.clone())
+            .or_insert_with(|| PackageConsumers {
+                cross_package: !defining_packages.contains(&package_dir),
+                package,
+                package_dir,
+                files: Vec::new(),
+            })
+            .files.push(file);
+    }
+    let mut consumers: Vec<PackageConsumers> = grouped.into_values().collect();
+    for group in &mut consumers {
+        group.files.sort();
+    }
+    
+    let mut reexported_by: Vec<PathBuf> = exporters.into_iter().filter(|f| !defined_in.contains(f)).collect();
+    reexported_by.sort();
+    let mut defined_in = defined_in;
+    defined_in.sort();
+    sites.sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
+    
+    Ok(RenameImpact {
+        id: Uuid::new_v4(),
+        symbol: symbol.to_string(),
+        search_path,
+        defined_in,
+        reexported_by,
+        sites,
+        dynamic,
+        consumers,
+        usage,
+        computed_at: Utc::now(),
+    })
+}
+
+/// The monorepo root above `path` (pnpm workspace or git root), falling
+/// back to its nearest project root
+pub fn workspace_root(path: &Path) -> PathBuf {
+    path.ancestors()
+        .find(|d| d.join("pnpm-workspace.yaml").is_file() || d.join(".git").exists())
+        .map(Path::to_path_buf)
+        .or_else(|| super::find_project_root(&path.join("_")).ok())
+        .unwrap_or_else(|| path.to_path_buf())
+}
+
+/// Where on a line the symbol is
+enum Region {
+    Code,
+    Comment,
+    /// In a string literal; `exact` if the string is just the name, and
+    /// `computed` if it's inside `[...]`
+    String { exact: bool, computed: bool },
+}
+
+/// The kind of site a line is, or None if the symbol only appears inside a
+/// longer identifier. Exact-name strings are noted as dynamic usage.
+fn classify_line(
+    line: &str,
+    symbol: &str,
+    usage_type: UsageType,
+    file: &Path,
+    line_number: u32,
+    dynamic: &mut Vec<DynamicUsage>,
+) -> Option<SiteKind> {
+    let regions: Vec<Region> = word_positions(line, symbol).into_iter().map(|p| locate(line, p, symbol)).collect();
+    if regions.is_empty() {
+        return None;
+    }
+    
+    for region in &regions {
+        if let Region::String { exact: true, computed } = region {
+            let (kind, message) = if *computed {
+                (DynamicUsageKind::ComputedAccess, format!("Looks up '{}' with a computed property", symbol))
+            } else {
+                (DynamicUsageKind::NameString, format!("Names '{}' in a string (lookup key, route, or event?)", symbol))
+            };
+            dynamic.push(DynamicUsage { file: file.to_path_buf(), line: Some(line_number), kind, message });
+        }
+    }
+    
+    let kind = if regions.iter().any(|r| matches!(r, Region::Code)) {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("import ") || (trimmed.starts_with("export {") && trimmed.contains(" from ")) {
+            SiteKind::Import
+        } else {
+            match usage_type {
+                UsageType::Definition => SiteKind::Definition,
+                UsageType::TypeOnly => SiteKind::TypeOnly,
+                UsageType::Usage => SiteKind::Reference,
+            }
+        }
+    } else if regions.iter().any(|r| matches!(r, Region::String { .. })) {
+        SiteKind::String
+    } else {
+        SiteKind::Comment
+    };
+    Some(kind)
+}
+
+/// Byte offsets where `symbol` appears as a whole identifier
+fn word_positions(line: &str, symbol: &str) -> Vec<usize> {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_' || c == '$';
+    line.match_indices(symbol)
+        .filter(|(pos, _)| {
+            let before = line[..*pos].chars().next_back();
+            let after = line[pos + symbol.len()..].chars().next();
+            !before.is_some_and(is_ident) && !after.is_some_and(is_ident)
+        })
+        .map(|(pos, _)| pos)
+        .collect()
+}
+
+/// What the byte at `pos` sits in: code, a comment, or a string literal
+fn locate(line: &str, pos: usize, symbol: &str) -> Region {
+    let trimmed = line.trim_start();
+    if trimmed.starts_with("/*") || trimmed.starts_with('*') || trimmed.starts_with('#') {
+        return Region::Comment;
+    }
+    
+    let bytes = line.as_bytes();
+    let mut quote: Option<(u8, usize)> = None;
+    let mut i = 0;
+    while i < pos {
+        let b = bytes[i];
+        match quote {
+            Some(_) if b == b'\\' => i += 1,
+            Some((q, _)) if b == q => quote = None,
+            Some(_) => {}
+            None if b == b'/' && bytes.get(i + 1) == Some(&b'/') => return Region::Comment,
+            None if matches!(b, b'\'' | b'"' | b'`') => quote = Some((b, i)),
+            None => {}
+        }
+        i += 1;
+    }
+    
+    match quote {
+        None => Region::Code,
+        // `${symbol}` inside a template literal is code
+        Some((b'`', open)) if line[open..pos].rfind("${").is_some_and(|s| !line[open + s..pos].contains('}')) => Region::Code,
+        Some((q, open)) => {
+            let close = line[pos..].find(q as char).map_or(line.len(), |c| pos + c);
+            Region::String {
+                exact: &line[open + 1..close] == symbol,
+                computed: line[..open].trim_end().ends_with('['),
+            }
+        }
+    }
+}
+
+/// Mentions in config files, which `count_usages` doesn't read
+fn collect_config_sites(symbol: &str, dir: &Path, sites: &mut Vec<RenameSite>) {
+    if dir.is_file() {
+        return;
+    }
+    let Ok(entries) = fs::read_dir(dir) else { return };
+    for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        if name.starts_with('.') || is_generated_dir(name) || name.ends_with("-lock.json") || name.ends_with(".lock") {
+            continue;
+        }
+        if path.is_dir() {
+            collect_config_sites(symbol, &path, sites);
+            continue;
+        }
+        
+        let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        if !CONFIG_EXTENSIONS.contains(&ext) || is_generated_file(&path) {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(&path) else { continue };
+        for (i, line) in content.lines().enumerate() {
+            if let Some(column) = word_positions(line, symbol).first() {
+                sites.push(RenameSite {
+                    file: path.clone(),
+                    line: i as u32 + 1,
+                    column: *column as u32,
+                    kind: SiteKind::Config,
+                    context: line.trim().to_string(),
+                });
+            }
+        }
+    }
+}
+
+/// `import * as ns from 'spec'`: (ns, spec)
+fn namespace_imports(content: &str) -> Vec<(String, String)> {
+    content.lines()
+        .filter_map(|line| {
+            let rest = line.trim().strip_prefix("import * as ")?;
+            let (namespace, spec) = rest.split_once(" from ")?;
+            let spec = spec.trim().trim_end_matches(';').trim_matches(|c| c == '\'' || c == '"');
+            Some((namespace.trim().to_string(), spec.to_string()))
+        })
+        .collect()
+}
+
+/// Nearest package.json for files, cached per directory
+#[derive(Default)]
+struct PackageLookup {
+    cache: HashMap<PathBuf, (Option<PathBuf>, Option<String>)>,
+}
+
+impl PackageLookup {
+    /// (package dir, package name)
+    fn of(&mut self, file: &Path) -> (Option<PathBuf>, Option<String>) {
+        let Some(dir) = file.parent() else { return (None, None) };
+        if let Some(hit) = self.cache.get(dir) {
+            return hit.clone();
+        }
+        
+        let package_dir = dir.ancestors().find(|d| d.join("package.json").is_file()).map(Path::to_path_buf);
+        let name = package_dir.as_ref()
+            .and_then(|d| fs::read_to_string(d.join("package.json")).ok())
+            .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok())
+            .and_then(|m| m.get("name")?.as_str().map(str::to_string));
+        let result = (package_dir, name);
+        self.cache.insert(dir.to_path_buf(), result.clone());
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+    
+    #[test]
+    fn test_rename_impact() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        fs::write(root.join("pnpm-workspace.yaml"), "packages: ['packages/*']\n").unwrap();
+        for (file, content) in [
+            ("packages/utils/package.json", r#"{ "name": "@acme/utils" }"#),
+            ("packages/utils/src/dates.ts", "// formatDate renders ISO dates\nexport function formatDate(d: Date) { return d.toISOString(); }\n"),
+            ("packages/utils/src/index.ts", "export * from './dates';\n"),
+            ("packages/utils/src/local.ts", "import { formatDate } from './dates';\nconst label = `${formatDate(new Date())}`;\n"),
+            ("packages/web/package.json", r#"{ "name": "web" }"#),
+            ("packages/web/src/page.ts", "import { formatDate } from '@acme/utils';\nconst handlers = { formatDate };\nhandlers['formatDate'](new Date());\n"),
+            ("packages/web/src/all.ts", "import * as utils from '../../utils/src/dates';\nexport const pick = (k: string) => utils[k];\n"),
+            ("packages/web/routes.json", r#"{ "handler": "formatDate" }"#),
+        ] {
+            let path = root.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        
+        let impact = analyze_rename_impact("formatDate", &root.join("packages/utils/src/dates.ts"), None).unwrap();
+        assert_eq!(impact.search_path, root);
+        assert_eq!(impact.defined_in, vec![root.join("packages/utils/src/dates.ts")]);
+        assert_eq!(impact.reexported_by, vec![root.join("packages/utils/src/index.ts")]);
+        
+        let kind_at = |file: &str, line: u32| impact.sites.iter().find(|s| s.file.ends_with(file) && s.line == line).map(|s| s.kind);
+        assert_eq!(kind_at("dates.ts", 1), Some(SiteKind::Comment));
+        assert_eq!(kind_at("dates.ts", 2), Some(SiteKind::Definition));
+        assert_eq!(kind_at("local.ts", 1), Some(SiteKind::Import));
+        assert_eq!(kind_at("local.ts", 2), Some(SiteKind::Reference));
+        assert_eq!(kind_at("page.ts", 2), Some(SiteKind::Reference));
+        assert_eq!(kind_at("page.ts", 3), Some(SiteKind::String));
+        assert_eq!(kind_at("routes.json", 1), Some(SiteKind::Config));
+        assert!(!impact.is_mechanical());
+        
+        let kinds: Vec<DynamicUsageKind> = impact.dynamic.iter().map(|d| d.kind).collect();
+        assert!(kinds.contains(&DynamicUsageKind::ComputedAccess));
+        assert!(kinds.contains(&DynamicUsageKind::StarReexport));
+        assert!(kinds.contains(&DynamicUsageKind::NamespaceLookup));
+        
+        // web imports it by package name, through the barrel
+        let web = impact.cross_package_consumers().find(|c| c.package.as_deref() == Some("web")).unwrap();
+        assert_eq!(web.files, vec![root.join("packages/web/src/all.ts"), root.join("packages/web/src/page.ts")]);
+        let utils = impact.consumers.iter().find(|c| c.package.as_deref() == Some("@acme/utils")).unwrap();
+        assert!(!utils.cross_package);
+        assert_eq!(utils.files, vec![root.join("packages/utils/src/local.ts")]);
+    }
+}
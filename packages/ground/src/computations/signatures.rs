@@ -0,0 +1,414 @@
+//! Signature Clusters
+//!
+//! Duplicate detection needs two bodies to match. Convergent reinvention
+//! gets there later: each package grows its own `formatDate`, `fmtDate` or
+//! `dateFormat`, with different bodies, and by the time they're copies of
+//! each other every caller has picked one.
+//!
+//! This clusters exported functions whose names mean the same thing and
+//! whose signatures line up, across packages, regardless of their bodies:
+//!
+//! - Names are split into words (`fmtDate` → `fmt`, `date`), abbreviations
+//!   and common synonyms are expanded (`fmt` → `format`, `fetch` → `get`),
+//!   plurals are folded, and word order is ignored, so `formatDate`,
+//!   `fmtDate` and `dateFormat` share a key
+//! - Members must take the same number of parameters, and declared return
+//!   types must agree
+//!
+//! Each cluster suggests a canonical API: the most common name, preferring
+//! the unabbreviated spelling, from the package that already has it.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::fs;
+use serde::{Serialize, Deserialize};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::function_dry::{extract_functions, is_test_file};
+
+/// Options for signature clustering
+#[derive(Debug, Clone)]
+pub struct SignatureOptions {
+    /// Include test files
+    pub include_tests: bool,
+    /// Only report clusters spanning more than one package
+    pub cross_package: bool,
+}
+
+impl Default for SignatureOptions {
+    fn default() -> Self {
+        Self {
+            include_tests: false,
+            cross_package: true,
+        }
+    }
+}
+
+/// An exported function, as far as its callers can see
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedSignature {
+    pub name: String,
+    pub file: PathBuf,
+    pub line: usize,
+    /// Package it belongs to (`name` from the nearest package.json, or its directory)
+    pub package: String,
+    pub parameters: Vec<String>,
+    pub return_type: Option<String>,
+    pub is_async: bool,
+    /// Content hash of the function, to tell variants from copies
+    pub body_hash: String,
+}
+
+impl ExportedSignature {
+    /// `name(a, b): T`
+    pub fn signature(&self) -> String {
+        let mut signature = format!("{}({})", self.name, self.parameters.join(", "));
+        if let Some(return_type) = &self.return_type {
+            signature.push_str(": ");
+            signature.push_str(return_type);
+        }
+        signature
+    }
+}
+
+/// Exported functions that look like the same API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignatureCluster {
+    /// Normalized name words shared by every member
+    pub words: Vec<String>,
+    /// Parameter count shared by every member
+    pub arity: usize,
+    pub members: Vec<ExportedSignature>,
+    /// Index into `members` of the suggested canonical API
+    pub canonical: usize,
+    /// Packages the members live in
+    pub packages: Vec<String>,
+    /// Distinct names among the members
+    pub names: Vec<String>,
+    pub suggestion: String,
+}
+
+impl SignatureCluster {
+    pub fn canonical(&self) -> &ExportedSignature {
+        &self.members[self.canonical]
+    }
+    
+    /// Members other than the canonical one
+    pub fn variants(&self) -> impl Iterator<Item = &ExportedSignature> {
+        self.members.iter().enumerate().filter(move |(i, _)| *i != self.canonical).map(|(_, m)| m)
+    }
+}
+
+/// Result of signature clustering
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignatureClusterReport {
+    pub id: Uuid,
+    pub files_analyzed: usize,
+    pub exported_functions: usize,
+    pub clusters: Vec<SignatureCluster>,
+    pub computed_at: DateTime<Utc>,
+}
+
+/// Cluster the exported functions in `files` by name and signature
+pub fn analyze_signature_clusters(files: &[PathBuf], options: &SignatureOptions) -> SignatureClusterReport {
+    let mut packages = PackageNames::default();
+    let mut exported = Vec::new();
+    let mut files_analyzed = 0;
+    
+    for path in files {
+        if !options.include_tests && is_test_file(path) {
+            continue;
+        }
+        let Ok(functions) = extract_functions(path) else { continue };
+        files_analyzed += 1;
+        
+        for function in functions.into_iter().filter(|f| f.is_exported) {
+            exported.push(ExportedSignature {
+                package: packages.of(path),
+                file: path.clone(),
+                line: function.start_line,
+                return_type: function.return_type.as_deref().map(normalize_type).filter(|t| !t.is_empty()),
+                body_hash: crate::fingerprint::content_hash(&function.normalized_body),
+                name: function.name,
+                parameters: function.parameters,
+                is_async: function.is_async,
+            });
+        }
+    }
+    let exported_functions = exported.len();
+    
+    let mut groups: BTreeMap<(Vec<String>, usize), Vec<ExportedSignature>> = BTreeMap::new();
+    for signature in exported {
+        let words = name_words(&signature.name);
+        if words.is_empty() {
+            continue;
+        }
+        groups.entry((words, signature.parameters.len())).or_default().push(signature);
+    }
+    
+    let mut clusters = Vec::new();
+    for ((words, arity), members) in groups {
+        for members in split_by_return_type(members) {
+            if let Some(cluster) = build_cluster(words.clone(), arity, members, options) {
+                clusters.push(cluster);
+            }
+        }
+    }
+    clusters.sort_by(|a, b| b.members.len().cmp(&a.members.len()).then_with(|| a.words.cmp(&b.words)));
+    
+    SignatureClusterReport {
+        id: Uuid::new_v4(),
+        files_analyzed,
+        exported_functions,
+        clusters,
+        computed_at: Utc::now(),
+    }
+}
+
+/// Group members whose declared return types agree (members without one
+/// join the largest group)
+fn split_by_return_type(members: Vec<ExportedSignature>) -> Vec<Vec<ExportedSignature>> {
+    let declared: BTreeSet<String> = members.iter().filter_map(|m| m.return_type.clone()).collect();
+    if declared.len() <= 1 {
+        return vec![members];
+    }
+    
+    let mut groups: Vec<Vec<ExportedSignature>> = Vec::new();
+    for return_type in declared {
+        groups.push(members.iter().filter(|m| m.return_type.as_ref() == Some(&return_type)).cloned().collect());
+    }
+    groups.sort_by_key(|g| std::cmp::Reverse(g.len()));
+    groups[0].extend(members.into_iter().filter(|m| m.return_type.is_none()));
+    groups
+}
+
+fn build_cluster(words: Vec<String>, arity: usize, mut members: Vec<ExportedSignature>, options: &SignatureOptions) -> Option<SignatureCluster> {
+    members.sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
+    
+    let packages: Vec<String> = members.iter().map(|m| m.package.clone()).collect::<BTreeSet<_>>().into_iter().collect();
+    let files: BTreeSet<&Path> = members.iter().map(|m| m.file.as_path()).collect();
+    let spread = if options.cross_package { packages.len() } else { files.len() };
+    // Copies of one function are the duplicate check's business
+    let bodies: BTreeSet<&str> = members.iter().map(|m| m.body_hash.as_str()).collect();
+    if spread < 2 || bodies.len() < 2 {
+        return None;
+    }
+    
+    let mut name_counts: HashMap<&str, usize> = HashMap::new();
+    for member in &members {
+        *name_counts.entry(member.name.as_str()).or_default() += 1;
+    }
+    let canonical_name = name_counts.iter()
+        .max_by(|(a, ca), (b, cb)| ca.cmp(cb).then(a.len().cmp(&b.len())).then(b.cmp(a)))
+        .map(|(name, _)| name.to_string())?;
+    let canonical = members.iter().position(|m| m.name == canonical_name)?;
+    let mut names: Vec<String> = name_counts.into_keys().map(str::to_string).collect();
+    names.sort();
+    
+    let home = &members[canonical];
+    let suggestion = format!(
+        "{} exported functions in {} packages look like the same API ({}); consolidate on `{}` from {}",
+        members.len(),
+        packages.len(),
+        names.join(", "),
+        home.signature(),
+        if home.package.is_empty() { home.file.display().to_string() } else { home.package.clone() }
+    );
+    
+    Some(SignatureCluster {
+        words,
+        arity,
+        canonical,
+        packages,
+        names,
+        suggestion,
+        members,
+    })
+}
+
+/// Normalized words of a function name, sorted: split on case and
+/// separators, abbreviations and synonyms expanded, plurals folded
+pub fn name_words(name: &str) -> Vec<String> {
+    let words: BTreeSet<String> = split_identifier(name)
+        .into_iter()
+        .map(|w| canonical_word(&w))
+        .filter(|w| !w.is_empty())
+        .collect();
+    words.into_iter().collect()
+}
+
+fn split_identifier(name: &str) -> Vec<String> {
+    let chars: Vec<char> = name.chars().collect();
+    let mut words = Vec::new();
+    let mut current = String::new();
+    
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        // `parseJSON` → parse, JSON; `JSONParse` → JSON, Parse
+        let boundary = c.is_uppercase() && i > 0 && (
+            chars[i - 1].is_lowercase()
+                || chars[i - 1].is_ascii_digit()
+                || (chars[i - 1].is_uppercase() && chars.get(i + 1).is_some_and(|n| n.is_lowercase()))
+        );
+        if boundary && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        current.extend(c.to_lowercase());
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+fn canonical_word(word: &str) -> String {
+    let expanded = match word {
+        "fmt" => "format",
+        "str" => "string",
+        "num" => "number",
+        "calc" => "calculate",
+        "init" => "initialize",
+        "cfg" | "conf" => "config",
+        "msg" => "message",
+        "err" => "error",
+        "req" => "request",
+        "res" | "resp" => "response",
+        "val" => "value",
+        "btn" => "button",
+        "idx" => "index",
+        "len" => "length",
+        "dt" => "date",
+        "ts" => "timestamp",
+        "obj" => "object",
+        "arr" => "array",
+        "el" | "elem" => "element",
+        "evt" => "event",
+        "cb" => "callback",
+        "fn" | "func" => "function",
+        "gen" => "generate",
+        "del" | "remove" | "rm" => "delete",
+        "src" => "source",
+        "dst" | "dest" => "destination",
+        "tmp" | "temp" => "temporary",
+        "prev" => "previous",
+        "cur" | "curr" => "current",
+        "avg" => "average",
+        "addr" => "address",
+        "ctx" => "context",
+        "env" => "environment",
+        "param" => "parameter",
+        "arg" => "argument",
+        "attr" => "attribute",
+        "util" => "utility",
+        // Verbs that name the same operation
+        "fetch" | "load" | "retrieve" | "read" => "get",
+        "make" | "build" => "create",
+        "check" | "verify" => "validate",
+        "to" => "",
+        other => other,
+    };
+    singular(expanded)
+}
+
+fn singular(word: &str) -> String {
+    if let Some(stem) = word.strip_suffix("ies").filter(|s| s.len() >= 2) {
+        return format!("{stem}y");
+    }
+    let keep = word.len() < 3 || ["ss", "us", "is"].iter().any(|s| word.ends_with(s));
+    match word.strip_suffix('s') {
+        Some(stem) if !keep => stem.to_string(),
+        _ => word.to_string(),
+    }
+}
+
+fn normalize_type(return_type: &str) -> String {
+    return_type.trim_start_matches(':').split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Package labels by directory, read once per directory
+#[derive(Default)]
+struct PackageNames {
+    cache: HashMap<PathBuf, String>,
+}
+
+impl PackageNames {
+    fn of(&mut self, file: &Path) -> String {
+        let Some(dir) = file.parent() else { return String::new() };
+        if let Some(name) = self.cache.get(dir) {
+            return name.clone();
+        }
+        
+        let name = dir.ancestors()
+            .find(|d| d.join("package.json").is_file())
+            .map(|d| {
+                fs::read_to_string(d.join("package.json")).ok()
+                    .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok())
+                    .and_then(|m| m.get("name")?.as_str().map(str::to_string))
+                    .unwrap_or_else(|| d.display().to_string())
+            })
+            .unwrap_or_default();
+        self.cache.insert(dir.to_path_buf(), name.clone());
+        name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+    
+    #[test]
+    fn test_name_words() {
+        assert_eq!(name_words("formatDate"), vec!["date", "format"]);
+        assert_eq!(name_words("fmtDate"), name_words("dateFormat"));
+        assert_eq!(name_words("format_dates"), name_words("formatDate"));
+        assert_eq!(name_words("parseJSONString"), vec!["json", "parse", "string"]);
+        assert_eq!(name_words("fetchUser"), name_words("loadUsers"));
+        assert_ne!(name_words("formatDate"), name_words("parseDate"));
+    }
+    
+    #[test]
+    fn test_clusters_near_miss_apis_across_packages() {
+        let dir = tempdir().unwrap();
+        let write = |rel: &str, content: &str| {
+            let path = dir.path().join(rel);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, content).unwrap();
+            path
+        };
+        write("packages/utils/package.json", r#"{ "name": "@cs/utils" }"#);
+        write("packages/web/package.json", r#"{ "name": "@cs/web" }"#);
+        write("packages/api/package.json", r#"{ "name": "@cs/api" }"#);
+        write("packages/admin/package.json", r#"{ "name": "@cs/admin" }"#);
+        
+        let files = vec![
+            write("packages/utils/src/date.ts", "export function formatDate(date: Date, pattern: string): string {\n    return pattern.replace('YYYY', String(date.getFullYear()));\n}\n"),
+            write("packages/web/src/time.ts", "export const fmtDate = (d: Date, fmt: string): string => d.toLocaleDateString(fmt);\n"),
+            write("packages/api/src/helpers.ts", "export function dateFormat(value: Date, style: string): string {\n    return new Intl.DateTimeFormat(style).format(value);\n}\n\nexport function formatDate(d: Date): string {\n    return d.toISOString();\n}\n"),
+            write("packages/admin/src/format.ts", "export function formatDate(date: Date, locale: string) {\n    return date.toLocaleString(locale);\n}\n"),
+            // Not exported, so not an API
+            write("packages/web/src/local.ts", "function formatDate(a: Date, b: string): string { return ''; }\n"),
+            // Same package only
+            write("packages/api/src/users.ts", "export function getUser(id: string) { return db.get(id); }\n"),
+            write("packages/api/src/accounts.ts", "export function fetchUser(id: string) { return api.fetch(id); }\n"),
+        ];
+        
+        let report = analyze_signature_clusters(&files, &SignatureOptions::default());
+        assert_eq!(report.clusters.len(), 1);
+        let cluster = &report.clusters[0];
+        assert_eq!((cluster.arity, cluster.members.len(), cluster.packages.len()), (2, 4, 4));
+        assert_eq!(cluster.names, vec!["dateFormat", "fmtDate", "formatDate"]);
+        assert_eq!(cluster.canonical().name, "formatDate");
+        assert_eq!(cluster.variants().count(), 3);
+        assert!(cluster.suggestion.contains("consolidate on `formatDate(date, locale)` from @cs/admin"));
+        
+        let within = analyze_signature_clusters(&files, &SignatureOptions { cross_package: false, ..Default::default() });
+        assert_eq!(within.clusters.len(), 2);
+    }
+}
@@ -4,16 +4,25 @@
 //! - Token-level diff (via `similar` crate)
 //! - AST structure comparison (via tree-sitter)
 //! - Content hash comparison
+//!
+//! Snippets (e.g. two diff hunks) can be compared without touching disk;
+//! their evidence is keyed by content hash as `snippet:<sha256>`.
+//!
+//! Evidence carries an explanation alongside the score: the largest aligned
+//! regions (line ranges in both files, plus the token sequence they share
+//! once whitespace and comments are stripped), so a reviewer can see where
+//! "87%" comes from without opening both files.
 
 use std::path::{Path, PathBuf};
 use std::fs;
 use serde::{Serialize, Deserialize};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
-use similar::{TextDiff, Algorithm};
+use similar::{capture_diff_slices, DiffTag, TextDiff, Algorithm};
 
 use super::ComputationError;
 use super::ast_similarity::{extract_fingerprint, compare_fingerprints};
+use super::function_dry::strip_comments;
 
 /// Evidence of computed similarity between two files
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -45,10 +54,55 @@ pub struct SimilarityEvidence {
     /// Hash of file B content
     pub hash_b: String,
     
+    /// Where the similarity comes from
+    #[serde(default)]
+    pub explanation: SimilarityExplanation,
+    
     /// When this computation was performed
     pub computed_at: DateTime<Utc>,
 }
 
+/// Most regions kept in an explanation
+const MAX_EXPLAINED_REGIONS: usize = 5;
+
+/// Regions sharing fewer tokens than this (a lone `}`, a blank `return;`)
+/// aren't worth showing
+const MIN_REGION_TOKENS: usize = 6;
+
+/// Changed lines still count as aligned when their tokens are this similar
+const ALIGNED_EDIT_SIMILARITY: f64 = 0.6;
+
+/// The aligned regions behind a similarity score
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SimilarityExplanation {
+    /// Largest aligned regions, most shared tokens first
+    pub regions: Vec<AlignedRegion>,
+    /// Share of file A's code lines inside an aligned region
+    pub coverage_a: f64,
+    /// Share of file B's code lines inside an aligned region
+    pub coverage_b: f64,
+}
+
+/// A stretch of code that lines up in both files
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlignedRegion {
+    /// First and last line in file A (1-based, inclusive)
+    pub lines_a: (usize, usize),
+    /// First and last line in file B (1-based, inclusive)
+    pub lines_b: (usize, usize),
+    /// Token similarity within the region (1.0 = identical apart from whitespace and comments)
+    pub similarity: f64,
+    /// Tokens both sides share, in order
+    pub shared_tokens: Vec<String>,
+}
+
+impl AlignedRegion {
+    /// Shared tokens as one normalized line of code
+    pub fn shared_text(&self) -> String {
+        self.shared_tokens.join(" ")
+    }
+}
+
 impl SimilarityEvidence {
     /// Check if this evidence meets a similarity threshold
     pub fn meets_threshold(&self, threshold: f64) -> bool {
@@ -64,6 +118,51 @@ pub fn compute_similarity(file_a: &Path, file_b: &Path) -> Result<SimilarityEvid
     let content_b = fs::read_to_string(file_b)
         .map_err(|_| ComputationError::FileNotFound(file_b.to_path_buf()))?;
     
+    // Compute AST similarity if language is supported
+    let extension = file_a.extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("");
+    
+    Ok(compare_contents(file_a.to_path_buf(), file_b.to_path_buf(), &content_a, &content_b, extension))
+}
+
+/// Compute similarity between two in-memory code snippets
+///
+/// `language` may be a name ("typescript", "rust") or an extension ("ts").
+/// Evidence is keyed by content hash: `file_a`/`file_b` are
+/// `snippet:<sha256>`, so the same snippets always map to the same pair.
+pub fn compute_snippet_similarity(code_a: &str, code_b: &str, language: &str) -> SimilarityEvidence {
+    let extension = language_extension(language);
+    compare_contents(snippet_key(code_a), snippet_key(code_b), code_a, code_b, extension)
+}
+
+/// Registry key for a snippet's content
+pub fn snippet_key(code: &str) -> PathBuf {
+    use sha2::{Sha256, Digest};
+    PathBuf::from(format!("snippet:{:x}", Sha256::digest(code.as_bytes())))
+}
+
+/// Map a language name to the file extension the AST parsers expect
+fn language_extension(language: &str) -> &str {
+    match language.to_lowercase().as_str() {
+        "typescript" => "ts",
+        "tsx" => "tsx",
+        "javascript" => "js",
+        "jsx" => "jsx",
+        "rust" => "rs",
+        "python" => "py",
+        _ => language,
+    }
+}
+
+/// Similarity between two pieces of content, recorded against the given keys
+fn compare_contents(
+    file_a: PathBuf,
+    file_b: PathBuf,
+    content_a: &str,
+    content_b: &str,
+    extension: &str,
+) -> SimilarityEvidence {
     // Compute hashes
     use sha2::{Sha256, Digest};
     let hash_a = format!("{:x}", Sha256::digest(content_a.as_bytes()));
@@ -72,7 +171,7 @@ pub fn compute_similarity(file_a: &Path, file_b: &Path) -> Result<SimilarityEvid
     // Compute line-level similarity using diff
     let diff = TextDiff::configure()
         .algorithm(Algorithm::Patience)
-        .diff_lines(&content_a, &content_b);
+        .diff_lines(content_a, content_b);
     
     let line_similarity = diff.ratio() as f64;
     
@@ -81,12 +180,7 @@ pub fn compute_similarity(file_a: &Path, file_b: &Path) -> Result<SimilarityEvid
     let tokens_b: Vec<&str> = content_b.split_whitespace().collect();
     let token_overlap = compute_token_overlap(&tokens_a, &tokens_b);
     
-    // Compute AST similarity if language is supported
-    let extension = file_a.extension()
-        .and_then(|e| e.to_str())
-        .unwrap_or("");
-    
-    let ast_similarity = compute_ast_similarity(&content_a, &content_b, extension);
+    let ast_similarity = compute_ast_similarity(content_a, content_b, extension);
     
     // Combined similarity score (weighted average)
     // If AST is available, weight it heavily as it's more reliable
@@ -101,18 +195,140 @@ pub fn compute_similarity(file_a: &Path, file_b: &Path) -> Result<SimilarityEvid
         }
     };
     
-    Ok(SimilarityEvidence {
+    SimilarityEvidence {
         id: Uuid::new_v4(),
-        file_a: file_a.to_path_buf(),
-        file_b: file_b.to_path_buf(),
+        file_a,
+        file_b,
         similarity,
         token_overlap,
         line_similarity,
         ast_similarity,
         hash_a,
         hash_b,
+        explanation: explain_similarity(content_a, content_b),
         computed_at: Utc::now(),
-    })
+    }
+}
+
+/// Find the regions that line up between two pieces of content
+///
+/// Lines are compared with whitespace and comments stripped. Runs of equal
+/// lines, plus changed lines whose tokens mostly match (a renamed variable),
+/// form a region; each region's shared tokens are their longest common
+/// subsequence.
+pub fn explain_similarity(content_a: &str, content_b: &str) -> SimilarityExplanation {
+    let lines_a = code_lines(content_a);
+    let lines_b = code_lines(content_b);
+    let text_a: Vec<&str> = lines_a.iter().map(|(_, l)| l.as_str()).collect();
+    let text_b: Vec<&str> = lines_b.iter().map(|(_, l)| l.as_str()).collect();
+    
+    // Runs of aligned lines, as index ranges into lines_a/lines_b
+    let mut runs: Vec<(std::ops::Range<usize>, std::ops::Range<usize>)> = Vec::new();
+    let mut current: Option<(std::ops::Range<usize>, std::ops::Range<usize>)> = None;
+    for op in capture_diff_slices(Algorithm::Patience, &text_a, &text_b) {
+        let (tag, old, new) = op.as_tag_tuple();
+        let aligned = match tag {
+            DiffTag::Equal => true,
+            DiffTag::Replace => {
+                let shorter = old.len().min(new.len()) as f64;
+                let longer = old.len().max(new.len()) as f64;
+                shorter / longer >= 0.5
+                    && token_similarity(&tokens(&text_a[old.clone()].join("\n")), &tokens(&text_b[new.clone()].join("\n")))
+                        >= ALIGNED_EDIT_SIMILARITY
+            }
+            DiffTag::Delete | DiffTag::Insert => false,
+        };
+        
+        current = match (current, aligned) {
+            (Some((a, b)), true) => Some((a.start..old.end, b.start..new.end)),
+            (None, true) => Some((old, new)),
+            (run, false) => {
+                runs.extend(run);
+                None
+            }
+        };
+    }
+    runs.extend(current);
+    
+    let mut regions: Vec<AlignedRegion> = Vec::new();
+    let (mut covered_a, mut covered_b) = (0, 0);
+    for (a, b) in runs {
+        let tokens_a = tokens(&text_a[a.clone()].join("\n"));
+        let tokens_b = tokens(&text_b[b.clone()].join("\n"));
+        let shared = shared_tokens(&tokens_a, &tokens_b);
+        if shared.len() < MIN_REGION_TOKENS {
+            continue;
+        }
+        
+        covered_a += a.len();
+        covered_b += b.len();
+        regions.push(AlignedRegion {
+            lines_a: (lines_a[a.start].0, lines_a[a.end - 1].0),
+            lines_b: (lines_b[b.start].0, lines_b[b.end - 1].0),
+            similarity: 2.0 * shared.len() as f64 / (tokens_a.len() + tokens_b.len()) as f64,
+            shared_tokens: shared,
+        });
+    }
+    
+    regions.sort_by_key(|r| std::cmp::Reverse(r.shared_tokens.len()));
+    regions.truncate(MAX_EXPLAINED_REGIONS);
+    
+    let coverage = |covered: usize, total: usize| if total == 0 { 0.0 } else { covered as f64 / total as f64 };
+    SimilarityExplanation {
+        regions,
+        coverage_a: coverage(covered_a, lines_a.len()),
+        coverage_b: coverage(covered_b, lines_b.len()),
+    }
+}
+
+/// Non-blank, non-comment lines with whitespace collapsed, keyed by line number
+fn code_lines(content: &str) -> Vec<(usize, String)> {
+    strip_comments(content)
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            let code = line.split_whitespace().collect::<Vec<_>>().join(" ");
+            (!code.is_empty()).then_some((i + 1, code))
+        })
+        .collect()
+}
+
+/// Identifiers, numbers, and single punctuation characters
+fn tokens(code: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut word = String::new();
+    for c in code.chars() {
+        if c.is_alphanumeric() || c == '_' || c == '$' {
+            word.push(c);
+            continue;
+        }
+        if !word.is_empty() {
+            tokens.push(std::mem::take(&mut word));
+        }
+        if !c.is_whitespace() {
+            tokens.push(c.to_string());
+        }
+    }
+    if !word.is_empty() {
+        tokens.push(word);
+    }
+    tokens
+}
+
+/// Longest common subsequence of two token sequences
+fn shared_tokens(a: &[String], b: &[String]) -> Vec<String> {
+    capture_diff_slices(Algorithm::Myers, a, b)
+        .into_iter()
+        .filter(|op| op.tag() == DiffTag::Equal)
+        .flat_map(|op| a[op.old_range()].to_vec())
+        .collect()
+}
+
+pub(super) fn token_similarity(a: &[String], b: &[String]) -> f64 {
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    2.0 * shared_tokens(a, b).len() as f64 / (a.len() + b.len()) as f64
 }
 
 /// Compute AST-based similarity between two code strings
@@ -229,4 +445,65 @@ export { validateEmail };
         assert!(evidence.similarity > 0.4, "Expected similarity > 0.4, got {}", evidence.similarity);
         assert!(evidence.similarity < 0.99, "Expected similarity < 0.99, got {}", evidence.similarity);
     }
+    
+    #[test]
+    fn test_explanation_aligns_renamed_region() {
+        let content_a = r#"import { z } from 'zod';
+
+// Validate an email address
+function validateEmail(email: string): boolean {
+    const regex = /^[^\s@]+@[^\s@]+$/;
+    return regex.test(email);
+}
+"#;
+        let content_b = r#"function validateEmail(email: string): boolean {
+    const pattern = /^[^\s@]+@[^\s@]+$/;   // same check
+    return pattern.test(email);
+}
+
+export const unrelated = 42;
+"#;
+        
+        let explanation = explain_similarity(content_a, content_b);
+        assert_eq!(explanation.regions.len(), 1);
+        
+        // The renamed variable doesn't break the region up
+        let region = &explanation.regions[0];
+        assert_eq!(region.lines_a, (4, 7));
+        assert_eq!(region.lines_b, (1, 4));
+        assert!(region.similarity > 0.9 && region.similarity < 1.0);
+        assert!(region.shared_text().starts_with("function validateEmail ( email : string )"));
+        assert!(!region.shared_tokens.contains(&"regex".to_string()));
+        
+        assert!((explanation.coverage_a - 0.8).abs() < 1e-9);
+        assert!((explanation.coverage_b - 0.8).abs() < 1e-9);
+    }
+    
+    #[test]
+    fn test_code_lines_keep_comment_markers_in_strings() {
+        let content = "const url = \"http://example.com\"; // docs\n/* block\n   comment */ const x = '/* not a comment */';\n";
+        assert_eq!(code_lines(content), vec![
+            (1, "const url = \"http://example.com\";".to_string()),
+            (3, "const x = '/* not a comment */';".to_string()),
+        ]);
+    }
+    
+    #[test]
+    fn test_snippets_match_file_comparison() {
+        let dir = tempdir().unwrap();
+        let file_a = dir.path().join("a.ts");
+        let file_b = dir.path().join("b.ts");
+        
+        let code_a = "function add(a: number, b: number) { return a + b; }";
+        let code_b = "function sum(x: number, y: number) { return x + y; }";
+        File::create(&file_a).unwrap().write_all(code_a.as_bytes()).unwrap();
+        File::create(&file_b).unwrap().write_all(code_b.as_bytes()).unwrap();
+        
+        let from_files = compute_similarity(&file_a, &file_b).unwrap();
+        let from_snippets = compute_snippet_similarity(code_a, code_b, "typescript");
+        
+        assert!((from_files.similarity - from_snippets.similarity).abs() < 1e-9);
+        assert_eq!(from_snippets.file_a, PathBuf::from(format!("snippet:{}", from_files.hash_a)));
+        assert!(from_snippets.ast_similarity.is_some());
+    }
 }
@@ -0,0 +1,227 @@
+//! Svelte State Connectivity
+//!
+//! Svelte apps share state through stores and context as much as through
+//! imports. A store module is in use when components subscribe to its
+//! stores (`$cart` in markup, `cart.subscribe(...)`, `get(cart)`) and import
+//! the module, even when that import went through an alias the import graph
+//! can't follow. Imports are matched by module name, so `$lib/stores/cart`
+//! counts for `src/lib/stores/cart.ts`. A module that calls
+//! `setContext(KEY, ...)` is in use when something calls `getContext(KEY)`,
+//! and the other way round.
+//!
+//! These count as connections for orphan detection. A store module whose
+//! stores nobody subscribes to, and whose context keys nobody reads or
+//! provides, is still an orphan.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use regex_lite::Regex;
+use serde::{Deserialize, Serialize};
+
+/// Svelte 5 runes and component globals, which look like subscriptions
+const RUNES: &[&str] = &["state", "derived", "effect", "props", "bindable", "inspect", "host"];
+
+lazy_static::lazy_static! {
+    /// `$name` outside a template literal
+    static ref SUBSCRIPTION: Regex = Regex::new(r"(?:^|[^\w$])\$([A-Za-z_][\w]*)").unwrap();
+    /// `name.subscribe(` or `get(name)`, but not `map.get(name)`
+    static ref EXPLICIT_SUBSCRIPTION: Regex =
+        Regex::new(r"(?:\b([A-Za-z_$][\w$]*)\.subscribe\s*\(|(?:(?m:^)|[^.\w$])get\s*\(\s*([A-Za-z_$][\w$]*)\s*\))").unwrap();
+    /// `from 'specifier'`, `import 'specifier'`, `import('specifier')`
+    static ref IMPORT_SOURCE: Regex =
+        Regex::new(r#"\b(?:from|import)\s*\(?\s*['"]([^'"]+)['"]"#).unwrap();
+    /// `export const cart = writable(...)`, `derived(...)`, `createCartStore(...)`
+    static ref EXPORTED_STORE: Regex = Regex::new(
+        r"export\s+(?:const|let)\s+([A-Za-z_$][\w$]*)\s*(?::[^=]*)?=\s*(?:writable|readable|derived|persisted|\w*[Ss]tore)\s*[<(]"
+    ).unwrap();
+    /// `setContext(KEY, ...)`, `getContext<T>('key')`, `hasContext(KEY)`
+    static ref CONTEXT_CALL: Regex =
+        Regex::new(r"\b(set|get|has)Context\s*(?:<[^>]*>)?\s*\(\s*([^,)]+)").unwrap();
+}
+
+/// How a module is connected through shared state
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StateConnectionKind {
+    /// Other files subscribe to a store the module exports
+    StoreSubscription,
+    /// Other files read a context key the module provides, or provide one it reads
+    ContextKey,
+}
+
+/// A store or context key linking a module to other files
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateConnection {
+    pub kind: StateConnectionKind,
+    /// Store name, or context key (string keys without quotes)
+    pub name: String,
+    /// Files on the other end
+    pub files: Vec<PathBuf>,
+}
+
+/// Store subscriptions and context calls across a project, built once and
+/// queried per module
+#[derive(Debug, Default)]
+pub struct StateIndex {
+    subscribers: HashMap<String, HashSet<PathBuf>>,
+    /// Import specifiers of each subscribing file
+    imports: HashMap<PathBuf, Vec<String>>,
+    context_setters: HashMap<String, HashSet<PathBuf>>,
+    context_getters: HashMap<String, HashSet<PathBuf>>,
+}
+
+impl StateIndex {
+    /// Index the stores and context keys the given files use
+    pub fn build(files: &[PathBuf]) -> Self {
+        let mut index = Self::default();
+        for file in files {
+            let Ok(content) = fs::read_to_string(file) else {
+                continue;
+            };
+            index.add(file, &content);
+        }
+        index
+    }
+    
+    fn add(&mut self, file: &Path, content: &str) {
+        let file = file.canonicalize().unwrap_or_else(|_| file.to_path_buf());
+        
+        if file.extension().is_some_and(|e| e == "svelte") && content.contains('$') {
+            for capture in SUBSCRIPTION.captures_iter(content) {
+                let name = &capture[1];
+                if !RUNES.contains(&name) {
+                    self.subscribers.entry(name.to_string()).or_default().insert(file.clone());
+                }
+            }
+        }
+        for capture in EXPLICIT_SUBSCRIPTION.captures_iter(content) {
+            if let Some(name) = capture.get(1).or_else(|| capture.get(2)) {
+                self.subscribers.entry(name.as_str().to_string()).or_default().insert(file.clone());
+            }
+        }
+        let imports: Vec<String> = IMPORT_SOURCE.captures_iter(content).map(|c| c[1].to_string()).collect();
+        if !imports.is_empty() {
+            self.imports.insert(file.clone(), imports);
+        }
+        
+        for (call, key) in context_calls(content) {
+            let calls = if call == "set" { &mut self.context_setters } else { &mut self.context_getters };
+            calls.entry(key).or_default().insert(file.clone());
+        }
+    }
+    
+    /// State connections between a module and the rest of the index
+    pub fn connections(&self, module: &Path) -> Vec<StateConnection> {
+        let Ok(content) = fs::read_to_string(module) else {
+            return Vec::new();
+        };
+        let module = module.canonicalize().unwrap_or_else(|_| module.to_path_buf());
+        let others = |files: Option<&HashSet<PathBuf>>| -> Vec<PathBuf> {
+            let mut others: Vec<PathBuf> = files.into_iter().flatten()
+                .filter(|f| **f != module)
+                .cloned()
+                .collect();
+            others.sort();
+            others
+        };
+        
+        let mut connections = Vec::new();
+        for capture in EXPORTED_STORE.captures_iter(&content) {
+            let name = capture[1].to_string();
+            // A same-named store from another module isn't this one
+            let files: Vec<PathBuf> = others(self.subscribers.get(&name)).into_iter()
+                .filter(|f| self.imports.get(f).is_some_and(|specifiers| specifiers.iter().any(|s| names_module(s, &module))))
+                .collect();
+            if !files.is_empty() {
+                connections.push(StateConnection { kind: StateConnectionKind::StoreSubscription, name, files });
+            }
+        }
+        
+        let mut seen = HashSet::new();
+        for (call, key) in context_calls(&content) {
+            // A provider connects to readers, a reader to providers
+            let counterpart = if call == "set" { &self.context_getters } else { &self.context_setters };
+            let files = others(counterpart.get(&key));
+            if !files.is_empty() && seen.insert(key.clone()) {
+                connections.push(StateConnection { kind: StateConnectionKind::ContextKey, name: key, files });
+            }
+        }
+        
+        connections
+    }
+}
+
+/// Whether a module exports stores or uses context, so is worth indexing
+/// the project for
+pub fn shares_state(module: &Path) -> bool {
+    fs::read_to_string(module)
+        .is_ok_and(|content| EXPORTED_STORE.is_match(&content) || CONTEXT_CALL.is_match(&content))
+}
+
+/// Whether an import specifier could resolve to `module`: its last segment
+/// is the module's file stem, or its directory for an `index` module
+fn names_module(specifier: &str, module: &Path) -> bool {
+    let last = specifier.trim_end_matches('/').rsplit('/').next().unwrap_or(specifier);
+    let last = Path::new(last).file_stem().and_then(|s| s.to_str()).unwrap_or(last);
+    let stem = module.file_stem().and_then(|s| s.to_str());
+    if stem == Some("index") {
+        module.parent().and_then(|p| p.file_name()).and_then(|n| n.to_str()) == Some(last)
+    } else {
+        stem == Some(last)
+    }
+}
+
+/// `(set|get|has, key)` for each context call; string keys lose their quotes
+fn context_calls(content: &str) -> Vec<(&str, String)> {
+    CONTEXT_CALL.captures_iter(content)
+        .map(|capture| {
+            let call = capture.get(1).map_or("", |m| m.as_str());
+            let key = capture[2].trim().trim_matches(|c| matches!(c, '\'' | '"' | '`')).to_string();
+            (call, key)
+        })
+        .filter(|(_, key)| !key.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+    
+    #[test]
+    fn test_store_and_context_connections() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        let write = |name: &str, content: &str| {
+            let path = root.join(name);
+            fs::write(&path, content).unwrap();
+            path
+        };
+        
+        let cart = write("cart.ts", "import { writable } from 'svelte/store';\nexport const cart = writable<string[]>([]);\n");
+        let theme = write("theme.ts", "import { setContext } from 'svelte';\nexport function provideTheme(t) { setContext('theme', t); }\n");
+        let abandoned = write("wishlist.ts", "export const wishlist = writable([]);\nexport function init() { setContext(WISHLIST, wishlist); }\n");
+        let page = write("Page.svelte", "<script>\n  import { cart } from '$lib/stores/cart';\n  let count = $state(0);\n  const theme = getContext('theme');\n</script>\n<p>{$cart.length} {`${count}`}</p>\n");
+        // A local `cart`, and a Map lookup that isn't svelte/store's `get`
+        let local = write("Local.svelte", "<script>\n  import { writable } from 'svelte/store';\n  const cart = writable([]);\n</script>\n<p>{$cart.length}</p>\n");
+        let lookup = write("lookup.ts", "import { cart } from './cart';\nexport const wishlistSize = (m) => m.get(wishlist);\n");
+        
+        let index = StateIndex::build(&[cart.clone(), theme.clone(), abandoned.clone(), page.clone(), local, lookup]);
+        let page = page.canonicalize().unwrap();
+        
+        let connections = index.connections(&cart);
+        assert_eq!(connections.len(), 1);
+        assert_eq!((connections[0].kind, connections[0].name.as_str()), (StateConnectionKind::StoreSubscription, "cart"));
+        assert_eq!(connections[0].files, vec![page.clone()]);
+        
+        let connections = index.connections(&theme);
+        assert_eq!((connections[0].kind, connections[0].name.as_str()), (StateConnectionKind::ContextKey, "theme"));
+        
+        // Nobody subscribes to the wishlist or reads its context key, and
+        // `$state` is a rune rather than a store
+        assert!(index.connections(&abandoned).is_empty());
+        assert!(!index.subscribers.contains_key("wishlist"));
+        assert!(!index.subscribers.contains_key("state"));
+    }
+}
@@ -10,6 +10,8 @@ use uuid::Uuid;
 use chrono::{DateTime, Utc};
 
 use super::ComputationError;
+use super::generated::{is_generated_dir, is_generated_file};
+use super::walk::source_files;
 
 /// Evidence of computed usage count for a symbol
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -350,24 +352,9 @@ fn is_definition_line(line: &str, symbol: &str) -> bool {
 }
 
 fn search_directory(symbol: &str, dir: &Path, locations: &mut Vec<UsageLocation>) -> Result<(), ComputationError> {
-    for entry in fs::read_dir(dir)? {
-        let entry = entry?;
-        let path = entry.path();
-        
-        // Skip hidden directories and node_modules
-        if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-            if name.starts_with('.') || name == "node_modules" || name == "target" || name == "dist" {
-                continue;
-            }
-        }
-        
-        if path.is_dir() {
-            search_directory(symbol, &path, locations)?;
-        } else if path.is_file() {
-            search_file(symbol, &path, locations)?;
-        }
+    for path in source_files(dir, &[]) {
+        search_file(symbol, &path, locations)?;
     }
-    
     Ok(())
 }
 
@@ -521,8 +508,7 @@ fn check_barrel_imports_in_dir(symbol: &str, barrel_dir: &str, barrel_path: &Pat
         let path = entry.path();
         
         if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-            if name.starts_with('.') || 
-               matches!(name, "node_modules" | "target" | "dist" | "build" | ".git") {
+            if name.starts_with('.') || is_generated_dir(name) {
                 continue;
             }
         }
@@ -537,7 +523,7 @@ fn check_barrel_imports_in_dir(symbol: &str, barrel_dir: &str, barrel_path: &Pat
             }
         } else if path.is_file() && path != *barrel_path {
             let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
-            if matches!(ext, "ts" | "tsx" | "js" | "jsx" | "svelte") {
+            if matches!(ext, "ts" | "tsx" | "js" | "jsx" | "svelte") && !is_generated_file(&path) {
                 // Use tree-sitter to extract imports
                 if let Ok(imports) = extract_imports(&path) {
                     for import in imports {
@@ -586,8 +572,7 @@ fn check_import_in_dir_ast(symbol: &str, source_file: &Path, dir: &Path) -> Resu
         let path = entry.path();
         
         if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-            if name.starts_with('.') || 
-               matches!(name, "node_modules" | "target" | "dist" | "build" | ".git") {
+            if name.starts_with('.') || is_generated_dir(name) {
                 continue;
             }
         }
@@ -602,7 +587,7 @@ fn check_import_in_dir_ast(symbol: &str, source_file: &Path, dir: &Path) -> Resu
             }
         } else if path.is_file() && path != source_file {
             let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
-            if matches!(ext, "ts" | "tsx" | "js" | "jsx" | "svelte") {
+            if matches!(ext, "ts" | "tsx" | "js" | "jsx" | "svelte") && !is_generated_file(&path) {
                 // Use tree-sitter to extract imports
                 if let Ok(imports) = extract_imports(&path) {
                     for import in imports {
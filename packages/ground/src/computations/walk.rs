@@ -0,0 +1,124 @@
+//! Source File Walking
+//!
+//! Every analyzer that collects files goes through `source_files`, so they
+//! agree on what is source. A path is skipped when it is:
+//!
+//! 1. **Hidden** - names starting with `.`
+//! 2. **Ignored** - matched by a `.gitignore` or `.ignore` in the directory
+//!    walked or any parent (plus `.git/info/exclude`), with git's rules
+//! 3. **Generated** - anything `generated::is_generated_file` rejects; the
+//!    `generated.paths` globs from `.ground.yml` are applied as walk
+//!    overrides so matching directories are never read
+//!
+//! Ignored and generated directories are never entered. The walk runs on
+//! the `ignore` crate's thread pool, which matters for monorepos where the
+//! walk (and the header reads that generated-code detection needs)
+//! dominates small analyses.
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use ignore::overrides::{Override, OverrideBuilder};
+use ignore::{WalkBuilder, WalkState};
+
+use super::generated::{generated_globs, is_generated_dir, is_generated_file};
+
+/// Source files under `dir` with one of `extensions` (any file if empty),
+/// sorted. Skips hidden, ignored, and generated paths. Paths start with
+/// `dir` as given.
+pub fn source_files(dir: &Path, extensions: &[&str]) -> Vec<PathBuf> {
+    let canonical = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+    let found = Mutex::new(Vec::new());
+    
+    WalkBuilder::new(&canonical)
+        .require_git(false)
+        .overrides(generated_overrides(&canonical))
+        .filter_entry(|entry| {
+            !entry.file_type().is_some_and(|t| t.is_dir())
+                || entry.depth() == 0
+                || !is_generated_dir(&entry.file_name().to_string_lossy())
+        })
+        .build_parallel()
+        .run(|| {
+            let found = &found;
+            Box::new(move |entry| {
+                let Ok(entry) = entry else {
+                    return WalkState::Continue;
+                };
+                let path = entry.path();
+                let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+                if entry.file_type().is_some_and(|t| t.is_file())
+                    && (extensions.is_empty() || extensions.contains(&ext))
+                    && !is_generated_file(path)
+                {
+                    found.lock().unwrap().push(entry.into_path());
+                }
+                WalkState::Continue
+            })
+        });
+    
+    let mut files: Vec<PathBuf> = found.into_inner().unwrap()
+        .into_iter()
+        .map(|file| match file.strip_prefix(&canonical) {
+            Ok(relative) => dir.join(relative),
+            Err(_) => file,
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+/// `generated.paths` globs as ignore overrides, relative to the repository
+/// root they're written against
+fn generated_overrides(dir: &Path) -> Override {
+    let (root, globs) = generated_globs(dir);
+    let mut builder = OverrideBuilder::new(&root);
+    for glob in &globs {
+        // `!` in an override means "ignore"; a bad glob was already dropped
+        // when the rules were loaded
+        let _ = builder.add(&format!("!{}", glob));
+        if let Some(prefix) = glob.strip_suffix("/**") {
+            let _ = builder.add(&format!("!{}", prefix));
+        }
+    }
+    builder.build().unwrap_or_else(|_| Override::empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+    
+    #[test]
+    fn test_source_files_respects_ignore_files() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        fs::create_dir_all(root.join(".git/info")).unwrap();
+        fs::write(root.join(".git/info/exclude"), "scratch/\n").unwrap();
+        fs::write(root.join(".gitignore"), "# local builds\nout-*/\n*.local.ts\n!keep.local.ts\n/top.ts\n").unwrap();
+        fs::write(root.join(".ground.yml"), "generated:\n  paths:\n    - \"src/gql/**\"\n").unwrap();
+        
+        for sub in ["src/gql", "src/out-cache", "src/nested", "scratch"] {
+            fs::create_dir_all(root.join(sub)).unwrap();
+        }
+        fs::write(root.join("src/nested/.ignore"), "draft.ts\n").unwrap();
+        for file in [
+            "top.ts", "src/top.ts", "src/app.ts", "src/env.local.ts", "src/keep.local.ts",
+            "src/gql/types.ts", "src/out-cache/chunk.ts", "src/nested/draft.ts", "src/nested/page.ts",
+            "src/readme.md", "scratch/try.ts",
+        ] {
+            fs::write(root.join(file), "export const a = 1;\n").unwrap();
+        }
+        
+        let relative = |files: Vec<PathBuf>| -> Vec<String> {
+            files.iter().map(|f| f.strip_prefix(&root).unwrap().to_string_lossy().into_owned()).collect()
+        };
+        assert_eq!(
+            relative(source_files(&root, &["ts"])),
+            vec!["src/app.ts", "src/keep.local.ts", "src/nested/page.ts", "src/top.ts"]
+        );
+        
+        // Walking a subdirectory still honours the root's patterns
+        assert_eq!(relative(source_files(&root.join("src/nested"), &[])), vec!["src/nested/page.ts"]);
+    }
+}
@@ -29,12 +29,57 @@
 //!   # Specific file pairs to skip in duplicate detection
 //!   duplicate_pairs:
 //!     - ["src/a.ts", "src/b.ts"]  # Known intentional duplication
+//!   
+//!   # Specific findings to skip, by fingerprint (survives file moves)
+//!   fingerprints:
+//!     - 3f9a1c0b7e2d4a56
+//!   
+//!   # package.json dependencies used in ways Ground can't see
+//!   dependencies:
+//!     - "@fontsource/*"            # Imported from CSS
 //! 
 //! thresholds:
 //!   duplicate_similarity: 80      # Percent (default: 80)
 //!   min_function_lines: 5         # Lines (default: 5)
 //!   max_dead_export_age_days: 30  # Days before flagging (optional)
 //! 
+//! public_api:
+//!   # Exports other repos import; never reported as dead (tagged externally_used)
+//!   symbols:
+//!     - formatDate                             # Any module
+//!     - name: "Canon*"
+//!       module: "@create-something/components"   # Package name or path glob
+//! 
+//! generated:
+//!   # Codegen output to treat like node_modules (globs, relative to repo root)
+//!   paths:
+//!     - "src/lib/graphql/**"
+//!   # Extra header markers (in addition to @generated and
+//!   # "Code generated ... DO NOT EDIT")
+//!   markers:
+//!     - "Generated by Prisma"
+//! 
+//! conventions:
+//!   # Directory layout rules, checked by `ground check run conventions`
+//!   - name: worker-layout
+//!     description: Workers live under workers/*/src
+//!     files: "**/*.worker.ts"     # Files the rule applies to
+//!     allow: ["workers/*/src/**"] # ...must be under one of these
+//!   - name: worker-entry
+//!     dirs: "workers/*"           # Directories the rule applies to
+//!     require: ["src/index.ts"]   # ...must contain these
+//!   - name: no-shared-in-apps
+//!     description: Shared code must not live under apps/
+//!     files: "**/shared/**"
+//!     deny: ["apps/**"]           # ...must not be under these
+//!     relocate: "packages/shared/src/{file}"  # Where to move offenders
+//! 
+//! audit:
+//!   # What `ground audit --all` covers (nightly cron)
+//!   roots: ["packages/ui", "apps/web"]  # Relative to this file (default: its directory)
+//!   checks: [dead-exports, orphans]     # Default: every directory-wide check
+//!   webhook: https://hooks.slack.com/services/...  # POSTed the run digest
+//! 
 //! report:
 //!   format: markdown              # text, markdown, json
 //!   include_suggestions: true
@@ -60,10 +105,26 @@ pub struct GroundConfig {
     #[serde(default)]
     pub ignore: IgnoreConfig,
     
+    /// Generated code detection
+    #[serde(default)]
+    pub generated: GeneratedConfig,
+    
+    /// Exports consumed outside this repo
+    #[serde(default)]
+    pub public_api: PublicApiConfig,
+    
     /// Analysis thresholds
     #[serde(default)]
     pub thresholds: ThresholdConfig,
     
+    /// Directory structure rules
+    #[serde(default)]
+    pub conventions: Vec<ConventionRule>,
+    
+    /// Scheduled audit settings
+    #[serde(default)]
+    pub audit: AuditConfig,
+    
     /// Report settings
     #[serde(default)]
     pub report: ReportConfig,
@@ -91,6 +152,130 @@ pub struct IgnoreConfig {
     /// Specific file pairs to ignore in duplicate detection
     #[serde(default)]
     pub duplicate_pairs: Vec<[String; 2]>,
+    
+    /// Finding fingerprints to ignore (see `fingerprint`)
+    #[serde(default)]
+    pub fingerprints: Vec<String>,
+    
+    /// Package names/patterns to leave out of unused and missing dependency checks
+    #[serde(default)]
+    pub dependencies: Vec<String>,
+}
+
+/// Generated code configuration
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct GeneratedConfig {
+    /// Codegen output path patterns (relative to the repo root)
+    #[serde(default)]
+    pub paths: Vec<String>,
+    
+    /// Additional header markers that identify generated files
+    #[serde(default)]
+    pub markers: Vec<String>,
+}
+
+/// Public API manifest: exports other repos consume
+///
+/// Usage reports generated downstream (`ground public-api report`) and placed
+/// in `.ground/external-usage/` add to this list.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct PublicApiConfig {
+    /// Externally consumed symbols
+    #[serde(default)]
+    pub symbols: Vec<PublicSymbol>,
+}
+
+/// An export declared as consumed from outside the repo
+///
+/// Written as a plain name, or `{ name, module }` to pin where it's exported from.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(from = "PublicSymbolDef")]
+pub struct PublicSymbol {
+    /// Export name (glob patterns allowed)
+    pub name: String,
+    /// Package name (subpaths allowed) or path glob; any module if omitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub module: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum PublicSymbolDef {
+    Name(String),
+    Full {
+        name: String,
+        #[serde(default)]
+        module: Option<String>,
+    },
+}
+
+impl From<PublicSymbolDef> for PublicSymbol {
+    fn from(def: PublicSymbolDef) -> Self {
+        match def {
+            PublicSymbolDef::Name(name) => PublicSymbol { name, module: None },
+            PublicSymbolDef::Full { name, module } => PublicSymbol { name, module },
+        }
+    }
+}
+
+/// A directory structure rule
+///
+/// Paths and globs are relative to the directory holding the config. A rule
+/// applies to the files matching `files` (which must sit under an `allow`
+/// glob and outside every `deny` glob) or to the directories matching `dirs`
+/// (which must contain every `require` path).
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct ConventionRule {
+    /// Short identifier shown in findings
+    pub name: String,
+    
+    /// The convention in words
+    #[serde(default)]
+    pub description: Option<String>,
+    
+    /// Files the rule applies to (glob)
+    #[serde(default)]
+    pub files: Option<String>,
+    
+    /// Directories the rule applies to (glob)
+    #[serde(default)]
+    pub dirs: Option<String>,
+    
+    /// Matching files must be under one of these globs
+    #[serde(default)]
+    pub allow: Vec<String>,
+    
+    /// Matching files must not be under any of these globs
+    #[serde(default)]
+    pub deny: Vec<String>,
+    
+    /// Paths every matching directory must contain
+    #[serde(default)]
+    pub require: Vec<String>,
+    
+    /// Where an offending file should move: a path with `{file}` (file name),
+    /// `{stem}` (name before the first dot), and `{dir}` (parent directory
+    /// name) filled in. Defaults to the first `allow` glob with its
+    /// wildcards filled in.
+    #[serde(default)]
+    pub relocate: Option<String>,
+}
+
+/// What `ground audit --all` runs
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct AuditConfig {
+    /// Directories to audit, relative to the directory holding the config
+    /// (that directory alone if empty)
+    #[serde(default)]
+    pub roots: Vec<String>,
+    
+    /// Checks to run (every check that takes a directory if empty)
+    #[serde(default)]
+    pub checks: Vec<String>,
+    
+    /// URL the run digest is POSTed to as JSON
+    #[serde(default)]
+    pub webhook: Option<String>,
 }
 
 /// Threshold configuration
@@ -226,6 +411,12 @@ impl GroundConfig {
         self.ignore.exports.extend(other.ignore.exports);
         self.ignore.paths.extend(other.ignore.paths);
         self.ignore.duplicate_pairs.extend(other.ignore.duplicate_pairs);
+        self.ignore.fingerprints.extend(other.ignore.fingerprints);
+        self.ignore.dependencies.extend(other.ignore.dependencies);
+        self.generated.paths.extend(other.generated.paths);
+        self.generated.markers.extend(other.generated.markers);
+        self.public_api.symbols.extend(other.public_api.symbols);
+        self.conventions.extend(other.conventions);
         
         // Deduplicate
         self.ignore.functions.sort();
@@ -234,6 +425,16 @@ impl GroundConfig {
         self.ignore.exports.dedup();
         self.ignore.paths.sort();
         self.ignore.paths.dedup();
+        self.ignore.fingerprints.sort();
+        self.ignore.fingerprints.dedup();
+        self.ignore.dependencies.sort();
+        self.ignore.dependencies.dedup();
+        self.generated.paths.sort();
+        self.generated.paths.dedup();
+        self.generated.markers.sort();
+        self.generated.markers.dedup();
+        self.public_api.symbols.dedup();
+        self.conventions.dedup();
         // duplicate_pairs are harder to dedupe, leave as-is
         
         // For thresholds, keep current values (base config wins)
@@ -258,6 +459,46 @@ impl GroundConfig {
         Self::default()
     }
     
+    /// Find and load the nearest config in `start_dir` or its ancestors
+    pub fn find_in_ancestors(start_dir: &Path) -> Option<Self> {
+        Self::find_with_root(start_dir).map(|(config, _)| config)
+    }
+    
+    /// Like `find_in_ancestors`, also returning the directory the config was found in
+    pub fn find_with_root(start_dir: &Path) -> Option<(Self, PathBuf)> {
+        let mut current = if start_dir.is_absolute() {
+            start_dir.to_path_buf()
+        } else {
+            std::env::current_dir().ok()?.join(start_dir)
+        };
+        
+        // Canonicalize to resolve any symlinks or relative components
+        if let Ok(canonical) = current.canonicalize() {
+            current = canonical;
+        }
+        
+        // Walk up to 10 levels to avoid infinite loops
+        for _ in 0..10 {
+            for name in &[".ground.yml", ".ground.yaml", "ground.yml", "ground.yaml"] {
+                let config_path = current.join(name);
+                if config_path.exists() {
+                    if let Ok(config) = Self::load(&config_path) {
+                        return Some((config, current));
+                    }
+                }
+            }
+            
+            match current.parent() {
+                Some(parent) if parent != current => {
+                    current = parent.to_path_buf();
+                }
+                _ => break,
+            }
+        }
+        
+        None
+    }
+    
     /// Check if a function name should be ignored
     pub fn should_ignore_function(&self, name: &str) -> bool {
         self.ignore.functions.iter().any(|pattern| {
@@ -328,6 +569,22 @@ impl GroundConfig {
         })
     }
     
+    /// Check if a package.json dependency should be ignored
+    pub fn should_ignore_dependency(&self, name: &str) -> bool {
+        self.ignore.dependencies.iter().any(|pattern| {
+            if pattern.contains('*') {
+                Pattern::new(pattern).map(|p| p.matches(name)).unwrap_or(false)
+            } else {
+                pattern == name
+            }
+        })
+    }
+    
+    /// Check if a finding should be ignored by its fingerprint
+    pub fn should_ignore_fingerprint(&self, fingerprint: &str) -> bool {
+        self.ignore.fingerprints.iter().any(|f| f == fingerprint)
+    }
+    
     /// Get similarity threshold as f64 (0.0-1.0)
     pub fn similarity_threshold(&self) -> f64 {
         self.thresholds.duplicate_similarity as f64 / 100.0
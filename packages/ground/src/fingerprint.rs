@@ -0,0 +1,211 @@
+//! Finding Fingerprints
+//!
+//! A finding identified by file + name becomes "new" again the moment its
+//! file moves, which resurrects everything a team has already suppressed.
+//! Fingerprints are derived from the code instead of where it lives:
+//!
+//! ```text
+//! fingerprint = sha256(kind, symbol, sorted content hashes)[..16]
+//! content hash = sha256(source with comments and whitespace stripped)
+//! ```
+//!
+//! The content is whatever the finding is about: a function body for
+//! duplicates (both sides, so the pair matches in either order), the flagged
+//! line for dead exports and drift, the whole module for orphans. Paths never
+//! go in, so moving or renaming a file keeps its fingerprints; editing the
+//! code itself produces a new one, which is the point.
+//!
+//! Fingerprints are matched by baselines (`.ground/baseline.json`), by
+//! `ignore.fingerprints` in `.ground.yml`, and by `ground_diff`.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use crate::computations::function_dry::normalize_function_body;
+
+/// Where `ground check run --write-baseline` puts the baseline, relative to
+/// the checked directory
+pub const BASELINE_FILE: &str = ".ground/baseline.json";
+
+#[derive(Error, Debug)]
+pub enum BaselineError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    
+    #[error("Invalid baseline {path}: {message}")]
+    Parse { path: PathBuf, message: String },
+}
+
+/// Hash of source with comments and whitespace stripped
+pub fn content_hash(source: &str) -> String {
+    let digest = Sha256::digest(normalize_function_body(source).as_bytes());
+    format!("{:x}", digest)[..16].to_string()
+}
+
+/// Fingerprint a finding from its kind, symbol, and content hashes
+///
+/// Content hashes are sorted, so a pair fingerprints the same whichever
+/// side is reported first.
+pub fn fingerprint(kind: &str, symbol: Option<&str>, content_hashes: &[&str]) -> String {
+    let mut hashes = content_hashes.to_vec();
+    hashes.sort_unstable();
+    
+    let mut hasher = Sha256::new();
+    hasher.update(kind.as_bytes());
+    hasher.update([0]);
+    hasher.update(symbol.unwrap_or("").as_bytes());
+    for hash in hashes {
+        hasher.update([0]);
+        hasher.update(hash.as_bytes());
+    }
+    format!("{:x}", hasher.finalize())[..16].to_string()
+}
+
+/// Fingerprint a finding about one file: the given line if there is one,
+/// otherwise the whole file
+///
+/// Falls back to the file name when the file can't be read (deleted since
+/// the check ran), which is still better than the full path.
+pub fn fingerprint_location(kind: &str, symbol: Option<&str>, file: &Path, line: Option<usize>) -> String {
+    let content = std::fs::read_to_string(file).ok();
+    let source = match (&content, line) {
+        (Some(content), Some(line)) => content.lines().nth(line.saturating_sub(1)).unwrap_or(content),
+        (Some(content), None) => content,
+        (None, _) => file.file_name().and_then(|n| n.to_str()).unwrap_or(""),
+    };
+    fingerprint(kind, symbol, &[&content_hash(source)])
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Baselines
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Findings accepted as known, by fingerprint
+///
+/// Checks run against a baseline only report findings it doesn't contain,
+/// so CI can fail on new problems without first fixing every old one.
+/// Entries can expire, after which the finding is reported again.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Baseline {
+    pub created_at: Option<DateTime<Utc>>,
+    pub fingerprints: BTreeSet<String>,
+    /// When accepted findings stop being suppressed, by fingerprint
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub expires: BTreeMap<String, DateTime<Utc>>,
+}
+
+impl Baseline {
+    /// Baseline of the given fingerprints, stamped now
+    pub fn new(fingerprints: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            created_at: Some(Utc::now()),
+            fingerprints: fingerprints.into_iter().collect(),
+            expires: BTreeMap::new(),
+        }
+    }
+    
+    /// Expire every entry at `at`
+    pub fn expiring(mut self, at: DateTime<Utc>) -> Self {
+        self.expires = self.fingerprints.iter().map(|f| (f.clone(), at)).collect();
+        self
+    }
+    
+    /// Load a baseline file
+    pub fn load(path: &Path) -> Result<Self, BaselineError> {
+        let contents = std::fs::read_to_string(path)?;
+        serde_json::from_str(&contents).map_err(|e| BaselineError::Parse {
+            path: path.to_path_buf(),
+            message: e.to_string(),
+        })
+    }
+    
+    /// Load `<dir>/.ground/baseline.json` if there is one
+    pub fn find_in(dir: &Path) -> Option<Self> {
+        let path = dir.join(BASELINE_FILE);
+        path.exists().then(|| Self::load(&path).ok()).flatten()
+    }
+    
+    /// Write the baseline, creating its directory if needed
+    pub fn save(&self, path: &Path) -> Result<(), BaselineError> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(|e| BaselineError::Parse {
+            path: path.to_path_buf(),
+            message: e.to_string(),
+        })?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+    
+    /// Whether a finding is accepted (present and not expired)
+    pub fn contains(&self, fingerprint: &str) -> bool {
+        self.fingerprints.contains(fingerprint)
+            && self.expires.get(fingerprint).is_none_or(|at| *at > Utc::now())
+    }
+    
+    /// Drop entries that expired before `now`, returning their fingerprints
+    pub fn prune_expired(&mut self, now: DateTime<Utc>) -> Vec<String> {
+        let expired: Vec<String> = self.expires.iter()
+            .filter(|(_, at)| **at <= now)
+            .map(|(fingerprint, _)| fingerprint.clone())
+            .collect();
+        for fingerprint in &expired {
+            self.fingerprints.remove(fingerprint);
+            self.expires.remove(fingerprint);
+        }
+        expired
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+    
+    #[test]
+    fn test_fingerprint_survives_moves_not_edits() {
+        let dir = tempdir().unwrap();
+        let body = "export function stray(x: number) {\n  // scale it\n  return x * 2;\n}\n";
+        let before = dir.path().join("src/lib/stray.ts");
+        let after = dir.path().join("src/utils/moved.ts");
+        for path in [&before, &after] {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        }
+        std::fs::write(&before, body).unwrap();
+        std::fs::write(&after, body.replace("  // scale it\n", "").replace("  ", "    ")).unwrap();
+        
+        // Moved and reformatted: same fingerprint
+        let fp = fingerprint_location("orphans", None, &before, None);
+        assert_eq!(fp, fingerprint_location("orphans", None, &after, None));
+        assert_eq!(fp.len(), 16);
+        
+        // Same content, different kind or symbol: different fingerprint
+        assert_ne!(fp, fingerprint_location("dead_exports", Some("stray"), &before, Some(1)));
+        
+        // Edited: different fingerprint
+        std::fs::write(&after, body.replace("x * 2", "x * 3")).unwrap();
+        assert_ne!(fp, fingerprint_location("orphans", None, &after, None));
+        
+        // Pairs match in either order
+        let (a, b) = (content_hash("return a;"), content_hash("return b;"));
+        assert_eq!(fingerprint("dup", Some("f"), &[&a, &b]), fingerprint("dup", Some("f"), &[&b, &a]));
+        
+        let baseline = Baseline::new([fp.clone()]);
+        let path = dir.path().join(BASELINE_FILE);
+        baseline.save(&path).unwrap();
+        assert!(Baseline::find_in(dir.path()).unwrap().contains(&fp));
+        
+        // Expired entries stop suppressing, and pruning removes them
+        let mut expired = Baseline::new([fp.clone(), "kept".to_string()])
+            .expiring(Utc::now() - chrono::Duration::days(1));
+        expired.expires.remove("kept");
+        assert!(!expired.contains(&fp));
+        assert_eq!(expired.prune_expired(Utc::now()), vec![fp]);
+        assert!(expired.contains("kept"));
+    }
+}
@@ -39,6 +39,7 @@
 //! | Duplicates | "Are these files the same?" | `ground compare` |
 //! | Dead code | "Is this used anywhere?" | `ground count uses` |
 //! | Orphans | "Does anything connect to this?" | `ground check connections` |
+//! | Unused deps | "Does the package import this?" | `ground check dependencies` |
 
 pub mod computations;
 pub mod registry;
@@ -50,13 +51,21 @@ pub mod config;
 pub mod report;
 pub mod loom;
 pub mod ui_resources;
+pub mod check;
+pub mod fingerprint;
+pub mod audit;
+pub mod calibration;
 
 use std::path::Path;
 use thiserror::Error;
 
-pub use computations::{SimilarityEvidence, UsageEvidence, ConnectivityEvidence};
+pub use computations::{SimilarityEvidence, UsageEvidence, ConnectivityEvidence, DependencyEvidence, HealthReport, HealthTrend};
 pub use registry::VerificationRegistry;
-pub use claims::{DryViolation, ExistenceClaim, ConnectivityClaim, ClaimRejected};
+pub use claims::{DryViolation, ExistenceClaim, ConnectivityClaim, UnusedDependencyClaim, ClaimRejected};
+pub use check::{run_check, CheckKind, Scope, CheckOptions, CheckProgress, CheckReport, CheckError, Finding, Severity};
+pub use fingerprint::{Baseline, BaselineError};
+pub use audit::{AuditOptions, AuditRun, AuditError};
+pub use calibration::{CalibrationReport, CheckCalibration, Feedback, FixOutcome};
 
 /// Configuration for claim thresholds
 #[derive(Debug, Clone)]
@@ -98,6 +107,9 @@ pub enum VerifiedTriadError {
     
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
+    
+    #[error("No audit has found {0}; pass its check and confidence")]
+    UnknownFinding(String),
 }
 
 impl VerifiedTriad {
@@ -130,6 +142,21 @@ impl VerifiedTriad {
         Ok(evidence)
     }
     
+    /// Compute similarity between two code snippets (DRY level)
+    ///
+    /// Evidence is recorded under `snippet:<sha256>` keys, which can be
+    /// passed to `claim_dry_violation` like file paths.
+    pub fn compare_snippets(
+        &mut self,
+        code_a: &str,
+        code_b: &str,
+        language: &str,
+    ) -> Result<SimilarityEvidence, VerifiedTriadError> {
+        let evidence = computations::compute_snippet_similarity(code_a, code_b, language);
+        self.registry.record_similarity(&evidence)?;
+        Ok(evidence)
+    }
+    
     /// Count usages of a symbol (Rams level)
     pub fn count_usages(
         &mut self,
@@ -141,6 +168,20 @@ impl VerifiedTriad {
         Ok(evidence)
     }
     
+    /// Find everything renaming a symbol would touch
+    ///
+    /// Records the usage counts it gathered, so a dead-code claim can follow.
+    pub fn rename_impact(
+        &mut self,
+        symbol: &str,
+        path: impl AsRef<Path>,
+        graph: Option<&computations::SymbolGraph>,
+    ) -> Result<computations::RenameImpact, VerifiedTriadError> {
+        let impact = computations::analyze_rename_impact(symbol, path.as_ref(), graph)?;
+        self.registry.record_usage(&impact.usage)?;
+        Ok(impact)
+    }
+    
     /// Analyze connectivity of a module (Heidegger level)
     pub fn analyze_connectivity(
         &mut self,
@@ -151,6 +192,95 @@ impl VerifiedTriad {
         Ok(evidence)
     }
     
+    /// Cross-reference a package's package.json against its imports
+    pub fn analyze_dependencies(
+        &mut self,
+        package_dir: impl AsRef<Path>,
+    ) -> Result<DependencyEvidence, VerifiedTriadError> {
+        let evidence = computations::analyze_dependencies(package_dir.as_ref(), None)?;
+        self.registry.record_dependencies(&evidence)?;
+        Ok(evidence)
+    }
+    
+    /// Compute the repo health score and its change since the last run
+    ///
+    /// Every run is recorded, so the next call reports deltas against this one.
+    pub fn compute_health(
+        &mut self,
+        directory: impl AsRef<Path>,
+    ) -> Result<(HealthReport, Option<HealthTrend>), VerifiedTriadError> {
+        let report = computations::compute_health(directory.as_ref(), self.thresholds.dry_similarity)?;
+        let trend = self.registry.latest_health(&report.directory)?
+            .map(|previous| report.trend_since(&previous));
+        self.registry.record_health(&report)?;
+        Ok((report, trend))
+    }
+    
+    /// Run the check suite and health score over each root and record the
+    /// results (see [`audit`])
+    pub fn audit(&mut self, options: &AuditOptions) -> Result<AuditRun, AuditError> {
+        audit::run_audit(self, options)
+    }
+    
+    /// Previous audit runs (newest first)
+    pub fn audit_history(&self, limit: usize) -> Result<Vec<AuditRun>, VerifiedTriadError> {
+        Ok(self.registry.audit_history(limit)?)
+    }
+    
+    /// Record whether the fix for a finding was accepted or rejected
+    ///
+    /// The check and confidence default to what the most recent audit that
+    /// found the fingerprint reported.
+    pub fn record_feedback(
+        &mut self,
+        fingerprint: &str,
+        outcome: FixOutcome,
+        check: Option<CheckKind>,
+        confidence: Option<f64>,
+        note: Option<String>,
+    ) -> Result<Feedback, VerifiedTriadError> {
+        let audited = if check.is_none() || confidence.is_none() {
+            self.registry.latest_audit_finding(fingerprint)?
+        } else {
+            None
+        };
+        let check = check.or(audited.as_ref().map(|(kind, _)| *kind));
+        let confidence = confidence.or(audited.as_ref().and_then(|(_, f)| f.confidence.as_ref()).map(|c| c.score));
+        let (Some(check), Some(confidence)) = (check, confidence) else {
+            return Err(VerifiedTriadError::UnknownFinding(fingerprint.to_string()));
+        };
+        
+        let feedback = Feedback {
+            fingerprint: fingerprint.to_string(),
+            check,
+            confidence: confidence.clamp(0.0, 1.0),
+            outcome,
+            note,
+            recorded_at: chrono::Utc::now(),
+        };
+        self.registry.record_feedback(&feedback)?;
+        Ok(feedback)
+    }
+    
+    /// Predicted confidence against observed precision for each check with
+    /// recorded outcomes; the target defaults to the auto-fix threshold
+    pub fn calibration(&self, target_precision: Option<f64>) -> Result<CalibrationReport, VerifiedTriadError> {
+        let feedback = self.registry.feedback(None)?;
+        Ok(calibration::calibrate(
+            &feedback,
+            target_precision.unwrap_or(computations::AUTO_FIX_THRESHOLD),
+        ))
+    }
+    
+    /// Previous health runs for a directory (newest first)
+    pub fn health_history(
+        &self,
+        directory: impl AsRef<Path>,
+        limit: usize,
+    ) -> Result<Vec<HealthReport>, VerifiedTriadError> {
+        Ok(self.registry.health_history(directory.as_ref(), limit)?)
+    }
+    
     /// Claim a DRY violation (requires prior similarity computation)
     pub fn claim_dry_violation(
         &self,
@@ -207,6 +337,23 @@ impl VerifiedTriad {
         ).map_err(VerifiedTriadError::ClaimRejected)
     }
     
+    /// Claim a package.json dependency is unused (requires prior dependency analysis)
+    pub fn claim_unused_dependency(
+        &self,
+        package_dir: impl AsRef<Path>,
+        dependency: &str,
+        reason: impl Into<String>,
+    ) -> Result<UnusedDependencyClaim, VerifiedTriadError> {
+        let evidence = self.registry.get_dependencies(package_dir.as_ref())?
+            .ok_or_else(|| ClaimRejected::NoEvidence {
+                claim_type: "unused dependency".to_string(),
+                suggestion: format!("Run: ground check dependencies {:?}", package_dir.as_ref()),
+            })?;
+        
+        UnusedDependencyClaim::from_evidence(evidence, dependency, reason.into())
+            .map_err(VerifiedTriadError::ClaimRejected)
+    }
+    
     /// Get current thresholds
     pub fn thresholds(&self) -> &TriadThresholds {
         &self.thresholds
@@ -238,4 +385,27 @@ mod tests {
             _ => panic!("Expected NoEvidence error"),
         }
     }
+    
+    #[test]
+    fn test_unused_dependency_claim_requires_analysis() {
+        let dir = tempdir().unwrap();
+        let pkg = dir.path().join("pkg");
+        std::fs::create_dir_all(&pkg).unwrap();
+        std::fs::write(pkg.join("package.json"), r#"{ "dependencies": { "zod": "^3.0.0", "lodash": "^4.0.0" } }"#).unwrap();
+        std::fs::write(pkg.join("index.ts"), "import { z } from 'zod';\nexport const schema = z.string();\n").unwrap();
+        
+        let mut vt = VerifiedTriad::new(dir.path().join("test.db")).unwrap();
+        assert!(matches!(
+            vt.claim_unused_dependency(&pkg, "lodash", "never imported"),
+            Err(VerifiedTriadError::ClaimRejected(ClaimRejected::NoEvidence { .. }))
+        ));
+        
+        vt.analyze_dependencies(&pkg).unwrap();
+        let claim = vt.claim_unused_dependency(&pkg, "lodash", "never imported").unwrap();
+        assert_eq!(claim.dependency, "lodash");
+        
+        // Imported, or not declared at all: blocked
+        assert!(vt.claim_unused_dependency(&pkg, "zod", "looks unused").is_err());
+        assert!(vt.claim_unused_dependency(&pkg, "react", "looks unused").is_err());
+    }
 }
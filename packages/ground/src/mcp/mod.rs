@@ -17,15 +17,29 @@
 //! - `ground_find_duplicate_functions` - Find function-level duplicates
 //! - `ground_find_dead_exports` - Find unused exports (traces re-exports)
 //! - `ground_find_orphans` - Batch scan for orphaned modules
+//! - `ground_find_barrel_bloat` - Barrels re-exporting heavy modules most consumers skip
+//! - `ground_find_signature_clusters` - Same API exported under different names across packages
 //! - `ground_check_environment` - Detect Workers/Node.js API safety issues
 //! - `ground_check_connections` - Check module connectivity
+//! - `ground_check_dependencies` - Unused and undeclared package.json dependencies
+//! - `ground_check_conventions` - Directory layout against `.ground.yml` conventions
 //! - `ground_count_uses` - Count symbol uses (distinguishes type-only)
+//! - `ground_rename_impact` - Everything renaming a symbol would touch, including dynamic uses
+//! - `ground_trace_feature` - Removal plan for a feature: code reachable only through its entry
 //! - `ground_compare` - Compare two files for similarity
+//! - `ground_compare_snippets` - Compare two in-memory snippets for similarity
 //! - `ground_suggest_fix` - Get refactoring suggestions
+//! - `ground_health` - Composite health score per package, with trend since last run
+//! - `ground_quick_scan` - Sampled duplication and adoption estimates, and whether a full audit is warranted
+//!
+//! ### Calibration
+//! - `ground_record_feedback` - Record whether the fix for a finding was accepted or rejected
+//! - `ground_calibration` - Predicted confidence against observed precision per check
 //!
 //! ### Claims (Audit Trail)
 //! - `ground_claim_dead_code` - Claim code is dead (blocked until verified)
 //! - `ground_claim_orphan` - Claim module is orphaned (blocked until verified)
+//! - `ground_claim_unused_dependency` - Claim a dependency is unused (blocked until verified)
 
 use std::path::{Path, PathBuf};
 use std::sync::Mutex;
@@ -37,10 +51,15 @@ use crate::{VerifiedTriad, VerifiedTriadError};
 use crate::computations::{analyze_function_dry_with_options, FunctionDryOptions};
 use crate::computations::environment::{analyze_environment_safety, WarningSeverity, RuntimeEnvironment};
 use crate::computations::{BloomFilter, HyperLogLog};
-use crate::computations::confidence::orphan_confidence;
+use crate::computations::confidence::{orphan_confidence, ConfidenceScore};
 use crate::computations::framework::{detect_framework, is_implicit_entry};
+use crate::computations::generated::{is_generated_dir, is_generated_file};
+use crate::computations::walk::source_files;
 use crate::monorepo::{detect_monorepo, suggest_refactoring, generate_loom_command};
 use crate::config::GroundConfig;
+use crate::check::CheckKind;
+use crate::fingerprint::{content_hash, fingerprint, fingerprint_location, Baseline};
+use crate::computations::{PublicApiManifest, SimilarityExplanation};
 
 /// Log progress to stderr (visible in MCP server logs)
 #[allow(unused_macros)]
@@ -82,6 +101,161 @@ lazy_static::lazy_static! {
     static ref SYMBOL_GRAPH: Mutex<Option<crate::computations::SymbolGraph>> = Mutex::new(None);
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// Progress Reporting
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Files processed between progress updates
+const PROGRESS_EVERY: usize = 25;
+
+/// A progress update from a long-running tool call
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Progress {
+    /// What the tool is doing (e.g. "parsing", "orphans")
+    pub phase: String,
+    /// Units of work done in this phase
+    pub current: usize,
+    /// Total units in this phase, if known
+    pub total: Option<usize>,
+}
+
+impl Progress {
+    /// Human-readable status line
+    pub fn message(&self) -> String {
+        match self.total {
+            Some(total) => format!("{}: {}/{}", self.phase, self.current, total),
+            None => format!("{}: {}", self.phase, self.current),
+        }
+    }
+}
+
+type ProgressSink = Box<dyn Fn(&Progress)>;
+
+thread_local! {
+    /// Receiver for progress updates during the current tool call
+    static PROGRESS_SINK: std::cell::RefCell<Option<ProgressSink>> = std::cell::RefCell::new(None);
+}
+
+/// Report progress to the active sink, if any
+fn report_progress(phase: &str, current: usize, total: Option<usize>) {
+    PROGRESS_SINK.with(|sink| {
+        if let Some(ref sink) = *sink.borrow() {
+            sink(&Progress { phase: phase.to_string(), current, total });
+        }
+    });
+}
+
+/// Report file-level progress, throttled to every `PROGRESS_EVERY` files
+pub(crate) fn report_file_progress(phase: &str, current: usize, total: usize) {
+    if current.is_multiple_of(PROGRESS_EVERY) || current == total {
+        report_progress(phase, current, Some(total));
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Pagination
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Findings per page when the caller doesn't say
+const DEFAULT_PAGE_SIZE: usize = 100;
+
+/// Largest page a caller can ask for
+const MAX_PAGE_SIZE: usize = 1000;
+
+/// A window over a list of findings. Tools sort their findings before
+/// slicing, so the same call with the next page (or the returned
+/// `next_cursor`) picks up where the last one stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Page {
+    offset: usize,
+    size: usize,
+}
+
+impl Page {
+    /// From `page_size` plus `cursor` (an offset from `next_cursor`) or
+    /// `page` (1-based); the cursor wins when both are given
+    fn from_args(args: &Value) -> Self {
+        let size = args.get("page_size")
+            .and_then(|v| v.as_u64())
+            .map_or(DEFAULT_PAGE_SIZE, |s| (s as usize).clamp(1, MAX_PAGE_SIZE));
+        let offset = match args.get("cursor").and_then(|v| v.as_str()).and_then(|c| c.parse::<usize>().ok()) {
+            Some(offset) => offset,
+            None => {
+                let page = args.get("page").and_then(|v| v.as_u64()).unwrap_or(1).max(1) as usize;
+                (page - 1).saturating_mul(size)
+            }
+        };
+        Self { offset, size }
+    }
+    
+    fn slice<'a, T>(&self, items: &'a [T]) -> &'a [T] {
+        let start = self.offset.min(items.len());
+        let end = self.offset.saturating_add(self.size).min(items.len());
+        &items[start..end]
+    }
+    
+    /// Cursor for the page after this one, if `total` runs past it
+    fn next_cursor(&self, total: usize) -> Option<String> {
+        let next = self.offset.saturating_add(self.size);
+        (next < total).then(|| next.to_string())
+    }
+    
+    /// The `pagination` object for a list of `total` findings
+    fn describe(&self, total: usize) -> Value {
+        json!({
+            "page": self.offset / self.size + 1,
+            "page_size": self.size,
+            "total": total,
+            "total_pages": total.div_ceil(self.size),
+            "next_cursor": self.next_cursor(total)
+        })
+    }
+}
+
+/// Replace the sorted list at `field` with the requested page of it and
+/// describe the page under `pagination`
+fn paginate(result: &mut Value, field: &str, page: Page) {
+    let Some(items) = result.get(field).and_then(|v| v.as_array()).cloned() else {
+        return;
+    };
+    let total = items.len();
+    result[field] = json!(page.slice(&items));
+    result["pagination"] = page.describe(total);
+}
+
+/// Page each sorted list under `fields` with the same window, returning the
+/// `pagination` object; `total` counts each list and `total_pages` follows
+/// the longest
+fn paginate_each(result: &mut Value, fields: &[&str], page: Page) -> Value {
+    let mut totals = serde_json::Map::new();
+    for &field in fields {
+        let items = result[field].as_array().cloned().unwrap_or_default();
+        totals.insert(field.to_string(), json!(items.len()));
+        result[field] = json!(page.slice(&items));
+    }
+    let largest = totals.values().filter_map(|t| t.as_u64()).max().unwrap_or(0) as usize;
+    let mut pagination = page.describe(largest);
+    pagination["total"] = Value::Object(totals);
+    pagination
+}
+
+/// Add the pagination parameters to a tool's input schema
+fn paginated(mut schema: Value) -> Value {
+    schema["properties"]["page"] = json!({
+        "type": "integer",
+        "description": "1-based page of findings to return (default: 1)"
+    });
+    schema["properties"]["page_size"] = json!({
+        "type": "integer",
+        "description": format!("Findings per page (default: {}, max: {})", DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE)
+    });
+    schema["properties"]["cursor"] = json!({
+        "type": "string",
+        "description": "next_cursor from the previous page; takes precedence over page"
+    });
+    schema
+}
+
 /// MCP Tool definitions for Ground
 pub fn list_tools() -> Vec<ToolDefinition> {
     vec![
@@ -104,6 +278,28 @@ pub fn list_tools() -> Vec<ToolDefinition> {
                 "required": ["file_a", "file_b"]
             }),
         },
+        ToolDefinition {
+            name: "ground_compare_snippets".to_string(),
+            description: "Compare two code snippets (e.g. diff hunks) for similarity without writing them to disk. Evidence is keyed by content hash; claim with the returned snippet keys.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "code_a": {
+                        "type": "string",
+                        "description": "First snippet"
+                    },
+                    "code_b": {
+                        "type": "string",
+                        "description": "Second snippet"
+                    },
+                    "language": {
+                        "type": "string",
+                        "description": "Language of both snippets: typescript, javascript, rust, python (or an extension like ts). Other languages fall back to line/token similarity."
+                    }
+                },
+                "required": ["code_a", "code_b", "language"]
+            }),
+        },
         ToolDefinition {
             name: "ground_count_uses".to_string(),
             description: "Count how many times a symbol is used. You need to do this before you can claim it's dead code. Distinguishes between runtime usages and type-only usages (TypeScript generics, type annotations, etc.). Type-only usages count as valid usage for types/interfaces.".to_string(),
@@ -125,7 +321,7 @@ pub fn list_tools() -> Vec<ToolDefinition> {
         ToolDefinition {
             name: "ground_check_connections".to_string(),
             description: "Check if a module is connected to other code. Understands Cloudflare Workers (routes, crons, bindings). You need to do this before you can claim it's orphaned.".to_string(),
-            input_schema: json!({
+            input_schema: paginated(json!({
                 "type": "object",
                 "properties": {
                     "module_path": {
@@ -134,12 +330,40 @@ pub fn list_tools() -> Vec<ToolDefinition> {
                     }
                 },
                 "required": ["module_path"]
+            })),
+        },
+        ToolDefinition {
+            name: "ground_check_dependencies".to_string(),
+            description: "Cross-reference package.json dependencies against what the package imports. Finds unused dependencies (declared, never imported, run by a script, or configured) and missing ones (imported but not declared). Checks every package under the directory. You need to do this before you can claim a dependency is unused.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "directory": {
+                        "type": "string",
+                        "description": "Package directory, or a directory containing packages"
+                    }
+                },
+                "required": ["directory"]
             }),
         },
         ToolDefinition {
-            name: "ground_find_duplicate_functions".to_string(),
-            description: "Find duplicate functions across files AND within files. Catches inter-file duplicates (same name, different files) and intra-file duplicates (different names, similar implementation in same file). Research shows same-file clones have ~18% higher bug propagation risk. Supports cross-package detection in monorepos. Loads .ground.yml for ignore patterns.".to_string(),
+            name: "ground_check_conventions".to_string(),
+            description: "Check the directory layout against the conventions declared in .ground.yml (e.g. workers under workers/*/src with an index.ts, no shared code under apps/). Reports misplaced files, files in forbidden locations, and directories missing required files, each with a suggested path to move the file to or create.".to_string(),
             input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "directory": {
+                        "type": "string",
+                        "description": "Directory to check (rules resolve against the .ground.yml location)"
+                    }
+                },
+                "required": ["directory"]
+            }),
+        },
+        ToolDefinition {
+            name: "ground_find_duplicate_functions".to_string(),
+            description: "Find duplicate functions across files AND within files. Catches inter-file duplicates (same name, different files) and intra-file duplicates (different names, similar implementation in same file). Each duplicate has a clone_type: exact and renamed clones are safe to consolidate, gapped clones (statements added or removed) need review. Research shows same-file clones have ~18% higher bug propagation risk. Supports cross-package detection in monorepos. Loads .ground.yml for ignore patterns.".to_string(),
+            input_schema: paginated(json!({
                 "type": "object",
                 "properties": {
                     "directory": {
@@ -181,7 +405,7 @@ pub fn list_tools() -> Vec<ToolDefinition> {
                     }
                 },
                 "required": []
-            }),
+            })),
         },
         // Claim tools (audit trail - blocked until verified)
         ToolDefinition {
@@ -220,6 +444,28 @@ pub fn list_tools() -> Vec<ToolDefinition> {
                 "required": ["module_path", "reason"]
             }),
         },
+        ToolDefinition {
+            name: "ground_claim_unused_dependency".to_string(),
+            description: "Claim that a package.json dependency is unused. Blocked if you haven't checked the package's dependencies first, or if the package actually uses it.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "package_dir": {
+                        "type": "string",
+                        "description": "Directory containing the package.json"
+                    },
+                    "dependency": {
+                        "type": "string",
+                        "description": "Package name as declared"
+                    },
+                    "reason": {
+                        "type": "string",
+                        "description": "Why you're claiming this"
+                    }
+                },
+                "required": ["package_dir", "dependency", "reason"]
+            }),
+        },
         // Other tools
         ToolDefinition {
             name: "ground_suggest_fix".to_string(),
@@ -260,7 +506,7 @@ pub fn list_tools() -> Vec<ToolDefinition> {
         ToolDefinition {
             name: "ground_find_orphans".to_string(),
             description: "Find orphaned modules in a directory. Scans all TypeScript/JavaScript files and identifies those with no incoming connections (nothing imports them) and no architectural connections (not a Worker entry point, not a package.json bin entry).".to_string(),
-            input_schema: json!({
+            input_schema: paginated(json!({
                 "type": "object",
                 "properties": {
                     "directory": {
@@ -273,12 +519,92 @@ pub fn list_tools() -> Vec<ToolDefinition> {
                     }
                 },
                 "required": ["directory"]
+            })),
+        },
+        ToolDefinition {
+            name: "ground_find_barrel_bloat".to_string(),
+            description: "Find barrel files (index.ts re-exporting a directory) and measure how much of their surface consumers actually import. Flags re-exported modules that pull in heavy dependencies (packages no sibling module needs, or lots of source) while most of the barrel's consumers never use them, with a suggestion for splitting them out. Every consumer of a barrel bundles what it re-exports, so these drive Worker bundle size. Reuses the ground_build_graph graph when it covers the directory.".to_string(),
+            input_schema: paginated(json!({
+                "type": "object",
+                "properties": {
+                    "directory": {
+                        "type": "string",
+                        "description": "Directory to look for barrels in (consumers are found across the whole project)"
+                    },
+                    "max_share": {
+                        "type": "number",
+                        "description": "Flag heavy modules used by less than this share of a barrel's consumers (0.0-1.0, default: 0.5)"
+                    },
+                    "heavy_kb": {
+                        "type": "number",
+                        "description": "Local source (KB, transitively) that makes a module heavy on its own (default: 32)"
+                    }
+                },
+                "required": ["directory"]
+            })),
+        },
+        ToolDefinition {
+            name: "ground_find_signature_clusters".to_string(),
+            description: "Find exported functions in different packages that look like the same API even though their bodies differ: names that mean the same once split into words, abbreviations expanded and word order ignored (formatDate, fmtDate, dateFormat), with the same parameter count and compatible return types. Each cluster suggests a canonical API (the most common name, from the package that has it). Catches convergent reinvention before it becomes duplication; copies with identical bodies are left to ground_find_duplicate_functions.".to_string(),
+            input_schema: paginated(json!({
+                "type": "object",
+                "properties": {
+                    "directory": {
+                        "type": "string",
+                        "description": "Directory to scan, usually the monorepo root"
+                    },
+                    "include_tests": {
+                        "type": "boolean",
+                        "description": "Include test files in scan (default: false)"
+                    },
+                    "within_package": {
+                        "type": "boolean",
+                        "description": "Also report clusters whose members share a package, across files (default: false)"
+                    }
+                },
+                "required": ["directory"]
+            })),
+        },
+        ToolDefinition {
+            name: "ground_rename_impact".to_string(),
+            description: "Check what renaming an exported symbol would touch before doing it. Returns every line mentioning it (classified as definition, import, reference, type-only, string, comment, or config), dynamic uses a rename can't follow (name strings, obj['name'] lookups, indexed namespace imports, export * re-exports), and the files importing it grouped by package, flagging packages other than the defining one. Also records usage evidence, so ground_claim_dead_code works afterwards. Reuses the ground_build_graph graph when it covers the path.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "symbol": {
+                        "type": "string",
+                        "description": "Symbol to rename"
+                    },
+                    "path": {
+                        "type": "string",
+                        "description": "File defining the symbol, or a directory to search (consumers are found across the whole workspace)"
+                    }
+                },
+                "required": ["symbol", "path"]
+            }),
+        },
+        ToolDefinition {
+            name: "ground_trace_feature".to_string(),
+            description: "Plan the removal of a whole feature from its entry (a route, a module, or one exported symbol). Returns the exclusive closure (files reachable only through the entry), exports of remaining files that lose their last consumer, test files that only test the feature, and imports of the feature from code that stays (edits needed). Replaces rounds of dead-export findings with one reviewed plan. Reuses the ground_build_graph graph when it covers the entry.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "entry": {
+                        "type": "string",
+                        "description": "Entry file (e.g. src/routes/legacy/+page.svelte), or file#symbol for one export"
+                    },
+                    "symbol": {
+                        "type": "string",
+                        "description": "Optional: exported symbol of the entry file (same as file#symbol)"
+                    }
+                },
+                "required": ["entry"]
             }),
         },
         ToolDefinition {
             name: "ground_find_dead_exports".to_string(),
             description: "Find exports in a module that are never imported elsewhere in the codebase. Helps identify unused API surface.".to_string(),
-            input_schema: json!({
+            input_schema: paginated(json!({
                 "type": "object",
                 "properties": {
                     "module_path": {
@@ -295,7 +621,7 @@ pub fn list_tools() -> Vec<ToolDefinition> {
                     }
                 },
                 "required": ["module_path"]
-            }),
+            })),
         },
         // Graph-based analysis (fast for repo-wide scans)
         ToolDefinition {
@@ -315,7 +641,7 @@ pub fn list_tools() -> Vec<ToolDefinition> {
         ToolDefinition {
             name: "ground_query_dead".to_string(),
             description: "Query the symbol graph for dead exports. Run ground_build_graph first. Automatically filters out framework conventions (SvelteKit load/GET/POST, hooks, etc.) unless raw=true.".to_string(),
-            input_schema: json!({
+            input_schema: paginated(json!({
                 "type": "object",
                 "properties": {
                     "file": {
@@ -328,13 +654,112 @@ pub fn list_tools() -> Vec<ToolDefinition> {
                     }
                 },
                 "required": []
+            })),
+        },
+        // Repo health
+        ToolDefinition {
+            name: "ground_health".to_string(),
+            description: "Composite health score (0-100) combining duplication density, dead export ratio, orphan count, design token adoption, and import cycles. Computed per package and overall; each run is recorded so the response includes deltas since the previous run.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "directory": {
+                        "type": "string",
+                        "description": "Repository or package root to score"
+                    },
+                    "history": {
+                        "type": "integer",
+                        "description": "Optional: include this many previous runs (score and timestamp). Default: 0"
+                    }
+                },
+                "required": ["directory"]
+            }),
+        },
+        ToolDefinition {
+            name: "ground_quick_scan".to_string(),
+            description: "Estimate duplication density and design token adoption from a seeded, reproducible sample of files, with 95% confidence intervals. Takes seconds on a huge monorepo; the verdict says whether a full audit is warranted, not needed, or needs a bigger sample.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "directory": {
+                        "type": "string",
+                        "description": "Repository or package root to scan"
+                    },
+                    "sample_percent": {
+                        "type": "number",
+                        "description": "Optional: percent of files to sample. Default: 5"
+                    },
+                    "seed": {
+                        "type": "integer",
+                        "description": "Optional: sampling seed; the same seed picks the same files. Default: 0"
+                    },
+                    "duplication_budget": {
+                        "type": "number",
+                        "description": "Optional: audit warranted above this duplication density (percent). Default: 10"
+                    },
+                    "adoption_floor": {
+                        "type": "number",
+                        "description": "Optional: audit warranted below this token adoption (percent). Default: 70"
+                    },
+                    "include_tests": {
+                        "type": "boolean",
+                        "description": "Optional: sample test files too. Default: false"
+                    }
+                },
+                "required": ["directory"]
+            }),
+        },
+        // Calibration
+        ToolDefinition {
+            name: "ground_record_feedback".to_string(),
+            description: "Record whether the fix for a finding was accepted or rejected, keyed by its fingerprint. Outcomes feed ground_calibration. Check and confidence default to what the last audit that found the fingerprint reported.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "fingerprint": {
+                        "type": "string",
+                        "description": "Finding fingerprint"
+                    },
+                    "outcome": {
+                        "type": "string",
+                        "enum": ["accepted", "rejected"],
+                        "description": "accepted if the finding was real and the fix went in, rejected otherwise"
+                    },
+                    "check": {
+                        "type": "string",
+                        "description": "Optional: check that reported the finding (e.g. dead-exports)"
+                    },
+                    "confidence": {
+                        "type": "number",
+                        "description": "Optional: confidence the finding was reported with (0.0-1.0)"
+                    },
+                    "note": {
+                        "type": "string",
+                        "description": "Optional: why"
+                    }
+                },
+                "required": ["fingerprint", "outcome"]
+            }),
+        },
+        ToolDefinition {
+            name: "ground_calibration".to_string(),
+            description: "Compare predicted confidence with observed precision (share of fixes accepted) per check, in 0.1-wide buckets. Suggests the lowest auto-fix threshold whose findings met the target precision.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "target_precision": {
+                        "type": "number",
+                        "description": "Optional: precision an auto-fix threshold should deliver. Default: 0.9"
+                    }
+                },
+                "required": []
             }),
         },
         // AI-Native Tools
         ToolDefinition {
             name: "ground_analyze".to_string(),
             description: "Batch analysis: returns duplicates, dead exports, orphans, and environment issues in one call. Reduces agent round-trips. Each finding includes confidence scores and structured fix actions.".to_string(),
-            input_schema: json!({
+            input_schema: paginated(json!({
                 "type": "object",
                 "properties": {
                     "directory": {
@@ -360,12 +785,12 @@ pub fn list_tools() -> Vec<ToolDefinition> {
                     }
                 },
                 "required": ["directory"]
-            }),
+            })),
         },
         ToolDefinition {
             name: "ground_diff".to_string(),
             description: "Incremental analysis: only report NEW issues since a baseline. Compares against git base branch or saved baseline. Agents shouldn't re-process known issues.".to_string(),
-            input_schema: json!({
+            input_schema: paginated(json!({
                 "type": "object",
                 "properties": {
                     "directory": {
@@ -387,10 +812,14 @@ pub fn list_tools() -> Vec<ToolDefinition> {
                     "cross_package": {
                         "type": "boolean",
                         "description": "Scan across packages in monorepo. Default: false"
+                    },
+                    "baseline": {
+                        "type": "string",
+                        "description": "Baseline file of known finding fingerprints. Default: <directory>/.ground/baseline.json, if present"
                     }
                 },
                 "required": ["directory"]
-            }),
+            })),
         },
         ToolDefinition {
             name: "ground_verify_fix".to_string(),
@@ -423,7 +852,7 @@ pub fn list_tools() -> Vec<ToolDefinition> {
         ToolDefinition {
             name: "ground_find_drift".to_string(),
             description: "Find design system drift (violations of Canon design tokens). Detects hardcoded colors, spacing, typography, and Svelte 4 patterns that should use design tokens. Use 'extensions' to filter by file type for accurate CSS-only analysis.".to_string(),
-            input_schema: json!({
+            input_schema: paginated(json!({
                 "type": "object",
                 "properties": {
                     "directory": {
@@ -445,7 +874,7 @@ pub fn list_tools() -> Vec<ToolDefinition> {
                     }
                 },
                 "required": []
-            }),
+            })),
         },
         ToolDefinition {
             name: "ground_adoption_ratio".to_string(),
@@ -482,7 +911,7 @@ pub fn list_tools() -> Vec<ToolDefinition> {
         ToolDefinition {
             name: "ground_mine_patterns".to_string(),
             description: "Mine patterns to discover implicit design tokens. Analyzes CSS values across Svelte files to find repeated patterns that should become Canon tokens.".to_string(),
-            input_schema: json!({
+            input_schema: paginated(json!({
                 "type": "object",
                 "properties": {
                     "directory": {
@@ -495,7 +924,7 @@ pub fn list_tools() -> Vec<ToolDefinition> {
                     }
                 },
                 "required": []
-            }),
+            })),
         },
         ToolDefinition {
             name: "ground_explain".to_string(),
@@ -549,6 +978,24 @@ impl ToolResult {
     }
 }
 
+/// Handle a tool call, receiving progress updates as it runs
+///
+/// Long-running tools (`ground_build_graph`, `ground_analyze`,
+/// `ground_find_orphans`, `ground_find_duplicate_functions`) report their
+/// phase and files processed so clients can show progress instead of
+/// assuming the server hung.
+pub fn handle_tool_call_with_progress(
+    g: &mut VerifiedTriad,
+    tool_name: &str,
+    args: &Value,
+    on_progress: impl Fn(&Progress) + 'static,
+) -> ToolResult {
+    PROGRESS_SINK.with(|sink| *sink.borrow_mut() = Some(Box::new(on_progress)));
+    let result = handle_tool_call(g, tool_name, args);
+    PROGRESS_SINK.with(|sink| *sink.borrow_mut() = None);
+    result
+}
+
 /// Handle a tool call
 pub fn handle_tool_call(
     g: &mut VerifiedTriad,
@@ -557,16 +1004,24 @@ pub fn handle_tool_call(
 ) -> ToolResult {
     match tool_name {
         "ground_compare" => handle_compare(g, args),
+        "ground_compare_snippets" => handle_compare_snippets(g, args),
         "ground_count_uses" => handle_count_uses(g, args),
         "ground_check_connections" => handle_check_connections(g, args),
+        "ground_check_dependencies" => handle_check_dependencies(g, args),
+        "ground_check_conventions" => handle_check_conventions(args),
         "ground_find_duplicate_functions" => handle_find_duplicate_functions(args),
         // Claims (audit trail)
         "ground_claim_dead_code" => handle_claim_dead_code(g, args),
         "ground_claim_orphan" => handle_claim_orphan(g, args),
+        "ground_claim_unused_dependency" => handle_claim_unused_dependency(g, args),
         // Other tools
         "ground_suggest_fix" => handle_suggest_fix(args),
         "ground_check_environment" => handle_check_environment(args),
         "ground_find_orphans" => handle_find_orphans(args),
+        "ground_find_barrel_bloat" => handle_find_barrel_bloat(args),
+        "ground_find_signature_clusters" => handle_find_signature_clusters(args),
+        "ground_rename_impact" => handle_rename_impact(g, args),
+        "ground_trace_feature" => handle_trace_feature(args),
         "ground_find_dead_exports" => handle_find_dead_exports(args),
         // AI-Native tools
         "ground_analyze" => handle_batch_analyze(args),
@@ -581,6 +1036,12 @@ pub fn handle_tool_call(
         // Graph-based analysis
         "ground_build_graph" => handle_build_graph(args),
         "ground_query_dead" => handle_query_dead(args),
+        // Repo health
+        "ground_health" => handle_health(g, args),
+        "ground_quick_scan" => handle_quick_scan(args),
+        // Calibration
+        "ground_record_feedback" => handle_record_feedback(g, args),
+        "ground_calibration" => handle_calibration(g, args),
         _ => ToolResult::error(format!("Unknown tool: {}", tool_name)),
     }
 }
@@ -626,6 +1087,7 @@ fn handle_compare(g: &mut VerifiedTriad, args: &Value) -> ToolResult {
                 "is_duplicate": is_duplicate,
                 "threshold": threshold,
                 "evidence_id": evidence.id.to_string(),
+                "explanation": explanation_json(&evidence.explanation),
                 "cached_hint": was_cached,
                 "message": if is_duplicate {
                     format!("These look like duplicates ({:.1}% similar). You can now claim this with ground_claim_duplicate.", evidence.similarity * 100.0)
@@ -638,6 +1100,63 @@ fn handle_compare(g: &mut VerifiedTriad, args: &Value) -> ToolResult {
     }
 }
 
+fn handle_compare_snippets(g: &mut VerifiedTriad, args: &Value) -> ToolResult {
+    let code_a = match args.get("code_a").and_then(|v| v.as_str()) {
+        Some(s) => s,
+        None => return ToolResult::error("Missing: code_a"),
+    };
+    let code_b = match args.get("code_b").and_then(|v| v.as_str()) {
+        Some(s) => s,
+        None => return ToolResult::error("Missing: code_b"),
+    };
+    let language = match args.get("language").and_then(|v| v.as_str()) {
+        Some(s) => s,
+        None => return ToolResult::error("Missing: language"),
+    };
+    
+    match g.compare_snippets(code_a, code_b, language) {
+        Ok(evidence) => {
+            let threshold = g.thresholds().dry_similarity;
+            let is_duplicate = evidence.similarity >= threshold;
+            
+            ToolResult::success(json!({
+                "compared": true,
+                "similarity": evidence.similarity,
+                "similarity_percent": format!("{:.1}%", evidence.similarity * 100.0),
+                "line_similarity": evidence.line_similarity,
+                "token_overlap": evidence.token_overlap,
+                "ast_similarity": evidence.ast_similarity,
+                "is_duplicate": is_duplicate,
+                "threshold": threshold,
+                "evidence_id": evidence.id.to_string(),
+                "snippet_a": evidence.file_a.to_string_lossy(),
+                "snippet_b": evidence.file_b.to_string_lossy(),
+                "explanation": explanation_json(&evidence.explanation),
+                "message": if is_duplicate {
+                    format!("These snippets look like duplicates ({:.1}% similar). Use snippet_a/snippet_b in place of file paths when claiming.", evidence.similarity * 100.0)
+                } else {
+                    format!("Not duplicates ({:.1}% similar, need {:.0}%).", evidence.similarity * 100.0, threshold * 100.0)
+                }
+            }))
+        }
+        Err(e) => ToolResult::error(format!("Comparison failed: {}", e)),
+    }
+}
+
+/// Aligned regions with their shared tokens as one normalized line
+fn explanation_json(explanation: &SimilarityExplanation) -> Value {
+    json!({
+        "coverage_a": explanation.coverage_a,
+        "coverage_b": explanation.coverage_b,
+        "regions": explanation.regions.iter().map(|r| json!({
+            "lines_a": format!("{}-{}", r.lines_a.0, r.lines_a.1),
+            "lines_b": format!("{}-{}", r.lines_b.0, r.lines_b.1),
+            "similarity": r.similarity,
+            "shared": r.shared_text()
+        })).collect::<Vec<_>>()
+    })
+}
+
 fn handle_count_uses(g: &mut VerifiedTriad, args: &Value) -> ToolResult {
     let symbol = match args.get("symbol").and_then(|v| v.as_str()) {
         Some(s) => s,
@@ -742,7 +1261,7 @@ fn handle_check_connections(g: &mut VerifiedTriad, args: &Value) -> ToolResult {
                 })
             });
             
-            ToolResult::success(json!({
+            let mut result = json!({
                 "checked": true,
                 "module": module_path.to_string_lossy(),
                 "connections": {
@@ -752,6 +1271,7 @@ fn handle_check_connections(g: &mut VerifiedTriad, args: &Value) -> ToolResult {
                 },
                 "is_orphan": is_orphan,
                 "architectural": arch_info,
+                "state": evidence.state,
                 "evidence_id": evidence.id.to_string(),
                 "message": if evidence.has_architectural_connections() {
                     format!("This is a Cloudflare Worker with {} architectural connections.", 
@@ -761,13 +1281,28 @@ fn handle_check_connections(g: &mut VerifiedTriad, args: &Value) -> ToolResult {
                 } else {
                     format!("{} things connect to this ({} incoming, {} outgoing).", total, evidence.incoming_connections, evidence.outgoing_connections)
                 }
-            }))
+            });
+            paginate(&mut result, "state", Page::from_args(args));
+            ToolResult::success(result)
         }
         Err(e) => ToolResult::error(format!("Check failed: {}", e)),
     }
 }
 
 fn handle_find_duplicate_functions(args: &Value) -> ToolResult {
+    let mut result = find_duplicate_functions(args);
+    if result.success {
+        result.content["pagination"] = paginate_each(
+            &mut result.content,
+            &["duplicates", "cross_package_duplicates", "intra_file_duplicates"],
+            Page::from_args(args),
+        );
+    }
+    result
+}
+
+/// Every duplicate function under the directories, sorted by location
+fn find_duplicate_functions(args: &Value) -> ToolResult {
     let exclude_tests = args.get("exclude_tests")
         .and_then(|v| v.as_bool())
         .unwrap_or(false);
@@ -789,7 +1324,7 @@ fn handle_find_duplicate_functions(args: &Value) -> ToolResult {
     let config = if let Some(path) = config_path {
         GroundConfig::load(&path).unwrap_or_default()
     } else if let Some(ref dir) = base_dir {
-        find_config_in_ancestors(dir).unwrap_or_default()
+        GroundConfig::find_in_ancestors(dir).unwrap_or_default()
     } else {
         GroundConfig::load_default()
     };
@@ -880,7 +1415,8 @@ fn handle_find_duplicate_functions(args: &Value) -> ToolResult {
     let mut file_to_package: std::collections::HashMap<PathBuf, String> = std::collections::HashMap::new();
     let max_files = 500;
     
-    'outer: for dir in &directories {
+    'outer: for (i, dir) in directories.iter().enumerate() {
+        report_progress("collecting", i + 1, Some(directories.len()));
         let package_name = extract_package_name(dir);
         let mut dir_files: Vec<PathBuf> = Vec::new();
         collect_ts_files(dir, &mut dir_files);
@@ -912,6 +1448,7 @@ fn handle_find_duplicate_functions(args: &Value) -> ToolResult {
     }
     
     mcp_log!("Analyzing {} files for duplicate functions (threshold={:.0}%)", files.len(), threshold * 100.0);
+    report_progress("comparing functions", files.len(), None);
     
     match analyze_function_dry_with_options(&files, threshold, &options) {
         Ok(report) => {
@@ -934,6 +1471,16 @@ fn handle_find_duplicate_functions(args: &Value) -> ToolResult {
                     continue;
                 }
                 
+                let fingerprint = fingerprint(
+                    CheckKind::DuplicateFunctions.as_str(),
+                    Some(&d.function_name),
+                    &[&content_hash(&d.function_a.source), &content_hash(&d.function_b.source)],
+                );
+                if config.should_ignore_fingerprint(&fingerprint) {
+                    ignored_count += 1;
+                    continue;
+                }
+                
                 let pkg_a = file_to_package.get(&d.file_a)
                     .map(|s| s.as_str())
                     .unwrap_or("unknown");
@@ -944,12 +1491,15 @@ fn handle_find_duplicate_functions(args: &Value) -> ToolResult {
                 let dup_entry = json!({
                     "function": d.function_name,
                     "similarity": format!("{:.1}%", d.similarity * 100.0),
+                    "clone_type": d.clone_type,
+                    "safe_to_consolidate": d.clone_type.is_safe_to_consolidate(),
                     "file_a": d.file_a.to_string_lossy(),
                     "file_b": d.file_b.to_string_lossy(),
                     "package_a": pkg_a,
                     "package_b": pkg_b,
                     "lines_a": format!("{}-{}", d.function_a.start_line, d.function_a.end_line),
-                    "lines_b": format!("{}-{}", d.function_b.start_line, d.function_b.end_line)
+                    "lines_b": format!("{}-{}", d.function_b.start_line, d.function_b.end_line),
+                    "fingerprint": fingerprint
                 });
                 
                 if pkg_a != pkg_b {
@@ -977,6 +1527,8 @@ fn handle_find_duplicate_functions(args: &Value) -> ToolResult {
                     "function_a": d.function_a_name,
                     "function_b": d.function_b_name,
                     "similarity": format!("{:.1}%", d.similarity * 100.0),
+                    "clone_type": d.clone_type,
+                    "safe_to_consolidate": d.clone_type.is_safe_to_consolidate(),
                     "file": d.file.to_string_lossy(),
                     "package": pkg,
                     "lines_a": format!("{}-{}", d.function_a.start_line, d.function_a.end_line),
@@ -985,6 +1537,12 @@ fn handle_find_duplicate_functions(args: &Value) -> ToolResult {
                 }));
             }
             
+            let by_location = |d: &Value| ["file_a", "file", "lines_a", "function", "function_a"]
+                .map(|k| d[k].as_str().unwrap_or("").to_string());
+            for dups in [&mut same_package_dups, &mut cross_package_dups, &mut intra_file_dups] {
+                dups.sort_by_key(by_location);
+            }
+            
             // Build message
             let inter_file_count = same_package_dups.len() + cross_package_dups.len();
             let intra_file_count = intra_file_dups.len();
@@ -1013,7 +1571,17 @@ fn handle_find_duplicate_functions(args: &Value) -> ToolResult {
                     ));
                 }
                 
-                format!("Found {}. Consider consolidating.", parts.join(", "))
+                let needs_review = same_package_dups.iter()
+                    .chain(&cross_package_dups)
+                    .chain(&intra_file_dups)
+                    .filter(|d| d["safe_to_consolidate"] == json!(false))
+                    .count();
+                format!(
+                    "Found {}. {} exact or renamed clone(s) are safe to consolidate; {} gapped clone(s) need review.",
+                    parts.join(", "),
+                    total_dup_count - needs_review,
+                    needs_review
+                )
             };
             
             let mut response = json!({
@@ -1056,44 +1624,6 @@ fn handle_find_duplicate_functions(args: &Value) -> ToolResult {
     }
 }
 
-/// Find .ground.yml by walking up the directory tree
-/// This allows configs to be placed at the monorepo root and still apply to subdirectories
-fn find_config_in_ancestors(start_dir: &Path) -> Option<GroundConfig> {
-    let mut current = if start_dir.is_absolute() {
-        start_dir.to_path_buf()
-    } else {
-        std::env::current_dir().ok()?.join(start_dir)
-    };
-    
-    // Canonicalize to resolve any symlinks or relative components
-    if let Ok(canonical) = current.canonicalize() {
-        current = canonical;
-    }
-    
-    // Walk up to 10 levels to avoid infinite loops
-    for _ in 0..10 {
-        // Check for .ground.yml in current directory
-        for name in &[".ground.yml", ".ground.yaml", "ground.yml", "ground.yaml"] {
-            let config_path = current.join(name);
-            if config_path.exists() {
-                if let Ok(config) = GroundConfig::load(&config_path) {
-                    return Some(config);
-                }
-            }
-        }
-        
-        // Move to parent directory
-        match current.parent() {
-            Some(parent) if parent != current => {
-                current = parent.to_path_buf();
-            }
-            _ => break,
-        }
-    }
-    
-    None
-}
-
 /// Discover packages in a monorepo by looking for common patterns
 fn discover_monorepo_packages(base: &Path) -> Vec<PathBuf> {
     let mut packages = Vec::new();
@@ -1287,6 +1817,142 @@ fn handle_claim_orphan(g: &VerifiedTriad, args: &Value) -> ToolResult {
     }
 }
 
+fn handle_check_dependencies(g: &mut VerifiedTriad, args: &Value) -> ToolResult {
+    let directory = match args.get("directory").and_then(|v| v.as_str()) {
+        Some(s) => PathBuf::from(s),
+        None => return ToolResult::error("Missing: directory"),
+    };
+    
+    let mut packages = crate::computations::find_packages(&directory);
+    if packages.is_empty() {
+        match directory.ancestors().find(|d| d.join("package.json").is_file()) {
+            Some(package) => packages.push(package.to_path_buf()),
+            None => return ToolResult::error(format!("No package.json in or above {}", directory.display())),
+        }
+    }
+    
+    let mut results = Vec::new();
+    for package in &packages {
+        match g.analyze_dependencies(package) {
+            Ok(evidence) => results.push(json!({
+                "package_dir": evidence.package_dir.display().to_string(),
+                "package_name": evidence.package_name,
+                "declared": evidence.declared.len(),
+                "unused": evidence.unused.iter().map(|d| json!({
+                    "name": d.name,
+                    "kind": d.kind.as_str(),
+                    "line": d.line
+                })).collect::<Vec<_>>(),
+                "missing": evidence.missing.iter().map(|m| json!({
+                    "name": m.name,
+                    "imported_by": m.imported_by.iter().map(|f| f.display().to_string()).collect::<Vec<_>>()
+                })).collect::<Vec<_>>(),
+                "evidence_id": evidence.id.to_string()
+            })),
+            Err(e) => results.push(json!({
+                "package_dir": package.display().to_string(),
+                "error": e.to_string()
+            })),
+        }
+    }
+    
+    let unused: usize = results.iter().filter_map(|r| r["unused"].as_array()).map(|a| a.len()).sum();
+    let missing: usize = results.iter().filter_map(|r| r["missing"].as_array()).map(|a| a.len()).sum();
+    let message = if unused == 0 && missing == 0 {
+        format!("Dependencies match imports in {} package(s)", results.len())
+    } else {
+        format!(
+            "{} unused and {} missing dependencies in {} package(s). Unused ones can now be claimed with ground_claim_unused_dependency.",
+            unused, missing, results.len()
+        )
+    };
+    
+    ToolResult::success(json!({
+        "packages": results,
+        "unused_count": unused,
+        "missing_count": missing,
+        "message": message
+    }))
+}
+
+fn handle_check_conventions(args: &Value) -> ToolResult {
+    use crate::check::{run_check, CheckKind, CheckOptions, Scope};
+    
+    let directory = match args.get("directory").and_then(|v| v.as_str()) {
+        Some(d) => PathBuf::from(d),
+        None => return ToolResult::error("Missing required parameter: directory"),
+    };
+    
+    let report = match run_check(CheckKind::Conventions, Scope::Directory(directory.clone()), &CheckOptions::default()) {
+        Ok(report) => report,
+        Err(e) => return ToolResult::error(format!("Conventions check failed: {}", e)),
+    };
+    
+    let violations: Vec<_> = report.findings.iter().map(|f| json!({
+        "rule": f.symbol,
+        "path": f.file.display().to_string(),
+        "message": f.message,
+        "suggested_path": f.suggested_path.as_ref().map(|p| p.display().to_string()),
+        "fingerprint": f.fingerprint
+    })).collect();
+    
+    let message = if violations.is_empty() {
+        format!("Layout matches the conventions ({} files checked)", report.files_scanned)
+    } else {
+        format!("{} convention violation(s); each has a suggested_path to move to or create", violations.len())
+    };
+    
+    ToolResult::success(json!({
+        "directory": directory.display().to_string(),
+        "files_checked": report.files_scanned,
+        "violation_count": violations.len(),
+        "violations": violations,
+        "suppressed": report.suppressed,
+        "message": message
+    }))
+}
+
+fn handle_claim_unused_dependency(g: &VerifiedTriad, args: &Value) -> ToolResult {
+    let package_dir = match args.get("package_dir").and_then(|v| v.as_str()) {
+        Some(s) => PathBuf::from(s),
+        None => return ToolResult::error("Missing: package_dir"),
+    };
+    let dependency = match args.get("dependency").and_then(|v| v.as_str()) {
+        Some(s) => s,
+        None => return ToolResult::error("Missing: dependency"),
+    };
+    let reason = match args.get("reason").and_then(|v| v.as_str()) {
+        Some(s) => s,
+        None => return ToolResult::error("Missing: reason"),
+    };
+    
+    match g.claim_unused_dependency(&package_dir, dependency, reason) {
+        Ok(claim) => ToolResult::success(json!({
+            "claimed": true,
+            "claim_id": claim.id.to_string(),
+            "dependency": claim.dependency,
+            "kind": claim.kind.as_str(),
+            "evidence_id": claim.evidence_id.to_string(),
+            "message": format!("Claim recorded: {} is unused in {}", dependency, package_dir.display())
+        })),
+        Err(e) => {
+            let suggestion = match &e {
+                VerifiedTriadError::ClaimRejected(crate::ClaimRejected::NoEvidence { .. }) => {
+                    "You need to check dependencies first with ground_check_dependencies."
+                }
+                _ => "Make sure you've checked dependencies and it's actually unused.",
+            };
+            ToolResult::success(json!({
+                "claimed": false,
+                "blocked": true,
+                "reason": e.to_string(),
+                "suggestion": suggestion,
+                "message": format!("Claim blocked: {}", e)
+            }))
+        }
+    }
+}
+
 /// Kept for potential future use - status can be included in analyze response
 #[allow(dead_code)]
 fn handle_status(g: &VerifiedTriad) -> ToolResult {
@@ -1443,8 +2109,17 @@ fn handle_check_environment(args: &Value) -> ToolResult {
     }
 }
 
-fn handle_find_orphans(args: &Value) -> ToolResult {
-    use crate::computations::analyze_connectivity;
+fn handle_find_orphans(args: &Value) -> ToolResult {
+    let mut result = find_orphans(args);
+    if result.success {
+        paginate(&mut result.content, "orphans", Page::from_args(args));
+    }
+    result
+}
+
+/// Every orphan under the directory, sorted by path
+fn find_orphans(args: &Value) -> ToolResult {
+    use crate::check::{run_check, CheckKind, CheckOptions, Scope};
     
     let directory = match args.get("directory").and_then(|v| v.as_str()) {
         Some(d) => PathBuf::from(d),
@@ -1455,68 +2130,32 @@ fn handle_find_orphans(args: &Value) -> ToolResult {
         .and_then(|v| v.as_bool())
         .unwrap_or(false);
     
-    // Load config for path filtering
-    let config = find_config_in_ancestors(&directory).unwrap_or_default();
-    
-    // Collect files
-    let mut files = Vec::new();
-    collect_ts_files(&directory, &mut files);
-    let total_files_before_filter = files.len();
+    // Reuse the graph from ground_build_graph when it covers this directory
+    let graph = SYMBOL_GRAPH.lock().unwrap().clone().map(std::sync::Arc::new);
     
-    // Filter using config path patterns first
-    let files: Vec<_> = files.into_iter()
-        .filter(|f| !config.should_ignore_path(f))
-        .collect();
-    let paths_ignored = total_files_before_filter - files.len();
-    
-    // Filter out test files if needed (in addition to config)
-    let files: Vec<_> = if include_tests {
-        files
-    } else {
-        files.into_iter()
-            .filter(|f| {
-                let name = f.file_name().and_then(|n| n.to_str()).unwrap_or("");
-                !name.contains(".test.") && 
-                !name.contains(".spec.") &&
-                !f.to_string_lossy().contains("__tests__")
-            })
-            .collect()
+    let options = CheckOptions {
+        include_tests,
+        graph,
+        progress: Some(std::sync::Arc::new(report_file_progress)),
+        ..Default::default()
+    };
+    let report = match run_check(CheckKind::Orphans, Scope::Directory(directory.clone()), &options) {
+        Ok(report) => report,
+        Err(e) => return ToolResult::error(format!("Orphan scan failed: {}", e)),
     };
     
-    // Filter out index files, type declarations, and SvelteKit route files
-    let files: Vec<_> = files.into_iter()
-        .filter(|f| {
-            let name = f.file_name().and_then(|n| n.to_str()).unwrap_or("");
-            !name.ends_with(".d.ts") &&
-            name != "index.ts" &&
-            name != "index.js" &&
-            !name.starts_with("+")  // SvelteKit route files
+    let mut findings: Vec<_> = report.findings.iter().collect();
+    findings.sort_by(|a, b| a.file.cmp(&b.file));
+    let orphans: Vec<_> = findings.into_iter().map(|f| {
+        json!({
+            "path": f.file.display().to_string(),
+            "relative_path": f.file.strip_prefix(&directory)
+                .map(|p| p.display().to_string())
+                .unwrap_or_else(|_| f.file.display().to_string()),
+            "confidence": f.confidence
         })
-        .collect();
-    
-    let mut orphans = Vec::new();
-    let mut connected = 0;
-    let mut errors = 0;
-    
-    for file in &files {
-        match analyze_connectivity(file) {
-            Ok(evidence) => {
-                if evidence.total_connections() == 0 && evidence.architectural.is_none() {
-                    orphans.push(json!({
-                        "path": file.display().to_string(),
-                        "relative_path": file.strip_prefix(&directory)
-                            .map(|p| p.display().to_string())
-                            .unwrap_or_else(|_| file.display().to_string())
-                    }));
-                } else {
-                    connected += 1;
-                }
-            }
-            Err(_) => {
-                errors += 1;
-            }
-        }
-    }
+    }).collect();
+    let connected = report.files_scanned - orphans.len();
     
     let message = if orphans.is_empty() {
         format!("No orphaned modules found. {} modules are connected.", connected)
@@ -1524,23 +2163,289 @@ fn handle_find_orphans(args: &Value) -> ToolResult {
         format!("Found {} orphaned modules (nothing imports them).", orphans.len())
     };
     
-    let mut response = json!({
+    ToolResult::success(json!({
         "directory": directory.display().to_string(),
-        "files_scanned": files.len(),
+        "files_scanned": report.files_scanned,
         "orphan_count": orphans.len(),
         "orphans": orphans,
         "connected_count": connected,
-        "error_count": errors,
+        "duration_ms": report.duration_ms,
         "message": message
-    });
+    }))
+}
+
+fn handle_find_barrel_bloat(args: &Value) -> ToolResult {
+    use crate::computations::{analyze_barrels, find_project_root, BarrelOptions, SymbolGraph};
+    
+    let directory = match args.get("directory").and_then(|v| v.as_str()) {
+        Some(d) => PathBuf::from(d),
+        None => return ToolResult::error("Missing required parameter: directory"),
+    };
+    let root = directory.canonicalize().unwrap_or_else(|_| directory.clone());
     
-    // Add config filtering info if paths were ignored
-    if paths_ignored > 0 {
-        response["paths_ignored_by_config"] = json!(paths_ignored);
-        response["config_applied"] = json!(true);
+    let mut options = BarrelOptions::default();
+    if let Some(share) = args.get("max_share").and_then(|v| v.as_f64()) {
+        options.max_share = share;
+    }
+    if let Some(kb) = args.get("heavy_kb").and_then(|v| v.as_f64()) {
+        options.heavy_bytes = (kb * 1024.0) as u64;
     }
     
-    ToolResult::success(response)
+    // Reuse the graph from ground_build_graph when it covers this directory
+    let cached = SYMBOL_GRAPH.lock().unwrap().clone()
+        .filter(|g| g.root_dir.canonicalize().is_ok_and(|r| root.starts_with(r)));
+    let graph = match cached {
+        Some(graph) => graph,
+        None => {
+            let project_root = find_project_root(&root.join("_")).unwrap_or_else(|_| root.clone());
+            match SymbolGraph::build(&project_root, Some(&|done, total| report_file_progress("barrel_bloat", done, total))) {
+                Ok(graph) => graph,
+                Err(e) => return ToolResult::error(format!("Failed to build graph: {}", e)),
+            }
+        }
+    };
+    
+    let report = analyze_barrels(&graph, &root, &options);
+    let bloated: usize = report.barrels.iter().map(|b| b.bloated().count()).sum();
+    let barrels: Vec<_> = report.barrels.iter().map(|b| json!({
+        "path": b.path.display().to_string(),
+        "exported": b.exported,
+        "consumed": b.consumed,
+        "utilization": (b.utilization * 100.0).round() / 100.0,
+        "consumer_count": b.consumers.len(),
+        "modules": b.modules.iter().map(|m| json!({
+            "path": m.path.display().to_string(),
+            "specifier": m.specifier,
+            "symbols": m.symbols,
+            "consumers": m.consumers,
+            "share": (m.share * 100.0).round() / 100.0,
+            "packages": m.packages,
+            "exclusive_packages": m.exclusive_packages,
+            "source_bytes": m.source_bytes,
+            "bloat": m.bloat,
+            "suggestion": m.suggestion
+        })).collect::<Vec<_>>()
+    })).collect();
+    
+    let message = if report.barrels.is_empty() {
+        format!("No barrels found ({} files analyzed)", report.files_analyzed)
+    } else if bloated == 0 {
+        format!("{} barrel(s), none re-exporting heavy modules most consumers skip", report.barrels.len())
+    } else {
+        format!(
+            "{} heavy module(s) across {} barrel(s) are bundled by consumers that don't use them; see each module's suggestion",
+            bloated,
+            report.barrels.iter().filter(|b| b.bloated().count() > 0).count()
+        )
+    };
+    
+    let mut result = json!({
+        "directory": directory.display().to_string(),
+        "files_analyzed": report.files_analyzed,
+        "barrel_count": report.barrels.len(),
+        "bloated_module_count": bloated,
+        "barrels": barrels,
+        "evidence_id": report.id.to_string(),
+        "message": message
+    });
+    paginate(&mut result, "barrels", Page::from_args(args));
+    ToolResult::success(result)
+}
+
+fn handle_find_signature_clusters(args: &Value) -> ToolResult {
+    use crate::computations::{analyze_signature_clusters, walk::source_files, SignatureOptions};
+    
+    let directory = match args.get("directory").and_then(|v| v.as_str()) {
+        Some(d) => PathBuf::from(d),
+        None => return ToolResult::error("Missing required parameter: directory"),
+    };
+    let options = SignatureOptions {
+        include_tests: args.get("include_tests").and_then(|v| v.as_bool()).unwrap_or(false),
+        cross_package: !args.get("within_package").and_then(|v| v.as_bool()).unwrap_or(false),
+    };
+    
+    let files = source_files(&directory, &["ts", "tsx", "js", "jsx"]);
+    let report = analyze_signature_clusters(&files, &options);
+    let clusters: Vec<_> = report.clusters.iter().map(|c| json!({
+        "names": c.names,
+        "words": c.words,
+        "arity": c.arity,
+        "packages": c.packages,
+        "canonical": {
+            "name": c.canonical().name,
+            "signature": c.canonical().signature(),
+            "file": c.canonical().file.display().to_string(),
+            "line": c.canonical().line,
+            "package": c.canonical().package
+        },
+        "members": c.members.iter().map(|m| json!({
+            "name": m.name,
+            "signature": m.signature(),
+            "file": m.file.display().to_string(),
+            "line": m.line,
+            "package": m.package,
+            "is_async": m.is_async
+        })).collect::<Vec<_>>(),
+        "suggestion": c.suggestion
+    })).collect();
+    
+    let message = if report.clusters.is_empty() {
+        format!("No near-miss APIs among {} exported functions", report.exported_functions)
+    } else {
+        format!(
+            "{} API(s) reinvented under different names or bodies across packages; consolidate each on its canonical signature",
+            report.clusters.len()
+        )
+    };
+    
+    let mut content = json!({
+        "directory": directory.display().to_string(),
+        "files_analyzed": report.files_analyzed,
+        "exported_functions": report.exported_functions,
+        "cluster_count": report.clusters.len(),
+        "clusters": clusters,
+        "evidence_id": report.id.to_string(),
+        "message": message
+    });
+    paginate(&mut content, "clusters", Page::from_args(args));
+    ToolResult::success(content)
+}
+
+fn handle_rename_impact(g: &mut VerifiedTriad, args: &Value) -> ToolResult {
+    use crate::computations::{rename::workspace_root, SymbolGraph};
+    
+    let symbol = match args.get("symbol").and_then(|v| v.as_str()) {
+        Some(s) => s,
+        None => return ToolResult::error("Missing: symbol"),
+    };
+    let path = match args.get("path").and_then(|v| v.as_str()) {
+        Some(p) => PathBuf::from(p),
+        None => return ToolResult::error("Missing: path"),
+    };
+    let Ok(path) = path.canonicalize() else {
+        return ToolResult::error(format!("Path not found: {}", path.display()));
+    };
+    
+    // Importers can live in any package, so the graph has to cover the workspace
+    let workspace = workspace_root(&path);
+    let cached = SYMBOL_GRAPH.lock().unwrap().clone()
+        .filter(|g| g.root_dir.canonicalize().is_ok_and(|r| workspace.starts_with(r)));
+    let graph = match cached {
+        Some(graph) => graph,
+        None => match SymbolGraph::build(&workspace, Some(&|done, total| report_file_progress("rename_impact", done, total))) {
+            Ok(graph) => graph,
+            Err(e) => return ToolResult::error(format!("Failed to build graph: {}", e)),
+        },
+    };
+    
+    mcp_log!("Checking rename impact of '{}' from {}", symbol, path.display());
+    
+    let impact = match g.rename_impact(symbol, &path, Some(&graph)) {
+        Ok(impact) => impact,
+        Err(e) => return ToolResult::error(format!("Rename impact failed: {}", e)),
+    };
+    
+    let textual = impact.textual_sites().count();
+    let cross_package: Vec<_> = impact.cross_package_consumers().filter_map(|c| c.package.clone()).collect();
+    let message = if impact.defined_in.is_empty() {
+        format!("No export named '{}' found; only text mentions are listed", symbol)
+    } else if impact.is_mechanical() {
+        format!(
+            "'{}' can be renamed mechanically: {} site(s), no strings, comments, config entries, or dynamic uses",
+            symbol, impact.sites.len()
+        )
+    } else {
+        format!(
+            "'{}': {} site(s), {} an editor rename won't update, {} dynamic use(s) to check by hand",
+            symbol, impact.sites.len(), textual, impact.dynamic.len()
+        )
+    };
+    
+    ToolResult::success(json!({
+        "symbol": symbol,
+        "search_path": impact.search_path.display().to_string(),
+        "defined_in": impact.defined_in.iter().map(|p| p.display().to_string()).collect::<Vec<_>>(),
+        "reexported_by": impact.reexported_by.iter().map(|p| p.display().to_string()).collect::<Vec<_>>(),
+        "site_count": impact.sites.len(),
+        "textual_site_count": textual,
+        "sites": impact.sites,
+        "dynamic": impact.dynamic,
+        "consumers": impact.consumers,
+        "cross_package": cross_package,
+        "mechanical": impact.is_mechanical(),
+        "evidence_recorded": true,
+        "evidence_id": impact.usage.id.to_string(),
+        "message": message
+    }))
+}
+
+fn handle_trace_feature(args: &Value) -> ToolResult {
+    use crate::computations::{rename::workspace_root, trace_feature, SymbolGraph};
+    
+    let entry = match args.get("entry").and_then(|v| v.as_str()) {
+        Some(s) => s,
+        None => return ToolResult::error("Missing: entry"),
+    };
+    let (path, symbol) = match args.get("symbol").and_then(|v| v.as_str()) {
+        Some(symbol) => (entry, Some(symbol)),
+        None => match entry.rsplit_once('#') {
+            Some((path, symbol)) => (path, Some(symbol)),
+            None => (entry, None),
+        },
+    };
+    let Ok(path) = PathBuf::from(path).canonicalize() else {
+        return ToolResult::error(format!("Entry not found: {}", path));
+    };
+    
+    // Anything in the workspace might still use the feature
+    let workspace = workspace_root(&path);
+    let cached = SYMBOL_GRAPH.lock().unwrap().clone()
+        .filter(|g| g.root_dir.canonicalize().is_ok_and(|r| workspace.starts_with(r)));
+    let graph = match cached {
+        Some(graph) => graph,
+        None => match SymbolGraph::build(&workspace, Some(&|done, total| report_file_progress("trace_feature", done, total))) {
+            Ok(graph) => graph,
+            Err(e) => return ToolResult::error(format!("Failed to build graph: {}", e)),
+        },
+    };
+    
+    let entry = match symbol {
+        Some(symbol) => format!("{}#{}", path.display(), symbol),
+        None => path.display().to_string(),
+    };
+    mcp_log!("Tracing feature from {}", entry);
+    
+    let trace = match trace_feature(&entry, &graph) {
+        Ok(trace) => trace,
+        Err(e) => return ToolResult::error(format!("Feature trace failed: {}", e)),
+    };
+    
+    let message = if trace.entry_shared {
+        format!(
+            "Other code imports {} for more than '{}': remove the export and update {} reference(s); no files go with it",
+            path.display(), symbol.unwrap_or_default(), trace.references.len()
+        )
+    } else {
+        format!(
+            "Removing the feature deletes {} file(s) ({} lines incl. tests), {} export(s) and {} test file(s); {} reference(s) in remaining code need editing",
+            trace.files.len(), trace.lines, trace.exports.len(), trace.tests.len(), trace.references.len()
+        )
+    };
+    
+    ToolResult::success(json!({
+        "entry": trace.entry.display().to_string(),
+        "symbol": trace.symbol,
+        "files": trace.files,
+        "exports": trace.exports,
+        "tests": trace.tests,
+        "references": trace.references,
+        "shared": trace.shared,
+        "entry_shared": trace.entry_shared,
+        "removals": trace.removals(),
+        "lines": trace.lines,
+        "evidence_id": trace.id.to_string(),
+        "message": message
+    }))
 }
 
 fn handle_find_dead_exports(args: &Value) -> ToolResult {
@@ -1597,7 +2502,11 @@ fn handle_find_dead_exports(args: &Value) -> ToolResult {
     
     match find_dead_exports(&module_path, &search_scope) {
         Ok(report) => {
-            let dead_exports: Vec<_> = report.dead_exports.iter().map(|d| {
+            // Exports other repos consume aren't dead, just not used here
+            let (dead, externally_used) = PublicApiManifest::load(&search_scope)
+                .partition(report.dead_exports.iter(), |d| (&d.name, &d.file, d.line));
+            
+            let dead_exports: Vec<_> = dead.iter().map(|d| {
                 json!({
                     "name": d.name,
                     "file": d.file.display().to_string(),
@@ -1606,7 +2515,7 @@ fn handle_find_dead_exports(args: &Value) -> ToolResult {
                 })
             }).collect();
             
-            let message = if report.dead_exports.is_empty() {
+            let message = if dead.is_empty() {
                 format!(
                     "All {} export(s) from {} are used somewhere in {}.",
                     report.total_exports,
@@ -1616,21 +2525,24 @@ fn handle_find_dead_exports(args: &Value) -> ToolResult {
             } else {
                 format!(
                     "Found {} unused export(s) out of {} total in {}.",
-                    report.dead_exports.len(),
+                    dead.len(),
                     report.total_exports,
                     module_path.display()
                 )
             };
             
-            ToolResult::success(json!({
+            let mut result = json!({
                 "module_path": report.module_path.display().to_string(),
                 "search_scope": report.search_scope.display().to_string(),
                 "total_exports": report.total_exports,
-                "dead_export_count": report.dead_exports.len(),
+                "dead_export_count": dead.len(),
                 "dead_exports": dead_exports,
-                "all_used": report.dead_exports.is_empty(),
+                "externally_used": externally_used,
+                "all_used": dead.is_empty(),
                 "message": message
-            }))
+            });
+            paginate(&mut result, "dead_exports", Page::from_args(args));
+            ToolResult::success(result)
         }
         Err(e) => ToolResult::error(format!("Failed to find dead exports: {}", e)),
     }
@@ -1661,8 +2573,7 @@ fn count_files_recursive(dir: &PathBuf, count: &mut usize, max_count: usize) {
         let path = entry.path();
         
         if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-            if name.starts_with('.') || 
-               matches!(name, "node_modules" | "target" | "dist" | "build" | ".svelte-kit") {
+            if name.starts_with('.') || is_generated_dir(name) {
                 continue;
             }
         }
@@ -1671,7 +2582,7 @@ fn count_files_recursive(dir: &PathBuf, count: &mut usize, max_count: usize) {
             count_files_recursive(&path, count, max_count);
         } else if path.is_file() {
             let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
-            if matches!(ext, "ts" | "tsx" | "js" | "jsx" | "svelte") {
+            if matches!(ext, "ts" | "tsx" | "js" | "jsx" | "svelte") && !is_generated_file(&path) {
                 *count += 1;
             }
         }
@@ -1797,31 +2708,8 @@ fn collect_workspace_candidates() -> Vec<PathBuf> {
     candidates
 }
 
-fn collect_ts_files(dir: &PathBuf, files: &mut Vec<PathBuf>) {
-    let entries = match std::fs::read_dir(dir) {
-        Ok(e) => e,
-        Err(_) => return,
-    };
-    
-    for entry in entries.filter_map(|e| e.ok()) {
-        let path = entry.path();
-        
-        if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-            if name.starts_with('.') || 
-               matches!(name, "node_modules" | "target" | "dist" | "build" | ".svelte-kit") {
-                continue;
-            }
-        }
-        
-        if path.is_dir() {
-            collect_ts_files(&path, files);
-        } else if path.is_file() {
-            let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
-            if matches!(ext, "ts" | "tsx" | "js" | "jsx") {
-                files.push(path);
-            }
-        }
-    }
+fn collect_ts_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    files.extend(source_files(dir, &["ts", "tsx", "js", "jsx"]));
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -2200,7 +3088,7 @@ fn handle_batch_analyze(args: &Value) -> ToolResult {
         .unwrap_or_default();
     
     // Load config
-    let config = find_config_in_ancestors(&directory).unwrap_or_default();
+    let config = GroundConfig::find_in_ancestors(&directory).unwrap_or_default();
     
     // Detect framework for smarter analysis
     let framework_detection = detect_framework(&directory);
@@ -2230,15 +3118,26 @@ fn handle_batch_analyze(args: &Value) -> ToolResult {
     let mut total_issues = 0;
     let mut auto_fixable = 0;
     
+    // Phases in run order, for progress reporting
+    let phases: Vec<&str> = ["duplicates", "orphans", "environment"].into_iter()
+        .filter(|c| checks.contains(c))
+        .collect();
+    let phase_started = |name: &str| {
+        if let Some(i) = phases.iter().position(|p| *p == name) {
+            report_progress(&format!("analyze: {}", name), i, Some(phases.len()));
+        }
+    };
+    
     // Run duplicate check
     if checks.contains(&"duplicates") {
+        phase_started("duplicates");
         let dup_args = json!({
             "directory": directory.to_string_lossy(),
             "cross_package": cross_package,
             "threshold": config.similarity_threshold()
         });
         
-        if let ToolResult { success: true, content, .. } = handle_find_duplicate_functions(&dup_args) {
+        if let ToolResult { success: true, content, .. } = find_duplicate_functions(&dup_args) {
             // Convert duplicates to structured findings with fixes
             let mut structured_dups: Vec<Value> = Vec::new();
             
@@ -2320,18 +3219,20 @@ fn handle_batch_analyze(args: &Value) -> ToolResult {
                 }
             }
             
+            structured_dups.sort_by_key(|d| (d["files"].to_string(), d["function"].to_string()));
             results["findings"]["duplicates"] = json!(structured_dups);
         }
     }
     
     // Run orphan check
     if checks.contains(&"orphans") {
+        phase_started("orphans");
         let orphan_args = json!({
             "directory": directory.to_string_lossy(),
             "include_tests": false
         });
         
-        if let ToolResult { success: true, content, .. } = handle_find_orphans(&orphan_args) {
+        if let ToolResult { success: true, content, .. } = find_orphans(&orphan_args) {
             if let Some(orphans) = content.get("orphans").and_then(|v| v.as_array()) {
                 let structured_orphans: Vec<Value> = orphans.iter().filter_map(|o| {
                     let path_str = o.get("path").and_then(|v| v.as_str()).unwrap_or("");
@@ -2347,17 +3248,10 @@ fn handle_batch_analyze(args: &Value) -> ToolResult {
                     let is_config = filename.ends_with(".config.ts") || filename.ends_with(".config.js") ||
                                    matches!(filename, "tsconfig.json" | "vite.config.ts" | "vitest.config.ts");
                     
-                    // Calculate confidence using Bayesian scoring
-                    let confidence = orphan_confidence(
-                        0,  // incoming_imports (we know it's 0 since it's flagged as orphan)
-                        0,  // outgoing_imports (unknown, assume 0)
-                        false,  // is_entry_point (would need package.json check)
-                        is_test,
-                        is_config,
-                        false,  // has_architectural_connections (would need deeper check)
-                        None,   // pagerank_percentile (not computed here)
-                        is_framework_entry,
-                    );
+                    // Bayesian confidence from graph properties, computed by the scan
+                    let confidence = o.get("confidence")
+                        .and_then(|c| serde_json::from_value::<ConfidenceScore>(c.clone()).ok())
+                        .unwrap_or_else(|| orphan_confidence(0, 0, false, is_test, is_config, false, None, is_framework_entry));
                     
                     // Skip low-confidence findings (likely false positives)
                     if confidence.score < 0.3 {
@@ -2388,6 +3282,7 @@ fn handle_batch_analyze(args: &Value) -> ToolResult {
                     }))
                 }).collect();
                 
+                // Already sorted by path
                 results["findings"]["orphans"] = json!(structured_orphans);
             }
         }
@@ -2395,6 +3290,7 @@ fn handle_batch_analyze(args: &Value) -> ToolResult {
     
     // Run environment check if entry points provided
     if checks.contains(&"environment") && !entry_points.is_empty() {
+        phase_started("environment");
         let mut env_issues: Vec<Value> = Vec::new();
         
         for entry in &entry_points {
@@ -2436,6 +3332,14 @@ fn handle_batch_analyze(args: &Value) -> ToolResult {
     results["summary"]["auto_fixable"] = json!(auto_fixable);
     results["summary"]["needs_review"] = json!(total_issues - auto_fixable);
     
+    // Every category is paged with the same window; the summary and
+    // `pagination.total` count all findings
+    results["pagination"] = paginate_each(
+        &mut results["findings"],
+        &["duplicates", "dead_exports", "orphans", "environment_issues"],
+        Page::from_args(args),
+    );
+    
     let message = if total_issues == 0 {
         "No issues found. Codebase is clean.".to_string()
     } else {
@@ -2668,7 +3572,21 @@ fn handle_diff(args: &Value) -> ToolResult {
     }
     
     // Load config
-    let config = find_config_in_ancestors(&directory).unwrap_or_default();
+    let config = GroundConfig::find_in_ancestors(&directory).unwrap_or_default();
+    
+    // Known findings, matched by fingerprint so moved files don't resurface them
+    let baseline = match args.get("baseline").and_then(|v| v.as_str()) {
+        Some(path) => match Baseline::load(&resolve_path(path)) {
+            Ok(baseline) => Some(baseline),
+            Err(e) => return ToolResult::error(format!("Failed to load baseline: {}", e)),
+        },
+        None => Baseline::find_in(&directory),
+    };
+    let is_known = |fingerprint: &str| {
+        config.should_ignore_fingerprint(fingerprint)
+            || baseline.as_ref().is_some_and(|b| b.contains(fingerprint))
+    };
+    let mut known_issues = 0;
     
     // Filter changed files by config ignore patterns and file type
     let relevant_files: Vec<PathBuf> = changed_files.iter()
@@ -2694,7 +3612,7 @@ fn handle_diff(args: &Value) -> ToolResult {
             "threshold": config.similarity_threshold()
         });
         
-        if let ToolResult { success: true, content, .. } = handle_find_duplicate_functions(&dup_args) {
+        if let ToolResult { success: true, content, .. } = find_duplicate_functions(&dup_args) {
             // Filter duplicates to only those involving changed files
             if let Some(dups) = content.get("duplicates").and_then(|v| v.as_array()) {
                 for dup in dups {
@@ -2708,12 +3626,16 @@ fn handle_diff(args: &Value) -> ToolResult {
                         f_str.ends_with(file_a) || f_str.ends_with(file_b)
                     });
                     
-                    if involves_changed {
+                    let fingerprint = dup.get("fingerprint").and_then(|v| v.as_str()).unwrap_or("");
+                    if involves_changed && is_known(fingerprint) {
+                        known_issues += 1;
+                    } else if involves_changed {
                         new_issues.push(json!({
                             "type": "duplicate_function",
                             "function": dup.get("function"),
                             "similarity": dup.get("similarity"),
                             "files": [file_a, file_b],
+                            "fingerprint": fingerprint,
                             "introduced_by": "current_branch"
                         }));
                     }
@@ -2732,13 +3654,17 @@ fn handle_diff(args: &Value) -> ToolResult {
                         f_str.ends_with(file_a) || f_str.ends_with(file_b)
                     });
                     
-                    if involves_changed {
+                    let fingerprint = dup.get("fingerprint").and_then(|v| v.as_str()).unwrap_or("");
+                    if involves_changed && is_known(fingerprint) {
+                        known_issues += 1;
+                    } else if involves_changed {
                         new_issues.push(json!({
                             "type": "cross_package_duplicate",
                             "function": dup.get("function"),
                             "similarity": dup.get("similarity"),
                             "files": [file_a, file_b],
                             "packages": [dup.get("package_a"), dup.get("package_b")],
+                            "fingerprint": fingerprint,
                             "introduced_by": "current_branch"
                         }));
                     }
@@ -2764,9 +3690,16 @@ fn handle_diff(args: &Value) -> ToolResult {
                 use crate::computations::analyze_connectivity;
                 if let Ok(evidence) = analyze_connectivity(file) {
                     if evidence.total_connections() == 0 && evidence.architectural.is_none() {
+                        // A moved file is "new" to git, but not to the baseline
+                        let fingerprint = fingerprint_location(CheckKind::Orphans.as_str(), None, file, None);
+                        if is_known(&fingerprint) {
+                            known_issues += 1;
+                            continue;
+                        }
                         new_issues.push(json!({
                             "type": "orphan_module",
                             "path": file.to_string_lossy(),
+                            "fingerprint": fingerprint,
                             "introduced_by": "current_branch",
                             "is_new_file": true
                         }));
@@ -2788,7 +3721,7 @@ fn handle_diff(args: &Value) -> ToolResult {
         )
     };
     
-    ToolResult::success(json!({
+    let mut result = json!({
         "base": base_ref,
         "changed_files": relevant_files.len(),
         "changed_file_list": relevant_files.iter()
@@ -2796,9 +3729,12 @@ fn handle_diff(args: &Value) -> ToolResult {
             .collect::<Vec<_>>(),
         "new_issues": new_issues,
         "total_new_issues": new_issues.len(),
+        "known_issues": known_issues,
         "checks_run": checks,
         "message": message
-    }))
+    });
+    paginate(&mut result, "new_issues", Page::from_args(args));
+    ToolResult::success(result)
 }
 
 /// Get list of files changed since a git ref
@@ -2957,15 +3893,13 @@ fn handle_find_drift(args: &Value) -> ToolResult {
     
     match analyze_patterns(&directory, &config) {
         Ok(report) => {
-            // Filter violations by category
-            let violations: Vec<_> = if category == "all" {
-                report.violations.clone()
-            } else {
-                report.violations.iter()
-                    .filter(|v| v.category == category)
-                    .cloned()
-                    .collect()
-            };
+            // Filter violations by category, in file and line order so
+            // pages are stable
+            let mut violations: Vec<_> = report.file_evidence.iter()
+                .flat_map(|e| e.violations.iter().map(move |v| (&e.file, v)))
+                .filter(|(_, v)| category == "all" || v.category == category)
+                .collect();
+            violations.sort_by(|(fa, a), (fb, b)| (fa, a.line, a.column).cmp(&(fb, b.line, b.column)));
             
             // Get files below threshold if specified
             let files_below: Vec<_> = if let Some(threshold) = below_threshold {
@@ -2987,14 +3921,15 @@ fn handle_find_drift(args: &Value) -> ToolResult {
                 HealthStatus::Critical => "critical",
             };
             
-            ToolResult::success(json!({
+            let mut result = json!({
                 "drift_detected": !violations.is_empty(),
                 "violations_count": violations.len(),
                 "overall_adoption_ratio": format!("{:.1}%", report.overall_adoption_ratio),
                 "health_status": health_status,
                 "files_analyzed": report.files_analyzed,
                 "category_filter": category,
-                "violations": violations.iter().take(20).map(|v| json!({
+                "violations": violations.iter().map(|(file, v)| json!({
+                    "file": file.display().to_string(),
                     "category": v.category,
                     "line": v.line,
                     "property": v.property,
@@ -3017,7 +3952,9 @@ fn handle_find_drift(args: &Value) -> ToolResult {
                         if category == "all" { "design system" } else { category },
                         report.overall_adoption_ratio)
                 }
-            }))
+            });
+            paginate(&mut result, "violations", Page::from_args(args));
+            ToolResult::success(result)
         }
         Err(e) => ToolResult::error(format!("Pattern analysis failed: {}", e)),
     }
@@ -3161,10 +4098,10 @@ fn handle_mine_patterns(args: &Value) -> ToolResult {
     
     match mine_patterns(&directory, min_occurrences) {
         Ok(report) => {
-            ToolResult::success(json!({
+            let mut result = json!({
                 "files_analyzed": report.files_analyzed,
                 "discovered_patterns_count": report.discovered_patterns.len(),
-                "discovered_patterns": report.discovered_patterns.iter().take(20).map(|p| json!({
+                "discovered_patterns": report.discovered_patterns.iter().map(|p| json!({
                     "category": p.category,
                     "property": p.property,
                     "value": p.value,
@@ -3172,13 +4109,13 @@ fn handle_mine_patterns(args: &Value) -> ToolResult {
                     "should_tokenize": p.should_tokenize,
                     "files_sample": p.files.iter().take(3).collect::<Vec<_>>()
                 })).collect::<Vec<_>>(),
-                "value_clusters": report.value_clusters.iter().take(10).map(|c| json!({
+                "value_clusters": report.value_clusters.iter().map(|c| json!({
                     "category": c.category,
                     "representative": c.representative,
                     "total_occurrences": c.total_occurrences,
                     "variant_count": c.values.len()
                 })).collect::<Vec<_>>(),
-                "suggested_tokens": report.suggested_tokens.iter().take(10).map(|s| json!({
+                "suggested_tokens": report.suggested_tokens.iter().map(|s| json!({
                     "name": s.name,
                     "value": s.value,
                     "category": s.category,
@@ -3193,7 +4130,13 @@ fn handle_mine_patterns(args: &Value) -> ToolResult {
                         report.discovered_patterns.len(),
                         report.suggested_tokens.len())
                 }
-            }))
+            });
+            result["pagination"] = paginate_each(
+                &mut result,
+                &["discovered_patterns", "value_clusters", "suggested_tokens"],
+                Page::from_args(args),
+            );
+            ToolResult::success(result)
         }
         Err(e) => ToolResult::error(format!("Pattern mining failed: {}", e)),
     }
@@ -3287,6 +4230,8 @@ fn handle_build_graph(args: &Value) -> ToolResult {
     let last_logged = std::cell::Cell::new(0usize);
     
     let graph = match SymbolGraph::build(&directory, Some(&|current, total| {
+        report_file_progress("parsing", current, total);
+        
         // Log progress every 100 files
         let last = last_logged.get();
         if current - last >= 100 || current == total {
@@ -3439,6 +4384,8 @@ fn handle_query_dead(args: &Value) -> ToolResult {
         set
     };
     
+    let manifest = PublicApiManifest::load(&graph.root_dir);
+    
     // Check if a dead export is a framework convention
     let is_framework_convention = |dead: &crate::computations::GraphDeadExport| -> bool {
         // Check if export name is a framework convention
@@ -3457,6 +4404,8 @@ fn handle_query_dead(args: &Value) -> ToolResult {
         
         let (framework_dead, true_dead): (Vec<_>, Vec<_>) = all_dead.iter()
             .partition(|d| is_framework_convention(d));
+        let (mut true_dead, externally_used) = manifest.partition(true_dead, |d| (&d.name, &d.file, d.line));
+        true_dead.sort_by(|a, b| (a.line, &a.name).cmp(&(b.line, &b.name)));
         
         let dead_json: Vec<_> = true_dead.iter().map(|d| json!({
             "name": d.name,
@@ -3469,6 +4418,7 @@ fn handle_query_dead(args: &Value) -> ToolResult {
             "file": file_path.to_string_lossy(),
             "dead_export_count": true_dead.len(),
             "dead_exports": dead_json,
+            "externally_used": externally_used,
             "framework": framework_detection.primary.as_str(),
             "message": if true_dead.is_empty() {
                 format!("All exports in {} are used.", file_path.display())
@@ -3482,6 +4432,7 @@ fn handle_query_dead(args: &Value) -> ToolResult {
             result["note"] = json!("Framework convention exports (load, GET, etc.) were filtered. Use raw=true to see all.");
         }
         
+        paginate(&mut result, "dead_exports", Page::from_args(args));
         ToolResult::success(result)
     } else {
         // Query all files
@@ -3490,14 +4441,18 @@ fn handle_query_dead(args: &Value) -> ToolResult {
         // Partition into true dead vs framework conventions
         let (framework_dead, true_dead): (Vec<_>, Vec<_>) = report.dead_exports.iter()
             .partition(|d| is_framework_convention(d));
+        let (true_dead, externally_used) = manifest.partition(true_dead, |d| (&d.name, &d.file, d.line));
         
-        // Group true dead by file
-        let mut by_file: HashMap<String, Vec<&crate::computations::GraphDeadExport>> = HashMap::new();
+        // Group true dead by file, in path and line order so pages are stable
+        let mut by_file: std::collections::BTreeMap<String, Vec<&crate::computations::GraphDeadExport>> = Default::default();
         for dead in &true_dead {
             by_file.entry(dead.file.to_string_lossy().to_string())
                 .or_default()
                 .push(dead);
         }
+        for exports in by_file.values_mut() {
+            exports.sort_by(|a, b| (a.line, &a.name).cmp(&(b.line, &b.name)));
+        }
         
         let files_with_dead: Vec<_> = by_file.iter().map(|(file, exports)| json!({
             "file": file,
@@ -3515,6 +4470,7 @@ fn handle_query_dead(args: &Value) -> ToolResult {
             "total_exports": report.total_exports,
             "dead_export_count": true_dead.len(),
             "files_with_dead_exports": by_file.len(),
+            "externally_used": externally_used,
             "files_analyzed": report.files_analyzed,
             "query_time_ms": report.query_time_ms,
             "by_file": files_with_dead,
@@ -3532,10 +4488,167 @@ fn handle_query_dead(args: &Value) -> ToolResult {
             result["note"] = json!("Framework exports (load, GET, handle, etc.) were filtered. Use raw=true to see all.");
         }
         
+        // Paged by file; a file's dead exports are never split across pages
+        paginate(&mut result, "by_file", Page::from_args(args));
         ToolResult::success(result)
     }
 }
 
+fn handle_health(g: &mut VerifiedTriad, args: &Value) -> ToolResult {
+    let directory = match args.get("directory").and_then(|v| v.as_str()) {
+        Some(s) => PathBuf::from(s),
+        None => return ToolResult::error("Missing: directory"),
+    };
+    let history = args.get("history")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0) as usize;
+    
+    mcp_log!("Computing health score for {:?}", directory);
+    
+    let (report, trend) = match g.compute_health(&directory) {
+        Ok(result) => result,
+        Err(e) => return ToolResult::error(format!("Health computation failed: {}", e)),
+    };
+    
+    let packages: Vec<Value> = report.packages.iter()
+        .map(|p| {
+            let delta = trend.as_ref()
+                .and_then(|t| t.packages.iter().find(|d| d.name == p.name))
+                .and_then(|d| d.delta);
+            json!({
+                "name": p.name,
+                "path": p.path.to_string_lossy(),
+                "score": round1(p.score),
+                "delta": delta.map(round1),
+                "duplication_density": p.metrics.duplication_density(),
+                "dead_export_ratio": p.metrics.dead_export_ratio(),
+                "orphans": p.metrics.orphans,
+                "adoption_ratio": p.metrics.adoption_ratio(),
+                "cycles": p.metrics.cycles,
+            })
+        })
+        .collect();
+    
+    let mut response = json!({
+        "directory": report.directory.to_string_lossy(),
+        "score": round1(report.score),
+        "computed_at": report.computed_at.to_rfc3339(),
+        "metrics": report.metrics,
+        "ratios": {
+            "duplication_density": report.metrics.duplication_density(),
+            "dead_export_ratio": report.metrics.dead_export_ratio(),
+            "orphan_ratio": report.metrics.orphan_ratio(),
+            "adoption_ratio": report.metrics.adoption_ratio(),
+            "cycle_ratio": report.metrics.cycle_ratio(),
+        },
+        "packages": packages,
+        "trend": trend,
+        "message": match &trend {
+            Some(t) => format!("Health {:.1}/100 ({:+.1} since {}).", report.score, t.score_delta, t.previous_at.format("%Y-%m-%d %H:%M")),
+            None => format!("Health {:.1}/100 (first recorded run).", report.score),
+        },
+    });
+    
+    if history > 0 {
+        // The run just recorded is first; skip it
+        let runs: Vec<Value> = g.health_history(&directory, history + 1)
+            .unwrap_or_default()
+            .into_iter()
+            .skip(1)
+            .map(|r| json!({ "score": round1(r.score), "computed_at": r.computed_at.to_rfc3339() }))
+            .collect();
+        response["history"] = json!(runs);
+    }
+    
+    ToolResult::success(response)
+}
+
+fn handle_quick_scan(args: &Value) -> ToolResult {
+    use crate::computations::{quick_scan, QuickScanOptions};
+    
+    let directory = match args.get("directory").and_then(|v| v.as_str()) {
+        Some(s) => PathBuf::from(s),
+        None => return ToolResult::error("Missing: directory"),
+    };
+    let defaults = QuickScanOptions::default();
+    let percent = |key: &str, default: f64| args.get(key).and_then(|v| v.as_f64()).map(|p| p / 100.0).unwrap_or(default);
+    let options = QuickScanOptions {
+        sample_rate: percent("sample_percent", defaults.sample_rate),
+        seed: args.get("seed").and_then(|v| v.as_u64()).unwrap_or(defaults.seed),
+        duplication_budget: percent("duplication_budget", defaults.duplication_budget),
+        adoption_floor: percent("adoption_floor", defaults.adoption_floor),
+        include_tests: args.get("include_tests").and_then(|v| v.as_bool()).unwrap_or(false),
+        ..defaults
+    };
+    
+    mcp_log!("Quick scan of {:?} ({}% sample)", directory, options.sample_rate * 100.0);
+    
+    match quick_scan(&directory, &options) {
+        Ok(report) => {
+            let message = format!(
+                "Sampled {} of {} files in {}ms: full audit {}. {}.",
+                report.files_sampled,
+                report.files_total,
+                report.duration_ms,
+                report.verdict.as_str().replace('_', " "),
+                report.reasons.join("; "),
+            );
+            let mut response = json!(report);
+            response["message"] = json!(message);
+            ToolResult::success(response)
+        }
+        Err(e) => ToolResult::error(format!("Quick scan failed: {}", e)),
+    }
+}
+
+fn handle_record_feedback(g: &mut VerifiedTriad, args: &Value) -> ToolResult {
+    let fingerprint = match args.get("fingerprint").and_then(|v| v.as_str()) {
+        Some(s) => s,
+        None => return ToolResult::error("Missing: fingerprint"),
+    };
+    let outcome = match args.get("outcome").and_then(|v| v.as_str()).map(str::parse::<crate::FixOutcome>) {
+        Some(Ok(outcome)) => outcome,
+        Some(Err(e)) => return ToolResult::error(e.to_string()),
+        None => return ToolResult::error("Missing: outcome"),
+    };
+    let check = match args.get("check").and_then(|v| v.as_str()).map(str::parse::<crate::CheckKind>).transpose() {
+        Ok(check) => check,
+        Err(e) => return ToolResult::error(e.to_string()),
+    };
+    let confidence = args.get("confidence").and_then(|v| v.as_f64());
+    let note = args.get("note").and_then(|v| v.as_str()).map(String::from);
+    
+    match g.record_feedback(fingerprint, outcome, check, confidence, note) {
+        Ok(feedback) => ToolResult::success(json!({
+            "recorded": feedback,
+            "message": format!("Recorded {} for {}.", feedback.outcome.as_str(), feedback.fingerprint),
+        })),
+        Err(e) => ToolResult::error(format!("Could not record feedback: {}", e)),
+    }
+}
+
+fn handle_calibration(g: &mut VerifiedTriad, args: &Value) -> ToolResult {
+    let target = args.get("target_precision").and_then(|v| v.as_f64());
+    
+    match g.calibration(target) {
+        Ok(report) => {
+            let message = if report.checks.is_empty() {
+                "No fix outcomes recorded yet; record them with ground_record_feedback.".to_string()
+            } else {
+                format!("Calibration for {} check(s) from recorded fix outcomes.", report.checks.len())
+            };
+            let mut response = json!(report);
+            response["message"] = json!(message);
+            ToolResult::success(response)
+        }
+        Err(e) => ToolResult::error(format!("Calibration failed: {}", e)),
+    }
+}
+
+fn round1(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -3544,17 +4657,27 @@ mod tests {
     #[test]
     fn test_tool_definitions() {
         let tools = list_tools();
-        assert_eq!(tools.len(), 20); // Focused AI-native tool set + pattern analysis + graph tools + explain
+        assert_eq!(tools.len(), 32); // Focused AI-native tool set + pattern analysis + graph tools + explain + calibration
         
         let names: Vec<_> = tools.iter().map(|t| t.name.as_str()).collect();
         // Check tools
         assert!(names.contains(&"ground_compare"));
+        assert!(names.contains(&"ground_compare_snippets"));
+        assert!(names.contains(&"ground_health"));
+        assert!(names.contains(&"ground_quick_scan"));
         assert!(names.contains(&"ground_count_uses"));
         assert!(names.contains(&"ground_check_connections"));
+        assert!(names.contains(&"ground_check_dependencies"));
+        assert!(names.contains(&"ground_check_conventions"));
+        assert!(names.contains(&"ground_find_barrel_bloat"));
+        assert!(names.contains(&"ground_find_signature_clusters"));
+        assert!(names.contains(&"ground_rename_impact"));
+        assert!(names.contains(&"ground_trace_feature"));
         assert!(names.contains(&"ground_find_duplicate_functions"));
         // Claim tools (audit trail)
         assert!(names.contains(&"ground_claim_dead_code"));
         assert!(names.contains(&"ground_claim_orphan"));
+        assert!(names.contains(&"ground_claim_unused_dependency"));
         // Other tools
         assert!(names.contains(&"ground_suggest_fix"));
         assert!(names.contains(&"ground_check_environment"));
@@ -3570,6 +4693,9 @@ mod tests {
         assert!(names.contains(&"ground_analyze"));
         assert!(names.contains(&"ground_diff"));
         assert!(names.contains(&"ground_verify_fix"));
+        // Calibration
+        assert!(names.contains(&"ground_record_feedback"));
+        assert!(names.contains(&"ground_calibration"));
     }
     
     #[test]
@@ -3588,4 +4714,88 @@ mod tests {
         assert_eq!(result.content["claimed"], false);
         assert_eq!(result.content["blocked"], true);
     }
+    
+    #[test]
+    fn test_progress_reported_during_orphan_scan() {
+        use std::rc::Rc;
+        use std::cell::RefCell;
+        
+        let dir = tempdir().unwrap();
+        for i in 0..30 {
+            std::fs::write(dir.path().join(format!("mod{}.ts", i)), "export const x = 1;\n").unwrap();
+        }
+        let mut g = VerifiedTriad::new(dir.path().join("test.db")).unwrap();
+        
+        let updates: Rc<RefCell<Vec<Progress>>> = Rc::default();
+        let sink = updates.clone();
+        let result = handle_tool_call_with_progress(&mut g, "ground_find_orphans", &json!({
+            "directory": dir.path().to_string_lossy()
+        }), move |p| sink.borrow_mut().push(p.clone()));
+        
+        assert!(result.success);
+        let updates = updates.borrow();
+        assert_eq!(updates.len(), 2); // every 25 files, plus the last
+        assert_eq!(updates[1].message(), "orphans: 30/30");
+        
+        // The sink is cleared once the call returns
+        report_progress("orphans", 1, None);
+        assert_eq!(updates.len(), 2);
+    }
+    
+    #[test]
+    fn test_orphans_paged_with_cursor() {
+        let dir = tempdir().unwrap();
+        for i in 0..30 {
+            std::fs::write(dir.path().join(format!("mod{:02}.ts", i)), "export const x = 1;\n").unwrap();
+        }
+        
+        let mut seen = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let mut args = json!({ "directory": dir.path().to_string_lossy(), "page_size": 12 });
+            if let Some(ref c) = cursor {
+                args["cursor"] = json!(c);
+            }
+            let result = handle_find_orphans(&args);
+            assert!(result.success);
+            assert_eq!(result.content["orphan_count"], 30);
+            assert_eq!(result.content["pagination"]["total_pages"], 3);
+            seen.extend(result.content["orphans"].as_array().unwrap().iter()
+                .map(|o| o["relative_path"].as_str().unwrap().to_string()));
+            match result.content["pagination"]["next_cursor"].as_str() {
+                Some(next) => cursor = Some(next.to_string()),
+                None => break,
+            }
+        }
+        
+        // Every orphan exactly once, in path order
+        let expected: Vec<String> = (0..30).map(|i| format!("mod{:02}.ts", i)).collect();
+        assert_eq!(seen, expected);
+        
+        // `page` lands on the same window as the cursor
+        let third = handle_find_orphans(&json!({ "directory": dir.path().to_string_lossy(), "page": 3, "page_size": 12 }));
+        assert_eq!(third.content["orphans"].as_array().unwrap().len(), 6);
+        assert_eq!(third.content["pagination"]["page"], 3);
+    }
+    
+    #[test]
+    fn test_duplicate_lists_paged_together() {
+        let dir = tempdir().unwrap();
+        let body = "{\n    const total = items.reduce((sum, item) => sum + item.price, 0);\n    const tax = total * 0.2;\n    return total + tax;\n}\n";
+        for i in 0..4 {
+            std::fs::write(dir.path().join(format!("cart{}.ts", i)), format!("export function checkout(items) {}", body)).unwrap();
+        }
+        
+        let args = |page: u64| json!({ "directory": dir.path().to_string_lossy(), "min_lines": 1, "page": page, "page_size": 4 });
+        let first = handle_find_duplicate_functions(&args(1));
+        assert!(first.success);
+        assert_eq!(first.content["duplicate_count"], 6);
+        assert_eq!(first.content["pagination"]["total"]["duplicates"], 6);
+        assert_eq!(first.content["pagination"]["next_cursor"], "4");
+        assert_eq!(first.content["duplicates"].as_array().unwrap().len(), 4);
+        
+        let second = handle_find_duplicate_functions(&args(2));
+        assert_eq!(second.content["duplicates"].as_array().unwrap().len(), 2);
+        assert!(second.content["pagination"]["next_cursor"].is_null());
+    }
 }
@@ -5,11 +5,15 @@
 //! Tracks all computed evidence. Claims are validated against this registry.
 //! If evidence doesn't exist for a claim, the claim is BLOCKED.
 
+use std::collections::HashSet;
 use std::path::Path;
-use rusqlite::{Connection, params};
+use rusqlite::{Connection, OptionalExtension, params};
 use thiserror::Error;
 
-use crate::computations::{SimilarityEvidence, UsageEvidence, ConnectivityEvidence};
+use crate::audit::AuditRun;
+use crate::calibration::Feedback;
+use crate::check::{CheckKind, Finding};
+use crate::computations::{SimilarityEvidence, UsageEvidence, ConnectivityEvidence, DependencyEvidence, HealthReport};
 
 #[derive(Error, Debug)]
 pub enum RegistryError {
@@ -75,6 +79,73 @@ impl VerificationRegistry {
             
             CREATE INDEX IF NOT EXISTS idx_connectivity_module 
             ON connectivity_evidence(module_path);
+            
+            CREATE TABLE IF NOT EXISTS dependency_evidence (
+                id TEXT PRIMARY KEY,
+                package_dir TEXT NOT NULL,
+                unused_count INTEGER NOT NULL,
+                missing_count INTEGER NOT NULL,
+                computed_at TEXT NOT NULL,
+                evidence_json TEXT NOT NULL
+            );
+            
+            CREATE INDEX IF NOT EXISTS idx_dependency_package 
+            ON dependency_evidence(package_dir);
+            
+            CREATE TABLE IF NOT EXISTS health_runs (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                directory TEXT NOT NULL,
+                score REAL NOT NULL,
+                computed_at TEXT NOT NULL,
+                report_json TEXT NOT NULL
+            );
+            
+            CREATE INDEX IF NOT EXISTS idx_health_directory 
+            ON health_runs(directory, computed_at);
+            
+            CREATE TABLE IF NOT EXISTS audit_runs (
+                id TEXT PRIMARY KEY,
+                started_at TEXT NOT NULL,
+                finished_at TEXT NOT NULL,
+                ground_version TEXT NOT NULL,
+                git_commit TEXT,
+                run_json TEXT NOT NULL
+            );
+            
+            CREATE TABLE IF NOT EXISTS audit_checks (
+                run_id TEXT NOT NULL,
+                root TEXT NOT NULL,
+                check_kind TEXT NOT NULL,
+                findings INTEGER NOT NULL,
+                suppressed INTEGER NOT NULL,
+                files_scanned INTEGER NOT NULL,
+                error TEXT
+            );
+            
+            CREATE INDEX IF NOT EXISTS idx_audit_checks_root 
+            ON audit_checks(root, check_kind);
+            
+            CREATE TABLE IF NOT EXISTS audit_findings (
+                run_id TEXT NOT NULL,
+                root TEXT NOT NULL,
+                check_kind TEXT NOT NULL,
+                fingerprint TEXT NOT NULL,
+                file TEXT NOT NULL,
+                line INTEGER,
+                severity TEXT NOT NULL,
+                message TEXT NOT NULL
+            );
+            
+            CREATE INDEX IF NOT EXISTS idx_audit_findings_run 
+            ON audit_findings(run_id, root, check_kind);
+            
+            CREATE TABLE IF NOT EXISTS finding_feedback (
+                fingerprint TEXT NOT NULL,
+                check_kind TEXT NOT NULL,
+                recorded_at TEXT NOT NULL,
+                feedback_json TEXT NOT NULL,
+                PRIMARY KEY (fingerprint, check_kind)
+            );
         "#)?;
         
         Ok(Self { conn })
@@ -118,6 +189,70 @@ impl VerificationRegistry {
                 computed_at TEXT NOT NULL,
                 evidence_json TEXT NOT NULL
             );
+            
+            CREATE TABLE IF NOT EXISTS dependency_evidence (
+                id TEXT PRIMARY KEY,
+                package_dir TEXT NOT NULL,
+                unused_count INTEGER NOT NULL,
+                missing_count INTEGER NOT NULL,
+                computed_at TEXT NOT NULL,
+                evidence_json TEXT NOT NULL
+            );
+            
+            CREATE TABLE IF NOT EXISTS health_runs (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                directory TEXT NOT NULL,
+                score REAL NOT NULL,
+                computed_at TEXT NOT NULL,
+                report_json TEXT NOT NULL
+            );
+            
+            CREATE INDEX IF NOT EXISTS idx_health_directory 
+            ON health_runs(directory, computed_at);
+            
+            CREATE TABLE IF NOT EXISTS audit_runs (
+                id TEXT PRIMARY KEY,
+                started_at TEXT NOT NULL,
+                finished_at TEXT NOT NULL,
+                ground_version TEXT NOT NULL,
+                git_commit TEXT,
+                run_json TEXT NOT NULL
+            );
+            
+            CREATE TABLE IF NOT EXISTS audit_checks (
+                run_id TEXT NOT NULL,
+                root TEXT NOT NULL,
+                check_kind TEXT NOT NULL,
+                findings INTEGER NOT NULL,
+                suppressed INTEGER NOT NULL,
+                files_scanned INTEGER NOT NULL,
+                error TEXT
+            );
+            
+            CREATE INDEX IF NOT EXISTS idx_audit_checks_root 
+            ON audit_checks(root, check_kind);
+            
+            CREATE TABLE IF NOT EXISTS audit_findings (
+                run_id TEXT NOT NULL,
+                root TEXT NOT NULL,
+                check_kind TEXT NOT NULL,
+                fingerprint TEXT NOT NULL,
+                file TEXT NOT NULL,
+                line INTEGER,
+                severity TEXT NOT NULL,
+                message TEXT NOT NULL
+            );
+            
+            CREATE INDEX IF NOT EXISTS idx_audit_findings_run 
+            ON audit_findings(run_id, root, check_kind);
+            
+            CREATE TABLE IF NOT EXISTS finding_feedback (
+                fingerprint TEXT NOT NULL,
+                check_kind TEXT NOT NULL,
+                recorded_at TEXT NOT NULL,
+                feedback_json TEXT NOT NULL,
+                PRIMARY KEY (fingerprint, check_kind)
+            );
         "#)?;
         
         Ok(registry)
@@ -257,6 +392,238 @@ impl VerificationRegistry {
         }
     }
     
+    // --- Dependency Evidence ---
+    
+    /// Record package dependency analysis result
+    pub fn record_dependencies(&mut self, evidence: &DependencyEvidence) -> Result<(), RegistryError> {
+        let json = serde_json::to_string(evidence)?;
+        
+        self.conn.execute(
+            r#"INSERT OR REPLACE INTO dependency_evidence 
+               (id, package_dir, unused_count, missing_count, computed_at, evidence_json)
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6)"#,
+            params![
+                evidence.id.to_string(),
+                evidence.package_dir.to_string_lossy().to_string(),
+                evidence.unused.len() as i64,
+                evidence.missing.len() as i64,
+                evidence.computed_at.to_rfc3339(),
+                json,
+            ],
+        )?;
+        
+        Ok(())
+    }
+    
+    /// Get the latest dependency evidence for a package directory
+    pub fn get_dependencies(&self, package_dir: &Path) -> Result<Option<DependencyEvidence>, RegistryError> {
+        let dir = package_dir.canonicalize().unwrap_or_else(|_| package_dir.to_path_buf());
+        
+        let result: Option<String> = self.conn.query_row(
+            r#"SELECT evidence_json FROM dependency_evidence 
+               WHERE package_dir = ?1
+               ORDER BY computed_at DESC LIMIT 1"#,
+            params![dir.to_string_lossy().to_string()],
+            |row| row.get(0),
+        ).ok();
+        
+        match result {
+            Some(json) => Ok(Some(serde_json::from_str(&json)?)),
+            None => Ok(None),
+        }
+    }
+    
+    // --- Health Runs ---
+    
+    /// Record a health score run
+    pub fn record_health(&mut self, report: &HealthReport) -> Result<(), RegistryError> {
+        let json = serde_json::to_string(report)?;
+        
+        self.conn.execute(
+            r#"INSERT INTO health_runs (directory, score, computed_at, report_json)
+               VALUES (?1, ?2, ?3, ?4)"#,
+            params![
+                report.directory.to_string_lossy().to_string(),
+                report.score,
+                report.computed_at.to_rfc3339(),
+                json,
+            ],
+        )?;
+        
+        Ok(())
+    }
+    
+    /// Get the most recent health run for a directory
+    pub fn latest_health(&self, directory: &Path) -> Result<Option<HealthReport>, RegistryError> {
+        Ok(self.health_history(directory, 1)?.into_iter().next())
+    }
+    
+    /// Get recent health runs for a directory (newest first)
+    pub fn health_history(&self, directory: &Path, limit: usize) -> Result<Vec<HealthReport>, RegistryError> {
+        let mut stmt = self.conn.prepare(
+            r#"SELECT report_json FROM health_runs 
+               WHERE directory = ?1
+               ORDER BY computed_at DESC, id DESC LIMIT ?2"#,
+        )?;
+        
+        let rows = stmt.query_map(
+            params![directory.to_string_lossy().to_string(), limit as i64],
+            |row| row.get::<_, String>(0),
+        )?;
+        
+        let mut reports = Vec::new();
+        for json in rows {
+            reports.push(serde_json::from_str(&json?)?);
+        }
+        Ok(reports)
+    }
+    
+    // --- Audits ---
+    
+    /// Record an audit run with a row per check and per finding
+    pub fn record_audit(&mut self, run: &AuditRun) -> Result<(), RegistryError> {
+        let tx = self.conn.transaction()?;
+        tx.execute(
+            r#"INSERT INTO audit_runs (id, started_at, finished_at, ground_version, git_commit, run_json)
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6)"#,
+            params![
+                run.id,
+                run.started_at.to_rfc3339(),
+                run.finished_at.to_rfc3339(),
+                run.ground_version,
+                run.git_commit,
+                serde_json::to_string(run)?,
+            ],
+        )?;
+        
+        for root in &run.roots {
+            let root_path = root.root.to_string_lossy().to_string();
+            for check in &root.checks {
+                tx.execute(
+                    r#"INSERT INTO audit_checks (run_id, root, check_kind, findings, suppressed, files_scanned, error)
+                       VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)"#,
+                    params![
+                        run.id,
+                        root_path,
+                        check.kind.as_str(),
+                        check.findings.len() as i64,
+                        check.suppressed as i64,
+                        check.files_scanned as i64,
+                        check.error,
+                    ],
+                )?;
+                for finding in &check.findings {
+                    tx.execute(
+                        r#"INSERT INTO audit_findings (run_id, root, check_kind, fingerprint, file, line, severity, message)
+                           VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)"#,
+                        params![
+                            run.id,
+                            root_path,
+                            check.kind.as_str(),
+                            finding.fingerprint,
+                            finding.file.to_string_lossy().to_string(),
+                            finding.line.map(|l| l as i64),
+                            serde_json::to_value(finding.severity)?.as_str().unwrap_or_default().to_string(),
+                            finding.message,
+                        ],
+                    )?;
+                }
+            }
+        }
+        
+        tx.commit()?;
+        Ok(())
+    }
+    
+    /// Fingerprints the last successful audit of a root's check found, if
+    /// it's been audited before
+    pub fn previous_audit_fingerprints(&self, root: &Path, kind: CheckKind) -> Result<Option<HashSet<String>>, RegistryError> {
+        let root = root.to_string_lossy().to_string();
+        let run_id: Option<String> = self.conn.query_row(
+            r#"SELECT c.run_id FROM audit_checks c JOIN audit_runs r ON r.id = c.run_id
+               WHERE c.root = ?1 AND c.check_kind = ?2 AND c.error IS NULL
+               ORDER BY r.finished_at DESC LIMIT 1"#,
+            params![root, kind.as_str()],
+            |row| row.get(0),
+        ).optional()?;
+        let Some(run_id) = run_id else {
+            return Ok(None);
+        };
+        
+        let mut stmt = self.conn.prepare(
+            "SELECT fingerprint FROM audit_findings WHERE run_id = ?1 AND root = ?2 AND check_kind = ?3",
+        )?;
+        let rows = stmt.query_map(params![run_id, root, kind.as_str()], |row| row.get::<_, String>(0))?;
+        Ok(Some(rows.collect::<Result<_, _>>()?))
+    }
+    
+    /// Recent audit runs (newest first)
+    pub fn audit_history(&self, limit: usize) -> Result<Vec<AuditRun>, RegistryError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT run_json FROM audit_runs ORDER BY finished_at DESC LIMIT ?1",
+        )?;
+        let rows = stmt.query_map(params![limit as i64], |row| row.get::<_, String>(0))?;
+        
+        let mut runs = Vec::new();
+        for json in rows {
+            runs.push(serde_json::from_str(&json?)?);
+        }
+        Ok(runs)
+    }
+    
+    /// The finding with a fingerprint in the most recent audit that found it
+    pub fn latest_audit_finding(&self, fingerprint: &str) -> Result<Option<(CheckKind, Finding)>, RegistryError> {
+        let json: Option<String> = self.conn.query_row(
+            r#"SELECT r.run_json FROM audit_findings f JOIN audit_runs r ON r.id = f.run_id
+               WHERE f.fingerprint = ?1 ORDER BY r.finished_at DESC LIMIT 1"#,
+            params![fingerprint],
+            |row| row.get(0),
+        ).optional()?;
+        let Some(json) = json else {
+            return Ok(None);
+        };
+        
+        let run: AuditRun = serde_json::from_str(&json)?;
+        Ok(run.roots.into_iter()
+            .flat_map(|root| root.checks)
+            .find_map(|check| {
+                let kind = check.kind;
+                check.findings.into_iter().find(|f| f.fingerprint == fingerprint).map(|f| (kind, f))
+            }))
+    }
+    
+    // --- Feedback Methods ---
+    
+    /// Record a fix outcome, replacing any earlier one for the same finding
+    pub fn record_feedback(&mut self, feedback: &Feedback) -> Result<(), RegistryError> {
+        self.conn.execute(
+            r#"INSERT OR REPLACE INTO finding_feedback (fingerprint, check_kind, recorded_at, feedback_json)
+               VALUES (?1, ?2, ?3, ?4)"#,
+            params![
+                feedback.fingerprint,
+                feedback.check.as_str(),
+                feedback.recorded_at.to_rfc3339(),
+                serde_json::to_string(feedback)?,
+            ],
+        )?;
+        Ok(())
+    }
+    
+    /// Recorded fix outcomes (oldest first), optionally for one check
+    pub fn feedback(&self, kind: Option<CheckKind>) -> Result<Vec<Feedback>, RegistryError> {
+        let mut stmt = self.conn.prepare(
+            r#"SELECT feedback_json FROM finding_feedback
+               WHERE ?1 IS NULL OR check_kind = ?1 ORDER BY recorded_at"#,
+        )?;
+        let rows = stmt.query_map(params![kind.map(|k| k.as_str())], |row| row.get::<_, String>(0))?;
+        
+        let mut feedback = Vec::new();
+        for json in rows {
+            feedback.push(serde_json::from_str(&json?)?);
+        }
+        Ok(feedback)
+    }
+    
     // --- Utility Methods ---
     
     /// List all computations (for debugging/display)
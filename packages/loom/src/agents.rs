@@ -5,11 +5,13 @@
 
 use std::collections::HashMap;
 use std::path::Path;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use rusqlite::{Connection, params};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use crate::probe::Probe;
+
 #[derive(Error, Debug)]
 pub enum AgentError {
     #[error("Database error: {0}")]
@@ -185,34 +187,222 @@ pub struct QualityMetrics {
     pub failures: u32,
     /// Average time to complete (seconds)
     pub avg_duration_secs: f64,
-    /// Quality score by task type (learned)
-    pub by_type: HashMap<String, f32>,
+    /// Learned score, successes, failures and durations per task type
+    #[serde(default, deserialize_with = "type_records")]
+    pub by_type: HashMap<String, TypeQuality>,
+    /// Completions a reviewer rejected or that were reopened (each also
+    /// counted in `failures`)
+    #[serde(default)]
+    pub rejections: u32,
+    /// Successes and failures weighted by recency, which the success rates
+    /// come from (`successes` and `failures` are lifetime counts)
+    #[serde(default)]
+    pub recent: Tally,
+    /// When `recent` was last decayed
+    #[serde(default)]
+    pub decayed_at: Option<DateTime<Utc>>,
+}
+
+/// Successes and failures that lose half their weight every half-life, so
+/// a failure two half-lives old counts a quarter as much as one today
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Tally {
+    pub successes: f64,
+    pub failures: f64,
+}
+
+impl Tally {
+    pub fn total(&self) -> f64 {
+        self.successes + self.failures
+    }
+    
+    /// Recent weights, or the lifetime counts for metrics recorded before
+    /// there were any
+    fn or_counts(self, successes: u32, failures: u32) -> Self {
+        if self.total() > 0.0 {
+            self
+        } else {
+            Self { successes: successes as f64, failures: failures as f64 }
+        }
+    }
+    
+    fn scale(&mut self, factor: f64) {
+        self.successes *= factor;
+        self.failures *= factor;
+    }
+}
+
+/// Runs of prior weight the overall success rate gets against an agent's
+/// record on a task type, so one lucky run doesn't outrank a long record
+const TYPE_PRIOR_RUNS: f64 = 3.0;
+
+/// An agent's record on one task type
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TypeQuality {
+    /// Learned score, moved toward 1.0 by successes and approvals and
+    /// toward 0.0 by failures
+    #[serde(default = "default_type_score")]
+    pub score: f32,
+    #[serde(default)]
+    pub successes: u32,
+    #[serde(default)]
+    pub failures: u32,
+    /// Average time to complete a task of this type successfully (seconds)
+    #[serde(default)]
+    pub avg_duration_secs: f64,
+    #[serde(default)]
+    pub recent: Tally,
+}
+
+impl Default for TypeQuality {
+    fn default() -> Self {
+        Self { score: default_type_score(), successes: 0, failures: 0, avg_duration_secs: 0.0, recent: Tally::default() }
+    }
+}
+
+fn default_type_score() -> f32 {
+    0.5
+}
+
+/// Per-type records, or the bare scores metrics were saved with before
+/// types kept counts
+fn type_records<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<HashMap<String, TypeQuality>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Stored {
+        Record(TypeQuality),
+        Score(f32),
+    }
+    let stored: HashMap<String, Stored> = HashMap::deserialize(deserializer)?;
+    Ok(stored.into_iter()
+        .map(|(task_type, stored)| match stored {
+            Stored::Record(record) => (task_type, record),
+            Stored::Score(score) => (task_type, TypeQuality { score, ..Default::default() }),
+        })
+        .collect())
+}
+
+impl TypeQuality {
+    pub fn runs(&self) -> u32 {
+        self.successes + self.failures
+    }
+    
+    /// Recency-weighted successes and failures
+    pub fn tally(&self) -> Tally {
+        self.recent.or_counts(self.successes, self.failures)
+    }
+    
+    pub fn success_rate(&self) -> f64 {
+        let tally = self.tally();
+        if tally.total() == 0.0 { 0.5 } else { tally.successes / tally.total() }
+    }
 }
 
 impl QualityMetrics {
+    /// Recency-weighted successes and failures
+    pub fn tally(&self) -> Tally {
+        self.recent.or_counts(self.successes, self.failures)
+    }
+    
+    /// Success rate, weighted toward recent executions
     pub fn success_rate(&self) -> f64 {
-        let total = self.successes + self.failures;
-        if total == 0 { 0.5 } else { self.successes as f64 / total as f64 }
+        let tally = self.tally();
+        if tally.total() == 0.0 { 0.5 } else { tally.successes / tally.total() }
+    }
+    
+    /// Age the recent weights to `now`, halving them every `half_life`
+    /// since they were last aged
+    pub fn decay(&mut self, now: DateTime<Utc>, half_life: Duration) {
+        self.recent = self.tally();
+        for stats in self.by_type.values_mut() {
+            stats.recent = stats.tally();
+        }
+        if let Some(at) = self.decayed_at {
+            let elapsed = (now - at).num_seconds();
+            if elapsed > 0 && half_life.num_seconds() > 0 {
+                let factor = 0.5f64.powf(elapsed as f64 / half_life.num_seconds() as f64);
+                self.recent.scale(factor);
+                for stats in self.by_type.values_mut() {
+                    stats.recent.scale(factor);
+                }
+            }
+        }
+        self.decayed_at = Some(now);
+    }
+    
+    /// Success rate on tasks of these types (a task's labels), pooled over
+    /// the types with history and pulled toward the overall rate while
+    /// there are few runs; the overall rate when there are none
+    pub fn success_rate_for(&self, task_types: &[String]) -> f64 {
+        let (successes, runs) = task_types.iter()
+            .filter_map(|t| self.by_type.get(t))
+            .map(TypeQuality::tally)
+            .fold((0.0, 0.0), |(s, n), t| (s + t.successes, n + t.total()));
+        if runs == 0.0 {
+            return self.success_rate();
+        }
+        (successes + TYPE_PRIOR_RUNS * self.success_rate()) / (runs + TYPE_PRIOR_RUNS)
+    }
+    
+    /// Average successful duration on tasks of these types, or overall when
+    /// none of them has completed
+    pub fn avg_duration_for(&self, task_types: &[String]) -> f64 {
+        let (total, count) = task_types.iter()
+            .filter_map(|t| self.by_type.get(t))
+            .fold((0.0, 0.0), |(d, n), t| {
+                let weight = t.tally().successes;
+                (d + t.avg_duration_secs * weight, n + weight)
+            });
+        if count == 0.0 { self.avg_duration_secs } else { total / count }
     }
     
     pub fn record_success(&mut self, task_type: &str, duration_secs: f64) {
+        self.recent = self.tally();
         self.successes += 1;
-        // Update rolling average
-        let total = self.successes + self.failures;
-        self.avg_duration_secs = 
-            (self.avg_duration_secs * (total - 1) as f64 + duration_secs) / total as f64;
+        self.recent.successes += 1.0;
+        // Update rolling average, weighted like the rates
+        self.avg_duration_secs += (duration_secs - self.avg_duration_secs) / self.recent.total();
         
-        // Update type-specific score
-        let score = self.by_type.entry(task_type.to_string()).or_insert(0.5);
-        *score = (*score * 0.9) + 0.1; // Increase towards 1.0
+        let stats = self.by_type.entry(task_type.to_string()).or_default();
+        stats.recent = stats.tally();
+        stats.successes += 1;
+        stats.recent.successes += 1.0;
+        stats.avg_duration_secs += (duration_secs - stats.avg_duration_secs) / stats.recent.successes;
+        stats.score = (stats.score * 0.9) + 0.1; // Increase towards 1.0
     }
     
     pub fn record_failure(&mut self, task_type: &str) {
+        self.recent = self.tally();
         self.failures += 1;
-        
-        // Update type-specific score
-        let score = self.by_type.entry(task_type.to_string()).or_insert(0.5);
-        *score = *score * 0.9; // Decrease towards 0.0
+        self.recent.failures += 1.0;
+        let stats = self.by_type.entry(task_type.to_string()).or_default();
+        stats.recent = stats.tally();
+        stats.failures += 1;
+        stats.recent.failures += 1.0;
+        stats.score *= 0.9; // Decrease towards 0.0
+    }
+    
+    /// Fold in a review of completed work of the given types. A rejection
+    /// is a failure; an approval only raises the type scores, since the
+    /// completion counted as a success when it happened.
+    pub fn record_review(&mut self, task_types: &[String], approved: bool) {
+        if !approved {
+            self.recent = self.tally();
+            self.failures += 1;
+            self.recent.failures += 1.0;
+            self.rejections += 1;
+        }
+        let unknown = ["unknown".to_string()];
+        let task_types = if task_types.is_empty() { &unknown[..] } else { task_types };
+        for task_type in task_types {
+            let stats = self.by_type.entry(task_type.clone()).or_default();
+            if !approved {
+                stats.recent = stats.tally();
+                stats.failures += 1;
+                stats.recent.failures += 1.0;
+            }
+            stats.score = if approved { (stats.score * 0.9) + 0.1 } else { stats.score * 0.9 };
+        }
     }
 }
 
@@ -239,6 +429,15 @@ pub struct AgentProfile {
     pub available: bool,
     /// Last used timestamp
     pub last_used: Option<DateTime<Utc>>,
+    /// Last heartbeat (agents that never ping are judged by `available` alone)
+    #[serde(default)]
+    pub last_heartbeat: Option<DateTime<Utc>>,
+    /// Stopped sending heartbeats for longer than the timeout
+    #[serde(default)]
+    pub unresponsive: bool,
+    /// Latest probe of the agent's CLI
+    #[serde(default)]
+    pub probe: Option<Probe>,
 }
 
 impl AgentProfile {
@@ -255,6 +454,9 @@ impl AgentProfile {
             active: 0,
             available: true,
             last_used: None,
+            last_heartbeat: None,
+            unresponsive: false,
+            probe: None,
         }
     }
     
@@ -271,6 +473,9 @@ impl AgentProfile {
             active: 0,
             available: true,
             last_used: None,
+            last_heartbeat: None,
+            unresponsive: false,
+            probe: None,
         }
     }
     
@@ -287,6 +492,9 @@ impl AgentProfile {
             active: 0,
             available: true,
             last_used: None,
+            last_heartbeat: None,
+            unresponsive: false,
+            probe: None,
         }
     }
     
@@ -303,12 +511,20 @@ impl AgentProfile {
             active: 0,
             available: true,
             last_used: None,
+            last_heartbeat: None,
+            unresponsive: false,
+            probe: None,
         }
     }
     
     /// Check if agent has capacity
+    /// Cold-start latency from the latest probe (0 when never probed)
+    pub fn cold_start_secs(&self) -> f64 {
+        self.probe.as_ref().and_then(|p| p.cold_start_ms).map_or(0.0, |ms| ms as f64 / 1000.0)
+    }
+    
     pub fn has_capacity(&self) -> bool {
-        self.available && self.active < self.max_concurrent
+        self.available && !self.unresponsive && self.active < self.max_concurrent
     }
     
     /// Score this agent for a task
@@ -329,8 +545,8 @@ impl AgentProfile {
             capability_score /= matches as f64;
         }
         
-        // Quality score from history
-        let quality_score = self.quality.success_rate();
+        // Quality score from history, on this kind of task where there is some
+        let quality_score = self.quality.success_rate_for(labels);
         
         // Cost score (inverse - lower cost is better, but capped to prevent dominating)
         let estimated_cost = self.cost.estimate(estimated_tokens);
@@ -355,9 +571,24 @@ impl AgentProfile {
     }
 }
 
+/// An agent's latest heartbeat
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Heartbeat {
+    pub agent_id: String,
+    pub last_seen: DateTime<Utc>,
+    /// Whatever the agent reported with it (e.g. "idle", "busy: lm-4f2a")
+    pub status: Option<String>,
+    /// Past the timeout, so routing skips the agent
+    pub unresponsive: bool,
+}
+
 /// Agent registry - stores and manages all agent profiles
 pub struct AgentRegistry {
     conn: Connection,
+    /// Silence after which an agent that has sent heartbeats is unresponsive
+    heartbeat_timeout: Option<Duration>,
+    /// Age over which quality metrics lose half their weight
+    quality_half_life: Option<Duration>,
 }
 
 impl AgentRegistry {
@@ -369,7 +600,14 @@ impl AgentRegistry {
         conn.execute_batch("PRAGMA journal_mode=WAL; PRAGMA busy_timeout=5000;")
             .map_err(|e| AgentError::Database(e))?;
         
-        let registry = Self { conn };
+        let registry = Self { conn, heartbeat_timeout: None, quality_half_life: None };
+        registry.init_schema()?;
+        Ok(registry)
+    }
+    
+    /// Create an in-memory agent registry (for testing)
+    pub fn in_memory() -> Result<Self, AgentError> {
+        let registry = Self { conn: Connection::open_in_memory()?, heartbeat_timeout: None, quality_half_life: None };
         registry.init_schema()?;
         Ok(registry)
     }
@@ -410,7 +648,17 @@ impl AgentRegistry {
             );
             
             CREATE INDEX IF NOT EXISTS idx_history_agent ON agent_history(agent_id);
+            
+            CREATE TABLE IF NOT EXISTS agent_heartbeats (
+                agent_id TEXT PRIMARY KEY,
+                last_seen TEXT NOT NULL,
+                status TEXT
+            );
         "#)?;
+        
+        // Migration: pricing table version a cost was last recomputed with
+        // (fails silently if the column already exists)
+        let _ = self.conn.execute("ALTER TABLE agent_history ADD COLUMN pricing_version TEXT", []);
         Ok(())
     }
     
@@ -430,6 +678,34 @@ impl AgentRegistry {
         Ok(())
     }
     
+    /// Set the half-life over which executions lose weight in quality
+    /// metrics (`None` weighs them all equally)
+    pub fn set_quality_half_life(&mut self, half_life: Option<Duration>) {
+        self.quality_half_life = half_life;
+    }
+    
+    /// Add an agent, or reconfigure one, at runtime. What's been learned
+    /// about a known agent (quality history, load, last use) is kept.
+    pub fn register_agent(&mut self, mut profile: AgentProfile) -> Result<AgentProfile, AgentError> {
+        if let Some(existing) = self.get_profile(&profile.id)? {
+            profile.quality = existing.quality;
+            profile.active = existing.active;
+            profile.last_used = existing.last_used;
+        }
+        self.upsert_profile(&profile)?;
+        self.get_profile(&profile.id)?.ok_or(AgentError::NotFound(profile.id))
+    }
+    
+    /// Apply a probe: the agent is available if its CLI answered
+    pub fn record_probe(&mut self, probe: &Probe) -> Result<(), AgentError> {
+        let Some(mut profile) = self.get_profile(&probe.agent_id)? else {
+            return Err(AgentError::NotFound(probe.agent_id.clone()));
+        };
+        profile.available = probe.available;
+        profile.probe = Some(probe.clone());
+        self.upsert_profile(&profile)
+    }
+    
     /// Register agents from a TOML file path
     pub fn register_from_file(&mut self, path: impl AsRef<std::path::Path>) -> Result<(), AgentError> {
         let config = crate::models::ModelsConfig::load(path)
@@ -461,7 +737,12 @@ impl AgentRegistry {
         ).ok();
         
         match result {
-            Some(json) => Ok(Some(serde_json::from_str(&json)?)),
+            Some(json) => {
+                let mut profile: AgentProfile = serde_json::from_str(&json)?;
+                let heartbeats = self.heartbeats()?;
+                self.apply_heartbeat(&mut profile, &heartbeats);
+                Ok(Some(profile))
+            }
             None => Ok(None),
         }
     }
@@ -469,7 +750,7 @@ impl AgentRegistry {
     /// Get all agent profiles
     pub fn all_profiles(&self) -> Result<Vec<AgentProfile>, AgentError> {
         let mut stmt = self.conn.prepare("SELECT profile_json FROM agent_profiles")?;
-        let profiles = stmt.query_map([], |row| {
+        let mut profiles: Vec<AgentProfile> = stmt.query_map([], |row| {
             let json: String = row.get(0)?;
             Ok(json)
         })?
@@ -477,9 +758,75 @@ impl AgentRegistry {
         .filter_map(|json| serde_json::from_str(&json).ok())
         .collect();
         
+        let heartbeats = self.heartbeats()?;
+        for profile in &mut profiles {
+            self.apply_heartbeat(profile, &heartbeats);
+        }
+        
         Ok(profiles)
     }
     
+    // ─────────────────────────────────────────────────────────────────────
+    // Heartbeats
+    // ─────────────────────────────────────────────────────────────────────
+    
+    /// Set how long an agent can go without a heartbeat before routing
+    /// skips it (`None` never times out)
+    pub fn set_heartbeat_timeout(&mut self, timeout: Option<Duration>) {
+        self.heartbeat_timeout = timeout;
+    }
+    
+    /// Record that an agent is alive
+    pub fn heartbeat(&mut self, agent_id: &str, status: Option<&str>) -> Result<Heartbeat, AgentError> {
+        if self.get_profile(agent_id)?.is_none() {
+            return Err(AgentError::NotFound(agent_id.to_string()));
+        }
+        
+        let now = Utc::now();
+        self.conn.execute(
+            "INSERT OR REPLACE INTO agent_heartbeats (agent_id, last_seen, status) VALUES (?1, ?2, ?3)",
+            params![agent_id, now.to_rfc3339(), status],
+        )?;
+        
+        Ok(Heartbeat {
+            agent_id: agent_id.to_string(),
+            last_seen: now,
+            status: status.map(String::from),
+            unresponsive: false,
+        })
+    }
+    
+    /// Latest heartbeat from every agent that has sent one
+    pub fn heartbeats(&self) -> Result<Vec<Heartbeat>, AgentError> {
+        let mut stmt = self.conn.prepare("SELECT agent_id, last_seen, status FROM agent_heartbeats ORDER BY agent_id")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, Option<String>>(2)?))
+        })?;
+        
+        let now = Utc::now();
+        let mut heartbeats = Vec::new();
+        for row in rows {
+            let (agent_id, last_seen, status) = row?;
+            let Ok(last_seen) = DateTime::parse_from_rfc3339(&last_seen) else {
+                continue;
+            };
+            let last_seen = last_seen.with_timezone(&Utc);
+            heartbeats.push(Heartbeat {
+                unresponsive: self.heartbeat_timeout.is_some_and(|timeout| now - last_seen > timeout),
+                agent_id,
+                last_seen,
+                status,
+            });
+        }
+        Ok(heartbeats)
+    }
+    
+    fn apply_heartbeat(&self, profile: &mut AgentProfile, heartbeats: &[Heartbeat]) {
+        let heartbeat = heartbeats.iter().find(|h| h.agent_id == profile.id);
+        profile.last_heartbeat = heartbeat.map(|h| h.last_seen);
+        profile.unresponsive = heartbeat.is_some_and(|h| h.unresponsive);
+    }
+    
     /// Record a task execution result
     pub fn record_execution(
         &mut self,
@@ -507,6 +854,9 @@ impl AgentRegistry {
                 active: 0,
                 available: false, // Not available for dispatch (historical record only)
                 last_used: None,
+                last_heartbeat: None,
+                unresponsive: false,
+                probe: None,
             }
         });
         
@@ -531,6 +881,9 @@ impl AgentRegistry {
         
         // Update the profile's quality metrics
         let task_type = task_type.unwrap_or("unknown");
+        if let Some(half_life) = self.quality_half_life {
+            profile.quality.decay(now, half_life);
+        }
         if success {
             profile.quality.record_success(task_type, duration_secs);
         } else {
@@ -542,6 +895,19 @@ impl AgentRegistry {
         Ok(())
     }
     
+    /// Record a review of an agent's completed work against each of its
+    /// task types
+    pub fn record_review(&mut self, agent_id: &str, task_types: &[String], approved: bool) -> Result<(), AgentError> {
+        let Some(mut profile) = self.get_profile(agent_id)? else {
+            return Err(AgentError::NotFound(agent_id.to_string()));
+        };
+        if let Some(half_life) = self.quality_half_life {
+            profile.quality.decay(Utc::now(), half_life);
+        }
+        profile.quality.record_review(task_types, approved);
+        self.upsert_profile(&profile)
+    }
+    
     /// Get best agent for a task
     pub fn best_agent_for(
         &self,
@@ -735,6 +1101,88 @@ impl AgentRegistry {
         Ok(records)
     }
     
+    /// Recompute the cost of every recorded execution
+    ///
+    /// `price` maps an agent, when it ran, and its token count to a cost, or
+    /// `None` to leave the execution alone. Returns each execution with its
+    /// new cost; with `dry_run` nothing is written.
+    pub fn reprice_executions(
+        &mut self,
+        price: impl Fn(&str, DateTime<Utc>, u64) -> Option<f64>,
+        pricing_version: &str,
+        dry_run: bool,
+    ) -> Result<Vec<(ExecutionRecord, Option<f64>)>, AgentError> {
+        let tx = self.conn.transaction()?;
+        let mut results = Vec::new();
+        {
+            let mut stmt = tx.prepare(
+                "SELECT id, agent_id, task_id, task_type, success, duration_secs, tokens_used, cost, timestamp
+                 FROM agent_history
+                 ORDER BY timestamp ASC"
+            )?;
+            let rows = stmt.query_map([], |row| {
+                let timestamp_str: String = row.get(8)?;
+                Ok((row.get::<_, i64>(0)?, ExecutionRecord {
+                    agent_id: row.get(1)?,
+                    task_id: row.get(2)?,
+                    task_type: row.get(3)?,
+                    success: row.get::<_, i32>(4)? == 1,
+                    duration_secs: row.get(5)?,
+                    tokens_used: row.get::<_, Option<i64>>(6)?.map(|t| t as u64),
+                    cost: row.get(7)?,
+                    timestamp: DateTime::parse_from_rfc3339(&timestamp_str)
+                        .map(|dt| dt.with_timezone(&Utc))
+                        .ok(),
+                }))
+            })?.collect::<Result<Vec<_>, _>>()?;
+            
+            for (id, record) in rows {
+                let cost = match (record.tokens_used, record.timestamp) {
+                    (Some(tokens), Some(at)) => price(&record.agent_id, at, tokens),
+                    _ => None,
+                };
+                if let (Some(cost), false) = (cost, dry_run) {
+                    tx.execute(
+                        "UPDATE agent_history SET cost = ?1, pricing_version = ?2 WHERE id = ?3",
+                        params![cost, pricing_version, id],
+                    )?;
+                }
+                results.push((record, cost));
+            }
+        }
+        tx.commit()?;
+        
+        Ok(results)
+    }
+    
+    /// Get execution history for all agents since a point in time, oldest first
+    pub fn get_history_since(&self, since: DateTime<Utc>) -> Result<Vec<ExecutionRecord>, AgentError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT agent_id, task_id, task_type, success, duration_secs, tokens_used, cost, timestamp
+             FROM agent_history
+             WHERE timestamp >= ?1
+             ORDER BY timestamp ASC"
+        )?;
+        
+        let records = stmt.query_map(params![since.to_rfc3339()], |row| {
+            let timestamp_str: String = row.get(7)?;
+            Ok(ExecutionRecord {
+                agent_id: row.get(0)?,
+                task_id: row.get(1)?,
+                task_type: row.get(2)?,
+                success: row.get::<_, i32>(3)? == 1,
+                duration_secs: row.get(4)?,
+                tokens_used: row.get::<_, Option<i64>>(5)?.map(|t| t as u64),
+                cost: row.get(6)?,
+                timestamp: DateTime::parse_from_rfc3339(&timestamp_str)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .ok(),
+            })
+        })?.collect::<Result<Vec<_>, _>>()?;
+        
+        Ok(records)
+    }
+    
     /// Get top performing agents for a task type
     pub fn top_agents_for_type(
         &self,
@@ -761,6 +1209,43 @@ impl AgentRegistry {
         
         Ok(results)
     }
+    
+    /// Prune agent sessions for tasks that no longer exist and execution
+    /// history older than `cutoff`. Returns (orphaned sessions, stale history rows).
+    pub fn prune(
+        &mut self,
+        live_task_ids: &std::collections::HashSet<String>,
+        cutoff: DateTime<Utc>,
+    ) -> Result<(u32, u32), AgentError> {
+        let tx = self.conn.transaction()?;
+        
+        let session_tasks: Vec<(String, String)> = {
+            let mut stmt = tx.prepare("SELECT id, task_id FROM agent_sessions")?;
+            let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+                .collect::<Result<Vec<_>, _>>()?;
+            rows
+        };
+        
+        let mut orphaned = 0;
+        for (id, task_id) in session_tasks {
+            if !live_task_ids.contains(&task_id) {
+                orphaned += tx.execute("DELETE FROM agent_sessions WHERE id = ?1", params![id])?;
+            }
+        }
+        
+        let stale = tx.execute(
+            "DELETE FROM agent_history WHERE timestamp < ?1",
+            params![cutoff.to_rfc3339()],
+        )?;
+        
+        tx.commit()?;
+        Ok((orphaned as u32, stale as u32))
+    }
+    
+    /// VACUUM the database, returning bytes reclaimed
+    pub fn vacuum(&self) -> Result<u64, AgentError> {
+        Ok(crate::maintenance::vacuum(&self.conn)?)
+    }
 }
 
 /// Analytics summary from agent history
@@ -853,6 +1338,65 @@ mod tests {
         assert!(cursor_score > claude_score);
     }
     
+    #[test]
+    fn test_old_failures_decay() {
+        let half_life = Duration::days(30);
+        let start = Utc::now() - Duration::days(120);
+        let mut quality = QualityMetrics::default();
+        quality.decay(start, half_life);
+        for _ in 0..8 {
+            quality.record_failure("coding");
+        }
+        
+        // Four half-lives later the eight failures weigh half a failure
+        quality.decay(start + Duration::days(120), half_life);
+        for _ in 0..2 {
+            quality.record_success("coding", 60.0);
+        }
+        assert_eq!((quality.successes, quality.failures), (2, 8));
+        assert!((quality.success_rate() - 0.8).abs() < 1e-9);
+        assert!(quality.success_rate_for(&["coding".to_string()]) > 0.75);
+        
+        // Metrics saved before decay start from their counts, and bare
+        // per-type scores keep their value
+        let legacy: QualityMetrics = serde_json::from_str(
+            r#"{"successes": 1, "failures": 3, "avg_duration_secs": 0, "by_type": {"docs": 0.75}}"#,
+        ).unwrap();
+        assert_eq!(legacy.success_rate(), 0.25);
+        assert_eq!((legacy.by_type["docs"].score, legacy.by_type["docs"].runs()), (0.75, 0));
+        assert_eq!(crate::models::ModelsConfig::defaults().quality.half_life(), Some(half_life));
+    }
+    
+    #[test]
+    fn test_silent_agents_drop_out_of_routing() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut registry = AgentRegistry::open(dir.path().join("agents.db")).unwrap();
+        registry.upsert_profile(&AgentProfile::claude_code()).unwrap();
+        registry.upsert_profile(&AgentProfile::cursor()).unwrap();
+        
+        assert!(registry.heartbeat("nobody", None).is_err());
+        registry.heartbeat("cursor", Some("idle")).unwrap();
+        
+        // Without a timeout a heartbeat never goes stale
+        assert!(registry.all_profiles().unwrap().iter().all(|p| p.has_capacity()));
+        
+        // Backdate cursor's heartbeat past the timeout; claude-code never
+        // sent one, so it's still judged on `available`
+        registry.set_heartbeat_timeout(Some(Duration::seconds(60)));
+        registry.conn.execute(
+            "UPDATE agent_heartbeats SET last_seen = ?1",
+            params![(Utc::now() - Duration::seconds(120)).to_rfc3339()],
+        ).unwrap();
+        
+        let cursor = registry.get_profile("cursor").unwrap().unwrap();
+        assert!(cursor.unresponsive && !cursor.has_capacity());
+        assert!(registry.get_profile("claude-code").unwrap().unwrap().has_capacity());
+        assert_eq!(registry.heartbeats().unwrap()[0].status.as_deref(), Some("idle"));
+        
+        registry.heartbeat("cursor", None).unwrap();
+        assert!(registry.get_profile("cursor").unwrap().unwrap().has_capacity());
+    }
+    
     #[test]
     fn test_cost_estimation() {
         let opus = CostModel::claude_opus();
@@ -642,6 +642,7 @@ impl Backfill {
                         parent: None,
                         evidence: Some(format!("beads:{}", issue.id)),
                         repo: None, // Will use default from config
+                        kind: Default::default(),
                     })?;
                     
                     created_tasks.insert(issue.id.clone(), task.id.clone());
@@ -668,7 +669,7 @@ impl Backfill {
                 &task_id,
                 Some(&record.inferred_task_type),
                 record.inferred_success,
-                record.inferred_duration_secs,
+                Some(record.inferred_duration_secs),
             )?;
             
             result.executions_recorded += 1;
@@ -16,26 +16,49 @@
 //! lm ready        # Tasks ready to work on
 //! lm mine         # Tasks claimed by you
 //! lm summary      # Overview of all work
+//! lm pause --label deploy --reason "incident"  # Freeze a label or repo
 //!
 //! # Work on a task
 //! lm claim lm-abc --agent claude-code
+//! lm renew lm-abc --agent claude-code  # Keep the claim's lease alive
+//! lm leases       # Expired leases other agents can take over
+//! lm subscribe ui,frontend --agent cursor
+//! lm next --agent cursor  # Claim best task from subscribed topics
 //! lm route lm-abc # Get routing recommendation
 //! lm done lm-abc --evidence "commit abc123"
+//! lm done lm-abc --pr https://github.com/org/repo/pull/42 --tests-added 3
+//! lm comment lm-abc "Fails only on CI" --agent codex
+//! lm plan lm-abc PLAN.md  # Steps become sub-tasks; re-run after editing
+//! lm history lm-abc  # Every state transition
+//! lm daemon events --event completed  # Live task and session events
 //!
 //! # Sessions & Recovery
 //! lm session start lm-abc --agent claude-code
 //! lm checkpoint "Initial analysis complete"
 //! lm session end --status completed
 //! lm recover      # List recoverable sessions
+//! lm reap --dry-run  # Claims idle past [claims] timeout-mins
+//!
+//! # Schedules (UTC)
+//! lm schedule add "Rotate API keys" --cron "0 9 * * mon"
+//! lm schedule add "Renew cert" --at 2025-06-01T09:00:00Z
+//! lm schedule list
 //!
 //! # Formulas
 //! lm formula list
 //! lm formula show feature
-//! lm formula run feature --feature_name "dark mode" --package io
+//! lm formula run feature --vars feature_name="dark mode" --vars package=io
+//! lm formula run feature --vars feature_name=search --vars package=io --create-only
+//! lm formula run feature --vars feature_name=search --vars package=io --dry-run
+//! lm formula install https://example.com/team-formulas.tar.gz
+//! lm formula packs --check
 //!
 //! # Agents
 //! lm agents       # List available agents
 //! lm agent claude-code  # Show agent details
+//! lm heartbeat cursor --status idle  # Keep an agent in routing
+//! lm pricing sync  # Update models.toml to current prices
+//! lm pricing reprice --dry-run  # Re-price recorded executions
 //!
 //! # Git sync
 //! lm sync         # Full sync with git
@@ -47,7 +70,8 @@ use clap::{Parser, Subcommand};
 use loom::{
     Loom, LoomError, CreateTask, Status,
     RoutingStrategy, RoutingConstraints, SessionStatus,
-    Backfill, BackfillOptions, BackfillAnalytics,
+    Backfill, BackfillOptions, BackfillAnalytics, Liveness, Recurrence, LogLevel, PauseScope, Outcome,
+    RunStatus, StepStatus, VariableType, CostFormat,
 };
 
 /// Loom - AI-native coordination layer
@@ -103,6 +127,79 @@ enum Commands {
         agent: Option<String>,
     },
     
+    /// Extend your lease on a claimed task
+    Renew {
+        /// Task ID
+        id: String,
+        
+        /// Agent holding the lease
+        #[arg(long, short)]
+        agent: Option<String>,
+    },
+    
+    /// List leases that have expired or expire soon
+    Leases {
+        /// Include leases expiring within this many minutes
+        #[arg(long, default_value = "0")]
+        within: i64,
+    },
+    
+    /// Claim the best ready task from your subscribed topics
+    Next {
+        /// Agent pulling work
+        #[arg(long, short)]
+        agent: Option<String>,
+    },
+    
+    /// Subscribe an agent to label topics
+    Subscribe {
+        /// Labels (comma-separated)
+        labels: String,
+        
+        /// Agent subscribing
+        #[arg(long, short)]
+        agent: Option<String>,
+        
+        /// Remove these topics instead
+        #[arg(long)]
+        remove: bool,
+    },
+    
+    /// Ask a question that blocks work until a human answers
+    Ask {
+        /// The decision needed
+        question: String,
+        
+        /// Allowed answers (comma-separated)
+        #[arg(long, short)]
+        options: Option<String>,
+        
+        /// Decision to apply if unanswered by the deadline
+        #[arg(long)]
+        default: Option<String>,
+        
+        /// Hours until the default decision applies
+        #[arg(long)]
+        deadline_hours: Option<f64>,
+        
+        /// Task IDs that wait on the answer (comma-separated)
+        #[arg(long, short)]
+        blocks: Option<String>,
+    },
+    
+    /// Answer a question
+    Answer {
+        /// Question task ID
+        id: String,
+        
+        /// The decision
+        decision: String,
+        
+        /// Why
+        #[arg(long, short)]
+        rationale: Option<String>,
+    },
+    
     /// Release a claimed task
     Release {
         /// Task ID
@@ -117,6 +214,22 @@ enum Commands {
         /// Evidence (commit hash, URL, etc.)
         #[arg(long, short)]
         evidence: Option<String>,
+        
+        /// Pull request URL (repeatable)
+        #[arg(long)]
+        pr: Vec<String>,
+        
+        /// Artifact produced (repeatable)
+        #[arg(long)]
+        artifact: Vec<String>,
+        
+        /// Number of tests added
+        #[arg(long)]
+        tests_added: Option<u32>,
+        
+        /// Follow-up task ID (repeatable)
+        #[arg(long)]
+        follow_up: Vec<String>,
     },
     
     /// Cancel a task
@@ -125,6 +238,42 @@ enum Commands {
         id: String,
     },
     
+    /// Put a done task back in the queue; counts against the agent that did it
+    Reopen {
+        /// Task ID
+        id: String,
+        
+        /// Why it's being reopened
+        #[arg(long, short)]
+        reason: Option<String>,
+        
+        /// Who is reopening it
+        #[arg(long, short)]
+        agent: Option<String>,
+    },
+    
+    /// Approve or reject a done task; rejecting reopens it
+    Review {
+        /// Task ID
+        id: String,
+        
+        /// Approve the work
+        #[arg(long, conflicts_with = "reject", required_unless_present = "reject")]
+        approve: bool,
+        
+        /// Reject the work
+        #[arg(long)]
+        reject: bool,
+        
+        /// Note recorded as a comment
+        #[arg(long, short)]
+        note: Option<String>,
+        
+        /// Reviewer
+        #[arg(long, short)]
+        agent: Option<String>,
+    },
+    
     /// List all tasks
     List {
         /// Filter by status
@@ -156,6 +305,25 @@ enum Commands {
         id: String,
     },
     
+    /// Comment on a task (lists its comments when no body is given)
+    Comment {
+        /// Task ID
+        id: String,
+        
+        /// Comment text
+        body: Option<String>,
+        
+        /// Comment author
+        #[arg(long, short)]
+        agent: Option<String>,
+    },
+    
+    /// Show a task's history of state transitions
+    History {
+        /// Task ID
+        id: String,
+    },
+    
     /// Add a dependency (task blocked by another)
     Block {
         /// Task that is blocked
@@ -183,21 +351,72 @@ enum Commands {
         title: String,
     },
     
+    /// Import a markdown plan as sub-tasks (re-import to update them)
+    Plan {
+        /// Task the plan is for
+        id: String,
+        
+        /// Markdown file, or - for stdin
+        file: String,
+    },
+    
     /// Show work summary
     Summary,
     
+    /// Check the work store for problems (dependency cycles)
+    Doctor,
+    
+    /// Freeze a label or repo: its tasks leave the ready queue and can't be
+    /// claimed or routed (lists pauses when no scope is given)
+    Pause {
+        /// Label to pause
+        #[arg(long, conflicts_with = "repo")]
+        label: Option<String>,
+        
+        /// Repository to pause
+        #[arg(long)]
+        repo: Option<String>,
+        
+        /// Why (shown in summaries)
+        #[arg(long, short)]
+        reason: Option<String>,
+    },
+    
+    /// Lift a pause
+    Unpause {
+        /// Label to unpause
+        #[arg(long, conflicts_with = "repo", required_unless_present = "repo")]
+        label: Option<String>,
+        
+        /// Repository to unpause
+        #[arg(long)]
+        repo: Option<String>,
+    },
+    
     /// Route a task to the best agent
     Route {
         /// Task ID
         id: String,
         
-        /// Routing strategy (best, cheapest, fastest)
+        /// Routing strategy (best, cheapest, fastest, learned, least-loaded, round-robin)
         #[arg(long, short, default_value = "best")]
         strategy: String,
         
         /// Maximum cost in dollars
         #[arg(long)]
         max_cost: Option<f64>,
+        
+        /// Agent to prefer when eligible (repeatable)
+        #[arg(long)]
+        prefer: Vec<String>,
+        
+        /// Agent never to route to (repeatable)
+        #[arg(long)]
+        exclude: Vec<String>,
+        
+        /// Route a sub-task to the agent that did its parent
+        #[arg(long)]
+        sticky: bool,
     },
     
     /// Session management
@@ -219,6 +438,13 @@ enum Commands {
     /// List recoverable sessions
     Recover,
     
+    /// Release claims with no activity for [claims] timeout-mins
+    Reap {
+        /// Show stale claims without releasing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    
     /// Resume a session
     Resume {
         /// Session ID
@@ -234,14 +460,53 @@ enum Commands {
         id: String,
     },
     
+    /// Report that an agent is alive (agents that stop reporting are skipped by routing)
+    Heartbeat {
+        /// Agent ID
+        agent: String,
+        /// Free-form status (e.g. idle, busy)
+        #[arg(long)]
+        status: Option<String>,
+    },
+    
+    /// Run agents' CLIs with --version to check they exist and time their start
+    Probe {
+        /// Agent ID (all agents if omitted)
+        agent: Option<String>,
+        /// Seconds each CLI has to answer
+        #[arg(long, default_value = "10")]
+        timeout: u64,
+    },
+    
+    /// Model prices (.loom/pricing.toml or the bundled table)
+    Pricing {
+        #[command(subcommand)]
+        command: PricingCommands,
+    },
+    
     /// Formula management
     Formula {
         #[command(subcommand)]
         command: FormulaCommands,
     },
     
+    /// Task templates (.loom/templates/)
+    Template {
+        #[command(subcommand)]
+        command: TemplateCommands,
+    },
+    
+    /// Recurring and deferred tasks
+    Schedule {
+        #[command(subcommand)]
+        command: ScheduleCommands,
+    },
+    
     /// Git sync operations
-    Sync,
+    Sync {
+        #[command(subcommand)]
+        command: Option<SyncCommands>,
+    },
     
     /// Export and push to git
     Push,
@@ -278,6 +543,12 @@ enum Commands {
         dry_run: bool,
     },
     
+    /// Prune orphaned records and VACUUM the databases
+    Maintain,
+    
+    /// Verify the evidence log hasn't been tampered with
+    VerifyLog,
+    
     /// Show analytics from backfilled data
     Analytics {
         /// Start date filter
@@ -288,6 +559,46 @@ enum Commands {
         #[arg(long, short)]
         agent: Option<String>,
     },
+    
+    /// Export recorded costs per task, agent and label (csv or json)
+    Costs {
+        /// Output format: csv or json
+        #[arg(long, short, default_value = "csv")]
+        format: String,
+        
+        /// Start date (ISO 8601 or relative like "30 days ago")
+        #[arg(long, short)]
+        since: Option<String>,
+        
+        /// Write to a file instead of stdout
+        #[arg(long, short)]
+        output: Option<std::path::PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum PricingCommands {
+    /// Update models.toml and agent costs to the prices in effect today
+    Sync {
+        /// Pricing table to use instead
+        #[arg(long)]
+        file: Option<String>,
+        
+        /// Preview without writing
+        #[arg(long)]
+        dry_run: bool,
+    },
+    
+    /// Recompute recorded execution costs at the price in effect when they ran
+    Reprice {
+        /// Pricing table to use instead
+        #[arg(long)]
+        file: Option<String>,
+        
+        /// Preview without writing
+        #[arg(long)]
+        dry_run: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -318,6 +629,24 @@ enum SessionCommands {
         /// Task ID
         task: String,
     },
+    
+    /// Pause a session (paused time doesn't count as worked)
+    Pause {
+        /// Session ID
+        session: String,
+    },
+    
+    /// Resume a paused session
+    Unpause {
+        /// Session ID
+        session: String,
+    },
+    
+    /// Show active working time for a session, or totals per task and agent
+    Time {
+        /// Session ID (default: all sessions)
+        session: Option<String>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -339,19 +668,159 @@ enum FormulaCommands {
         /// Variables (key=value format)
         #[arg(long, short)]
         vars: Vec<String>,
+        
+        /// Only create the step tasks, for agents to take from the queue
+        #[arg(long)]
+        create_only: bool,
+        
+        /// Show the tasks, routing, and cost without creating anything
+        #[arg(long, conflicts_with = "create_only")]
+        dry_run: bool,
+    },
+    
+    /// Install a formula pack from a directory, archive, or https:// URL
+    Install {
+        /// Local path or https:// URL (.tar, .tar.gz, .tgz, .zip)
+        source: String,
+    },
+    
+    /// List installed formula packs and check their sources for updates
+    Packs {
+        /// Re-fetch each pack's source to check for a newer version
+        #[arg(long)]
+        check: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum TemplateCommands {
+    /// List task templates
+    List,
+    
+    /// Create a task tree from a template
+    Create {
+        /// Template name
+        name: String,
+        
+        /// Variables (key=value format)
+        #[arg(long, short)]
+        vars: Vec<String>,
+        
+        /// Create the tree under this task
+        #[arg(long)]
+        parent: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ScheduleCommands {
+    /// Schedule a task on a cron expression or for a time
+    Add {
+        /// Task title
+        title: String,
+        
+        /// Create the task every time this fires (5-field cron, UTC)
+        #[arg(long, conflicts_with = "at", required_unless_present = "at")]
+        cron: Option<String>,
+        
+        /// Create the task once, at this time (RFC 3339)
+        #[arg(long)]
+        at: Option<String>,
+        
+        /// Description
+        #[arg(long, short)]
+        description: Option<String>,
+        
+        /// Labels (comma-separated)
+        #[arg(long, short)]
+        labels: Option<String>,
+        
+        /// Parent task ID
+        #[arg(long, short)]
+        parent: Option<String>,
+    },
+    
+    /// List schedules
+    List,
+    
+    /// Remove a schedule
+    Rm {
+        /// Schedule ID
+        id: String,
+    },
+    
+    /// Create tasks for due schedules now (the daemon does this every minute)
+    Run,
+}
+
+#[derive(Subcommand)]
+enum SyncCommands {
+    /// Two-way sync with GitHub Issues ([sync.github] in config.toml)
+    Github {
+        /// Show what would change without touching either side
+        #[arg(long)]
+        dry_run: bool,
+    },
+    
+    /// Two-way sync with a Linear team ([sync.linear] in config.toml)
+    Linear {
+        /// Show what would change without touching either side
+        #[arg(long)]
+        dry_run: bool,
+    },
+    
+    /// Two-way sync with a Jira project ([sync.jira] in config.toml)
+    Jira {
+        /// Show what would change without touching either side
+        #[arg(long)]
+        dry_run: bool,
     },
 }
 
 #[derive(Subcommand)]
 enum DaemonCommands {
     /// Start the daemon
-    Start,
+    Start {
+        /// Also serve the HTTP API on this address (e.g. 127.0.0.1:7420)
+        #[arg(long)]
+        http: Option<String>,
+    },
     
     /// Check daemon status
     Status,
     
     /// Stop the daemon
     Stop,
+    
+    /// Show recent daemon log entries (redacted per [logging])
+    Logs {
+        /// Number of entries
+        #[arg(long, short, default_value = "50")]
+        lines: usize,
+        
+        /// Minimum level (info, warn, error)
+        #[arg(long, default_value = "info")]
+        level: String,
+    },
+    
+    /// Stream task and session events as they happen (needs a running daemon)
+    Events {
+        /// Only these events, e.g. completed, task.claimed, session.checkpoint
+        #[arg(long = "event", short)]
+        events: Vec<String>,
+        
+        /// Only events for this task
+        #[arg(long)]
+        task: Option<String>,
+        
+        /// Only events by this agent
+        #[arg(long)]
+        agent: Option<String>,
+        
+        /// Print raw JSON lines
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 fn main() {
@@ -413,6 +882,7 @@ fn run() -> Result<(), LoomError> {
                 parent,
                 evidence: None,
                 repo: None,
+                kind: Default::default(),
             })?;
             
             println!("Created: {} - {}", task.id, task.title);
@@ -421,8 +891,85 @@ fn run() -> Result<(), LoomError> {
         Commands::Claim { id, agent } => {
             let mut loom = Loom::open(".")?;
             let agent = agent.unwrap_or_else(get_hostname);
-            let task = loom.claim(&id, &agent)?;
-            println!("Claimed: {} by {}", task.id, agent);
+            let lease = loom.claim(&id, &agent)?;
+            println!("Claimed: {} by {}", lease.task_id, agent);
+            println!("Lease expires: {}", lease.expires_at.format("%Y-%m-%d %H:%M UTC"));
+        }
+        
+        Commands::Renew { id, agent } => {
+            let mut loom = Loom::open(".")?;
+            let agent = agent.unwrap_or_else(get_hostname);
+            let lease = loom.renew(&id, &agent)?;
+            println!("Renewed: {} until {}", lease.task_id, lease.expires_at.format("%Y-%m-%d %H:%M UTC"));
+        }
+        
+        Commands::Leases { within } => {
+            let loom = Loom::open(".")?;
+            let now = chrono::Utc::now();
+            let leases = loom.expiring_leases(chrono::Duration::minutes(within))?;
+            
+            if leases.is_empty() {
+                println!("No expiring leases");
+            } else {
+                println!("{:<12} {:<16} {:<20} RENEWALS", "TASK", "AGENT", "EXPIRES");
+                for lease in leases {
+                    let expires = if lease.is_expired(now) {
+                        "expired".to_string()
+                    } else {
+                        lease.expires_at.format("%Y-%m-%d %H:%M").to_string()
+                    };
+                    println!("{:<12} {:<16} {:<20} {}", lease.task_id, lease.agent, expires, lease.renewals);
+                }
+            }
+        }
+        
+        Commands::Next { agent } => {
+            let mut loom = Loom::open(".")?;
+            let agent = agent.unwrap_or_else(get_hostname);
+            match loom.next_task(&agent)? {
+                Some(task) => println!("Claimed: {} - {}", task.id, task.title),
+                None => println!("No matching tasks ready for {}", agent),
+            }
+        }
+        
+        Commands::Subscribe { labels, agent, remove } => {
+            let mut loom = Loom::open(".")?;
+            let agent = agent.unwrap_or_else(get_hostname);
+            let labels: Vec<String> = labels.split(',').map(|s| s.trim().to_string()).collect();
+            
+            if remove {
+                loom.unsubscribe(&agent, &labels)?;
+            } else {
+                loom.subscribe(&agent, &labels)?;
+            }
+            println!("{} topics: {:?}", agent, loom.subscriptions(&agent)?);
+        }
+        
+        Commands::Ask { question, options, default, deadline_hours, blocks } => {
+            let mut loom = Loom::open(".")?;
+            let split = |s: Option<String>| -> Vec<String> {
+                s.map(|s| s.split(',').map(|p| p.trim().to_string()).collect()).unwrap_or_default()
+            };
+            let deadline = deadline_hours
+                .map(|h| chrono::Utc::now() + chrono::Duration::seconds((h * 3600.0) as i64));
+            
+            let (task, _) = loom.ask(
+                CreateTask { title: question, ..Default::default() },
+                split(options),
+                deadline,
+                default,
+                &split(blocks),
+            )?;
+            println!("Asked: {} - {}", task.id, task.title);
+        }
+        
+        Commands::Answer { id, decision, rationale } => {
+            let mut loom = Loom::open(".")?;
+            let unblocked = loom.answer(&id, &decision, rationale.as_deref(), "human")?;
+            println!("Answered: {} -> {}", id, decision);
+            if !unblocked.is_empty() {
+                println!("Unblocked: {}", unblocked.join(", "));
+            }
         }
         
         Commands::Release { id } => {
@@ -431,19 +978,45 @@ fn run() -> Result<(), LoomError> {
             println!("Released: {}", id);
         }
         
-        Commands::Done { id, evidence } => {
+        Commands::Done { id, evidence, pr, artifact, tests_added, follow_up } => {
             let mut loom = Loom::open(".")?;
             loom.complete(&id, evidence.as_deref())?;
+            let outcome = loom.record_outcome(&id, Outcome {
+                artifacts: artifact,
+                pr_urls: pr,
+                tests_added,
+                follow_ups: follow_up,
+                ..Default::default()
+            })?;
             println!("Completed: {}", id);
             if let Some(ev) = evidence {
                 println!("Evidence: {}", ev);
             }
+            if !outcome.is_empty() {
+                print_outcome(&outcome);
+            }
+        }
+        
+        Commands::Cancel { id } => {
+            let mut loom = Loom::open(".")?;
+            loom.cancel(&id)?;
+            println!("Cancelled: {}", id);
+        }
+        
+        Commands::Reopen { id, reason, agent } => {
+            let mut loom = Loom::open(".")?;
+            let task = loom.reopen(&id, &agent.unwrap_or_else(get_hostname), reason.as_deref())?;
+            println!("Reopened: {} ({})", task.id, task.status.as_str());
         }
         
-        Commands::Cancel { id } => {
+        Commands::Review { id, approve, note, agent, .. } => {
             let mut loom = Loom::open(".")?;
-            loom.cancel(&id)?;
-            println!("Cancelled: {}", id);
+            let task = loom.review(&id, approve, &agent.unwrap_or_else(get_hostname), note.as_deref())?;
+            if approve {
+                println!("Approved: {}", task.id);
+            } else {
+                println!("Rejected: {} (reopened, {})", task.id, task.status.as_str());
+            }
         }
         
         Commands::List { status, label, repo } => {
@@ -548,6 +1121,13 @@ fn run() -> Result<(), LoomError> {
             println!("Agent:       {}", task.agent.as_deref().unwrap_or("-"));
             println!("Labels:      {:?}", task.labels);
             println!("Parent:      {}", task.parent.as_deref().unwrap_or("-"));
+            if let Some(link) = loom.plan_link(&task.id)? {
+                if link.section.is_empty() {
+                    println!("Plan:        line {}", link.line);
+                } else {
+                    println!("Plan:        {} (#{}, line {})", link.section, link.anchor, link.line);
+                }
+            }
             println!("Evidence:    {}", task.evidence.as_deref().unwrap_or("-"));
             println!("Created:     {}", task.created_at);
             println!("Updated:     {}", task.updated_at);
@@ -555,6 +1135,56 @@ fn run() -> Result<(), LoomError> {
             if let Some(desc) = &task.description {
                 println!("\nDescription:\n{}", desc);
             }
+            
+            if let Some(outcome) = loom.outcome(&task.id)? {
+                println!("\nOutcome:");
+                print_outcome(&outcome);
+            }
+            
+            let comments = loom.comments(&task.id)?;
+            if !comments.is_empty() {
+                println!("\nComments:");
+                print_comments(&comments);
+            }
+        }
+        
+        Commands::History { id } => {
+            let loom = Loom::open(".")?;
+            let history = loom.history(&id)?;
+            if history.is_empty() {
+                println!("No history for {}", id);
+            }
+            for entry in &history {
+                let change = match (&entry.from, &entry.to) {
+                    (Some(from), Some(to)) => format!(" ({} → {})", from, to),
+                    _ => String::new(),
+                };
+                println!(
+                    "  [{}] {}{}{}",
+                    entry.at.format("%Y-%m-%d %H:%M:%S"),
+                    entry.event,
+                    change,
+                    entry.agent.as_deref().map(|a| format!(" by {}", a)).unwrap_or_default()
+                );
+            }
+        }
+        
+        Commands::Comment { id, body, agent } => {
+            let mut loom = Loom::open(".")?;
+            match body {
+                Some(body) => {
+                    let author = agent.unwrap_or_else(get_hostname);
+                    let comment = loom.comment(&id, &author, &body)?;
+                    println!("Commented on {} as {}", comment.task_id, comment.author);
+                }
+                None => {
+                    let comments = loom.comments(&id)?;
+                    if comments.is_empty() {
+                        println!("No comments on {}", id);
+                    }
+                    print_comments(&comments);
+                }
+            }
         }
         
         Commands::Block { task, by } => {
@@ -569,12 +1199,54 @@ fn run() -> Result<(), LoomError> {
             println!("{} is no longer blocked by {}", task, by);
         }
         
+        Commands::Doctor => {
+            let loom = Loom::open(".")?;
+            let cycles = loom.dependency_cycles()?;
+            if cycles.is_empty() {
+                println!("No dependency cycles");
+                return Ok(());
+            }
+            
+            println!("{} dependency cycle(s); these tasks can never become ready:", cycles.len());
+            for cycle in &cycles {
+                println!("  {}", cycle.join(" → "));
+                if let [.., task, by] = cycle.as_slice() {
+                    println!("    fix: lm unblock {} {}", task, by);
+                }
+            }
+            std::process::exit(1);
+        }
+        
         Commands::Spawn { parent, title } => {
             let mut loom = Loom::open(".")?;
             let task = loom.spawn(&parent, title)?;
             println!("Created sub-task: {} under {}", task.id, parent);
         }
         
+        Commands::Plan { id, file } => {
+            let mut loom = Loom::open(".")?;
+            let markdown = if file == "-" {
+                std::io::read_to_string(std::io::stdin())?
+            } else {
+                std::fs::read_to_string(&file)?
+            };
+            
+            let import = loom.import_plan(&id, &markdown)?;
+            println!(
+                "Imported plan into {}: {} created, {} updated, {} unchanged",
+                id, import.created.len(), import.updated.len(), import.unchanged
+            );
+            for (what, ids) in [
+                ("Completed (checked off)", &import.completed),
+                ("Cancelled (removed from plan)", &import.cancelled),
+                ("Removed from plan but in progress", &import.detached),
+            ] {
+                if !ids.is_empty() {
+                    println!("{}: {}", what, ids.join(", "));
+                }
+            }
+        }
+        
         Commands::Summary => {
             let loom = Loom::open(".")?;
             let summary = loom.summary()?;
@@ -587,9 +1259,57 @@ fn run() -> Result<(), LoomError> {
             println!("Blocked:   {}", summary.blocked);
             println!("Done:      {}", summary.done);
             println!("Cancelled: {}", summary.cancelled);
+            if !summary.pauses.is_empty() {
+                println!("Paused:    {}", summary.paused);
+                for pause in &summary.pauses {
+                    println!("  {} - {}", pause.scope, pause.reason.as_deref().unwrap_or("no reason given"));
+                }
+            }
+        }
+        
+        Commands::Pause { label, repo, reason } => {
+            let mut loom = Loom::open(".")?;
+            let scope = match (label, repo) {
+                (Some(label), _) => PauseScope::Label(label),
+                (None, Some(repo)) => PauseScope::Repo(repo),
+                (None, None) => {
+                    let pauses = loom.pauses()?;
+                    if pauses.is_empty() {
+                        println!("Nothing is paused");
+                    }
+                    for pause in &pauses {
+                        println!(
+                            "{:<20} since {} by {}: {}",
+                            pause.scope.to_string(),
+                            pause.paused_at.format("%Y-%m-%d %H:%M"),
+                            pause.paused_by.as_deref().unwrap_or("unknown"),
+                            pause.reason.as_deref().unwrap_or("no reason given")
+                        );
+                    }
+                    return Ok(());
+                }
+            };
+            
+            let pause = loom.pause(scope, reason.as_deref(), Some(&get_hostname()))?;
+            println!("Paused {}", pause.scope);
+        }
+        
+        Commands::Unpause { label, repo } => {
+            let mut loom = Loom::open(".")?;
+            let scope = match (label, repo) {
+                (Some(label), _) => PauseScope::Label(label),
+                (None, Some(repo)) => PauseScope::Repo(repo),
+                (None, None) => unreachable!("clap requires --label or --repo"),
+            };
+            
+            if loom.unpause(&scope)? {
+                println!("Unpaused {}", scope);
+            } else {
+                println!("{} wasn't paused", scope);
+            }
         }
         
-        Commands::Route { id, strategy, max_cost } => {
+        Commands::Route { id, strategy, max_cost, prefer, exclude, sticky } => {
             let mut loom = Loom::open(".")?;
             let task = loom.get(&id)?
                 .ok_or_else(|| LoomError::Config(format!("Task not found: {}", id)))?;
@@ -598,11 +1318,17 @@ fn run() -> Result<(), LoomError> {
                 "best" => RoutingStrategy::Best,
                 "cheapest" => RoutingStrategy::Cheapest,
                 "fastest" => RoutingStrategy::Fastest,
+                "learned" => RoutingStrategy::Learned,
+                "least-loaded" => RoutingStrategy::LeastLoaded,
+                "round-robin" => RoutingStrategy::RoundRobin,
                 s => return Err(LoomError::Config(format!("Unknown strategy: {}", s))),
             };
             
             let constraints = RoutingConstraints {
                 max_cost,
+                preferred_agents: prefer,
+                excluded_agents: exclude,
+                sticky_agent: if sticky { loom.sticky_agent(&task)? } else { None },
                 ..Default::default()
             };
             
@@ -614,9 +1340,15 @@ fn run() -> Result<(), LoomError> {
             println!("Reason:     {}", decision.reason);
             println!("Cost:       ${:.4}", decision.estimated_cost);
             println!("Confidence: {:.0}%", decision.confidence * 100.0);
+            if let Some(budget) = decision.context_budget {
+                println!("Context:    {} tokens of task context", budget);
+            }
             if !decision.alternatives.is_empty() {
                 println!("Alternatives: {:?}", decision.alternatives);
             }
+            for warning in &decision.warnings {
+                println!("Warning:    {}", warning);
+            }
         }
         
         Commands::Session { command } => {
@@ -669,6 +1401,50 @@ fn run() -> Result<(), LoomError> {
                         println!("No active session for task {}", task);
                     }
                 }
+                
+                SessionCommands::Pause { session } => {
+                    let mut loom = Loom::open(".")?;
+                    loom.pause_session(&session)?;
+                    let time = loom.session_time(&session)?;
+                    println!("Paused session {} ({} worked)", session, format_secs(time.active_secs));
+                }
+                
+                SessionCommands::Unpause { session } => {
+                    let mut loom = Loom::open(".")?;
+                    loom.unpause_session(&session)?;
+                    let time = loom.session_time(&session)?;
+                    println!("Resumed session {} ({} paused so far)", session, format_secs(time.paused_secs));
+                }
+                
+                SessionCommands::Time { session: Some(session) } => {
+                    let loom = Loom::open(".")?;
+                    let time = loom.session_time(&session)?;
+                    println!("Session: {}", time.session_id);
+                    println!("Task:    {}", time.task_id);
+                    println!("Agent:   {}", time.agent_id);
+                    println!("Worked:  {}", format_secs(time.active_secs));
+                    println!("Paused:  {}{}", format_secs(time.paused_secs), if time.paused { " (paused now)" } else { "" });
+                }
+                
+                SessionCommands::Time { session: None } => {
+                    let loom = Loom::open(".")?;
+                    let report = loom.time_report()?;
+                    if report.by_task.is_empty() {
+                        println!("No sessions recorded");
+                        return Ok(());
+                    }
+                    
+                    for (heading, totals) in [("Task", &report.by_task), ("Agent", &report.by_agent)] {
+                        println!("{:<24} {:>8} {:>10} {:>10}", heading, "Sessions", "Worked", "Paused");
+                        for total in totals {
+                            println!(
+                                "{:<24} {:>8} {:>10} {:>10}",
+                                truncate(&total.id, 24), total.sessions, format_secs(total.active_secs), format_secs(total.paused_secs)
+                            );
+                        }
+                        println!();
+                    }
+                }
             }
         }
         
@@ -685,6 +1461,29 @@ fn run() -> Result<(), LoomError> {
             println!("Created checkpoint: {} (sequence {})", checkpoint.id, checkpoint.sequence);
         }
         
+        Commands::Reap { dry_run } => {
+            let mut loom = Loom::open(".")?;
+            let reaped = loom.reap_stale_claims(dry_run)?;
+            
+            if reaped.is_empty() {
+                println!("No stale claims");
+            } else {
+                let verb = if dry_run { "Stale" } else { "Released" };
+                for claim in reaped {
+                    println!(
+                        "{}: {} ({}, idle since {})",
+                        verb,
+                        claim.task_id,
+                        claim.agent,
+                        claim.idle_since.format("%Y-%m-%d %H:%M UTC")
+                    );
+                    if let Some(session) = claim.session_id {
+                        println!("  Session {} flagged for recovery", session);
+                    }
+                }
+            }
+        }
+        
         Commands::Recover => {
             let loom = Loom::open(".")?;
             let sessions = loom.recoverable_sessions()?;
@@ -721,18 +1520,95 @@ fn run() -> Result<(), LoomError> {
             let loom = Loom::open(".")?;
             let agents = loom.agents()?;
             
-            println!("{:<12} {:<20} {:<10} {:?}", "ID", "NAME", "AVAILABLE", "SUCCESS RATE");
+            println!("{:<12} {:<20} {:<14} {:?}", "ID", "NAME", "AVAILABLE", "SUCCESS RATE");
             for agent in agents {
                 println!(
-                    "{:<12} {:<20} {:<10} {:.0}%",
+                    "{:<12} {:<20} {:<14} {:.0}%",
                     agent.id,
                     agent.name,
-                    if agent.available { "yes" } else { "no" },
+                    match (agent.available, agent.unresponsive) {
+                        (false, _) => "no",
+                        (true, true) => "unresponsive",
+                        (true, false) => "yes",
+                    },
                     agent.quality.success_rate() * 100.0
                 );
             }
         }
         
+        Commands::Pricing { command } => {
+            let mut loom = Loom::open(".")?;
+            
+            match command {
+                PricingCommands::Sync { file, dry_run } => {
+                    let changes = loom.sync_pricing(file.as_deref().map(std::path::Path::new), dry_run)?;
+                    if changes.is_empty() {
+                        println!("Prices are up to date");
+                    }
+                    for change in &changes {
+                        println!(
+                            "{:<24} ${}/${} → ${}/${} per 1K (since {})",
+                            change.model,
+                            change.from_input_per_1k,
+                            change.from_output_per_1k,
+                            change.to_input_per_1k,
+                            change.to_output_per_1k,
+                            change.effective
+                        );
+                    }
+                    if dry_run && !changes.is_empty() {
+                        println!("\n(dry run - models.toml not changed)");
+                    }
+                }
+                
+                PricingCommands::Reprice { file, dry_run } => {
+                    let repricing = loom.reprice_history(file.as_deref().map(std::path::Path::new), dry_run)?;
+                    println!("Pricing table {}", repricing.version);
+                    println!();
+                    println!("{:<8} {:>10} {:>12} {:>12}", "MONTH", "EXECUTIONS", "BEFORE", "AFTER");
+                    for (month, cost) in &repricing.by_month {
+                        println!("{:<8} {:>10} {:>12.4} {:>12.4}", month, cost.executions, cost.before_usd, cost.after_usd);
+                    }
+                    println!();
+                    println!(
+                        "Re-priced {} execution(s): ${:.4} → ${:.4} ({} without tokens or a price skipped)",
+                        repricing.repriced, repricing.before_usd, repricing.after_usd, repricing.skipped
+                    );
+                    if dry_run {
+                        println!("(dry run - history not changed)");
+                    }
+                }
+            }
+        }
+        
+        Commands::Heartbeat { agent, status } => {
+            let mut loom = Loom::open(".")?;
+            let heartbeat = loom.heartbeat(&agent, status.as_deref())?;
+            println!("Heartbeat: {} at {}", heartbeat.agent_id, heartbeat.last_seen.format("%H:%M:%S"));
+        }
+        
+        Commands::Probe { agent, timeout } => {
+            let mut loom = Loom::open(".")?;
+            let probes = loom.probe_agents(agent.as_deref(), std::time::Duration::from_secs(timeout.max(1)))?;
+            
+            println!("{:<24} {:<10} {:>11}  VERSION", "ID", "AVAILABLE", "COLD START");
+            for probe in probes {
+                let cold_start = probe.cold_start_ms.map(|ms| format!("{}ms", ms)).unwrap_or_else(|| "-".to_string());
+                let detail = match (&probe.error, &probe.version) {
+                    (Some(error), _) => error.clone(),
+                    (None, Some(version)) => version.clone(),
+                    (None, None) => String::new(),
+                };
+                println!(
+                    "{:<24} {:<10} {:>11}  {}",
+                    probe.agent_id,
+                    if probe.available { "yes" } else { "no" },
+                    cold_start,
+                    detail
+                );
+            }
+        }
+        
         Commands::Agent { id } => {
             let loom = Loom::open(".")?;
             let agent = loom.agent(&id)?
@@ -742,6 +1618,24 @@ fn run() -> Result<(), LoomError> {
             println!("Name:        {}", agent.name);
             println!("CLI:         {}", agent.cli_path);
             println!("Available:   {}", agent.available);
+            if let Some(seen) = agent.last_heartbeat {
+                println!(
+                    "Heartbeat:   {}{}",
+                    seen.format("%Y-%m-%d %H:%M:%S"),
+                    if agent.unresponsive { " (unresponsive)" } else { "" }
+                );
+            }
+            if let Some(probe) = &agent.probe {
+                println!(
+                    "Probed:      {} ({})",
+                    probe.probed_at.format("%Y-%m-%d %H:%M:%S"),
+                    match (&probe.error, probe.cold_start_ms) {
+                        (Some(error), _) => error.clone(),
+                        (None, Some(ms)) => format!("{}ms cold start", ms),
+                        (None, None) => "answered".to_string(),
+                    }
+                );
+            }
             println!("Concurrent:  {}/{}", agent.active, agent.max_concurrent);
             println!();
             println!("Capabilities:");
@@ -763,6 +1657,14 @@ fn run() -> Result<(), LoomError> {
             println!("Quality:");
             println!("  Success rate: {:.0}%", agent.quality.success_rate() * 100.0);
             println!("  Avg duration: {:.0}s", agent.quality.avg_duration_secs);
+            let mut types: Vec<_> = agent.quality.by_type.iter().collect();
+            types.sort_by(|a, b| b.1.runs().cmp(&a.1.runs()).then_with(|| a.0.cmp(b.0)));
+            for (task_type, stats) in types {
+                println!(
+                    "  {:<12} {:.0}% of {} ({:.0}s avg)",
+                    task_type, stats.success_rate() * 100.0, stats.runs(), stats.avg_duration_secs
+                );
+            }
         }
         
         Commands::Formula { command } => {
@@ -788,13 +1690,21 @@ fn run() -> Result<(), LoomError> {
                     println!("Description: {}", formula.description);
                     println!("Quality: {:?}", formula.quality);
                     println!("Agent: {}", formula.agent.as_deref().unwrap_or("auto"));
+                    if let Some(provenance) = loom.formula_provenance(&name) {
+                        println!("Pack: {} {} ({})", provenance.pack, provenance.version, provenance.source);
+                    }
                     println!();
                     
                     if !formula.variables.is_empty() {
                         println!("Variables:");
                         for var in &formula.variables {
-                            println!("  {} - {} {}", 
+                            let kind = match var.kind {
+                                VariableType::Enum => format!("one of {}", var.options.join("|")),
+                                kind => kind.as_str().to_string(),
+                            };
+                            println!("  {} ({}) - {} {}", 
                                 var.name, 
+                                kind,
                                 var.description,
                                 if var.required { "(required)" } else { "" }
                             );
@@ -808,11 +1718,8 @@ fn run() -> Result<(), LoomError> {
                     }
                 }
                 
-                FormulaCommands::Run { name, vars } => {
+                FormulaCommands::Run { name, vars, create_only, dry_run } => {
                     let mut loom = Loom::open(".")?;
-                    let formula = loom.formula(&name)
-                        .ok_or_else(|| LoomError::Config(format!("Formula not found: {}", name)))?
-                        .clone();
                     
                     // Parse variables
                     let mut var_map = std::collections::HashMap::new();
@@ -822,50 +1729,222 @@ fn run() -> Result<(), LoomError> {
                         }
                     }
                     
-                    // Validate variables
-                    formula.validate_variables(&var_map)
-                        .map_err(|e| LoomError::Config(e.to_string()))?;
+                    if dry_run {
+                        let plan = loom.plan_formula(&name, &var_map)?;
+                        println!("Plan for {}: {}", name, plan.tasks.title);
+                        println!();
+                        for (i, step) in plan.steps.iter().enumerate() {
+                            let routed = step.routing.as_ref().map_or("unroutable", |r| r.agent_id.as_str());
+                            let cost = step.estimated_cost.map_or("?".to_string(), |c| format!("${:.4}", c));
+                            println!("  {}. {} → {} ({})", i + 1, step.title, routed, cost);
+                            if !step.waits_on.is_empty() {
+                                println!("       after {}", step.waits_on.join(", "));
+                            }
+                        }
+                        println!();
+                        println!("Tasks: {}  Tokens: ~{}  Cost: ~${:.4}", plan.tasks.count(), plan.estimated_tokens, plan.estimated_cost);
+                        for warning in &plan.warnings {
+                            println!("  ⚠ {}", warning);
+                        }
+                        return Ok(());
+                    }
                     
-                    // Get routing decision
-                    let decision = loom.route_for_formula(&formula)?;
+                    let run = if create_only {
+                        loom.start_formula(&name, &var_map)?
+                    } else {
+                        println!("Running formula: {}", name);
+                        println!();
+                        loom.run_formula(&name, &var_map)?
+                    };
                     
-                    println!("Running formula: {}", formula.name);
-                    println!("Agent: {} ({})", decision.agent_id, decision.reason);
+                    println!("Run task: {} ({})", run.root_id, run.status.as_str());
                     println!();
+                    println!("Steps:");
+                    for (i, step) in run.steps.iter().enumerate() {
+                        let marker = match step.status {
+                            StepStatus::Completed => "✓",
+                            StepStatus::Failed => "✗",
+                            StepStatus::Pending => " ",
+                        };
+                        println!("  {} {}. {} {} → {}{}",
+                            marker,
+                            i + 1,
+                            step.step_id,
+                            step.task_id,
+                            step.agent,
+                            if step.checkpoint { " [checkpoint]" } else { "" }
+                        );
+                        if let Some(error) = &step.error {
+                            println!("       {}", error);
+                        }
+                    }
                     
-                    // Create task for the formula
-                    let task = loom.create_task(CreateTask {
-                        title: format!("[{}] {}", formula.name, formula.description),
-                        description: Some(format!("Variables: {:?}", var_map)),
-                        priority: Default::default(),
-                        labels: formula.labels.clone(),
-                        parent: None,
-                        evidence: None,
-                        repo: None,
-                    })?;
+                    if run.status == RunStatus::Failed || run.status == RunStatus::Stalled {
+                        std::process::exit(1);
+                    }
+                }
+                
+                FormulaCommands::Install { source } => {
+                    let mut loom = Loom::open(".")?;
+                    let pack = loom.install_formula_pack(&source)?;
                     
-                    println!("Created task: {}", task.id);
-                    println!();
-                    println!("Steps to execute:");
-                    for (i, step) in formula.steps.iter().enumerate() {
-                        let prompt = step.prompt.as_ref()
-                            .map(|p| formula.expand_template(p, &var_map))
+                    println!("Installed {} {} from {}", pack.name, pack.version, pack.source);
+                    let mut names: Vec<_> = pack.formulas.keys().collect();
+                    names.sort();
+                    for name in names {
+                        println!("  {}", name);
+                    }
+                }
+                
+                FormulaCommands::Packs { check } => {
+                    let loom = Loom::open(".")?;
+                    let packs = loom.formula_packs()?;
+                    if packs.is_empty() {
+                        println!("No formula packs installed");
+                        return Ok(());
+                    }
+                    
+                    let updates = if check { loom.check_formula_updates()? } else { Vec::new() };
+                    
+                    println!("Formula Packs:");
+                    for pack in &packs {
+                        let update = updates.iter()
+                            .find(|u| u.pack == pack.name && u.is_newer())
+                            .map(|u| format!(" → {} available", u.available))
                             .unwrap_or_default();
-                        
-                        println!("  {}. {} {}", 
-                            i + 1, 
-                            step.title,
-                            if step.checkpoint { "[checkpoint]" } else { "" }
-                        );
-                        if !prompt.is_empty() {
-                            println!("     Prompt: {}", prompt);
+                        println!("  {:<15} {:<8} {} formulas from {}{}",
+                            pack.name, pack.version, pack.formulas.len(), pack.source, update);
+                    }
+                }
+            }
+        }
+        
+        Commands::Template { command } => {
+            match command {
+                TemplateCommands::List => {
+                    let loom = Loom::open(".")?;
+                    let templates = loom.templates()?;
+                    if templates.is_empty() {
+                        println!("No templates in {}", loom.root().join("templates").display());
+                        return Ok(());
+                    }
+                    
+                    println!("Templates:");
+                    for template in &templates {
+                        println!("  {:<15} {}", template.name, template.summary.as_deref().unwrap_or(&template.title));
+                    }
+                }
+                
+                TemplateCommands::Create { name, vars, parent } => {
+                    let mut loom = Loom::open(".")?;
+                    
+                    let mut var_map = std::collections::HashMap::new();
+                    for var_str in vars {
+                        if let Some((k, v)) = var_str.split_once('=') {
+                            var_map.insert(k.to_string(), v.to_string());
                         }
                     }
+                    
+                    let tasks = loom.create_from_template(&name, &var_map, parent.as_deref())?;
+                    println!("Created {} tasks from template '{}':", tasks.len(), name);
+                    // Parents come first, so each task's depth is known before its children's
+                    let mut depths = std::collections::HashMap::new();
+                    for task in &tasks {
+                        let depth = task.parent.as_ref().and_then(|p| depths.get(p)).map_or(0, |d| d + 1);
+                        depths.insert(task.id.clone(), depth);
+                        println!("  {}{} {}", "  ".repeat(depth), task.id, task.title);
+                    }
+                }
+            }
+        }
+        
+        Commands::Schedule { command } => {
+            match command {
+                ScheduleCommands::Add { title, cron, at, description, labels, parent } => {
+                    let mut loom = Loom::open(".")?;
+                    let recurrence = match (cron, at) {
+                        (Some(expr), _) => Recurrence::Cron(expr),
+                        (None, Some(at)) => Recurrence::DueAt(
+                            chrono::DateTime::parse_from_rfc3339(&at)
+                                .map_err(|e| LoomError::Config(format!("Invalid --at '{}': {}", at, e)))?
+                                .with_timezone(&chrono::Utc),
+                        ),
+                        (None, None) => unreachable!("clap requires --cron or --at"),
+                    };
+                    let labels: Vec<String> = labels
+                        .map(|l| l.split(',').map(|s| s.trim().to_string()).collect())
+                        .unwrap_or_default();
+                    
+                    let schedule = loom.schedule(
+                        CreateTask { title, description, labels, parent, ..Default::default() },
+                        recurrence,
+                    )?;
+                    println!("Scheduled: {} - {}", schedule.id, schedule.title);
+                    if let Some(next) = schedule.next_run {
+                        println!("  next run: {}", next.format("%Y-%m-%d %H:%M UTC"));
+                    }
+                }
+                
+                ScheduleCommands::List => {
+                    let loom = Loom::open(".")?;
+                    let schedules = loom.schedules()?;
+                    if schedules.is_empty() {
+                        println!("No schedules");
+                        return Ok(());
+                    }
+                    
+                    for schedule in &schedules {
+                        let when = match &schedule.recurrence {
+                            Recurrence::Cron(expr) => format!("cron {}", expr),
+                            Recurrence::DueAt(at) => format!("at {}", at.format("%Y-%m-%d %H:%M")),
+                        };
+                        let next = schedule.next_run
+                            .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+                            .unwrap_or_else(|| "done".to_string());
+                        println!("  {} {:<30} {:<22} next: {}", schedule.id, schedule.title, when, next);
+                    }
+                }
+                
+                ScheduleCommands::Rm { id } => {
+                    let mut loom = Loom::open(".")?;
+                    loom.unschedule(&id)?;
+                    println!("Removed schedule {}", id);
+                }
+                
+                ScheduleCommands::Run => {
+                    let mut loom = Loom::open(".")?;
+                    let created = loom.run_schedules()?;
+                    if created.is_empty() {
+                        println!("No schedules due");
+                    }
+                    for task in &created {
+                        println!("Created: {} - {}", task.id, task.title);
+                    }
                 }
             }
         }
         
-        Commands::Sync | Commands::Push | Commands::Pull => {
+        Commands::Sync { command: Some(command) } => {
+            let mut loom = Loom::open(".")?;
+            let (report, prefix) = match command {
+                SyncCommands::Github { dry_run } => (loom.sync_github(dry_run)?, "#"),
+                SyncCommands::Linear { dry_run } => (loom.sync_linear(dry_run)?, ""),
+                SyncCommands::Jira { dry_run } => (loom.sync_jira(dry_run)?, ""),
+            };
+            
+            println!("{}", report.summary());
+            for conflict in &report.conflicts {
+                println!("  conflict: {} / {}{} (kept {})", conflict.task_id, prefix, conflict.remote_id, conflict.kept);
+            }
+            for issue in &report.missing_remote {
+                println!("  missing: {}{} is no longer listed", prefix, issue);
+            }
+            for error in &report.errors {
+                eprintln!("  error: {}", error);
+            }
+        }
+        
+        Commands::Sync { command: None } | Commands::Push | Commands::Pull => {
             // These require git to be available
             println!("Git sync not yet fully implemented");
             println!("Use 'lm list --format json' to export tasks manually");
@@ -892,6 +1971,26 @@ fn run() -> Result<(), LoomError> {
             println!("{}", BackfillAnalytics::format_result(&result));
         }
         
+        Commands::Costs { format, since, output } => {
+            let loom = Loom::open(".")?;
+            let format = CostFormat::parse(&format)
+                .ok_or_else(|| LoomError::Config(format!("Unknown format: {} (expected csv or json)", format)))?;
+            let since = match since {
+                Some(s) => Some(Backfill::parse_date(&s).map_err(|e| LoomError::Config(e.to_string()))?),
+                None => None,
+            };
+            
+            let report = loom.cost_report(since)?;
+            let rendered = report.render(format).map_err(|e| LoomError::Config(e.to_string()))?;
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, rendered)?;
+                    eprintln!("Wrote {} cost entries (${:.2}) to {}", report.entries.len(), report.total_usd(), path.display());
+                }
+                None => print!("{}", rendered),
+            }
+        }
+        
         Commands::Analytics { since: _since, agent } => {
             let loom = Loom::open(".")?;
             
@@ -927,17 +2026,58 @@ fn run() -> Result<(), LoomError> {
             }
         }
         
+        Commands::Maintain => {
+            let mut loom = Loom::open(".")?;
+            let report = loom.maintain()?;
+            
+            println!("Maintenance");
+            println!("===========");
+            println!("Orphaned sessions:       {}", report.orphaned_sessions);
+            println!("Expired sessions:        {}", report.expired_sessions);
+            println!("Orphaned checkpoints:    {}", report.orphaned_checkpoints);
+            println!("Orphaned recovery:       {}", report.orphaned_recovery);
+            println!("Orphaned agent sessions: {}", report.orphaned_agent_sessions);
+            println!("Stale history:           {}", report.stale_history);
+            println!();
+            for (db, bytes) in &report.reclaimed {
+                println!("{:<10} reclaimed {} bytes", db, bytes);
+            }
+        }
+        
+        Commands::VerifyLog => {
+            let loom = Loom::open(".")?;
+            let verification = loom.verify_evidence_log()?;
+            
+            println!("Entries:    {}", verification.entries);
+            println!("Signatures: {}", verification.signatures_checked);
+            println!("Head:       {}", verification.head);
+            
+            if verification.is_valid() {
+                println!("\nEvidence log intact");
+            } else {
+                println!("\nTampering detected ({} issues):", verification.issues.len());
+                for issue in &verification.issues {
+                    println!("  {:?}", issue);
+                }
+                std::process::exit(1);
+            }
+        }
+        
         Commands::Daemon { command } => {
             match command {
-                DaemonCommands::Start => {
+                DaemonCommands::Start { http } => {
                     println!("Starting daemon...");
                     let runtime = tokio::runtime::Runtime::new()
                         .map_err(|e| LoomError::Io(e.into()))?;
                     
                     runtime.block_on(async {
                         let loom = Loom::open(".")?;
-                        let daemon = loom::daemon::Daemon::new(loom.root())
-                            .map_err(|e| LoomError::Config(e.to_string()))?;
+                        let mut daemon = loom::daemon::Daemon::new(loom.root())
+                            .map_err(|e| LoomError::Config(e.to_string()))?
+                            .with_loom(loom);
+                        if let Some(bind) = http {
+                            daemon = daemon.with_http(bind);
+                        }
                         daemon.run().await
                             .map_err(|e| LoomError::Config(e.to_string()))
                     })?;
@@ -945,27 +2085,96 @@ fn run() -> Result<(), LoomError> {
                 
                 DaemonCommands::Status => {
                     let loom = Loom::open(".")?;
-                    let socket_path = loom.root().join("run.sock");
+                    let health = loom.daemon_status();
                     
-                    if socket_path.exists() {
-                        println!("Daemon: running");
-                        println!("Socket: {}", socket_path.display());
-                    } else {
-                        println!("Daemon: not running");
+                    match health.state {
+                        Liveness::Running => println!("Daemon: running"),
+                        Liveness::NotRunning => println!("Daemon: not running"),
+                        Liveness::Stale => println!("Daemon: not running (stale)"),
+                        Liveness::Unresponsive => println!("Daemon: unresponsive"),
+                    }
+                    println!("Socket: {}", health.socket.display());
+                    if let Some(pid) = health.pid {
+                        println!("PID:    {}", pid);
+                    }
+                    if let Some(status) = &health.status {
+                        println!("Uptime: {}s", status.uptime_secs);
+                        println!("Queue:  {} ready, {} in progress", status.queue_depth, status.in_progress);
+                        if let Some(error) = &status.last_error {
+                            println!("Last error ({}): {}", error.at.format("%Y-%m-%d %H:%M:%S"), error.message);
+                        }
+                    }
+                    if let Some(problem) = &health.problem {
+                        println!("Problem: {}", problem);
                     }
                 }
                 
                 DaemonCommands::Stop => {
                     let loom = Loom::open(".")?;
-                    let socket_path = loom.root().join("run.sock");
+                    let health = loom.daemon_status();
                     
-                    if socket_path.exists() {
-                        std::fs::remove_file(&socket_path)?;
-                        println!("Daemon stopped");
+                    if health.state == Liveness::Running {
+                        loom::daemon::DaemonClient::new(&health.socket)
+                            .request_blocking(&loom::daemon::Request::Shutdown, std::time::Duration::from_secs(5))
+                            .map_err(|e| LoomError::Config(e.to_string()))?;
+                        println!("Daemon stopping");
                     } else {
                         println!("Daemon not running");
                     }
                 }
+                
+                DaemonCommands::Logs { lines, level } => {
+                    let loom = Loom::open(".")?;
+                    let level = LogLevel::parse(&level)
+                        .ok_or_else(|| LoomError::Config(format!("Unknown level: {}", level)))?;
+                    
+                    // Oldest first, like a log file
+                    for entry in loom.logs(lines, level)?.iter().rev() {
+                        let fields: Vec<String> = entry.fields.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+                        println!(
+                            "{} {:<5} {:<11} {} {}",
+                            entry.at.format("%Y-%m-%d %H:%M:%S"),
+                            format!("{:?}", entry.level).to_lowercase(),
+                            entry.event,
+                            entry.message,
+                            fields.join(" ")
+                        );
+                    }
+                }
+                
+                DaemonCommands::Events { events, task, agent, json } => {
+                    let loom = Loom::open(".")?;
+                    let health = loom.daemon_status();
+                    if health.state != Liveness::Running {
+                        return Err(LoomError::Config("Daemon not running; start it with `lm daemon start`".to_string()));
+                    }
+                    
+                    let filter = loom::EventFilter { events, task_id: task, agent };
+                    let runtime = tokio::runtime::Runtime::new().map_err(LoomError::Io)?;
+                    runtime.block_on(loom::daemon::DaemonClient::new(&health.socket).subscribe(filter, |item| {
+                        match item {
+                            Ok(event) if json => println!("{}", serde_json::to_string(&event).unwrap_or_default()),
+                            Ok(event) => {
+                                let change = match (&event.from, &event.to) {
+                                    (Some(from), Some(to)) => format!("{} → {}", from, to),
+                                    (None, Some(to)) => to.clone(),
+                                    _ => event.detail.clone().unwrap_or_default(),
+                                };
+                                println!(
+                                    "{} {:<7} {:<11} {:<10} {:<14} {}",
+                                    event.at.format("%H:%M:%S"),
+                                    event.source.as_str(),
+                                    event.event,
+                                    event.task_id,
+                                    event.agent.as_deref().unwrap_or("-"),
+                                    change
+                                );
+                            }
+                            Err(message) => eprintln!("Warning: {}", message),
+                        }
+                        true
+                    })).map_err(|e| LoomError::Config(e.to_string()))?;
+                }
             }
         }
     }
@@ -992,6 +2201,30 @@ fn truncate(s: &str, max: usize) -> String {
     }
 }
 
+fn print_comments(comments: &[loom::Comment]) {
+    for comment in comments {
+        println!("  [{}] {}: {}", comment.created_at.format("%Y-%m-%d %H:%M"), comment.author, comment.body);
+    }
+}
+
+fn print_outcome(outcome: &Outcome) {
+    let list = |items: &[String]| if items.is_empty() { "-".to_string() } else { items.join(", ") };
+    println!("  PRs:         {}", list(&outcome.pr_urls));
+    println!("  Artifacts:   {}", list(&outcome.artifacts));
+    println!("  Tests added: {}", outcome.tests_added.map_or("-".to_string(), |n| n.to_string()));
+    println!("  Files:       {}", outcome.files_touched.len());
+    println!("  Follow-ups:  {}", list(&outcome.follow_ups));
+}
+
+fn format_secs(secs: f64) -> String {
+    let secs = secs.round() as u64;
+    match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3599 => format!("{}m {:02}s", secs / 60, secs % 60),
+        _ => format!("{}h {:02}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
 fn get_hostname() -> String {
     hostname::get()
         .map(|h| h.to_string_lossy().to_string())
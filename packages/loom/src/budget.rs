@@ -0,0 +1,235 @@
+//! Spend Budgets
+//!
+//! Daily USD caps on recorded task cost, configured under `[budgets]`: one on
+//! all spend and one per label or repo. Routing checks a task's estimated
+//! cost against what's been spent since midnight UTC; a task counts against
+//! every budget whose label or repo it has. Over a cap it fails, or with
+//! `on-exceed = "warn"` goes ahead and reports the overrun. An actual cost
+//! has already been spent, so completing with one always records it and
+//! reports any overrun; under `"refuse"` claims on tasks a spent budget
+//! covers then fail until the next day. Spend lives in work.db's cost
+//! ledger, so every process on a repo sees the same totals.
+
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::config::{BudgetAction, BudgetConfig};
+use crate::work::{Spend, Task, WorkError, WorkStore};
+
+#[derive(Error, Debug)]
+pub enum BudgetError {
+    #[error("Budget: {0}; raise the cap under [budgets] or wait for tomorrow (UTC)")]
+    Exceeded(Overrun),
+    
+    #[error("Budget: the {0} of ${1:.2} is spent; raise the cap under [budgets] or wait for tomorrow (UTC)")]
+    Spent(BudgetScope, f64),
+    
+    #[error("Work store error: {0}")]
+    Work(#[from] WorkError),
+}
+
+/// What a budget caps
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
+pub enum BudgetScope {
+    /// All spend
+    All,
+    Label(String),
+    Repo(String),
+}
+
+impl BudgetScope {
+    /// Whether spend on a task with these labels and repo counts against it
+    fn covers(&self, labels: &[String], repo: Option<&str>) -> bool {
+        match self {
+            BudgetScope::All => true,
+            BudgetScope::Label(label) => labels.contains(label),
+            BudgetScope::Repo(name) => repo == Some(name.as_str()),
+        }
+    }
+}
+
+impl fmt::Display for BudgetScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BudgetScope::All => f.write_str("daily budget"),
+            BudgetScope::Label(label) => write!(f, "daily budget for label {}", label),
+            BudgetScope::Repo(repo) => write!(f, "daily budget for repo {}", repo),
+        }
+    }
+}
+
+/// Today's spend against one budget
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BudgetUsage {
+    pub scope: BudgetScope,
+    pub limit_usd: f64,
+    pub spent_usd: f64,
+    pub remaining_usd: f64,
+}
+
+/// A cost that takes a budget past its cap
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Overrun {
+    pub scope: BudgetScope,
+    pub limit_usd: f64,
+    /// Spent today before this cost
+    pub spent_usd: f64,
+    pub adding_usd: f64,
+}
+
+impl fmt::Display for Overrun {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "${:.2} would bring the {} to ${:.2} (limit ${:.2})",
+            self.adding_usd, self.scope, self.spent_usd + self.adding_usd, self.limit_usd
+        )
+    }
+}
+
+/// Every configured budget with what's been spent against it today
+pub fn status(store: &WorkStore, config: &BudgetConfig) -> Result<Vec<BudgetUsage>, BudgetError> {
+    let spend = store.spend_since(start_of_day(Utc::now()))?;
+    Ok(budgets(config)
+        .map(|(scope, limit_usd)| {
+            let spent_usd = spent(&spend, &scope);
+            BudgetUsage { scope, limit_usd, spent_usd, remaining_usd: (limit_usd - spent_usd).max(0.0) }
+        })
+        .collect())
+}
+
+/// Check whether spending `adding_usd` more on `task` stays within its
+/// budgets. Overruns fail under `on-exceed = "refuse"`; under `"warn"`
+/// they're returned instead.
+pub fn check(store: &WorkStore, config: &BudgetConfig, task: &Task, adding_usd: f64) -> Result<Vec<Overrun>, BudgetError> {
+    let overruns = overruns(store, config, task, adding_usd)?;
+    match config.on_exceed {
+        BudgetAction::Refuse => match overruns.into_iter().next() {
+            Some(overrun) => Err(BudgetError::Exceeded(overrun)),
+            None => Ok(Vec::new()),
+        },
+        BudgetAction::Warn => Ok(overruns),
+    }
+}
+
+/// Budgets that spending `adding_usd` more on `task` takes past their cap,
+/// whatever `on-exceed` says
+pub fn overruns(store: &WorkStore, config: &BudgetConfig, task: &Task, adding_usd: f64) -> Result<Vec<Overrun>, BudgetError> {
+    if adding_usd <= 0.0 {
+        return Ok(Vec::new());
+    }
+    
+    let mut applicable = budgets(config)
+        .filter(|(scope, _)| scope.covers(&task.labels, task.repo.as_deref()))
+        .peekable();
+    if applicable.peek().is_none() {
+        return Ok(Vec::new());
+    }
+    
+    let spend = store.spend_since(start_of_day(Utc::now()))?;
+    Ok(applicable
+        .map(|(scope, limit_usd)| Overrun { spent_usd: spent(&spend, &scope), scope, limit_usd, adding_usd })
+        .filter(|o| o.spent_usd + o.adding_usd > o.limit_usd)
+        .collect())
+}
+
+/// Under `on-exceed = "refuse"`, fail if a budget covering `task` is
+/// already spent, so no more work starts against it today
+pub fn check_claim(store: &WorkStore, config: &BudgetConfig, task: &Task) -> Result<(), BudgetError> {
+    if config.on_exceed != BudgetAction::Refuse {
+        return Ok(());
+    }
+    let mut applicable = budgets(config)
+        .filter(|(scope, _)| scope.covers(&task.labels, task.repo.as_deref()))
+        .peekable();
+    if applicable.peek().is_none() {
+        return Ok(());
+    }
+    
+    let spend = store.spend_since(start_of_day(Utc::now()))?;
+    for (scope, limit_usd) in applicable {
+        if spent(&spend, &scope) >= limit_usd {
+            return Err(BudgetError::Spent(scope, limit_usd));
+        }
+    }
+    Ok(())
+}
+
+/// Configured caps, overall first
+fn budgets(config: &BudgetConfig) -> impl Iterator<Item = (BudgetScope, f64)> + '_ {
+    let mut labels: Vec<_> = config.labels.iter().collect();
+    labels.sort_by(|a, b| a.0.cmp(b.0));
+    let mut repos: Vec<_> = config.repos.iter().collect();
+    repos.sort_by(|a, b| a.0.cmp(b.0));
+    
+    config.daily_usd.map(|limit| (BudgetScope::All, limit))
+        .into_iter()
+        .chain(labels.into_iter().map(|(label, limit)| (BudgetScope::Label(label.clone()), *limit)))
+        .chain(repos.into_iter().map(|(repo, limit)| (BudgetScope::Repo(repo.clone()), *limit)))
+}
+
+fn spent(spend: &[Spend], scope: &BudgetScope) -> f64 {
+    spend.iter()
+        .filter(|s| scope.covers(&s.labels, s.repo.as_deref()))
+        .map(|s| s.amount_usd)
+        .sum()
+}
+
+fn start_of_day(now: DateTime<Utc>) -> DateTime<Utc> {
+    now.date_naive().and_hms_opt(0, 0, 0).map(|d| d.and_utc()).unwrap_or(now)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::work::CreateTask;
+    
+    #[test]
+    fn test_budgets_cover_matching_tasks() {
+        let mut store = WorkStore::in_memory().unwrap();
+        let mut config = BudgetConfig { daily_usd: Some(10.0), ..Default::default() };
+        config.labels.insert("experimental".to_string(), 2.0);
+        
+        let spike = store.create(CreateTask {
+            title: "spike".to_string(),
+            labels: vec!["experimental".to_string()],
+            ..Default::default()
+        }).unwrap();
+        let other = store.create(CreateTask { title: "other".to_string(), ..Default::default() }).unwrap();
+        
+        check(&store, &config, &spike, 1.5).unwrap();
+        store.record_cost(&spike.id, 1.5).unwrap();
+        // Re-recording replaces the cost rather than adding to it
+        store.record_cost(&spike.id, 1.5).unwrap();
+        
+        match check(&store, &config, &spike, 1.0) {
+            Err(BudgetError::Exceeded(overrun)) => {
+                assert_eq!(overrun.scope, BudgetScope::Label("experimental".to_string()));
+                assert!((overrun.spent_usd - 1.5).abs() < 1e-9);
+            }
+            other => panic!("expected an overrun, got {:?}", other),
+        }
+        // The label cap doesn't apply to unlabelled tasks, the daily one does
+        check(&store, &config, &other, 8.0).unwrap();
+        assert!(check(&store, &config, &other, 9.0).is_err());
+        
+        // Claims only fail once a cap is actually spent
+        check_claim(&store, &config, &spike).unwrap();
+        store.record_cost(&spike.id, 2.5).unwrap();
+        assert!(matches!(check_claim(&store, &config, &spike), Err(BudgetError::Spent(BudgetScope::Label(_), _))));
+        check_claim(&store, &config, &other).unwrap();
+        
+        config.on_exceed = BudgetAction::Warn;
+        let overruns = check(&store, &config, &spike, 9.0).unwrap();
+        assert_eq!(overruns.len(), 2);
+        check_claim(&store, &config, &spike).unwrap();
+        
+        let usage = status(&store, &config).unwrap();
+        assert_eq!(usage[0].scope, BudgetScope::All);
+        assert!((usage[0].remaining_usd - 7.5).abs() < 1e-9);
+    }
+}
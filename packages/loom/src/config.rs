@@ -27,13 +27,23 @@
 //! [agents]
 //! "Micah Johnson" = "human"
 //! "claude@anthropic.com" = "claude-code"
+//!
+//! # Evidence required to complete labeled tasks
+//! [evidence.require.deploy]
+//! patterns = ['https?://\S+']
+//! expected = "a deployment or CI run URL"
 //! ```
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use crate::policy::EvidenceRequirement;
+use crate::sync::tracker::{ConflictStrategy, TrackerSyncOptions};
+use crate::work::Priority;
+use crate::platform::{canonical, expand_path, same_path};
+
 #[derive(Error, Debug)]
 pub enum ConfigError {
     #[error("IO error: {0}")]
@@ -80,6 +90,62 @@ pub struct LoomConfig {
     /// Sync configuration
     #[serde(default)]
     pub sync: SyncConfig,
+    
+    /// Human stakeholders keyed by mention handle (e.g. "sam" for `@sam`)
+    #[serde(default)]
+    pub stakeholders: HashMap<String, StakeholderConfig>,
+    
+    /// Notification delivery settings
+    #[serde(default)]
+    pub notifications: NotificationConfig,
+    
+    /// Garbage collection and retention settings
+    #[serde(default)]
+    pub maintenance: MaintenanceConfig,
+    
+    /// Tamper-evident evidence log settings
+    #[serde(default)]
+    pub evidence: EvidenceConfig,
+    
+    /// HTTP API served by the daemon
+    #[serde(default)]
+    pub http: HttpConfig,
+    
+    /// Per-agent rate limits and quotas on tool calls
+    #[serde(default)]
+    pub limits: LimitsConfig,
+    
+    /// Daily spend caps, overall and per label or repo
+    #[serde(default)]
+    pub budgets: BudgetConfig,
+    
+    /// Daemon log redaction and rotation
+    #[serde(default)]
+    pub logging: LoggingConfig,
+    
+    /// Parent/sub-task behaviour
+    #[serde(default)]
+    pub subtasks: SubtaskConfig,
+    
+    /// When agents that stop sending heartbeats drop out of routing
+    #[serde(default)]
+    pub heartbeat: HeartbeatConfig,
+    
+    /// How often the daemon checks agents' CLIs
+    #[serde(default)]
+    pub probe: ProbeConfig,
+    
+    /// How the learned routing strategy explores
+    #[serde(default)]
+    pub routing: RoutingConfig,
+    
+    /// When claims left idle are released
+    #[serde(default)]
+    pub claims: ClaimsConfig,
+    
+    /// URLs the daemon POSTs task events to
+    #[serde(default)]
+    pub webhooks: Vec<WebhookConfig>,
 }
 
 fn default_issue_prefix() -> String {
@@ -96,6 +162,20 @@ impl Default for LoomConfig {
             agents: HashMap::new(),
             backfill: BackfillConfig::default(),
             sync: SyncConfig::default(),
+            stakeholders: HashMap::new(),
+            notifications: NotificationConfig::default(),
+            maintenance: MaintenanceConfig::default(),
+            evidence: EvidenceConfig::default(),
+            http: HttpConfig::default(),
+            limits: LimitsConfig::default(),
+            budgets: BudgetConfig::default(),
+            logging: LoggingConfig::default(),
+            subtasks: SubtaskConfig::default(),
+            heartbeat: HeartbeatConfig::default(),
+            probe: ProbeConfig::default(),
+            routing: RoutingConfig::default(),
+            claims: ClaimsConfig::default(),
+            webhooks: Vec::new(),
         }
     }
 }
@@ -149,6 +229,648 @@ pub struct SyncConfig {
     /// Auto-sync on task completion
     #[serde(default)]
     pub auto_sync: bool,
+    
+    /// Two-way sync with GitHub Issues
+    #[serde(default)]
+    pub github: Option<GitHubSyncConfig>,
+    
+    /// Two-way sync with a Linear team
+    #[serde(default)]
+    pub linear: Option<LinearSyncConfig>,
+    
+    /// Two-way sync with a Jira project
+    #[serde(default)]
+    pub jira: Option<JiraSyncConfig>,
+}
+
+/// Settings shared by issue tracker syncs
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct TrackerSettings {
+    /// Only sync tasks and issues carrying this label
+    #[serde(default)]
+    pub label: Option<String>,
+    
+    /// Loom agent ID -> tracker user (unmapped agents aren't assigned remotely)
+    #[serde(default)]
+    pub assignees: HashMap<String, String>,
+    
+    /// Which side wins when both changed: "newest", "local", or "remote"
+    #[serde(default)]
+    pub conflict: ConflictStrategy,
+}
+
+impl TrackerSettings {
+    pub fn sync_options(&self, dry_run: bool) -> TrackerSyncOptions {
+        TrackerSyncOptions {
+            label: self.label.clone(),
+            assignees: self.assignees.clone(),
+            conflict: self.conflict,
+            dry_run,
+        }
+    }
+}
+
+/// GitHub Issues sync
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct GitHubSyncConfig {
+    /// Repository as owner/name
+    pub repo: String,
+    
+    #[serde(flatten)]
+    pub settings: TrackerSettings,
+}
+
+/// Linear sync
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct LinearSyncConfig {
+    /// Team key (e.g. "ENG")
+    pub team: String,
+    
+    /// Environment variable holding the Linear API key
+    #[serde(default = "default_linear_key_env")]
+    pub api_key_env: String,
+    
+    #[serde(flatten)]
+    pub settings: TrackerSettings,
+}
+
+fn default_linear_key_env() -> String {
+    "LINEAR_API_KEY".to_string()
+}
+
+impl LinearSyncConfig {
+    /// API key from the environment, if set
+    pub fn api_key(&self) -> Option<String> {
+        std::env::var(&self.api_key_env).ok().filter(|k| !k.is_empty())
+    }
+}
+
+/// Jira sync
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct JiraSyncConfig {
+    /// Site URL, e.g. https://acme.atlassian.net
+    pub url: String,
+    
+    /// Project key (e.g. "ENG")
+    pub project: String,
+    
+    /// Account email for Jira Cloud; leave unset to use the token as a
+    /// Server/Data Center personal access token
+    #[serde(default)]
+    pub email: Option<String>,
+    
+    /// Environment variable holding the API token
+    #[serde(default = "default_jira_token_env")]
+    pub token_env: String,
+    
+    /// Extra JQL narrowing which issues sync
+    #[serde(default)]
+    pub jql: Option<String>,
+    
+    /// Issue type for tasks pushed to Jira
+    #[serde(default = "default_jira_issue_type")]
+    pub issue_type: String,
+    
+    /// Jira priority name -> Loom priority, for schemes beyond the defaults
+    /// (Highest/High/Medium/Low/Lowest, Blocker/Critical/Major/Minor/Trivial)
+    #[serde(default)]
+    pub priorities: BTreeMap<String, Priority>,
+    
+    #[serde(flatten)]
+    pub settings: TrackerSettings,
+}
+
+fn default_jira_token_env() -> String {
+    "JIRA_API_TOKEN".to_string()
+}
+
+fn default_jira_issue_type() -> String {
+    "Task".to_string()
+}
+
+impl JiraSyncConfig {
+    /// API token from the environment, if set
+    pub fn token(&self) -> Option<String> {
+        std::env::var(&self.token_env).ok().filter(|t| !t.is_empty())
+    }
+}
+
+/// A human stakeholder and the work they care about
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct StakeholderConfig {
+    /// Labels this stakeholder follows
+    #[serde(default)]
+    pub labels: Vec<String>,
+    
+    /// Repository IDs this stakeholder follows
+    #[serde(default)]
+    pub repos: Vec<String>,
+    
+    /// Webhook URL for digests and mentions (written to .loom/digests/ when unset)
+    #[serde(default)]
+    pub webhook: Option<String>,
+}
+
+/// Notification delivery settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct NotificationConfig {
+    /// Minutes between stakeholder digests produced by the daemon
+    #[serde(default = "default_digest_interval")]
+    pub digest_interval_mins: u64,
+}
+
+fn default_digest_interval() -> u64 {
+    60
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            digest_interval_mins: default_digest_interval(),
+        }
+    }
+}
+
+/// Garbage collection and retention settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct MaintenanceConfig {
+    /// Days to keep finished sessions and their checkpoints
+    #[serde(default = "default_session_retention")]
+    pub session_retention_days: u32,
+    
+    /// Days to keep agent execution history
+    #[serde(default = "default_history_retention")]
+    pub history_retention_days: u32,
+    
+    /// Hours between maintenance runs scheduled by the daemon (0 disables)
+    #[serde(default = "default_maintenance_interval")]
+    pub interval_hours: u64,
+}
+
+fn default_session_retention() -> u32 {
+    90
+}
+
+fn default_history_retention() -> u32 {
+    365
+}
+
+fn default_maintenance_interval() -> u64 {
+    24
+}
+
+impl Default for MaintenanceConfig {
+    fn default() -> Self {
+        Self {
+            session_retention_days: default_session_retention(),
+            history_retention_days: default_history_retention(),
+            interval_hours: default_maintenance_interval(),
+        }
+    }
+}
+
+/// Tamper-evident evidence log settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct EvidenceConfig {
+    /// Append completion evidence to the hash-chained log in work.db
+    #[serde(default)]
+    pub sign: bool,
+    
+    /// Environment variable holding the HMAC signing key
+    #[serde(default = "default_key_env")]
+    pub key_env: String,
+    
+    /// Evidence required to complete tasks, keyed by label
+    /// (setting any label replaces the built-in deploy/security rules)
+    #[serde(default = "crate::policy::evidence_requirements")]
+    pub require: HashMap<String, EvidenceRequirement>,
+}
+
+/// Parent/sub-task settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SubtaskConfig {
+    /// Complete a parent once every sub-task that isn't cancelled is done
+    /// (parents that are blocked or need evidence stay open)
+    #[serde(default)]
+    pub auto_complete_parents: bool,
+}
+
+/// Agent heartbeat settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct HeartbeatConfig {
+    /// Seconds without a heartbeat before routing skips an agent (0 disables).
+    /// Agents that have never sent one are unaffected.
+    #[serde(default = "default_heartbeat_timeout")]
+    pub timeout_secs: u64,
+}
+
+fn default_heartbeat_timeout() -> u64 {
+    300
+}
+
+impl Default for HeartbeatConfig {
+    fn default() -> Self {
+        Self {
+            timeout_secs: default_heartbeat_timeout(),
+        }
+    }
+}
+
+impl HeartbeatConfig {
+    pub fn timeout(&self) -> Option<chrono::Duration> {
+        (self.timeout_secs > 0).then(|| chrono::Duration::seconds(self.timeout_secs as i64))
+    }
+}
+
+/// Agent probe settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ProbeConfig {
+    /// Minutes between the daemon's probes of every agent (0 disables)
+    #[serde(default = "default_probe_interval")]
+    pub interval_mins: u64,
+    /// Seconds an agent's CLI has to answer `--version`
+    #[serde(default = "default_probe_timeout")]
+    pub timeout_secs: u64,
+}
+
+fn default_probe_interval() -> u64 {
+    60
+}
+
+fn default_probe_timeout() -> u64 {
+    10
+}
+
+impl Default for ProbeConfig {
+    fn default() -> Self {
+        Self {
+            interval_mins: default_probe_interval(),
+            timeout_secs: default_probe_timeout(),
+        }
+    }
+}
+
+impl ProbeConfig {
+    pub fn timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.timeout_secs.max(1))
+    }
+}
+
+/// Routing settings: label affinities and exclusions, sticky sub-tasks,
+/// and how the learned strategy explores
+///
+/// ```toml
+/// [routing]
+/// sticky = true
+///
+/// [routing.affinity]
+/// ui = ["cursor"]
+///
+/// [routing.exclude]
+/// migration = ["gemini"]
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct RoutingConfig {
+    /// Share of `learned` routing decisions that try the least-tried agent
+    /// instead of the best-performing one (0 never explores, 1 always does)
+    #[serde(default = "default_exploration_rate")]
+    pub exploration_rate: f64,
+    
+    /// Days of execution history the learned strategy weighs
+    #[serde(default = "default_history_days")]
+    pub history_days: u32,
+    
+    /// Agents preferred for tasks with a label: when any is eligible, routing
+    /// picks among them alone
+    #[serde(default)]
+    pub affinity: BTreeMap<String, Vec<String>>,
+    
+    /// Agents never routed tasks with a label
+    #[serde(default)]
+    pub exclude: BTreeMap<String, Vec<String>>,
+    
+    /// Route sub-tasks to the agent that did their parent when it's eligible
+    #[serde(default)]
+    pub sticky: bool,
+}
+
+fn default_exploration_rate() -> f64 {
+    0.1
+}
+
+fn default_history_days() -> u32 {
+    30
+}
+
+impl Default for RoutingConfig {
+    fn default() -> Self {
+        Self {
+            exploration_rate: default_exploration_rate(),
+            history_days: default_history_days(),
+            affinity: BTreeMap::new(),
+            exclude: BTreeMap::new(),
+            sticky: false,
+        }
+    }
+}
+
+/// Stale claim settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ClaimsConfig {
+    /// Minutes a claimed task can go without activity (task updates,
+    /// checkpoints, or heartbeats from its agent) before the daemon releases
+    /// it (0 disables).
+    #[serde(default = "default_claim_timeout")]
+    pub timeout_mins: u64,
+    
+    /// Minutes a claim's lease lasts; agents renew it while they work, and
+    /// once it expires another agent can claim the task (minimum 1).
+    #[serde(default = "default_lease_mins")]
+    pub lease_mins: u64,
+}
+
+fn default_claim_timeout() -> u64 {
+    120
+}
+
+fn default_lease_mins() -> u64 {
+    30
+}
+
+impl Default for ClaimsConfig {
+    fn default() -> Self {
+        Self {
+            timeout_mins: default_claim_timeout(),
+            lease_mins: default_lease_mins(),
+        }
+    }
+}
+
+impl ClaimsConfig {
+    pub fn timeout(&self) -> Option<chrono::Duration> {
+        (self.timeout_mins > 0).then(|| chrono::Duration::minutes(self.timeout_mins as i64))
+    }
+    
+    pub fn lease_duration(&self) -> chrono::Duration {
+        chrono::Duration::minutes(self.lease_mins.max(1) as i64)
+    }
+}
+
+/// A webhook the daemon delivers task events to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct WebhookConfig {
+    pub url: String,
+    
+    /// Events to send: created, claimed, completed, blocked, and also
+    /// released, unblocked, cancelled or priority
+    #[serde(default = "default_webhook_events")]
+    pub events: Vec<String>,
+    
+    /// Only send events for tasks with one of these labels (all when empty)
+    #[serde(default)]
+    pub labels: Vec<String>,
+    
+    /// Environment variable holding a secret to sign bodies with
+    /// (`X-Loom-Signature: sha256=<HMAC>`)
+    #[serde(default)]
+    pub secret_env: Option<String>,
+    
+    /// Failed deliveries of one event before it's skipped
+    #[serde(default = "default_webhook_attempts")]
+    pub max_attempts: u32,
+}
+
+fn default_webhook_events() -> Vec<String> {
+    ["created", "claimed", "completed", "blocked"].map(String::from).to_vec()
+}
+
+fn default_webhook_attempts() -> u32 {
+    5
+}
+
+impl WebhookConfig {
+    pub fn wants(&self, event: &str) -> bool {
+        self.events.iter().any(|e| e == event)
+    }
+}
+
+/// HTTP API settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct HttpConfig {
+    /// Address the daemon serves the HTTP API on (unset disables it)
+    #[serde(default)]
+    pub bind: Option<String>,
+    
+    /// Environment variable holding the bearer token clients must send
+    /// (no auth when unset, which is only allowed on loopback)
+    #[serde(default = "default_token_env")]
+    pub token_env: String,
+}
+
+fn default_token_env() -> String {
+    "LOOM_HTTP_TOKEN".to_string()
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            bind: None,
+            token_env: default_token_env(),
+        }
+    }
+}
+
+impl HttpConfig {
+    /// Bearer token from the environment, if set
+    pub fn token(&self) -> Option<String> {
+        std::env::var(&self.token_env).ok().filter(|t| !t.is_empty())
+    }
+}
+
+/// Per-agent rate limits and quotas, enforced on MCP and HTTP tool calls
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct LimitsConfig {
+    /// Limits for every agent
+    #[serde(flatten)]
+    pub default: AgentLimits,
+    
+    /// Overrides by agent ID (unset fields fall back to the defaults above)
+    #[serde(default)]
+    pub agents: HashMap<String, AgentLimits>,
+}
+
+/// Rate limits and quotas for one agent (unset means unlimited)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AgentLimits {
+    /// Tasks the agent may create per minute
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub creates_per_minute: Option<u32>,
+    
+    /// Tasks the agent may claim per minute
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub claims_per_minute: Option<u32>,
+    
+    /// Open (not done or cancelled) tasks the agent may have created
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_open_tasks: Option<u32>,
+}
+
+impl LimitsConfig {
+    /// Effective limits for an agent
+    pub fn for_agent(&self, agent: &str) -> AgentLimits {
+        let Some(overrides) = self.agents.get(agent) else {
+            return self.default;
+        };
+        AgentLimits {
+            creates_per_minute: overrides.creates_per_minute.or(self.default.creates_per_minute),
+            claims_per_minute: overrides.claims_per_minute.or(self.default.claims_per_minute),
+            max_open_tasks: overrides.max_open_tasks.or(self.default.max_open_tasks),
+        }
+    }
+}
+
+/// Daily USD spend caps on recorded task cost (UTC days)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct BudgetConfig {
+    /// What happens when routing or completing a task would go over a cap
+    #[serde(default)]
+    pub on_exceed: BudgetAction,
+    
+    /// Cap on all spend per day
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daily_usd: Option<f64>,
+    
+    /// Per-day caps on tasks with a label
+    #[serde(default)]
+    pub labels: HashMap<String, f64>,
+    
+    /// Per-day caps on tasks in a repo
+    #[serde(default)]
+    pub repos: HashMap<String, f64>,
+}
+
+/// Response to a budget that would be exceeded
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BudgetAction {
+    /// Fail the route, and claims once the budget is spent
+    #[default]
+    Refuse,
+    /// Go ahead and report the overrun
+    Warn,
+}
+
+/// Daemon log settings (`.loom/log/`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct LoggingConfig {
+    /// Entry fields whose values are never written (task titles, evidence, ...)
+    #[serde(default = "default_redact_fields")]
+    pub redact_fields: Vec<String>,
+    
+    /// Regexes whose matches are redacted from every message and field
+    #[serde(default = "default_redact_patterns")]
+    pub redact_patterns: Vec<String>,
+    
+    /// How redacted values are written
+    #[serde(default)]
+    pub redaction: Redaction,
+    
+    /// Rotate the log once it reaches this size
+    #[serde(default = "default_max_file_bytes")]
+    pub max_file_bytes: u64,
+    
+    /// Rotated logs to keep besides the current one
+    #[serde(default = "default_max_files")]
+    pub max_files: usize,
+}
+
+/// How a redacted value is written
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Redaction {
+    /// `[redacted]`
+    #[default]
+    Mask,
+    /// `[redacted:1a2b3c4d]`, a digest prefix, so entries about the same value
+    /// can be correlated. Unkeyed: a guessed value can be checked against it.
+    Hash,
+}
+
+fn default_redact_fields() -> Vec<String> {
+    ["title", "description", "evidence"].map(String::from).to_vec()
+}
+
+fn default_redact_patterns() -> Vec<String> {
+    [
+        r"(?i)\b(api[_-]?key|token|secret|password)\s*[=:]\s*\S+",
+        r"\b(sk|ghp|gho|xox[abp])[-_][A-Za-z0-9_-]{16,}",
+        r"(?i)bearer\s+[A-Za-z0-9._~+/-]+=*",
+    ]
+    .map(String::from)
+    .to_vec()
+}
+
+fn default_max_file_bytes() -> u64 {
+    1024 * 1024
+}
+
+fn default_max_files() -> usize {
+    5
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            redact_fields: default_redact_fields(),
+            redact_patterns: default_redact_patterns(),
+            redaction: Redaction::default(),
+            max_file_bytes: default_max_file_bytes(),
+            max_files: default_max_files(),
+        }
+    }
+}
+
+fn default_key_env() -> String {
+    "LOOM_EVIDENCE_KEY".to_string()
+}
+
+impl Default for EvidenceConfig {
+    fn default() -> Self {
+        Self {
+            sign: false,
+            key_env: default_key_env(),
+            require: crate::policy::evidence_requirements(),
+        }
+    }
+}
+
+impl EvidenceConfig {
+    /// Signing key from the environment, if set
+    pub fn signing_key(&self) -> Option<Vec<u8>> {
+        std::env::var(&self.key_env)
+            .ok()
+            .filter(|k| !k.is_empty())
+            .map(String::into_bytes)
+    }
 }
 
 impl LoomConfig {
@@ -197,8 +919,8 @@ impl LoomConfig {
             return id.clone();
         }
         
-        // Fall back to directory name
-        root.as_ref()
+        // Fall back to directory name ("." resolves to the current one)
+        canonical(root)
             .file_name()
             .and_then(|n| n.to_str())
             .map(|s| s.to_lowercase().replace(' ', "-"))
@@ -233,14 +955,7 @@ impl LoomConfig {
             expand_path(&self.repos.primary)
         };
         
-        // Canonicalize for comparison
-        let path_canon = std::fs::canonicalize(path.as_ref()).ok();
-        let primary_canon = std::fs::canonicalize(&primary).ok();
-        
-        match (path_canon, primary_canon) {
-            (Some(p), Some(pr)) => p == pr,
-            _ => false,
-        }
+        path.as_ref().exists() && primary.exists() && same_path(path, primary)
     }
     
     /// Create a default config for a new repository
@@ -253,6 +968,20 @@ impl LoomConfig {
             agents: HashMap::new(),
             backfill: BackfillConfig::default(),
             sync: SyncConfig::default(),
+            stakeholders: HashMap::new(),
+            notifications: NotificationConfig::default(),
+            maintenance: MaintenanceConfig::default(),
+            evidence: EvidenceConfig::default(),
+            http: HttpConfig::default(),
+            limits: LimitsConfig::default(),
+            budgets: BudgetConfig::default(),
+            logging: LoggingConfig::default(),
+            subtasks: SubtaskConfig::default(),
+            heartbeat: HeartbeatConfig::default(),
+            probe: ProbeConfig::default(),
+            routing: RoutingConfig::default(),
+            claims: ClaimsConfig::default(),
+            webhooks: Vec::new(),
         }
     }
     
@@ -307,6 +1036,142 @@ primary = "."
 # sync-branch = "loom-sync"
 # Auto-sync on task completion
 # auto-sync = false
+
+# Two-way sync with GitHub Issues ('lm sync github'), via the gh CLI
+# [sync.github]
+# repo = "owner/name"
+# label = "loom"                      # only sync tasks/issues with this label
+# conflict = "newest"                 # or "local" / "remote"
+# assignees = { claude-code = "octocat" }
+
+# Two-way sync with a Linear team ('lm sync linear'); the API key is read
+# from the environment
+# [sync.linear]
+# team = "ENG"
+# api-key-env = "LINEAR_API_KEY"
+# assignees = { claude-code = "sam@example.com" }
+
+# Two-way sync with a Jira project ('lm sync jira'); epics come in as
+# parent tasks. Set email for Jira Cloud, or leave it out to use the token
+# as a Server/Data Center personal access token
+# [sync.jira]
+# url = "https://acme.atlassian.net"
+# project = "ENG"
+# email = "you@example.com"
+# token-env = "JIRA_API_TOKEN"
+# jql = "issuetype in (Epic, Story)"
+# priorities = { P0 = "critical", P1 = "high", P2 = "normal", P3 = "low" }
+
+# Stakeholders (humans) and the labels/repos they follow.
+# The daemon sends each one a digest of matching activity, and mentioning
+# them in a task (e.g. "@sam") sends a targeted notification right away.
+# [stakeholders.sam]
+# labels = ["auth", "billing"]
+# repos = ["csm"]
+# webhook = "https://hooks.example.com/loom"
+
+# Notification settings
+[notifications]
+# Minutes between stakeholder digests
+# digest-interval-mins = 60
+
+# Garbage collection (run by the daemon, or manually with 'lm maintain')
+[maintenance]
+# Days to keep finished sessions and their checkpoints
+# session-retention-days = 90
+# Days to keep agent execution history
+# history-retention-days = 365
+# Hours between scheduled runs (0 disables)
+# interval-hours = 24
+
+# Evidence log: chain completion evidence into an append-only log so
+# edits can be detected later ('lm verify-log' / loom_verify_log).
+# Entries are HMAC-signed when the key environment variable is set.
+[evidence]
+# sign = false
+# key-env = "LOOM_EVIDENCE_KEY"
+
+# HTTP API: the daemon serves the MCP operations as REST + JSON for
+# dashboards and CI jobs ('lm daemon start --http' overrides bind).
+# Requests need 'Authorization: Bearer <token>' when the variable is set;
+# without it, only loopback addresses can be bound.
+[http]
+# bind = "127.0.0.1:7420"
+# token-env = "LOOM_HTTP_TOKEN"
+
+# Per-agent rate limits and quotas on MCP/HTTP tool calls (unset: unlimited).
+# Calls that don't name an agent count against "anonymous"; check usage
+# with loom_quota.
+[limits]
+# creates-per-minute = 30
+# claims-per-minute = 60
+# max-open-tasks = 200
+# [limits.agents.cursor]
+# creates-per-minute = 10
+
+# Daemon log (.loom/log/daemon.jsonl): values of these fields and matches
+# of these patterns are redacted before they reach disk ('mask' or 'hash').
+# Fetch recent entries with 'lm daemon logs' / loom_logs.
+[logging]
+# redact-fields = ["title", "description", "evidence"]
+# redact-patterns = ['(?i)\b(api[_-]?key|token|secret|password)\s*[=:]\s*\S+']
+# redaction = "mask"
+# max-file-bytes = 1048576
+# max-files = 5
+
+# Complete a parent task once all its sub-tasks are done.
+[subtasks]
+# auto-complete-parents = false
+
+# Daily spend caps in USD (UTC days) on cost recorded with tasks. Routing
+# checks the estimate and fails over a cap; completing records the actual
+# cost and warns, and claims fail once a cap is spent. on-exceed = "warn"
+# only warns. See loom_budgets.
+[budgets]
+# on-exceed = "refuse"
+# daily-usd = 50.0
+# [budgets.labels]
+# experimental = 5.0
+# [budgets.repos]
+# api = 20.0
+
+# Agents that send heartbeats (loom_heartbeat, 'lm heartbeat', or
+# POST /agents/<id>/heartbeat) are skipped by routing once they go quiet
+# this long. Agents that never send one are routed on 'available' alone.
+[heartbeat]
+# timeout-secs = 300
+
+# The daemon runs each agent's CLI with --version this often, marking agents
+# whose command is missing or doesn't answer within timeout-secs unavailable,
+# and recording how long the others take to start. 0 disables.
+[probe]
+# interval-mins = 60
+# timeout-secs = 10
+
+# The 'learned' routing strategy sends each task to the agent with the best
+# success record on tasks with the same labels, and tries the least-tried
+# agent instead for exploration-rate of decisions.
+[routing]
+# exploration-rate = 0.1
+# history-days = 30
+
+# Claims are leases: agents renew them ('lm renew', loom_renew) and another
+# agent can take over a task once its lease expires. Claims with no activity
+# for timeout-mins (no task updates, checkpoints, lease renewals, or
+# heartbeats from the claiming agent) are released by the daemon, and the
+# task's session is flagged for recovery. 0 disables the release.
+[claims]
+# lease-mins = 30
+# timeout-mins = 120
+
+# The daemon POSTs a JSON event to each webhook as tasks change. Add one
+# [[webhooks]] table per URL.
+# [[webhooks]]
+# url = "https://example.com/hooks/loom"
+# events = ["created", "claimed", "completed", "blocked"]
+# labels = ["deploy"]                   # only tasks with one of these labels
+# secret-env = "LOOM_WEBHOOK_SECRET"    # sign bodies (X-Loom-Signature)
+# max-attempts = 5
 "#;
         
         std::fs::write(&config_path, default_config)?;
@@ -314,21 +1179,6 @@ primary = "."
     }
 }
 
-/// Expand ~ and environment variables in a path
-fn expand_path(path: &str) -> PathBuf {
-    let expanded = if path.starts_with('~') {
-        if let Some(home) = dirs::home_dir() {
-            path.replacen('~', &home.to_string_lossy(), 1)
-        } else {
-            path.to_string()
-        }
-    } else {
-        path.to_string()
-    };
-    
-    PathBuf::from(expanded)
-}
-
 /// Repository info for unified views
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RepoInfo {
@@ -453,4 +1303,37 @@ mod tests {
         let id = config.effective_repo_id("/path/to/my-project");
         assert_eq!(id, "custom");
     }
+    
+    #[test]
+    fn test_stakeholders_parse() {
+        let config: LoomConfig = toml::from_str(r#"
+            [stakeholders.sam]
+            labels = ["auth"]
+            webhook = "https://example.com/hook"
+        "#).unwrap();
+        
+        let sam = config.stakeholders.get("sam").unwrap();
+        assert_eq!(sam.labels, vec!["auth"]);
+        assert!(sam.repos.is_empty());
+        assert_eq!(config.notifications.digest_interval_mins, 60);
+    }
+    
+    #[test]
+    fn test_limits_parse() {
+        let config: LoomConfig = toml::from_str(r#"
+            [limits]
+            creates-per-minute = 30
+            max-open-tasks = 200
+            
+            [limits.agents.cursor]
+            creates-per-minute = 5
+        "#).unwrap();
+        
+        let cursor = config.limits.for_agent("cursor");
+        assert_eq!(cursor.creates_per_minute, Some(5));
+        assert_eq!(cursor.max_open_tasks, Some(200));
+        assert_eq!(cursor.claims_per_minute, None);
+        assert_eq!(config.limits.for_agent("codex").creates_per_minute, Some(30));
+        assert_eq!(LoomConfig::default().limits.for_agent("codex"), AgentLimits::default());
+    }
 }
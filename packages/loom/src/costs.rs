@@ -0,0 +1,249 @@
+//! Cost Export
+//!
+//! Spend per task, agent and label over time, as CSV or JSON for finance
+//! reporting. Rows come from two places:
+//!
+//! - The cost ledger in work.db: every change to a task's `actual_cost_usd`,
+//!   timestamped, attributed to the agent holding the task at the time
+//! - Execution records in agents.db that carry a cost
+//!
+//! A task with ledger entries is costed from the ledger alone, since an
+//! agent's execution costs usually end up recorded on its task as well. A
+//! task with none, ever, is costed from its executions. Either way nothing
+//! is counted twice, even in a report whose window starts after the task's
+//! ledger entries.
+//!
+//! CSV cells that a spreadsheet would read as a formula are prefixed with
+//! `'`, since task titles and labels come from whoever files the task.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::agents::ExecutionRecord;
+use crate::work::{Spend, Task};
+
+/// Output format for a cost export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CostFormat {
+    Csv,
+    Json,
+}
+
+impl CostFormat {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "csv" => Some(CostFormat::Csv),
+            "json" => Some(CostFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+/// Where a cost was recorded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CostSource {
+    /// A change to the task's `actual_cost_usd`
+    Ledger,
+    /// An execution record with a cost
+    Execution,
+}
+
+impl CostSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CostSource::Ledger => "ledger",
+            CostSource::Execution => "execution",
+        }
+    }
+}
+
+/// One recorded cost
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CostEntry {
+    /// UTC day it was recorded
+    pub date: NaiveDate,
+    pub recorded_at: DateTime<Utc>,
+    pub task_id: String,
+    pub title: String,
+    /// Agent charged: the one that ran the execution, or the one holding the task
+    pub agent: Option<String>,
+    pub labels: Vec<String>,
+    pub repo: Option<String>,
+    pub source: CostSource,
+    /// Negative when a task's cost was corrected down
+    pub amount_usd: f64,
+}
+
+/// Spend under one task, agent, label or day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CostTotal {
+    pub key: String,
+    pub amount_usd: f64,
+    /// Distinct tasks the spend was on
+    pub tasks: usize,
+}
+
+/// Costs recorded over a period
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CostReport {
+    pub since: Option<DateTime<Utc>>,
+    pub generated_at: DateTime<Utc>,
+    /// Oldest first
+    pub entries: Vec<CostEntry>,
+}
+
+/// Label spend on tasks without any is filed under
+const UNLABELLED: &str = "(unlabelled)";
+
+/// Agent spend nobody held the task for is filed under
+const UNASSIGNED: &str = "(unassigned)";
+
+impl CostReport {
+    /// Build a report from ledger entries, execution records, and the tasks
+    /// they were recorded on (tasks missing from `tasks` are left out)
+    ///
+    /// `ledgered` holds every task with ledger entries, including ones
+    /// recorded before `since`; their executions are skipped.
+    pub fn build(
+        ledger: &[Spend],
+        ledgered: &HashSet<String>,
+        executions: &[ExecutionRecord],
+        tasks: &HashMap<String, Task>,
+        since: Option<DateTime<Utc>>,
+    ) -> Self {
+        let mut entries = Vec::new();
+        
+        for spend in ledger {
+            let Some(task) = tasks.get(&spend.task_id) else { continue };
+            entries.push(entry(task, spend.recorded_at, spend.agent.clone(), CostSource::Ledger, spend.amount_usd));
+        }
+        for record in executions {
+            let (Some(cost), Some(timestamp)) = (record.cost, record.timestamp) else { continue };
+            if ledgered.contains(&record.task_id) {
+                continue;
+            }
+            let Some(task) = tasks.get(&record.task_id) else { continue };
+            entries.push(entry(task, timestamp, Some(record.agent_id.clone()), CostSource::Execution, cost));
+        }
+        entries.sort_by(|a, b| a.recorded_at.cmp(&b.recorded_at).then_with(|| a.task_id.cmp(&b.task_id)));
+        
+        Self { since, generated_at: Utc::now(), entries }
+    }
+    
+    pub fn total_usd(&self) -> f64 {
+        self.entries.iter().map(|e| e.amount_usd).sum()
+    }
+    
+    /// Spend per task, most expensive first
+    pub fn by_task(&self) -> Vec<CostTotal> {
+        self.totals(|e| vec![e.task_id.clone()])
+    }
+    
+    /// Spend per agent, most expensive first
+    pub fn by_agent(&self) -> Vec<CostTotal> {
+        self.totals(|e| vec![e.agent.clone().unwrap_or_else(|| UNASSIGNED.to_string())])
+    }
+    
+    /// Spend per label, most expensive first; a task's spend counts toward
+    /// each of its labels, so these add up to more than the total
+    pub fn by_label(&self) -> Vec<CostTotal> {
+        self.totals(|e| if e.labels.is_empty() { vec![UNLABELLED.to_string()] } else { e.labels.clone() })
+    }
+    
+    /// Spend per UTC day, oldest first
+    pub fn by_day(&self) -> Vec<CostTotal> {
+        let mut days = self.totals(|e| vec![e.date.to_string()]);
+        days.sort_by(|a, b| a.key.cmp(&b.key));
+        days
+    }
+    
+    fn totals(&self, keys: impl Fn(&CostEntry) -> Vec<String>) -> Vec<CostTotal> {
+        let mut totals: BTreeMap<String, (f64, BTreeSet<&str>)> = BTreeMap::new();
+        for entry in &self.entries {
+            for key in keys(entry) {
+                let total = totals.entry(key).or_default();
+                total.0 += entry.amount_usd;
+                total.1.insert(&entry.task_id);
+            }
+        }
+        let mut totals: Vec<CostTotal> = totals.into_iter()
+            .map(|(key, (amount_usd, tasks))| CostTotal { key, amount_usd, tasks: tasks.len() })
+            .collect();
+        totals.sort_by(|a, b| b.amount_usd.total_cmp(&a.amount_usd));
+        totals
+    }
+    
+    /// One row per recorded cost, with labels joined by `;`
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("date,recorded_at,task_id,title,agent,labels,repo,source,amount_usd\n");
+        for entry in &self.entries {
+            let fields = [
+                entry.date.to_string(),
+                entry.recorded_at.to_rfc3339(),
+                entry.task_id.clone(),
+                entry.title.clone(),
+                entry.agent.clone().unwrap_or_default(),
+                entry.labels.join(";"),
+                entry.repo.clone().unwrap_or_default(),
+                entry.source.as_str().to_string(),
+            ];
+            let row: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+            csv.push_str(&row.join(","));
+            csv.push_str(&format!(",{:.6}\n", entry.amount_usd));
+        }
+        csv
+    }
+    
+    /// The entries, with totals by task, agent, label and day
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(&serde_json::json!({
+            "since": self.since,
+            "generated_at": self.generated_at,
+            "total_usd": self.total_usd(),
+            "by_task": self.by_task(),
+            "by_agent": self.by_agent(),
+            "by_label": self.by_label(),
+            "by_day": self.by_day(),
+            "entries": self.entries,
+        }))
+    }
+    
+    pub fn render(&self, format: CostFormat) -> Result<String, serde_json::Error> {
+        match format {
+            CostFormat::Csv => Ok(self.to_csv()),
+            CostFormat::Json => self.to_json(),
+        }
+    }
+}
+
+fn entry(task: &Task, recorded_at: DateTime<Utc>, agent: Option<String>, source: CostSource, amount_usd: f64) -> CostEntry {
+    CostEntry {
+        date: recorded_at.date_naive(),
+        recorded_at,
+        task_id: task.id.clone(),
+        title: task.title.clone(),
+        agent,
+        labels: task.labels.clone(),
+        repo: task.repo.clone(),
+        source,
+        amount_usd,
+    }
+}
+
+/// Quote a CSV field when it needs it (RFC 4180), defusing any that a
+/// spreadsheet would run as a formula
+fn csv_field(field: &str) -> String {
+    let field = if field.starts_with(['=', '+', '-', '@']) {
+        format!("'{}", field)
+    } else {
+        field.to_string()
+    };
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field
+    }
+}
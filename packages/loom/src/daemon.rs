@@ -1,18 +1,47 @@
 //! Loom Daemon
 //!
-//! Tokio-based coordination via Unix socket.
+//! Tokio-based coordination via a Unix socket (a named pipe on Windows;
+//! see `ipc`).
 //! Provides RPC interface for agents and coordinates dispatch.
+//! Optionally serves the MCP operations over HTTP as well (see `http`).
+//!
+//! One daemon per `.loom`: `daemon.pid` is the lock, and a socket or PID
+//! file left by a daemon that died uncleanly is cleaned up on the next start.
+//! `check_health` probes the socket without needing the async runtime.
+//!
+//! A `subscribe` request turns its connection into an event stream: the
+//! daemon follows the task and session histories and writes each new event
+//! the subscriber asked for as a JSON line until the client disconnects
+//! (see `events`).
+//!
+//! With `[auto] enabled = true` in `dispatch.toml`, the daemon also works
+//! the queue itself: ready tasks that route with enough confidence are
+//! claimed for the chosen agent and run by the `Orchestrator`, within the
+//! configured concurrency limits.
 
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use tokio::net::{UnixListener, UnixStream};
+use std::time::Duration;
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
-use tokio::sync::Mutex;
+use tokio::sync::{broadcast, Mutex};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::work::{WorkStore, CreateTask, Status};
-use crate::dispatch::{Dispatcher, DispatchConfig};
+use crate::dispatch::{Dispatcher, DispatchConfig, AutoDispatch, AutoDispatchPicks};
+use crate::config::{BudgetConfig, LoomConfig};
+use crate::budget;
+use crate::policy::EvidenceRules;
+use crate::notify;
+use crate::logging::{DaemonLog, LogEntry, LogError, LogLevel};
+use crate::ipc::{Endpoint, Stream};
+use crate::events::{Event, EventFilter};
+use crate::orchestrator::{ExecutionResult as OrchestratorResult, Orchestrator, OrchestratorConfig};
+use crate::routing::RoutingConstraints;
+use crate::platform::process_alive;
 
 #[derive(Error, Debug)]
 pub enum DaemonError {
@@ -28,11 +57,23 @@ pub enum DaemonError {
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
     
-    #[error("Daemon already running")]
-    AlreadyRunning,
+    #[error("Loom error: {0}")]
+    Loom(#[from] crate::LoomError),
+    
+    #[error("Log error: {0}")]
+    Log(#[from] LogError),
+    
+    #[error("Daemon already running (pid {0}); stop it first, or remove .loom/daemon.pid if that process isn't loom")]
+    AlreadyRunning(u32),
     
     #[error("Daemon not running")]
     NotRunning,
+    
+    #[error("Daemon refused the request: {0}")]
+    Refused(String),
+    
+    #[error("Won't serve the HTTP API on {bind} without a token; set ${env}, or bind to 127.0.0.1")]
+    UnprotectedHttp { bind: String, env: String },
 }
 
 /// RPC Request
@@ -49,8 +90,8 @@ pub enum Request {
     Create { title: String, description: Option<String>, labels: Vec<String>, parent: Option<String> },
     /// Claim a task
     Claim { id: String, agent: String },
-    /// Complete a task
-    Complete { id: String, evidence: Option<String> },
+    /// Complete a task; given the agent, only while it holds the lease
+    Complete { id: String, agent: Option<String>, evidence: Option<String> },
     /// Cancel a task
     Cancel { id: String },
     /// Spawn a sub-task
@@ -63,8 +104,18 @@ pub enum Request {
     Dispatch { id: String, agent: Option<String> },
     /// Health check
     Ping,
+    /// Uptime, queue depth, and last error
+    Status,
     /// Shutdown daemon
     Shutdown,
+    /// Stream task and session events on this connection until it closes
+    Subscribe {
+        /// Event names, bare or qualified (`task.completed`); all when empty
+        #[serde(default)]
+        events: Vec<String>,
+        task_id: Option<String>,
+        agent: Option<String>,
+    },
 }
 
 /// RPC Response
@@ -89,21 +140,203 @@ impl Response {
     }
 }
 
+/// Socket file in the `.loom` directory
+const SOCKET_FILE: &str = "run.sock";
+
+/// PID file in the `.loom` directory; also the single-daemon lock
+const PID_FILE: &str = "daemon.pid";
+
+/// How long a health check waits for the daemon to answer
+const HEALTH_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// How often the task and session histories are checked for new events
+const EVENT_POLL: Duration = Duration::from_millis(250);
+
+/// Events a slow subscriber can fall behind by before it misses some
+const EVENT_BACKLOG: usize = 1024;
+
+/// An error the daemon hit while running
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LastError {
+    pub at: DateTime<Utc>,
+    pub message: String,
+}
+
+/// What a running daemon reports about itself
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DaemonStatus {
+    pub pid: u32,
+    pub started_at: DateTime<Utc>,
+    pub uptime_secs: i64,
+    /// Ready tasks waiting to be picked up
+    pub queue_depth: usize,
+    /// Tasks currently claimed by an agent
+    pub in_progress: usize,
+    pub last_error: Option<LastError>,
+}
+
 /// Daemon state
 pub struct DaemonState {
     store: WorkStore,
+    /// Checked before claims over the socket
+    budgets: BudgetConfig,
     dispatcher: Option<Dispatcher>,
-    shutdown: bool,
+    /// Signalled by a `Shutdown` request
+    shutdown: Arc<tokio::sync::Notify>,
+    started_at: DateTime<Utc>,
+    last_error: Option<LastError>,
+    log: Arc<DaemonLog>,
+    /// New task and session events, for subscribers
+    events: broadcast::Sender<Event>,
 }
 
 impl DaemonState {
-    fn new(store: WorkStore, dispatcher: Option<Dispatcher>) -> Self {
+    fn new(store: WorkStore, budgets: BudgetConfig, dispatcher: Option<Dispatcher>, log: Arc<DaemonLog>) -> Self {
         Self {
             store,
+            budgets,
             dispatcher,
-            shutdown: false,
+            shutdown: Arc::new(tokio::sync::Notify::new()),
+            started_at: Utc::now(),
+            last_error: None,
+            log,
+            events: broadcast::channel(EVENT_BACKLOG).0,
         }
     }
+    
+    /// Log an error and keep it for `Status`
+    fn record_error(&mut self, message: String) {
+        self.log(LogEntry::error("error", message.clone()));
+        self.last_error = Some(LastError { at: Utc::now(), message });
+    }
+    
+    fn log(&self, entry: LogEntry) {
+        write_log(&self.log, entry);
+    }
+    
+    fn status(&self) -> Result<DaemonStatus, DaemonError> {
+        Ok(DaemonStatus {
+            pid: std::process::id(),
+            started_at: self.started_at,
+            uptime_secs: (Utc::now() - self.started_at).num_seconds(),
+            queue_depth: self.store.ready()?.len(),
+            in_progress: self.store.list_by_status(Status::Claimed)?.len(),
+            last_error: self.last_error.clone(),
+        })
+    }
+}
+
+/// Exclusive claim on a `.loom` directory, held while the daemon runs
+///
+/// The PID file is written to a temporary name and hard-linked into place,
+/// so it appears atomically with its contents and two daemons starting at
+/// once can't both take it. A PID file whose process is gone was left by a
+/// daemon that died uncleanly and is taken over.
+struct PidLock {
+    path: PathBuf,
+}
+
+impl PidLock {
+    fn acquire(root: &Path) -> Result<Self, DaemonError> {
+        let path = root.join(PID_FILE);
+        let staging = root.join(format!("{}.{}", PID_FILE, std::process::id()));
+        std::fs::write(&staging, std::process::id().to_string())?;
+        
+        let result = loop {
+            match std::fs::hard_link(&staging, &path) {
+                Ok(()) => break Ok(Self { path }),
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => match read_pid(&path) {
+                    Some(pid) if process_alive(pid) => break Err(DaemonError::AlreadyRunning(pid)),
+                    _ => {
+                        eprintln!("Removing stale {} left by a daemon that exited uncleanly", PID_FILE);
+                        match std::fs::remove_file(&path) {
+                            Ok(()) => {}
+                            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                            Err(e) => break Err(e.into()),
+                        }
+                    }
+                },
+                Err(e) => break Err(e.into()),
+            }
+        };
+        let _ = std::fs::remove_file(&staging);
+        result
+    }
+}
+
+impl Drop for PidLock {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+fn read_pid(path: &Path) -> Option<u32> {
+    std::fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+/// Whether a daemon is serving a `.loom` directory
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Liveness {
+    /// Answering on its socket
+    Running,
+    /// No socket and no PID file
+    NotRunning,
+    /// Socket or PID file left by a daemon that died uncleanly
+    Stale,
+    /// The process is alive but the socket doesn't answer
+    Unresponsive,
+}
+
+/// Result of probing the daemon for a `.loom` directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DaemonHealth {
+    pub state: Liveness,
+    pub socket: PathBuf,
+    /// PID from the PID file, when there is one
+    pub pid: Option<u32>,
+    /// Self-reported status, when the daemon answered
+    pub status: Option<DaemonStatus>,
+    /// What's wrong, when not running cleanly
+    pub problem: Option<String>,
+}
+
+/// Probe the daemon for a `.loom` directory: check its PID file, then ask
+/// it for its status over the socket (blocking, with a short timeout)
+pub fn check_health(root: &Path) -> DaemonHealth {
+    let socket = root.join(SOCKET_FILE);
+    let pid = read_pid(&root.join(PID_FILE));
+    let alive = pid.is_some_and(process_alive);
+    let has_pid_file = root.join(PID_FILE).exists();
+    
+    let answer = if Endpoint::new(&socket).exists() {
+        Some(DaemonClient::new(&socket).request_blocking(&Request::Status, HEALTH_TIMEOUT))
+    } else {
+        None
+    };
+    
+    let (state, status, problem) = match answer {
+        Some(Ok(Response::Ok { data })) => match serde_json::from_value(data) {
+            Ok(status) => (Liveness::Running, Some(status), None),
+            Err(e) => (Liveness::Running, None, Some(format!("unexpected status reply: {}", e))),
+        },
+        Some(Ok(Response::Error { message })) => (Liveness::Running, None, Some(message)),
+        Some(Err(e)) if alive => (Liveness::Unresponsive, None, Some(format!("socket doesn't answer: {}", e))),
+        Some(Err(_)) => (
+            Liveness::Stale,
+            None,
+            Some("socket left by a daemon that exited uncleanly; the next start removes it".to_string()),
+        ),
+        None if alive => (Liveness::Unresponsive, None, Some("daemon process has no socket".to_string())),
+        None if has_pid_file => (
+            Liveness::Stale,
+            None,
+            Some("PID file left by a daemon that exited uncleanly; the next start removes it".to_string()),
+        ),
+        None => (Liveness::NotRunning, None, None),
+    };
+    
+    DaemonHealth { state, socket, pid, status, problem }
 }
 
 /// The Loom daemon
@@ -111,39 +344,86 @@ pub struct Daemon {
     root: PathBuf,
     socket_path: PathBuf,
     state: Arc<Mutex<DaemonState>>,
+    config: LoomConfig,
+    http_bind: Option<String>,
+    /// Loom the HTTP API serves, when the caller already has one open
+    loom: Option<Arc<Mutex<crate::Loom>>>,
+    log: Arc<DaemonLog>,
+    /// `dispatch.toml`, when it turns auto-dispatch on
+    auto_config: Option<DispatchConfig>,
+    /// Tasks being run by auto-dispatch, and their agents
+    dispatched: Arc<std::sync::Mutex<HashMap<String, String>>>,
+    /// Set while a probe pass is running, so a slow one isn't overlapped
+    probing: Arc<AtomicBool>,
+    /// Set while webhook deliveries are running
+    delivering: Arc<AtomicBool>,
 }
 
 impl Daemon {
     /// Create a new daemon for a Loom directory
     pub fn new(root: impl AsRef<Path>) -> Result<Self, DaemonError> {
         let root = root.as_ref().to_path_buf();
-        let socket_path = root.join("run.sock");
+        let socket_path = root.join(SOCKET_FILE);
         let db_path = root.join("work.db");
         
-        let store = WorkStore::open(&db_path)?;
+        // Stakeholder/notification settings live in the repo-level config
+        let config = LoomConfig::load(root.parent().unwrap_or(&root)).unwrap_or_default();
+        
+        let mut store = WorkStore::open(&db_path)?;
+        // A bad redaction pattern fails startup rather than logging unredacted
+        let log = Arc::new(DaemonLog::open(root.join("log"), &config.logging)?);
+        
+        // Completions over the socket get the same evidence guardrail as the library
+        match EvidenceRules::compile(&config.evidence.require) {
+            Ok(rules) => store.set_evidence_rules(rules),
+            Err(e) => write_log(&log, LogEntry::new(LogLevel::Warn, "config", format!("Ignoring evidence requirements: invalid pattern: {}", e))),
+        }
+        store.set_auto_complete_parents(config.subtasks.auto_complete_parents);
+        store.set_lease_duration(config.claims.lease_duration());
         
         // Try to load dispatcher config
         let dispatch_path = root.join("dispatch.toml");
-        let dispatcher = if dispatch_path.exists() {
-            DispatchConfig::from_file(&dispatch_path)
-                .map(Dispatcher::new)
-                .ok()
+        let dispatch_config = if dispatch_path.exists() {
+            DispatchConfig::from_file(&dispatch_path).ok()
         } else {
             None
         };
+        let dispatcher = dispatch_config.clone().map(Dispatcher::new);
+        let auto_config = dispatch_config.filter(|c| c.auto.enabled);
         
-        let state = Arc::new(Mutex::new(DaemonState::new(store, dispatcher)));
+        let state = Arc::new(Mutex::new(DaemonState::new(store, config.budgets.clone(), dispatcher, log.clone())));
+        let http_bind = config.http.bind.clone();
         
         Ok(Self {
             root,
             socket_path,
             state,
+            config,
+            http_bind,
+            loom: None,
+            log,
+            auto_config,
+            dispatched: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            probing: Arc::new(AtomicBool::new(false)),
+            delivering: Arc::new(AtomicBool::new(false)),
         })
     }
     
-    /// Check if daemon is already running
+    /// Serve the HTTP API on this address (overrides `[http] bind`)
+    pub fn with_http(mut self, bind: impl Into<String>) -> Self {
+        self.http_bind = Some(bind.into());
+        self
+    }
+    
+    /// Serve the HTTP API from this Loom rather than opening another
+    pub fn with_loom(mut self, loom: crate::Loom) -> Self {
+        self.loom = Some(Arc::new(Mutex::new(loom)));
+        self
+    }
+    
+    /// Check if a daemon is already running (and answering) for this directory
     pub fn is_running(&self) -> bool {
-        self.socket_path.exists()
+        check_health(&self.root).state == Liveness::Running
     }
     
     /// Get the socket path
@@ -153,69 +433,581 @@ impl Daemon {
     
     /// Run the daemon
     pub async fn run(&self) -> Result<(), DaemonError> {
-        // Remove stale socket if exists
-        if self.socket_path.exists() {
-            std::fs::remove_file(&self.socket_path)?;
+        // Released (and the PID file removed) however this returns
+        let _lock = PidLock::acquire(&self.root)?;
+        
+        // Bind HTTP first so a taken port fails before the socket exists
+        let http_server = match &self.http_bind {
+            Some(bind) => Some(self.start_http(bind).await?),
+            None => None,
+        };
+        
+        // Holding the lock means any existing socket is a dead daemon's
+        let endpoint = Endpoint::new(&self.socket_path);
+        if cfg!(unix) && endpoint.exists() {
+            self.log(LogEntry::new(LogLevel::Warn, "start", "Removing stale socket left by a daemon that exited uncleanly"));
+            endpoint.remove_stale()?;
         }
         
         // Create listener
-        let listener = UnixListener::bind(&self.socket_path)?;
+        let mut listener = endpoint.bind()?;
         
-        // Write PID file
-        let pid_path = self.root.join("daemon.pid");
-        std::fs::write(&pid_path, std::process::id().to_string())?;
+        self.log(
+            LogEntry::info("start", format!("Loom daemon started on {}", endpoint.display()))
+                .field("pid", std::process::id()),
+        );
         
-        eprintln!("Loom daemon started on {}", self.socket_path.display());
+        // Mentions and question deadlines are checked every minute; full digests on the configured interval
+        let digest_secs = self.config.notifications.digest_interval_mins.max(1) * 60;
+        let mut mention_tick = tokio::time::interval(std::time::Duration::from_secs(60));
+        let mut digest_tick = tokio::time::interval(std::time::Duration::from_secs(digest_secs));
+        // Maintenance runs on its own schedule; an interval of 0 disables it
+        let maintenance_hours = self.config.maintenance.interval_hours;
+        let maintenance_period = std::time::Duration::from_secs(maintenance_hours.max(1) * 3600);
+        let mut maintenance_tick = tokio::time::interval_at(
+            tokio::time::Instant::now() + maintenance_period,
+            maintenance_period,
+        );
+        // Agents are probed at startup, then on their own schedule
+        let probe_mins = self.config.probe.interval_mins;
+        let mut probe_tick = tokio::time::interval(std::time::Duration::from_secs(probe_mins.max(1) * 60));
+        // Webhooks follow the task history closely
+        let mut webhook_tick = tokio::time::interval(std::time::Duration::from_secs(5));
+        let dispatch_secs = self.auto_config.as_ref().map_or(30, |c| c.auto.interval_secs.max(1));
+        let mut dispatch_tick = tokio::time::interval(std::time::Duration::from_secs(dispatch_secs));
+        let mut last_digest = chrono::Utc::now();
+        let (shutdown, events) = {
+            let state = self.state.lock().await;
+            (state.shutdown.clone(), state.events.clone())
+        };
+        let event_feed = self.start_event_feed(events);
         
         loop {
             tokio::select! {
+                _ = mention_tick.tick() => {
+                    self.send_mentions().await;
+                    self.apply_default_decisions().await;
+                    self.run_schedules().await;
+                    self.reap_stale_claims().await;
+                }
+                _ = webhook_tick.tick(), if !self.config.webhooks.is_empty() => {
+                    self.deliver_webhooks();
+                }
+                _ = dispatch_tick.tick(), if self.auto_config.is_some() => {
+                    self.auto_dispatch().await;
+                }
+                _ = maintenance_tick.tick(), if maintenance_hours > 0 => {
+                    self.run_maintenance().await;
+                }
+                _ = probe_tick.tick(), if probe_mins > 0 => {
+                    self.probe_agents();
+                }
+                _ = digest_tick.tick() => {
+                    let now = chrono::Utc::now();
+                    self.notify_stakeholders(last_digest).await;
+                    last_digest = now;
+                }
                 result = listener.accept() => {
                     match result {
-                        Ok((stream, _)) => {
+                        Ok(stream) => {
                             let state = self.state.clone();
                             tokio::spawn(async move {
-                                if let Err(e) = handle_connection(stream, state).await {
-                                    eprintln!("Connection error: {}", e);
+                                if let Err(e) = handle_connection(stream, state.clone()).await {
+                                    state.lock().await.record_error(format!("Connection error: {}", e));
                                 }
                             });
                         }
                         Err(e) => {
-                            eprintln!("Accept error: {}", e);
+                            self.record_error(format!("Accept error: {}", e)).await;
                         }
                     }
                 }
+                _ = shutdown.notified() => {
+                    self.log(LogEntry::info("stop", "Shutting down..."));
+                    break;
+                }
                 _ = tokio::signal::ctrl_c() => {
-                    eprintln!("Shutting down...");
+                    self.log(LogEntry::info("stop", "Shutting down..."));
                     break;
                 }
             }
+        }
+        
+        // Cleanup
+        event_feed.store(true, Ordering::Relaxed);
+        if let Some(server) = http_server {
+            server.abort();
+        }
+        endpoint.remove_stale()?;
+        
+        Ok(())
+    }
+    
+    /// Log an error and keep it for `Status`
+    async fn record_error(&self, message: String) {
+        self.state.lock().await.record_error(message);
+    }
+    
+    fn log(&self, entry: LogEntry) {
+        write_log(&self.log, entry);
+    }
+    
+    /// Bind the HTTP API and serve it in the background
+    ///
+    /// All HTTP requests share one `Loom` on the same stores the socket RPC
+    /// uses: the one given to `with_loom`, or one opened here. Off loopback,
+    /// a bearer token is required.
+    async fn start_http(&self, bind: &str) -> Result<tokio::task::JoinHandle<()>, DaemonError> {
+        let listener = tokio::net::TcpListener::bind(bind).await?;
+        let token = self.config.http.token();
+        if token.is_none() && !listener.local_addr()?.ip().is_loopback() {
+            return Err(DaemonError::UnprotectedHttp {
+                bind: bind.to_string(),
+                env: self.config.http.token_env.clone(),
+            });
+        }
+        let loom = match &self.loom {
+            Some(loom) => loom.clone(),
+            None => Arc::new(Mutex::new(crate::Loom::open(self.root.parent().unwrap_or(&self.root))?)),
+        };
+        
+        self.log(LogEntry::info("http", format!(
+            "HTTP API listening on http://{}{}",
+            listener.local_addr()?,
+            if token.is_some() { " (bearer token required)" } else { "" }
+        )));
+        
+        let log = self.log.clone();
+        Ok(tokio::spawn(async move {
+            if let Err(e) = crate::http::serve(listener, loom, token).await {
+                write_log(&log, LogEntry::error("http", format!("HTTP API stopped: {}", e)));
+            }
+        }))
+    }
+    
+    /// Run a maintenance pass (prune + VACUUM) off the async runtime
+    async fn run_maintenance(&self) {
+        let repo_root = self.root.parent().unwrap_or(&self.root).to_path_buf();
+        let result = tokio::task::spawn_blocking(move || {
+            crate::Loom::open(&repo_root)
+                .and_then(|mut loom| loom.maintain())
+                .map_err(|e| e.to_string())
+        }).await;
+        
+        match result {
+            Ok(Ok(report)) => self.log(LogEntry::info("maintenance", format!(
+                "Maintenance: pruned {} rows, reclaimed {} bytes",
+                report.rows_pruned(),
+                report.bytes_reclaimed()
+            ))),
+            Ok(Err(e)) => self.record_error(format!("Maintenance error: {}", e)).await,
+            Err(e) => self.record_error(format!("Maintenance error: {}", e)).await,
+        }
+    }
+    
+    /// Probe every agent's CLI in the background, logging the ones whose
+    /// availability changed
+    ///
+    /// Probing waits up to the timeout on each agent, so it runs apart from
+    /// the main loop. Each result is stored as it arrives, and a tick that
+    /// finds the last pass still running skips.
+    fn probe_agents(&self) {
+        if self.probing.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        let repo_root = self.root.parent().unwrap_or(&self.root).to_path_buf();
+        let timeout = self.config.probe.timeout();
+        let (state, log, probing) = (self.state.clone(), self.log.clone(), self.probing.clone());
+        
+        tokio::spawn(async move {
+            let result = tokio::task::spawn_blocking(move || {
+                crate::Loom::open(&repo_root)
+                    .and_then(|mut loom| {
+                        let before: std::collections::HashMap<String, bool> =
+                            loom.agents()?.into_iter().map(|a| (a.id, a.available)).collect();
+                        let probes = loom.probe_agents(None, timeout)?;
+                        Ok(probes.into_iter().filter(|p| before.get(&p.agent_id) != Some(&p.available)).collect::<Vec<_>>())
+                    })
+                    .map_err(|e| e.to_string())
+            }).await;
+            probing.store(false, Ordering::Release);
+            
+            match result {
+                Ok(Ok(changed)) => {
+                    for probe in changed {
+                        let entry = if probe.available {
+                            LogEntry::info("probe", format!("Agent {} is available", probe.agent_id))
+                                .field("cold_start_ms", probe.cold_start_ms.unwrap_or_default())
+                        } else {
+                            LogEntry::new(LogLevel::Warn, "probe", format!(
+                                "Agent {} is unavailable: {}",
+                                probe.agent_id,
+                                probe.error.as_deref().unwrap_or("no answer")
+                            ))
+                        };
+                        write_log(&log, entry.field("agent", &probe.agent_id));
+                    }
+                }
+                Ok(Err(e)) => state.lock().await.record_error(format!("Agent probe error: {}", e)),
+                Err(e) => state.lock().await.record_error(format!("Agent probe error: {}", e)),
+            }
+        });
+    }
+    
+    /// Resolve questions whose deadline has passed with their default decision
+    async fn apply_default_decisions(&self) {
+        let repo_root = self.root.parent().unwrap_or(&self.root).to_path_buf();
+        let result = tokio::task::spawn_blocking(move || {
+            crate::Loom::open(&repo_root)
+                .and_then(|mut loom| loom.apply_default_decisions())
+                .map_err(|e| e.to_string())
+        }).await;
+        
+        match result {
+            Ok(Ok(applied)) => {
+                for (question, decision) in applied {
+                    self.log(
+                        LogEntry::info("question", format!("Question {} defaulted to '{}'", question, decision))
+                            .field("id", &question)
+                            .field("decision", &decision),
+                    );
+                }
+            }
+            Ok(Err(e)) => self.record_error(format!("Question deadline error: {}", e)).await,
+            Err(e) => self.record_error(format!("Question deadline error: {}", e)).await,
+        }
+    }
+    
+    /// Create the tasks for schedules that have come due
+    async fn run_schedules(&self) {
+        let repo_root = self.root.parent().unwrap_or(&self.root).to_path_buf();
+        let result = tokio::task::spawn_blocking(move || {
+            crate::Loom::open(&repo_root)
+                .and_then(|mut loom| loom.run_schedules())
+                .map_err(|e| e.to_string())
+        }).await;
+        
+        match result {
+            Ok(Ok(created)) => {
+                for task in created {
+                    self.log(
+                        LogEntry::info("schedule", format!("Scheduled task {}: {}", task.id, task.title))
+                            .field("id", &task.id)
+                            .field("title", &task.title),
+                    );
+                }
+            }
+            Ok(Err(e)) => self.record_error(format!("Schedule error: {}", e)).await,
+            Err(e) => self.record_error(format!("Schedule error: {}", e)).await,
+        }
+    }
+    
+    /// Release claims whose agent has gone quiet
+    async fn reap_stale_claims(&self) {
+        if self.config.claims.timeout().is_none() {
+            return;
+        }
+        let repo_root = self.root.parent().unwrap_or(&self.root).to_path_buf();
+        let result = tokio::task::spawn_blocking(move || {
+            crate::Loom::open(&repo_root)
+                .and_then(|mut loom| loom.reap_stale_claims(false))
+                .map_err(|e| e.to_string())
+        }).await;
+        
+        match result {
+            Ok(Ok(reaped)) => {
+                for claim in reaped {
+                    self.log(
+                        LogEntry::new(LogLevel::Warn, "claim", format!("Released stale claim on {} by {}", claim.task_id, claim.agent))
+                            .field("id", &claim.task_id)
+                            .field("agent", &claim.agent)
+                            .field("idle_since", claim.idle_since.to_rfc3339()),
+                    );
+                }
+            }
+            Ok(Err(e)) => self.record_error(format!("Claim reaper error: {}", e)).await,
+            Err(e) => self.record_error(format!("Claim reaper error: {}", e)).await,
+        }
+    }
+    
+    /// POST new task events to the configured webhooks in the background
+    ///
+    /// A slow endpoint can take the full request timeout, so delivery runs
+    /// apart from the main loop. A tick that finds the last delivery still
+    /// running skips; the cursors pick up where it left off.
+    fn deliver_webhooks(&self) {
+        if self.delivering.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        let repo_root = self.root.parent().unwrap_or(&self.root).to_path_buf();
+        let (state, log, delivering) = (self.state.clone(), self.log.clone(), self.delivering.clone());
+        
+        tokio::spawn(async move {
+            let result = tokio::task::spawn_blocking(move || {
+                crate::Loom::open(&repo_root)
+                    .and_then(|mut loom| loom.deliver_webhooks())
+                    .map_err(|e| e.to_string())
+            }).await;
+            delivering.store(false, Ordering::Release);
             
-            // Check for shutdown flag
-            let should_shutdown = {
-                let state = self.state.lock().await;
-                state.shutdown
+            match result {
+                Ok(Ok(deliveries)) => {
+                    for delivery in deliveries {
+                        if delivery.delivered > 0 {
+                            write_log(&log,
+                                LogEntry::info("webhook", format!("Delivered {} events to {}", delivery.delivered, delivery.url))
+                                    .field("url", &delivery.url)
+                                    .field("delivered", delivery.delivered),
+                            );
+                        }
+                        if let Some(error) = &delivery.error {
+                            write_log(&log,
+                                LogEntry::new(LogLevel::Warn, "webhook", format!("Webhook {} failed: {}", delivery.url, error))
+                                    .field("url", &delivery.url)
+                                    .field("skipped", delivery.skipped.len()),
+                            );
+                        }
+                    }
+                }
+                Ok(Err(e)) => state.lock().await.record_error(format!("Webhook error: {}", e)),
+                Err(e) => state.lock().await.record_error(format!("Webhook error: {}", e)),
+            }
+        });
+    }
+    
+    /// Claim ready tasks that route confidently and run them with the
+    /// orchestrator, each on a thread of its own; ask for approval of the
+    /// rest when `[auto] approvals` is on
+    async fn auto_dispatch(&self) {
+        let Some(config) = self.auto_config.clone() else { return };
+        let worktrees = config.auto.worktrees;
+        let running = self.dispatched.lock().expect("dispatch map poisoned").clone();
+        if running.len() >= config.auto.max_concurrent {
+            return;
+        }
+        
+        let repo_root = self.root.parent().unwrap_or(&self.root).to_path_buf();
+        let root = repo_root.clone();
+        let result = tokio::task::spawn_blocking(move || -> Result<AutoDispatchPicks, crate::LoomError> {
+            let mut loom = crate::Loom::open(&root)?;
+            
+            // Only route to agents the orchestrator can actually run here
+            let orchestrator = Orchestrator::new();
+            let excluded_agents = loom.agents()?
+                .into_iter()
+                .filter(|p| orchestrator.backend_for(p).is_none())
+                .map(|p| p.id)
+                .collect();
+            let constraints = RoutingConstraints { excluded_agents, ..Default::default() };
+            loom.auto_dispatch(&config, &running, &constraints)
+        }).await;
+        
+        let picks = match result {
+            Ok(Ok(picks)) => picks,
+            Ok(Err(e)) => return self.record_error(format!("Auto-dispatch error: {}", e)).await,
+            Err(e) => return self.record_error(format!("Auto-dispatch error: {}", e)).await,
+        };
+        
+        for pick in &picks.approvals {
+            self.log(
+                LogEntry::info(
+                    "dispatch",
+                    format!("Asked for approval to dispatch {} to {}", pick.task.id, pick.decision.agent_id),
+                )
+                .field("task", &pick.task.id)
+                .field("agent", &pick.decision.agent_id)
+                .field("confidence", format!("{:.2}", pick.decision.confidence))
+                .field("threshold", format!("{:.2}", pick.threshold)),
+            );
+        }
+        
+        for pick in picks.dispatch {
+            let AutoDispatch { task, decision, .. } = pick;
+            self.log(
+                LogEntry::info("dispatch", format!("Dispatching {} to {}", task.id, decision.agent_id))
+                    .field("task", &task.id)
+                    .field("agent", &decision.agent_id)
+                    .field("confidence", format!("{:.2}", decision.confidence)),
+            );
+            self.dispatched.lock().expect("dispatch map poisoned").insert(task.id.clone(), decision.agent_id.clone());
+            
+            let (repo_root, dispatched, log) = (repo_root.clone(), self.dispatched.clone(), self.log.clone());
+            // Agent runs take minutes; a plain thread keeps them from holding
+            // up the runtime's shutdown
+            std::thread::spawn(move || {
+                let outcome = run_dispatched(&repo_root, &task, &decision.agent_id, worktrees);
+                dispatched.lock().expect("dispatch map poisoned").remove(&task.id);
+                let entry = match outcome {
+                    Ok(result) if result.success => LogEntry::info(
+                        "dispatch",
+                        format!("{} completed by {} in {:.0}s", task.id, result.backend.as_str(), result.duration_secs),
+                    ),
+                    Ok(result) if result.timed_out => LogEntry::new(
+                        LogLevel::Warn,
+                        "dispatch",
+                        format!("{} timed out on {}; released for another agent", task.id, result.backend.as_str()),
+                    ),
+                    Ok(result) => LogEntry::new(
+                        LogLevel::Warn,
+                        "dispatch",
+                        format!(
+                            "{} failed on {}; left claimed for review: {}",
+                            task.id,
+                            result.backend.as_str(),
+                            result.error.as_deref().unwrap_or("agent reported failure")
+                        ),
+                    ),
+                    Err(e) => LogEntry::error("dispatch", format!("{} could not be run: {}", task.id, e)),
+                };
+                write_log(&log, entry.field("task", &task.id));
+            });
+        }
+    }
+    
+    /// Follow the task and session histories on a thread of their own,
+    /// sending new events to subscribers. Returns the flag that stops it.
+    fn start_event_feed(&self, events: broadcast::Sender<Event>) -> Arc<AtomicBool> {
+        let stop = Arc::new(AtomicBool::new(false));
+        let stopped = stop.clone();
+        let repo_root = self.root.parent().unwrap_or(&self.root).to_path_buf();
+        let log = self.log.clone();
+        
+        std::thread::spawn(move || {
+            let loom = match crate::Loom::open(&repo_root) {
+                Ok(loom) => loom,
+                Err(e) => {
+                    write_log(&log, LogEntry::error("events", format!("Event stream unavailable: {}", e)));
+                    return;
+                }
+            };
+            let mut cursor = match loom.event_cursor() {
+                Ok(cursor) => cursor,
+                Err(e) => {
+                    write_log(&log, LogEntry::error("events", format!("Event stream unavailable: {}", e)));
+                    return;
+                }
             };
+            // Report each distinct failure once rather than every poll
+            let mut failing: Option<String> = None;
             
-            if should_shutdown {
-                break;
+            while !stopped.load(Ordering::Relaxed) {
+                std::thread::sleep(EVENT_POLL);
+                // Nobody listening: skip ahead rather than build a backlog
+                let result = if events.receiver_count() == 0 {
+                    loom.event_cursor().map(|latest| {
+                        cursor = latest;
+                        Vec::new()
+                    })
+                } else {
+                    loom.poll_events(&mut cursor)
+                };
+                
+                match result {
+                    Ok(new) => {
+                        failing = None;
+                        for event in new {
+                            // Only fails when the last subscriber just left
+                            let _ = events.send(event);
+                        }
+                    }
+                    Err(e) if failing.as_deref() != Some(&e.to_string()) => {
+                        write_log(&log, LogEntry::error("events", format!("Event stream error: {}", e)));
+                        failing = Some(e.to_string());
+                    }
+                    Err(_) => {}
+                }
             }
+        });
+        
+        stop
+    }
+    
+    /// Alert stakeholders to new mentions; cursors in the store keep each
+    /// mention from being sent twice, across restarts too
+    async fn send_mentions(&self) {
+        if self.config.stakeholders.is_empty() {
+            return;
         }
         
-        // Cleanup
-        if self.socket_path.exists() {
-            std::fs::remove_file(&self.socket_path)?;
+        let repo_root = self.root.parent().unwrap_or(&self.root).to_path_buf();
+        let result = tokio::task::spawn_blocking(move || {
+            crate::Loom::open(&repo_root)
+                .and_then(|mut loom| loom.send_mentions())
+                .map_err(|e| e.to_string())
+        }).await;
+        
+        match result {
+            Ok(Ok(errors)) => {
+                for e in errors {
+                    self.record_error(format!("Notification error: {}", e)).await;
+                }
+            }
+            Ok(Err(e)) => self.record_error(format!("Notification error: {}", e)).await,
+            Err(e) => self.record_error(format!("Notification error: {}", e)).await,
         }
-        if pid_path.exists() {
-            std::fs::remove_file(&pid_path)?;
+    }
+    
+    /// Build and deliver stakeholder digests (or mention-only alerts) for
+    /// activity since `since`
+    async fn notify_stakeholders(&self, since: chrono::DateTime<chrono::Utc>) {
+        if self.config.stakeholders.is_empty() {
+            return;
         }
         
-        Ok(())
+        let tasks = {
+            let mut state = self.state.lock().await;
+            match state.store.list_all() {
+                Ok(tasks) => tasks,
+                Err(e) => {
+                    state.record_error(format!("Notification error: {}", e));
+                    return;
+                }
+            }
+        };
+        
+        let digests = notify::build_digests(&self.config.stakeholders, &tasks, since);
+        for digest in digests {
+            let stakeholder = self.config.stakeholders[&digest.stakeholder].clone();
+            let root = self.root.clone();
+            let result = tokio::task::spawn_blocking(move || {
+                notify::deliver(&digest, &stakeholder, &root)
+                    .map_err(|e| format!("@{}: {}", digest.stakeholder, e))
+            }).await;
+            
+            if let Ok(Err(e)) = result {
+                self.record_error(format!("Notification error: {}", e)).await;
+            }
+        }
     }
 }
 
+/// Run an auto-dispatched task to the end, completing it if the agent succeeds
+fn run_dispatched(repo_root: &Path, task: &crate::Task, agent: &str, worktrees: bool) -> Result<OrchestratorResult, String> {
+    let mut loom = crate::Loom::open(repo_root).map_err(|e| e.to_string())?;
+    let orchestrator = Orchestrator::with_config(OrchestratorConfig {
+        working_dir: repo_root.to_path_buf(),
+        notifications: false,
+        worktrees,
+        ..Default::default()
+    });
+    // Dispatch only routes to agents with a backend, but one may have been
+    // uninstalled since
+    let backend = loom.agent(agent)
+        .map_err(|e| e.to_string())?
+        .and_then(|profile| orchestrator.backend_for(&profile))
+        .ok_or_else(|| format!("No backend can run {} here", agent))?;
+    
+    let result = orchestrator.execute_with(task, backend, agent, &mut loom).map_err(|e| e.to_string())?;
+    if result.success {
+        let evidence = format!("{} (auto-dispatched)", result.evidence());
+        loom.complete_as(&task.id, agent, Some(&evidence)).map_err(|e| e.to_string())?;
+    }
+    Ok(result)
+}
+
 /// Handle a single connection
-async fn handle_connection(stream: UnixStream, state: Arc<Mutex<DaemonState>>) -> Result<(), DaemonError> {
-    let (reader, mut writer) = stream.into_split();
+async fn handle_connection(stream: Stream, state: Arc<Mutex<DaemonState>>) -> Result<(), DaemonError> {
+    let (reader, mut writer) = tokio::io::split(stream);
     let mut reader = BufReader::new(reader);
     let mut line = String::new();
     
@@ -237,7 +1029,21 @@ async fn handle_connection(stream: UnixStream, state: Arc<Mutex<DaemonState>>) -
             }
         };
         
+        if let Request::Subscribe { events, task_id, agent } = request {
+            let filter = EventFilter { events, task_id, agent };
+            let receiver = state.lock().await.events.subscribe();
+            return stream_events(reader, writer, receiver, filter).await;
+        }
+        
+        let entry = request_entry(&request);
         let response = handle_request(request, &state).await;
+        if let Some(mut entry) = entry {
+            if let Response::Error { message } = &response {
+                entry.level = LogLevel::Warn;
+                entry.fields.insert("error".to_string(), message.clone());
+            }
+            state.lock().await.log(entry);
+        }
         let json = serde_json::to_string(&response)? + "\n";
         writer.write_all(json.as_bytes()).await?;
     }
@@ -245,6 +1051,88 @@ async fn handle_connection(stream: UnixStream, state: Arc<Mutex<DaemonState>>) -
     Ok(())
 }
 
+/// Write events matching `filter` to a subscriber until it disconnects
+///
+/// Each event is sent as a `Response::Ok` line; a subscriber too slow to
+/// keep up gets a `Response::Error` line saying how many it missed, and the
+/// stream carries on from there.
+async fn stream_events<R, W>(
+    mut reader: BufReader<R>,
+    mut writer: W,
+    mut receiver: broadcast::Receiver<Event>,
+    filter: EventFilter,
+) -> Result<(), DaemonError>
+where
+    R: tokio::io::AsyncRead + Unpin,
+    W: tokio::io::AsyncWrite + Unpin,
+{
+    let mut pending = vec![Response::ok(serde_json::json!({ "subscribed": true }))];
+    let mut discard = String::new();
+    
+    loop {
+        for response in pending.drain(..) {
+            let json = serde_json::to_string(&response)? + "\n";
+            match writer.write_all(json.as_bytes()).await {
+                Ok(()) => {}
+                // The subscriber went away mid-write
+                Err(e) if matches!(e.kind(), std::io::ErrorKind::BrokenPipe | std::io::ErrorKind::ConnectionReset) => {
+                    return Ok(());
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+        
+        tokio::select! {
+            received = receiver.recv() => match received {
+                Ok(event) if filter.matches(&event) => pending.push(Response::ok(event)),
+                Ok(_) => {}
+                Err(broadcast::error::RecvError::Lagged(missed)) => {
+                    pending.push(Response::error(format!("Fell behind; missed {} events", missed)));
+                }
+                Err(broadcast::error::RecvError::Closed) => return Ok(()),
+            },
+            // Anything the subscriber sends is ignored; EOF ends the stream
+            read = reader.read_line(&mut discard) => {
+                if read? == 0 {
+                    return Ok(());
+                }
+                discard.clear();
+            }
+        }
+    }
+}
+
+/// Log entry for a request that changes something (reads aren't logged)
+fn request_entry(request: &Request) -> Option<LogEntry> {
+    if matches!(
+        request,
+        Request::Ready | Request::List { .. } | Request::Get { .. } | Request::Summary | Request::Ping | Request::Status
+            | Request::Subscribe { .. }
+    ) {
+        return None;
+    }
+    
+    let value = serde_json::to_value(request).ok()?;
+    let cmd = value["cmd"].as_str()?.to_string();
+    let mut entry = LogEntry::info("request", format!("{} request", cmd)).field("cmd", &cmd);
+    for (key, value) in value.as_object()? {
+        match value {
+            serde_json::Value::Null => {}
+            _ if key == "cmd" => {}
+            serde_json::Value::String(s) => entry = entry.field(key, s),
+            other => entry = entry.field(key, other),
+        }
+    }
+    Some(entry)
+}
+
+/// Write to the daemon log; a failed write is reported but never stops the daemon
+fn write_log(log: &DaemonLog, entry: LogEntry) {
+    if let Err(e) = log.write(entry) {
+        eprintln!("Log write failed: {}", e);
+    }
+}
+
 /// Handle a single request
 async fn handle_request(request: Request, state: &Arc<Mutex<DaemonState>>) -> Response {
     let mut state = state.lock().await;
@@ -254,11 +1142,23 @@ async fn handle_request(request: Request, state: &Arc<Mutex<DaemonState>>) -> Re
             Response::ok("pong")
         }
         
+        Request::Status => {
+            match state.status() {
+                Ok(status) => Response::ok(status),
+                Err(e) => Response::error(e.to_string()),
+            }
+        }
+        
         Request::Shutdown => {
-            state.shutdown = true;
+            state.shutdown.notify_one();
             Response::ok("shutting down")
         }
         
+        // Streams are set up by `handle_connection` before requests get here
+        Request::Subscribe { .. } => {
+            Response::error("subscribe must be sent on its own connection")
+        }
+        
         Request::Ready => {
             match state.store.ready() {
                 Ok(tasks) => Response::ok(tasks),
@@ -305,14 +1205,25 @@ async fn handle_request(request: Request, state: &Arc<Mutex<DaemonState>>) -> Re
         }
         
         Request::Claim { id, agent } => {
-            match state.store.claim(&id, &agent) {
+            let claim = match state.store.get(&id) {
+                Ok(Some(task)) => budget::check_claim(&state.store, &state.budgets, &task)
+                    .map_err(|e| e.to_string())
+                    .and_then(|()| state.store.claim(&id, &agent).map_err(|e| e.to_string())),
+                Ok(None) => Err(format!("Task not found: {}", id)),
+                Err(e) => Err(e.to_string()),
+            };
+            match claim {
                 Ok(task) => Response::ok(task),
-                Err(e) => Response::error(e.to_string()),
+                Err(e) => Response::error(e),
             }
         }
         
-        Request::Complete { id, evidence } => {
-            match state.store.complete(&id, evidence.as_deref()) {
+        Request::Complete { id, agent, evidence } => {
+            let completed = match &agent {
+                Some(agent) => state.store.complete_as(&id, agent, evidence.as_deref()),
+                None => state.store.complete(&id, evidence.as_deref()),
+            };
+            match completed {
                 Ok(unblocked) => {
                     let msg = if unblocked.is_empty() {
                         "completed".to_string()
@@ -391,25 +1302,25 @@ async fn handle_request(request: Request, state: &Arc<Mutex<DaemonState>>) -> Re
 
 /// Client for communicating with daemon
 pub struct DaemonClient {
-    socket_path: PathBuf,
+    endpoint: Endpoint,
 }
 
 impl DaemonClient {
     pub fn new(socket_path: impl AsRef<Path>) -> Self {
         Self {
-            socket_path: socket_path.as_ref().to_path_buf(),
+            endpoint: Endpoint::new(socket_path),
         }
     }
     
-    /// Check if daemon is running
+    /// Check if a daemon is accepting connections (a stale socket file isn't)
     pub fn is_running(&self) -> bool {
-        self.socket_path.exists()
+        self.endpoint.is_listening()
     }
     
     /// Send a request to the daemon
     pub async fn request(&self, request: Request) -> Result<Response, DaemonError> {
-        let stream = UnixStream::connect(&self.socket_path).await?;
-        let (reader, mut writer) = stream.into_split();
+        let stream = self.endpoint.connect().await?;
+        let (reader, mut writer) = tokio::io::split(stream);
         
         // Send request
         let json = serde_json::to_string(&request)? + "\n";
@@ -424,6 +1335,13 @@ impl DaemonClient {
         Ok(response)
     }
     
+    /// Send a request without an async runtime, giving up after `timeout`
+    pub fn request_blocking(&self, request: &Request, timeout: Duration) -> Result<Response, DaemonError> {
+        let json = serde_json::to_string(request)? + "\n";
+        let line = self.endpoint.request_blocking(&json, timeout)?;
+        Ok(serde_json::from_str(&line)?)
+    }
+    
     /// Convenience methods
     pub async fn ready(&self) -> Result<Response, DaemonError> {
         self.request(Request::Ready).await
@@ -442,8 +1360,8 @@ impl DaemonClient {
         self.request(Request::Claim { id, agent }).await
     }
     
-    pub async fn complete(&self, id: String, evidence: Option<String>) -> Result<Response, DaemonError> {
-        self.request(Request::Complete { id, evidence }).await
+    pub async fn complete(&self, id: String, agent: Option<String>, evidence: Option<String>) -> Result<Response, DaemonError> {
+        self.request(Request::Complete { id, agent, evidence }).await
     }
     
     pub async fn ping(&self) -> Result<bool, DaemonError> {
@@ -457,6 +1375,48 @@ impl DaemonClient {
         self.request(Request::Shutdown).await?;
         Ok(())
     }
+    
+    pub async fn status(&self) -> Result<Response, DaemonError> {
+        self.request(Request::Status).await
+    }
+    
+    /// Stream events matching `filter`, passing each to `on_event` (or the
+    /// daemon's message when events were missed) until it returns false or
+    /// the daemon goes away
+    pub async fn subscribe(
+        &self,
+        filter: EventFilter,
+        mut on_event: impl FnMut(Result<Event, String>) -> bool,
+    ) -> Result<(), DaemonError> {
+        let stream = self.endpoint.connect().await?;
+        let (reader, mut writer) = tokio::io::split(stream);
+        
+        let request = Request::Subscribe { events: filter.events, task_id: filter.task_id, agent: filter.agent };
+        let json = serde_json::to_string(&request)? + "\n";
+        writer.write_all(json.as_bytes()).await?;
+        
+        let mut reader = BufReader::new(reader);
+        let mut line = String::new();
+        let mut subscribed = false;
+        loop {
+            line.clear();
+            if reader.read_line(&mut line).await? == 0 {
+                return Ok(());
+            }
+            let item = match serde_json::from_str::<Response>(&line)? {
+                Response::Error { message } if !subscribed => return Err(DaemonError::Refused(message)),
+                Response::Error { message } => Err(message),
+                Response::Ok { .. } if !subscribed => {
+                    subscribed = true;
+                    continue;
+                }
+                Response::Ok { data } => Ok(serde_json::from_value(data)?),
+            };
+            if !on_event(item) {
+                return Ok(());
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -484,4 +1444,82 @@ mod tests {
         assert!(json.contains("ok"));
         assert!(json.contains("hello"));
     }
+    
+    #[cfg(unix)]
+    #[test]
+    fn test_pid_lock_is_exclusive_and_recovers_stale() {
+        let dir = tempfile::tempdir().unwrap();
+        
+        let lock = PidLock::acquire(dir.path()).unwrap();
+        match PidLock::acquire(dir.path()) {
+            Err(DaemonError::AlreadyRunning(pid)) => assert_eq!(pid, std::process::id()),
+            other => panic!("expected AlreadyRunning, got {:?}", other.map(|_| ())),
+        }
+        drop(lock);
+        assert!(!dir.path().join(PID_FILE).exists());
+        
+        // A PID file naming a process that has exited is taken over
+        let mut child = std::process::Command::new("true").spawn().unwrap();
+        let dead = child.id();
+        child.wait().unwrap();
+        std::fs::write(dir.path().join(PID_FILE), dead.to_string()).unwrap();
+        assert_eq!(check_health(dir.path()).state, Liveness::Stale);
+        
+        let _lock = PidLock::acquire(dir.path()).unwrap();
+        assert_eq!(read_pid(&dir.path().join(PID_FILE)), Some(std::process::id()));
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn test_health_detects_stale_socket() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(check_health(dir.path()).state, Liveness::NotRunning);
+        
+        // Dropping a listener leaves its socket file behind, like a crash
+        drop(std::os::unix::net::UnixListener::bind(dir.path().join(SOCKET_FILE)).unwrap());
+        let health = check_health(dir.path());
+        assert_eq!(health.state, Liveness::Stale);
+        assert!(health.status.is_none());
+        assert!(!DaemonClient::new(dir.path().join(SOCKET_FILE)).is_running());
+    }
+    
+    #[test]
+    fn test_complete_checks_the_holder() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = WorkStore::in_memory().unwrap();
+        let task = store.create(crate::CreateTask { title: "Rotate the keys".into(), ..Default::default() }).unwrap();
+        let log = Arc::new(DaemonLog::open(dir.path(), &Default::default()).unwrap());
+        let state = Arc::new(Mutex::new(DaemonState::new(store, BudgetConfig::default(), None, log)));
+        let complete = |agent: &str| Request::Complete { id: task.id.clone(), agent: Some(agent.into()), evidence: None };
+        
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        runtime.block_on(async {
+            let claim = Request::Claim { id: task.id.clone(), agent: "agent-b".into() };
+            assert!(matches!(handle_request(claim, &state).await, Response::Ok { .. }));
+            // An agent whose lease was taken over can't finish the task
+            assert!(matches!(handle_request(complete("agent-a"), &state).await, Response::Error { .. }));
+            assert!(matches!(handle_request(complete("agent-b"), &state).await, Response::Ok { .. }));
+        });
+        
+        // Older clients that don't name the agent still parse
+        let request: Request = serde_json::from_str(r#"{"cmd": "complete", "id": "lm-1"}"#).unwrap();
+        assert!(matches!(request, Request::Complete { agent: None, .. }));
+    }
+    
+    #[test]
+    fn test_http_needs_a_token_off_loopback() {
+        let dir = tempfile::tempdir().unwrap();
+        let loom = crate::Loom::init(dir.path()).unwrap();
+        let mut daemon = Daemon::new(loom.root()).unwrap().with_loom(loom);
+        daemon.config.http.token_env = "LOOM_TEST_UNSET_HTTP_TOKEN".to_string();
+        
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+        runtime.block_on(async {
+            match daemon.start_http("0.0.0.0:0").await {
+                Err(DaemonError::UnprotectedHttp { env, .. }) => assert_eq!(env, "LOOM_TEST_UNSET_HTTP_TOKEN"),
+                other => panic!("expected UnprotectedHttp, got {:?}", other.map(|_| ())),
+            }
+            daemon.start_http("127.0.0.1:0").await.unwrap().abort();
+        });
+    }
 }
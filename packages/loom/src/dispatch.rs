@@ -3,7 +3,7 @@
 //! Multi-CLI agent routing for task execution.
 //! Supports Claude, Codex, Gemini CLI, and Cursor.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::pin::Pin;
 use std::process::Stdio;
@@ -12,7 +12,8 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use tokio::process::Command;
 
-use crate::work::Task;
+use crate::routing::RoutingDecision;
+use crate::work::{Task, TaskKind};
 
 /// Boxed future for async agent execution
 pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
@@ -102,6 +103,81 @@ pub struct RoutingConfig {
     pub labels: HashMap<String, String>,
 }
 
+/// Daemon auto-dispatch: ready tasks routed with enough confidence are
+/// handed to the orchestrator without waiting for an agent to claim them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutoDispatchConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Routing confidence (0-1) a task needs to be dispatched
+    #[serde(default = "default_min_confidence")]
+    pub min_confidence: f64,
+    /// Only tasks with one of these labels are dispatched (any task when empty)
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Confidence needed for tasks with a label, in place of
+    /// `min_confidence`; the strictest of a task's labels applies
+    #[serde(default)]
+    pub thresholds: HashMap<String, f64>,
+    /// Ask a human to approve tasks that route below their threshold,
+    /// rather than leaving them for agents to claim
+    #[serde(default)]
+    pub approvals: bool,
+    /// Run each dispatched task in a git worktree of its own, on branch
+    /// `loom/<task-id>`
+    #[serde(default)]
+    pub worktrees: bool,
+    /// Auto-dispatched runs at once, across all agents
+    #[serde(default = "default_auto_concurrent")]
+    pub max_concurrent: usize,
+    /// Seconds between checks for ready tasks
+    #[serde(default = "default_auto_interval")]
+    pub interval_secs: u64,
+}
+
+fn default_min_confidence() -> f64 {
+    0.8
+}
+
+fn default_auto_concurrent() -> usize {
+    1
+}
+
+fn default_auto_interval() -> u64 {
+    30
+}
+
+impl AutoDispatchConfig {
+    /// Whether the task's labels let it be dispatched
+    pub fn allows(&self, task: &Task) -> bool {
+        self.labels.is_empty() || task.labels.iter().any(|l| self.labels.contains(l))
+    }
+    
+    /// Confidence the task needs: the highest threshold among its labels,
+    /// else `min_confidence`
+    pub fn threshold(&self, task: &Task) -> f64 {
+        task.labels.iter()
+            .filter_map(|l| self.thresholds.get(l).copied())
+            .reduce(f64::max)
+            .unwrap_or(self.min_confidence)
+    }
+}
+
+impl Default for AutoDispatchConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_confidence: default_min_confidence(),
+            labels: Vec::new(),
+            thresholds: HashMap::new(),
+            approvals: false,
+            worktrees: false,
+            max_concurrent: default_auto_concurrent(),
+            interval_secs: default_auto_interval(),
+        }
+    }
+}
+
 /// Full dispatch configuration
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct DispatchConfig {
@@ -109,6 +185,8 @@ pub struct DispatchConfig {
     pub agents: HashMap<String, AgentConfig>,
     #[serde(default)]
     pub routing: RoutingConfig,
+    #[serde(default)]
+    pub auto: AutoDispatchConfig,
 }
 
 impl DispatchConfig {
@@ -126,6 +204,81 @@ impl DispatchConfig {
         std::fs::write(path, content)?;
         Ok(())
     }
+    
+    /// Concurrent runs allowed for an agent (1 unless it's configured)
+    pub fn agent_limit(&self, agent: &str) -> u32 {
+        self.agents.get(agent).map(|c| c.max_concurrent).unwrap_or(1)
+    }
+}
+
+/// A ready task the daemon should dispatch now, or ask about
+#[derive(Debug, Clone)]
+pub struct AutoDispatch {
+    pub task: Task,
+    pub decision: RoutingDecision,
+    /// Confidence the task needed
+    pub threshold: f64,
+}
+
+/// What auto-dispatch makes of the ready queue
+#[derive(Debug, Clone, Default)]
+pub struct AutoDispatchPicks {
+    /// Tasks to dispatch now
+    pub dispatch: Vec<AutoDispatch>,
+    /// Tasks that routed below their threshold, to ask a human about
+    /// (only with `approvals` on)
+    pub approvals: Vec<AutoDispatch>,
+}
+
+/// Choose which ready tasks to dispatch, most urgent (then oldest) first
+///
+/// `running` maps task IDs already being worked on by the daemon to their
+/// agents. A task is picked when its labels are allowed, `route` gives it
+/// an agent with at least its threshold (or a human approved it, per
+/// `approved`), and neither the overall limit nor the agent's own
+/// `max_concurrent` is reached. Below the threshold it goes for approval
+/// when `approvals` is on. Other tasks stay ready for agents to claim
+/// themselves, as do questions, which are for humans.
+pub fn pick_auto_dispatch(
+    config: &DispatchConfig,
+    mut ready: Vec<Task>,
+    running: &HashMap<String, String>,
+    approved: &HashSet<String>,
+    mut route: impl FnMut(&Task) -> Option<RoutingDecision>,
+) -> AutoDispatchPicks {
+    // Ready tasks come newest first
+    ready.reverse();
+    ready.sort_by_key(|t| t.priority as u8);
+    
+    let mut per_agent: HashMap<String, u32> = HashMap::new();
+    for agent in running.values() {
+        *per_agent.entry(agent.clone()).or_insert(0) += 1;
+    }
+    
+    let mut picks = AutoDispatchPicks::default();
+    for task in ready {
+        if running.len() + picks.dispatch.len() >= config.auto.max_concurrent {
+            break;
+        }
+        if running.contains_key(&task.id) || task.kind == TaskKind::Question || !config.auto.allows(&task) {
+            continue;
+        }
+        let Some(decision) = route(&task) else { continue };
+        let threshold = config.auto.threshold(&task);
+        if decision.confidence < threshold && !approved.contains(&task.id) {
+            if config.auto.approvals {
+                picks.approvals.push(AutoDispatch { task, decision, threshold });
+            }
+            continue;
+        }
+        let active = per_agent.entry(decision.agent_id.clone()).or_insert(0);
+        if *active >= config.agent_limit(&decision.agent_id) {
+            continue;
+        }
+        *active += 1;
+        picks.dispatch.push(AutoDispatch { task, decision, threshold });
+    }
+    picks
 }
 
 /// Agent trait for dispatch
@@ -253,6 +406,11 @@ impl Dispatcher {
         }
     }
     
+    /// The configuration the dispatcher was built from
+    pub fn config(&self) -> &DispatchConfig {
+        &self.config
+    }
+    
     /// Get available agents
     pub fn available_agents(&self) -> Vec<&str> {
         self.agents.iter()
@@ -385,5 +543,87 @@ labels = { ui = "cursor" }
         let config: DispatchConfig = toml::from_str(toml).unwrap();
         assert_eq!(config.agents.len(), 2);
         assert_eq!(config.routing.default, Some("claude".to_string()));
+        assert!(!config.auto.enabled);
+    }
+    
+    #[test]
+    fn test_pick_auto_dispatch() {
+        let config: DispatchConfig = toml::from_str(r#"
+[agents.claude]
+path = "claude"
+max_concurrent = 2
+
+[auto]
+enabled = true
+min_confidence = 0.8
+max_concurrent = 3
+"#).unwrap();
+        
+        let mut store = crate::work::WorkStore::in_memory().unwrap();
+        let mut create = |title: &str, priority: crate::work::Priority| {
+            store.create(crate::work::CreateTask { title: title.to_string(), priority, ..Default::default() }).unwrap();
+        };
+        create("running", crate::work::Priority::High);
+        create("old", crate::work::Priority::Normal);
+        create("urgent", crate::work::Priority::Critical);
+        create("gem", crate::work::Priority::Normal);
+        create("unsure", crate::work::Priority::Normal);
+        create("new", crate::work::Priority::Normal);
+        // Questions wait for a human however confidently they'd route
+        store.create(crate::work::CreateTask {
+            title: "question".to_string(),
+            kind: TaskKind::Question,
+            priority: crate::work::Priority::Critical,
+            ..Default::default()
+        }).unwrap();
+        let ready = store.ready().unwrap();
+        assert!(ready.iter().any(|t| t.kind == TaskKind::Question));
+        let id = |title: &str| ready.iter().find(|t| t.title == title).unwrap().id.clone();
+        let running = HashMap::from([(id("running"), "claude".to_string())]);
+        
+        let route = |task: &Task| {
+            let (agent, confidence) = match task.title.as_str() {
+                "unsure" => ("claude", 0.5),
+                "gem" => ("gemini", 0.9),
+                _ => ("claude", 0.9),
+            };
+            Some(RoutingDecision {
+                agent_id: agent.to_string(),
+                reason: String::new(),
+                estimated_cost: 0.0,
+                confidence,
+                alternatives: vec![],
+                warnings: vec![],
+                context_budget: None,
+            })
+        };
+        let titles = |picks: &[AutoDispatch]| picks.iter().map(|p| p.task.title.clone()).collect::<Vec<_>>();
+        
+        // claude has one slot left after lm-running, so lm-old waits; the
+        // low-confidence task is left for agents to claim
+        let picks = pick_auto_dispatch(&config, ready.clone(), &running, &HashSet::new(), route);
+        assert_eq!(titles(&picks.dispatch), vec!["urgent", "gem"]);
+        assert!(picks.approvals.is_empty());
+        
+        // With approvals on, the unsure task is asked about; once approved it
+        // goes out despite its confidence
+        let mut config = config;
+        config.auto.approvals = true;
+        config.auto.max_concurrent = 10;
+        config.agents.get_mut("claude").unwrap().max_concurrent = 10;
+        let picks = pick_auto_dispatch(&config, ready.clone(), &running, &HashSet::new(), route);
+        assert_eq!(titles(&picks.approvals), vec!["unsure"]);
+        let picks = pick_auto_dispatch(&config, ready.clone(), &running, &HashSet::from([id("unsure")]), route);
+        assert!(titles(&picks.dispatch).contains(&"unsure".to_string()));
+        
+        // Per-label thresholds and allowed labels
+        let labelled = |labels: &[&str]| Task { labels: labels.iter().map(|l| l.to_string()).collect(), ..ready[0].clone() };
+        config.auto.thresholds = HashMap::from([("infra".to_string(), 0.95), ("docs".to_string(), 0.6)]);
+        assert_eq!(config.auto.threshold(&labelled(&["docs"])), 0.6);
+        assert_eq!(config.auto.threshold(&labelled(&["docs", "infra"])), 0.95);
+        assert_eq!(config.auto.threshold(&labelled(&[])), 0.8);
+        config.auto.labels = vec!["docs".to_string()];
+        assert!(config.auto.allows(&labelled(&["docs"])));
+        assert!(!config.auto.allows(&labelled(&["infra"])));
     }
 }
@@ -0,0 +1,206 @@
+//! Event Stream
+//!
+//! Live dashboards and reactive agents subscribe to the daemon instead of
+//! polling it. Task transitions (work.db `task_history`) and session events
+//! (memory.db `session_events`) are both recorded by triggers, so changes
+//! made by any process show up; the daemon follows the two tables and sends
+//! each new entry to every subscriber on its socket as one JSON line.
+//!
+//! ```text
+//! → {"cmd": "subscribe", "events": ["completed", "session.checkpoint"], "agent": "cursor"}
+//! ← {"status": "ok", "data": {"subscribed": true}}
+//! ← {"status": "ok", "data": {"source": "task", "event": "completed", "task_id": "lm-a1b2", ...}}
+//! ```
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::memory::{MemoryError, MemoryStore, SessionEvent};
+use crate::work::{HistoryEntry, WorkError, WorkStore};
+
+/// Entries read from each table per poll
+const BATCH: usize = 500;
+
+/// Which history an event comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventSource {
+    Task,
+    Session,
+}
+
+impl EventSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventSource::Task => "task",
+            EventSource::Session => "session",
+        }
+    }
+}
+
+/// Something that happened to a task or session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Event {
+    pub source: EventSource,
+    /// `claimed`, `completed`, ... for tasks (see `HistoryEntry`);
+    /// `started`, `checkpoint`, ... for sessions (see `SessionEvent`)
+    pub event: String,
+    /// Position in its source's history
+    pub seq: i64,
+    pub task_id: String,
+    pub session_id: Option<String>,
+    pub agent: Option<String>,
+    /// Old and new status or priority, for task events
+    pub from: Option<String>,
+    pub to: Option<String>,
+    /// Checkpoint summary, for session checkpoints
+    pub detail: Option<String>,
+    pub at: DateTime<Utc>,
+}
+
+impl From<HistoryEntry> for Event {
+    fn from(entry: HistoryEntry) -> Self {
+        Self {
+            source: EventSource::Task,
+            event: entry.event,
+            seq: entry.seq,
+            task_id: entry.task_id,
+            session_id: None,
+            agent: entry.agent,
+            from: entry.from,
+            to: entry.to,
+            detail: None,
+            at: entry.at,
+        }
+    }
+}
+
+impl From<SessionEvent> for Event {
+    fn from(event: SessionEvent) -> Self {
+        Self {
+            source: EventSource::Session,
+            event: event.event,
+            seq: event.seq,
+            task_id: event.task_id,
+            session_id: Some(event.session_id),
+            agent: Some(event.agent_id),
+            from: None,
+            to: None,
+            detail: event.detail,
+            at: event.at,
+        }
+    }
+}
+
+/// Which events a subscriber wants (everything, by default)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EventFilter {
+    /// Event names, bare (`completed`, for tasks and sessions alike) or
+    /// qualified by source (`task.completed`, `session.checkpoint`)
+    #[serde(default)]
+    pub events: Vec<String>,
+    #[serde(default)]
+    pub task_id: Option<String>,
+    #[serde(default)]
+    pub agent: Option<String>,
+}
+
+impl EventFilter {
+    pub fn matches(&self, event: &Event) -> bool {
+        let named = self.events.is_empty()
+            || self.events.iter().any(|name| match name.split_once('.') {
+                Some((source, name)) => source == event.source.as_str() && name == event.event,
+                None => *name == event.event,
+            });
+        named
+            && self.task_id.as_ref().is_none_or(|id| *id == event.task_id)
+            && self.agent.as_ref().is_none_or(|agent| event.agent.as_ref() == Some(agent))
+    }
+}
+
+/// How far a reader has got through both histories
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventCursor {
+    pub task_seq: i64,
+    pub session_seq: i64,
+}
+
+impl EventCursor {
+    /// A cursor past everything recorded so far
+    pub fn latest(work: &WorkStore, memory: &MemoryStore) -> Result<Self, EventsError> {
+        Ok(Self {
+            task_seq: work.latest_history_seq()?,
+            session_seq: memory.latest_session_event_seq()?,
+        })
+    }
+    
+    /// Events recorded since the cursor, oldest first, advancing it
+    pub fn poll(&mut self, work: &WorkStore, memory: &MemoryStore) -> Result<Vec<Event>, EventsError> {
+        let tasks = work.history_since(self.task_seq, BATCH)?;
+        let sessions = memory.session_events_since(self.session_seq, BATCH)?;
+        if let Some(last) = tasks.last() {
+            self.task_seq = last.seq;
+        }
+        if let Some(last) = sessions.last() {
+            self.session_seq = last.seq;
+        }
+        
+        let mut events: Vec<Event> = tasks.into_iter().map(Event::from)
+            .chain(sessions.into_iter().map(Event::from))
+            .collect();
+        // Stable, so each source keeps its own order on ties
+        events.sort_by_key(|e| e.at);
+        Ok(events)
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum EventsError {
+    #[error("Work store error: {0}")]
+    Work(#[from] WorkError),
+    
+    #[error("Memory error: {0}")]
+    Memory(#[from] MemoryError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::work::CreateTask;
+    
+    #[test]
+    fn test_poll_merges_task_and_session_events() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut work = WorkStore::open(dir.path().join("work.db")).unwrap();
+        let mut memory = MemoryStore::open(dir.path().join("memory.db")).unwrap();
+        
+        work.create(CreateTask { title: "Old news".to_string(), ..Default::default() }).unwrap();
+        let mut cursor = EventCursor::latest(&work, &memory).unwrap();
+        assert!(cursor.poll(&work, &memory).unwrap().is_empty());
+        
+        let task = work.create(CreateTask { title: "Live".to_string(), ..Default::default() }).unwrap();
+        work.claim(&task.id, "cursor").unwrap();
+        // Timestamps are to the millisecond; keep the two sources apart
+        let tick = || std::thread::sleep(std::time::Duration::from_millis(5));
+        tick();
+        let session = memory.start_session("cursor", &task.id, None, None).unwrap();
+        memory.create_checkpoint(&session.id, "Parser done", None).unwrap();
+        memory.end_session(&session.id, crate::memory::SessionStatus::Completed).unwrap();
+        tick();
+        work.complete(&task.id, Some("merged")).unwrap();
+        
+        let events = cursor.poll(&work, &memory).unwrap();
+        let names: Vec<String> = events.iter().map(|e| format!("{}.{}", e.source.as_str(), e.event)).collect();
+        assert_eq!(names, vec![
+            "task.created", "task.claimed", "session.started", "session.checkpoint", "session.completed", "task.completed",
+        ]);
+        assert_eq!(events[3].detail.as_deref(), Some("Parser done"));
+        assert!(cursor.poll(&work, &memory).unwrap().is_empty());
+        
+        let filter = EventFilter { events: vec!["completed".to_string()], ..Default::default() };
+        assert_eq!(events.iter().filter(|e| filter.matches(e)).count(), 2);
+        let filter = EventFilter { events: vec!["task.completed".to_string()], agent: Some("cursor".to_string()), ..Default::default() };
+        assert_eq!(events.iter().filter(|e| filter.matches(e)).count(), 1);
+        assert_eq!(events.iter().filter(|e| EventFilter { agent: Some("codex".to_string()), ..Default::default() }.matches(e)).count(), 0);
+    }
+}
@@ -0,0 +1,114 @@
+//! Evidence Log
+//!
+//! Tamper-evident record of completion evidence. When `[evidence] sign` is
+//! enabled, every completion appends an entry to the `evidence_log` table in
+//! work.db. Each entry's hash covers the previous entry's hash, so editing or
+//! deleting any entry breaks the chain from that point on. If a signing key is
+//! available, each hash is additionally HMAC-signed so the chain can't simply
+//! be recomputed after an edit.
+//!
+//! `WorkStore::verify_evidence_log()` walks the chain and cross-checks the
+//! evidence currently stored on each task against its latest log entry.
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Hash of the (virtual) entry before the first one
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// A single entry in the evidence log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvidenceEntry {
+    /// Position in the log (monotonic)
+    pub seq: i64,
+    pub task_id: String,
+    pub evidence: String,
+    pub created_at: DateTime<Utc>,
+    /// Hash of the previous entry
+    pub prev_hash: String,
+    /// SHA-256 over prev_hash, task_id, evidence and created_at
+    pub hash: String,
+    /// HMAC-SHA256 of `hash` (when a signing key was configured)
+    pub signature: Option<String>,
+}
+
+/// A problem found while verifying the log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum LogIssue {
+    /// Entry contents no longer match its hash
+    HashMismatch { seq: i64, task_id: String },
+    /// Entry doesn't point at the previous entry (deleted or reordered)
+    BrokenChain { seq: i64, expected_prev: String, found_prev: String },
+    /// Signature doesn't match the configured key
+    BadSignature { seq: i64, task_id: String },
+    /// Task evidence differs from what was logged at completion
+    EvidenceEdited { task_id: String, seq: i64 },
+}
+
+/// Result of verifying the evidence log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogVerification {
+    /// Entries checked
+    pub entries: u32,
+    /// Entries whose signature was checked against the key
+    pub signatures_checked: u32,
+    /// Hash of the last entry; anchor this externally to detect truncation
+    pub head: String,
+    pub issues: Vec<LogIssue>,
+}
+
+impl LogVerification {
+    /// No tampering detected
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// Hash an entry, chaining it to the previous one
+pub fn entry_hash(prev_hash: &str, task_id: &str, evidence: &str, created_at: &str) -> String {
+    let mut hasher = Sha256::new();
+    for part in [prev_hash, task_id, evidence, created_at] {
+        // Length-prefix each field so boundaries can't be shifted
+        hasher.update((part.len() as u64).to_be_bytes());
+        hasher.update(part.as_bytes());
+    }
+    hex::encode(hasher.finalize())
+}
+
+/// HMAC-SHA256 signature of an entry hash
+pub fn sign(key: &[u8], hash: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(hash.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Check a signature in constant time
+pub fn verify_signature(key: &[u8], hash: &str, signature: &str) -> bool {
+    let Ok(bytes) = hex::decode(signature) else {
+        return false;
+    };
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(hash.as_bytes());
+    mac.verify_slice(&bytes).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_hash_chains_and_signs() {
+        let first = entry_hash(GENESIS_HASH, "lm-1", "tests pass", "2026-01-01T00:00:00+00:00");
+        let second = entry_hash(&first, "lm-2", "deployed", "2026-01-01T00:01:00+00:00");
+        assert_ne!(first, second);
+        assert_ne!(first, entry_hash(GENESIS_HASH, "lm-1", "tests pass!", "2026-01-01T00:00:00+00:00"));
+        
+        let sig = sign(b"secret", &first);
+        assert!(verify_signature(b"secret", &first, &sig));
+        assert!(!verify_signature(b"other", &first, &sig));
+        assert!(!verify_signature(b"secret", &second, &sig));
+    }
+}
@@ -4,8 +4,10 @@
 //! Like Gas Town's polecat formulas, but more flexible.
 
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -16,6 +18,9 @@ pub enum FormulaError {
     #[error("Missing required variable: {0}")]
     MissingVariable(String),
     
+    #[error("Invalid value for variable {name}: {reason}")]
+    InvalidVariable { name: String, reason: String },
+    
     #[error("Parse error: {0}")]
     Parse(String),
     
@@ -24,6 +29,12 @@ pub enum FormulaError {
     
     #[error("TOML error: {0}")]
     Toml(#[from] toml::de::Error),
+    
+    #[error("Invalid formula pack: {0}")]
+    Pack(String),
+    
+    #[error("Failed to fetch formula pack: {0}")]
+    Fetch(String),
 }
 
 /// Quality tier for formula execution
@@ -71,14 +82,152 @@ impl QualityTier {
     }
 }
 
+/// What kind of value a formula variable takes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VariableType {
+    /// Free text (optionally matching `pattern`)
+    #[default]
+    String,
+    /// One of `options`
+    Enum,
+    /// A file or directory path inside the project: relative, without `..`
+    Path,
+    /// A finite number (optionally between `min` and `max`)
+    Number,
+}
+
+impl VariableType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VariableType::String => "string",
+            VariableType::Enum => "enum",
+            VariableType::Path => "path",
+            VariableType::Number => "number",
+        }
+    }
+}
+
 /// A variable in a formula
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// ```toml
+/// [[variables]]
+/// name = "package"
+/// description = "Package to work in"
+/// type = "enum"
+/// options = ["io", "space", "agency"]
+/// default = "io"
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Variable {
     pub name: String,
     pub description: String,
     #[serde(default)]
     pub required: bool,
     pub default: Option<String>,
+    #[serde(default, rename = "type")]
+    pub kind: VariableType,
+    /// Allowed values (enum)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<String>,
+    /// Regex the whole value has to match (string)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    /// Bounds (number)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+    /// The path has to exist (path)
+    #[serde(default)]
+    pub must_exist: bool,
+}
+
+impl Variable {
+    /// Check a value against the variable's type; paths resolve against `base`
+    pub fn check(&self, value: &str, base: &Path) -> Result<(), FormulaError> {
+        let invalid = |reason: String| FormulaError::InvalidVariable { name: self.name.clone(), reason };
+        
+        match self.kind {
+            VariableType::String => {
+                if let Some(pattern) = &self.pattern {
+                    let regex = regex::Regex::new(&format!("^(?:{})$", pattern))
+                        .map_err(|e| invalid(format!("bad pattern in formula: {}", e)))?;
+                    if !regex.is_match(value) {
+                        return Err(invalid(format!("'{}' doesn't match {}", value, pattern)));
+                    }
+                }
+            }
+            VariableType::Enum => {
+                if !self.options.iter().any(|o| o == value) {
+                    return Err(invalid(format!("'{}' is not one of {}", value, self.options.join(", "))));
+                }
+            }
+            VariableType::Path => {
+                if value.trim().is_empty() {
+                    return Err(invalid("path is empty".to_string()));
+                }
+                let path = Path::new(value);
+                if path.components().any(|c| !matches!(c, std::path::Component::Normal(_) | std::path::Component::CurDir)) {
+                    return Err(invalid(format!("{} is outside the project (use a relative path without '..')", value)));
+                }
+                if self.must_exist && !base.join(value).exists() {
+                    return Err(invalid(format!("{} does not exist", value)));
+                }
+            }
+            VariableType::Number => {
+                if self.min.is_some_and(|min| !min.is_finite()) || self.max.is_some_and(|max| !max.is_finite()) {
+                    return Err(invalid("bounds in formula must be finite numbers".to_string()));
+                }
+                let number: f64 = value.trim().parse()
+                    .ok()
+                    .filter(|n: &f64| n.is_finite())
+                    .ok_or_else(|| invalid(format!("'{}' is not a number", value)))?;
+                if self.min.is_some_and(|min| number < min) || self.max.is_some_and(|max| number > max) {
+                    let bound = |b: Option<f64>| b.map_or("…".to_string(), |b| b.to_string());
+                    return Err(invalid(format!("{} is outside {}..{}", value, bound(self.min), bound(self.max))));
+                }
+            }
+        }
+        Ok(())
+    }
+    
+    /// JSON Schema for the variable, for clients rendering a form
+    pub fn schema(&self) -> serde_json::Value {
+        let mut schema = serde_json::json!({ "description": self.description });
+        match self.kind {
+            VariableType::String => {
+                schema["type"] = "string".into();
+                if let Some(pattern) = &self.pattern {
+                    schema["pattern"] = format!("^(?:{})$", pattern).into();
+                }
+            }
+            VariableType::Enum => {
+                schema["type"] = "string".into();
+                schema["enum"] = self.options.clone().into();
+            }
+            VariableType::Path => {
+                schema["type"] = "string".into();
+                schema["format"] = "path".into();
+            }
+            VariableType::Number => {
+                schema["type"] = "number".into();
+                if let Some(min) = self.min {
+                    schema["minimum"] = min.into();
+                }
+                if let Some(max) = self.max {
+                    schema["maximum"] = max.into();
+                }
+            }
+        }
+        if let Some(default) = &self.default {
+            schema["default"] = match self.kind {
+                VariableType::Number => default.parse::<f64>().map_or_else(|_| default.clone().into(), Into::into),
+                _ => default.clone().into(),
+            };
+        }
+        schema
+    }
 }
 
 /// A step in a formula
@@ -170,14 +319,52 @@ impl Formula {
     
     /// Validate that all required variables are provided
     pub fn validate_variables(&self, vars: &HashMap<String, String>) -> Result<(), FormulaError> {
+        self.validate_variables_in(vars, Path::new("."))
+    }
+    
+    /// Validate variables before anything is created: required ones are
+    /// provided, no unknown ones are, and every value (defaults included)
+    /// fits its type. Paths resolve against `base`.
+    pub fn validate_variables_in(&self, vars: &HashMap<String, String>, base: &Path) -> Result<(), FormulaError> {
+        let mut unknown: Vec<&String> = vars.keys()
+            .filter(|name| !self.variables.iter().any(|v| &v.name == *name))
+            .collect();
+        if !unknown.is_empty() {
+            unknown.sort();
+            let known: Vec<&str> = self.variables.iter().map(|v| v.name.as_str()).collect();
+            return Err(FormulaError::InvalidVariable {
+                name: unknown[0].clone(),
+                reason: format!("{} takes no such variable (it takes: {})", self.name, if known.is_empty() { "none".to_string() } else { known.join(", ") }),
+            });
+        }
+        
         for var in &self.variables {
-            if var.required && !vars.contains_key(&var.name) && var.default.is_none() {
-                return Err(FormulaError::MissingVariable(var.name.clone()));
+            match vars.get(&var.name).or(var.default.as_ref()) {
+                Some(value) => var.check(value, base)?,
+                None if var.required => return Err(FormulaError::MissingVariable(var.name.clone())),
+                None => {}
             }
         }
         Ok(())
     }
     
+    /// JSON Schema for the formula's variables
+    pub fn variables_schema(&self) -> serde_json::Value {
+        let properties: serde_json::Map<String, serde_json::Value> = self.variables.iter()
+            .map(|v| (v.name.clone(), v.schema()))
+            .collect();
+        let required: Vec<&str> = self.variables.iter()
+            .filter(|v| v.required && v.default.is_none())
+            .map(|v| v.name.as_str())
+            .collect();
+        serde_json::json!({
+            "type": "object",
+            "properties": properties,
+            "required": required,
+            "additionalProperties": false,
+        })
+    }
+    
     /// Expand a template with variables
     pub fn expand_template(&self, template: &str, vars: &HashMap<String, String>) -> String {
         let mut result = template.to_string();
@@ -220,6 +407,7 @@ impl Formula {
                     description: "The task ID to work on".to_string(),
                     required: true,
                     default: None,
+                    ..Default::default()
                 },
             ],
             steps: vec![
@@ -283,12 +471,14 @@ impl Formula {
                     description: "Name of the feature".to_string(),
                     required: true,
                     default: None,
+                    ..Default::default()
                 },
                 Variable {
                     name: "package".to_string(),
                     description: "Package to implement in".to_string(),
                     required: true,
                     default: None,
+                    ..Default::default()
                 },
             ],
             steps: vec![
@@ -369,6 +559,7 @@ impl Formula {
                     description: "Description of the bug".to_string(),
                     required: true,
                     default: None,
+                    ..Default::default()
                 },
             ],
             steps: vec![
@@ -447,12 +638,14 @@ impl Formula {
                     description: "File or directory to refactor".to_string(),
                     required: true,
                     default: None,
+                    ..Default::default()
                 },
                 Variable {
                     name: "goal".to_string(),
                     description: "What to improve".to_string(),
                     required: true,
                     default: None,
+                    ..Default::default()
                 },
             ],
             steps: vec![
@@ -525,12 +718,15 @@ impl Formula {
 /// Formula registry
 pub struct FormulaRegistry {
     formulas: HashMap<String, Formula>,
+    /// Where formulas installed from packs came from, by formula name
+    provenance: HashMap<String, FormulaProvenance>,
 }
 
 impl FormulaRegistry {
     pub fn new() -> Self {
         let mut registry = Self {
             formulas: HashMap::new(),
+            provenance: HashMap::new(),
         };
         
         // Register built-in formulas
@@ -557,7 +753,7 @@ impl FormulaRegistry {
         registry
     }
     
-    /// Load formulas from a directory
+    /// Load formulas from a directory, including packs installed into its subdirectories
     pub fn load_from_dir(&mut self, dir: impl AsRef<Path>) -> Result<usize, FormulaError> {
         let dir = dir.as_ref();
         let mut count = 0;
@@ -570,7 +766,11 @@ impl FormulaRegistry {
             let entry = entry?;
             let path = entry.path();
             
-            if path.extension().map(|e| e == "toml").unwrap_or(false) {
+            // Hidden directories are installs still in progress
+            let hidden = entry.file_name().to_string_lossy().starts_with('.');
+            if path.is_dir() && !hidden && path.join(INSTALLED_FILE).exists() {
+                count += self.load_pack_dir(&path)?;
+            } else if path.extension().map(|e| e == "toml").unwrap_or(false) {
                 if let Ok(formula) = Formula::from_file(&path) {
                     self.register(formula);
                     count += 1;
@@ -591,10 +791,331 @@ impl FormulaRegistry {
         self.formulas.get(name)
     }
     
+    /// Preview running a formula: its task tree, where each step would be
+    /// routed among `profiles`, and the estimated cost. Nothing is written.
+    pub fn plan(
+        &self,
+        name: &str,
+        vars: &HashMap<String, String>,
+        profiles: &[crate::agents::AgentProfile],
+    ) -> Result<crate::workflow::FormulaPlan, FormulaError> {
+        let formula = self.get(name).ok_or_else(|| FormulaError::NotFound(name.to_string()))?;
+        formula.validate_variables(vars)?;
+        Ok(crate::workflow::plan(formula, vars, profiles))
+    }
+    
     /// List all formula names
     pub fn list(&self) -> Vec<&str> {
         self.formulas.keys().map(|s| s.as_str()).collect()
     }
+    
+    /// Where a formula was installed from (None for built-in and hand-written formulas)
+    pub fn provenance(&self, name: &str) -> Option<&FormulaProvenance> {
+        self.provenance.get(name)
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Formula Packs
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Manifest at the root of a pack
+const MANIFEST_FILE: &str = "pack.toml";
+
+/// Install record written next to an installed pack's formulas
+const INSTALLED_FILE: &str = "installed.toml";
+
+/// A pack's `pack.toml`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackManifest {
+    /// Pack name; also the directory it installs into
+    pub name: String,
+    /// Version, compared numerically component by component ("1.10.0" > "1.9.2")
+    pub version: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Formula files in the pack (default: every other .toml at the pack root)
+    #[serde(default)]
+    pub formulas: Vec<String>,
+}
+
+/// What was installed, from where, and the checksum of each formula file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstalledPack {
+    pub name: String,
+    pub version: String,
+    /// Local path or HTTPS URL the pack was installed from
+    pub source: String,
+    pub installed_at: DateTime<Utc>,
+    /// Formula name → sha256 of its TOML file
+    pub formulas: HashMap<String, String>,
+}
+
+/// Where an installed formula came from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FormulaProvenance {
+    pub pack: String,
+    pub version: String,
+    pub source: String,
+    pub installed_at: DateTime<Utc>,
+    pub sha256: String,
+}
+
+/// An installed pack compared against its source
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackUpdate {
+    pub pack: String,
+    pub source: String,
+    pub installed: String,
+    pub available: String,
+}
+
+impl PackUpdate {
+    pub fn is_newer(&self) -> bool {
+        version_newer(&self.available, &self.installed)
+    }
+}
+
+impl FormulaRegistry {
+    /// Install a formula pack into `dir` (normally `.loom/formulas`) and register its formulas.
+    ///
+    /// `source` is a local directory, a local `.tar`/`.tar.gz`/`.tgz`/`.zip`, or an
+    /// `https://` URL to one of those archives. Every formula is parsed before anything
+    /// is written, so a broken pack leaves the previous version in place.
+    pub fn install(&mut self, source: &str, dir: impl AsRef<Path>) -> Result<InstalledPack, FormulaError> {
+        // Record local sources absolutely so update checks work from anywhere
+        let source = match std::fs::canonicalize(source) {
+            Ok(path) => path.to_string_lossy().to_string(),
+            Err(_) => source.to_string(),
+        };
+        let source = source.as_str();
+        let staging = Staging::fetch(source)?;
+        let (root, manifest) = staging.manifest()?;
+        
+        if !is_plain_name(&manifest.name) {
+            return Err(FormulaError::Pack(format!("pack name '{}' must be a plain directory name", manifest.name)));
+        }
+        
+        let files = if manifest.formulas.is_empty() {
+            let mut files: Vec<String> = std::fs::read_dir(&root)?
+                .filter_map(|e| e.ok())
+                .map(|e| e.file_name().to_string_lossy().to_string())
+                .filter(|f| f.ends_with(".toml") && f != MANIFEST_FILE)
+                .collect();
+            files.sort();
+            files
+        } else {
+            manifest.formulas.clone()
+        };
+        
+        let mut formulas = Vec::new();
+        for file in &files {
+            if !is_plain_name(file) {
+                return Err(FormulaError::Pack(format!("formula path '{}' must be a file at the pack root", file)));
+            }
+            let content = std::fs::read_to_string(root.join(file))
+                .map_err(|e| FormulaError::Pack(format!("{}: {}", file, e)))?;
+            let formula = Formula::from_toml(&content)
+                .map_err(|e| FormulaError::Pack(format!("{}: {}", file, e)))?;
+            formulas.push((file.clone(), content, formula));
+        }
+        if formulas.is_empty() {
+            return Err(FormulaError::Pack("pack contains no formulas".to_string()));
+        }
+        
+        let installed = InstalledPack {
+            name: manifest.name.clone(),
+            version: manifest.version.clone(),
+            source: source.to_string(),
+            installed_at: Utc::now(),
+            formulas: formulas.iter()
+                .map(|(_, content, formula)| (formula.name.clone(), sha256_hex(content)))
+                .collect(),
+        };
+        
+        // Write next to the target and swap in, replacing any previous version
+        let target = dir.as_ref().join(&manifest.name);
+        let temp = dir.as_ref().join(format!(".{}.installing", manifest.name));
+        if temp.exists() {
+            std::fs::remove_dir_all(&temp)?;
+        }
+        std::fs::create_dir_all(&temp)?;
+        for (file, content, _) in &formulas {
+            std::fs::write(temp.join(file), content)?;
+        }
+        std::fs::copy(root.join(MANIFEST_FILE), temp.join(MANIFEST_FILE))?;
+        std::fs::write(temp.join(INSTALLED_FILE), toml::to_string_pretty(&installed)
+            .map_err(|e| FormulaError::Pack(e.to_string()))?)?;
+        if target.exists() {
+            std::fs::remove_dir_all(&target)?;
+        }
+        std::fs::rename(&temp, &target)?;
+        
+        for (_, _, formula) in formulas {
+            self.register_from_pack(formula, &installed);
+        }
+        Ok(installed)
+    }
+    
+    /// Packs installed in `dir`
+    pub fn installed_packs(dir: impl AsRef<Path>) -> Result<Vec<InstalledPack>, FormulaError> {
+        let dir = dir.as_ref();
+        let mut packs = Vec::new();
+        if !dir.exists() {
+            return Ok(packs);
+        }
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path().join(INSTALLED_FILE);
+            if path.exists() {
+                packs.push(toml::from_str(&std::fs::read_to_string(path)?)?);
+            }
+        }
+        packs.sort_by(|a: &InstalledPack, b| a.name.cmp(&b.name));
+        Ok(packs)
+    }
+    
+    /// Re-fetch each installed pack's source and report the version it now offers.
+    /// Sources that can't be reached are skipped.
+    pub fn check_updates(dir: impl AsRef<Path>) -> Result<Vec<PackUpdate>, FormulaError> {
+        let mut updates = Vec::new();
+        for pack in Self::installed_packs(dir)? {
+            let Ok(staging) = Staging::fetch(&pack.source) else { continue };
+            let Ok((_, manifest)) = staging.manifest() else { continue };
+            updates.push(PackUpdate {
+                pack: pack.name,
+                source: pack.source,
+                installed: pack.version,
+                available: manifest.version,
+            });
+        }
+        Ok(updates)
+    }
+    
+    fn load_pack_dir(&mut self, pack_dir: &Path) -> Result<usize, FormulaError> {
+        let installed: InstalledPack = toml::from_str(&std::fs::read_to_string(pack_dir.join(INSTALLED_FILE))?)?;
+        let mut count = 0;
+        for entry in std::fs::read_dir(pack_dir)? {
+            let path = entry?.path();
+            let is_formula = path.extension().map(|e| e == "toml").unwrap_or(false)
+                && path.file_name().map(|f| f != MANIFEST_FILE && f != INSTALLED_FILE).unwrap_or(false);
+            if is_formula {
+                if let Ok(formula) = Formula::from_file(&path) {
+                    self.register_from_pack(formula, &installed);
+                    count += 1;
+                }
+            }
+        }
+        Ok(count)
+    }
+    
+    fn register_from_pack(&mut self, formula: Formula, pack: &InstalledPack) {
+        self.provenance.insert(formula.name.clone(), FormulaProvenance {
+            pack: pack.name.clone(),
+            version: pack.version.clone(),
+            source: pack.source.clone(),
+            installed_at: pack.installed_at,
+            sha256: pack.formulas.get(&formula.name).cloned().unwrap_or_default(),
+        });
+        self.register(formula);
+    }
+}
+
+/// A pack fetched or extracted into a scratch directory (removed on drop)
+struct Staging {
+    /// Local directory sources are read in place
+    root: PathBuf,
+    scratch: Option<PathBuf>,
+}
+
+impl Staging {
+    fn fetch(source: &str) -> Result<Self, FormulaError> {
+        if source.starts_with("http://") {
+            return Err(FormulaError::Fetch("only https:// URLs are supported".to_string()));
+        }
+        
+        let local = Path::new(source);
+        if local.is_dir() {
+            return Ok(Self { root: local.to_path_buf(), scratch: None });
+        }
+        
+        let scratch = std::env::temp_dir().join(format!("loom-pack-{}", &uuid::Uuid::new_v4().to_string()[..8]));
+        std::fs::create_dir_all(&scratch)?;
+        let staging = Self { root: scratch.join("pack"), scratch: Some(scratch.clone()) };
+        std::fs::create_dir_all(&staging.root)?;
+        
+        // Archive type comes from the name, ignoring any query string
+        let name = source.split(['?', '#']).next().unwrap_or(source).to_lowercase();
+        let archive = if source.starts_with("https://") {
+            let file = scratch.join("download");
+            crate::net::download(source, &file).map_err(FormulaError::Fetch)?;
+            file
+        } else if local.is_file() {
+            local.to_path_buf()
+        } else {
+            return Err(FormulaError::Fetch(format!("{} is not a directory, archive, or https:// URL", source)));
+        };
+        
+        let file = std::fs::File::open(&archive)?;
+        let unpacked = if name.ends_with(".zip") {
+            zip::ZipArchive::new(file).and_then(|mut zip| zip.extract(&staging.root)).map_err(|e| e.to_string())
+        } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            tar::Archive::new(flate2::read::GzDecoder::new(file)).unpack(&staging.root).map_err(|e| e.to_string())
+        } else if name.ends_with(".tar") {
+            tar::Archive::new(file).unpack(&staging.root).map_err(|e| e.to_string())
+        } else {
+            return Err(FormulaError::Fetch(format!("{} is not a .tar, .tar.gz, .tgz, or .zip archive", source)));
+        };
+        // Both refuse entries that would land outside the staging directory
+        unpacked.map_err(|e| FormulaError::Fetch(format!("Couldn't unpack {}: {}", source, e)))?;
+        
+        Ok(staging)
+    }
+    
+    /// The pack root (archives often wrap it in one top-level directory) and its manifest
+    fn manifest(&self) -> Result<(PathBuf, PackManifest), FormulaError> {
+        let mut root = self.root.clone();
+        if !root.join(MANIFEST_FILE).exists() {
+            let dirs: Vec<PathBuf> = std::fs::read_dir(&root)?
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| p.is_dir())
+                .collect();
+            match dirs.as_slice() {
+                [only] if only.join(MANIFEST_FILE).exists() => root = only.clone(),
+                _ => return Err(FormulaError::Pack(format!("no {} found", MANIFEST_FILE))),
+            }
+        }
+        
+        let manifest: PackManifest = toml::from_str(&std::fs::read_to_string(root.join(MANIFEST_FILE))?)?;
+        Ok((root, manifest))
+    }
+}
+
+impl Drop for Staging {
+    fn drop(&mut self) {
+        if let Some(ref scratch) = self.scratch {
+            let _ = std::fs::remove_dir_all(scratch);
+        }
+    }
+}
+
+fn is_plain_name(name: &str) -> bool {
+    !name.is_empty() && !name.starts_with('.') && !name.contains(['/', '\\'])
+}
+
+fn sha256_hex(content: &str) -> String {
+    hex::encode(Sha256::digest(content.as_bytes()))
+}
+
+/// Whether version `a` is newer than `b`, comparing numeric components
+fn version_newer(a: &str, b: &str) -> bool {
+    let parse = |v: &str| -> Vec<u64> {
+        v.trim_start_matches('v')
+            .split('.')
+            .map(|part| part.chars().take_while(|c| c.is_ascii_digit()).collect::<String>().parse().unwrap_or(0))
+            .collect()
+    };
+    parse(a) > parse(b)
 }
 
 impl Default for FormulaRegistry {
@@ -646,6 +1167,117 @@ checkpoint = true
         assert!(expanded.contains("packages/io"));
     }
     
+    #[test]
+    fn test_typed_variables() {
+        let toml = r#"
+name = "typed"
+description = "Typed variables"
+
+[[variables]]
+name = "package"
+description = "Package to work in"
+type = "enum"
+options = ["io", "space"]
+default = "io"
+
+[[variables]]
+name = "retries"
+description = "How many attempts"
+type = "number"
+min = 1
+max = 5
+required = true
+
+[[variables]]
+name = "config"
+description = "Config file"
+type = "path"
+must_exist = true
+
+[[variables]]
+name = "slug"
+description = "URL slug"
+pattern = "[a-z-]+"
+"#;
+        let formula = Formula::from_toml(toml).unwrap();
+        assert_eq!(formula.variables[0].kind, VariableType::Enum);
+        
+        let base = tempfile::tempdir().unwrap();
+        std::fs::write(base.path().join("loom.toml"), "").unwrap();
+        let validate = |pairs: &[(&str, &str)]| {
+            let vars: HashMap<String, String> = pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+            formula.validate_variables_in(&vars, base.path())
+        };
+        
+        assert!(validate(&[("retries", "3"), ("config", "loom.toml"), ("slug", "dark-mode")]).is_ok());
+        assert!(matches!(validate(&[]), Err(FormulaError::MissingVariable(name)) if name == "retries"));
+        let invalid = |pairs: &[(&str, &str)]| match validate(pairs) {
+            Err(FormulaError::InvalidVariable { name, .. }) => name,
+            other => panic!("expected an invalid variable, got {:?}", other),
+        };
+        assert_eq!(invalid(&[("retries", "9")]), "retries");
+        assert_eq!(invalid(&[("retries", "two")]), "retries");
+        assert_eq!(invalid(&[("retries", "NaN")]), "retries");
+        assert_eq!(invalid(&[("retries", "inf")]), "retries");
+        assert_eq!(invalid(&[("retries", "2"), ("config", "../loom.toml")]), "config");
+        assert_eq!(invalid(&[("retries", "2"), ("config", "/etc/passwd")]), "config");
+        assert_eq!(invalid(&[("retries", "2"), ("package", "agency")]), "package");
+        assert_eq!(invalid(&[("retries", "2"), ("config", "missing.toml")]), "config");
+        assert_eq!(invalid(&[("retries", "2"), ("slug", "Dark Mode")]), "slug");
+        assert_eq!(invalid(&[("retries", "2"), ("pakage", "io")]), "pakage");
+        
+        let schema = formula.variables_schema();
+        assert_eq!(schema["required"], serde_json::json!(["retries"]));
+        assert_eq!(schema["properties"]["package"]["enum"], serde_json::json!(["io", "space"]));
+        assert_eq!(schema["properties"]["retries"]["maximum"], serde_json::json!(5.0));
+        
+        let unbounded = Formula::from_toml(&toml.replace("max = 5", "max = nan")).unwrap();
+        let vars = HashMap::from([("retries".to_string(), "3".to_string())]);
+        assert!(unbounded.validate_variables_in(&vars, base.path()).is_err());
+    }
+    
+    #[test]
+    fn test_install_pack() {
+        let source = tempfile::tempdir().unwrap();
+        let loom = tempfile::tempdir().unwrap();
+        let write = |file: &str, content: &str| std::fs::write(source.path().join(file), content).unwrap();
+        
+        write("pack.toml", "name = \"shared\"\nversion = \"1.2.0\"\n");
+        write("deploy.toml", "name = \"deploy-worker\"\ndescription = \"Ship a worker\"\n");
+        
+        let mut registry = FormulaRegistry::new();
+        let installed = registry.install(source.path().to_str().unwrap(), loom.path()).unwrap();
+        assert_eq!(installed.version, "1.2.0");
+        assert!(registry.get("deploy-worker").is_some());
+        assert_eq!(registry.provenance("deploy-worker").unwrap().pack, "shared");
+        assert!(registry.provenance("feature").is_none());
+        
+        // Reloading from disk keeps provenance
+        let mut reloaded = FormulaRegistry::new();
+        assert_eq!(reloaded.load_from_dir(loom.path()).unwrap(), 1);
+        assert_eq!(reloaded.provenance("deploy-worker").unwrap().sha256, installed.formulas["deploy-worker"]);
+        
+        write("pack.toml", "name = \"shared\"\nversion = \"1.10.0\"\n");
+        let updates = FormulaRegistry::check_updates(loom.path()).unwrap();
+        assert!(updates[0].is_newer());
+        
+        // A broken formula fails the install and leaves the old version in place
+        write("broken.toml", "description = 3");
+        assert!(matches!(registry.install(source.path().to_str().unwrap(), loom.path()), Err(FormulaError::Pack(_))));
+        assert_eq!(FormulaRegistry::installed_packs(loom.path()).unwrap()[0].version, "1.2.0");
+        
+        // Archives usually wrap the pack in one top-level directory
+        std::fs::remove_file(source.path().join("broken.toml")).unwrap();
+        let archive = loom.path().join("shared-1.10.0.tgz");
+        let mut tar = tar::Builder::new(flate2::write::GzEncoder::new(
+            std::fs::File::create(&archive).unwrap(),
+            flate2::Compression::default(),
+        ));
+        tar.append_dir_all("shared-1.10.0", source.path()).unwrap();
+        tar.into_inner().unwrap().finish().unwrap();
+        assert_eq!(registry.install(archive.to_str().unwrap(), loom.path()).unwrap().version, "1.10.0");
+    }
+    
     #[test]
     fn test_builtin_formulas() {
         let registry = FormulaRegistry::new();
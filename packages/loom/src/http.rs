@@ -0,0 +1,462 @@
+//! HTTP API
+//!
+//! Serves the MCP tools as REST + JSON so clients that don't speak MCP
+//! (dashboards, CI jobs) can create, claim, and complete tasks. Every route
+//! is a thin mapping onto `mcp::call_tool`, so both surfaces stay in step
+//! and share one `Loom` (and its SQLite stores).
+//!
+//! ```text
+//! GET  /health                  liveness (never needs auth)
+//! GET  /tools                   tool definitions
+//! POST /tools/{name}            call any tool with the JSON body as arguments
+//! GET  /tasks?status=&label=    loom_list
+//! POST /tasks                   loom_create
+//! GET  /tasks/ready             loom_ready
+//! GET  /tasks/blocked           loom_blocked
+//! GET  /tasks/{id}              loom_get
+//! POST /tasks/{id}/{action}     claim | renew | release | complete | cancel | spawn
+//! POST /tasks/{id}/plan         loom_import_plan
+//! GET  /agents/{agent}/tasks    loom_mine
+//! POST /agents/{agent}/next     loom_next_task
+//! GET  /summary?label=          loom_summary
+//! ```
+//!
+//! One request per connection; responses close it.
+//!
+//! There are no CORS headers, so browsers won't let other sites' pages call
+//! the API, and `POST`s must be `Content-Type: application/json`, which a
+//! plain form can't send. Off loopback the daemon also requires a bearer
+//! token.
+
+use std::sync::Arc;
+use std::time::Duration;
+use serde_json::{json, Map, Value};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Mutex;
+
+use crate::mcp;
+use crate::Loom;
+
+/// Largest request body accepted
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// Largest request line and headers accepted, together
+const MAX_HEAD_BYTES: u64 = 16 * 1024;
+
+/// Longest a client may take to send its request
+pub const READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A parsed HTTP request
+#[derive(Debug, Clone, Default)]
+pub struct HttpRequest {
+    pub method: String,
+    pub path: String,
+    pub query: Vec<(String, String)>,
+    /// Bearer token from the Authorization header
+    pub bearer: Option<String>,
+    /// Media type from the Content-Type header, without parameters
+    pub content_type: Option<String>,
+    pub body: Vec<u8>,
+}
+
+impl HttpRequest {
+    fn query_param(&self, name: &str) -> Option<&str> {
+        self.query.iter().find(|(k, _)| k == name).map(|(_, v)| v.as_str())
+    }
+    
+    fn is_json(&self) -> bool {
+        self.content_type.as_deref().is_some_and(|t| t.eq_ignore_ascii_case("application/json"))
+    }
+}
+
+/// Status code and JSON body
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub body: Value,
+}
+
+impl HttpResponse {
+    fn ok(body: Value) -> Self {
+        Self { status: 200, body }
+    }
+    
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Self { status, body: json!({ "error": message.into() }) }
+    }
+    
+    fn to_bytes(&self) -> Vec<u8> {
+        let body = if self.body.is_null() { String::new() } else { self.body.to_string() };
+        format!(
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            self.status,
+            reason(self.status),
+            body.len(),
+            body
+        ).into_bytes()
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        204 => "No Content",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        408 => "Request Timeout",
+        413 => "Payload Too Large",
+        415 => "Unsupported Media Type",
+        431 => "Request Header Fields Too Large",
+        _ => "Internal Server Error",
+    }
+}
+
+/// Accept connections until the listener fails, serving each on its own task
+pub async fn serve(listener: TcpListener, loom: Arc<Mutex<Loom>>, token: Option<String>) -> std::io::Result<()> {
+    let token = token.map(Arc::new);
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let loom = loom.clone();
+        let token = token.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(stream, loom, token.as_deref().map(String::as_str)).await {
+                eprintln!("HTTP connection error: {}", e);
+            }
+        });
+    }
+}
+
+async fn handle_connection(stream: TcpStream, loom: Arc<Mutex<Loom>>, token: Option<&str>) -> std::io::Result<()> {
+    let (reader, mut writer) = stream.into_split();
+    let mut reader = BufReader::new(reader);
+    
+    let response = match tokio::time::timeout(READ_TIMEOUT, read_request(&mut reader)).await {
+        Ok(Ok(Some(request))) => {
+            let mut loom = loom.lock().await;
+            handle(&mut loom, &request, token)
+        }
+        Ok(Ok(None)) => return Ok(()),
+        Ok(Err(response)) => response,
+        Err(_) => HttpResponse::error(408, format!("No complete request within {}s", READ_TIMEOUT.as_secs())),
+    };
+    
+    writer.write_all(&response.to_bytes()).await?;
+    writer.shutdown().await
+}
+
+/// Read one request; `Ok(None)` if the client closed before sending one
+pub async fn read_request<R: AsyncBufRead + Unpin>(reader: &mut R) -> Result<Option<HttpRequest>, HttpResponse> {
+    let bad_request = |e: std::io::Error| HttpResponse::error(400, e.to_string());
+    let too_large = || HttpResponse::error(431, format!("Request line and headers exceed {} bytes", MAX_HEAD_BYTES));
+    
+    // Lines are read through a cap, so a client can't stream one forever
+    let mut head = (&mut *reader).take(MAX_HEAD_BYTES);
+    let mut line = String::new();
+    if head.read_line(&mut line).await.map_err(bad_request)? == 0 {
+        return Ok(None);
+    }
+    if !line.ends_with('\n') {
+        return Err(too_large());
+    }
+    let mut parts = line.split_whitespace();
+    let (method, target) = match (parts.next(), parts.next()) {
+        (Some(method), Some(target)) => (method.to_uppercase(), target.to_string()),
+        _ => return Err(HttpResponse::error(400, "Malformed request line")),
+    };
+    
+    let mut content_length = 0;
+    let mut bearer = None;
+    let mut content_type = None;
+    loop {
+        line.clear();
+        if head.read_line(&mut line).await.map_err(bad_request)? == 0 {
+            if head.limit() == 0 {
+                return Err(too_large());
+            }
+            break;
+        }
+        if !line.ends_with('\n') && head.limit() == 0 {
+            return Err(too_large());
+        }
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        let Some((name, value)) = header.split_once(':') else { continue };
+        let value = value.trim();
+        match name.trim().to_ascii_lowercase().as_str() {
+            "content-length" => {
+                content_length = value.parse()
+                    .map_err(|_| HttpResponse::error(400, "Invalid Content-Length"))?;
+            }
+            "authorization" => {
+                bearer = value.strip_prefix("Bearer ").map(|t| t.trim().to_string());
+            }
+            "content-type" => {
+                content_type = value.split(';').next().map(|t| t.trim().to_string());
+            }
+            _ => {}
+        }
+    }
+    
+    if content_length > MAX_BODY_BYTES {
+        return Err(HttpResponse::error(413, format!("Body exceeds {} bytes", MAX_BODY_BYTES)));
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).await.map_err(bad_request)?;
+    
+    let (path, query) = target.split_once('?').unwrap_or((&target, ""));
+    Ok(Some(HttpRequest {
+        method,
+        path: path.trim_end_matches('/').to_string(),
+        query: parse_query(query),
+        bearer,
+        content_type,
+        body,
+    }))
+}
+
+/// Route a request to its tool and run it
+pub fn handle(loom: &mut Loom, request: &HttpRequest, token: Option<&str>) -> HttpResponse {
+    if request.path == "/health" {
+        return HttpResponse::ok(json!({ "status": "ok" }));
+    }
+    if let Some(token) = token {
+        if request.bearer.as_deref() != Some(token) {
+            return HttpResponse::error(401, "Missing or invalid bearer token");
+        }
+    }
+    if request.method == "GET" && request.path == "/tools" {
+        return HttpResponse::ok(json!({ "tools": mcp::list_tools() }));
+    }
+    // Anything that changes state must come from a client that set this,
+    // which a cross-site form can't
+    if request.method != "GET" && !request.is_json() {
+        return HttpResponse::error(415, "Requests other than GET must be Content-Type: application/json");
+    }
+    
+    let body = if request.body.is_empty() {
+        Map::new()
+    } else {
+        match serde_json::from_slice(&request.body) {
+            Ok(Value::Object(body)) => body,
+            Ok(_) => return HttpResponse::error(400, "Body must be a JSON object"),
+            Err(e) => return HttpResponse::error(400, format!("Invalid JSON: {}", e)),
+        }
+    };
+    
+    let Some((tool, args)) = route(request, body) else {
+        return HttpResponse::error(404, format!("No route for {} {}", request.method, request.path));
+    };
+    
+    match mcp::call_tool(loom, &tool, Value::Object(args)) {
+        Ok(result) if tool == "loom_get" && result.get("error").is_some() => HttpResponse { status: 404, body: result },
+        Ok(result) => HttpResponse::ok(result),
+        Err(e) if e.starts_with("Unknown tool") => HttpResponse::error(404, e),
+        Err(e) => HttpResponse::error(400, e),
+    }
+}
+
+/// Tool name and arguments for a request, if any route matches
+///
+/// Path segments and query parameters are merged into the body, taking
+/// precedence over it.
+fn route(request: &HttpRequest, mut args: Map<String, Value>) -> Option<(String, Map<String, Value>)> {
+    let segments: Vec<&str> = request.path.split('/').filter(|s| !s.is_empty()).collect();
+    let mut set = |key: &str, value: &str| {
+        args.insert(key.to_string(), Value::String(value.to_string()));
+    };
+    
+    let tool = match (request.method.as_str(), segments.as_slice()) {
+        ("POST", ["tools", name]) => name.to_string(),
+        ("GET", ["tasks"]) => {
+            for key in ["status", "label", "repo"] {
+                if let Some(value) = request.query_param(key) {
+                    set(key, value);
+                }
+            }
+            "loom_list".to_string()
+        }
+        ("POST", ["tasks"]) => "loom_create".to_string(),
+        ("GET", ["tasks", "ready"]) => "loom_ready".to_string(),
+        ("GET", ["tasks", "blocked"]) => "loom_blocked".to_string(),
+        ("GET", ["tasks", id]) => {
+            set("task_id", id);
+            "loom_get".to_string()
+        }
+        ("GET", ["tasks", id, "history"]) => {
+            set("task_id", id);
+            "loom_history".to_string()
+        }
+        ("GET", ["tasks", id, "comments"]) => {
+            set("task_id", id);
+            "loom_comments".to_string()
+        }
+        ("POST", ["tasks", id, "comments"]) => {
+            set("task_id", id);
+            "loom_comment".to_string()
+        }
+        ("POST", ["tasks", id, "spawn"]) => {
+            set("parent_id", id);
+            "loom_spawn".to_string()
+        }
+        ("POST", ["tasks", id, "plan"]) => {
+            set("task_id", id);
+            "loom_import_plan".to_string()
+        }
+        ("POST", ["tasks", id, action @ ("claim" | "renew" | "release" | "complete" | "cancel")]) => {
+            set("task_id", id);
+            format!("loom_{}", action)
+        }
+        ("GET", ["agents", agent, "tasks"]) => {
+            set("agent", agent);
+            "loom_mine".to_string()
+        }
+        ("POST", ["agents", agent, "next"]) => {
+            set("agent", agent);
+            "loom_next_task".to_string()
+        }
+        ("POST", ["agents", agent, "heartbeat"]) => {
+            set("agent", agent);
+            "loom_heartbeat".to_string()
+        }
+        ("GET", ["summary"]) => {
+            if let Some(label) = request.query_param("label") {
+                set("label", label);
+            }
+            "loom_summary".to_string()
+        }
+        _ => return None,
+    };
+    
+    Some((tool, args))
+}
+
+fn parse_query(query: &str) -> Vec<(String, String)> {
+    query.split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(key), percent_decode(value))
+        })
+        .collect()
+}
+
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => out.push(b' '),
+            b'%' => match s.get(i + 1..i + 3).and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                Some(b) => {
+                    out.push(b);
+                    i += 3;
+                    continue;
+                }
+                None => out.push(b'%'),
+            },
+            b => out.push(b),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+    
+    fn request(method: &str, target: &str, body: Value) -> HttpRequest {
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        HttpRequest {
+            method: method.to_string(),
+            path: path.to_string(),
+            query: parse_query(query),
+            bearer: None,
+            content_type: Some("application/json".to_string()),
+            body: if body.is_null() { Vec::new() } else { body.to_string().into_bytes() },
+        }
+    }
+    
+    #[test]
+    fn test_task_lifecycle_over_http() {
+        let dir = tempdir().unwrap();
+        let mut loom = Loom::init(dir.path()).unwrap();
+        
+        let created = handle(&mut loom, &request("POST", "/tasks", json!({ "title": "Ship it", "labels": ["ci"] })), None);
+        assert_eq!(created.status, 200);
+        let id = created.body["id"].as_str().unwrap().to_string();
+        
+        let ready = handle(&mut loom, &request("GET", "/tasks/ready", Value::Null), None);
+        assert_eq!(ready.body.as_array().unwrap().len(), 1);
+        
+        let claimed = handle(&mut loom, &request("POST", &format!("/tasks/{}/claim", id), json!({ "agent": "ci" })), None);
+        assert_eq!(claimed.status, 200, "{}", claimed.body);
+        
+        let mine = handle(&mut loom, &request("GET", "/agents/ci/tasks", Value::Null), None);
+        assert_eq!(mine.body.as_array().unwrap().len(), 1);
+        
+        let done = handle(&mut loom, &request("POST", &format!("/tasks/{}/complete", id), json!({ "evidence": "abc123" })), None);
+        assert_eq!(done.status, 200, "{}", done.body);
+        
+        let listed = handle(&mut loom, &request("GET", "/tasks?status=done&label=ci", Value::Null), None);
+        assert_eq!(listed.body.as_array().unwrap().len(), 1);
+        
+        // Generic tool route reaches the same operations
+        let summary = handle(&mut loom, &request("POST", "/tools/loom_summary", Value::Null), None);
+        assert_eq!(summary.body["done"], 1);
+        
+        assert_eq!(handle(&mut loom, &request("GET", "/tasks/lm-missing", Value::Null), None).status, 404);
+        assert_eq!(handle(&mut loom, &request("DELETE", "/tasks", Value::Null), None).status, 404);
+        assert_eq!(handle(&mut loom, &request("POST", "/tasks", json!([1])), None).status, 400);
+        
+        // Mutations need a JSON content type, which cross-site forms can't send
+        let mut form = request("POST", "/tasks", json!({ "title": "Sneaky" }));
+        form.content_type = Some("text/plain".to_string());
+        assert_eq!(handle(&mut loom, &form, None).status, 415);
+        form.content_type = None;
+        assert_eq!(handle(&mut loom, &form, None).status, 415);
+        assert_eq!(handle(&mut loom, &request("OPTIONS", "/tasks", Value::Null), None).status, 404);
+        assert!(!String::from_utf8(HttpResponse::ok(Value::Null).to_bytes()).unwrap().contains("Access-Control"));
+    }
+    
+    #[test]
+    fn test_bearer_token() {
+        let dir = tempdir().unwrap();
+        let mut loom = Loom::init(dir.path()).unwrap();
+        let mut req = request("GET", "/summary", Value::Null);
+        
+        assert_eq!(handle(&mut loom, &req, Some("secret")).status, 401);
+        assert_eq!(handle(&mut loom, &request("GET", "/health", Value::Null), Some("secret")).status, 200);
+        
+        req.bearer = Some("secret".to_string());
+        assert_eq!(handle(&mut loom, &req, Some("secret")).status, 200);
+    }
+    
+    #[test]
+    fn test_read_request() {
+        let raw = b"POST /tasks/lm-1/claim?x=a%20b+c HTTP/1.1\r\nAuthorization: Bearer t0k\r\nContent-Type: application/json; charset=utf-8\r\nContent-Length: 16\r\n\r\n{\"agent\":\"ci-1\"}";
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        let request = runtime.block_on(read_request(&mut &raw[..])).unwrap().unwrap();
+        
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/tasks/lm-1/claim");
+        assert_eq!(request.query_param("x"), Some("a b c"));
+        assert_eq!(request.bearer.as_deref(), Some("t0k"));
+        assert!(request.is_json());
+        assert_eq!(request.body, b"{\"agent\":\"ci-1\"}");
+        
+        // Endless headers are cut off rather than buffered
+        let mut raw = b"GET /summary HTTP/1.1\r\nX-Padding: ".to_vec();
+        raw.extend(std::iter::repeat_n(b'a', MAX_HEAD_BYTES as usize));
+        let err = runtime.block_on(read_request(&mut &raw[..])).unwrap_err();
+        assert_eq!(err.status, 431);
+        let raw = vec![b'G'; MAX_HEAD_BYTES as usize + 10];
+        assert_eq!(runtime.block_on(read_request(&mut &raw[..])).unwrap_err().status, 431);
+    }
+}
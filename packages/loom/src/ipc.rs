@@ -0,0 +1,219 @@
+//! Daemon Transport
+//!
+//! The daemon listens on a Unix socket (`.loom/run.sock`) on Unix and on a
+//! named pipe on Windows. Pipes don't live in the filesystem, so the pipe
+//! name is derived from the `.loom` path: every process that opens the same
+//! loom reaches the same daemon. Requests and replies are one JSON line each
+//! either way.
+
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use tokio::io::{AsyncRead, AsyncWrite};
+
+/// Where a daemon listens
+#[derive(Debug, Clone)]
+pub struct Endpoint {
+    /// Socket file on Unix; on Windows the file that would be the socket,
+    /// from which the pipe name is derived
+    path: PathBuf,
+}
+
+impl Endpoint {
+    pub fn new(socket_path: impl AsRef<Path>) -> Self {
+        Self { path: socket_path.as_ref().to_path_buf() }
+    }
+    
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+    
+    /// What to show users: the socket path, or the pipe name on Windows
+    pub fn display(&self) -> String {
+        #[cfg(windows)]
+        return pipe_name(&self.path);
+        #[cfg(unix)]
+        return self.path.display().to_string();
+    }
+    
+    /// Whether something is listening, or left behind by a daemon that
+    /// died (Unix sockets outlive their process; pipes don't)
+    pub fn exists(&self) -> bool {
+        #[cfg(windows)]
+        return self.is_listening();
+        #[cfg(unix)]
+        return self.path.exists();
+    }
+    
+    /// Whether a daemon accepts connections (a stale socket file doesn't)
+    pub fn is_listening(&self) -> bool {
+        #[cfg(windows)]
+        return self.connect_blocking().is_ok();
+        #[cfg(unix)]
+        return std::os::unix::net::UnixStream::connect(&self.path).is_ok();
+    }
+    
+    /// Remove what a crashed daemon left behind (nothing to do for pipes)
+    pub fn remove_stale(&self) -> std::io::Result<()> {
+        if cfg!(unix) && self.path.exists() {
+            std::fs::remove_file(&self.path)?;
+        }
+        Ok(())
+    }
+    
+    /// Send one request line and read one reply line without an async
+    /// runtime, giving up after `timeout`
+    pub fn request_blocking(&self, line: &str, timeout: Duration) -> std::io::Result<String> {
+        #[cfg(unix)]
+        {
+            let mut stream = std::os::unix::net::UnixStream::connect(&self.path)?;
+            stream.set_read_timeout(Some(timeout))?;
+            stream.set_write_timeout(Some(timeout))?;
+            exchange(&mut stream, line)
+        }
+        #[cfg(windows)]
+        {
+            // Pipe handles opened as files have no timeouts; wait on a thread
+            let mut pipe = self.connect_blocking()?;
+            let line = line.to_string();
+            let (tx, rx) = std::sync::mpsc::channel();
+            std::thread::spawn(move || {
+                let _ = tx.send(exchange(&mut pipe, &line));
+            });
+            rx.recv_timeout(timeout).unwrap_or_else(|_| {
+                Err(std::io::Error::new(std::io::ErrorKind::TimedOut, "daemon didn't answer in time"))
+            })
+        }
+    }
+    
+    #[cfg(windows)]
+    fn connect_blocking(&self) -> std::io::Result<std::fs::File> {
+        std::fs::OpenOptions::new().read(true).write(true).open(pipe_name(&self.path))
+    }
+    
+    /// Connect to a listening daemon
+    pub async fn connect(&self) -> std::io::Result<Stream> {
+        #[cfg(unix)]
+        {
+            Ok(Box::new(tokio::net::UnixStream::connect(&self.path).await?))
+        }
+        #[cfg(windows)]
+        {
+            use tokio::net::windows::named_pipe::ClientOptions;
+            // ERROR_PIPE_BUSY: every instance is taken until the daemon
+            // creates the next one, which it does as soon as one connects
+            const ERROR_PIPE_BUSY: i32 = 231;
+            let name = pipe_name(&self.path);
+            let mut attempts = 0;
+            loop {
+                match ClientOptions::new().open(&name) {
+                    Ok(client) => return Ok(Box::new(client)),
+                    Err(e) if e.raw_os_error() == Some(ERROR_PIPE_BUSY) && attempts < 50 => {
+                        attempts += 1;
+                        tokio::time::sleep(Duration::from_millis(20)).await;
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+        }
+    }
+    
+    /// Start listening
+    pub fn bind(&self) -> std::io::Result<Listener> {
+        #[cfg(unix)]
+        {
+            Ok(Listener { inner: tokio::net::UnixListener::bind(&self.path)? })
+        }
+        #[cfg(windows)]
+        {
+            use tokio::net::windows::named_pipe::ServerOptions;
+            let name = pipe_name(&self.path);
+            // Fails if another daemon already serves this loom
+            let next = ServerOptions::new().first_pipe_instance(true).create(&name)?;
+            Ok(Listener { name, next })
+        }
+    }
+}
+
+fn exchange<S: std::io::Read + Write>(stream: &mut S, line: &str) -> std::io::Result<String> {
+    stream.write_all(line.as_bytes())?;
+    stream.flush()?;
+    let mut reply = String::new();
+    std::io::BufReader::new(stream).read_line(&mut reply)?;
+    Ok(reply)
+}
+
+/// Named pipe for a loom: `\\.\pipe\loom-` and a hash of its path
+#[cfg(windows)]
+fn pipe_name(path: &Path) -> String {
+    use sha2::{Digest, Sha256};
+    let path = crate::platform::canonical(path.parent().unwrap_or(path));
+    let key = path.to_string_lossy().to_lowercase();
+    let digest = hex::encode(Sha256::digest(key.as_bytes()));
+    format!(r"\\.\pipe\loom-{}", &digest[..16])
+}
+
+/// A connection to or from the daemon
+pub type Stream = Box<dyn Connection>;
+
+pub trait Connection: AsyncRead + AsyncWrite + Unpin + Send {}
+
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> Connection for T {}
+
+/// Accepts daemon connections
+pub struct Listener {
+    #[cfg(unix)]
+    inner: tokio::net::UnixListener,
+    #[cfg(windows)]
+    name: String,
+    #[cfg(windows)]
+    next: tokio::net::windows::named_pipe::NamedPipeServer,
+}
+
+impl Listener {
+    pub async fn accept(&mut self) -> std::io::Result<Stream> {
+        #[cfg(unix)]
+        {
+            let (stream, _) = self.inner.accept().await?;
+            Ok(Box::new(stream))
+        }
+        #[cfg(windows)]
+        {
+            use tokio::net::windows::named_pipe::ServerOptions;
+            self.next.connect().await?;
+            // Each client gets its own instance; open the next before handing this one off
+            let next = ServerOptions::new().create(&self.name)?;
+            Ok(Box::new(std::mem::replace(&mut self.next, next)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    
+    #[tokio::test]
+    async fn test_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let endpoint = Endpoint::new(dir.path().join("run.sock"));
+        assert!(!endpoint.exists());
+        
+        let mut listener = endpoint.bind().unwrap();
+        let server = tokio::spawn(async move {
+            let stream = listener.accept().await.unwrap();
+            let (reader, mut writer) = tokio::io::split(stream);
+            let mut line = String::new();
+            BufReader::new(reader).read_line(&mut line).await.unwrap();
+            writer.write_all(line.to_uppercase().as_bytes()).await.unwrap();
+        });
+        
+        let reply = tokio::task::spawn_blocking(move || endpoint.request_blocking("ping\n", Duration::from_secs(5)))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(reply, "PING\n");
+        server.await.unwrap();
+    }
+}
@@ -52,8 +52,10 @@
 //! ├── agents.db         # Agent profiles and history
 //! ├── memory.db         # Sessions and checkpoints
 //! ├── run.sock          # Daemon socket
+//! ├── daemon.pid        # Daemon lock
 //! ├── dispatch.toml     # Agent config
 //! ├── formulas/         # Custom formulas
+//! ├── templates/        # Task templates
 //! ├── tasks.jsonl       # Git sync export
 //! └── log/              # Daemon logs
 //! ```
@@ -68,33 +70,78 @@ pub mod formulas;
 pub mod routing;
 pub mod sync;
 pub mod verify;
+pub mod templates;
+pub mod plan;
+pub mod quota;
+pub mod budget;
+pub mod pricing;
+pub mod outcome;
+pub mod schedule;
+pub mod logging;
 pub mod policy;
 pub mod models;
 pub mod orchestrator;
 pub mod backfill;
 pub mod config;
 pub mod ui_resources;
+pub mod notify;
+pub mod net;
+pub mod webhooks;
+pub mod events;
+pub mod maintenance;
+pub mod http;
+pub mod evidence;
+pub mod utilization;
+pub mod costs;
+pub mod platform;
+pub mod ipc;
+pub mod workflow;
+pub mod probe;
+#[cfg(any(test, feature = "test-util"))]
+pub mod testing;
 
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 
-pub use work::{Task, Status, Priority, CreateTask, WorkStore, WorkSummary, WorkError};
-pub use dispatch::{Agent, AgentConfig, DispatchConfig, Dispatcher, DispatchError};
-pub use agents::{AgentProfile, AgentRegistry, Capabilities, CostModel, QualityMetrics, RequiredFeatures, AnalyticsSummary, ExecutionRecord};
+pub use work::{Task, TaskKind, Lease, StaleClaim, Question, Status, Priority, CreateTask, WorkStore, WorkSummary, WorkError, Rollup, RemoteLink, Pause, PauseScope, Comment, DispatchAudit, HistoryEntry};
+pub use dispatch::{Agent, AgentConfig, AutoDispatch, AutoDispatchConfig, AutoDispatchPicks, DispatchConfig, Dispatcher, DispatchError};
+pub use agents::{AgentProfile, AgentRegistry, Capabilities, Heartbeat, CostModel, QualityMetrics, TypeQuality, Tally, RequiredFeatures, AnalyticsSummary, ExecutionRecord};
 pub use memory::{
-    Session, SessionContext, SessionStatus, Checkpoint, MemoryStore, MemoryError,
+    Session, SessionContext, SessionStatus, SessionSpan, SessionTime, TimeTotal, TimeReport, SessionEvent, Checkpoint, MemoryStore, MemoryError,
     // Enhanced context types (Harness AgentContext parity)
     FileModification, ChangeType, IssueUpdate, TaskProgress, TestState, Decision,
 };
-pub use formulas::{Formula, FormulaRegistry, QualityTier, Step, Variable};
-pub use routing::{Router, RoutingStrategy, RoutingConstraints, RoutingDecision};
+pub use formulas::{Formula, FormulaRegistry, QualityTier, Step, Variable, VariableType, PackManifest, InstalledPack, FormulaProvenance, PackUpdate};
+pub use routing::{AgentLoad, Router, RoutingStrategy, RoutingConstraints, RoutingDecision, RoutingSimulation, SimulatedAssignment};
 pub use sync::{GitSync, SyncState, SyncResult, SyncError};
+pub use sync::tracker::{IssueTracker, IssueFields, IssueState, RemoteIssue, ConflictStrategy, TrackerSyncOptions, TrackerSyncReport};
+pub use sync::github::GitHubTracker;
+pub use sync::linear::LinearSync;
+pub use sync::jira::JiraTracker;
 pub use verify::{Verifier, VerificationResult, CheckType, VerifyError, format_evidence};
-pub use policy::{Complexity, score_agent, route_by_label, requires_verification};
-pub use models::{ModelsConfig, ModelConfig, ModelTier, ModelFamily};
-pub use orchestrator::{Orchestrator, OrchestratorConfig, AgentBackend, ExecutionResult, send_notification};
+pub use policy::{Complexity, score_agent, route_by_label, requires_verification, EvidenceRequirement, EvidenceRules};
+pub use models::{ModelsConfig, ModelConfig, ModelTier, ModelFamily, QualitySettings};
+pub use probe::Probe;
+pub use orchestrator::{Orchestrator, OrchestratorConfig, AgentBackend, ExecutionResult, FailureClass, RetryPolicy, SandboxConfig, send_notification};
 pub use backfill::{Backfill, BackfillOptions, BackfillResult, BackfillError, BackfillAnalytics, CommitRecord, BeadsIssue, CorrelatedRecord};
-pub use config::{LoomConfig, RepoConfig, RepoInfo, ConfigError};
+pub use config::{LoomConfig, RepoConfig, RepoInfo, ConfigError, StakeholderConfig, NotificationConfig, MaintenanceConfig, EvidenceConfig, HttpConfig, LimitsConfig, AgentLimits, LoggingConfig, Redaction, SubtaskConfig, HeartbeatConfig, RoutingConfig, ClaimsConfig, WebhookConfig, TrackerSettings, GitHubSyncConfig, LinearSyncConfig, JiraSyncConfig};
+pub use evidence::{EvidenceEntry, LogIssue, LogVerification};
+pub use maintenance::MaintenanceReport;
+pub use outcome::{Outcome, OutcomeSummary};
+pub use notify::{Digest, DigestEntry, NotifyError, extract_mentions};
+pub use webhooks::WebhookDelivery;
+pub use events::{Event, EventSource, EventFilter, EventCursor, EventsError};
+pub use utilization::{AgentUtilization, UtilizationReport};
+pub use costs::{CostEntry, CostFormat, CostReport, CostSource, CostTotal};
+pub use daemon::{DaemonHealth, DaemonStatus, Liveness};
+pub use templates::{TaskTemplate, TemplateTask, PlannedTask, TemplateError};
+pub use plan::{PlanStep, PlanLink, PlanImport};
+pub use workflow::{FormulaRun, StepRun, RunStatus, StepStatus, FormulaPlan, StepPlan, FanOut, FanOutMember};
+pub use quota::{AgentAction, QuotaStatus, QuotaError};
+pub use budget::{BudgetError, BudgetScope, BudgetUsage, Overrun};
+pub use pricing::{PricingTable, PriceEntry, PriceChange, Repricing, PricingError};
+pub use schedule::{Schedule, Recurrence, CronExpr};
+pub use logging::{LogEntry, LogLevel, LogError};
 
 /// Loom error types
 #[derive(Error, Debug)]
@@ -117,6 +164,24 @@ pub enum LoomError {
     #[error("Formula error: {0}")]
     Formula(#[from] formulas::FormulaError),
     
+    #[error("Log error: {0}")]
+    Log(#[from] LogError),
+    
+    #[error("Template error: {0}")]
+    Template(#[from] templates::TemplateError),
+    
+    #[error("{0}")]
+    Quota(#[from] quota::QuotaError),
+    
+    #[error("{0}")]
+    Budget(#[from] budget::BudgetError),
+    
+    #[error("Pricing error: {0}")]
+    Pricing(#[from] pricing::PricingError),
+    
+    #[error("Event error: {0}")]
+    Events(#[from] EventsError),
+    
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
     
@@ -140,6 +205,18 @@ pub struct Loom {
     formulas: FormulaRegistry,
     dispatch: Option<Dispatcher>,
     config: LoomConfig,
+    /// Temporary directory holding `root` for an in-memory Loom
+    _scratch: Option<ScratchDir>,
+}
+
+/// A directory removed when dropped
+#[cfg_attr(not(any(test, feature = "test-util")), allow(dead_code))]
+struct ScratchDir(PathBuf);
+
+impl Drop for ScratchDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
 }
 
 impl Loom {
@@ -149,6 +226,13 @@ impl Loom {
         std::fs::create_dir_all(&root)?;
         std::fs::create_dir_all(root.join("log"))?;
         std::fs::create_dir_all(root.join("formulas"))?;
+        std::fs::create_dir_all(root.join("templates"))?;
+        
+        // Synced files stay LF so Windows and Unix checkouts diff cleanly
+        let attributes_path = root.join(".gitattributes");
+        if !attributes_path.exists() {
+            std::fs::write(&attributes_path, "*.jsonl text eol=lf\n*.toml text eol=lf\n")?;
+        }
         
         // Create default config if it doesn't exist
         let config_path = root.join("config.toml");
@@ -166,8 +250,14 @@ impl Loom {
         if let Some(ref repo_id) = config.repo_id {
             store.set_default_repo(Some(repo_id.clone()));
         }
+        store.set_evidence_signing(config.evidence.sign, config.evidence.signing_key());
+        store.set_evidence_rules(EvidenceRules::compile(&config.evidence.require)
+            .map_err(|e| LoomError::Config(format!("Invalid evidence pattern: {}", e)))?);
+        store.set_auto_complete_parents(config.subtasks.auto_complete_parents);
+        store.set_lease_duration(config.claims.lease_duration());
         
         let mut agents = AgentRegistry::open(root.join("agents.db"))?;
+        agents.set_heartbeat_timeout(config.heartbeat.timeout());
         let memory = MemoryStore::open(root.join("memory.db"))?;
         
         // Create default models.toml if it doesn't exist
@@ -179,9 +269,11 @@ impl Loom {
         // Load agents from models config
         let models_config = ModelsConfig::load_or_default(&root);
         agents.register_from_config(&models_config)?;
+        agents.set_quality_half_life(models_config.quality.half_life());
         
         // Initialize router and formulas (using Create Something optimized defaults)
-        let router = Router::new();
+        let mut router = Router::new();
+        router.set_config(config.routing.clone());
         let mut formulas = FormulaRegistry::create_something();
         let _ = formulas.load_from_dir(root.join("formulas"));
         
@@ -228,6 +320,7 @@ default = "claude"
             formulas,
             dispatch: None,
             config,
+            _scratch: None,
         })
     }
     
@@ -247,10 +340,18 @@ default = "claude"
         if let Some(ref repo_id) = config.repo_id {
             store.set_default_repo(Some(repo_id.clone()));
         }
+        store.set_evidence_signing(config.evidence.sign, config.evidence.signing_key());
+        store.set_evidence_rules(EvidenceRules::compile(&config.evidence.require)
+            .map_err(|e| LoomError::Config(format!("Invalid evidence pattern: {}", e)))?);
+        store.set_auto_complete_parents(config.subtasks.auto_complete_parents);
+        store.set_lease_duration(config.claims.lease_duration());
         
-        let agents = AgentRegistry::open(root.join("agents.db"))?;
+        let mut agents = AgentRegistry::open(root.join("agents.db"))?;
+        agents.set_heartbeat_timeout(config.heartbeat.timeout());
+        agents.set_quality_half_life(ModelsConfig::load_or_default(&root).quality.half_life());
         let memory = MemoryStore::open(root.join("memory.db"))?;
-        let router = Router::new();
+        let mut router = Router::new();
+        router.set_config(config.routing.clone());
         let mut formulas = FormulaRegistry::create_something();
         let _ = formulas.load_from_dir(root.join("formulas"));
         
@@ -266,6 +367,49 @@ default = "claude"
             formulas,
             dispatch,
             config,
+            _scratch: None,
+        })
+    }
+    
+    /// A Loom held entirely in memory, for tests in this crate and others
+    /// (with the `test-util` feature)
+    ///
+    /// The stores are SQLite `:memory:` databases, and config, models, and
+    /// dispatch settings are the defaults, with no formulas or templates
+    /// beyond the built-in ones. Files a Loom keeps beside its stores, like
+    /// discuss-phase context, go to a fresh temporary directory that's
+    /// removed when the Loom is dropped. [`testing`] has fixture builders
+    /// for tasks and sessions.
+    #[cfg(any(test, feature = "test-util"))]
+    pub fn in_memory() -> Result<Self, LoomError> {
+        let scratch = ScratchDir(std::env::temp_dir().join(format!("loom-{}", uuid::Uuid::new_v4())));
+        let root = scratch.0.join(".loom");
+        std::fs::create_dir_all(&root)?;
+        
+        let config = LoomConfig::default();
+        let mut store = WorkStore::in_memory()?;
+        store.set_auto_complete_parents(config.subtasks.auto_complete_parents);
+        store.set_lease_duration(config.claims.lease_duration());
+        
+        let mut agents = AgentRegistry::in_memory()?;
+        agents.set_heartbeat_timeout(config.heartbeat.timeout());
+        let models_config = ModelsConfig::defaults();
+        agents.register_from_config(&models_config)?;
+        agents.set_quality_half_life(models_config.quality.half_life());
+        
+        let mut router = Router::new();
+        router.set_config(config.routing.clone());
+        
+        Ok(Self {
+            root,
+            store,
+            agents,
+            memory: MemoryStore::in_memory()?,
+            router,
+            formulas: FormulaRegistry::create_something(),
+            dispatch: None,
+            config,
+            _scratch: Some(scratch),
         })
     }
     
@@ -420,9 +564,25 @@ default = "claude"
     }
     
     /// Claim a task for an agent
-    pub fn claim(&mut self, id: &str, agent: &str) -> Result<Task, LoomError> {
-        let task = self.store.claim(id, agent)?;
-        Ok(task)
+    ///
+    /// The claim is a lease that lasts `[claims] lease-mins`; renew it while
+    /// working, or another agent can take the task over once it expires.
+    /// Fails while one of the task's `[budgets]` is spent, unless they only
+    /// warn.
+    pub fn claim(&mut self, id: &str, agent: &str) -> Result<Lease, LoomError> {
+        let task = self.store.get(id)?.ok_or_else(|| WorkError::NotFound(id.to_string()))?;
+        budget::check_claim(&self.store, &self.config.budgets, &task)?;
+        Ok(self.store.claim(id, agent)?)
+    }
+    
+    /// Extend an agent's lease on a task it has claimed
+    pub fn renew(&mut self, id: &str, agent: &str) -> Result<Lease, LoomError> {
+        Ok(self.store.renew(id, agent)?)
+    }
+    
+    /// Leases expiring within `within`, including ones already expired
+    pub fn expiring_leases(&self, within: chrono::Duration) -> Result<Vec<Lease>, LoomError> {
+        Ok(self.store.expiring_leases(within)?)
     }
     
     /// Release a claimed task
@@ -431,22 +591,112 @@ default = "claude"
         Ok(())
     }
     
+    /// Release claims idle longer than `[claims] timeout-mins`
+    ///
+    /// A claim counts as active while the task is updated, its lease is
+    /// renewed, its session checkpoints, or its agent sends heartbeats.
+    /// Released tasks go back to ready with a comment, and their active
+    /// session is flagged as interrupted so the next agent can resume it.
+    /// With `dry_run` nothing changes.
+    pub fn reap_stale_claims(&mut self, dry_run: bool) -> Result<Vec<StaleClaim>, LoomError> {
+        let Some(timeout) = self.config.claims.timeout() else {
+            return Ok(Vec::new());
+        };
+        let cutoff = chrono::Utc::now() - timeout;
+        let heartbeats = self.agents.heartbeats()?;
+        
+        let mut reaped = Vec::new();
+        for task in self.store.claims_idle_since(cutoff)? {
+            let agent = task.agent.clone().unwrap_or_default();
+            let session = self.memory.get_active_session_for_task(&task.id)?;
+            
+            let mut idle_since = task.updated_at;
+            if let Some(lease) = self.store.get_lease(&task.id)? {
+                // Renewals push the expiry out, so a live lease is recent activity
+                idle_since = idle_since.max(lease.expires_at - self.config.claims.lease_duration());
+            }
+            if let Some(session) = &session {
+                idle_since = idle_since.max(session.started_at);
+                if let Some(checkpoint) = session.last_checkpoint.as_deref() {
+                    if let Some(checkpoint) = self.memory.get_checkpoint(checkpoint)? {
+                        idle_since = idle_since.max(checkpoint.created_at);
+                    }
+                }
+            }
+            if let Some(heartbeat) = heartbeats.iter().find(|h| h.agent_id == agent) {
+                idle_since = idle_since.max(heartbeat.last_seen);
+            }
+            if idle_since >= cutoff {
+                continue;
+            }
+            
+            let session_id = session.map(|s| s.id);
+            if !dry_run {
+                self.store.release(&task.id)?;
+                let note = format!(
+                    "Released claim by {}: no activity since {}",
+                    agent,
+                    idle_since.format("%Y-%m-%d %H:%M UTC")
+                );
+                self.store.add_comment(&task.id, "loom", &note)?;
+                if let Some(session_id) = &session_id {
+                    self.memory.interrupt_session(session_id, &note)?;
+                }
+            }
+            reaped.push(StaleClaim { task_id: task.id, agent, idle_since, session_id });
+        }
+        Ok(reaped)
+    }
+    
     /// Complete a task with optional evidence
     /// Returns list of task IDs that were auto-unblocked
     pub fn complete(&mut self, id: &str, evidence: Option<&str>) -> Result<Vec<String>, LoomError> {
         let unblocked = self.store.complete(id, evidence)?;
+        self.record_outcome(id, Outcome::default())?;
+        Ok(unblocked)
+    }
+    
+    /// Complete a task on behalf of the agent working on it, failing unless
+    /// the agent still holds its lease
+    pub fn complete_as(&mut self, id: &str, agent: &str, evidence: Option<&str>) -> Result<Vec<String>, LoomError> {
+        let unblocked = self.store.complete_as(id, agent, evidence)?;
+        self.record_outcome(id, Outcome::default())?;
         Ok(unblocked)
     }
     
+    /// Fail unless `agent` holds the lease on a claimed task
+    pub fn check_lease(&self, id: &str, agent: &str) -> Result<(), LoomError> {
+        Ok(self.store.check_lease(id, agent)?)
+    }
+    
     /// Complete a task with cost tracking
+    ///
+    /// The cost has already been spent, so it's always recorded; the
+    /// `[budgets]` it takes over their cap come back with the unblocked
+    /// task IDs. Under `on-exceed = "refuse"` they then stop new claims.
+    /// With an `agent`, fails unless it holds the task's lease, as
+    /// [`Loom::complete_as`] does.
     pub fn complete_with_cost(
         &mut self, 
         id: &str, 
+        agent: Option<&str>,
         evidence: Option<&str>,
         cost_usd: f64,
-    ) -> Result<Vec<String>, LoomError> {
-        let unblocked = self.store.complete_with_cost(id, evidence, cost_usd)?;
-        Ok(unblocked)
+    ) -> Result<(Vec<String>, Vec<Overrun>), LoomError> {
+        if let Some(agent) = agent {
+            self.store.check_lease(id, agent)?;
+        }
+        let task = self.store.get(id)?.ok_or_else(|| WorkError::NotFound(id.to_string()))?;
+        // Recording replaces any cost recorded before
+        let adding_usd = cost_usd - task.actual_cost_usd.unwrap_or(0.0);
+        let overruns = budget::overruns(&self.store, &self.config.budgets, &task, adding_usd)?;
+        self.store.record_cost(id, cost_usd)?;
+        let unblocked = match agent {
+            Some(agent) => self.store.complete_as(id, agent, evidence)?,
+            None => self.store.complete(id, evidence)?,
+        };
+        self.record_outcome(id, Outcome::default())?;
+        Ok((unblocked, overruns))
     }
     
     /// Record actual cost for a task (in USD)
@@ -455,6 +705,37 @@ default = "claude"
         Ok(())
     }
     
+    /// Record what a task produced
+    ///
+    /// The outcome is assembled from the task's sessions and its evidence,
+    /// then merged with anything recorded earlier and with `reported`, which
+    /// wins where they disagree. Nothing is stored while the outcome is empty.
+    pub fn record_outcome(&mut self, id: &str, reported: Outcome) -> Result<Outcome, LoomError> {
+        let task = self.store.get(id)?.ok_or_else(|| WorkError::NotFound(id.to_string()))?;
+        let sessions = self.memory.sessions_for_task(id)?;
+        let mut outcome = Outcome::from_sessions(&sessions, task.evidence.as_deref());
+        if let Some(recorded) = self.store.get_outcome(id)? {
+            outcome = outcome.merge(recorded);
+        }
+        let outcome = outcome.merge(reported);
+        
+        if outcome.is_empty() {
+            return Ok(outcome);
+        }
+        Ok(self.store.set_outcome(id, &outcome)?)
+    }
+    
+    /// The recorded outcome of a task
+    pub fn outcome(&self, id: &str) -> Result<Option<Outcome>, LoomError> {
+        Ok(self.store.get_outcome(id)?)
+    }
+    
+    /// Totals across outcomes recorded since `since`
+    pub fn outcome_summary(&self, since: chrono::DateTime<chrono::Utc>) -> Result<OutcomeSummary, LoomError> {
+        let outcomes = self.store.outcomes_since(since)?;
+        Ok(outcome::summarize(outcomes.iter().map(|(_, o)| o)))
+    }
+    
     /// Set task priority
     pub fn set_priority(&mut self, id: &str, priority: Priority) -> Result<(), LoomError> {
         self.store.update_priority(id, priority)?;
@@ -486,6 +767,7 @@ default = "claude"
         
         if all_passed || results.is_empty() {
             let unblocked = self.store.complete(id, Some(&evidence))?;
+            self.record_outcome(id, Outcome::default())?;
             Ok((VerificationResult {
                 check_type: CheckType::Duplicates, // Placeholder
                 passed: true,
@@ -512,12 +794,105 @@ default = "claude"
         }
     }
     
+    /// Create a task tree from `.loom/templates/<name>.toml`, optionally under
+    /// an existing task. Returns the created tasks, root first.
+    pub fn create_from_template(
+        &mut self,
+        name: &str,
+        vars: &std::collections::HashMap<String, String>,
+        parent: Option<&str>,
+    ) -> Result<Vec<Task>, LoomError> {
+        let plan = self.template(name)?.instantiate(vars)?;
+        Ok(self.store.create_tree(&plan, parent)?)
+    }
+    
+    /// Turn a markdown plan into sub-tasks of `task_id`, each blocked by the
+    /// step before it. Re-importing an edited plan updates the same tasks.
+    pub fn import_plan(&mut self, task_id: &str, markdown: &str) -> Result<PlanImport, LoomError> {
+        let steps = plan::parse(markdown);
+        Ok(self.store.import_plan(task_id, &steps)?)
+    }
+    
+    /// The plan section a task was imported from
+    pub fn plan_link(&self, task_id: &str) -> Result<Option<PlanLink>, LoomError> {
+        Ok(self.store.plan_link(task_id)?)
+    }
+    
+    /// Load a task template by name
+    pub fn template(&self, name: &str) -> Result<TaskTemplate, LoomError> {
+        Ok(TaskTemplate::load(self.root.join("templates"), name)?)
+    }
+    
+    /// All task templates in `.loom/templates/`
+    pub fn templates(&self) -> Result<Vec<TaskTemplate>, LoomError> {
+        Ok(TaskTemplate::load_all(self.root.join("templates"))?)
+    }
+    
     /// Cancel a task
     pub fn cancel(&mut self, id: &str) -> Result<(), LoomError> {
         self.store.cancel(id)?;
         Ok(())
     }
     
+    /// Put a done task back in the queue, counting it as a failure against
+    /// the agent that completed it (per task type, through its labels) so
+    /// work that bounces routes lower. `by` and `reason` go in a comment.
+    pub fn reopen(&mut self, id: &str, by: &str, reason: Option<&str>) -> Result<Task, LoomError> {
+        let completed_by = self.store.get(id)?.and_then(|t| t.agent);
+        let task = self.store.reopen(id)?;
+        if let Some(agent) = completed_by {
+            self.record_review(&agent, &task.labels, false)?;
+        }
+        
+        let body = match reason {
+            Some(reason) => format!("Reopened by {}: {}", by, reason),
+            None => format!("Reopened by {}", by),
+        };
+        self.store.add_comment(id, by, &body)?;
+        Ok(task)
+    }
+    
+    /// Record a reviewer's verdict on a done task
+    ///
+    /// An approval raises the completing agent's quality for the task's
+    /// labels, once per reviewer; a rejection reopens the task (see
+    /// [`Loom::reopen`]).
+    pub fn review(&mut self, id: &str, approved: bool, reviewer: &str, note: Option<&str>) -> Result<Task, LoomError> {
+        let task = self.store.get(id)?.ok_or_else(|| WorkError::NotFound(id.to_string()))?;
+        if task.status != Status::Done {
+            return Err(WorkError::NotReviewable(id.to_string()).into());
+        }
+        if !approved {
+            let reason = match note {
+                Some(note) => format!("rejected in review ({})", note),
+                None => "rejected in review".to_string(),
+            };
+            return self.reopen(id, reviewer, Some(&reason));
+        }
+        
+        if !self.store.record_approval(id, reviewer)? {
+            return Ok(task);
+        }
+        if let Some(agent) = &task.agent {
+            self.record_review(agent, &task.labels, true)?;
+        }
+        let body = match note {
+            Some(note) => format!("Approved by {}: {}", reviewer, note),
+            None => format!("Approved by {}", reviewer),
+        };
+        self.store.add_comment(id, reviewer, &body)?;
+        Ok(task)
+    }
+    
+    /// Feed a review into an agent's quality metrics; agents without a
+    /// profile (people, usually) have none to update
+    fn record_review(&mut self, agent: &str, labels: &[String], approved: bool) -> Result<(), LoomError> {
+        match self.agents.record_review(agent, labels, approved) {
+            Ok(()) | Err(agents::AgentError::NotFound(_)) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+    
     // ─────────────────────────────────────────────────────────────────────
     // Dependencies
     // ─────────────────────────────────────────────────────────────────────
@@ -534,6 +909,12 @@ default = "claude"
         Ok(())
     }
     
+    /// Dependency cycles in the store, each as a path of task IDs that ends
+    /// where it starts (new cycles are rejected by `block`)
+    pub fn dependency_cycles(&self) -> Result<Vec<Vec<String>>, LoomError> {
+        Ok(self.store.dependency_cycles()?)
+    }
+    
     // ─────────────────────────────────────────────────────────────────────
     // Queries
     // ─────────────────────────────────────────────────────────────────────
@@ -573,6 +954,16 @@ default = "claude"
         Ok(self.store.children(parent_id)?)
     }
     
+    /// Get a parent task's progress rolled up from its sub-tasks
+    pub fn rollup(&self, id: &str) -> Result<Option<Rollup>, LoomError> {
+        Ok(self.store.rollup(id)?)
+    }
+    
+    /// Get rollups for every task with sub-tasks, keyed by task ID
+    pub fn rollups(&self) -> Result<std::collections::HashMap<String, Rollup>, LoomError> {
+        Ok(self.store.rollups()?)
+    }
+    
     /// Get summary statistics
     pub fn summary(&self) -> Result<WorkSummary, LoomError> {
         Ok(self.store.summary()?)
@@ -583,29 +974,259 @@ default = "claude"
         Ok(self.store.summary_by_label(label)?)
     }
     
+    // ─────────────────────────────────────────────────────────────────────
+    // Work Queues (pull-based claiming)
+    // ─────────────────────────────────────────────────────────────────────
+    
+    /// Subscribe an agent to label topics
+    pub fn subscribe(&mut self, agent: &str, labels: &[String]) -> Result<(), LoomError> {
+        self.store.subscribe(agent, labels)?;
+        Ok(())
+    }
+    
+    /// Unsubscribe an agent from label topics (all topics when empty)
+    pub fn unsubscribe(&mut self, agent: &str, labels: &[String]) -> Result<(), LoomError> {
+        self.store.unsubscribe(agent, labels)?;
+        Ok(())
+    }
+    
+    /// Get the label topics an agent is subscribed to
+    pub fn subscriptions(&self, agent: &str) -> Result<Vec<String>, LoomError> {
+        Ok(self.store.subscriptions(agent)?)
+    }
+    
+    /// Atomically select, claim, and return the best ready task for an agent
+    ///
+    /// Candidates are ready tasks carrying at least one of the agent's
+    /// subscribed labels (any ready task if the agent has no subscriptions).
+    /// They are ranked by priority, then by routing score for the agent's
+    /// profile, then oldest first. If another agent claims a candidate between
+    /// selection and claim, the next candidate is tried, and so is the next
+    /// one after a candidate whose `[budgets]` refuse claims.
+    pub fn next_task(&mut self, agent: &str) -> Result<Option<Task>, LoomError> {
+        let topics = self.store.subscriptions(agent)?;
+        let profile = self.agents.get_profile(agent)?;
+        
+        let mut candidates: Vec<(Task, f64)> = self.store.ready()?
+            .into_iter()
+            .filter(|t| t.kind == TaskKind::Task)
+            .filter(|t| topics.is_empty() || t.labels.iter().any(|l| topics.contains(l)))
+            .map(|t| {
+                let score = profile.as_ref().map(|p| score_agent(p, &t)).unwrap_or(0.0);
+                (t, score)
+            })
+            .collect();
+        
+        candidates.sort_by(|(a, a_score), (b, b_score)| {
+            priority_rank(a.priority).cmp(&priority_rank(b.priority))
+                .then(b_score.partial_cmp(a_score).unwrap_or(std::cmp::Ordering::Equal))
+                .then(a.created_at.cmp(&b.created_at))
+        });
+        
+        for (task, _) in candidates {
+            match budget::check_claim(&self.store, &self.config.budgets, &task) {
+                Err(BudgetError::Spent(..)) => continue,
+                result => result?,
+            }
+            if let Some(claimed) = self.store.try_claim(&task.id, agent)? {
+                return Ok(Some(claimed));
+            }
+        }
+        
+        Ok(None)
+    }
+    
+    // ─────────────────────────────────────────────────────────────────────
+    // Pauses
+    // ─────────────────────────────────────────────────────────────────────
+    
+    /// Freeze a label or repo (e.g. all `deploy` work during an incident).
+    /// Its tasks drop out of `ready()`, `next_task`, and routing, and can't
+    /// be claimed until it's unpaused; claimed work is left alone.
+    pub fn pause(&mut self, scope: PauseScope, reason: Option<&str>, paused_by: Option<&str>) -> Result<Pause, LoomError> {
+        Ok(self.store.pause(scope, reason, paused_by)?)
+    }
+    
+    /// Lift a pause; false if the scope wasn't paused
+    pub fn unpause(&mut self, scope: &PauseScope) -> Result<bool, LoomError> {
+        Ok(self.store.unpause(scope)?)
+    }
+    
+    /// Active pauses
+    pub fn pauses(&self) -> Result<Vec<Pause>, LoomError> {
+        Ok(self.store.pauses()?)
+    }
+    
+    // ─────────────────────────────────────────────────────────────────────
+    // Comments
+    // ─────────────────────────────────────────────────────────────────────
+    
+    /// Leave a comment on a task. Agents use the thread for handoff notes
+    /// and findings instead of appending to the description.
+    pub fn comment(&mut self, task_id: &str, author: &str, body: &str) -> Result<Comment, LoomError> {
+        Ok(self.store.add_comment(task_id, author, body)?)
+    }
+    
+    /// A task's comments, oldest first
+    pub fn comments(&self, task_id: &str) -> Result<Vec<Comment>, LoomError> {
+        Ok(self.store.comments(task_id)?)
+    }
+    
+    /// The latest comments across all tasks, newest first
+    pub fn recent_comments(&self, limit: usize) -> Result<Vec<Comment>, LoomError> {
+        Ok(self.store.recent_comments(limit)?)
+    }
+    
+    // ─────────────────────────────────────────────────────────────────────
+    // History
+    // ─────────────────────────────────────────────────────────────────────
+    
+    /// Every state transition of a task (created, claimed, released,
+    /// completed, priority changes, ...), oldest first. The history table is
+    /// append-only, so this is what actually happened even when several
+    /// agents touched the task.
+    pub fn history(&self, task_id: &str) -> Result<Vec<HistoryEntry>, LoomError> {
+        Ok(self.store.history(task_id)?)
+    }
+    
     // ─────────────────────────────────────────────────────────────────────
     // Smart Routing
     // ─────────────────────────────────────────────────────────────────────
     
     /// Route a task to the best agent
     pub fn route(&mut self, task: &Task) -> Result<RoutingDecision, LoomError> {
-        self.router.route(
-            task,
-            &self.agents,
-            RoutingStrategy::Best,
-            &RoutingConstraints::default(),
-        ).map_err(LoomError::Routing)
+        self.route_with(task, RoutingStrategy::Best, &RoutingConstraints::default())
     }
     
     /// Route with custom strategy and constraints
+    ///
+    /// Fails if the estimated cost would take one of the task's `[budgets]`
+    /// over its cap; with `on-exceed = "warn"` the overruns are added to the
+    /// decision's warnings instead. With `[routing] sticky`, sub-tasks go
+    /// back to their parent's agent unless the constraints name one already.
     pub fn route_with(
         &mut self,
         task: &Task,
         strategy: RoutingStrategy,
         constraints: &RoutingConstraints,
     ) -> Result<RoutingDecision, LoomError> {
-        self.router.route(task, &self.agents, strategy, constraints)
-            .map_err(LoomError::Routing)
+        self.check_not_paused(task)?;
+        if matches!(strategy, RoutingStrategy::LeastLoaded | RoutingStrategy::RoundRobin) {
+            let loads = self.agent_loads()?;
+            self.router.set_loads(loads);
+        }
+        let mut constraints = constraints.clone();
+        if self.config.routing.sticky && constraints.sticky_agent.is_none() {
+            constraints.sticky_agent = self.sticky_agent(task)?;
+        }
+        let mut decision = self.router.route(task, &self.agents, strategy, &constraints)
+            .map_err(LoomError::Routing)?;
+        let overruns = self.check_budget(task, decision.estimated_cost)?;
+        decision.warnings.extend(overruns.iter().map(|o| format!("Over budget: {}", o)));
+        self.attach_context_budget(&mut decision)?;
+        Ok(decision)
+    }
+    
+    /// Route a task's ready sub-tasks, each to a different agent while there
+    /// are enough agents to go round; after that agents are reused. Nothing
+    /// is claimed.
+    pub fn route_children(
+        &mut self,
+        parent_id: &str,
+        constraints: &RoutingConstraints,
+    ) -> Result<Vec<(Task, RoutingDecision)>, LoomError> {
+        let children: Vec<Task> = self.ready()?
+            .into_iter()
+            .filter(|t| t.parent.as_deref() == Some(parent_id))
+            .collect();
+        
+        let mut routed: Vec<(Task, RoutingDecision)> = Vec::new();
+        for child in children {
+            let mut distinct = constraints.clone();
+            distinct.excluded_agents.extend(routed.iter().map(|(_, d)| d.agent_id.clone()));
+            let decision = match self.route_with(&child, RoutingStrategy::Best, &distinct) {
+                Ok(decision) => decision,
+                Err(LoomError::Routing(_)) => self.route_with(&child, RoutingStrategy::Best, constraints)?,
+                Err(e) => return Err(e),
+            };
+            routed.push((child, decision));
+        }
+        Ok(routed)
+    }
+    
+    /// The agent that did a sub-task's parent, for sticky routing
+    pub fn sticky_agent(&self, task: &Task) -> Result<Option<String>, LoomError> {
+        let Some(parent) = &task.parent else {
+            return Ok(None);
+        };
+        Ok(self.get(parent)?.and_then(|p| p.agent))
+    }
+    
+    /// Tasks each agent has claimed against its limit: `max_concurrent`
+    /// from dispatch.toml when the agent is configured there, else its
+    /// profile's
+    pub fn agent_loads(&self) -> Result<std::collections::HashMap<String, AgentLoad>, LoomError> {
+        let dispatch = self.dispatch.as_ref().map(|d| d.config());
+        let mut loads = std::collections::HashMap::new();
+        for profile in self.agents.all_profiles()? {
+            let limit = dispatch
+                .and_then(|c| c.agents.get(&profile.id))
+                .map_or(profile.max_concurrent, |c| c.max_concurrent);
+            let claimed = self.mine(&profile.id)?.len() as u32;
+            loads.insert(profile.id, AgentLoad { claimed, limit });
+        }
+        Ok(loads)
+    }
+    
+    /// How much task context the chosen agent can take, from its entry in
+    /// models.toml or, for agents registered another way, its profile's
+    /// window with the default reservations
+    fn attach_context_budget(&self, decision: &mut RoutingDecision) -> Result<(), LoomError> {
+        let budget = match ModelsConfig::load_or_default(&self.root).context_budget(&decision.agent_id) {
+            Some(budget) => Some(budget),
+            None => self.agents.get_profile(&decision.agent_id)?
+                .map(|p| models::default_context_budget(p.capabilities.max_context)),
+        };
+        decision.context_budget = budget;
+        Ok(())
+    }
+    
+    /// Paused tasks aren't routed
+    fn check_not_paused(&self, task: &Task) -> Result<(), LoomError> {
+        match self.store.pause_covering(task)? {
+            Some(pause) => Err(WorkError::Paused { id: task.id.clone(), scope: pause.scope.to_string() }.into()),
+            None => Ok(()),
+        }
+    }
+    
+    /// Dry-run a candidate models config against recent tasks
+    ///
+    /// Replays the `recent` most recently created tasks through both the
+    /// current agent profiles and the candidate config, reporting how
+    /// assignments, estimated costs, and predicted success rates would shift.
+    /// Learned quality history carries over to candidate models with the same ID.
+    pub fn simulate_routing(
+        &self,
+        candidate: &ModelsConfig,
+        recent: usize,
+    ) -> Result<RoutingSimulation, LoomError> {
+        let current = self.agents.all_profiles()?;
+        
+        let candidate_profiles: Vec<AgentProfile> = candidate.profiles()
+            .into_iter()
+            .map(|mut profile| {
+                if let Some(existing) = current.iter().find(|p| p.id == profile.id) {
+                    profile.quality = existing.quality.clone();
+                    profile.active = existing.active;
+                    profile.available = existing.available;
+                }
+                profile
+            })
+            .collect();
+        
+        let tasks: Vec<Task> = self.store.list_all()?.into_iter().take(recent).collect();
+        
+        Ok(RoutingSimulation::run(&tasks, &current, &candidate_profiles, RoutingStrategy::Best))
     }
     
     /// Get all agent profiles
@@ -618,6 +1239,87 @@ default = "claude"
         Ok(self.agents.get_profile(id)?)
     }
     
+    /// Register an agent configured like a models.toml entry, without
+    /// editing models.toml. It's stored in the agent registry, so it stays
+    /// registered across restarts; registering a known ID reconfigures it.
+    pub fn register_agent(&mut self, id: &str, config: &ModelConfig) -> Result<AgentProfile, LoomError> {
+        if id.trim().is_empty() || id.contains(char::is_whitespace) {
+            return Err(LoomError::Config(format!("Invalid agent ID: {:?}", id)));
+        }
+        config.validate().map_err(|e| LoomError::Config(format!("Invalid agent {}: {}", id, e)))?;
+        Ok(self.agents.register_agent(config.to_profile(id))?)
+    }
+    
+    /// Probe agents' CLIs (one agent, or every agent with a command) and
+    /// update their availability and cold-start latency
+    pub fn probe_agents(&mut self, only: Option<&str>, timeout: std::time::Duration) -> Result<Vec<Probe>, LoomError> {
+        let mut agents = match only {
+            Some(id) => vec![self.agents.get_profile(id)?.ok_or_else(|| agents::AgentError::NotFound(id.to_string()))?],
+            // Backfilled agents have no command to run
+            None => self.agents.all_profiles()?.into_iter().filter(|a| !a.cli_path.is_empty()).collect(),
+        };
+        agents.sort_by(|a, b| a.id.cmp(&b.id));
+        let mut probes = Vec::new();
+        for agent in &agents {
+            let probe = probe::probe(agent, timeout);
+            self.agents.record_probe(&probe)?;
+            probes.push(probe);
+        }
+        Ok(probes)
+    }
+    
+    /// Record that an agent is alive, keeping it in routing
+    pub fn heartbeat(&mut self, agent_id: &str, status: Option<&str>) -> Result<Heartbeat, LoomError> {
+        Ok(self.agents.heartbeat(agent_id, status)?)
+    }
+    
+    /// Latest heartbeat from each agent that sends them
+    pub fn heartbeats(&self) -> Result<Vec<Heartbeat>, LoomError> {
+        Ok(self.agents.heartbeats()?)
+    }
+    
+    /// The pricing table at `path`, or `.loom/pricing.toml`, or the bundled one
+    pub fn pricing_table(&self, path: Option<&Path>) -> Result<PricingTable, LoomError> {
+        Ok(match path {
+            Some(path) => PricingTable::load(path)?,
+            None => PricingTable::load_or_bundled(&self.root)?,
+        })
+    }
+    
+    /// Bring models.toml and the agent profiles up to today's prices
+    ///
+    /// Returns the models whose price changed; with `dry_run` nothing is written.
+    pub fn sync_pricing(&mut self, path: Option<&Path>, dry_run: bool) -> Result<Vec<PriceChange>, LoomError> {
+        let table = self.pricing_table(path)?;
+        let models = ModelsConfig::load_or_default(&self.root);
+        let changes = pricing::price_changes(&models, &table, chrono::Utc::now().date_naive());
+        if dry_run || changes.is_empty() {
+            return Ok(changes);
+        }
+        
+        let models_path = self.root.join("models.toml");
+        if models_path.exists() {
+            let content = std::fs::read_to_string(&models_path)?;
+            std::fs::write(&models_path, pricing::apply_to_models_toml(&content, &changes))?;
+        }
+        for change in &changes {
+            if let Some(mut profile) = self.agents.get_profile(&change.model)? {
+                profile.cost.input_per_1k = change.to_input_per_1k;
+                profile.cost.output_per_1k = change.to_output_per_1k;
+                self.agents.upsert_profile(&profile)?;
+            }
+        }
+        
+        Ok(changes)
+    }
+    
+    /// Recompute recorded execution costs at the prices in effect when they ran
+    pub fn reprice_history(&mut self, path: Option<&Path>, dry_run: bool) -> Result<Repricing, LoomError> {
+        let table = self.pricing_table(path)?;
+        let models = ModelsConfig::load_or_default(&self.root);
+        Ok(pricing::reprice(&mut self.agents, &table, &models, dry_run)?)
+    }
+    
     // ─────────────────────────────────────────────────────────────────────
     // Sessions & Memory
     // ─────────────────────────────────────────────────────────────────────
@@ -665,6 +1367,28 @@ default = "claude"
         Ok(())
     }
     
+    /// Pause a session; paused time doesn't count as worked
+    pub fn pause_session(&mut self, session_id: &str) -> Result<(), LoomError> {
+        self.memory.pause_session(session_id)?;
+        Ok(())
+    }
+    
+    /// Resume a paused session
+    pub fn unpause_session(&mut self, session_id: &str) -> Result<(), LoomError> {
+        self.memory.unpause_session(session_id)?;
+        Ok(())
+    }
+    
+    /// Active and paused time for a session
+    pub fn session_time(&self, session_id: &str) -> Result<SessionTime, LoomError> {
+        Ok(self.memory.session_time(session_id)?)
+    }
+    
+    /// Active working time per task and per agent
+    pub fn time_report(&self) -> Result<TimeReport, LoomError> {
+        Ok(self.memory.time_report()?)
+    }
+    
     /// Get recoverable sessions
     pub fn recoverable_sessions(&self) -> Result<Vec<Session>, LoomError> {
         Ok(self.memory.get_recoverable_sessions()?)
@@ -676,14 +1400,21 @@ default = "claude"
     }
     
     /// Record execution result for learning
+    ///
+    /// Without a duration, the agent's tracked session time on the task is
+    /// used, so pauses don't count against its speed.
     pub fn record_execution(
         &mut self,
         agent_id: &str,
         task_id: &str,
         task_type: Option<&str>,
         success: bool,
-        duration_secs: f64,
+        duration_secs: Option<f64>,
     ) -> Result<(), LoomError> {
+        let duration_secs = match duration_secs {
+            Some(secs) => secs,
+            None => self.memory.worked_secs(task_id, agent_id)?,
+        };
         self.agents.record_execution(
             agent_id,
             task_id,
@@ -696,6 +1427,40 @@ default = "claude"
         Ok(())
     }
     
+    /// Per-agent, per-day active vs idle time, completions, and cost since a point in time
+    pub fn utilization(&self, since: chrono::DateTime<chrono::Utc>) -> Result<UtilizationReport, LoomError> {
+        let now = chrono::Utc::now();
+        let spans = self.memory.session_spans(since, now)?;
+        let records = self.agents.get_history_since(since)?;
+        Ok(UtilizationReport::build(&spans, &records, since, now))
+    }
+    
+    /// Costs recorded since a point in time (or ever), per task, agent and
+    /// label (see [`costs`])
+    pub fn cost_report(&self, since: Option<chrono::DateTime<chrono::Utc>>) -> Result<CostReport, LoomError> {
+        let from = since.unwrap_or(chrono::DateTime::UNIX_EPOCH);
+        let ledger = self.store.spend_since(from)?;
+        let executions = self.agents.get_history_since(from)?;
+        
+        let mut tasks = std::collections::HashMap::new();
+        let ids: std::collections::BTreeSet<&str> = ledger.iter().map(|s| s.task_id.as_str())
+            .chain(executions.iter().filter(|r| r.cost.is_some()).map(|r| r.task_id.as_str()))
+            .collect();
+        let ledgered = self.store.ledgered_tasks(ids.iter().copied())?;
+        for id in ids {
+            if let Some(task) = self.store.get(id)? {
+                tasks.insert(id.to_string(), task);
+            }
+        }
+        Ok(CostReport::build(&ledger, &ledgered, &executions, &tasks, since))
+    }
+    
+    /// Every recorded cost as CSV or JSON, for finance reporting
+    pub fn export_costs(&self, format: CostFormat) -> Result<String, LoomError> {
+        let report = self.cost_report(None)?;
+        Ok(report.render(format).map_err(WorkError::from)?)
+    }
+    
     // ─────────────────────────────────────────────────────────────────────
     // GSD-Inspired: Discuss Phase (Pre-Planning)
     // ─────────────────────────────────────────────────────────────────────
@@ -712,36 +1477,39 @@ default = "claude"
         options: Option<&[String]>,
     ) -> Result<(), LoomError> {
         use std::fs::{OpenOptions};
+        use std::fmt::Write as _;
         use std::io::Write;
         
-        // Store preferences in a {task-id}-CONTEXT.md file in the loom directory
-        let context_file = self.root.join(format!("{}-CONTEXT.md", task_id));
-        
-        let mut file = OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(&context_file)?;
-        
         // Write the preference in markdown format
-        writeln!(file, "\n## {} Preference: {}", category, question)?;
-        writeln!(file)?;
+        let mut entry = String::new();
+        let _ = writeln!(entry, "\n## {} Preference: {}", category, question);
+        let _ = writeln!(entry);
         
         if let Some(opts) = options {
-            writeln!(file, "**Options considered:**")?;
+            let _ = writeln!(entry, "**Options considered:**");
             for opt in opts {
                 let marker = if opt == decision { "✓" } else { "-" };
-                writeln!(file, "{} {}", marker, opt)?;
+                let _ = writeln!(entry, "{} {}", marker, opt);
             }
-            writeln!(file)?;
+            let _ = writeln!(entry);
         }
         
-        writeln!(file, "**Decision:** {}", decision)?;
+        let _ = writeln!(entry, "**Decision:** {}", decision);
         
         if let Some(rat) = rationale {
-            writeln!(file, "\n**Rationale:** {}", rat)?;
+            let _ = writeln!(entry, "\n**Rationale:** {}", rat);
         }
         
-        writeln!(file, "\n---")?;
+        let _ = writeln!(entry, "\n---");
+        
+        // Store preferences in a {task-id}-CONTEXT.md file in the loom directory
+        let context_file = self.root.join(format!("{}-CONTEXT.md", task_id));
+        
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&context_file)?;
+        file.write_all(entry.as_bytes())?;
         
         Ok(())
     }
@@ -757,6 +1525,287 @@ default = "claude"
         }
     }
     
+    // ─────────────────────────────────────────────────────────────────────
+    // Questions (Decision Needed)
+    // ─────────────────────────────────────────────────────────────────────
+    
+    /// Ask a question that blocks the given tasks until it's answered
+    ///
+    /// If a deadline and default decision are set, the default is applied
+    /// once the deadline passes (see `apply_default_decisions`).
+    pub fn ask(
+        &mut self,
+        params: CreateTask,
+        options: Vec<String>,
+        deadline: Option<chrono::DateTime<chrono::Utc>>,
+        default_decision: Option<String>,
+        blocks: &[String],
+    ) -> Result<(Task, Question), LoomError> {
+        let (task, question) = self.store.create_question(params, options, deadline, default_decision)?;
+        for blocked in blocks {
+            self.store.add_dependency(blocked, &task.id)?;
+        }
+        Ok((task, question))
+    }
+    
+    /// Get the question details for a question task
+    pub fn question(&self, task_id: &str) -> Result<Option<Question>, LoomError> {
+        Ok(self.store.question(task_id)?)
+    }
+    
+    /// Unanswered questions, soonest deadline first
+    pub fn open_questions(&self) -> Result<Vec<Question>, LoomError> {
+        Ok(self.store.open_questions()?)
+    }
+    
+    /// Answer a question, returning the newly unblocked task IDs
+    ///
+    /// The decision is recorded as a preference on the question and on every
+    /// task that depends on it, so it shows up alongside discuss-phase context.
+    pub fn answer(
+        &mut self,
+        task_id: &str,
+        decision: &str,
+        rationale: Option<&str>,
+        answered_by: &str,
+    ) -> Result<Vec<String>, LoomError> {
+        let task = self.store.get(task_id)?
+            .ok_or_else(|| WorkError::NotFound(task_id.to_string()))?;
+        let dependents = self.store.dependents(task_id)?;
+        
+        let unblocked = self.store.answer(task_id, decision, rationale, answered_by)?;
+        
+        let options = self.store.question(task_id)?
+            .map(|q| q.options)
+            .unwrap_or_default();
+        let options = (!options.is_empty()).then_some(options.as_slice());
+        for id in std::iter::once(task_id.to_string()).chain(dependents) {
+            self.record_preference(&id, "decision", &task.title, decision, rationale, options)?;
+        }
+        
+        Ok(unblocked)
+    }
+    
+    /// Apply the default decision to every question past its deadline
+    ///
+    /// Returns `(question_id, decision)` for each question resolved.
+    pub fn apply_default_decisions(&mut self) -> Result<Vec<(String, String)>, LoomError> {
+        let now = chrono::Utc::now();
+        let mut applied = Vec::new();
+        
+        for question in self.store.open_questions()? {
+            if !question.is_overdue(now) {
+                continue;
+            }
+            if let Some(default) = question.default_decision {
+                self.answer(&question.task_id, &default, Some("Deadline passed; default applied"), "default")?;
+                applied.push((question.task_id, default));
+            }
+        }
+        
+        Ok(applied)
+    }
+    
+    // ─────────────────────────────────────────────────────────────────────
+    // Stakeholder Notifications
+    // ─────────────────────────────────────────────────────────────────────
+    
+    /// Build per-stakeholder digests of task activity since a point in time
+    pub fn digests(&self, since: chrono::DateTime<chrono::Utc>) -> Result<Vec<Digest>, LoomError> {
+        let tasks = self.store.list_all()?;
+        Ok(notify::build_digests(&self.config.stakeholders, &tasks, since))
+    }
+    
+    /// Alert each stakeholder to mentions they haven't been sent yet,
+    /// returning the deliveries that failed
+    pub fn send_mentions(&mut self) -> Result<Vec<String>, LoomError> {
+        let tasks = self.store.list_all()?;
+        let (stakeholders, root) = (&self.config.stakeholders, &self.root);
+        Ok(notify::send_mentions(&mut self.store, stakeholders, &tasks, |digest| {
+            notify::deliver(digest, &stakeholders[&digest.stakeholder], root).map_err(|e| e.to_string())
+        })?)
+    }
+    
+    /// Deliver a digest to its stakeholder (webhook or .loom/digests/)
+    pub fn deliver_digest(&self, digest: &Digest) -> Result<(), LoomError> {
+        let stakeholder = self.config.stakeholders.get(&digest.stakeholder)
+            .ok_or_else(|| LoomError::Config(format!("Unknown stakeholder: {}", digest.stakeholder)))?;
+        notify::deliver(digest, stakeholder, &self.root)
+            .map_err(|e| LoomError::Config(e.to_string()))
+    }
+    
+    /// POST task events recorded since the last run to each `[[webhooks]]` URL
+    pub fn deliver_webhooks(&mut self) -> Result<Vec<WebhookDelivery>, LoomError> {
+        Ok(webhooks::deliver(&mut self.store, &self.config.webhooks, webhooks::post)?)
+    }
+    
+    /// A cursor past every task and session event recorded so far
+    pub fn event_cursor(&self) -> Result<EventCursor, LoomError> {
+        Ok(EventCursor::latest(&self.store, &self.memory)?)
+    }
+    
+    /// Task and session events recorded since the cursor, oldest first
+    pub fn poll_events(&self, cursor: &mut EventCursor) -> Result<Vec<Event>, LoomError> {
+        Ok(cursor.poll(&self.store, &self.memory)?)
+    }
+    
+    // ─────────────────────────────────────────────────────────────────────
+    // Issue Tracker Sync
+    // ─────────────────────────────────────────────────────────────────────
+    
+    /// Two-way sync with an issue tracker
+    pub fn sync_tracker(
+        &mut self,
+        tracker: &dyn IssueTracker,
+        options: &TrackerSyncOptions,
+    ) -> Result<TrackerSyncReport, LoomError> {
+        Ok(sync::tracker::sync_tracker(&mut self.store, tracker, options)?)
+    }
+    
+    /// Two-way sync with the GitHub repo in `[sync.github]`
+    pub fn sync_github(&mut self, dry_run: bool) -> Result<TrackerSyncReport, LoomError> {
+        let config = self.config.sync.github.clone()
+            .ok_or_else(|| LoomError::Config("No [sync.github] repo configured".to_string()))?;
+        let tracker = GitHubTracker::new(&config.repo)?;
+        self.sync_tracker(&tracker, &config.settings.sync_options(dry_run))
+    }
+    
+    /// Two-way sync with the Linear team in `[sync.linear]`
+    pub fn sync_linear(&mut self, dry_run: bool) -> Result<TrackerSyncReport, LoomError> {
+        let config = self.config.sync.linear.clone()
+            .ok_or_else(|| LoomError::Config("No [sync.linear] team configured".to_string()))?;
+        let api_key = config.api_key()
+            .ok_or_else(|| LoomError::Config(format!("Linear API key not set (${})", config.api_key_env)))?;
+        let tracker = LinearSync::new(&config.team, api_key)?;
+        self.sync_tracker(&tracker, &config.settings.sync_options(dry_run))
+    }
+    
+    /// Two-way sync with the Jira project in `[sync.jira]`
+    pub fn sync_jira(&mut self, dry_run: bool) -> Result<TrackerSyncReport, LoomError> {
+        let config = self.config.sync.jira.clone()
+            .ok_or_else(|| LoomError::Config("No [sync.jira] project configured".to_string()))?;
+        let token = config.token()
+            .ok_or_else(|| LoomError::Config(format!("Jira API token not set (${})", config.token_env)))?;
+        let tracker = JiraTracker::new(&config.url, &config.project, config.email.as_deref(), &token)?
+            .with_jql(config.jql.clone())
+            .with_issue_type(&config.issue_type)
+            .with_priorities(config.priorities.clone());
+        self.sync_tracker(&tracker, &config.settings.sync_options(dry_run))
+    }
+    
+    // ─────────────────────────────────────────────────────────────────────
+    // Quotas
+    // ─────────────────────────────────────────────────────────────────────
+    
+    /// An agent's usage against its `[limits]`
+    pub fn quota_status(&self, agent: &str) -> Result<QuotaStatus, LoomError> {
+        Ok(quota::status(&self.store, agent, self.config.limits.for_agent(agent))?)
+    }
+    
+    /// Fail with an explanation if `agent` may not take `count` more of an
+    /// action right now
+    pub fn check_quota(&self, agent: &str, action: AgentAction, count: u32) -> Result<(), LoomError> {
+        Ok(quota::check(&self.store, agent, self.config.limits.for_agent(agent), action, count)?)
+    }
+    
+    /// Charge an action on these tasks to an agent's quota
+    pub fn record_activity(&mut self, agent: &str, action: AgentAction, task_ids: &[String]) -> Result<(), LoomError> {
+        for id in task_ids {
+            self.store.record_activity(agent, action.as_str(), id)?;
+        }
+        Ok(())
+    }
+    
+    // ─────────────────────────────────────────────────────────────────────
+    // Budgets
+    // ─────────────────────────────────────────────────────────────────────
+    
+    /// Today's spend against each of the `[budgets]`
+    pub fn budget_status(&self) -> Result<Vec<BudgetUsage>, LoomError> {
+        Ok(budget::status(&self.store, &self.config.budgets)?)
+    }
+    
+    /// Check spending `adding_usd` more on a task against its budgets: fails
+    /// on an overrun, or returns the overruns when budgets only warn
+    pub fn check_budget(&self, task: &Task, adding_usd: f64) -> Result<Vec<Overrun>, LoomError> {
+        Ok(budget::check(&self.store, &self.config.budgets, task, adding_usd)?)
+    }
+    
+    
+    // ─────────────────────────────────────────────────────────────────────
+    // Schedules
+    // ─────────────────────────────────────────────────────────────────────
+    
+    /// Schedule a task to be created at `due_at` or on every cron firing (UTC)
+    pub fn schedule(&mut self, params: CreateTask, recurrence: Recurrence) -> Result<Schedule, LoomError> {
+        Ok(self.store.create_schedule(params, recurrence)?)
+    }
+    
+    /// All schedules, soonest first
+    pub fn schedules(&self) -> Result<Vec<Schedule>, LoomError> {
+        Ok(self.store.schedules()?)
+    }
+    
+    /// Remove a schedule
+    pub fn unschedule(&mut self, id: &str) -> Result<(), LoomError> {
+        Ok(self.store.delete_schedule(id)?)
+    }
+    
+    /// Create the tasks for every schedule that's due, so they show up in
+    /// `ready()`. The daemon does this each minute.
+    pub fn run_schedules(&mut self) -> Result<Vec<Task>, LoomError> {
+        Ok(self.store.materialize_due(chrono::Utc::now())?)
+    }
+    
+    // ─────────────────────────────────────────────────────────────────────
+    // Maintenance
+    // ─────────────────────────────────────────────────────────────────────
+    
+    /// Prune orphaned and expired records per `[maintenance]` retention,
+    /// then VACUUM every database and report the space reclaimed
+    pub fn maintain(&mut self) -> Result<MaintenanceReport, LoomError> {
+        let retention = &self.config.maintenance;
+        let now = chrono::Utc::now();
+        let session_cutoff = now - chrono::Duration::days(retention.session_retention_days as i64);
+        let history_cutoff = now - chrono::Duration::days(retention.history_retention_days as i64);
+        
+        let live = self.store.all_ids()?;
+        
+        let (orphaned_sessions, expired_sessions, orphaned_checkpoints, orphaned_recovery) =
+            self.memory.prune(&live, session_cutoff)?;
+        let (orphaned_agent_sessions, stale_history) = self.agents.prune(&live, history_cutoff)?;
+        
+        let reclaimed = vec![
+            ("work.db".to_string(), self.store.vacuum()?),
+            ("memory.db".to_string(), self.memory.vacuum()?),
+            ("agents.db".to_string(), self.agents.vacuum()?),
+        ];
+        
+        Ok(MaintenanceReport {
+            orphaned_sessions,
+            expired_sessions,
+            orphaned_checkpoints,
+            orphaned_recovery,
+            orphaned_agent_sessions,
+            stale_history,
+            reclaimed,
+        })
+    }
+    
+    // ─────────────────────────────────────────────────────────────────────
+    // Evidence Log
+    // ─────────────────────────────────────────────────────────────────────
+    
+    /// Verify the hash-chained evidence log and report any tampering
+    pub fn verify_evidence_log(&self) -> Result<LogVerification, LoomError> {
+        Ok(self.store.verify_evidence_log()?)
+    }
+    
+    /// Get evidence log entries, optionally for a single task
+    pub fn evidence_log(&self, task_id: Option<&str>) -> Result<Vec<EvidenceEntry>, LoomError> {
+        Ok(self.store.evidence_log(task_id)?)
+    }
+    
     // ─────────────────────────────────────────────────────────────────────
     // Formulas
     // ─────────────────────────────────────────────────────────────────────
@@ -771,10 +1820,103 @@ default = "claude"
         self.formulas.list()
     }
     
+    /// Install a formula pack (local directory, archive, or https:// URL) into .loom/formulas
+    pub fn install_formula_pack(&mut self, source: &str) -> Result<InstalledPack, LoomError> {
+        Ok(self.formulas.install(source, self.root.join("formulas"))?)
+    }
+    
+    /// Installed formula packs
+    pub fn formula_packs(&self) -> Result<Vec<InstalledPack>, LoomError> {
+        Ok(FormulaRegistry::installed_packs(self.root.join("formulas"))?)
+    }
+    
+    /// Check each installed pack's source for a newer version
+    pub fn check_formula_updates(&self) -> Result<Vec<PackUpdate>, LoomError> {
+        Ok(FormulaRegistry::check_updates(self.root.join("formulas"))?)
+    }
+    
+    /// Where an installed formula came from (None for built-in and local formulas)
+    pub fn formula_provenance(&self, name: &str) -> Option<&FormulaProvenance> {
+        self.formulas.provenance(name)
+    }
+    
+    /// Create the tasks for a formula run without running anything: a root
+    /// task, a sub-task per step, and the dependencies between them (see
+    /// [`workflow`]). Agents can then take the steps from the queue.
+    pub fn start_formula(
+        &mut self,
+        name: &str,
+        vars: &std::collections::HashMap<String, String>,
+    ) -> Result<FormulaRun, LoomError> {
+        let formula = self.formulas.get(name)
+            .cloned()
+            .ok_or_else(|| formulas::FormulaError::NotFound(name.to_string()))?;
+        // Path variables are relative to the project, not wherever we run
+        formula.validate_variables_in(vars, self.root.parent().unwrap_or(&self.root))?;
+        
+        let created = self.store.create_tree(&workflow::formula_tasks(&formula, vars), None)?;
+        let (root, steps) = created.split_first().expect("create_tree returns the root first");
+        let dependencies = workflow::step_dependencies(&formula.steps);
+        for (i, waits_on) in dependencies.iter().enumerate() {
+            for &j in waits_on {
+                self.store.add_dependency(&steps[i].id, &steps[j].id)?;
+            }
+        }
+        for i in workflow::final_steps(&dependencies) {
+            self.store.add_dependency(&root.id, &steps[i].id)?;
+        }
+        
+        Ok(FormulaRun::new(&formula, root, steps))
+    }
+    
+    /// Preview a formula run: the tasks it would create, where each step
+    /// would be routed, and the estimated cost, without creating anything
+    pub fn plan_formula(
+        &self,
+        name: &str,
+        vars: &std::collections::HashMap<String, String>,
+    ) -> Result<FormulaPlan, LoomError> {
+        let formula = self.formulas.get(name)
+            .ok_or_else(|| formulas::FormulaError::NotFound(name.to_string()))?;
+        formula.validate_variables_in(vars, self.root.parent().unwrap_or(&self.root))?;
+        Ok(workflow::plan(formula, vars, &self.agents.all_profiles()?))
+    }
+    
+    /// Create the tasks for a formula run and have the orchestrator work
+    /// through them, step by step, until the run completes or a step fails
+    pub fn run_formula(
+        &mut self,
+        name: &str,
+        vars: &std::collections::HashMap<String, String>,
+    ) -> Result<FormulaRun, LoomError> {
+        let mut run = self.start_formula(name, vars)?;
+        let orchestrator = Orchestrator::with_config(OrchestratorConfig {
+            working_dir: self.root.parent().unwrap_or(&self.root).to_path_buf(),
+            ..Default::default()
+        });
+        orchestrator.drive_formula(&mut run, self)?;
+        Ok(run)
+    }
+    
+    /// Run a task's ready sub-tasks at once on different agents, returning
+    /// when all of them complete or one fails (see [`Orchestrator::fan_out`])
+    pub fn fan_out(&mut self, parent_id: &str) -> Result<FanOut, LoomError> {
+        if self.get(parent_id)?.is_none() {
+            return Err(WorkError::NotFound(parent_id.to_string()).into());
+        }
+        let orchestrator = Orchestrator::with_config(OrchestratorConfig {
+            working_dir: self.root.parent().unwrap_or(&self.root).to_path_buf(),
+            ..Default::default()
+        });
+        orchestrator.fan_out(parent_id, self)
+    }
+    
     /// Route a task using a formula
     pub fn route_for_formula(&mut self, formula: &Formula) -> Result<RoutingDecision, LoomError> {
-        self.router.route_for_formula(formula, &self.agents, &RoutingConstraints::default())
-            .map_err(LoomError::Routing)
+        let mut decision = self.router.route_for_formula(formula, &self.agents, &RoutingConstraints::default())
+            .map_err(LoomError::Routing)?;
+        self.attach_context_budget(&mut decision)?;
+        Ok(decision)
     }
     
     // ─────────────────────────────────────────────────────────────────────
@@ -796,6 +1938,119 @@ default = "claude"
         self.dispatch = Some(Self::load_dispatch(&self.root)?);
         Ok(())
     }
+    
+    /// Claim the ready tasks auto-dispatch should run now
+    ///
+    /// Tasks routed below their threshold get an approval question blocking
+    /// them when `[auto] approvals` is on; once it's answered "approve" they
+    /// are dispatched on a later pass, and a "reject" leaves them for agents
+    /// to claim. Every dispatch and approval request goes in the audit trail.
+    pub fn auto_dispatch(
+        &mut self,
+        config: &DispatchConfig,
+        running: &std::collections::HashMap<String, String>,
+        constraints: &RoutingConstraints,
+    ) -> Result<AutoDispatchPicks, LoomError> {
+        // The latest approval asked for each ready task
+        let ready = self.ready()?;
+        let mut asked = std::collections::HashMap::new();
+        for task in &ready {
+            if let Some(question) = self.store.latest_approval_request(&task.id)? {
+                asked.insert(task.id.clone(), question);
+            }
+        }
+        let mut approved = std::collections::HashSet::new();
+        let mut rejected = std::collections::HashSet::new();
+        for (task_id, question) in &asked {
+            match self.store.question(question)?.and_then(|q| q.decision) {
+                Some(decision) if decision == "approve" => approved.insert(task_id.clone()),
+                Some(_) => rejected.insert(task_id.clone()),
+                None => false,
+            };
+        }
+        
+        let ready = ready.into_iter().filter(|t| !rejected.contains(&t.id)).collect();
+        let picks = dispatch::pick_auto_dispatch(config, ready, running, &approved, |task| {
+            self.route_with(task, RoutingStrategy::Best, constraints).ok()
+        });
+        
+        let mut dispatched = Vec::new();
+        for pick in picks.dispatch {
+            // Claimed here so agents polling the queue don't take them too
+            if self.claim(&pick.task.id, &pick.decision.agent_id).is_err() {
+                continue;
+            }
+            let question = asked.get(&pick.task.id).filter(|_| approved.contains(&pick.task.id));
+            self.store.record_dispatch(
+                &pick.task.id,
+                &pick.decision.agent_id,
+                pick.decision.confidence,
+                pick.threshold,
+                "dispatched",
+                question.map(String::as_str),
+            )?;
+            dispatched.push(pick);
+        }
+        
+        for pick in &picks.approvals {
+            let params = CreateTask {
+                title: format!("Approve auto-dispatch of {} to {}?", pick.task.id, pick.decision.agent_id),
+                description: Some(format!(
+                    "{}\n\nRouted with {:.0}% confidence (needs {:.0}%): {}",
+                    pick.task.title,
+                    pick.decision.confidence * 100.0,
+                    pick.threshold * 100.0,
+                    pick.decision.reason
+                )),
+                priority: pick.task.priority,
+                labels: vec!["approval".to_string()],
+                ..Default::default()
+            };
+            let options = vec!["approve".to_string(), "reject".to_string()];
+            let (question, _) = self.ask(params, options, None, None, std::slice::from_ref(&pick.task.id))?;
+            self.store.record_dispatch(
+                &pick.task.id,
+                &pick.decision.agent_id,
+                pick.decision.confidence,
+                pick.threshold,
+                "approval_requested",
+                Some(&question.id),
+            )?;
+        }
+        
+        Ok(AutoDispatchPicks { dispatch: dispatched, approvals: picks.approvals })
+    }
+    
+    /// Auto-dispatch decisions, oldest first; every task's when `task_id` is None
+    pub fn dispatch_audit(&self, task_id: Option<&str>) -> Result<Vec<DispatchAudit>, LoomError> {
+        Ok(self.store.dispatch_audit(task_id)?)
+    }
+    
+    // ─────────────────────────────────────────────────────────────────────
+    // Daemon
+    // ─────────────────────────────────────────────────────────────────────
+    
+    /// Probe the daemon for this Loom: running, stale, or unresponsive, plus
+    /// its uptime, queue depth, and last error when it answers
+    pub fn daemon_status(&self) -> DaemonHealth {
+        daemon::check_health(&self.root)
+    }
+    
+    /// The daemon's most recent log entries at or above `min_level`, newest
+    /// first. Entries were redacted per `[logging]` when they were written.
+    pub fn logs(&self, limit: usize, min_level: LogLevel) -> Result<Vec<LogEntry>, LoomError> {
+        Ok(logging::recent(self.root.join("log"), limit, min_level)?)
+    }
+}
+
+/// Sort key for priorities (lower is more urgent)
+fn priority_rank(priority: Priority) -> u8 {
+    match priority {
+        Priority::Critical => 0,
+        Priority::High => 1,
+        Priority::Normal => 2,
+        Priority::Low => 3,
+    }
 }
 
 #[cfg(test)]
@@ -842,6 +2097,173 @@ mod tests {
         // It may pick different agents based on the weighted factors
     }
     
+    #[test]
+    fn test_overspent_budget_stops_claims() {
+        let dir = tempdir().unwrap();
+        let mut loom = Loom::init(dir.path()).unwrap();
+        loom.config.budgets.daily_usd = Some(1.0);
+        
+        // The spend is recorded even though it takes the budget over
+        let first = loom.create("Index the archive").unwrap();
+        loom.claim(&first.id, "claude-sonnet").unwrap();
+        let (_, overruns) = loom.complete_with_cost(&first.id, None, None, 1.5).unwrap();
+        assert_eq!(overruns.len(), 1);
+        assert_eq!(loom.get(&first.id).unwrap().unwrap().actual_cost_usd, Some(1.5));
+        
+        let second = loom.create("Index the mailing list").unwrap();
+        let err = loom.claim(&second.id, "claude-sonnet").unwrap_err();
+        assert!(matches!(err, LoomError::Budget(BudgetError::Spent(BudgetScope::All, _))), "{}", err);
+        
+        loom.config.budgets.on_exceed = config::BudgetAction::Warn;
+        loom.claim(&second.id, "claude-sonnet").unwrap();
+    }
+    
+    #[test]
+    fn test_next_task_skips_spent_budgets() {
+        let dir = tempdir().unwrap();
+        let mut loom = Loom::init(dir.path()).unwrap();
+        loom.config.budgets.labels.insert("research".to_string(), 1.0);
+        let research = |title: &str| CreateTask {
+            title: title.into(),
+            labels: vec!["research".into()],
+            priority: Priority::Critical,
+            ..Default::default()
+        };
+        
+        let first = loom.create_task(research("Survey vector stores")).unwrap();
+        loom.claim(&first.id, "claude-sonnet").unwrap();
+        loom.complete_with_cost(&first.id, None, None, 1.0).unwrap();
+        
+        // The spent label's task outranks the other but isn't handed out
+        let covered = loom.create_task(research("Survey rerankers")).unwrap();
+        let other = loom.create("Fix the login redirect").unwrap();
+        assert_eq!(loom.next_task("claude-sonnet").unwrap().unwrap().id, other.id);
+        assert!(loom.next_task("claude-sonnet").unwrap().is_none());
+        assert_eq!(loom.get(&covered.id).unwrap().unwrap().status, Status::Ready);
+    }
+    
+    #[test]
+    fn test_export_costs() {
+        let dir = tempdir().unwrap();
+        let mut loom = Loom::init(dir.path()).unwrap();
+        
+        let api = loom.create_task(CreateTask {
+            title: "Build the API, with auth".into(),
+            labels: vec!["backend".into(), "auth".into()],
+            ..Default::default()
+        }).unwrap();
+        loom.claim(&api.id, "claude-sonnet").unwrap();
+        let (_, overruns) = loom.complete_with_cost(&api.id, Some("claude-sonnet"), Some("Shipped"), 2.0).unwrap();
+        assert!(overruns.is_empty());
+        // A correction goes in the ledger as the difference
+        loom.record_cost(&api.id, 1.5).unwrap();
+        
+        // Spend stays with the agent that ran up the cost, after a handoff too
+        let tests = loom.create("-1 flaky tests").unwrap();
+        loom.claim(&tests.id, "gemini-2-flash").unwrap();
+        loom.record_cost(&tests.id, 0.5).unwrap();
+        loom.release(&tests.id).unwrap();
+        loom.claim(&tests.id, "claude-sonnet").unwrap();
+        let handed_off = chrono::Utc::now();
+        
+        // Execution costs fill in for tasks without a recorded cost, and are
+        // ignored for tasks with one
+        let docs = loom.create("=HYPERLINK(\"https://example.com\") docs").unwrap();
+        loom.agents.record_execution("gemini-2-flash", &docs.id, None, true, 60.0, None, Some(0.25)).unwrap();
+        loom.agents.record_execution("claude-sonnet", &api.id, None, true, 60.0, None, Some(9.0)).unwrap();
+        
+        let report = loom.cost_report(None).unwrap();
+        assert_eq!(report.entries.len(), 4);
+        assert!((report.total_usd() - 2.25).abs() < 1e-9);
+        let by_agent = report.by_agent();
+        assert_eq!((by_agent[0].key.as_str(), by_agent[0].amount_usd), ("claude-sonnet", 1.5));
+        assert_eq!((by_agent[1].key.as_str(), by_agent[1].amount_usd), ("gemini-2-flash", 0.75));
+        let labels: Vec<_> = report.by_label().into_iter().map(|t| (t.key, t.amount_usd)).collect();
+        assert!(labels.contains(&("auth".to_string(), 1.5)) && labels.contains(&("(unlabelled)".to_string(), 0.75)));
+        
+        // A window that starts after the API's ledger entries still ignores
+        // its execution cost
+        let recent = loom.cost_report(Some(handed_off)).unwrap();
+        assert_eq!(recent.entries.len(), 1);
+        assert_eq!(recent.entries[0].task_id, docs.id);
+        
+        // Cells a spreadsheet would evaluate are defused; amounts are left alone
+        let csv = loom.export_costs(CostFormat::Csv).unwrap();
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(lines[0], "date,recorded_at,task_id,title,agent,labels,repo,source,amount_usd");
+        assert_eq!(lines.len(), 5);
+        assert!(lines[1].contains(",\"Build the API, with auth\",claude-sonnet,backend;auth,,ledger,2.000000"));
+        assert!(lines[2].ends_with(",ledger,-0.500000"));
+        assert!(lines[3].contains(",'-1 flaky tests,gemini-2-flash,"));
+        assert!(lines[4].contains(",\"'=HYPERLINK(\"\"https://example.com\"\") docs\",gemini-2-flash,"));
+        
+        let json: serde_json::Value = serde_json::from_str(&loom.export_costs(CostFormat::Json).unwrap()).unwrap();
+        assert_eq!(json["by_task"][0]["key"], api.id.as_str());
+        assert_eq!(json["entries"][3]["source"], "execution");
+    }
+    
+    #[test]
+    fn test_rejected_review_reopens_and_costs_the_agent() {
+        let dir = tempdir().unwrap();
+        let mut loom = Loom::init(dir.path()).unwrap();
+        let agent = loom.agents().unwrap()[0].id.clone();
+        
+        let task = loom.create_task(CreateTask {
+            title: "Fix the login redirect".into(),
+            labels: vec!["bugfix".into()],
+            ..Default::default()
+        }).unwrap();
+        loom.claim(&task.id, &agent).unwrap();
+        loom.complete(&task.id, Some("Redirect fixed")).unwrap();
+        
+        // A reviewer's approval counts once
+        let approved_once = loom.agents.get_profile(&agent).unwrap().unwrap().quality;
+        loom.review(&task.id, true, "bob", None).unwrap();
+        let approved = loom.agents.get_profile(&agent).unwrap().unwrap().quality;
+        assert!(approved.by_type["bugfix"].score > approved_once.by_type.get("bugfix").map_or(0.0, |t| t.score));
+        loom.review(&task.id, true, "bob", Some("again")).unwrap();
+        let approved_twice = loom.agents.get_profile(&agent).unwrap().unwrap().quality;
+        assert_eq!(approved_twice.by_type["bugfix"].score, approved.by_type["bugfix"].score);
+        let before = approved_twice;
+        
+        let reopened = loom.review(&task.id, false, "alice", Some("still loops")).unwrap();
+        assert_eq!(reopened.status, Status::Ready);
+        assert!(reopened.agent.is_none());
+        assert!(loom.history(&task.id).unwrap().iter().any(|h| h.event == "reopened"));
+        
+        let after = loom.agents.get_profile(&agent).unwrap().unwrap().quality;
+        assert_eq!(after.rejections, before.rejections + 1);
+        assert_eq!(after.failures, before.failures + 1);
+        assert!(after.by_type["bugfix"].score < before.by_type.get("bugfix").map_or(1.0, |t| t.score));
+        
+        // Only done work can be reviewed
+        let err = loom.review(&task.id, true, "alice", None).unwrap_err();
+        assert!(matches!(err, LoomError::Work(WorkError::NotReviewable(_))), "{}", err);
+    }
+    
+    #[test]
+    fn test_fan_out_routes_children_to_different_agents() {
+        let dir = tempdir().unwrap();
+        let mut loom = Loom::init(dir.path()).unwrap();
+        
+        let parent = loom.create("Ship the billing page").unwrap();
+        for title in ["Write the API handler", "Build the form", "Add integration tests"] {
+            loom.spawn(&parent.id, title).unwrap();
+        }
+        let other = loom.create("Unrelated work").unwrap();
+        
+        let routed = loom.route_children(&parent.id, &RoutingConstraints::default()).unwrap();
+        assert_eq!(routed.len(), 3);
+        assert!(routed.iter().all(|(task, _)| task.parent.as_deref() == Some(parent.id.as_str())));
+        let agents: std::collections::HashSet<_> = routed.iter().map(|(_, d)| d.agent_id.as_str()).collect();
+        assert_eq!(agents.len(), 3);
+        
+        // With no ready children there's nothing to run
+        let fan_out = loom.fan_out(&other.id).unwrap();
+        assert_eq!((fan_out.status, fan_out.members.len()), (RunStatus::Stalled, 0));
+        assert!(loom.fan_out("lm-missing").is_err());
+    }
+    
     #[test]
     fn test_session_lifecycle() {
         let dir = tempdir().unwrap();
@@ -872,4 +2294,186 @@ mod tests {
         assert!(loom.formula("bug-fix").is_some());
         assert!(loom.formula("refactor").is_some());
     }
+    
+    #[test]
+    fn test_next_task_respects_topics_and_priority() {
+        let dir = tempdir().unwrap();
+        let mut loom = Loom::init(dir.path()).unwrap();
+        
+        loom.create_task(CreateTask {
+            title: "Write docs".to_string(),
+            labels: vec!["docs".to_string()],
+            ..Default::default()
+        }).unwrap();
+        let normal = loom.create_task(CreateTask {
+            title: "Tweak button".to_string(),
+            labels: vec!["ui".to_string()],
+            ..Default::default()
+        }).unwrap();
+        let urgent = loom.create_task(CreateTask {
+            title: "Fix broken layout".to_string(),
+            priority: Priority::Critical,
+            labels: vec!["ui".to_string()],
+            ..Default::default()
+        }).unwrap();
+        
+        loom.subscribe("cursor", &["ui".to_string()]).unwrap();
+        
+        let first = loom.next_task("cursor").unwrap().unwrap();
+        assert_eq!(first.id, urgent.id);
+        assert_eq!(first.status, Status::Claimed);
+        
+        let second = loom.next_task("cursor").unwrap().unwrap();
+        assert_eq!(second.id, normal.id);
+        
+        // Only the docs task is left, which is outside cursor's topics
+        assert!(loom.next_task("cursor").unwrap().is_none());
+    }
+    
+    #[test]
+    fn test_maintain_prunes_orphaned_sessions() {
+        let dir = tempdir().unwrap();
+        let mut loom = Loom::init(dir.path()).unwrap();
+        
+        let task = loom.create("Real task").unwrap();
+        loom.start_session(&task.id, "claude-code").unwrap();
+        loom.start_session("lm-gone", "claude-code").unwrap();
+        
+        let report = loom.maintain().unwrap();
+        assert_eq!(report.orphaned_sessions, 1);
+        assert_eq!(report.reclaimed.len(), 3);
+        assert!(loom.current_session(&task.id).unwrap().is_some());
+    }
+    
+    #[test]
+    fn test_reap_releases_idle_claims() {
+        let dir = tempdir().unwrap();
+        let mut loom = Loom::init(dir.path()).unwrap();
+        
+        let idle = loom.create("Crashed agent's task").unwrap();
+        let busy = loom.create("Still being worked").unwrap();
+        loom.claim(&idle.id, "cursor").unwrap();
+        loom.claim(&busy.id, "codex").unwrap();
+        let session = loom.start_session(&idle.id, "cursor").unwrap();
+        
+        // Backdate both claims past the timeout; codex keeps sending heartbeats
+        let hours_ago = (chrono::Utc::now() - chrono::Duration::hours(3)).to_rfc3339();
+        let work = rusqlite::Connection::open(loom.root().join("work.db")).unwrap();
+        work.execute("UPDATE tasks SET updated_at = ?1", [&hours_ago]).unwrap();
+        work.execute("UPDATE leases SET expires_at = ?1", [&hours_ago]).unwrap();
+        rusqlite::Connection::open(loom.root().join("memory.db")).unwrap()
+            .execute("UPDATE sessions SET started_at = ?1", [&hours_ago]).unwrap();
+        loom.heartbeat("codex", None).unwrap();
+        
+        assert_eq!(loom.reap_stale_claims(true).unwrap().len(), 1);
+        assert_eq!(loom.get(&idle.id).unwrap().unwrap().status, Status::Claimed);
+        
+        let reaped = loom.reap_stale_claims(false).unwrap();
+        assert_eq!(reaped.len(), 1);
+        assert_eq!(reaped[0].task_id, idle.id);
+        assert_eq!(reaped[0].session_id.as_deref(), Some(session.id.as_str()));
+        assert_eq!(loom.get(&idle.id).unwrap().unwrap().status, Status::Ready);
+        assert_eq!(loom.get(&busy.id).unwrap().unwrap().status, Status::Claimed);
+        assert!(loom.recoverable_sessions().unwrap().iter().any(|s| s.id == session.id));
+    }
+    
+    #[test]
+    fn test_overdue_question_applies_default() {
+        let dir = tempdir().unwrap();
+        let mut loom = Loom::init(dir.path()).unwrap();
+        
+        let work = loom.create("Build pricing page").unwrap();
+        let (question, _) = loom.ask(
+            CreateTask { title: "Monthly or annual billing first?".to_string(), ..Default::default() },
+            vec!["monthly".to_string(), "annual".to_string()],
+            Some(chrono::Utc::now() - chrono::Duration::minutes(1)),
+            Some("monthly".to_string()),
+            std::slice::from_ref(&work.id),
+        ).unwrap();
+        assert_eq!(loom.get(&work.id).unwrap().unwrap().status, Status::Blocked);
+        
+        // Agents don't pick up questions
+        assert!(loom.next_task("claude-code").unwrap().is_none());
+        
+        let applied = loom.apply_default_decisions().unwrap();
+        assert_eq!(applied, vec![(question.id.clone(), "monthly".to_string())]);
+        assert_eq!(loom.get(&work.id).unwrap().unwrap().status, Status::Ready);
+        
+        let context = loom.get_preferences(&work.id).unwrap().unwrap();
+        assert!(context.contains("**Decision:** monthly"));
+    }
+    
+    #[test]
+    fn test_create_from_template() {
+        let dir = tempdir().unwrap();
+        let mut loom = Loom::init(dir.path()).unwrap();
+        std::fs::write(loom.root().join("templates/bug.toml"), r#"
+title = "Fix {{area}}"
+labels = ["bug"]
+priority = "high"
+
+[[children]]
+title = "Reproduce {{area}} bug"
+
+[[children]]
+title = "Add regression test"
+labels = ["testing"]
+"#).unwrap();
+        
+        assert_eq!(loom.templates().unwrap()[0].name, "bug");
+        let epic = loom.create("Stabilize checkout").unwrap();
+        
+        let vars = std::collections::HashMap::from([("area".to_string(), "cart".to_string())]);
+        let tasks = loom.create_from_template("bug", &vars, Some(&epic.id)).unwrap();
+        assert_eq!(tasks.len(), 3);
+        assert_eq!(tasks[0].title, "Fix cart");
+        assert_eq!(tasks[0].parent.as_deref(), Some(epic.id.as_str()));
+        assert_eq!(loom.children(&tasks[0].id).unwrap().len(), 2);
+        assert!(tasks.iter().all(|t| t.priority == Priority::High));
+        
+        // A missing variable creates nothing
+        let before = loom.list().unwrap().len();
+        assert!(loom.create_from_template("bug", &Default::default(), None).is_err());
+        assert!(loom.create_from_template("nope", &vars, None).is_err());
+        assert_eq!(loom.list().unwrap().len(), before);
+    }
+    
+    #[test]
+    fn test_auto_dispatch_asks_for_approval_below_threshold() {
+        let dir = tempdir().unwrap();
+        let mut loom = Loom::init(dir.path()).unwrap();
+        let task = loom.create("Migrate the billing tables").unwrap();
+        
+        // No route is ever this sure, so the task needs a human's go-ahead
+        let mut config = DispatchConfig::default();
+        config.auto.min_confidence = 1.5;
+        config.auto.approvals = true;
+        config.auto.max_concurrent = 5;
+        let running = std::collections::HashMap::new();
+        let constraints = RoutingConstraints::default();
+        
+        let picks = loom.auto_dispatch(&config, &running, &constraints).unwrap();
+        assert!(picks.dispatch.is_empty());
+        assert_eq!(picks.approvals.len(), 1);
+        assert!(!loom.ready().unwrap().iter().any(|t| t.id == task.id));
+        
+        let audit = loom.dispatch_audit(Some(&task.id)).unwrap();
+        assert_eq!(audit.len(), 1);
+        assert_eq!(audit[0].action, "approval_requested");
+        assert_eq!(audit[0].threshold, 1.5);
+        let question = audit[0].question.clone().unwrap();
+        
+        // The open question isn't asked again, and isn't dispatched itself
+        let picks = loom.auto_dispatch(&config, &running, &constraints).unwrap();
+        assert!(picks.dispatch.is_empty() && picks.approvals.is_empty());
+        
+        loom.answer(&question, "approve", None, "alice").unwrap();
+        let picks = loom.auto_dispatch(&config, &running, &constraints).unwrap();
+        assert_eq!(picks.dispatch.len(), 1);
+        assert_eq!(loom.get(&task.id).unwrap().unwrap().status, Status::Claimed);
+        
+        let audit = loom.dispatch_audit(Some(&task.id)).unwrap();
+        assert_eq!(audit.last().unwrap().action, "dispatched");
+        assert_eq!(audit.last().unwrap().question.as_deref(), Some(question.as_str()));
+    }
 }
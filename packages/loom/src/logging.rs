@@ -0,0 +1,279 @@
+//! Daemon Logs
+//!
+//! Structured daemon log as JSON lines in `.loom/log/daemon.jsonl`.
+//! Entries are redacted before they reach disk: values of the configured
+//! fields (task titles and evidence by default) and anything matching the
+//! redaction patterns (tokens, keys). The log rotates at a size cap and keeps
+//! a fixed number of old files, so it can't fill the disk.
+//!
+//! The files themselves are plain text, not encrypted: redaction is the only
+//! protection, and whatever it doesn't match is written as is. `hash`
+//! redaction is an unkeyed SHA-256 prefix, so anyone who can guess a short
+//! value can confirm it.
+
+use std::collections::{BTreeMap, HashSet};
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use crate::config::{LoggingConfig, Redaction};
+
+/// Current log file in the log directory; rotated files are `daemon.1.jsonl`, ...
+pub const LOG_FILE: &str = "daemon.jsonl";
+
+#[derive(Error, Debug)]
+pub enum LogError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    
+    #[error("Invalid redaction pattern: {0}")]
+    Pattern(#[from] regex::Error),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogLevel {
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "info" => Some(LogLevel::Info),
+            "warn" => Some(LogLevel::Warn),
+            "error" => Some(LogLevel::Error),
+            _ => None,
+        }
+    }
+}
+
+/// One log line
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogEntry {
+    pub at: DateTime<Utc>,
+    pub level: LogLevel,
+    /// What happened, as a stable key (e.g. "request", "schedule")
+    pub event: String,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub fields: BTreeMap<String, String>,
+}
+
+impl LogEntry {
+    pub fn new(level: LogLevel, event: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            at: Utc::now(),
+            level,
+            event: event.into(),
+            message: message.into(),
+            fields: BTreeMap::new(),
+        }
+    }
+    
+    pub fn info(event: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::new(LogLevel::Info, event, message)
+    }
+    
+    pub fn error(event: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::new(LogLevel::Error, event, message)
+    }
+    
+    /// Attach a field
+    pub fn field(mut self, key: impl Into<String>, value: impl ToString) -> Self {
+        self.fields.insert(key.into(), value.to_string());
+        self
+    }
+}
+
+/// Applies `[logging]` redaction rules to entries
+pub struct Redactor {
+    fields: HashSet<String>,
+    patterns: Vec<Regex>,
+    redaction: Redaction,
+}
+
+impl Redactor {
+    pub fn new(config: &LoggingConfig) -> Result<Self, LogError> {
+        Ok(Self {
+            fields: config.redact_fields.iter().cloned().collect(),
+            patterns: config
+                .redact_patterns
+                .iter()
+                .map(|p| Regex::new(p))
+                .collect::<Result<_, _>>()?,
+            redaction: config.redaction,
+        })
+    }
+    
+    /// Redact sensitive field values (wherever they also appear in the
+    /// message) and pattern matches
+    pub fn redact(&self, mut entry: LogEntry) -> LogEntry {
+        for (key, value) in entry.fields.iter_mut() {
+            if self.fields.contains(key) && !value.is_empty() {
+                let masked = self.mask(value);
+                entry.message = entry.message.replace(value.as_str(), &masked);
+                *value = masked;
+            } else {
+                *value = self.scrub(value);
+            }
+        }
+        entry.message = self.scrub(&entry.message);
+        entry
+    }
+    
+    fn scrub(&self, text: &str) -> String {
+        self.patterns.iter().fold(text.to_string(), |text, pattern| {
+            pattern.replace_all(&text, |c: &regex::Captures| self.mask(&c[0])).into_owned()
+        })
+    }
+    
+    fn mask(&self, value: &str) -> String {
+        match self.redaction {
+            Redaction::Mask => "[redacted]".to_string(),
+            Redaction::Hash => format!("[redacted:{}]", &hex::encode(Sha256::digest(value.as_bytes()))[..8]),
+        }
+    }
+}
+
+/// The daemon's log writer
+pub struct DaemonLog {
+    dir: PathBuf,
+    redactor: Redactor,
+    max_file_bytes: u64,
+    max_files: usize,
+}
+
+impl DaemonLog {
+    /// Log into `dir` (created if needed) with the given rules
+    pub fn open(dir: impl AsRef<Path>, config: &LoggingConfig) -> Result<Self, LogError> {
+        let dir = dir.as_ref().to_path_buf();
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self {
+            dir,
+            redactor: Redactor::new(config)?,
+            max_file_bytes: config.max_file_bytes.max(1),
+            max_files: config.max_files,
+        })
+    }
+    
+    /// Redact and append an entry, rotating first if the log is full.
+    /// The redacted message is echoed to stderr for foreground runs.
+    pub fn write(&self, entry: LogEntry) -> Result<(), LogError> {
+        let entry = self.redactor.redact(entry);
+        eprintln!("{}", entry.message);
+        
+        let line = serde_json::to_string(&entry).map_err(std::io::Error::from)? + "\n";
+        let path = self.dir.join(LOG_FILE);
+        let size = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+        if size > 0 && size + line.len() as u64 > self.max_file_bytes {
+            self.rotate()?;
+        }
+        
+        let mut file = std::fs::OpenOptions::new().create(true).append(true).open(&path)?;
+        file.write_all(line.as_bytes())?;
+        Ok(())
+    }
+    
+    /// Shift `daemon.N.jsonl` up by one, dropping the oldest beyond `max_files`
+    fn rotate(&self) -> Result<(), LogError> {
+        let current = self.dir.join(LOG_FILE);
+        if self.max_files == 0 {
+            std::fs::remove_file(current)?;
+            return Ok(());
+        }
+        
+        let _ = std::fs::remove_file(rotated_path(&self.dir, self.max_files));
+        for n in (1..self.max_files).rev() {
+            let from = rotated_path(&self.dir, n);
+            if from.exists() {
+                std::fs::rename(from, rotated_path(&self.dir, n + 1))?;
+            }
+        }
+        std::fs::rename(current, rotated_path(&self.dir, 1))?;
+        Ok(())
+    }
+}
+
+fn rotated_path(dir: &Path, n: usize) -> PathBuf {
+    dir.join(format!("daemon.{}.jsonl", n))
+}
+
+/// The most recent `limit` entries at or above `min_level`, newest first,
+/// reading back through rotated files as needed
+pub fn recent(dir: impl AsRef<Path>, limit: usize, min_level: LogLevel) -> Result<Vec<LogEntry>, LogError> {
+    let dir = dir.as_ref();
+    let files = std::iter::once(dir.join(LOG_FILE)).chain((1..).map(|n| rotated_path(dir, n)));
+    
+    let mut entries = Vec::new();
+    for path in files {
+        if entries.len() >= limit {
+            break;
+        }
+        let file = match std::fs::File::open(&path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => break,
+            Err(e) => return Err(e.into()),
+        };
+        
+        let mut in_file: Vec<LogEntry> = std::io::BufReader::new(file)
+            .lines()
+            .map_while(Result::ok)
+            .filter_map(|line| serde_json::from_str::<LogEntry>(&line).ok())
+            .filter(|entry| entry.level >= min_level)
+            .collect();
+        in_file.reverse();
+        entries.extend(in_file.into_iter().take(limit - entries.len()));
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_redaction_and_rotation() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = LoggingConfig { max_file_bytes: 400, max_files: 2, ..Default::default() };
+        let log = DaemonLog::open(dir.path(), &config).unwrap();
+        
+        log.write(
+            LogEntry::info("request", "Created lm-a1b2: Rotate prod password")
+                .field("id", "lm-a1b2")
+                .field("title", "Rotate prod password")
+                .field("evidence", "token=abc123"),
+        ).unwrap();
+        log.write(LogEntry::error("schedule", "Sync failed: api_key=sk-live-0123456789abcdef")).unwrap();
+        
+        let entries = recent(dir.path(), 10, LogLevel::Info).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].message, "Sync failed: [redacted]");
+        assert_eq!(entries[1].message, "Created lm-a1b2: [redacted]");
+        assert_eq!(entries[1].fields["id"], "lm-a1b2");
+        assert_eq!(entries[1].fields["title"], "[redacted]");
+        let on_disk = std::fs::read_to_string(dir.path().join(LOG_FILE)).unwrap();
+        assert!(!on_disk.contains("password") && !on_disk.contains("abc123") && !on_disk.contains("sk-live"));
+        
+        assert_eq!(recent(dir.path(), 10, LogLevel::Error).unwrap().len(), 1);
+        
+        // Only the current log and two rotated files are kept
+        for n in 0..20 {
+            log.write(LogEntry::info("tick", format!("tick {}", n))).unwrap();
+        }
+        assert!(dir.path().join("daemon.2.jsonl").exists());
+        assert!(!dir.path().join("daemon.3.jsonl").exists());
+        let entries = recent(dir.path(), 5, LogLevel::Info).unwrap();
+        assert_eq!(entries[0].message, "tick 19");
+        assert_eq!(entries[4].message, "tick 15");
+        
+        let hashed = Redactor::new(&LoggingConfig { redaction: Redaction::Hash, ..Default::default() }).unwrap();
+        let entry = hashed.redact(LogEntry::info("request", "").field("title", "secret plan"));
+        assert!(entry.fields["title"].starts_with("[redacted:") && entry.fields["title"].len() == 19);
+    }
+}
@@ -0,0 +1,60 @@
+//! Maintenance
+//!
+//! Garbage collection for the Loom databases. memory.db and agents.db
+//! accumulate orphaned checkpoints, sessions pointing at tasks that no longer
+//! exist, and stale execution history. `Loom::maintain()` prunes them per the
+//! `[maintenance]` retention config and VACUUMs each database.
+
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+
+/// What a maintenance pass removed and reclaimed
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MaintenanceReport {
+    /// Sessions whose task no longer exists
+    pub orphaned_sessions: u32,
+    /// Finished sessions older than the retention window
+    pub expired_sessions: u32,
+    /// Checkpoints whose session no longer exists
+    pub orphaned_checkpoints: u32,
+    /// Recovery queue entries whose session no longer exists
+    pub orphaned_recovery: u32,
+    /// Agent sessions whose task no longer exists
+    pub orphaned_agent_sessions: u32,
+    /// Execution records older than the retention window
+    pub stale_history: u32,
+    /// Bytes reclaimed by VACUUM, per database file
+    pub reclaimed: Vec<(String, u64)>,
+}
+
+impl MaintenanceReport {
+    /// Total rows pruned
+    pub fn rows_pruned(&self) -> u32 {
+        self.orphaned_sessions
+            + self.expired_sessions
+            + self.orphaned_checkpoints
+            + self.orphaned_recovery
+            + self.orphaned_agent_sessions
+            + self.stale_history
+    }
+    
+    /// Total bytes reclaimed across all databases
+    pub fn bytes_reclaimed(&self) -> u64 {
+        self.reclaimed.iter().map(|(_, b)| b).sum()
+    }
+}
+
+/// Database size in bytes according to SQLite's page accounting
+fn db_size(conn: &Connection) -> rusqlite::Result<u64> {
+    let pages: i64 = conn.query_row("PRAGMA page_count", [], |row| row.get(0))?;
+    let page_size: i64 = conn.query_row("PRAGMA page_size", [], |row| row.get(0))?;
+    Ok((pages * page_size) as u64)
+}
+
+/// Checkpoint the WAL and VACUUM, returning bytes reclaimed
+pub(crate) fn vacuum(conn: &Connection) -> rusqlite::Result<u64> {
+    let before = db_size(conn)?;
+    conn.execute_batch("PRAGMA wal_checkpoint(TRUNCATE); VACUUM;")?;
+    let after = db_size(conn)?;
+    Ok(before.saturating_sub(after))
+}
@@ -14,7 +14,9 @@ use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::io::{BufRead, Write};
 
-use crate::{Loom, CreateTask, Status, Priority, RoutingStrategy, RoutingConstraints, SessionStatus};
+use crate::{Loom, CreateTask, Status, Priority, RoutingStrategy, RoutingConstraints, SessionStatus, AgentAction, Recurrence, LogLevel, PauseScope, Outcome, PlanStep};
+use crate::plan;
+use crate::quota::ANONYMOUS_AGENT;
 use crate::ui_resources::UiRegistry;
 
 /// Tool UI metadata for MCP Apps
@@ -95,12 +97,13 @@ pub fn list_tools() -> Vec<ToolDefinition> {
                     "description": { "type": "string", "description": "Task description (optional)" },
                     "priority": { "type": "string", "enum": ["critical", "high", "normal", "low"], "description": "Task priority (default: normal)" },
                     "labels": { "type": "array", "items": { "type": "string" }, "description": "Labels for categorization and routing" },
-                    "parent": { "type": "string", "description": "Parent task ID for sub-tasks" }
+                    "parent": { "type": "string", "description": "Parent task ID for sub-tasks" },
+                    "agent": { "type": "string", "description": "Your agent ID (counts against your [limits] quota)" }
                 },
                 "required": ["title"]
             }),
         ),
-        tool("loom_claim", "Claim a task for this agent to work on", json!({
+        tool("loom_claim", "Claim a task for this agent to work on. The claim is a lease: renew it with loom_renew while working, or another agent can take the task over once it expires.", json!({
             "type": "object",
             "properties": {
                 "task_id": { "type": "string" },
@@ -108,6 +111,20 @@ pub fn list_tools() -> Vec<ToolDefinition> {
             },
             "required": ["task_id", "agent"]
         })),
+        tool("loom_renew", "Extend this agent's lease on a claimed task", json!({
+            "type": "object",
+            "properties": {
+                "task_id": { "type": "string" },
+                "agent": { "type": "string", "description": "Agent ID holding the lease" }
+            },
+            "required": ["task_id", "agent"]
+        })),
+        tool("loom_leases", "Leases expiring soon or already expired; expired tasks can be claimed by another agent", json!({
+            "type": "object",
+            "properties": {
+                "within_mins": { "type": "number", "description": "Include leases expiring within this many minutes (default: 0, expired only)" }
+            }
+        })),
         tool("loom_release", "Release a claimed task back to ready status", json!({
             "type": "object",
             "properties": { "task_id": { "type": "string" } },
@@ -117,8 +134,20 @@ pub fn list_tools() -> Vec<ToolDefinition> {
             "type": "object",
             "properties": {
                 "task_id": { "type": "string" },
+                "agent": { "type": "string", "description": "Agent ID completing the task; it must still hold the lease" },
                 "evidence": { "type": "string", "description": "Completion evidence (commit hash, URL, etc.)" },
-                "cost_usd": { "type": "number", "description": "Actual cost in USD (for tracking)" }
+                "cost_usd": { "type": "number", "description": "Actual cost in USD (for tracking; checked against [budgets])" },
+                "outcome": {
+                    "type": "object",
+                    "description": "What the work produced; merged with what Loom gathers from the task's sessions",
+                    "properties": {
+                        "artifacts": { "type": "array", "items": { "type": "string" } },
+                        "pr_urls": { "type": "array", "items": { "type": "string" } },
+                        "tests_added": { "type": "integer" },
+                        "files_touched": { "type": "array", "items": { "type": "string" } },
+                        "follow_ups": { "type": "array", "items": { "type": "string" }, "description": "IDs of follow-up tasks" }
+                    }
+                }
             },
             "required": ["task_id"]
         })),
@@ -127,14 +156,34 @@ pub fn list_tools() -> Vec<ToolDefinition> {
             "properties": { "task_id": { "type": "string" } },
             "required": ["task_id"]
         })),
+        tool("loom_review", "Approve or reject a completed task. A rejection reopens it; either way the verdict feeds the completing agent's routing quality for the task's labels.", json!({
+            "type": "object",
+            "properties": {
+                "task_id": { "type": "string" },
+                "verdict": { "type": "string", "enum": ["approve", "reject"] },
+                "reviewer": { "type": "string", "description": "Who reviewed the work" },
+                "note": { "type": "string", "description": "Why, recorded as a comment" }
+            },
+            "required": ["task_id", "verdict", "reviewer"]
+        })),
         tool("loom_spawn", "Create a sub-task under a parent task", json!({
             "type": "object",
             "properties": {
                 "parent_id": { "type": "string" },
-                "title": { "type": "string" }
+                "title": { "type": "string" },
+                "agent": { "type": "string", "description": "Your agent ID (counts against your [limits] quota)" }
             },
             "required": ["parent_id", "title"]
         })),
+        tool("loom_import_plan", "Turn a markdown plan (headings, numbered steps, checklists) into sub-tasks of a task, each blocked by the step before it. Re-import the edited plan to update the same tasks.", json!({
+            "type": "object",
+            "properties": {
+                "task_id": { "type": "string", "description": "Task the plan is for" },
+                "markdown": { "type": "string" },
+                "agent": { "type": "string", "description": "Your agent ID (counts against your [limits] quota)" }
+            },
+            "required": ["task_id", "markdown"]
+        })),
         
         // ─────────────────────────────────────────────────────────────────
         // Queries (with MCP Apps UI support for task visualization)
@@ -172,6 +221,26 @@ pub fn list_tools() -> Vec<ToolDefinition> {
             }
         })),
         
+        // ─────────────────────────────────────────────────────────────────
+        // Work Queues (labels as topics)
+        // ─────────────────────────────────────────────────────────────────
+        tool("loom_subscribe", "Subscribe an agent to label topics so loom_next_task pulls matching work", json!({
+            "type": "object",
+            "properties": {
+                "agent": { "type": "string", "description": "Agent ID subscribing" },
+                "labels": { "type": "array", "items": { "type": "string" }, "description": "Label topics to subscribe to" },
+                "unsubscribe": { "type": "boolean", "description": "Remove these topics instead (all topics if labels is empty)" }
+            },
+            "required": ["agent"]
+        })),
+        tool("loom_next_task", "Atomically select, claim, and return the best ready task for an agent from its subscribed topics. No list-then-claim race.", json!({
+            "type": "object",
+            "properties": {
+                "agent": { "type": "string", "description": "Agent ID pulling work" }
+            },
+            "required": ["agent"]
+        })),
+        
         // ─────────────────────────────────────────────────────────────────
         // Dependencies
         // ─────────────────────────────────────────────────────────────────
@@ -191,6 +260,47 @@ pub fn list_tools() -> Vec<ToolDefinition> {
             },
             "required": ["task_id", "blocked_by"]
         })),
+        tool("loom_pause", "Freeze a label or repo (e.g. deploy during an incident): its tasks leave loom_ready and routing and can't be claimed. Omit label and repo to list pauses.", json!({
+            "type": "object",
+            "properties": {
+                "label": { "type": "string", "description": "Label to pause" },
+                "repo": { "type": "string", "description": "Repository to pause" },
+                "reason": { "type": "string", "description": "Why (shown in summaries)" },
+                "agent": { "type": "string", "description": "Who is pausing" }
+            }
+        })),
+        tool("loom_unpause", "Lift a pause on a label or repo", json!({
+            "type": "object",
+            "properties": {
+                "label": { "type": "string" },
+                "repo": { "type": "string" }
+            }
+        })),
+        tool("loom_comment", "Leave a comment on a task (handoff notes, findings, questions for the next agent) without touching its description", json!({
+            "type": "object",
+            "properties": {
+                "task_id": { "type": "string" },
+                "body": { "type": "string" },
+                "agent": { "type": "string", "description": "Comment author" }
+            },
+            "required": ["task_id", "body"]
+        })),
+        tool("loom_history", "A task's audit log: every state transition (created, claimed, released, completed, cancelled, blocked, priority changes) with the agent and time, oldest first. Use it to debug multi-agent handoffs.", json!({
+            "type": "object",
+            "properties": { "task_id": { "type": "string" } },
+            "required": ["task_id"]
+        })),
+        tool("loom_comments", "Read a task's comment thread, oldest first. Omit task_id for the latest comments across all tasks.", json!({
+            "type": "object",
+            "properties": {
+                "task_id": { "type": "string" },
+                "limit": { "type": "integer", "description": "Max comments without task_id (default 20)" }
+            }
+        })),
+        tool("loom_doctor", "Check the work store for problems: lists dependency cycles (tasks that wait on each other and can never become ready)", json!({
+            "type": "object",
+            "properties": {}
+        })),
         
         // ─────────────────────────────────────────────────────────────────
         // Smart Routing
@@ -199,8 +309,11 @@ pub fn list_tools() -> Vec<ToolDefinition> {
             "type": "object",
             "properties": {
                 "task_id": { "type": "string" },
-                "strategy": { "type": "string", "enum": ["best", "cheapest", "fastest"], "default": "best" },
-                "max_cost": { "type": "number", "description": "Maximum cost in dollars" }
+                "strategy": { "type": "string", "enum": ["best", "cheapest", "fastest", "learned", "least_loaded", "round_robin"], "default": "best" },
+                "max_cost": { "type": "number", "description": "Maximum cost in dollars" },
+                "prefer_agents": { "type": "array", "items": { "type": "string" }, "description": "Agents to pick among when any is eligible" },
+                "exclude_agents": { "type": "array", "items": { "type": "string" }, "description": "Agents never to route to" },
+                "sticky": { "type": "boolean", "description": "Route a sub-task to the agent that did its parent", "default": false }
             },
             "required": ["task_id"]
         })),
@@ -208,6 +321,70 @@ pub fn list_tools() -> Vec<ToolDefinition> {
             "type": "object",
             "properties": {}
         })),
+        tool("loom_heartbeat", "Report that an agent is alive. Once an agent has sent one, routing skips it if it goes quiet for longer than [heartbeat] timeout-secs", json!({
+            "type": "object",
+            "properties": {
+                "agent": { "type": "string", "description": "Agent ID" },
+                "status": { "type": "string", "description": "Free-form status (e.g. idle, busy)" }
+            },
+            "required": ["agent"]
+        })),
+        tool("loom_probe_agents", "Run agents' CLIs with --version to check they exist, marking missing or unresponsive ones unavailable and recording how long the others take to start", json!({
+            "type": "object",
+            "properties": {
+                "agent": { "type": "string", "description": "Agent ID (all agents if omitted)" },
+                "timeout_secs": { "type": "integer", "description": "Seconds each CLI has to answer", "default": 10 }
+            }
+        })),
+        tool("loom_register_agent", "Register an agent (e.g. a custom local model) or reconfigure one, without editing models.toml. Takes the fields of a models.toml entry; a known agent keeps its quality history", json!({
+            "type": "object",
+            "properties": {
+                "id": { "type": "string", "description": "Agent ID" },
+                "family": { "type": "string", "description": "Model family (claude, gpt, gemini, or anything else)" },
+                "name": { "type": "string", "description": "Display name" },
+                "cli": { "type": "string", "description": "Command that runs the agent" },
+                "tier": { "type": "string", "enum": ["fast", "balanced", "powerful"], "default": "balanced" },
+                "input_per_1k": { "type": "number", "description": "Input cost per 1K tokens (USD, 0 for local models)" },
+                "output_per_1k": { "type": "number", "description": "Output cost per 1K tokens (USD)" },
+                "output_ratio": { "type": "number", "description": "Typical output/input token ratio", "default": 2.5 },
+                "planning": { "type": "number", "description": "Capability, 0.0 - 1.0", "default": 0.7 },
+                "coding": { "type": "number", "default": 0.7 },
+                "debugging": { "type": "number", "default": 0.7 },
+                "ui": { "type": "number", "default": 0.7 },
+                "docs": { "type": "number", "default": 0.7 },
+                "refactor": { "type": "number", "default": 0.7 },
+                "testing": { "type": "number", "default": 0.7 },
+                "max_context": { "type": "integer", "default": 128000 },
+                "mcp": { "type": "boolean", "default": false },
+                "checkpoints": { "type": "boolean", "default": false },
+                "git_aware": { "type": "boolean", "default": false },
+                "sub_agents": { "type": "boolean", "default": false },
+                "max_concurrent": { "type": "integer", "default": 3 }
+            },
+            "required": ["id", "family", "cli", "input_per_1k", "output_per_1k"]
+        })),
+        tool("loom_pricing_sync", "Update models.toml and agent costs to the prices in effect today, from .loom/pricing.toml or the bundled pricing table", json!({
+            "type": "object",
+            "properties": {
+                "path": { "type": "string", "description": "Pricing table to use instead" },
+                "dry_run": { "type": "boolean", "description": "Only report what would change" }
+            }
+        })),
+        tool("loom_reprice", "Recompute recorded execution costs from their token counts at the price in effect when they ran; returns totals before and after, per month", json!({
+            "type": "object",
+            "properties": {
+                "path": { "type": "string", "description": "Pricing table to use instead" },
+                "dry_run": { "type": "boolean", "description": "Only report the new totals" }
+            }
+        })),
+        tool("loom_simulate_routing", "Dry-run a candidate models.toml: replay recent tasks and report how assignments, costs, and predicted success would shift", json!({
+            "type": "object",
+            "properties": {
+                "config_path": { "type": "string", "description": "Path to the candidate models.toml" },
+                "recent": { "type": "number", "description": "Number of recent tasks to replay (default: 100)" }
+            },
+            "required": ["config_path"]
+        })),
         
         // ─────────────────────────────────────────────────────────────────
         // Sessions & Memory
@@ -228,6 +405,22 @@ pub fn list_tools() -> Vec<ToolDefinition> {
             },
             "required": ["session_id"]
         })),
+        tool("loom_session_pause", "Pause a session (a break, waiting on a human); paused time doesn't count as worked", json!({
+            "type": "object",
+            "properties": { "session_id": { "type": "string" } },
+            "required": ["session_id"]
+        })),
+        tool("loom_session_unpause", "Resume a paused session", json!({
+            "type": "object",
+            "properties": { "session_id": { "type": "string" } },
+            "required": ["session_id"]
+        })),
+        tool("loom_time", "Active working time (sessions minus pauses) for one session, or summed per task and per agent", json!({
+            "type": "object",
+            "properties": {
+                "session_id": { "type": "string", "description": "One session's time (default: totals per task and per agent)" }
+            }
+        })),
         tool("loom_checkpoint", "Create a checkpoint (save progress for crash recovery)", json!({
             "type": "object",
             "properties": {
@@ -258,6 +451,40 @@ pub fn list_tools() -> Vec<ToolDefinition> {
             "properties": { "name": { "type": "string" } },
             "required": ["name"]
         })),
+        tool("loom_run_formula", "Run a formula: creates a task for the run and a sub-task per step, each blocked by the step before it. With execute, the orchestrator then claims, runs, verifies and completes each step in turn (blocking until the run finishes); without it, agents take the steps from the queue.", json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string", "description": "Formula name" },
+                "variables": { "type": "object", "description": "Values for the formula's variables, checked against its variables_schema (see loom_formula) before any task is created" },
+                "execute": { "type": "boolean", "description": "Run the steps through the orchestrator now (default: false, only create the tasks)" },
+                "preview": { "type": "boolean", "description": "Return the task tree, per-step routing, and cost estimate without creating anything (default: false)" },
+                "agent": { "type": "string", "description": "Your agent ID (counts against your [limits] quota)" }
+            },
+            "required": ["name"]
+        })),
+        tool("loom_template", "Create a task tree from a template in .loom/templates/ (title pattern, labels, priority, child tasks). Omit name to list templates.", json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string", "description": "Template name (omit to list templates)" },
+                "variables": { "type": "object", "additionalProperties": { "type": "string" }, "description": "Values for the template's {{placeholders}}" },
+                "parent": { "type": "string", "description": "Create the tree under this task" },
+                "preview": { "type": "boolean", "description": "Show the tasks that would be created without creating them (default: false)" },
+                "agent": { "type": "string", "description": "Your agent ID (counts against your [limits] quota)" }
+            }
+        })),
+        tool("loom_schedule", "Schedule a task to be created on a cron expression (recurring) or at due_at (once); the daemon creates it as a ready task when due. Omit title to list schedules.", json!({
+            "type": "object",
+            "properties": {
+                "title": { "type": "string", "description": "Task title (omit to list schedules)" },
+                "cron": { "type": "string", "description": "5-field cron expression in UTC, e.g. \"0 9 * * mon\" or \"@daily\"" },
+                "due_at": { "type": "string", "description": "RFC 3339 time to create the task once" },
+                "description": { "type": "string" },
+                "priority": { "type": "string", "enum": ["critical", "high", "normal", "low"] },
+                "labels": { "type": "array", "items": { "type": "string" } },
+                "parent": { "type": "string", "description": "Parent for each created task" },
+                "remove": { "type": "string", "description": "Schedule ID to remove instead" }
+            }
+        })),
         
         // ─────────────────────────────────────────────────────────────────
         // Learning & Feedback
@@ -269,9 +496,9 @@ pub fn list_tools() -> Vec<ToolDefinition> {
                 "task_id": { "type": "string" },
                 "task_type": { "type": "string", "description": "Type of task (coding, debugging, planning, etc.)" },
                 "success": { "type": "boolean" },
-                "duration_secs": { "type": "number" }
+                "duration_secs": { "type": "number", "description": "Default: the agent's tracked session time on the task" }
             },
-            "required": ["agent_id", "task_id", "success", "duration_secs"]
+            "required": ["agent_id", "task_id", "success"]
         })),
         
         // ─────────────────────────────────────────────────────────────────
@@ -316,10 +543,63 @@ pub fn list_tools() -> Vec<ToolDefinition> {
                 "task_type": { "type": "string", "description": "Filter by task type (bug, feature, task, refactor)" }
             }
         })),
+        tool("loom_utilization", "Per-agent, per-day utilization: time in active sessions vs idle, tasks completed, and cost", json!({
+            "type": "object",
+            "properties": {
+                "days": { "type": "number", "description": "Look-back window in days (default: 7)" },
+                "agent": { "type": "string", "description": "Only this agent ID" }
+            }
+        })),
+        tool("loom_export_costs", "Recorded spend per task, agent and label over time, as CSV or JSON for finance reporting", json!({
+            "type": "object",
+            "properties": {
+                "format": { "type": "string", "enum": ["csv", "json"], "description": "Output format (default: json)" },
+                "days": { "type": "number", "description": "Look-back window in days (default: all time)" }
+            }
+        })),
         
         // ─────────────────────────────────────────────────────────────────
         // GSD-Inspired: Discuss & Verify (Pre-Planning)
         // ─────────────────────────────────────────────────────────────────
+        tool("loom_maintain", "Garbage-collect orphaned sessions, checkpoints, and stale execution history per retention config, then VACUUM the databases", json!({
+            "type": "object",
+            "properties": {}
+        })),
+        tool("loom_quota", "Check an agent's rate limits and quotas: creates and claims in the last minute, open tasks it created, and its [limits]", json!({
+            "type": "object",
+            "properties": {
+                "agent": { "type": "string", "description": "Agent ID (default: anonymous, for calls that don't name an agent)" }
+            }
+        })),
+        tool("loom_budgets", "Today's spend (UTC) against each daily budget under [budgets]: overall, per label, and per repo", json!({
+            "type": "object",
+            "properties": {}
+        })),
+        tool("loom_daemon_status", "Check the daemon for this repo: running, stale (died uncleanly), or unresponsive, with uptime, queue depth, and last error", json!({
+            "type": "object",
+            "properties": {}
+        })),
+        tool("loom_logs", "Recent daemon log entries, newest first. Task titles, evidence, and secrets are redacted per [logging].", json!({
+            "type": "object",
+            "properties": {
+                "limit": { "type": "integer", "description": "Entries to return (default: 50)" },
+                "level": { "type": "string", "enum": ["info", "warn", "error"], "description": "Minimum level (default: info)" }
+            }
+        })),
+        tool("loom_verify_log", "Verify the hash-chained evidence log. Detects edited, deleted, or re-signed completion evidence.", json!({
+            "type": "object",
+            "properties": {
+                "task_id": { "type": "string", "description": "Also return the log entries for this task" }
+            }
+        })),
+        tool("loom_digest", "Get per-stakeholder digests of recent activity (labels/repos they follow plus @mentions)", json!({
+            "type": "object",
+            "properties": {
+                "stakeholder": { "type": "string", "description": "Only this stakeholder's digest (handle without @)" },
+                "hours": { "type": "number", "description": "Look-back window in hours (default: 24)" },
+                "deliver": { "type": "boolean", "description": "Also deliver via webhook or .loom/digests/ (default: false)" }
+            }
+        })),
         tool("loom_discuss", "Capture implementation preferences before planning. Use for ambiguous features to align on visual style, API design, content structure, etc.", json!({
             "type": "object",
             "properties": {
@@ -332,6 +612,28 @@ pub fn list_tools() -> Vec<ToolDefinition> {
             },
             "required": ["task_id", "category", "question", "decision"]
         })),
+        tool("loom_ask", "Ask a human a question. Creates a question task that blocks the given tasks until answered; the default decision applies after the deadline.", json!({
+            "type": "object",
+            "properties": {
+                "question": { "type": "string", "description": "The decision needed" },
+                "options": { "type": "array", "items": { "type": "string" }, "description": "Allowed answers (any answer accepted if omitted)" },
+                "default": { "type": "string", "description": "Decision to apply if unanswered by the deadline" },
+                "deadline_hours": { "type": "number", "description": "Hours until the default decision applies" },
+                "blocks": { "type": "array", "items": { "type": "string" }, "description": "Task IDs that wait on this answer" },
+                "labels": { "type": "array", "items": { "type": "string" }, "description": "Labels (used for stakeholder digests)" }
+            },
+            "required": ["question"]
+        })),
+        tool("loom_answer", "Answer a question task. Records the decision as a preference and unblocks dependent tasks.", json!({
+            "type": "object",
+            "properties": {
+                "task_id": { "type": "string", "description": "Question task ID" },
+                "decision": { "type": "string", "description": "The decision" },
+                "rationale": { "type": "string", "description": "Why" },
+                "answered_by": { "type": "string", "description": "Who answered (default: human)" }
+            },
+            "required": ["task_id", "decision"]
+        })),
         tool("loom_verify_plan", "Verify a plan BEFORE execution. Checks plan against task requirements, validates file paths, ensures no scope creep.", json!({
             "type": "object",
             "properties": {
@@ -375,7 +677,102 @@ pub fn list_tools() -> Vec<ToolDefinition> {
 }
 
 /// Call a tool by name with arguments
+///
+/// Tools that create or claim tasks are charged to the calling agent's
+/// `[limits]`: the call is refused up front when it would exceed them, and
+/// recorded once it succeeds. By then the tasks exist, so a failure to
+/// record comes back as a warning rather than an error the agent would
+/// retry, creating them twice.
 pub fn call_tool(loom: &mut Loom, name: &str, args: Value) -> Result<Value, String> {
+    let metered = metered_actions(loom, name, &args);
+    for (agent, action, count) in &metered {
+        loom.check_quota(agent, *action, *count).map_err(|e| e.to_string())?;
+    }
+    
+    let mut result = run_tool(loom, name, args)?;
+    
+    let task_ids = touched_task_ids(&result);
+    for (agent, action, _) in &metered {
+        let Err(e) = loom.record_activity(agent, *action, &task_ids) else { continue };
+        let warning = json!(format!("Couldn't record {} by {} against its limits: {}", action.as_str(), agent, e));
+        if let Some(fields) = result.as_object_mut() {
+            match fields.entry("warnings").or_insert_with(|| json!([])).as_array_mut() {
+                Some(warnings) => warnings.push(warning),
+                None => fields["warnings"] = json!([warning]),
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// The (agent, action, count) charges a tool call incurs
+fn metered_actions(loom: &Loom, name: &str, args: &Value) -> Vec<(String, AgentAction, u32)> {
+    let agent = args["agent"].as_str().unwrap_or(ANONYMOUS_AGENT).to_string();
+    match name {
+        "loom_work" => vec![(agent.clone(), AgentAction::Create, 1), (agent, AgentAction::Claim, 1)],
+        "loom_create" | "loom_spawn" => vec![(agent, AgentAction::Create, 1)],
+        "loom_claim" | "loom_next_task" => vec![(agent, AgentAction::Claim, 1)],
+        "loom_template" if args["name"].is_string() && !args["preview"].as_bool().unwrap_or(false) => {
+            // Charge the whole tree; a template that doesn't load fails in the tool itself
+            let count = args["name"].as_str()
+                .and_then(|name| loom.template(name).ok())
+                .and_then(|t| t.instantiate(&template_vars(args)).ok())
+                .map_or(1, |plan| plan.count() as u32);
+            vec![(agent, AgentAction::Create, count)]
+        }
+        "loom_run_formula" if !args["preview"].as_bool().unwrap_or(false) => {
+            // The run's task and one per step
+            let count = args["name"].as_str()
+                .and_then(|name| loom.formula(name))
+                .map_or(1, |f| f.steps.len() + 1);
+            vec![(agent, AgentAction::Create, count as u32)]
+        }
+        "loom_import_plan" => {
+            // Charged for every step; a re-import only creates the new ones
+            let count = plan::parse(args["markdown"].as_str().unwrap_or_default())
+                .iter()
+                .map(PlanStep::count)
+                .sum::<usize>();
+            vec![(agent, AgentAction::Create, count.max(1) as u32)]
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// IDs of the tasks a create/claim tool returned
+fn touched_task_ids(result: &Value) -> Vec<String> {
+    if let Some(tasks) = result["tasks"].as_array() {
+        return tasks.iter().filter_map(|t| t["id"].as_str().map(String::from)).collect();
+    }
+    if let Some(created) = result["created"].as_array() {
+        return created.iter().filter_map(|id| id.as_str().map(String::from)).collect();
+    }
+    result["id"].as_str()
+        .or(result["task_id"].as_str())
+        .map(|id| vec![id.to_string()])
+        .unwrap_or_default()
+}
+
+/// `label` or `repo` argument of `loom_pause` / `loom_unpause`
+fn pause_scope(args: &Value) -> Option<PauseScope> {
+    match (args["label"].as_str(), args["repo"].as_str()) {
+        (Some(label), _) => Some(PauseScope::Label(label.to_string())),
+        (None, Some(repo)) => Some(PauseScope::Repo(repo.to_string())),
+        (None, None) => None,
+    }
+}
+
+/// `variables` argument of `loom_template`
+fn template_vars(args: &Value) -> std::collections::HashMap<String, String> {
+    args["variables"]
+        .as_object()
+        .map(|obj| obj.iter()
+            .map(|(k, v)| (k.clone(), v.as_str().map(str::to_string).unwrap_or_else(|| v.to_string())))
+            .collect())
+        .unwrap_or_default()
+}
+
+fn run_tool(loom: &mut Loom, name: &str, args: Value) -> Result<Value, String> {
     match name {
         // ─────────────────────────────────────────────────────────────────
         // Quick Work (Single-Agent Pattern) - Minimal Ceremony
@@ -400,6 +797,7 @@ pub fn call_tool(loom: &mut Loom, name: &str, args: Value) -> Result<Value, Stri
                 parent: None,
                 evidence: None,
                 repo: None,
+                kind: Default::default(),
             }).map_err(|e| e.to_string())?;
             
             // Claim it immediately
@@ -438,6 +836,7 @@ pub fn call_tool(loom: &mut Loom, name: &str, args: Value) -> Result<Value, Stri
                 parent,
                 evidence: None,
                 repo: None,
+                kind: Default::default(),
             }).map_err(|e| e.to_string())?;
             
             Ok(json!({
@@ -452,15 +851,36 @@ pub fn call_tool(loom: &mut Loom, name: &str, args: Value) -> Result<Value, Stri
             let task_id = args["task_id"].as_str().ok_or("Missing task_id")?;
             let agent = args["agent"].as_str().ok_or("Missing agent")?;
             
-            let task = loom.claim(task_id, agent).map_err(|e| e.to_string())?;
+            let lease = loom.claim(task_id, agent).map_err(|e| e.to_string())?;
             
             Ok(json!({
-                "id": task.id,
-                "agent": task.agent,
-                "status": "claimed"
+                "id": lease.task_id,
+                "agent": lease.agent,
+                "status": "claimed",
+                "lease_expires_at": lease.expires_at
             }))
         }
         
+        "loom_renew" => {
+            let task_id = args["task_id"].as_str().ok_or("Missing task_id")?;
+            let agent = args["agent"].as_str().ok_or("Missing agent")?;
+            let lease = loom.renew(task_id, agent).map_err(|e| e.to_string())?;
+            serde_json::to_value(lease).map_err(|e| e.to_string())
+        }
+        
+        "loom_leases" => {
+            let within = chrono::Duration::seconds((args["within_mins"].as_f64().unwrap_or(0.0) * 60.0) as i64);
+            let now = chrono::Utc::now();
+            let leases = loom.expiring_leases(within).map_err(|e| e.to_string())?;
+            Ok(json!(leases.iter().map(|l| json!({
+                "task_id": l.task_id,
+                "agent": l.agent,
+                "expires_at": l.expires_at,
+                "expired": l.is_expired(now),
+                "renewals": l.renewals
+            })).collect::<Vec<_>>()))
+        }
+        
         "loom_release" => {
             let task_id = args["task_id"].as_str().ok_or("Missing task_id")?;
             loom.release(task_id).map_err(|e| e.to_string())?;
@@ -469,23 +889,41 @@ pub fn call_tool(loom: &mut Loom, name: &str, args: Value) -> Result<Value, Stri
         
         "loom_complete" => {
             let task_id = args["task_id"].as_str().ok_or("Missing task_id")?;
+            let agent = args["agent"].as_str();
             let evidence = args["evidence"].as_str();
             let cost_usd = args["cost_usd"].as_f64();
+            let reported: Option<Outcome> = match args.get("outcome") {
+                Some(v) if !v.is_null() => Some(serde_json::from_value(v.clone()).map_err(|e| format!("Invalid outcome: {}", e))?),
+                _ => None,
+            };
             
-            // Record cost if provided
-            if let Some(cost) = cost_usd {
-                loom.record_cost(task_id, cost).map_err(|e| e.to_string())?;
-            }
-            
-            // Complete and get auto-unblocked tasks
-            let unblocked = loom.complete(task_id, evidence).map_err(|e| e.to_string())?;
+            // Complete and get auto-unblocked tasks, checking the cost against budgets
+            let (unblocked, overruns) = match cost_usd {
+                Some(cost) => loom.complete_with_cost(task_id, agent, evidence, cost).map_err(|e| e.to_string())?,
+                None => {
+                    let unblocked = match agent {
+                        Some(agent) => loom.complete_as(task_id, agent, evidence),
+                        None => loom.complete(task_id, evidence),
+                    };
+                    (unblocked.map_err(|e| e.to_string())?, Vec::new())
+                }
+            };
+            let outcome = match reported {
+                Some(reported) => Some(loom.record_outcome(task_id, reported).map_err(|e| e.to_string())?),
+                None => loom.outcome(task_id).map_err(|e| e.to_string())?,
+            };
             
-            Ok(json!({ 
+            let mut result = json!({ 
                 "completed": task_id, 
                 "evidence": evidence,
                 "cost_usd": cost_usd,
+                "outcome": outcome,
                 "unblocked": unblocked  // Tasks that are now ready
-            }))
+            });
+            if !overruns.is_empty() {
+                result["warnings"] = json!(overruns.iter().map(|o| format!("Over budget: {}", o)).collect::<Vec<_>>());
+            }
+            Ok(result)
         }
         
         "loom_cancel" => {
@@ -494,6 +932,18 @@ pub fn call_tool(loom: &mut Loom, name: &str, args: Value) -> Result<Value, Stri
             Ok(json!({ "cancelled": task_id }))
         }
         
+        "loom_review" => {
+            let task_id = args["task_id"].as_str().ok_or("Missing task_id")?;
+            let reviewer = args["reviewer"].as_str().ok_or("Missing reviewer")?;
+            let approved = match args["verdict"].as_str() {
+                Some("approve") => true,
+                Some("reject") => false,
+                _ => return Err("verdict must be \"approve\" or \"reject\"".to_string()),
+            };
+            let task = loom.review(task_id, approved, reviewer, args["note"].as_str()).map_err(|e| e.to_string())?;
+            Ok(json!({ "task_id": task.id, "approved": approved, "status": task.status }))
+        }
+        
         "loom_spawn" => {
             let parent_id = args["parent_id"].as_str().ok_or("Missing parent_id")?;
             let title = args["title"].as_str().ok_or("Missing title")?;
@@ -507,6 +957,13 @@ pub fn call_tool(loom: &mut Loom, name: &str, args: Value) -> Result<Value, Stri
             }))
         }
         
+        "loom_import_plan" => {
+            let task_id = args["task_id"].as_str().ok_or("Missing task_id")?;
+            let markdown = args["markdown"].as_str().ok_or("Missing markdown")?;
+            let import = loom.import_plan(task_id, markdown).map_err(|e| e.to_string())?;
+            Ok(json!(import))
+        }
+        
         // ─────────────────────────────────────────────────────────────────
         // Queries
         // ─────────────────────────────────────────────────────────────────
@@ -541,6 +998,11 @@ pub fn call_tool(loom: &mut Loom, name: &str, args: Value) -> Result<Value, Stri
         "loom_get" => {
             let task_id = args["task_id"].as_str().ok_or("Missing task_id")?;
             let task = loom.get(task_id).map_err(|e| e.to_string())?;
+            let rollup = loom.rollup(task_id).map_err(|e| e.to_string())?;
+            let comments = loom.comments(task_id).map_err(|e| e.to_string())?;
+            let subtasks = loom.children(task_id).map_err(|e| e.to_string())?;
+            let outcome = loom.outcome(task_id).map_err(|e| e.to_string())?;
+            let plan_source = loom.plan_link(task_id).map_err(|e| e.to_string())?;
             
             match task {
                 Some(t) => Ok(json!({
@@ -554,6 +1016,16 @@ pub fn call_tool(loom: &mut Loom, name: &str, args: Value) -> Result<Value, Stri
                     "parent": t.parent,
                     "evidence": t.evidence,
                     "actual_cost_usd": t.actual_cost_usd,
+                    "outcome": outcome,
+                    "plan_source": plan_source,
+                    "rollup": rollup,
+                    "subtasks": subtasks.iter().map(|c| json!({
+                        "id": c.id,
+                        "title": c.title,
+                        "status": c.status.as_str(),
+                        "rollup": c.rollup
+                    })).collect::<Vec<_>>(),
+                    "comments": comments,
                     "created_at": t.created_at.to_rfc3339(),
                     "updated_at": t.updated_at.to_rfc3339()
                 })),
@@ -579,6 +1051,8 @@ pub fn call_tool(loom: &mut Loom, name: &str, args: Value) -> Result<Value, Stri
             } else {
                 loom.list().map_err(|e| e.to_string())?
             };
+            let rollups = loom.rollups().map_err(|e| e.to_string())?;
+            let pauses = loom.pauses().map_err(|e| e.to_string())?;
             
             Ok(json!(tasks.iter().map(|t| json!({
                 "id": t.id,
@@ -588,7 +1062,10 @@ pub fn call_tool(loom: &mut Loom, name: &str, args: Value) -> Result<Value, Stri
                 "agent": t.agent,
                 "labels": t.labels,
                 "repo": t.repo,
-                "actual_cost_usd": t.actual_cost_usd
+                "parent": t.parent,
+                "actual_cost_usd": t.actual_cost_usd,
+                "rollup": rollups.get(&t.id),
+                "paused": pauses.iter().any(|p| p.covers(t))
             })).collect::<Vec<_>>()))
         }
         
@@ -608,10 +1085,50 @@ pub fn call_tool(loom: &mut Loom, name: &str, args: Value) -> Result<Value, Stri
                 "cancelled": summary.cancelled,
                 "total_cost_usd": summary.total_cost_usd,
                 "progress_pct": summary.progress_pct(),
-                "label": summary.label
+                "label": summary.label,
+                "parents": summary.parents,
+                "paused": summary.paused,
+                "pauses": summary.pauses
             }))
         }
         
+        // ─────────────────────────────────────────────────────────────────
+        // Work Queues
+        // ─────────────────────────────────────────────────────────────────
+        "loom_subscribe" => {
+            let agent = args["agent"].as_str().ok_or("Missing agent")?;
+            let labels: Vec<String> = args["labels"]
+                .as_array()
+                .map(|arr| arr.iter().filter_map(|v| v.as_str().map(String::from)).collect())
+                .unwrap_or_default();
+            
+            if args["unsubscribe"].as_bool().unwrap_or(false) {
+                loom.unsubscribe(agent, &labels).map_err(|e| e.to_string())?;
+            } else {
+                loom.subscribe(agent, &labels).map_err(|e| e.to_string())?;
+            }
+            
+            let topics = loom.subscriptions(agent).map_err(|e| e.to_string())?;
+            Ok(json!({ "agent": agent, "topics": topics }))
+        }
+        
+        "loom_next_task" => {
+            let agent = args["agent"].as_str().ok_or("Missing agent")?;
+            
+            match loom.next_task(agent).map_err(|e| e.to_string())? {
+                Some(t) => Ok(json!({
+                    "id": t.id,
+                    "title": t.title,
+                    "description": t.description,
+                    "status": "claimed",
+                    "priority": t.priority.as_str(),
+                    "agent": t.agent,
+                    "labels": t.labels
+                })),
+                None => Ok(json!({ "task": null, "message": "No matching tasks ready" }))
+            }
+        }
+        
         // ─────────────────────────────────────────────────────────────────
         // Dependencies
         // ─────────────────────────────────────────────────────────────────
@@ -629,6 +1146,52 @@ pub fn call_tool(loom: &mut Loom, name: &str, args: Value) -> Result<Value, Stri
             Ok(json!({ "unblocked": task_id, "from": blocked_by }))
         }
         
+        "loom_pause" => {
+            let Some(scope) = pause_scope(&args) else {
+                let pauses = loom.pauses().map_err(|e| e.to_string())?;
+                return Ok(json!({ "pauses": pauses }));
+            };
+            let pause = loom.pause(scope, args["reason"].as_str(), args["agent"].as_str())
+                .map_err(|e| e.to_string())?;
+            serde_json::to_value(pause).map_err(|e| e.to_string())
+        }
+        
+        "loom_unpause" => {
+            let scope = pause_scope(&args).ok_or("Provide label or repo")?;
+            let unpaused = loom.unpause(&scope).map_err(|e| e.to_string())?;
+            Ok(json!({ "scope": scope, "unpaused": unpaused }))
+        }
+        
+        "loom_comment" => {
+            let task_id = args["task_id"].as_str().ok_or("Missing task_id")?;
+            let body = args["body"].as_str().ok_or("Missing body")?;
+            let author = args["agent"].as_str().unwrap_or(ANONYMOUS_AGENT);
+            let comment = loom.comment(task_id, author, body).map_err(|e| e.to_string())?;
+            serde_json::to_value(comment).map_err(|e| e.to_string())
+        }
+        
+        "loom_comments" => {
+            let comments = match args["task_id"].as_str() {
+                Some(task_id) => loom.comments(task_id),
+                None => loom.recent_comments(args["limit"].as_u64().unwrap_or(20) as usize),
+            }.map_err(|e| e.to_string())?;
+            Ok(json!({ "count": comments.len(), "comments": comments }))
+        }
+        
+        "loom_history" => {
+            let task_id = args["task_id"].as_str().ok_or("Missing task_id")?;
+            let history = loom.history(task_id).map_err(|e| e.to_string())?;
+            Ok(json!({ "task_id": task_id, "count": history.len(), "history": history }))
+        }
+        
+        "loom_doctor" => {
+            let cycles = loom.dependency_cycles().map_err(|e| e.to_string())?;
+            Ok(json!({
+                "healthy": cycles.is_empty(),
+                "cycles": cycles
+            }))
+        }
+        
         // ─────────────────────────────────────────────────────────────────
         // Smart Routing
         // ─────────────────────────────────────────────────────────────────
@@ -640,11 +1203,27 @@ pub fn call_tool(loom: &mut Loom, name: &str, args: Value) -> Result<Value, Stri
             let strategy = match args["strategy"].as_str() {
                 Some("cheapest") => RoutingStrategy::Cheapest,
                 Some("fastest") => RoutingStrategy::Fastest,
+                Some("learned") => RoutingStrategy::Learned,
+                Some("least_loaded") => RoutingStrategy::LeastLoaded,
+                Some("round_robin") => RoutingStrategy::RoundRobin,
                 _ => RoutingStrategy::Best,
             };
             
+            let agents = |key: &str| -> Vec<String> {
+                args[key].as_array()
+                    .map(|arr| arr.iter().filter_map(|v| v.as_str().map(String::from)).collect())
+                    .unwrap_or_default()
+            };
+            let sticky_agent = if args["sticky"].as_bool().unwrap_or(false) {
+                loom.sticky_agent(&task).map_err(|e| e.to_string())?
+            } else {
+                None
+            };
             let constraints = RoutingConstraints {
                 max_cost: args["max_cost"].as_f64(),
+                preferred_agents: agents("prefer_agents"),
+                excluded_agents: agents("exclude_agents"),
+                sticky_agent,
                 ..Default::default()
             };
             
@@ -656,7 +1235,9 @@ pub fn call_tool(loom: &mut Loom, name: &str, args: Value) -> Result<Value, Stri
                 "reason": decision.reason,
                 "estimated_cost": decision.estimated_cost,
                 "confidence": decision.confidence,
-                "alternatives": decision.alternatives
+                "alternatives": decision.alternatives,
+                "warnings": decision.warnings,
+                "context_budget": decision.context_budget
             }))
         }
         
@@ -666,6 +1247,8 @@ pub fn call_tool(loom: &mut Loom, name: &str, args: Value) -> Result<Value, Stri
                 "id": a.id,
                 "name": a.name,
                 "available": a.available,
+                "unresponsive": a.unresponsive,
+                "last_heartbeat": a.last_heartbeat,
                 "success_rate": a.quality.success_rate(),
                 "capabilities": {
                     "planning": a.capabilities.planning,
@@ -679,6 +1262,65 @@ pub fn call_tool(loom: &mut Loom, name: &str, args: Value) -> Result<Value, Stri
             })).collect::<Vec<_>>()))
         }
         
+        "loom_heartbeat" => {
+            let agent = args["agent"].as_str().ok_or("Missing agent")?;
+            let heartbeat = loom.heartbeat(agent, args["status"].as_str()).map_err(|e| e.to_string())?;
+            serde_json::to_value(heartbeat).map_err(|e| e.to_string())
+        }
+        
+        "loom_probe_agents" => {
+            let timeout = std::time::Duration::from_secs(args["timeout_secs"].as_u64().unwrap_or(10).max(1));
+            let probes = loom.probe_agents(args["agent"].as_str(), timeout).map_err(|e| e.to_string())?;
+            serde_json::to_value(probes).map_err(|e| e.to_string())
+        }
+        
+        "loom_register_agent" => {
+            let id = args["id"].as_str().ok_or("Missing id")?;
+            let config: crate::ModelConfig = serde_json::from_value(args.clone()).map_err(|e| e.to_string())?;
+            let profile = loom.register_agent(id, &config).map_err(|e| e.to_string())?;
+            serde_json::to_value(profile).map_err(|e| e.to_string())
+        }
+        
+        "loom_pricing_sync" => {
+            let path = args["path"].as_str().map(std::path::Path::new);
+            let dry_run = args["dry_run"].as_bool().unwrap_or(false);
+            let changes = loom.sync_pricing(path, dry_run).map_err(|e| e.to_string())?;
+            Ok(json!({ "changes": changes, "dry_run": dry_run }))
+        }
+        
+        "loom_reprice" => {
+            let path = args["path"].as_str().map(std::path::Path::new);
+            let repricing = loom.reprice_history(path, args["dry_run"].as_bool().unwrap_or(false))
+                .map_err(|e| e.to_string())?;
+            serde_json::to_value(repricing).map_err(|e| e.to_string())
+        }
+        
+        "loom_simulate_routing" => {
+            let config_path = args["config_path"].as_str().ok_or("Missing config_path")?;
+            let recent = args["recent"].as_u64().unwrap_or(100) as usize;
+            
+            let candidate = crate::ModelsConfig::load(config_path).map_err(|e| e.to_string())?;
+            let sim = loom.simulate_routing(&candidate, recent).map_err(|e| e.to_string())?;
+            
+            Ok(json!({
+                "tasks": sim.tasks,
+                "reassigned": sim.reassigned,
+                "cost": {
+                    "current": sim.current_cost,
+                    "candidate": sim.candidate_cost,
+                    "delta": sim.cost_delta()
+                },
+                "predicted_success": {
+                    "current": sim.current_success,
+                    "candidate": sim.candidate_success,
+                    "delta": sim.success_delta()
+                },
+                "current_by_agent": sim.current_by_agent,
+                "candidate_by_agent": sim.candidate_by_agent,
+                "changes": sim.assignments.iter().filter(|a| a.changed()).collect::<Vec<_>>()
+            }))
+        }
+        
         // ─────────────────────────────────────────────────────────────────
         // Sessions & Memory
         // ─────────────────────────────────────────────────────────────────
@@ -712,6 +1354,31 @@ pub fn call_tool(loom: &mut Loom, name: &str, args: Value) -> Result<Value, Stri
             }))
         }
         
+        "loom_session_pause" | "loom_session_unpause" => {
+            let session_id = args["session_id"].as_str().ok_or("Missing session_id")?;
+            if name == "loom_session_pause" {
+                loom.pause_session(session_id).map_err(|e| e.to_string())?;
+            } else {
+                loom.unpause_session(session_id).map_err(|e| e.to_string())?;
+            }
+            
+            let time = loom.session_time(session_id).map_err(|e| e.to_string())?;
+            serde_json::to_value(time).map_err(|e| e.to_string())
+        }
+        
+        "loom_time" => {
+            match args["session_id"].as_str() {
+                Some(session_id) => {
+                    let time = loom.session_time(session_id).map_err(|e| e.to_string())?;
+                    serde_json::to_value(time).map_err(|e| e.to_string())
+                }
+                None => {
+                    let report = loom.time_report().map_err(|e| e.to_string())?;
+                    serde_json::to_value(report).map_err(|e| e.to_string())
+                }
+            }
+        }
+        
         "loom_checkpoint" => {
             let session_id = args["session_id"].as_str().ok_or("Missing session_id")?;
             let summary = args["summary"].as_str().ok_or("Missing summary")?;
@@ -775,18 +1442,107 @@ pub fn call_tool(loom: &mut Loom, name: &str, args: Value) -> Result<Value, Stri
                 "variables": formula.variables.iter().map(|v| json!({
                     "name": v.name,
                     "description": v.description,
+                    "type": v.kind.as_str(),
                     "required": v.required,
                     "default": v.default
                 })).collect::<Vec<_>>(),
+                "variables_schema": formula.variables_schema(),
                 "steps": formula.steps.iter().map(|s| json!({
                     "id": s.id,
                     "title": s.title,
                     "description": s.description,
                     "checkpoint": s.checkpoint
-                })).collect::<Vec<_>>()
+                })).collect::<Vec<_>>(),
+                "provenance": loom.formula_provenance(name)
             }))
         }
         
+        "loom_run_formula" => {
+            let name = args["name"].as_str().ok_or("Missing name")?;
+            let vars = template_vars(&args);
+            if args["preview"].as_bool().unwrap_or(false) {
+                return Ok(json!(loom.plan_formula(name, &vars).map_err(|e| e.to_string())?));
+            }
+            let run = if args["execute"].as_bool().unwrap_or(false) {
+                loom.run_formula(name, &vars)
+            } else {
+                loom.start_formula(name, &vars)
+            }.map_err(|e| e.to_string())?;
+            
+            let mut response = json!(run);
+            response["created"] = json!(std::iter::once(&run.root_id)
+                .chain(run.steps.iter().map(|s| &s.task_id))
+                .collect::<Vec<_>>());
+            Ok(response)
+        }
+        
+        "loom_template" => {
+            let Some(name) = args["name"].as_str() else {
+                let templates = loom.templates().map_err(|e| e.to_string())?;
+                return Ok(json!({
+                    "templates": templates.iter().map(|t| json!({
+                        "name": t.name,
+                        "summary": t.summary,
+                        "title": t.title,
+                        "variables": t.variables,
+                        "children": t.children.len()
+                    })).collect::<Vec<_>>()
+                }));
+            };
+            
+            let vars = template_vars(&args);
+            let parent = args["parent"].as_str();
+            
+            if args["preview"].as_bool().unwrap_or(false) {
+                let plan = loom.template(name)
+                    .and_then(|t| Ok(t.instantiate(&vars)?))
+                    .map_err(|e| e.to_string())?;
+                return Ok(json!({ "preview": plan, "count": plan.count() }));
+            }
+            
+            let tasks = loom.create_from_template(name, &vars, parent).map_err(|e| e.to_string())?;
+            Ok(json!({
+                "root": tasks[0].id,
+                "count": tasks.len(),
+                "tasks": tasks
+            }))
+        }
+        
+        "loom_schedule" => {
+            if let Some(id) = args["remove"].as_str() {
+                loom.unschedule(id).map_err(|e| e.to_string())?;
+                return Ok(json!({ "removed": id }));
+            }
+            let Some(title) = args["title"].as_str() else {
+                let schedules = loom.schedules().map_err(|e| e.to_string())?;
+                return Ok(json!({ "schedules": schedules }));
+            };
+            
+            let recurrence = match (args["cron"].as_str(), args["due_at"].as_str()) {
+                (Some(expr), None) => Recurrence::Cron(expr.to_string()),
+                (None, Some(at)) => Recurrence::DueAt(
+                    chrono::DateTime::parse_from_rfc3339(at)
+                        .map_err(|e| format!("Invalid due_at '{}': {}", at, e))?
+                        .with_timezone(&chrono::Utc),
+                ),
+                _ => return Err("Provide exactly one of cron or due_at".to_string()),
+            };
+            let params = CreateTask {
+                title: title.to_string(),
+                description: args["description"].as_str().map(String::from),
+                priority: args["priority"].as_str().and_then(Priority::from_str).unwrap_or_default(),
+                labels: args["labels"]
+                    .as_array()
+                    .map(|arr| arr.iter().filter_map(|v| v.as_str().map(String::from)).collect())
+                    .unwrap_or_default(),
+                parent: args["parent"].as_str().map(String::from),
+                ..Default::default()
+            };
+            
+            let schedule = loom.schedule(params, recurrence).map_err(|e| e.to_string())?;
+            serde_json::to_value(schedule).map_err(|e| e.to_string())
+        }
+        
         // ─────────────────────────────────────────────────────────────────
         // Learning
         // ─────────────────────────────────────────────────────────────────
@@ -795,7 +1551,7 @@ pub fn call_tool(loom: &mut Loom, name: &str, args: Value) -> Result<Value, Stri
             let task_id = args["task_id"].as_str().ok_or("Missing task_id")?;
             let task_type = args["task_type"].as_str();
             let success = args["success"].as_bool().ok_or("Missing success")?;
-            let duration_secs = args["duration_secs"].as_f64().ok_or("Missing duration_secs")?;
+            let duration_secs = args["duration_secs"].as_f64();
             
             loom.record_execution(agent_id, task_id, task_type, success, duration_secs)
                 .map_err(|e| e.to_string())?;
@@ -964,6 +1720,14 @@ pub fn call_tool(loom: &mut Loom, name: &str, args: Value) -> Result<Value, Stri
             // Get agent profiles with their stats
             let agents = loom.agents().map_err(|e| e.to_string())?;
             
+            // Last week's utilization, for weighing agents against what they cost
+            let week_ago = chrono::Utc::now() - chrono::Duration::days(7);
+            let mut utilization = loom.utilization(week_ago).map_err(|e| e.to_string())?;
+            let outcomes = loom.outcome_summary(week_ago).map_err(|e| e.to_string())?;
+            if let Some(f) = agent_filter {
+                utilization.days.retain(|d| d.agent_id.contains(f));
+            }
+            
             let agent_stats: Vec<Value> = agents.iter()
                 .filter(|a| agent_filter.map_or(true, |f| a.id.contains(f)))
                 .map(|a| json!({
@@ -985,13 +1749,135 @@ pub fn call_tool(loom: &mut Loom, name: &str, args: Value) -> Result<Value, Stri
                     "progress_pct": summary.progress_pct(),
                     "total_cost_usd": summary.total_cost_usd
                 },
-                "agents": agent_stats
+                "agents": agent_stats,
+                "utilization": {
+                    "since": utilization.since,
+                    "by_agent": utilization.by_agent()
+                },
+                "outcomes": outcomes
             }))
         }
         
+        "loom_utilization" => {
+            let days = args["days"].as_f64().unwrap_or(7.0);
+            let since = chrono::Utc::now() - chrono::Duration::minutes((days * 24.0 * 60.0) as i64);
+            let mut report = loom.utilization(since).map_err(|e| e.to_string())?;
+            if let Some(agent) = args["agent"].as_str() {
+                report = report.for_agent(agent);
+            }
+            
+            Ok(json!({
+                "since": report.since,
+                "until": report.until,
+                "days": report.days.iter().map(|d| json!({
+                    "agent_id": d.agent_id,
+                    "date": d.date,
+                    "active_secs": d.active_secs,
+                    "idle_secs": d.idle_secs,
+                    "utilization": d.utilization(),
+                    "sessions": d.sessions,
+                    "tasks_completed": d.tasks_completed,
+                    "tasks_failed": d.tasks_failed,
+                    "cost_usd": d.cost_usd
+                })).collect::<Vec<_>>(),
+                "by_agent": report.by_agent(),
+                "formatted": report.format_report()
+            }))
+        }
+        
+        "loom_export_costs" => {
+            let format = match args["format"].as_str() {
+                Some(f) => crate::CostFormat::parse(f).ok_or("format must be \"csv\" or \"json\"")?,
+                None => crate::CostFormat::Json,
+            };
+            let since = args["days"].as_f64()
+                .map(|days| chrono::Utc::now() - chrono::Duration::seconds((days * 86400.0) as i64));
+            let report = loom.cost_report(since).map_err(|e| e.to_string())?;
+            match format {
+                crate::CostFormat::Csv => Ok(json!({
+                    "total_usd": report.total_usd(),
+                    "entries": report.entries.len(),
+                    "csv": report.to_csv()
+                })),
+                crate::CostFormat::Json => Ok(json!({
+                    "since": report.since,
+                    "total_usd": report.total_usd(),
+                    "by_task": report.by_task(),
+                    "by_agent": report.by_agent(),
+                    "by_label": report.by_label(),
+                    "by_day": report.by_day(),
+                    "entries": report.entries
+                })),
+            }
+        }
+        
         // ─────────────────────────────────────────────────────────────────
         // GSD-Inspired: Discuss & Verify (Pre-Planning)
         // ─────────────────────────────────────────────────────────────────
+        "loom_maintain" => {
+            let report = loom.maintain().map_err(|e| e.to_string())?;
+            Ok(json!({
+                "rows_pruned": report.rows_pruned(),
+                "bytes_reclaimed": report.bytes_reclaimed(),
+                "report": report
+            }))
+        }
+        
+        "loom_quota" => {
+            let agent = args["agent"].as_str().unwrap_or(ANONYMOUS_AGENT);
+            let status = loom.quota_status(agent).map_err(|e| e.to_string())?;
+            serde_json::to_value(status).map_err(|e| e.to_string())
+        }
+        
+        "loom_budgets" => {
+            let budgets = loom.budget_status().map_err(|e| e.to_string())?;
+            Ok(json!({ "budgets": budgets }))
+        }
+        
+        "loom_daemon_status" => {
+            serde_json::to_value(loom.daemon_status()).map_err(|e| e.to_string())
+        }
+        
+        "loom_logs" => {
+            let limit = args["limit"].as_u64().unwrap_or(50) as usize;
+            let level = match args["level"].as_str() {
+                Some(level) => LogLevel::parse(level).ok_or_else(|| format!("Unknown level: {}", level))?,
+                None => LogLevel::Info,
+            };
+            let entries = loom.logs(limit, level).map_err(|e| e.to_string())?;
+            Ok(json!({ "count": entries.len(), "entries": entries }))
+        }
+        
+        "loom_verify_log" => {
+            let verification = loom.verify_evidence_log().map_err(|e| e.to_string())?;
+            let mut result = json!({
+                "valid": verification.is_valid(),
+                "verification": verification
+            });
+            if let Some(task_id) = args["task_id"].as_str() {
+                result["entries"] = json!(loom.evidence_log(Some(task_id)).map_err(|e| e.to_string())?);
+            }
+            Ok(result)
+        }
+        
+        "loom_digest" => {
+            let hours = args["hours"].as_f64().unwrap_or(24.0);
+            let since = chrono::Utc::now() - chrono::Duration::seconds((hours * 3600.0) as i64);
+            
+            let mut digests = loom.digests(since).map_err(|e| e.to_string())?;
+            if let Some(handle) = args["stakeholder"].as_str() {
+                digests.retain(|d| d.stakeholder == handle.trim_start_matches('@'));
+            }
+            
+            if args["deliver"].as_bool().unwrap_or(false) {
+                for digest in &digests {
+                    loom.deliver_digest(digest).map_err(|e| e.to_string())?;
+                }
+            }
+            
+            Ok(json!(digests))
+        }
+        
         "loom_discuss" => {
             let task_id = args["task_id"].as_str().ok_or("Missing task_id")?;
             let category = args["category"].as_str().ok_or("Missing category")?;
@@ -1017,6 +1903,55 @@ pub fn call_tool(loom: &mut Loom, name: &str, args: Value) -> Result<Value, Stri
             }))
         }
         
+        "loom_ask" => {
+            let question = args["question"].as_str().ok_or("Missing question")?;
+            let strings = |key: &str| -> Vec<String> {
+                args[key].as_array()
+                    .map(|arr| arr.iter().filter_map(|v| v.as_str().map(String::from)).collect())
+                    .unwrap_or_default()
+            };
+            let deadline = args["deadline_hours"].as_f64()
+                .map(|h| chrono::Utc::now() + chrono::Duration::seconds((h * 3600.0) as i64));
+            let default = args["default"].as_str().map(String::from);
+            
+            let (task, question) = loom.ask(
+                CreateTask {
+                    title: question.to_string(),
+                    labels: strings("labels"),
+                    ..Default::default()
+                },
+                strings("options"),
+                deadline,
+                default,
+                &strings("blocks"),
+            ).map_err(|e| e.to_string())?;
+            
+            Ok(json!({
+                "id": task.id,
+                "question": task.title,
+                "options": question.options,
+                "deadline": question.deadline.map(|d| d.to_rfc3339()),
+                "default": question.default_decision
+            }))
+        }
+        
+        "loom_answer" => {
+            let task_id = args["task_id"].as_str().ok_or("Missing task_id")?;
+            let decision = args["decision"].as_str().ok_or("Missing decision")?;
+            let rationale = args["rationale"].as_str();
+            let answered_by = args["answered_by"].as_str().unwrap_or("human");
+            
+            let unblocked = loom.answer(task_id, decision, rationale, answered_by)
+                .map_err(|e| e.to_string())?;
+            
+            Ok(json!({
+                "answered": true,
+                "task_id": task_id,
+                "decision": decision,
+                "unblocked": unblocked
+            }))
+        }
+        
         "loom_verify_plan" => {
             let task_id = args["task_id"].as_str().ok_or("Missing task_id")?;
             let plan = &args["plan"];
@@ -1221,10 +2156,29 @@ pub fn call_tool(loom: &mut Loom, name: &str, args: Value) -> Result<Value, Stri
     }
 }
 
+/// MCP protocol versions this server speaks, newest first
+pub const SUPPORTED_PROTOCOL_VERSIONS: [&str; 3] = ["2025-06-18", "2025-03-26", "2024-11-05"];
+
+// JSON-RPC error codes
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const INTERNAL_ERROR: i64 = -32603;
+const RESOURCE_NOT_FOUND: i64 = -32002;
+
+type RpcResult = Result<Value, (i64, String)>;
+
 /// MCP Server that handles JSON-RPC communication
+///
+/// Messages are newline-delimited JSON-RPC 2.0 (single messages or
+/// batches). A bad frame gets an error response and the server carries on;
+/// only I/O errors on the transport stop it.
 pub struct McpServer {
     loom: Loom,
     ui_registry: UiRegistry,
+    /// Version agreed in `initialize` (None until then)
+    protocol_version: Option<String>,
 }
 
 impl McpServer {
@@ -1232,52 +2186,109 @@ impl McpServer {
         Self { 
             loom,
             ui_registry: UiRegistry::new(),
+            protocol_version: None,
         }
     }
     
+    /// Protocol version negotiated with the client, once initialized
+    pub fn protocol_version(&self) -> Option<&str> {
+        self.protocol_version.as_deref()
+    }
+    
     /// Run the MCP server on stdin/stdout
     pub async fn run(&mut self) -> Result<(), std::io::Error> {
-        let stdin = std::io::stdin();
-        let mut stdout = std::io::stdout();
-        
-        for line in stdin.lock().lines() {
-            let line = line?;
-            if line.trim().is_empty() {
-                continue;
+        self.serve(std::io::stdin().lock(), std::io::stdout())
+    }
+    
+    /// Serve messages from `input` until it closes, writing responses to `output`
+    pub fn serve<R: BufRead, W: Write>(&mut self, mut input: R, mut output: W) -> Result<(), std::io::Error> {
+        let mut frame = Vec::new();
+        loop {
+            frame.clear();
+            if input.read_until(b'\n', &mut frame)? == 0 {
+                return Ok(());
             }
             
-            let response = self.handle_request(&line);
-            // Only write response if non-empty (notifications don't get responses)
-            if !response.is_empty() {
-                writeln!(stdout, "{}", response)?;
-                stdout.flush()?;
+            let response = match std::str::from_utf8(&frame) {
+                Ok(line) if line.trim().is_empty() => continue,
+                Ok(line) => self.handle_message(line.trim()),
+                Err(_) => Some(error_response(Value::Null, PARSE_ERROR, "Parse error: frame is not valid UTF-8").to_string()),
+            };
+            // Notifications don't get responses
+            if let Some(response) = response {
+                writeln!(output, "{}", response)?;
+                output.flush()?;
             }
         }
-        
-        Ok(())
     }
     
-    fn handle_request(&mut self, request: &str) -> String {
-        let req: Value = match serde_json::from_str(request) {
+    /// Handle one frame, returning the response to send (if any)
+    pub fn handle_message(&mut self, message: &str) -> Option<String> {
+        let message: Value = match serde_json::from_str(message) {
             Ok(v) => v,
-            Err(e) => return json!({ 
-                "jsonrpc": "2.0",
-                "id": null,
-                "error": { "code": -32700, "message": format!("Parse error: {}", e) }
-            }).to_string(),
+            Err(e) => return Some(error_response(Value::Null, PARSE_ERROR, &format!("Parse error: {}", e)).to_string()),
+        };
+        
+        match message {
+            Value::Array(batch) if batch.is_empty() => {
+                Some(error_response(Value::Null, INVALID_REQUEST, "Invalid request: empty batch").to_string())
+            }
+            Value::Array(batch) => {
+                let responses: Vec<Value> = batch.into_iter().filter_map(|m| self.handle_value(m)).collect();
+                (!responses.is_empty()).then(|| Value::Array(responses).to_string())
+            }
+            message => self.handle_value(message).map(|r| r.to_string()),
+        }
+    }
+    
+    fn handle_value(&mut self, message: Value) -> Option<Value> {
+        let id = message.get("id").cloned();
+        if id.as_ref().is_some_and(|id| !(id.is_string() || id.is_number())) {
+            return Some(error_response(Value::Null, INVALID_REQUEST, "Invalid request: id must be a string or number"));
+        }
+        
+        let method = message.get("method").and_then(|m| m.as_str())
+            .filter(|_| message.get("jsonrpc").and_then(|v| v.as_str()) == Some("2.0"));
+        let Some(method) = method else {
+            // Responses from the client; we never send requests, so nothing to do
+            if message.get("result").is_some() || message.get("error").is_some() {
+                return None;
+            }
+            return Some(error_response(
+                id.unwrap_or(Value::Null),
+                INVALID_REQUEST,
+                "Invalid request: expected a JSON-RPC 2.0 message with a method",
+            ));
         };
         
-        let method = req["method"].as_str().unwrap_or("");
-        let params = &req["params"];
-        let id = &req["id"];
+        let params = message.get("params").cloned().unwrap_or_else(|| json!({}));
+        let result = self.dispatch(method, &params);
         
-        let result = match method {
+        // Notifications never get a response, even when they fail
+        let id = id?;
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err((code, message)) => error_response(id, code, &message),
+        })
+    }
+    
+    fn dispatch(&mut self, method: &str, params: &Value) -> RpcResult {
+        match method {
             // MCP initialization handshake
             "initialize" => {
-                json!({
-                    "protocolVersion": "2024-11-05",
+                let requested = params["protocolVersion"].as_str()
+                    .ok_or((INVALID_PARAMS, "initialize requires protocolVersion".to_string()))?;
+                // Echo a version we support; otherwise offer our latest and
+                // let the client decide whether it can use it
+                let version = SUPPORTED_PROTOCOL_VERSIONS.iter()
+                    .find(|v| **v == requested)
+                    .unwrap_or(&SUPPORTED_PROTOCOL_VERSIONS[0]);
+                self.protocol_version = Some(version.to_string());
+                
+                Ok(json!({
+                    "protocolVersion": version,
                     "capabilities": {
-                        "tools": {},
+                        "tools": { "listChanged": false },
                         "resources": {
                             "subscribe": false,
                             "listChanged": false
@@ -1285,29 +2296,40 @@ impl McpServer {
                     },
                     "serverInfo": {
                         "name": "loom",
-                        "version": "0.2.0"
+                        "version": env!("CARGO_PKG_VERSION")
                     }
-                })
+                }))
             }
             
-            // Initialization complete notification (no response needed but we handle it)
-            "notifications/initialized" => {
-                return String::new(); // No response for notifications
-            }
+            "ping" => Ok(json!({})),
             
-            "tools/list" => {
-                let tools = list_tools();
-                json!({ "tools": tools })
-            }
+            // Initialized, cancelled, ...: nothing to do, and no response
+            m if m.starts_with("notifications/") => Ok(Value::Null),
+            
+            "tools/list" => Ok(json!({ "tools": list_tools() })),
             
             "tools/call" => {
-                let name = params["name"].as_str().unwrap_or("");
-                let args = params["arguments"].clone();
+                let name = params["name"].as_str()
+                    .ok_or((INVALID_PARAMS, "tools/call requires a tool name".to_string()))?;
+                if !list_tools().iter().any(|t| t.name == name) {
+                    return Err((INVALID_PARAMS, format!("Unknown tool: {}", name)));
+                }
+                let args = params.get("arguments").cloned().unwrap_or_else(|| json!({}));
                 
-                match call_tool(&mut self.loom, name, args) {
-                    Ok(result) => json!({ "content": [{ "type": "text", "text": result.to_string() }] }),
+                // A panicking tool fails its call, not the server
+                let loom = &mut self.loom;
+                let outcome = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| call_tool(loom, name, args)))
+                    .map_err(|_| (INTERNAL_ERROR, format!("Tool {} panicked", name)))?;
+                
+                Ok(match outcome {
+                    Ok(result) => json!({
+                        "content": [{
+                            "type": "text",
+                            "text": result.to_string()
+                        }]
+                    }),
                     Err(e) => json!({ "isError": true, "content": [{ "type": "text", "text": e }] }),
-                }
+                })
             }
             
             // MCP Apps: List UI resources
@@ -1321,42 +2343,40 @@ impl McpServer {
                         "mimeType": r.mime_type
                     }))
                     .collect();
-                json!({ "resources": resources })
+                Ok(json!({ "resources": resources }))
             }
             
+            "resources/templates/list" => Ok(json!({ "resourceTemplates": [] })),
+            
             // MCP Apps: Read a UI resource
             "resources/read" => {
-                let uri = params["uri"].as_str().unwrap_or("");
+                let uri = params["uri"].as_str()
+                    .ok_or((INVALID_PARAMS, "resources/read requires a uri".to_string()))?;
+                let resource = self.ui_registry.get(uri)
+                    .ok_or_else(|| (RESOURCE_NOT_FOUND, format!("Resource not found: {}", uri)))?;
                 
-                if let Some(resource) = self.ui_registry.get(uri) {
-                    json!({
-                        "contents": [{
-                            "uri": resource.uri,
-                            "mimeType": resource.mime_type,
-                            "text": resource.content
-                        }]
-                    })
-                } else {
-                    json!({
-                        "error": {
-                            "code": -32002,
-                            "message": format!("Resource not found: {}", uri)
-                        }
-                    })
-                }
+                Ok(json!({
+                    "contents": [{
+                        "uri": resource.uri,
+                        "mimeType": resource.mime_type,
+                        "text": resource.content
+                    }]
+                }))
             }
             
-            _ => json!({ "error": { "code": -32601, "message": format!("Unknown method: {}", method) } }),
-        };
-        
-        json!({
-            "jsonrpc": "2.0",
-            "id": id,
-            "result": result
-        }).to_string()
+            _ => Err((METHOD_NOT_FOUND, format!("Method not found: {}", method))),
+        }
     }
 }
 
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1370,12 +2390,26 @@ mod tests {
         let names: Vec<_> = tools.iter().map(|t| t.name.as_str()).collect();
         assert!(names.contains(&"loom_create"));
         assert!(names.contains(&"loom_claim"));
+        assert!(names.contains(&"loom_renew"));
+        assert!(names.contains(&"loom_import_plan"));
         assert!(names.contains(&"loom_complete"));
         assert!(names.contains(&"loom_ready"));
         assert!(names.contains(&"loom_route"));
+        assert!(names.contains(&"loom_heartbeat"));
+        assert!(names.contains(&"loom_register_agent"));
+        assert!(names.contains(&"loom_probe_agents"));
+        assert!(names.contains(&"loom_reprice"));
+        assert!(names.contains(&"loom_budgets"));
         assert!(names.contains(&"loom_session_start"));
+        assert!(names.contains(&"loom_session_pause"));
+        assert!(names.contains(&"loom_time"));
         assert!(names.contains(&"loom_checkpoint"));
         assert!(names.contains(&"loom_formulas"));
+        assert!(names.contains(&"loom_run_formula"));
+        
+        // A duplicate name would shadow the later tool's handler
+        let unique: std::collections::HashSet<_> = names.iter().collect();
+        assert_eq!(unique.len(), names.len());
     }
     
     #[test]
@@ -1403,4 +2437,85 @@ mod tests {
         assert_eq!(summary["total"], 1);
         assert_eq!(summary["ready"], 1);
     }
+    
+    #[test]
+    fn test_agent_rate_limits() {
+        use tempfile::tempdir;
+        
+        let dir = tempdir().unwrap();
+        Loom::init(dir.path()).unwrap();
+        let config = dir.path().join(".loom/config.toml");
+        let toml = std::fs::read_to_string(&config).unwrap()
+            .replace("# creates-per-minute = 30", "creates-per-minute = 2");
+        std::fs::write(&config, toml).unwrap();
+        let mut loom = Loom::open(dir.path()).unwrap();
+        
+        for title in ["one", "two"] {
+            call_tool(&mut loom, "loom_create", json!({ "title": title, "agent": "looper" })).unwrap();
+        }
+        let err = call_tool(&mut loom, "loom_create", json!({ "title": "three", "agent": "looper" })).unwrap_err();
+        assert!(err.contains("Rate limit: looper made 2 creates"), "{}", err);
+        assert_eq!(loom.list().unwrap().len(), 2);
+        
+        // Other agents have their own budget
+        call_tool(&mut loom, "loom_work", json!({ "title": "solo", "agent": "cursor" })).unwrap();
+        let quota = call_tool(&mut loom, "loom_quota", json!({ "agent": "looper" })).unwrap();
+        assert_eq!(quota["creates_last_minute"], 2);
+        assert_eq!(quota["open_tasks"], 2);
+        assert_eq!(quota["can_create"], false);
+        assert_eq!(quota["limits"]["creates-per-minute"], 2);
+    }
+    
+    #[test]
+    fn test_register_agent_at_runtime() {
+        let mut loom = Loom::in_memory().unwrap();
+        let args = json!({
+            "id": "local-llama",
+            "family": "llama",
+            "cli": "/opt/llama/run",
+            "input_per_1k": 0.0,
+            "output_per_1k": 0.0,
+            "coding": 0.9
+        });
+        let profile = call_tool(&mut loom, "loom_register_agent", args.clone()).unwrap();
+        assert_eq!(profile["cli_path"], "/opt/llama/run");
+        assert!(loom.agents().unwrap().iter().any(|a| a.id == "local-llama" && a.available));
+        
+        // Reconfiguring keeps what routing has learned
+        loom.record_execution("local-llama", "lm-1", Some("coding"), true, Some(30.0)).unwrap();
+        let mut args = args;
+        args["cli"] = json!("/opt/llama/run-v2");
+        call_tool(&mut loom, "loom_register_agent", args.clone()).unwrap();
+        let agent = loom.agent("local-llama").unwrap().unwrap();
+        assert_eq!(agent.cli_path, "/opt/llama/run-v2");
+        assert_eq!(agent.quality.successes, 1);
+        
+        args["coding"] = json!(1.5);
+        let err = call_tool(&mut loom, "loom_register_agent", args).unwrap_err();
+        assert!(err.contains("coding must be between 0.0 and 1.0"), "{}", err);
+    }
+    
+    #[test]
+    fn test_serve_survives_bad_frames() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut server = McpServer::new(Loom::init(dir.path()).unwrap());
+        
+        let mut input = Vec::new();
+        input.extend_from_slice(b"{\"jsonrpc\":\"2.0\",\"id\":1,\"method\":\"initialize\",\"params\":{\"protocolVersion\":\"2024-11-05\"}}\n");
+        input.extend_from_slice(b"\xff\xfe not utf-8\n\n");
+        input.extend_from_slice(b"{\"jsonrpc\":\"2.0\",\"method\":\"notifications/initialized\"}\n");
+        input.extend_from_slice(b"{\"jsonrpc\":\"2.0\",\"id\":2,\"method\":\"ping\"}");
+        let mut output = Vec::new();
+        server.serve(&input[..], &mut output).unwrap();
+        
+        let responses: Vec<Value> = String::from_utf8(output).unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(responses.len(), 3);
+        assert_eq!(responses[0]["result"]["protocolVersion"], "2024-11-05");
+        assert_eq!(responses[1]["error"]["code"], PARSE_ERROR);
+        assert_eq!(responses[2], json!({ "jsonrpc": "2.0", "id": 2, "result": {} }));
+        assert_eq!(server.protocol_version(), Some("2024-11-05"));
+    }
 }
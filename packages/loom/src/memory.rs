@@ -20,6 +20,9 @@ pub enum MemoryError {
     #[error("Checkpoint not found: {0}")]
     CheckpointNotFound(String),
     
+    #[error("Session is not active: {0}")]
+    SessionNotActive(String),
+    
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
     
@@ -27,6 +30,9 @@ pub enum MemoryError {
     Io(#[from] std::io::Error),
 }
 
+/// When an agent was working: (agent, started, ended)
+pub type SessionSpan = (String, DateTime<Utc>, DateTime<Utc>);
+
 /// Session status
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -374,6 +380,22 @@ impl SessionContext {
     }
 }
 
+/// A recorded step in a session's life, from the `session_events` table
+///
+/// `event` is one of `started`, `checkpoint` (with its summary as
+/// `detail`), `paused`, `unpaused`, `resumed`, or the status a session
+/// ended with (`completed`, `failed`, `interrupted`, `cancelled`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionEvent {
+    pub seq: i64,
+    pub session_id: String,
+    pub task_id: String,
+    pub agent_id: String,
+    pub event: String,
+    pub detail: Option<String>,
+    pub at: DateTime<Utc>,
+}
+
 /// A checkpoint - snapshot of session state for recovery
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Checkpoint {
@@ -393,6 +415,65 @@ pub struct Checkpoint {
     pub created_at: DateTime<Utc>,
 }
 
+/// Active working time for one session: wall time minus pauses
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionTime {
+    pub session_id: String,
+    pub agent_id: String,
+    pub task_id: String,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+    /// Paused right now
+    pub paused: bool,
+    pub active_secs: f64,
+    pub paused_secs: f64,
+}
+
+/// Working time summed over sessions, for one task or one agent
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TimeTotal {
+    /// Task or agent ID
+    pub id: String,
+    pub sessions: u32,
+    pub active_secs: f64,
+    pub paused_secs: f64,
+}
+
+/// Working time per task and per agent, most time first
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TimeReport {
+    pub by_task: Vec<TimeTotal>,
+    pub by_agent: Vec<TimeTotal>,
+}
+
+impl TimeReport {
+    fn build(times: &[SessionTime]) -> Self {
+        let total = |key: fn(&SessionTime) -> &String| {
+            let mut totals: std::collections::HashMap<&str, TimeTotal> = std::collections::HashMap::new();
+            for time in times {
+                let entry = totals.entry(key(time)).or_insert_with(|| TimeTotal { id: key(time).clone(), ..Default::default() });
+                entry.sessions += 1;
+                entry.active_secs += time.active_secs;
+                entry.paused_secs += time.paused_secs;
+            }
+            let mut totals: Vec<TimeTotal> = totals.into_values().collect();
+            totals.sort_by(|a, b| b.active_secs.total_cmp(&a.active_secs).then_with(|| a.id.cmp(&b.id)));
+            totals
+        };
+        
+        Self {
+            by_task: total(|t| &t.task_id),
+            by_agent: total(|t| &t.agent_id),
+        }
+    }
+}
+
+/// A session's worked intervals, with pauses cut out
+struct WorkedSession {
+    time: SessionTime,
+    intervals: Vec<(DateTime<Utc>, DateTime<Utc>)>,
+}
+
 /// Memory store - manages sessions, checkpoints, and context
 pub struct MemoryStore {
     conn: Connection,
@@ -411,6 +492,13 @@ impl MemoryStore {
         Ok(store)
     }
     
+    /// Create an in-memory memory store (for testing)
+    pub fn in_memory() -> Result<Self, MemoryError> {
+        let store = Self { conn: Connection::open_in_memory()? };
+        store.init_schema()?;
+        Ok(store)
+    }
+    
     fn init_schema(&self) -> Result<(), MemoryError> {
         self.conn.execute_batch(r#"
             CREATE TABLE IF NOT EXISTS sessions (
@@ -443,6 +531,16 @@ impl MemoryStore {
             
             CREATE INDEX IF NOT EXISTS idx_checkpoints_session ON checkpoints(session_id);
             
+            -- Pauses within a session; an open pause has no resumed_at
+            CREATE TABLE IF NOT EXISTS session_pauses (
+                session_id TEXT NOT NULL,
+                paused_at TEXT NOT NULL,
+                resumed_at TEXT,
+                FOREIGN KEY (session_id) REFERENCES sessions(id)
+            );
+            
+            CREATE INDEX IF NOT EXISTS idx_session_pauses_session ON session_pauses(session_id);
+            
             -- Recovery queue for interrupted sessions
             CREATE TABLE IF NOT EXISTS recovery_queue (
                 session_id TEXT PRIMARY KEY,
@@ -452,10 +550,95 @@ impl MemoryStore {
                 error_message TEXT,
                 FOREIGN KEY (session_id) REFERENCES sessions(id)
             );
+            
+            -- What happened to sessions, in order, for the daemon's event stream
+            CREATE TABLE IF NOT EXISTS session_events (
+                seq INTEGER PRIMARY KEY AUTOINCREMENT,
+                session_id TEXT NOT NULL,
+                task_id TEXT NOT NULL,
+                agent_id TEXT NOT NULL,
+                event TEXT NOT NULL,
+                detail TEXT,
+                at TEXT NOT NULL
+            );
+        "#)?;
+        
+        // Recorded by triggers, so every code path that touches a session counts
+        self.conn.execute_batch(r#"
+            CREATE TRIGGER IF NOT EXISTS session_events_started
+            AFTER INSERT ON sessions
+            BEGIN
+                INSERT INTO session_events (session_id, task_id, agent_id, event, at)
+                VALUES (NEW.id, NEW.task_id, NEW.agent_id, 'started', strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now'));
+            END;
+            
+            CREATE TRIGGER IF NOT EXISTS session_events_status
+            AFTER UPDATE OF status ON sessions
+            WHEN OLD.status IS NOT NEW.status
+            BEGIN
+                INSERT INTO session_events (session_id, task_id, agent_id, event, at)
+                VALUES (
+                    NEW.id, NEW.task_id, NEW.agent_id,
+                    CASE WHEN NEW.status = 'active' THEN 'resumed' ELSE NEW.status END,
+                    strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now')
+                );
+            END;
+            
+            CREATE TRIGGER IF NOT EXISTS session_events_checkpoint
+            AFTER INSERT ON checkpoints
+            BEGIN
+                INSERT INTO session_events (session_id, task_id, agent_id, event, detail, at)
+                SELECT s.id, s.task_id, s.agent_id, 'checkpoint', NEW.summary, strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now')
+                FROM sessions s WHERE s.id = NEW.session_id;
+            END;
+            
+            CREATE TRIGGER IF NOT EXISTS session_events_paused
+            AFTER INSERT ON session_pauses
+            BEGIN
+                INSERT INTO session_events (session_id, task_id, agent_id, event, at)
+                SELECT s.id, s.task_id, s.agent_id, 'paused', strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now')
+                FROM sessions s WHERE s.id = NEW.session_id;
+            END;
+            
+            CREATE TRIGGER IF NOT EXISTS session_events_unpaused
+            AFTER UPDATE OF resumed_at ON session_pauses
+            WHEN OLD.resumed_at IS NULL AND NEW.resumed_at IS NOT NULL
+            BEGIN
+                INSERT INTO session_events (session_id, task_id, agent_id, event, at)
+                SELECT s.id, s.task_id, s.agent_id, 'unpaused', strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now')
+                FROM sessions s WHERE s.id = NEW.session_id;
+            END;
         "#)?;
         Ok(())
     }
     
+    /// Session events after `seq`, oldest first
+    pub fn session_events_since(&self, seq: i64, limit: usize) -> Result<Vec<SessionEvent>, MemoryError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT seq, session_id, task_id, agent_id, event, detail, at FROM session_events
+             WHERE seq > ?1 ORDER BY seq LIMIT ?2"
+        )?;
+        let events = stmt.query_map(params![seq, limit as i64], |row| {
+            Ok(SessionEvent {
+                seq: row.get(0)?,
+                session_id: row.get(1)?,
+                task_id: row.get(2)?,
+                agent_id: row.get(3)?,
+                event: row.get(4)?,
+                detail: row.get(5)?,
+                at: DateTime::parse_from_rfc3339(&row.get::<_, String>(6)?)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .unwrap_or_else(|_| Utc::now()),
+            })
+        })?.collect::<Result<Vec<_>, _>>()?;
+        Ok(events)
+    }
+    
+    /// Sequence number of the newest session event (0 when there are none)
+    pub fn latest_session_event_seq(&self) -> Result<i64, MemoryError> {
+        Ok(self.conn.query_row("SELECT COALESCE(MAX(seq), 0) FROM session_events", [], |row| row.get(0))?)
+    }
+    
     // ─────────────────────────────────────────────────────────────────────
     // Session Management
     // ─────────────────────────────────────────────────────────────────────
@@ -564,6 +747,39 @@ impl MemoryStore {
         }
     }
     
+    /// All sessions on a task, oldest first
+    pub fn sessions_for_task(&self, task_id: &str) -> Result<Vec<Session>, MemoryError> {
+        let mut stmt = self.conn.prepare(
+            r#"SELECT id, agent_id, task_id, status, started_at, ended_at,
+                      working_dir, git_branch, last_checkpoint, context_json
+               FROM sessions 
+               WHERE task_id = ?1
+               ORDER BY started_at ASC"#
+        )?;
+        
+        let sessions = stmt.query_map(params![task_id], |row| {
+            let context_json: String = row.get(9)?;
+            Ok(Session {
+                id: row.get(0)?,
+                agent_id: row.get(1)?,
+                task_id: row.get(2)?,
+                status: SessionStatus::from_str(&row.get::<_, String>(3)?).unwrap_or(SessionStatus::Active),
+                started_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(4)?)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .unwrap_or_else(|_| Utc::now()),
+                ended_at: row.get::<_, Option<String>>(5)?
+                    .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+                    .map(|dt| dt.with_timezone(&Utc)),
+                working_dir: row.get(6)?,
+                git_branch: row.get(7)?,
+                last_checkpoint: row.get(8)?,
+                context: serde_json::from_str(&context_json).unwrap_or_default(),
+            })
+        })?.collect::<Result<Vec<_>, _>>()?;
+        
+        Ok(sessions)
+    }
+    
     /// Update session context
     pub fn update_context(&mut self, session_id: &str, context: &SessionContext) -> Result<(), MemoryError> {
         let json = serde_json::to_string(context)?;
@@ -585,6 +801,12 @@ impl MemoryStore {
             params![status.as_str(), now.to_rfc3339(), session_id],
         )?;
         
+        // Ending while paused closes the pause
+        self.conn.execute(
+            "UPDATE session_pauses SET resumed_at = ?1 WHERE session_id = ?2 AND resumed_at IS NULL",
+            params![now.to_rfc3339(), session_id],
+        )?;
+        
         // Remove from recovery queue if present
         self.conn.execute(
             "DELETE FROM recovery_queue WHERE session_id = ?1",
@@ -594,6 +816,178 @@ impl MemoryStore {
         Ok(())
     }
     
+    /// Flag an active session as interrupted and queue it for recovery
+    pub fn interrupt_session(&mut self, session_id: &str, reason: &str) -> Result<(), MemoryError> {
+        self.conn.execute(
+            "UPDATE sessions SET status = 'interrupted' WHERE id = ?1 AND status = 'active'",
+            params![session_id],
+        )?;
+        self.conn.execute(
+            "INSERT OR REPLACE INTO recovery_queue (session_id, error_message) VALUES (?1, ?2)",
+            params![session_id, reason],
+        )?;
+        Ok(())
+    }
+    
+    // ─────────────────────────────────────────────────────────────────────
+    // Time Tracking
+    // ─────────────────────────────────────────────────────────────────────
+    
+    /// Pause an active session; time until it's unpaused doesn't count as
+    /// worked. Pausing a paused session does nothing.
+    pub fn pause_session(&mut self, session_id: &str) -> Result<(), MemoryError> {
+        self.require_active(session_id)?;
+        if self.open_pause(session_id)?.is_none() {
+            self.conn.execute(
+                "INSERT INTO session_pauses (session_id, paused_at) VALUES (?1, ?2)",
+                params![session_id, Utc::now().to_rfc3339()],
+            )?;
+        }
+        Ok(())
+    }
+    
+    /// Resume a paused session. Unpausing a running session does nothing.
+    pub fn unpause_session(&mut self, session_id: &str) -> Result<(), MemoryError> {
+        self.require_active(session_id)?;
+        self.conn.execute(
+            "UPDATE session_pauses SET resumed_at = ?1 WHERE session_id = ?2 AND resumed_at IS NULL",
+            params![Utc::now().to_rfc3339(), session_id],
+        )?;
+        Ok(())
+    }
+    
+    /// Active and paused time for a session
+    pub fn session_time(&self, session_id: &str) -> Result<SessionTime, MemoryError> {
+        self.worked_sessions(Some(session_id), Utc::now())?
+            .into_iter()
+            .next()
+            .map(|w| w.time)
+            .ok_or_else(|| MemoryError::SessionNotFound(session_id.to_string()))
+    }
+    
+    /// Working time summed per task and per agent, over all sessions
+    pub fn time_report(&self) -> Result<TimeReport, MemoryError> {
+        let times: Vec<SessionTime> = self.worked_sessions(None, Utc::now())?.into_iter().map(|w| w.time).collect();
+        Ok(TimeReport::build(&times))
+    }
+    
+    /// Active seconds an agent has spent on a task, across sessions
+    pub fn worked_secs(&self, task_id: &str, agent_id: &str) -> Result<f64, MemoryError> {
+        Ok(self.worked_sessions(None, Utc::now())?
+            .iter()
+            .filter(|w| w.time.task_id == task_id && w.time.agent_id == agent_id)
+            .map(|w| w.time.active_secs)
+            .sum())
+    }
+    
+    fn require_active(&self, session_id: &str) -> Result<(), MemoryError> {
+        let session = self.get_session(session_id)?
+            .ok_or_else(|| MemoryError::SessionNotFound(session_id.to_string()))?;
+        if session.status != SessionStatus::Active {
+            return Err(MemoryError::SessionNotActive(session_id.to_string()));
+        }
+        Ok(())
+    }
+    
+    fn open_pause(&self, session_id: &str) -> Result<Option<String>, MemoryError> {
+        match self.conn.query_row(
+            "SELECT paused_at FROM session_pauses WHERE session_id = ?1 AND resumed_at IS NULL",
+            params![session_id],
+            |row| row.get(0),
+        ) {
+            Ok(paused_at) => Ok(Some(paused_at)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+    
+    /// Every session (or just `only`) with its pauses cut out. Active sessions
+    /// run until now; interrupted sessions never recorded an end, so they count
+    /// up to their last checkpoint and are skipped without one.
+    fn worked_sessions(&self, only: Option<&str>, now: DateTime<Utc>) -> Result<Vec<WorkedSession>, MemoryError> {
+        let mut stmt = self.conn.prepare(
+            r#"SELECT s.id, s.agent_id, s.task_id, s.status, s.started_at,
+                      COALESCE(s.ended_at, (SELECT MAX(c.created_at) FROM checkpoints c WHERE c.session_id = s.id))
+               FROM sessions s
+               WHERE ?1 IS NULL OR s.id = ?1
+               ORDER BY s.started_at"#
+        )?;
+        let rows = stmt.query_map(params![only], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, String>(4)?,
+                row.get::<_, Option<String>>(5)?,
+            ))
+        })?.collect::<Result<Vec<_>, _>>()?;
+        
+        let mut stmt = self.conn.prepare(
+            "SELECT session_id, paused_at, resumed_at FROM session_pauses WHERE ?1 IS NULL OR session_id = ?1 ORDER BY paused_at"
+        )?;
+        let mut pauses: std::collections::HashMap<String, Vec<(String, Option<String>)>> = std::collections::HashMap::new();
+        for row in stmt.query_map(params![only], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, Option<String>>(2)?))
+        })? {
+            let (session_id, paused_at, resumed_at) = row?;
+            pauses.entry(session_id).or_default().push((paused_at, resumed_at));
+        }
+        
+        let parse = |s: &str| DateTime::parse_from_rfc3339(s).ok().map(|dt| dt.with_timezone(&Utc));
+        let secs = |from: DateTime<Utc>, to: DateTime<Utc>| (to - from).num_milliseconds().max(0) as f64 / 1000.0;
+        let mut sessions = Vec::new();
+        for (id, agent_id, task_id, status, started_at, ended_at) in rows {
+            let Some(started) = parse(&started_at) else { continue };
+            let ended = if status == "active" { None } else { ended_at.as_deref().and_then(parse) };
+            let until = match ended {
+                Some(ended) => ended,
+                None if status == "active" => now,
+                None => continue,
+            };
+            
+            let mut intervals = Vec::new();
+            let mut cursor = started;
+            let mut paused = false;
+            let mut paused_secs = 0.0;
+            for (paused_at, resumed_at) in pauses.remove(&id).unwrap_or_default() {
+                let Some(from) = parse(&paused_at) else { continue };
+                let to = match resumed_at.as_deref().and_then(parse) {
+                    Some(to) => to,
+                    None => {
+                        paused = ended.is_none();
+                        until
+                    }
+                };
+                let (from, to) = (from.clamp(cursor, until), to.clamp(cursor, until));
+                if from > cursor {
+                    intervals.push((cursor, from));
+                }
+                paused_secs += secs(from, to);
+                cursor = cursor.max(to);
+            }
+            if until > cursor {
+                intervals.push((cursor, until));
+            }
+            
+            sessions.push(WorkedSession {
+                time: SessionTime {
+                    session_id: id,
+                    agent_id,
+                    task_id,
+                    started_at: started,
+                    ended_at: ended,
+                    paused,
+                    active_secs: intervals.iter().map(|(from, to)| secs(*from, *to)).sum(),
+                    paused_secs,
+                },
+                intervals,
+            });
+        }
+        
+        Ok(sessions)
+    }
+    
     // ─────────────────────────────────────────────────────────────────────
     // Checkpoints
     // ─────────────────────────────────────────────────────────────────────
@@ -769,6 +1163,106 @@ impl MemoryStore {
         self.get_session(session_id)?
             .ok_or_else(|| MemoryError::SessionNotFound(session_id.to_string()))
     }
+    
+    // ─────────────────────────────────────────────────────────────────────
+    // Utilization
+    // ─────────────────────────────────────────────────────────────────────
+    
+    /// When each agent was working, for every session overlapping `since..now`.
+    /// Paused stretches are left out, so a paused session yields one span per
+    /// worked stretch.
+    pub fn session_spans(
+        &self,
+        since: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<SessionSpan>, MemoryError> {
+        let mut spans = Vec::new();
+        for worked in self.worked_sessions(None, now)? {
+            for (started, ended) in worked.intervals {
+                let (started, ended) = (started.max(since), ended.min(now));
+                if ended > started {
+                    spans.push((worked.time.agent_id.clone(), started, ended));
+                }
+            }
+        }
+        
+        Ok(spans)
+    }
+    
+    // ─────────────────────────────────────────────────────────────────────
+    // Maintenance
+    // ─────────────────────────────────────────────────────────────────────
+    
+    /// Prune sessions for tasks that no longer exist, finished sessions older
+    /// than `cutoff`, and checkpoints/recovery entries left without a session.
+    /// Returns (orphaned sessions, expired sessions, orphaned checkpoints, orphaned recovery entries).
+    pub fn prune(
+        &mut self,
+        live_task_ids: &std::collections::HashSet<String>,
+        cutoff: DateTime<Utc>,
+    ) -> Result<(u32, u32, u32, u32), MemoryError> {
+        let tx = self.conn.transaction()?;
+        
+        // Checkpoints and recovery entries reference sessions, so collect the
+        // doomed sessions first and delete children before parents
+        let sessions: Vec<(String, String, String, Option<String>)> = {
+            let mut stmt = tx.prepare("SELECT id, task_id, status, ended_at FROM sessions")?;
+            let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))?
+                .collect::<Result<Vec<_>, _>>()?;
+            rows
+        };
+        
+        let mut orphaned = 0;
+        let mut expired = 0;
+        let mut doomed = Vec::new();
+        for (id, task_id, status, ended_at) in sessions {
+            // Active and interrupted sessions are kept regardless of age so they can still be recovered
+            let finished = matches!(status.as_str(), "completed" | "failed" | "cancelled");
+            let too_old = ended_at
+                .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+                .map(|dt| dt.with_timezone(&Utc) < cutoff)
+                .unwrap_or(false);
+            
+            if !live_task_ids.contains(&task_id) {
+                orphaned += 1;
+                doomed.push(id);
+            } else if finished && too_old {
+                expired += 1;
+                doomed.push(id);
+            }
+        }
+        
+        let mut checkpoints = 0;
+        let mut recovery = 0;
+        for id in &doomed {
+            checkpoints += tx.execute("DELETE FROM checkpoints WHERE session_id = ?1", params![id])?;
+            recovery += tx.execute("DELETE FROM recovery_queue WHERE session_id = ?1", params![id])?;
+            tx.execute("DELETE FROM session_pauses WHERE session_id = ?1", params![id])?;
+            tx.execute("DELETE FROM sessions WHERE id = ?1", params![id])?;
+        }
+        
+        // Anything left pointing at a missing session (e.g. from older databases)
+        checkpoints += tx.execute(
+            "DELETE FROM checkpoints WHERE session_id NOT IN (SELECT id FROM sessions)",
+            [],
+        )?;
+        recovery += tx.execute(
+            "DELETE FROM recovery_queue WHERE session_id NOT IN (SELECT id FROM sessions)",
+            [],
+        )?;
+        tx.execute(
+            "DELETE FROM session_pauses WHERE session_id NOT IN (SELECT id FROM sessions)",
+            [],
+        )?;
+        
+        tx.commit()?;
+        Ok((orphaned as u32, expired as u32, checkpoints as u32, recovery as u32))
+    }
+    
+    /// VACUUM the database, returning bytes reclaimed
+    pub fn vacuum(&self) -> Result<u64, MemoryError> {
+        Ok(crate::maintenance::vacuum(&self.conn)?)
+    }
 }
 
 #[cfg(test)]
@@ -829,4 +1323,67 @@ mod tests {
         let resumed = store.resume_session(&session.id).unwrap();
         assert_eq!(resumed.status, SessionStatus::Active);
     }
+    
+    #[test]
+    fn test_time_tracking() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("memory.db");
+        let mut store = MemoryStore::open(&db_path).unwrap();
+        
+        let session = store.start_session("claude-code", "task-1", None, None).unwrap();
+        store.pause_session(&session.id).unwrap();
+        store.pause_session(&session.id).unwrap();
+        assert!(store.session_time(&session.id).unwrap().paused);
+        store.unpause_session(&session.id).unwrap();
+        assert!(!store.session_time(&session.id).unwrap().paused);
+        
+        // Backdate: started an hour ago, paused for the middle half hour
+        let now = Utc::now();
+        let at = |mins: i64| (now - chrono::Duration::minutes(mins)).to_rfc3339();
+        store.conn.execute("UPDATE sessions SET started_at = ?1 WHERE id = ?2", params![at(60), session.id]).unwrap();
+        store.conn.execute("DELETE FROM session_pauses", []).unwrap();
+        store.conn.execute(
+            "INSERT INTO session_pauses (session_id, paused_at, resumed_at) VALUES (?1, ?2, ?3)",
+            params![session.id, at(45), at(15)],
+        ).unwrap();
+        store.end_session(&session.id, SessionStatus::Completed).unwrap();
+        
+        let time = store.session_time(&session.id).unwrap();
+        assert!((time.active_secs - 1800.0).abs() < 5.0);
+        assert!((time.paused_secs - 1800.0).abs() < 5.0);
+        assert!(matches!(store.pause_session(&session.id), Err(MemoryError::SessionNotActive(_))));
+        
+        // Utilization sees two worked stretches
+        let spans = store.session_spans(now - chrono::Duration::hours(2), Utc::now()).unwrap();
+        assert_eq!(spans.len(), 2);
+        
+        store.start_session("codex", "task-1", None, None).unwrap();
+        let report = store.time_report().unwrap();
+        assert_eq!(report.by_task.len(), 1);
+        assert_eq!(report.by_task[0].sessions, 2);
+        assert_eq!(report.by_agent[0].id, "claude-code");
+        assert!((store.worked_secs("task-1", "claude-code").unwrap() - 1800.0).abs() < 5.0);
+    }
+    
+    #[test]
+    fn test_prune_orphans() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("memory.db");
+        let mut store = MemoryStore::open(&db_path).unwrap();
+        
+        let kept = store.start_session("claude-code", "task-live", None, None).unwrap();
+        let orphan = store.start_session("claude-code", "task-gone", None, None).unwrap();
+        store.create_checkpoint(&orphan.id, "Lost work", None).unwrap();
+        
+        let live: std::collections::HashSet<String> = ["task-live".to_string()].into_iter().collect();
+        let (orphaned, expired, checkpoints, _) = store.prune(&live, Utc::now()).unwrap();
+        
+        assert_eq!(orphaned, 1);
+        assert_eq!(expired, 0);
+        assert_eq!(checkpoints, 1);
+        assert!(store.get_session(&kept.id).unwrap().is_some());
+        assert!(store.get_session(&orphan.id).unwrap().is_none());
+        
+        store.vacuum().unwrap();
+    }
 }
@@ -106,6 +106,14 @@ pub struct ModelConfig {
     #[serde(default = "default_context")]
     pub max_context: u64,
     
+    /// Context kept free for the model's response
+    #[serde(default = "default_reserved_output")]
+    pub reserved_output: u64,
+    
+    /// Context kept free for the system prompt and tool definitions
+    #[serde(default = "default_reserved_system")]
+    pub reserved_system: u64,
+    
     /// Supports MCP
     #[serde(default)]
     pub mcp: bool,
@@ -130,9 +138,50 @@ pub struct ModelConfig {
 fn default_output_ratio() -> f64 { 2.5 }
 fn default_capability() -> f64 { 0.7 }
 fn default_context() -> u64 { 128_000 }
+fn default_reserved_output() -> u64 { 16_000 }
+fn default_reserved_system() -> u64 { 8_000 }
+
+/// Task context that fits a window with the default reservations
+pub fn default_context_budget(max_context: u64) -> u64 {
+    max_context.saturating_sub(default_reserved_output() + default_reserved_system())
+}
 fn default_concurrent() -> u32 { 3 }
 
 impl ModelConfig {
+    /// Tokens of task context an agent can inject without truncation: the
+    /// window less what's reserved for the response and system prompt
+    pub fn context_budget(&self) -> u64 {
+        self.max_context.saturating_sub(self.reserved_output + self.reserved_system)
+    }
+    
+    /// Check the values routing relies on: capabilities within 0.0 - 1.0,
+    /// costs that aren't negative, room for at least one task
+    pub fn validate(&self) -> Result<(), String> {
+        let capabilities = [
+            ("planning", self.planning),
+            ("coding", self.coding),
+            ("debugging", self.debugging),
+            ("ui", self.ui),
+            ("docs", self.docs),
+            ("refactor", self.refactor),
+            ("testing", self.testing),
+        ];
+        for (name, value) in capabilities {
+            if !(0.0..=1.0).contains(&value) {
+                return Err(format!("{} must be between 0.0 and 1.0, not {}", name, value));
+            }
+        }
+        for (name, value) in [("input_per_1k", self.input_per_1k), ("output_per_1k", self.output_per_1k), ("output_ratio", self.output_ratio)] {
+            if value.is_nan() || value < 0.0 {
+                return Err(format!("{} can't be negative", name));
+            }
+        }
+        if self.max_concurrent == 0 {
+            return Err("max_concurrent must be at least 1".to_string());
+        }
+        Ok(())
+    }
+    
     /// Convert to AgentProfile
     pub fn to_profile(&self, id: &str) -> AgentProfile {
         AgentProfile {
@@ -163,6 +212,9 @@ impl ModelConfig {
             active: 0,
             available: true,
             last_used: None,
+            last_heartbeat: None,
+            unresponsive: false,
+            probe: None,
         }
     }
 }
@@ -173,6 +225,35 @@ pub struct ModelsConfig {
     /// Model definitions keyed by ID
     #[serde(default)]
     pub models: HashMap<String, ModelConfig>,
+    /// How quality metrics weigh past executions
+    #[serde(default)]
+    pub quality: QualitySettings,
+}
+
+/// How quality metrics weigh past executions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QualitySettings {
+    /// Days after which an execution counts half as much toward an agent's
+    /// success rate (0 weighs all history equally)
+    #[serde(default = "default_half_life_days")]
+    pub half_life_days: f64,
+}
+
+fn default_half_life_days() -> f64 {
+    30.0
+}
+
+impl Default for QualitySettings {
+    fn default() -> Self {
+        Self { half_life_days: default_half_life_days() }
+    }
+}
+
+impl QualitySettings {
+    pub fn half_life(&self) -> Option<chrono::Duration> {
+        (self.half_life_days > 0.0)
+            .then(|| chrono::Duration::seconds((self.half_life_days * 86_400.0) as i64))
+    }
 }
 
 impl ModelsConfig {
@@ -201,6 +282,11 @@ impl ModelsConfig {
         self.models.get(id).map(|c| c.to_profile(id))
     }
     
+    /// Context budget for a model, if it's configured
+    pub fn context_budget(&self, id: &str) -> Option<u64> {
+        self.models.get(id).map(ModelConfig::context_budget)
+    }
+    
     /// Default models configuration
     pub fn defaults() -> Self {
         let toml = include_str!("default_models.toml");
@@ -244,4 +330,29 @@ mod tests {
         assert_eq!(sonnet.id, "claude-sonnet");
         assert!(sonnet.capabilities.planning > 0.8);
     }
+    
+    #[test]
+    fn test_context_budget() {
+        let config: ModelsConfig = toml::from_str(r#"
+            [models.small]
+            family = "other"
+            input_per_1k = 0.001
+            output_per_1k = 0.002
+            max_context = 32000
+            reserved_output = 4000
+            
+            [models.tiny]
+            family = "other"
+            input_per_1k = 0.001
+            output_per_1k = 0.002
+            max_context = 8000
+        "#).unwrap();
+        
+        // 32k window - 4k response - 8k system prompt (the default)
+        assert_eq!(config.context_budget("small"), Some(20_000));
+        // Reservations larger than the window leave nothing, not an underflow
+        assert_eq!(config.context_budget("tiny"), Some(0));
+        assert_eq!(config.context_budget("missing"), None);
+        assert_eq!(ModelsConfig::defaults().context_budget("claude-sonnet"), Some(default_context_budget(200_000)));
+    }
 }
@@ -0,0 +1,118 @@
+//! Outbound HTTP
+//!
+//! Webhooks, stakeholder digests, tracker sync, and pack downloads all go
+//! through one client, so they share a timeout and an error shape. Error
+//! statuses come back as responses, since trackers put the reason in the
+//! body; callers that only care about success use `post_json`.
+
+use std::io::Read;
+use std::path::Path;
+use std::time::Duration;
+
+/// Longest any request may take, connection included
+pub const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Largest pack archive `download` accepts
+const MAX_DOWNLOAD_BYTES: u64 = 64 * 1024 * 1024;
+
+lazy_static::lazy_static! {
+    static ref AGENT: ureq::Agent = ureq::AgentBuilder::new()
+        .timeout(TIMEOUT)
+        .user_agent(concat!("loom/", env!("CARGO_PKG_VERSION")))
+        .build();
+}
+
+/// Send a request, returning the status code and body. A JSON `body` is
+/// sent with `Content-Type: application/json`.
+pub fn request(method: &str, url: &str, headers: &[(&str, String)], body: Option<&str>) -> Result<(u16, String), String> {
+    let mut request = AGENT.request(method, url);
+    for (name, value) in headers {
+        request = request.set(name, value);
+    }
+    
+    let response = match body {
+        Some(body) => request.set("Content-Type", "application/json").send_string(body),
+        None => request.call(),
+    };
+    let response = match response {
+        Ok(response) => response,
+        Err(ureq::Error::Status(_, response)) => response,
+        Err(e) => return Err(format!("{} {}: {}", method, url, e)),
+    };
+    
+    let status = response.status();
+    let body = response.into_string().map_err(|e| format!("{} {}: {}", method, url, e))?;
+    Ok((status, body))
+}
+
+/// POST a JSON body, failing unless the response is 2xx
+pub fn post_json(url: &str, body: &str, headers: &[(&str, String)]) -> Result<(), String> {
+    let (status, response) = request("POST", url, headers, Some(body))?;
+    if (200..300).contains(&status) {
+        Ok(())
+    } else {
+        Err(format!("{} returned {}: {}", url, status, response.trim()))
+    }
+}
+
+/// Download an https:// URL to `dest`
+pub fn download(url: &str, dest: &Path) -> Result<(), String> {
+    if !url.starts_with("https://") {
+        return Err("only https:// URLs are supported".to_string());
+    }
+    let response = AGENT.get(url).call().map_err(|e| format!("GET {}: {}", url, e))?;
+    
+    let mut body = Vec::new();
+    response.into_reader()
+        .take(MAX_DOWNLOAD_BYTES + 1)
+        .read_to_end(&mut body)
+        .map_err(|e| format!("GET {}: {}", url, e))?;
+    if body.len() as u64 > MAX_DOWNLOAD_BYTES {
+        return Err(format!("{} is larger than {} MB", url, MAX_DOWNLOAD_BYTES / (1024 * 1024)));
+    }
+    std::fs::write(dest, body).map_err(|e| format!("Couldn't write {}: {}", dest.display(), e))
+}
+
+/// `Authorization` value for HTTP basic auth
+pub fn basic_auth(user: &str, password: &str) -> String {
+    use base64::Engine;
+    format!("Basic {}", base64::engine::general_purpose::STANDARD.encode(format!("{}:{}", user, password)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    
+    #[test]
+    fn test_request_returns_error_statuses() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut head = Vec::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                head.push(line.trim().to_lowercase());
+            }
+            let mut stream = stream;
+            stream.write_all(b"HTTP/1.1 422 Unprocessable\r\nContent-Length: 9\r\nConnection: close\r\n\r\nbad input").unwrap();
+            head
+        });
+        
+        let (status, body) = request("POST", &url, &[("X-Loom-Event", "created".to_string())], Some("{}")).unwrap();
+        assert_eq!((status, body.as_str()), (422, "bad input"));
+        let head = server.join().unwrap();
+        assert!(head.contains(&"x-loom-event: created".to_string()));
+        assert!(head.contains(&"content-type: application/json".to_string()));
+        
+        assert_eq!(basic_auth("sam@example.com", "token"), "Basic c2FtQGV4YW1wbGUuY29tOnRva2Vu");
+        assert!(download("http://example.com/pack.tgz", Path::new("/dev/null")).is_err());
+    }
+}
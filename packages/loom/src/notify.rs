@@ -0,0 +1,360 @@
+//! Stakeholder Notifications
+//!
+//! Per-stakeholder digests instead of one global feed. Each human listed in
+//! `[stakeholders]` gets only the activity on labels or repos they follow,
+//! and an `@handle` mention in a task triggers a targeted notification.
+//!
+//! Delivery goes to the stakeholder's webhook when configured, otherwise the
+//! digest is appended to `.loom/digests/{handle}.md`.
+//!
+//! Mentions are numbered in the store as they're first seen, and each
+//! stakeholder's cursor records the newest one sent, so a restart neither
+//! repeats nor drops alerts, and a task that keeps changing doesn't alert
+//! again for a mention it already had.
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::Path;
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::config::StakeholderConfig;
+use crate::work::{Task, WorkError, WorkStore};
+
+#[derive(Error, Debug)]
+pub enum NotifyError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+    
+    #[error("Webhook failed: {0}")]
+    Webhook(String),
+}
+
+/// A single task in a stakeholder digest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DigestEntry {
+    pub task_id: String,
+    pub title: String,
+    pub status: String,
+    pub labels: Vec<String>,
+    pub repo: Option<String>,
+    /// The stakeholder was @mentioned on this task
+    pub mentioned: bool,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Activity relevant to one stakeholder
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Digest {
+    /// Stakeholder handle (without the `@`)
+    pub stakeholder: String,
+    /// Start of the digest window
+    pub since: DateTime<Utc>,
+    pub generated_at: DateTime<Utc>,
+    pub entries: Vec<DigestEntry>,
+}
+
+impl Digest {
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+    
+    /// Entries where the stakeholder was mentioned
+    pub fn mentions(&self) -> impl Iterator<Item = &DigestEntry> {
+        self.entries.iter().filter(|e| e.mentioned)
+    }
+    
+    /// Render as markdown
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!(
+            "## Digest for @{} ({})\n\n",
+            self.stakeholder,
+            self.generated_at.format("%Y-%m-%d %H:%M UTC")
+        );
+        
+        for entry in &self.entries {
+            out.push_str(&format!(
+                "- {}{} [{}] {}",
+                if entry.mentioned { "**@mention** " } else { "" },
+                entry.task_id,
+                entry.status,
+                entry.title
+            ));
+            if !entry.labels.is_empty() {
+                out.push_str(&format!(" ({})", entry.labels.join(", ")));
+            }
+            out.push('\n');
+        }
+        
+        out
+    }
+}
+
+/// Extract `@handle` mentions from free text
+pub fn extract_mentions(text: &str) -> Vec<String> {
+    lazy_static::lazy_static! {
+        static ref MENTION: Regex = Regex::new(r"(?:^|[^\w@.])@([A-Za-z0-9][A-Za-z0-9_-]*)").unwrap();
+    }
+    
+    let mut handles: Vec<String> = MENTION.captures_iter(text)
+        .map(|c| c[1].to_lowercase())
+        .collect();
+    handles.sort();
+    handles.dedup();
+    handles
+}
+
+/// Handles mentioned anywhere in a task's text fields
+pub fn task_mentions(task: &Task) -> Vec<String> {
+    let mut handles: Vec<String> = task_mention_lines(task).into_iter().map(|(handle, _)| handle).collect();
+    handles.dedup();
+    handles
+}
+
+/// `(handle, line)` for each mention in a task's text fields. The line
+/// identifies the mention, so editing elsewhere in the text doesn't make it
+/// new again.
+pub fn task_mention_lines(task: &Task) -> Vec<(String, String)> {
+    let mut mentions = Vec::new();
+    for text in [Some(&task.title), task.description.as_ref(), task.evidence.as_ref()].into_iter().flatten() {
+        for line in text.lines() {
+            mentions.extend(extract_mentions(line).into_iter().map(|handle| (handle, line.trim().to_string())));
+        }
+    }
+    mentions.sort();
+    mentions.dedup();
+    mentions
+}
+
+/// Whether a stakeholder follows a task by label or repo
+fn follows(config: &StakeholderConfig, task: &Task) -> bool {
+    let by_label = task.labels.iter().any(|l| config.labels.contains(l));
+    let by_repo = task.repo.as_ref().map(|r| config.repos.contains(r)).unwrap_or(false);
+    by_label || by_repo
+}
+
+/// Build one digest per stakeholder from tasks updated after `since`.
+/// Empty digests are dropped.
+pub fn build_digests(
+    stakeholders: &HashMap<String, StakeholderConfig>,
+    tasks: &[Task],
+    since: DateTime<Utc>,
+) -> Vec<Digest> {
+    let now = Utc::now();
+    let recent: Vec<(&Task, Vec<String>)> = tasks.iter()
+        .filter(|t| t.updated_at > since)
+        .map(|t| (t, task_mentions(t)))
+        .collect();
+    
+    let mut handles: Vec<&String> = stakeholders.keys().collect();
+    handles.sort();
+    
+    handles.into_iter()
+        .map(|handle| {
+            let config = &stakeholders[handle];
+            let entries = recent.iter()
+                .filter_map(|(task, mentions)| {
+                    let mentioned = mentions.contains(&handle.to_lowercase());
+                    (mentioned || follows(config, task)).then(|| digest_entry(task, mentioned))
+                })
+                .collect();
+            
+            Digest {
+                stakeholder: handle.clone(),
+                since,
+                generated_at: now,
+                entries,
+            }
+        })
+        .filter(|d| !d.is_empty())
+        .collect()
+}
+
+fn digest_entry(task: &Task, mentioned: bool) -> DigestEntry {
+    DigestEntry {
+        task_id: task.id.clone(),
+        title: task.title.clone(),
+        status: task.status.as_str().to_string(),
+        labels: task.labels.clone(),
+        repo: task.repo.clone(),
+        mentioned,
+        updated_at: task.updated_at,
+    }
+}
+
+/// Send each stakeholder a targeted alert for mentions recorded since the
+/// last one they were sent
+///
+/// Mentions in `tasks` are recorded first. A stakeholder seen for the first
+/// time starts from now rather than replaying every old mention. A failed
+/// delivery leaves the cursor alone, to be retried on the next run; the
+/// failures come back as `@handle: error` messages.
+pub fn send_mentions(
+    store: &mut WorkStore,
+    stakeholders: &HashMap<String, StakeholderConfig>,
+    tasks: &[Task],
+    mut send: impl FnMut(&Digest) -> Result<(), String>,
+) -> Result<Vec<String>, WorkError> {
+    for task in tasks {
+        for (handle, line) in task_mention_lines(task) {
+            store.record_mention(&handle, &task.id, &line)?;
+        }
+    }
+    
+    let mut handles: Vec<&String> = stakeholders.keys().collect();
+    handles.sort();
+    
+    let mut errors = Vec::new();
+    for handle in handles {
+        let key = handle.to_lowercase();
+        let Some(cursor) = store.mention_cursor(&key)? else {
+            let latest = store.latest_mention_id()?;
+            store.set_mention_cursor(&key, latest)?;
+            continue;
+        };
+        let mentions = store.mentions_since(&key, cursor)?;
+        let Some((newest, _, _)) = mentions.last() else {
+            continue;
+        };
+        
+        let mut task_ids: Vec<&String> = Vec::new();
+        for (_, task_id, _) in &mentions {
+            if !task_ids.contains(&task_id) {
+                task_ids.push(task_id);
+            }
+        }
+        let digest = Digest {
+            stakeholder: handle.clone(),
+            since: mentions[0].2,
+            generated_at: Utc::now(),
+            entries: task_ids.into_iter()
+                .filter_map(|id| tasks.iter().find(|t| &t.id == id))
+                .map(|task| digest_entry(task, true))
+                .collect(),
+        };
+        match send(&digest) {
+            Ok(()) => store.set_mention_cursor(&key, *newest)?,
+            Err(e) => errors.push(format!("@{}: {}", handle, e)),
+        }
+    }
+    Ok(errors)
+}
+
+/// Deliver a digest to its stakeholder
+pub fn deliver(digest: &Digest, config: &StakeholderConfig, loom_root: &Path) -> Result<(), NotifyError> {
+    match &config.webhook {
+        Some(url) => post_webhook(url, &serde_json::to_value(digest)?),
+        None => {
+            let dir = loom_root.join("digests");
+            std::fs::create_dir_all(&dir)?;
+            let mut file = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(dir.join(format!("{}.md", digest.stakeholder)))?;
+            writeln!(file, "{}", digest.to_markdown())?;
+            Ok(())
+        }
+    }
+}
+
+/// POST a JSON payload to a webhook
+pub fn post_webhook(url: &str, payload: &serde_json::Value) -> Result<(), NotifyError> {
+    crate::net::post_json(url, &serde_json::to_string(payload)?, &[]).map_err(NotifyError::Webhook)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::work::{Status, Priority, TaskKind};
+    
+    fn make_task(id: &str, labels: &[&str], description: Option<&str>) -> Task {
+        Task {
+            id: id.to_string(),
+            title: format!("Task {}", id),
+            description: description.map(String::from),
+            status: Status::Ready,
+            priority: Priority::Normal,
+            agent: None,
+            labels: labels.iter().map(|s| s.to_string()).collect(),
+            parent: None,
+            evidence: None,
+            actual_cost_usd: None,
+            repo: None,
+            kind: TaskKind::Task,
+            rollup: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+    
+    #[test]
+    fn test_extract_mentions() {
+        assert_eq!(extract_mentions("ping @Sam and @alex-k, cc @sam"), vec!["alex-k", "sam"]);
+        assert!(extract_mentions("mail me at sam@example.com").is_empty());
+    }
+    
+    #[test]
+    fn test_digests_are_per_stakeholder() {
+        let mut stakeholders = HashMap::new();
+        stakeholders.insert("sam".to_string(), StakeholderConfig {
+            labels: vec!["auth".to_string()],
+            ..Default::default()
+        });
+        stakeholders.insert("alex".to_string(), StakeholderConfig::default());
+        
+        let tasks = vec![
+            make_task("lm-1", &["auth"], None),
+            make_task("lm-2", &["ui"], Some("Need a call from @alex")),
+            make_task("lm-3", &["ui"], None),
+        ];
+        let since = Utc::now() - chrono::Duration::hours(1);
+        
+        let digests = build_digests(&stakeholders, &tasks, since);
+        assert_eq!(digests.len(), 2);
+        
+        let alex = digests.iter().find(|d| d.stakeholder == "alex").unwrap();
+        assert_eq!(alex.entries.len(), 1);
+        assert!(alex.entries[0].mentioned);
+        
+        let sam = digests.iter().find(|d| d.stakeholder == "sam").unwrap();
+        assert_eq!(sam.entries[0].task_id, "lm-1");
+    }
+    
+    #[test]
+    fn test_mentions_sent_once() {
+        let mut store = WorkStore::in_memory().unwrap();
+        let mut stakeholders = HashMap::new();
+        stakeholders.insert("alex".to_string(), StakeholderConfig::default());
+        let mut sent: Vec<Vec<String>> = Vec::new();
+        let mut run = |store: &mut WorkStore, tasks: &[Task], fail: bool| {
+            send_mentions(store, &stakeholders, tasks, |digest| {
+                if fail {
+                    return Err("offline".to_string());
+                }
+                sent.push(digest.entries.iter().map(|e| e.task_id.clone()).collect());
+                Ok(())
+            }).unwrap()
+        };
+        
+        // Mentions from before the first run aren't replayed
+        let old = make_task("lm-1", &[], Some("Ask @alex"));
+        run(&mut store, std::slice::from_ref(&old), false);
+        
+        let mut new = make_task("lm-2", &["ui"], Some("@alex can you review?"));
+        run(&mut store, &[old.clone(), new.clone()], false);
+        
+        // Editing the task elsewhere isn't a new mention; a failed send is retried
+        new.description = Some("@alex can you review?\nUpdated the screenshots".to_string());
+        run(&mut store, &[old.clone(), new.clone()], false);
+        new.evidence = Some("Approved, thanks @alex".to_string());
+        assert_eq!(run(&mut store, &[old.clone(), new.clone()], true), vec!["@alex: offline"]);
+        run(&mut store, &[old, new], false);
+        
+        assert_eq!(sent, vec![vec!["lm-2".to_string()], vec!["lm-2".to_string()]]);
+    }
+}
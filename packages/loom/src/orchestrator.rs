@@ -6,16 +6,47 @@
 //!
 //! Philosophy: Each task gets a fresh context. No pollution between tasks.
 //! This is "weniger, aber besser" - less, but better.
+//!
+//! Failed executions are retried by the backend's `RetryPolicy`: only the
+//! failure classes it names, up to its attempt limit, with the wait doubling
+//! between attempts. Every attempt is recorded as an execution, so learned
+//! routing sees flaky backends for what they are.
+//!
+//! With `worktrees` on, each task's agent works in a git worktree of its own
+//! under `.loom/worktrees/<task-id>`, on branch `loom/<task-id>`, so agents
+//! running at once don't trip over each other's changes. The branch goes in
+//! the completion evidence; the worktree is left for review.
+//!
+//! The `Sandboxed` backend runs another backend's CLI inside the
+//! `sandbox.container` command (a container, or an OS confinement tool such
+//! as `bwrap`), with a cleared environment (only the variables `sandbox.env`
+//! names pass through), in a working directory that has to be inside
+//! `working_dir`, with `HOME` and `TMPDIR` pointed inside it. Without a
+//! container command it doesn't run at all, since nothing else keeps an
+//! untrusted agent away from paths outside its directory.
 
-use std::path::PathBuf;
-use std::process::{Command, Stdio};
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::thread::JoinHandle;
 use std::time::{Duration, Instant};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::{Loom, Task, Priority, LoomError};
+use crate::agents::AgentProfile;
 use crate::memory::SessionStatus;
+use crate::platform::{kill_tree, own_process_group, shell_command};
+use crate::routing::RoutingConstraints;
+use crate::workflow::{FanOut, FormulaRun, RunStatus, StepStatus};
+
+/// How often running agents are checked on
+const AGENT_POLL: Duration = Duration::from_millis(250);
+
+/// Longest wait between attempts, whatever a retry policy asks for
+const MAX_BACKOFF: Duration = Duration::from_secs(3600);
 
 #[derive(Error, Debug)]
 pub enum OrchestratorError {
@@ -33,10 +64,13 @@ pub enum OrchestratorError {
     
     #[error("Configuration error: {0}")]
     Config(String),
+    
+    #[error("Worktree error: {0}")]
+    Worktree(String),
 }
 
 /// Agent backend types
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum AgentBackend {
     /// Claude Code CLI (`claude`)
@@ -45,6 +79,11 @@ pub enum AgentBackend {
     GeminiPro,
     /// Gemini CLI with Flash model (`gemini -m gemini-2.5-flash`)
     GeminiFlash,
+    /// Another backend's CLI, confined as `OrchestratorConfig::sandbox` says
+    Sandboxed,
+    /// The CLI an agent registered with (`AgentProfile::cli_path`), run as
+    /// `<cli> -p <prompt>` like the others
+    Custom,
 }
 
 impl AgentBackend {
@@ -53,6 +92,8 @@ impl AgentBackend {
             AgentBackend::ClaudeCode => "claude-code",
             AgentBackend::GeminiPro => "gemini-pro",
             AgentBackend::GeminiFlash => "gemini-flash",
+            AgentBackend::Sandboxed => "sandboxed",
+            AgentBackend::Custom => "custom",
         }
     }
     
@@ -61,6 +102,27 @@ impl AgentBackend {
         match self {
             AgentBackend::ClaudeCode => which_exists("claude"),
             AgentBackend::GeminiPro | AgentBackend::GeminiFlash => which_exists("gemini"),
+            // Depends on the sandbox config; see `Orchestrator::available_backends`
+            AgentBackend::Sandboxed => false,
+            // Depends on the agent; see `Orchestrator::backend_for`
+            AgentBackend::Custom => false,
+        }
+    }
+    
+    /// The backend that runs a routed agent (a model profile such as
+    /// `claude-sonnet` or `gemini-2-flash`), if the orchestrator has one
+    pub fn for_agent(agent_id: &str) -> Option<Self> {
+        let id = agent_id.to_lowercase();
+        if id.starts_with("sandbox") {
+            Some(AgentBackend::Sandboxed)
+        } else if id.starts_with("claude") {
+            Some(AgentBackend::ClaudeCode)
+        } else if id.starts_with("gemini") && id.contains("flash") {
+            Some(AgentBackend::GeminiFlash)
+        } else if id.starts_with("gemini") {
+            Some(AgentBackend::GeminiPro)
+        } else {
+            None
         }
     }
     
@@ -111,13 +173,8 @@ impl AgentBackend {
 }
 
 fn which_exists(cmd: &str) -> bool {
-    Command::new("which")
-        .arg(cmd)
-        .stdout(Stdio::null())
-        .stderr(Stdio::null())
-        .status()
-        .map(|s| s.success())
-        .unwrap_or(false)
+    // The which crate also resolves PATHEXT (.exe, .cmd) on Windows
+    which::which(cmd).is_ok()
 }
 
 /// Configuration for the orchestrator
@@ -133,8 +190,235 @@ pub struct OrchestratorConfig {
     pub working_dir: PathBuf,
     /// Enable system notifications
     pub notifications: bool,
-    /// Maximum runtime per task in seconds (0 = no limit)
+    /// Maximum runtime per execution in seconds (0 = no limit); an agent
+    /// still going is killed and its task released
     pub max_task_runtime_secs: u64,
+    /// Retry policy per backend; backends not listed get the default
+    #[serde(default)]
+    pub retries: HashMap<AgentBackend, RetryPolicy>,
+    /// Run each task in a git worktree of its own, on branch `loom/<task-id>`
+    #[serde(default)]
+    pub worktrees: bool,
+    /// How the `Sandboxed` backend confines its agent
+    #[serde(default)]
+    pub sandbox: SandboxConfig,
+}
+
+impl OrchestratorConfig {
+    /// How long one execution may run, if limited
+    pub fn timeout(&self) -> Option<Duration> {
+        (self.max_task_runtime_secs > 0).then(|| Duration::from_secs(self.max_task_runtime_secs))
+    }
+    
+    pub fn retry_policy(&self, backend: AgentBackend) -> RetryPolicy {
+        self.retries.get(&backend).cloned().unwrap_or_default()
+    }
+}
+
+/// How the `Sandboxed` backend confines its agent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SandboxConfig {
+    /// Backend whose CLI runs in the sandbox
+    pub backend: AgentBackend,
+    /// Environment variables passed through, or set with `NAME=value`;
+    /// everything else is dropped
+    pub env: Vec<String>,
+    /// Command the CLI runs inside, e.g. `["docker", "run", "--rm", "-v",
+    /// "{dir}:{dir}", "-w", "{dir}", "agents"]`, with `{dir}` replaced by
+    /// the working directory and `{home}` by your home directory. Required:
+    /// with none, the sandbox refuses to run.
+    pub container: Vec<String>,
+    /// Files and directories under your home directory (CLI logins) linked
+    /// into the sandbox's home; ones that don't exist are skipped
+    pub credentials: Vec<String>,
+}
+
+impl Default for SandboxConfig {
+    fn default() -> Self {
+        Self {
+            backend: AgentBackend::ClaudeCode,
+            env: ["PATH", "LANG", "TERM", "SystemRoot", "ANTHROPIC_API_KEY", "GEMINI_API_KEY", "GOOGLE_API_KEY"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            container: Vec::new(),
+            credentials: [".claude", ".claude.json", ".gemini"].into_iter().map(String::from).collect(),
+        }
+    }
+}
+
+impl SandboxConfig {
+    /// The backend run inside (a sandbox can't hold another sandbox, or a
+    /// custom CLI it doesn't know the agent of)
+    pub fn inner(&self) -> AgentBackend {
+        match self.backend {
+            AgentBackend::Sandboxed | AgentBackend::Custom => AgentBackend::ClaudeCode,
+            backend => backend,
+        }
+    }
+    
+    /// Whether a container command is configured and installed
+    pub fn is_available(&self) -> bool {
+        self.container.first().is_some_and(|program| which_exists(program))
+    }
+    
+    /// `inner` as the sandbox runs it: inside the container command, in
+    /// `dir`, which has to be inside `root`, with only the allowed
+    /// environment, and a home and temp directory of its own under `dir`
+    /// holding links to the credentials under `user_home`
+    fn wrap(&self, inner: &Command, dir: &Path, root: &Path, user_home: Option<&Path>) -> std::io::Result<Command> {
+        let Some((program, args)) = self.container.split_first() else {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                "the sandbox needs sandbox.container (a container or confinement command) to run an agent",
+            ));
+        };
+        let dir = confined(dir, root)?;
+        let home = dir.join(".loom-sandbox");
+        std::fs::create_dir_all(&home)?;
+        std::fs::write(home.join(".gitignore"), "*\n")?;
+        if let Some(user_home) = user_home {
+            for path in &self.credentials {
+                let (original, link) = (user_home.join(path), home.join(path));
+                if original.exists() && link.symlink_metadata().is_err() {
+                    if let Some(parent) = link.parent() {
+                        std::fs::create_dir_all(parent)?;
+                    }
+                    symlink(&original, &link)?;
+                }
+            }
+        }
+        
+        let mut command = Command::new(program);
+        command.args(args.iter().map(|arg| {
+            let arg = arg.replace("{dir}", &dir.to_string_lossy());
+            match user_home {
+                Some(user_home) => arg.replace("{home}", &user_home.to_string_lossy()),
+                None => arg,
+            }
+        }));
+        command.arg(inner.get_program());
+        command.args(inner.get_args());
+        
+        command.env_clear();
+        for entry in &self.env {
+            match entry.split_once('=') {
+                Some((name, value)) => {
+                    command.env(name, value);
+                }
+                None => {
+                    if let Some(value) = std::env::var_os(entry) {
+                        command.env(entry, value);
+                    }
+                }
+            }
+        }
+        for name in ["HOME", "USERPROFILE", "TMPDIR", "TEMP", "TMP"] {
+            command.env(name, &home);
+        }
+        command
+            .current_dir(&dir)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        Ok(command)
+    }
+}
+
+#[cfg(unix)]
+fn symlink(original: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(original, link)
+}
+
+#[cfg(windows)]
+fn symlink(original: &Path, link: &Path) -> std::io::Result<()> {
+    if original.is_dir() {
+        std::os::windows::fs::symlink_dir(original, link)
+    } else {
+        std::os::windows::fs::symlink_file(original, link)
+    }
+}
+
+/// `dir`, resolved, if it's inside `root`
+fn confined(dir: &Path, root: &Path) -> std::io::Result<PathBuf> {
+    let dir = dir.canonicalize()?;
+    let root = root.canonicalize()?;
+    if !dir.starts_with(&root) {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::PermissionDenied,
+            format!("sandbox directory {} is outside {}", dir.display(), root.display()),
+        ));
+    }
+    Ok(dir)
+}
+
+/// Kinds of failed execution, for choosing what to retry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureClass {
+    /// The CLI couldn't be started
+    Launch,
+    /// The CLI exited with an error
+    Crash,
+    /// The CLI exited with an error about rate limits or overload
+    RateLimit,
+    /// The agent finished but reported failure
+    Reported,
+    /// The agent ran past `max_task_runtime_secs` and was killed
+    Timeout,
+}
+
+impl FailureClass {
+    /// Class of a CLI that exited with an error, from what it printed
+    fn of_exit(stderr: &str) -> Self {
+        let stderr = stderr.to_lowercase();
+        let limited = ["rate limit", "rate_limit", "429", "overloaded", "quota exceeded", "too many requests"]
+            .iter()
+            .any(|marker| stderr.contains(marker));
+        if limited { FailureClass::RateLimit } else { FailureClass::Crash }
+    }
+}
+
+/// How a backend's failed executions are retried
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetryPolicy {
+    /// Attempts in all, the first included (1 never retries)
+    pub max_attempts: u32,
+    /// Wait before the first retry, doubling for each one after
+    pub initial_backoff_secs: f64,
+    /// Longest wait between attempts
+    pub max_backoff_secs: f64,
+    /// Failures worth another attempt
+    pub retry_on: Vec<FailureClass>,
+}
+
+impl Default for RetryPolicy {
+    /// Rate limits pass, so they're retried; anything else probably won't
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff_secs: 10.0,
+            max_backoff_secs: 300.0,
+            retry_on: vec![FailureClass::RateLimit],
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Whether to try again after `attempt` (counting from 1) failed this way
+    pub fn retries(&self, class: FailureClass, attempt: u32) -> bool {
+        attempt < self.max_attempts && self.retry_on.contains(&class)
+    }
+    
+    /// Wait after `attempt` (counting from 1) fails, at most `MAX_BACKOFF`
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let secs = self.initial_backoff_secs * 2f64.powi(attempt.saturating_sub(1).min(30) as i32);
+        // Negative and NaN settings mean no wait; unrepresentable ones the longest
+        Duration::try_from_secs_f64(secs.min(self.max_backoff_secs).max(0.0))
+            .map_or(MAX_BACKOFF, |wait| wait.min(MAX_BACKOFF))
+    }
 }
 
 impl Default for OrchestratorConfig {
@@ -146,6 +430,9 @@ impl Default for OrchestratorConfig {
             working_dir: PathBuf::from("."),
             notifications: true,
             max_task_runtime_secs: 600, // 10 minutes
+            retries: HashMap::new(),
+            worktrees: false,
+            sandbox: SandboxConfig::default(),
         }
     }
 }
@@ -154,15 +441,35 @@ impl Default for OrchestratorConfig {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExecutionResult {
     pub task_id: String,
+    /// Session the execution ran in
+    pub session_id: String,
     pub backend: AgentBackend,
     pub success: bool,
     pub output: String,
+    /// Attempts made, retries included
+    pub attempts: u32,
+    /// The last attempt ran out of time; its task was released
+    #[serde(default)]
+    pub timed_out: bool,
+    /// Branch the agent worked on, with `worktrees` on
+    #[serde(default)]
+    pub branch: Option<String>,
+    /// Where the agent worked: its worktree, or the working directory
+    #[serde(default)]
+    pub working_dir: PathBuf,
     pub duration_secs: f64,
     pub started_at: DateTime<Utc>,
     pub ended_at: DateTime<Utc>,
     pub error: Option<String>,
 }
 
+impl ExecutionResult {
+    /// Evidence for completing the task: who did it, and on which branch
+    pub fn evidence(&self) -> String {
+        completion_evidence(self.backend, self.branch.as_deref())
+    }
+}
+
 /// The orchestrator - runs tasks through agent backends
 pub struct Orchestrator {
     config: OrchestratorConfig,
@@ -184,11 +491,23 @@ impl Orchestrator {
     /// Get available backends (those actually installed)
     pub fn available_backends(&self) -> Vec<AgentBackend> {
         self.config.backends.iter()
-            .filter(|b| b.is_available())
+            .filter(|b| match b {
+                AgentBackend::Sandboxed => self.config.sandbox.is_available(),
+                b => b.is_available(),
+            })
             .cloned()
             .collect()
     }
     
+    /// The backend that runs a routed agent here: a built-in one that's
+    /// available, or else the CLI the agent registered with, if installed
+    pub fn backend_for(&self, agent: &AgentProfile) -> Option<AgentBackend> {
+        match AgentBackend::for_agent(&agent.id) {
+            Some(backend) => Some(backend).filter(|b| self.available_backends().contains(b)),
+            None => (!agent.cli_path.is_empty() && which_exists(&agent.cli_path)).then_some(AgentBackend::Custom),
+        }
+    }
+    
     /// Generate a prompt for a task
     pub fn generate_prompt(&self, task: &Task, loom: &Loom) -> String {
         let mut prompt = format!(
@@ -219,7 +538,8 @@ impl Orchestrator {
         prompt
     }
     
-    /// Execute a single task with the appropriate backend
+    /// Execute a single task with the appropriate backend, recording the
+    /// executions under the backend's name
     pub fn execute_task(&self, task: &Task, loom: &mut Loom) -> Result<ExecutionResult, OrchestratorError> {
         let backend = AgentBackend::for_task(task);
         let available = self.available_backends();
@@ -233,28 +553,61 @@ impl Orchestrator {
             return Err(OrchestratorError::NoAgentsAvailable);
         };
         
+        self.execute_with(task, actual_backend, actual_backend.as_str(), loom)
+    }
+    
+    /// Execute a single task with a given backend, recording each attempt
+    /// as an execution by `agent_id` so its quality and latency are tracked.
+    /// `AgentBackend::Custom` runs the agent's registered CLI.
+    pub fn execute_with(&self, task: &Task, actual_backend: AgentBackend, agent_id: &str, loom: &mut Loom) -> Result<ExecutionResult, OrchestratorError> {
         let prompt = self.generate_prompt(task, loom);
+        let cli = loom.agent(agent_id)?.map(|agent| agent.cli_path);
+        let mut lease = LeaseKeeper::new(loom, &task.id)?;
+        let (working_dir, branch) = self.workspace(&task.id)?;
         let started_at = Utc::now();
         let start_instant = Instant::now();
         
         // Start a session
         let session = loom.start_session(&task.id, actual_backend.as_str())?;
         
-        // Execute based on backend
-        let result = match actual_backend {
-            AgentBackend::ClaudeCode => self.run_claude_code(&prompt),
-            AgentBackend::GeminiPro => self.run_gemini(&prompt, "gemini-2.5-pro"),
-            AgentBackend::GeminiFlash => self.run_gemini(&prompt, "gemini-2.5-flash"),
+        // Execute, retrying as the backend's policy allows
+        let policy = self.config.retry_policy(actual_backend);
+        let task_type = task.labels.first().map(String::as_str);
+        let mut attempts = 0;
+        let mut failure;
+        let result = loop {
+            attempts += 1;
+            let attempt_start = Instant::now();
+            let result = self.run_backend(actual_backend, cli.as_deref(), &prompt, &working_dir, &mut || lease.tick(loom));
+            failure = match &result {
+                Ok(output) if reports_success(output) => None,
+                Ok(_) => Some(FailureClass::Reported),
+                Err((class, _)) => Some(*class),
+            };
+            loom.record_execution(
+                agent_id,
+                &task.id,
+                task_type,
+                failure.is_none(),
+                Some(attempt_start.elapsed().as_secs_f64()),
+            )?;
+            
+            match failure {
+                Some(class) if policy.retries(class, attempts) => {
+                    std::thread::sleep(policy.backoff(attempts));
+                    lease.tick(loom);
+                }
+                _ => break result.map_err(|(_, e)| e),
+            }
         };
         
         let duration_secs = start_instant.elapsed().as_secs_f64();
         let ended_at = Utc::now();
+        let timed_out = failure == Some(FailureClass::Timeout);
         
         match result {
             Ok(output) => {
-                // Check if output indicates success
-                let success = output.to_lowercase().contains("success") 
-                    || !output.to_lowercase().contains("fail");
+                let success = reports_success(&output);
                 
                 // End session
                 let status = if success { SessionStatus::Completed } else { SessionStatus::Failed };
@@ -268,9 +621,14 @@ impl Orchestrator {
                 
                 Ok(ExecutionResult {
                     task_id: task.id.clone(),
+                    session_id: session.id.clone(),
                     backend: actual_backend,
                     success,
                     output,
+                    attempts,
+                    timed_out,
+                    branch: branch.clone(),
+                    working_dir: working_dir.clone(),
                     duration_secs,
                     started_at,
                     ended_at,
@@ -278,7 +636,13 @@ impl Orchestrator {
                 })
             }
             Err(e) => {
-                let _ = loom.end_session(&session.id, SessionStatus::Failed);
+                if timed_out {
+                    // Killed rather than failed, so another agent can try
+                    let _ = loom.end_session(&session.id, SessionStatus::Interrupted);
+                    let _ = loom.release(&task.id);
+                } else {
+                    let _ = loom.end_session(&session.id, SessionStatus::Failed);
+                }
                 
                 if self.config.notifications {
                     let _ = send_notification("Task Error", &format!("{}: {}", task.title, e));
@@ -286,9 +650,14 @@ impl Orchestrator {
                 
                 Ok(ExecutionResult {
                     task_id: task.id.clone(),
+                    session_id: session.id.clone(),
                     backend: actual_backend,
                     success: false,
                     output: String::new(),
+                    attempts,
+                    timed_out,
+                    branch: branch.clone(),
+                    working_dir: working_dir.clone(),
                     duration_secs,
                     started_at,
                     ended_at,
@@ -298,44 +667,387 @@ impl Orchestrator {
         }
     }
     
-    /// Run Claude Code CLI
-    fn run_claude_code(&self, prompt: &str) -> Result<String, OrchestratorError> {
-        // Claude Code: claude --print -p "prompt"
-        // --print outputs to stdout instead of interactive mode
-        let output = Command::new("claude")
-            .args(["--print", "-p", prompt])
-            .current_dir(&self.config.working_dir)
+    /// Where a task's agent works, and on which branch: its own worktree
+    /// with `worktrees` on (see [`task_worktree`]), else the working directory
+    fn workspace(&self, task_id: &str) -> Result<(PathBuf, Option<String>), OrchestratorError> {
+        if !self.config.worktrees {
+            return Ok((self.config.working_dir.clone(), None));
+        }
+        let (path, branch) = task_worktree(&self.config.working_dir, task_id)?;
+        Ok((path, Some(branch)))
+    }
+    
+    /// The CLI invocation for a backend, run in `dir` with output captured
+    fn command(&self, backend: AgentBackend, cli: Option<&str>, prompt: &str, dir: &Path) -> std::io::Result<Command> {
+        let mut command = match backend {
+            AgentBackend::ClaudeCode => {
+                // Claude Code: claude --print -p "prompt"
+                // --print outputs to stdout instead of interactive mode
+                let mut command = Command::new("claude");
+                command.args(["--print", "-p", prompt]);
+                command
+            }
+            AgentBackend::GeminiPro | AgentBackend::GeminiFlash => {
+                // Gemini CLI: gemini -p "prompt" --yolo -m model
+                // --yolo enables autonomous mode (no confirmation prompts)
+                let model = if backend == AgentBackend::GeminiPro { "gemini-2.5-pro" } else { "gemini-2.5-flash" };
+                let mut command = Command::new("gemini");
+                command.args(["-p", prompt, "--yolo", "-m", model]);
+                command
+            }
+            AgentBackend::Sandboxed => {
+                let inner = self.command(self.config.sandbox.inner(), None, prompt, dir)?;
+                return self.config.sandbox.wrap(&inner, dir, &self.config.working_dir, dirs::home_dir().as_deref());
+            }
+            AgentBackend::Custom => {
+                let cli = cli.filter(|cli| !cli.is_empty()).ok_or_else(|| {
+                    std::io::Error::new(std::io::ErrorKind::NotFound, "the agent has no registered CLI")
+                })?;
+                let mut command = Command::new(cli);
+                command.args(["-p", prompt]);
+                command
+            }
+        };
+        command
+            .current_dir(dir)
+            .stdin(Stdio::null())
             .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()
-            .map_err(|e| OrchestratorError::ExecutionFailed(format!("Failed to run claude: {}", e)))?;
+            .stderr(Stdio::piped());
+        Ok(command)
+    }
+    
+    /// Run a backend's CLI to the end, or until it times out, classifying
+    /// any failure to run it. `on_poll` runs each time the CLI is checked on.
+    fn run_backend(
+        &self,
+        backend: AgentBackend,
+        cli: Option<&str>,
+        prompt: &str,
+        dir: &Path,
+        on_poll: &mut dyn FnMut(),
+    ) -> Result<String, (FailureClass, OrchestratorError)> {
+        let (program, name) = match backend {
+            AgentBackend::ClaudeCode => ("claude", "Claude Code"),
+            AgentBackend::GeminiPro | AgentBackend::GeminiFlash => ("gemini", "Gemini"),
+            AgentBackend::Sandboxed => ("the sandboxed agent", "Sandboxed agent"),
+            AgentBackend::Custom => {
+                let cli = cli.unwrap_or("the agent's CLI");
+                (cli, cli)
+            }
+        };
+        let process = self.command(backend, cli, prompt, dir)
+            .and_then(AgentProcess::spawn)
+            .map_err(|e| (
+                FailureClass::Launch,
+                OrchestratorError::ExecutionFailed(format!("Failed to run {}: {}", program, e)),
+            ))?;
         
-        if output.status.success() {
-            Ok(String::from_utf8_lossy(&output.stdout).to_string())
-        } else {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            Err(OrchestratorError::ExecutionFailed(format!("Claude Code failed: {}", stderr)))
+        match process.finish_polling(self.config.timeout(), on_poll) {
+            Some(Ok(output)) => Ok(output),
+            Some(Err(stderr)) => Err((FailureClass::of_exit(&stderr), OrchestratorError::ExecutionFailed(format!("{} failed: {}", name, stderr)))),
+            None => Err((
+                FailureClass::Timeout,
+                OrchestratorError::ExecutionFailed(format!("{} timed out after {}s", name, self.config.max_task_runtime_secs)),
+            )),
         }
     }
     
-    /// Run Gemini CLI
-    fn run_gemini(&self, prompt: &str, model: &str) -> Result<String, OrchestratorError> {
-        // Gemini CLI: gemini -p "prompt" --yolo -m model
-        // --yolo enables autonomous mode (no confirmation prompts)
-        let output = Command::new("gemini")
-            .args(["-p", prompt, "--yolo", "-m", model])
-            .current_dir(&self.config.working_dir)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
+    /// Work through a formula run until every step is done or one fails
+    /// (see [`crate::workflow`])
+    ///
+    /// Steps run one at a time, in order; parallel steps are only ordered
+    /// loosely, so other agents may take them from the queue meanwhile.
+    pub fn drive_formula(&self, run: &mut FormulaRun, loom: &mut Loom) -> Result<(), LoomError> {
+        loop {
+            let ready: Vec<String> = loom.ready()?.into_iter().map(|t| t.id).collect();
+            let Some(index) = run.steps.iter()
+                .position(|s| s.status == StepStatus::Pending && ready.contains(&s.task_id))
+            else {
+                break;
+            };
+            
+            self.run_step(run, index, loom)?;
+            if run.steps[index].status == StepStatus::Failed {
+                run.finish(RunStatus::Failed);
+                return Ok(());
+            }
+        }
+        
+        if run.completed_steps() < run.steps.len() {
+            run.finish(RunStatus::Stalled);
+            return Ok(());
+        }
+        
+        let evidence = format!("Formula {} completed ({} steps)", run.formula, run.steps.len());
+        loom.claim(&run.root_id, "orchestrator")?;
+        loom.complete_as(&run.root_id, "orchestrator", Some(&evidence))?;
+        run.finish(RunStatus::Completed);
+        
+        if self.config.notifications {
+            let _ = send_notification("Formula Completed", &run.formula);
+        }
+        Ok(())
+    }
+    
+    /// Claim, execute, verify and complete one step of a run
+    fn run_step(&self, run: &mut FormulaRun, index: usize, loom: &mut Loom) -> Result<(), LoomError> {
+        let step = &mut run.steps[index];
+        let task = loom.get(&step.task_id)?
+            .ok_or_else(|| LoomError::Work(crate::WorkError::NotFound(step.task_id.clone())))?;
+        
+        let backend = match loom.agent(&step.agent)? {
+            Some(agent) => self.backend_for(&agent),
+            None => AgentBackend::for_agent(&step.agent).filter(|b| self.available_backends().contains(b)),
+        };
+        let backend = backend.or_else(|| self.available_backends().first().copied());
+        let Some(backend) = backend else {
+            step.status = StepStatus::Failed;
+            step.error = Some(OrchestratorError::NoAgentsAvailable.to_string());
+            return Ok(());
+        };
+        
+        loom.claim(&task.id, &step.agent)?;
+        let result = match self.execute_with(&task, backend, &step.agent, loom) {
+            Ok(result) => result,
+            Err(OrchestratorError::Loom(e)) => return Err(e),
+            Err(e) => {
+                step.status = StepStatus::Failed;
+                step.error = Some(e.to_string());
+                return Ok(());
+            }
+        };
+        step.duration_secs = Some(result.duration_secs);
+        
+        if !result.success {
+            step.status = StepStatus::Failed;
+            step.error = Some(result.error.unwrap_or_else(|| "Agent reported failure".to_string()));
+            return Ok(());
+        }
+        if let Some(verify) = &step.verify {
+            if let Err(e) = self.run_verify(verify, &result.working_dir) {
+                step.status = StepStatus::Failed;
+                step.error = Some(e);
+                return Ok(());
+            }
+        }
+        
+        if step.checkpoint {
+            let summary = format!("{}: step {} ({}) done", run.formula, step.step_id, task.title);
+            step.checkpoint_id = Some(loom.checkpoint(&result.session_id, &summary)?.id);
+        }
+        let evidence = match &step.verify {
+            Some(verify) => format!("{}; `{}` passed", result.evidence(), verify),
+            None => result.evidence(),
+        };
+        // A run that outlived its lease and was taken over doesn't count
+        match loom.complete_as(&task.id, &step.agent, Some(&evidence)) {
+            Ok(_) => step.status = StepStatus::Completed,
+            Err(LoomError::Work(e @ crate::WorkError::LeaseNotHeld { .. })) => {
+                step.status = StepStatus::Failed;
+                step.error = Some(e.to_string());
+            }
+            Err(e) => return Err(e),
+        }
+        Ok(())
+    }
+    
+    /// Run a step's verify command where the step's agent worked
+    fn run_verify(&self, command: &str, dir: &Path) -> Result<(), String> {
+        let output = shell_command(command)
+            .current_dir(dir)
             .output()
-            .map_err(|e| OrchestratorError::ExecutionFailed(format!("Failed to run gemini: {}", e)))?;
+            .map_err(|e| format!("Failed to run `{}`: {}", command, e))?;
         
         if output.status.success() {
-            Ok(String::from_utf8_lossy(&output.stdout).to_string())
+            Ok(())
         } else {
             let stderr = String::from_utf8_lossy(&output.stderr);
-            Err(OrchestratorError::ExecutionFailed(format!("Gemini failed: {}", stderr)))
+            Err(format!("Verification `{}` failed: {}", command, stderr.trim()))
+        }
+    }
+    
+    /// Run a task's ready sub-tasks at once, each on a different agent while
+    /// there are enough to go round, until all complete or one fails (see
+    /// [`crate::workflow`])
+    ///
+    /// Every sub-task starts immediately, whatever `max_concurrent` says;
+    /// fanning out is asking for parallelism. When one fails the others'
+    /// agents are killed, their sessions cancelled, and their tasks released.
+    /// A sub-task whose session, worktree or agent can't be started is
+    /// released too, as is one that runs past `max_task_runtime_secs` (its
+    /// session interrupted). One whose agent fails, or whose completion is
+    /// refused, stays claimed by that agent for someone to look at; one that
+    /// can't be claimed was never held. An error from the store is returned
+    /// only after the running agents are stopped and their tasks released.
+    pub fn fan_out(&self, parent_id: &str, loom: &mut Loom) -> Result<FanOut, LoomError> {
+        // Only route to agents the orchestrator can actually run here
+        let agents: HashMap<String, AgentProfile> = loom.agents()?.into_iter().map(|p| (p.id.clone(), p)).collect();
+        let excluded_agents = agents.values()
+            .filter(|p| self.backend_for(p).is_none())
+            .map(|p| p.id.clone())
+            .collect();
+        let routed = loom.route_children(parent_id, &RoutingConstraints { excluded_agents, ..Default::default() })?;
+        
+        let mut fan_out = FanOut::new(parent_id, routed.iter()
+            .map(|(task, decision)| (task.id.clone(), decision.agent_id.clone())));
+        if routed.is_empty() {
+            fan_out.finish(RunStatus::Stalled);
+            return Ok(fan_out);
+        }
+        let assignments: Vec<String> = fan_out.members.iter()
+            .map(|m| format!("{} → {}", m.task_id, m.agent))
+            .collect();
+        loom.comment(parent_id, "orchestrator", &format!("Fanned out: {}", assignments.join(", ")))?;
+        
+        let mut running = Vec::new();
+        let mut branches = vec![None; routed.len()];
+        // Errors from the store stop the fan-out, but not before whatever it
+        // started is stopped and released
+        let mut run = || -> Result<Option<usize>, LoomError> {
+            for (index, (task, decision)) in routed.iter().enumerate() {
+                let agent = &agents[&decision.agent_id];
+                let backend = self.backend_for(agent).expect("fan-outs only route to agents with a backend");
+                let prompt = self.generate_prompt(task, loom);
+                if let Err(e) = loom.claim(&task.id, &decision.agent_id) {
+                    let member = &mut fan_out.members[index];
+                    member.status = StepStatus::Failed;
+                    member.error = Some(format!("Couldn't claim: {}", e));
+                    return Ok(Some(index));
+                }
+                let session = match loom.start_session(&task.id, backend.as_str()) {
+                    Ok(session) => session,
+                    Err(e) => {
+                        loom.release(&task.id)?;
+                        let member = &mut fan_out.members[index];
+                        member.status = StepStatus::Failed;
+                        member.error = Some(format!("Couldn't start a session: {}; released", e));
+                        return Ok(Some(index));
+                    }
+                };
+                let spawned = self.workspace(&task.id)
+                    .map_err(|e| e.to_string())
+                    .and_then(|(dir, branch)| {
+                        branches[index] = branch;
+                        self.command(backend, Some(&agent.cli_path), &prompt, &dir)
+                            .and_then(AgentProcess::spawn)
+                            .map_err(|e| format!("Failed to run {}: {}", backend.as_str(), e))
+                    });
+                match spawned {
+                    Ok(process) => running.push((index, backend, session.id, process)),
+                    Err(e) => {
+                        let _ = loom.end_session(&session.id, SessionStatus::Failed);
+                        loom.release(&task.id)?;
+                        let member = &mut fan_out.members[index];
+                        member.status = StepStatus::Failed;
+                        member.error = Some(format!("{}; released", e));
+                        return Ok(Some(index));
+                    }
+                }
+            }
+            
+            // Leases are renewed while the agents run, so a long run isn't
+            // taken over
+            let renew_every = lease_renewal_interval(loom);
+            let mut renewed = Instant::now();
+            while !running.is_empty() {
+                std::thread::sleep(AGENT_POLL);
+                if renewed.elapsed() >= renew_every {
+                    for (index, ..) in &running {
+                        let member = &fan_out.members[*index];
+                        // A lost lease shows when the task is completed
+                        let _ = loom.renew(&member.task_id, &member.agent);
+                    }
+                    renewed = Instant::now();
+                }
+                let mut i = 0;
+                while i < running.len() {
+                    if running[i].3.timed_out(self.config.timeout()) {
+                        let (index, _, session_id, process) = running.swap_remove(i);
+                        process.kill();
+                        let _ = loom.end_session(&session_id, SessionStatus::Interrupted);
+                        let member = &mut fan_out.members[index];
+                        loom.release(&member.task_id)?;
+                        let task_type = routed[index].0.labels.first().map(String::as_str);
+                        let duration_secs = self.config.max_task_runtime_secs as f64;
+                        loom.record_execution(&member.agent, &member.task_id, task_type, false, Some(duration_secs))?;
+                        member.duration_secs = Some(duration_secs);
+                        member.status = StepStatus::Failed;
+                        member.error = Some(format!("Timed out after {}s; released", self.config.max_task_runtime_secs));
+                        return Ok(Some(index));
+                    }
+                    let Some(result) = running[i].3.try_finish() else {
+                        i += 1;
+                        continue;
+                    };
+                    let (index, backend, session_id, process) = running.swap_remove(i);
+                    let member = &mut fan_out.members[index];
+                    let duration_secs = process.started.elapsed().as_secs_f64();
+                    member.duration_secs = Some(duration_secs);
+                    let succeeded = result.as_ref().is_ok_and(|output| reports_success(output));
+                    let task_type = routed[index].0.labels.first().map(String::as_str);
+                    loom.record_execution(&member.agent, &member.task_id, task_type, succeeded, Some(duration_secs))?;
+                    
+                    let result = match result {
+                        Ok(_) if succeeded => {
+                            let _ = loom.end_session(&session_id, SessionStatus::Completed);
+                            loom.complete_as(&member.task_id, &member.agent, Some(&completion_evidence(backend, branches[index].as_deref())))
+                                .map_err(|e| format!("Couldn't complete: {}", e))
+                        }
+                        result => {
+                            let _ = loom.end_session(&session_id, SessionStatus::Failed);
+                            Err(result.err().unwrap_or_else(|| "Agent reported failure".to_string()))
+                        }
+                    };
+                    match result {
+                        Ok(_) => member.status = StepStatus::Completed,
+                        Err(e) => {
+                            member.status = StepStatus::Failed;
+                            member.error = Some(e);
+                            return Ok(Some(index));
+                        }
+                    }
+                }
+            }
+            Ok(None)
+        };
+        let failed = run();
+        
+        // Only running members need stopping; the rest never started or
+        // have finished
+        let failed_id = match &failed {
+            Ok(Some(failed)) => fan_out.members[*failed].task_id.clone(),
+            _ => String::new(),
+        };
+        let mut error = None;
+        for (index, _, session_id, process) in running {
+            process.kill();
+            let _ = loom.end_session(&session_id, SessionStatus::Cancelled);
+            let member = &mut fan_out.members[index];
+            if let Err(e) = loom.release(&member.task_id) {
+                error.get_or_insert(e);
+            }
+            member.error = Some(format!("Stopped after {} failed", failed_id));
         }
+        match (failed, error) {
+            (Err(e), _) | (Ok(_), Some(e)) => return Err(e),
+            (Ok(None), None) => {
+                fan_out.finish(RunStatus::Completed);
+                if self.config.notifications {
+                    let _ = send_notification("Fan-out Completed", &format!("{} sub-tasks of {}", fan_out.members.len(), parent_id));
+                }
+                return Ok(fan_out);
+            }
+            (Ok(Some(_)), None) => {}
+        }
+        
+        for member in fan_out.members.iter_mut().filter(|m| m.status == StepStatus::Pending && m.error.is_none()) {
+            member.error = Some(format!("Not started; {} failed", failed_id));
+        }
+        fan_out.finish(RunStatus::Failed);
+        if self.config.notifications {
+            let _ = send_notification("Fan-out Failed", &failed_id);
+        }
+        Ok(fan_out)
     }
     
     /// Run the orchestrator loop (blocking)
@@ -347,7 +1059,7 @@ impl Orchestrator {
         
         loop {
             // Get ready tasks
-            let ready = loom.ready()?;
+            let mut ready = loom.ready()?;
             
             if ready.is_empty() {
                 // No tasks - check summary
@@ -366,27 +1078,41 @@ impl Orchestrator {
                 continue;
             }
             
-            // Pick highest priority task
-            let task = ready.into_iter()
-                .max_by_key(|t| match t.priority {
-                    Priority::Critical => 4,
-                    Priority::High => 3,
-                    Priority::Normal => 2,
-                    Priority::Low => 1,
-                })
-                .unwrap();
+            // Highest priority first; a task that can't be claimed (a spent
+            // budget, or another agent got there first) is skipped, not run
+            ready.sort_by_key(|t| std::cmp::Reverse(match t.priority {
+                Priority::Critical => 4,
+                Priority::High => 3,
+                Priority::Normal => 2,
+                Priority::Low => 1,
+            }));
+            let mut claimed = None;
+            for task in ready {
+                match loom.claim(&task.id, "orchestrator") {
+                    Ok(_) => {
+                        claimed = Some(task);
+                        break;
+                    }
+                    Err(e) => eprintln!("Skipping {}: {}", task.id, e),
+                }
+            }
+            let Some(task) = claimed else {
+                std::thread::sleep(Duration::from_secs(self.config.poll_interval_secs));
+                continue;
+            };
             
             eprintln!("Processing: {} ({})", task.title, task.id);
             
-            // Claim the task
-            let _ = loom.claim(&task.id, "orchestrator");
-            
             // Execute
             let result = self.execute_task(&task, loom)?;
             
             if result.success {
-                eprintln!("  ✓ Completed in {:.1}s", result.duration_secs);
-                let _ = loom.complete(&task.id, Some(&format!("Completed by {}", result.backend.as_str())));
+                match loom.complete_as(&task.id, "orchestrator", Some(&result.evidence())) {
+                    Ok(_) => eprintln!("  ✓ Completed in {:.1}s", result.duration_secs),
+                    Err(e) => eprintln!("  ✗ Finished in {:.1}s but couldn't complete: {}", result.duration_secs, e),
+                }
+            } else if result.timed_out {
+                eprintln!("  ✗ Timed out after {:.0}s; released", result.duration_secs);
             } else {
                 eprintln!("  ✗ Failed: {:?}", result.error);
                 // Leave task claimed but not complete - human intervention needed
@@ -406,6 +1132,168 @@ impl Default for Orchestrator {
     }
 }
 
+/// Evidence for a task an agent completed, naming the branch it worked on
+fn completion_evidence(backend: AgentBackend, branch: Option<&str>) -> String {
+    match branch {
+        Some(branch) => format!("Completed by {} on branch {}", backend.as_str(), branch),
+        None => format!("Completed by {}", backend.as_str()),
+    }
+}
+
+/// The worktree for a task under `<repo>/.loom/worktrees`, on branch
+/// `loom/<task-id>`: made from HEAD the first time, reused after that
+fn task_worktree(repo: &Path, task_id: &str) -> Result<(PathBuf, String), OrchestratorError> {
+    let dir = repo.join(".loom").join("worktrees");
+    let path = dir.join(task_id);
+    let branch = format!("loom/{}", task_id);
+    if path.exists() {
+        return Ok((path, branch));
+    }
+    
+    std::fs::create_dir_all(&dir)?;
+    // Keeps the checkouts out of the main worktree's status
+    let ignore = dir.join(".gitignore");
+    if !ignore.exists() {
+        std::fs::write(&ignore, "*\n")?;
+    }
+    
+    let git = |args: &[&std::ffi::OsStr]| -> Result<(), OrchestratorError> {
+        let output = Command::new("git").args(args).current_dir(repo).output()?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(OrchestratorError::Worktree(String::from_utf8_lossy(&output.stderr).trim().to_string()))
+        }
+    };
+    let branch_ref = format!("refs/heads/{}", branch);
+    let exists = git(&["rev-parse".as_ref(), "--verify".as_ref(), "--quiet".as_ref(), branch_ref.as_ref()]).is_ok();
+    if exists {
+        git(&["worktree".as_ref(), "add".as_ref(), path.as_os_str(), branch.as_ref()])?;
+    } else {
+        git(&["worktree".as_ref(), "add".as_ref(), "-b".as_ref(), branch.as_ref(), path.as_os_str()])?;
+    }
+    Ok((path, branch))
+}
+
+/// How often a running agent's lease is renewed: well inside the lease, so
+/// a slow poll doesn't let it lapse
+fn lease_renewal_interval(loom: &Loom) -> Duration {
+    (loom.config.claims.lease_duration() / 3).to_std().unwrap_or(AGENT_POLL).max(AGENT_POLL)
+}
+
+/// Renews the lease on a task while its agent runs, so a run longer than
+/// the lease isn't taken over
+struct LeaseKeeper {
+    task_id: String,
+    /// Agent holding the claim, if the task is claimed
+    holder: Option<String>,
+    every: Duration,
+    renewed: Instant,
+}
+
+impl LeaseKeeper {
+    fn new(loom: &Loom, task_id: &str) -> Result<Self, LoomError> {
+        let holder = loom.get(task_id)?
+            .filter(|task| task.status == crate::Status::Claimed)
+            .and_then(|task| task.agent);
+        Ok(Self { task_id: task_id.to_string(), holder, every: lease_renewal_interval(loom), renewed: Instant::now() })
+    }
+    
+    /// Renew the lease if it's due
+    fn tick(&mut self, loom: &mut Loom) {
+        let Some(holder) = &self.holder else {
+            return;
+        };
+        if self.renewed.elapsed() >= self.every {
+            // A lost lease shows when the task is completed
+            let _ = loom.renew(&self.task_id, holder);
+            self.renewed = Instant::now();
+        }
+    }
+}
+
+/// Whether an agent's final output reports success
+fn reports_success(output: &str) -> bool {
+    let output = output.to_lowercase();
+    output.contains("success") || !output.contains("fail")
+}
+
+/// An agent CLI running in the background, in a process group of its own.
+/// Output is drained on threads so a chatty agent can't fill its pipe and
+/// stall.
+struct AgentProcess {
+    child: Child,
+    /// Readers for stdout and stderr, joined once the agent exits
+    output: Option<(JoinHandle<String>, JoinHandle<String>)>,
+    started: Instant,
+}
+
+impl AgentProcess {
+    fn spawn(mut command: Command) -> std::io::Result<Self> {
+        own_process_group(&mut command);
+        let mut child = command.spawn()?;
+        let drain = |pipe: Option<Box<dyn Read + Send>>| std::thread::spawn(move || {
+            let mut text = String::new();
+            if let Some(mut pipe) = pipe {
+                let _ = pipe.read_to_string(&mut text);
+            }
+            text
+        });
+        let stdout = drain(child.stdout.take().map(|p| Box::new(p) as Box<dyn Read + Send>));
+        let stderr = drain(child.stderr.take().map(|p| Box::new(p) as Box<dyn Read + Send>));
+        Ok(Self { child, output: Some((stdout, stderr)), started: Instant::now() })
+    }
+    
+    /// The agent's output once it has exited: stdout on success, stderr on
+    /// a failing exit status
+    fn try_finish(&mut self) -> Option<Result<String, String>> {
+        let status = match self.child.try_wait() {
+            Ok(Some(status)) => status,
+            Ok(None) => return None,
+            Err(e) => return Some(Err(e.to_string())),
+        };
+        let (stdout, stderr) = self.output.take()?;
+        let stdout = stdout.join().unwrap_or_default();
+        let stderr = stderr.join().unwrap_or_default();
+        Some(if status.success() {
+            Ok(stdout)
+        } else {
+            Err(format!("Agent exited with {}: {}", status, stderr.trim()))
+        })
+    }
+    
+    /// Wait for the agent's output, killing it once it has run for
+    /// `timeout` (None when it was killed)
+    #[cfg_attr(not(test), allow(dead_code))]
+    fn finish_within(self, timeout: Option<Duration>) -> Option<Result<String, String>> {
+        self.finish_polling(timeout, || {})
+    }
+    
+    /// `finish_within`, calling `on_poll` each time the agent is checked on
+    fn finish_polling(mut self, timeout: Option<Duration>, mut on_poll: impl FnMut()) -> Option<Result<String, String>> {
+        loop {
+            if let Some(result) = self.try_finish() {
+                return Some(result);
+            }
+            if self.timed_out(timeout) {
+                self.kill();
+                return None;
+            }
+            std::thread::sleep(AGENT_POLL);
+            on_poll();
+        }
+    }
+    
+    fn timed_out(&self, timeout: Option<Duration>) -> bool {
+        timeout.is_some_and(|limit| self.started.elapsed() >= limit)
+    }
+    
+    /// Kill the agent and anything it started
+    fn kill(mut self) {
+        kill_tree(&mut self.child);
+    }
+}
+
 /// Send a system notification
 pub fn send_notification(title: &str, message: &str) -> Result<(), std::io::Error> {
     #[cfg(target_os = "macos")]
@@ -436,7 +1324,7 @@ pub fn send_notification(title: &str, message: &str) -> Result<(), std::io::Erro
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::Status;
+    use crate::{Status, TaskKind};
     
     #[test]
     fn test_backend_selection() {
@@ -453,6 +1341,8 @@ mod tests {
             evidence: None,
             actual_cost_usd: None,
             repo: None,
+            kind: TaskKind::Task,
+            rollup: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         };
@@ -471,16 +1361,352 @@ mod tests {
             evidence: None,
             actual_cost_usd: None,
             repo: None,
+            kind: TaskKind::Task,
+            rollup: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         };
         assert_eq!(AgentBackend::for_task(&simple_task), AgentBackend::GeminiFlash);
+        
+        // Routed model profiles
+        assert_eq!(AgentBackend::for_agent("claude-sonnet"), Some(AgentBackend::ClaudeCode));
+        assert_eq!(AgentBackend::for_agent("gemini-2-flash"), Some(AgentBackend::GeminiFlash));
+        assert_eq!(AgentBackend::for_agent("gemini-pro"), Some(AgentBackend::GeminiPro));
+        assert_eq!(AgentBackend::for_agent("cursor"), None);
+    }
+    
+    #[test]
+    fn test_retry_policy() {
+        let config: OrchestratorConfig = serde_json::from_value(serde_json::json!({
+            "poll_interval_secs": 5,
+            "max_concurrent": 1,
+            "backends": ["claude_code"],
+            "working_dir": ".",
+            "notifications": false,
+            "max_task_runtime_secs": 0,
+            "retries": {
+                "claude_code": { "max_attempts": 4, "initial_backoff_secs": 2.0, "max_backoff_secs": 5.0, "retry_on": ["crash", "rate_limit"] }
+            }
+        })).unwrap();
+        
+        let claude = config.retry_policy(AgentBackend::ClaudeCode);
+        assert!(claude.retries(FailureClass::Crash, 3));
+        assert!(!claude.retries(FailureClass::Crash, 4));
+        assert!(!claude.retries(FailureClass::Reported, 1));
+        let waits: Vec<_> = (1..=3).map(|attempt| claude.backoff(attempt).as_secs_f64()).collect();
+        assert_eq!(waits, vec![2.0, 4.0, 5.0]);
+        
+        // Out-of-range settings are clamped rather than panicking
+        let wild = |initial: f64, max: f64| RetryPolicy { initial_backoff_secs: initial, max_backoff_secs: max, ..Default::default() };
+        assert_eq!(wild(1e300, f64::INFINITY).backoff(30), MAX_BACKOFF);
+        assert_eq!(wild(f64::INFINITY, f64::INFINITY).backoff(1), MAX_BACKOFF);
+        assert_eq!(wild(-5.0, 10.0).backoff(2), Duration::ZERO);
+        assert_eq!(wild(f64::NAN, f64::NAN).backoff(1), Duration::ZERO);
+        
+        // Unlisted backends only retry rate limits
+        let gemini = config.retry_policy(AgentBackend::GeminiFlash);
+        assert!(gemini.retries(FailureClass::RateLimit, 1));
+        assert!(!gemini.retries(FailureClass::Crash, 1));
+        
+        assert_eq!(FailureClass::of_exit("Error: 429 Too Many Requests"), FailureClass::RateLimit);
+        assert_eq!(FailureClass::of_exit("panic: index out of range"), FailureClass::Crash);
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn test_sandbox_confines_the_agent() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path().canonicalize().unwrap();
+        let user_home = tempfile::tempdir().unwrap();
+        std::fs::create_dir(user_home.path().join(".claude")).unwrap();
+        std::fs::write(user_home.path().join(".claude/credentials.json"), "{}").unwrap();
+        let mut inner = Command::new("sh");
+        inner.args(["-c", "echo \"$PWD|$HOME|$LOOM_SANDBOX_TEST_SECRET|${PATH:+path}\"; ls \"$HOME/.claude\"; env"]);
+        let run = |sandbox: &SandboxConfig| {
+            let command = sandbox.wrap(&inner, &root, &root, Some(user_home.path())).unwrap();
+            AgentProcess::spawn(command).unwrap().finish_within(None).unwrap().unwrap()
+        };
+        
+        // Without a container command nothing runs
+        let err = SandboxConfig::default().wrap(&inner, &root, &root, None).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::Unsupported);
+        assert!(!SandboxConfig::default().is_available());
+        assert!(!AgentBackend::Sandboxed.is_available());
+        
+        // `env` stands in for a container that adds nothing
+        let sandbox = SandboxConfig {
+            container: vec!["env".into()],
+            env: vec!["PATH".into(), "LOOM_SANDBOX_TEST_SECRET=hunter2".into()],
+            ..Default::default()
+        };
+        assert!(sandbox.is_available());
+        let output = run(&sandbox);
+        let mut lines = output.lines();
+        let home = root.join(".loom-sandbox");
+        assert_eq!(lines.next().unwrap(), format!("{}|{}|hunter2|path", root.display(), home.display()));
+        assert_eq!(lines.next(), Some("credentials.json"));
+        assert!(home.join(".gitignore").exists());
+        
+        // Only the configured and sandbox variables reach the agent
+        let allowed = ["PATH", "LOOM_SANDBOX_TEST_SECRET", "HOME", "USERPROFILE", "TMPDIR", "TEMP", "TMP", "PWD", "OLDPWD", "SHLVL", "_"];
+        for line in lines {
+            let name = line.split('=').next().unwrap();
+            assert!(allowed.contains(&name), "{} leaked into the sandbox", name);
+        }
+        
+        // The CLI goes inside the container command
+        let sandbox = SandboxConfig { container: vec!["echo".into(), "--workdir={dir}".into(), "{home}".into()], ..Default::default() };
+        let output = run(&sandbox);
+        assert!(output.starts_with(&format!("--workdir={} {} sh -c", root.display(), user_home.path().display())));
+        
+        // Nothing runs outside the working directory
+        let outside = tempfile::tempdir().unwrap();
+        let err = sandbox.wrap(&inner, outside.path(), &root, None).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::PermissionDenied);
+        assert_eq!(AgentBackend::for_agent("sandboxed-claude"), Some(AgentBackend::Sandboxed));
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn test_registered_agent_runs_its_own_cli() {
+        use std::os::unix::fs::PermissionsExt;
+        
+        let dir = tempfile::tempdir().unwrap();
+        let cli = dir.path().join("local-llm");
+        std::fs::write(&cli, "#!/bin/sh\n[ \"$1\" = -p ] && echo \"$2\" > prompt.md && echo SUCCESS\n").unwrap();
+        std::fs::set_permissions(&cli, std::fs::Permissions::from_mode(0o755)).unwrap();
+        
+        let mut loom = Loom::in_memory().unwrap();
+        for (id, cli) in [("local-llm", cli.to_str().unwrap()), ("ghost", "/nonexistent/agent")] {
+            let config: crate::ModelConfig = serde_json::from_value(serde_json::json!({
+                "family": "other", "cli": cli, "input_per_1k": 0.0, "output_per_1k": 0.0
+            })).unwrap();
+            loom.register_agent(id, &config).unwrap();
+        }
+        let orchestrator = Orchestrator::with_config(OrchestratorConfig {
+            working_dir: dir.path().to_path_buf(),
+            notifications: false,
+            ..Default::default()
+        });
+        assert_eq!(orchestrator.backend_for(&loom.agent("local-llm").unwrap().unwrap()), Some(AgentBackend::Custom));
+        assert_eq!(orchestrator.backend_for(&loom.agent("ghost").unwrap().unwrap()), None);
+        
+        let task = loom.create("Summarise the changelog").unwrap();
+        loom.claim(&task.id, "local-llm").unwrap();
+        let result = orchestrator.execute_with(&task, AgentBackend::Custom, "local-llm", &mut loom).unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(std::fs::read_to_string(dir.path().join("prompt.md")).unwrap().contains("# Task: Summarise the changelog"));
+        
+        // The run counts toward the agent that was routed, not the backend
+        assert_eq!(loom.agent("local-llm").unwrap().unwrap().quality.successes, 1);
+        assert!(loom.agent("custom").unwrap().is_none());
+    }
+    
+    #[test]
+    fn test_lease_keeper_renews_the_holders_lease() {
+        let mut loom = Loom::in_memory().unwrap();
+        let task = loom.create("Rebuild the search index").unwrap();
+        assert!(LeaseKeeper::new(&loom, &task.id).unwrap().holder.is_none());
+        
+        loom.claim(&task.id, "claude-sonnet").unwrap();
+        let mut lease = LeaseKeeper::new(&loom, &task.id).unwrap();
+        assert_eq!(lease.holder.as_deref(), Some("claude-sonnet"));
+        // Renewals are only due every so often
+        lease.tick(&mut loom);
+        assert_eq!(loom.store.get_lease(&task.id).unwrap().unwrap().renewals, 0);
+        
+        lease.every = Duration::ZERO;
+        lease.tick(&mut loom);
+        assert_eq!(loom.store.get_lease(&task.id).unwrap().unwrap().renewals, 1);
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn test_fan_out_runs_at_once_and_cleans_up() {
+        use std::os::unix::fs::PermissionsExt;
+        
+        // Plain sub-tasks wait for each other to start, so they only succeed
+        // side by side
+        let dir = tempfile::tempdir().unwrap();
+        let cli = dir.path().join("stub-agent");
+        std::fs::write(&cli, format!(r#"#!/bin/sh
+case "$2" in
+  *Hang*) echo $$ > '{dir}/hang.pid'; exec sleep 30 ;;
+  *Break*) echo 'out of tokens' >&2; exit 1 ;;
+esac
+touch "started.$$"
+for i in $(seq 50); do
+  [ "$(ls | grep -c '^started')" -ge 2 ] && echo SUCCESS && exit 0
+  sleep 0.1
+done
+echo FAILURE
+"#, dir = dir.path().display())).unwrap();
+        std::fs::set_permissions(&cli, std::fs::Permissions::from_mode(0o755)).unwrap();
+        
+        let mut loom = Loom::in_memory().unwrap();
+        for id in ["stub-a", "stub-b"] {
+            let config: crate::ModelConfig = serde_json::from_value(serde_json::json!({
+                "family": "other", "cli": cli.to_str().unwrap(), "input_per_1k": 0.0, "output_per_1k": 0.0
+            })).unwrap();
+            loom.register_agent(id, &config).unwrap();
+        }
+        loom.config.budgets.labels.insert("frozen".to_string(), 0.0);
+        // No built-in backends, so only the stubs are routed to
+        let orchestrator = Orchestrator::with_config(OrchestratorConfig {
+            backends: Vec::new(),
+            working_dir: dir.path().to_path_buf(),
+            notifications: false,
+            ..Default::default()
+        });
+        let spawn = |loom: &mut Loom, parent: &str, title: &str, labels: &[&str]| {
+            // Newer sub-tasks are routed, and started, first
+            std::thread::sleep(Duration::from_millis(10));
+            loom.create_task(crate::CreateTask {
+                title: title.to_string(),
+                labels: labels.iter().map(|l| l.to_string()).collect(),
+                parent: Some(parent.to_string()),
+                ..Default::default()
+            }).unwrap().id
+        };
+        
+        let parent = loom.create("Document the release").unwrap();
+        let docs = spawn(&mut loom, &parent.id, "Write the docs", &[]);
+        let tests = spawn(&mut loom, &parent.id, "Write the tests", &[]);
+        let fan_out = orchestrator.fan_out(&parent.id, &mut loom).unwrap();
+        assert_eq!(fan_out.status, RunStatus::Completed, "{:?}", fan_out.members);
+        for id in [&docs, &tests] {
+            assert_eq!(loom.get(id).unwrap().unwrap().status, Status::Done);
+        }
+        
+        // A failure stops the agent still running and releases its task
+        let parent = loom.create("Migrate the ledger").unwrap();
+        let broken = spawn(&mut loom, &parent.id, "Break the build", &[]);
+        let hung = spawn(&mut loom, &parent.id, "Hang on the migration", &[]);
+        let fan_out = orchestrator.fan_out(&parent.id, &mut loom).unwrap();
+        assert_eq!(fan_out.status, RunStatus::Failed);
+        assert_eq!(loom.get(&broken).unwrap().unwrap().status, Status::Claimed);
+        let hung_task = loom.get(&hung).unwrap().unwrap();
+        assert_eq!((hung_task.status, hung_task.agent), (Status::Ready, None));
+        // A hung agent killed as soon as it started may not have written its pid
+        let assert_killed = || {
+            let pid_file = dir.path().join("hang.pid");
+            std::thread::sleep(Duration::from_millis(200));
+            let Ok(pid) = std::fs::read_to_string(&pid_file) else {
+                return;
+            };
+            let pid: u32 = pid.trim().parse().unwrap();
+            let deadline = Instant::now() + Duration::from_secs(5);
+            while crate::platform::process_alive(pid) && Instant::now() < deadline {
+                std::thread::sleep(Duration::from_millis(50));
+            }
+            assert!(!crate::platform::process_alive(pid));
+            std::fs::remove_file(pid_file).unwrap();
+        };
+        assert!(dir.path().join("hang.pid").exists());
+        assert_killed();
+        
+        // So does a sub-task that can't be claimed, rather than leaking the
+        // agents started before it
+        let parent = loom.create("Change the frozen API").unwrap();
+        let frozen = spawn(&mut loom, &parent.id, "Rename the frozen endpoint", &["frozen"]);
+        let hung = spawn(&mut loom, &parent.id, "Hang on the client", &[]);
+        let fan_out = orchestrator.fan_out(&parent.id, &mut loom).unwrap();
+        assert_eq!(fan_out.status, RunStatus::Failed);
+        let member = fan_out.members.iter().find(|m| m.task_id == frozen).unwrap();
+        assert!(member.error.as_deref().unwrap().starts_with("Couldn't claim"), "{:?}", member.error);
+        assert_eq!(loom.get(&frozen).unwrap().unwrap().status, Status::Ready);
+        let hung_task = loom.get(&hung).unwrap().unwrap();
+        assert_eq!((hung_task.status, hung_task.agent), (Status::Ready, None));
+        assert_killed();
+        
+        // As does one whose worktree can't be made (not a git repo here)
+        let in_worktrees = Orchestrator::with_config(OrchestratorConfig { worktrees: true, ..orchestrator.config.clone() });
+        let parent = loom.create("Tag the release").unwrap();
+        let tag = spawn(&mut loom, &parent.id, "Tag it", &[]);
+        let fan_out = in_worktrees.fan_out(&parent.id, &mut loom).unwrap();
+        assert_eq!(fan_out.status, RunStatus::Failed);
+        assert!(fan_out.members[0].error.as_deref().unwrap().ends_with("; released"), "{:?}", fan_out.members[0].error);
+        let tag_task = loom.get(&tag).unwrap().unwrap();
+        assert_eq!((tag_task.status, tag_task.agent), (Status::Ready, None));
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn test_agent_process_times_out() {
+        // Whatever the agent started goes down with it
+        let dir = tempfile::tempdir().unwrap();
+        let pid_file = dir.path().join("pid");
+        let mut command = shell_command(&format!("sleep 30 & echo $! > '{}'; wait", pid_file.display()));
+        command.stdout(Stdio::piped()).stderr(Stdio::piped());
+        let started = Instant::now();
+        let process = AgentProcess::spawn(command).unwrap();
+        assert!(process.finish_within(Some(Duration::from_millis(500))).is_none());
+        assert!(started.elapsed() < Duration::from_secs(10));
+        
+        let grandchild: u32 = std::fs::read_to_string(&pid_file).unwrap().trim().parse().unwrap();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while crate::platform::process_alive(grandchild) && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        assert!(!crate::platform::process_alive(grandchild));
+        
+        let mut command = shell_command("echo SUCCESS");
+        command.stdout(Stdio::piped()).stderr(Stdio::piped());
+        let process = AgentProcess::spawn(command).unwrap();
+        assert_eq!(process.finish_within(Some(Duration::from_secs(10))).unwrap().unwrap().trim(), "SUCCESS");
+    }
+    
+    #[test]
+    fn test_task_worktree() {
+        let dir = tempfile::tempdir().unwrap();
+        let git = |args: &[&str], cwd: &Path| {
+            let output = Command::new("git")
+                .args(["-c", "user.name=Loom", "-c", "user.email=loom@example.com"])
+                .args(args)
+                .current_dir(cwd)
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "git {:?}: {}", args, String::from_utf8_lossy(&output.stderr));
+            String::from_utf8_lossy(&output.stdout).trim().to_string()
+        };
+        git(&["init", "--quiet"], dir.path());
+        git(&["commit", "--quiet", "--allow-empty", "-m", "Initial"], dir.path());
+        
+        let (path, branch) = task_worktree(dir.path(), "lm-a1b2").unwrap();
+        assert_eq!(branch, "loom/lm-a1b2");
+        assert_eq!(path, dir.path().join(".loom/worktrees/lm-a1b2"));
+        assert_eq!(git(&["rev-parse", "--abbrev-ref", "HEAD"], &path), "loom/lm-a1b2");
+        // The checkout doesn't show up as untracked in the main worktree
+        assert_eq!(git(&["status", "--porcelain"], dir.path()), "");
+        
+        // A second run reuses the worktree, and a removed one is recreated
+        // on the existing branch
+        assert_eq!(task_worktree(dir.path(), "lm-a1b2").unwrap().0, path);
+        git(&["worktree", "remove", path.to_str().unwrap()], dir.path());
+        assert_eq!(task_worktree(dir.path(), "lm-a1b2").unwrap().0, path);
+        
+        let result = ExecutionResult {
+            task_id: "lm-a1b2".into(),
+            session_id: "ses-1".into(),
+            backend: AgentBackend::ClaudeCode,
+            success: true,
+            output: String::new(),
+            attempts: 1,
+            timed_out: false,
+            branch: Some(branch),
+            working_dir: path,
+            duration_secs: 1.0,
+            started_at: Utc::now(),
+            ended_at: Utc::now(),
+            error: None,
+        };
+        assert_eq!(result.evidence(), "Completed by claude-code on branch loom/lm-a1b2");
     }
     
     #[test]
     fn test_which_exists() {
-        // "which" should exist on all Unix systems
-        assert!(which_exists("which"));
+        // Whatever is running the tests is on PATH
+        assert!(which_exists("cargo"));
         // Random garbage shouldn't exist
         assert!(!which_exists("nonexistent_binary_12345"));
     }
@@ -0,0 +1,216 @@
+//! Task Outcomes
+//!
+//! Evidence says a task is done in free text; an outcome records what the
+//! work produced in a form analytics can count: artifacts, PR URLs, tests
+//! added, files touched and follow-up tasks. Outcomes are stored in work.db
+//! next to the task. On completion Loom assembles one from the task's
+//! sessions (files modified, sub-tasks spawned, PR links in notes and
+//! evidence) and merges in whatever the agent reported explicitly.
+
+use std::collections::BTreeSet;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::memory::{ChangeType, Session};
+
+/// What completing a task produced
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Outcome {
+    /// Build outputs, documents, releases and the like
+    #[serde(default)]
+    pub artifacts: Vec<String>,
+    #[serde(default)]
+    pub pr_urls: Vec<String>,
+    #[serde(default)]
+    pub tests_added: Option<u32>,
+    #[serde(default)]
+    pub files_touched: Vec<String>,
+    /// IDs of tasks created to carry on the work
+    #[serde(default)]
+    pub follow_ups: Vec<String>,
+    #[serde(default)]
+    pub recorded_at: Option<DateTime<Utc>>,
+}
+
+impl Outcome {
+    pub fn is_empty(&self) -> bool {
+        self.artifacts.is_empty()
+            && self.pr_urls.is_empty()
+            && self.tests_added.is_none()
+            && self.files_touched.is_empty()
+            && self.follow_ups.is_empty()
+    }
+    
+    /// Combine with `other`: lists are unioned, and `other`'s test count
+    /// wins when it has one
+    pub fn merge(mut self, other: Outcome) -> Outcome {
+        union(&mut self.artifacts, other.artifacts);
+        union(&mut self.pr_urls, other.pr_urls);
+        union(&mut self.files_touched, other.files_touched);
+        union(&mut self.follow_ups, other.follow_ups);
+        self.tests_added = other.tests_added.or(self.tests_added);
+        self.recorded_at = other.recorded_at.or(self.recorded_at);
+        self
+    }
+    
+    /// Assemble an outcome from the sessions worked on a task
+    ///
+    /// Files come from each session's modifications, follow-ups from the
+    /// sub-tasks it spawned, and artifacts from `artifact*` entries in its
+    /// custom context (comma-separated). PR URLs are picked out of the
+    /// evidence, notes and custom values. New files that look like tests
+    /// are counted as tests added.
+    pub fn from_sessions(sessions: &[Session], evidence: Option<&str>) -> Outcome {
+        let mut outcome = Outcome::default();
+        let mut tests_created = BTreeSet::new();
+        let mut text: Vec<&str> = evidence.into_iter().collect();
+        
+        for session in sessions {
+            let context = &session.context;
+            for file in &context.files_modified {
+                outcome.files_touched.push(file.path.clone());
+                if file.change_type == ChangeType::Created && is_test_file(&file.path) {
+                    tests_created.insert(file.path.as_str());
+                }
+            }
+            outcome.follow_ups.extend(context.sub_tasks.iter().cloned());
+            
+            let mut custom: Vec<_> = context.custom.iter().collect();
+            custom.sort();
+            for (key, value) in custom {
+                if key.starts_with("artifact") {
+                    outcome.artifacts.extend(
+                        value.split(',').map(str::trim).filter(|a| !a.is_empty()).map(String::from),
+                    );
+                }
+                text.push(value);
+            }
+            text.push(&context.agent_notes);
+        }
+        
+        outcome.pr_urls = text.iter().flat_map(|t| pr_urls(t)).collect();
+        if !tests_created.is_empty() {
+            outcome.tests_added = Some(tests_created.len() as u32);
+        }
+        
+        for list in [&mut outcome.artifacts, &mut outcome.pr_urls, &mut outcome.files_touched, &mut outcome.follow_ups] {
+            let items = std::mem::take(list);
+            union(list, items);
+        }
+        outcome
+    }
+}
+
+/// Append items not already in `list`, keeping order
+fn union(list: &mut Vec<String>, items: Vec<String>) {
+    for item in items {
+        if !list.contains(&item) {
+            list.push(item);
+        }
+    }
+}
+
+/// Pull request / merge request links in free text
+fn pr_urls(text: &str) -> Vec<String> {
+    text.split_whitespace()
+        .map(|word| word.trim_matches(|c: char| matches!(c, '(' | ')' | '<' | '>' | '"' | '\'' | ',' | '.' | ';')))
+        .filter(|word| word.starts_with("https://") || word.starts_with("http://"))
+        .filter(|word| word.contains("/pull/") || word.contains("/merge_requests/"))
+        .map(String::from)
+        .collect()
+}
+
+fn is_test_file(path: &str) -> bool {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    path.contains("/tests/")
+        || path.starts_with("tests/")
+        || name.starts_with("test_")
+        || name.contains("_test.")
+        || name.contains(".test.")
+        || name.contains(".spec.")
+}
+
+/// Outcomes across completed tasks
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OutcomeSummary {
+    pub tasks: usize,
+    pub artifacts: usize,
+    pub pull_requests: usize,
+    pub tests_added: u32,
+    pub files_touched: usize,
+    pub follow_ups: usize,
+}
+
+pub fn summarize<'a>(outcomes: impl IntoIterator<Item = &'a Outcome>) -> OutcomeSummary {
+    let mut summary = OutcomeSummary::default();
+    for outcome in outcomes {
+        summary.tasks += 1;
+        summary.artifacts += outcome.artifacts.len();
+        summary.pull_requests += outcome.pr_urls.len();
+        summary.tests_added += outcome.tests_added.unwrap_or(0);
+        summary.files_touched += outcome.files_touched.len();
+        summary.follow_ups += outcome.follow_ups.len();
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::{FileModification, SessionContext, SessionStatus};
+    
+    fn session(context: SessionContext) -> Session {
+        Session {
+            id: "s-1".to_string(),
+            agent_id: "claude-code".to_string(),
+            task_id: "lm-1".to_string(),
+            status: SessionStatus::Completed,
+            started_at: Utc::now(),
+            ended_at: None,
+            working_dir: None,
+            git_branch: None,
+            last_checkpoint: None,
+            context,
+        }
+    }
+    
+    #[test]
+    fn test_outcome_from_sessions() {
+        let file = |path: &str, change_type| FileModification {
+            path: path.to_string(),
+            summary: String::new(),
+            change_type,
+            lines_added: None,
+            lines_removed: None,
+        };
+        let mut context = SessionContext {
+            files_modified: vec![
+                file("src/auth.rs", ChangeType::Modified),
+                file("tests/auth_test.rs", ChangeType::Created),
+                file("src/auth.rs", ChangeType::Modified),
+            ],
+            sub_tasks: vec!["lm-7".to_string()],
+            agent_notes: "Opened https://github.com/acme/app/pull/42.".to_string(),
+            ..Default::default()
+        };
+        context.custom.insert("artifacts".to_string(), "dist/app.js, docs/auth.md".to_string());
+        
+        let outcome = Outcome::from_sessions(&[session(context)], Some("see https://github.com/acme/app/pull/42"));
+        assert_eq!(outcome.files_touched, vec!["src/auth.rs", "tests/auth_test.rs"]);
+        assert_eq!(outcome.pr_urls, vec!["https://github.com/acme/app/pull/42"]);
+        assert_eq!(outcome.artifacts, vec!["dist/app.js", "docs/auth.md"]);
+        assert_eq!(outcome.follow_ups, vec!["lm-7"]);
+        assert_eq!(outcome.tests_added, Some(1));
+        
+        // What the agent reports takes precedence over what was inferred
+        let reported = Outcome { tests_added: Some(4), follow_ups: vec!["lm-8".to_string()], ..Default::default() };
+        let merged = outcome.merge(reported);
+        assert_eq!(merged.tests_added, Some(4));
+        assert_eq!(merged.follow_ups, vec!["lm-7", "lm-8"]);
+        
+        let summary = summarize([&merged, &Outcome::default()]);
+        assert_eq!((summary.tasks, summary.tests_added, summary.pull_requests), (2, 4, 1));
+        assert!(Outcome::default().is_empty());
+    }
+}
@@ -0,0 +1,341 @@
+//! Plan Import
+//!
+//! Agents write plans as markdown: headings for phases, numbered steps and
+//! checklists under them. Importing a plan turns it into sub-tasks of an
+//! existing task, nested the way the document is, with each step blocked by
+//! the step before it. Every imported task remembers the section it came
+//! from, so re-importing an edited plan updates the same tasks instead of
+//! creating new ones.
+//!
+//! What counts as a step:
+//! - numbered list items (`1.` or `1)`) and checklist items (`- [ ]`, `- [x]`)
+//! - headings that have steps under them, or every heading when the plan
+//!   has no list steps at all
+//!
+//! Everything else under a step (paragraphs, plain bullets, code blocks)
+//! becomes its description. A single top-level `#` heading is the plan's
+//! title, not a step.
+
+use serde::{Deserialize, Serialize};
+
+/// A step parsed from a plan
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlanStep {
+    pub title: String,
+    pub description: Option<String>,
+    /// Heading path to the step, e.g. "Rollout > Staging"
+    pub section: String,
+    /// GitHub-style anchor of the nearest heading
+    pub anchor: String,
+    /// 1-based line the step starts on
+    pub line: usize,
+    /// Checked off (`- [x]`) in the plan
+    pub checked: bool,
+    /// A heading (a phase of the plan) rather than a list item
+    pub heading: bool,
+    pub children: Vec<PlanStep>,
+}
+
+impl PlanStep {
+    /// Key used to match the step against an earlier import: the title,
+    /// lowercased, without markdown emphasis or trailing punctuation
+    pub fn key(&self) -> String {
+        normalize(&self.title)
+    }
+    
+    /// Number of steps including this one
+    pub fn count(&self) -> usize {
+        1 + self.children.iter().map(PlanStep::count).sum::<usize>()
+    }
+}
+
+/// Where an imported task came from in its plan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanLink {
+    pub task_id: String,
+    /// Task the plan was imported into
+    pub root_id: String,
+    pub section: String,
+    pub anchor: String,
+    pub line: usize,
+}
+
+/// What an import changed
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PlanImport {
+    /// Task the plan was imported into
+    pub root_id: String,
+    pub created: Vec<String>,
+    /// Steps whose title, description or position changed
+    pub updated: Vec<String>,
+    pub unchanged: usize,
+    /// Open tasks completed because they're checked off in the plan
+    pub completed: Vec<String>,
+    /// Tasks whose steps were removed from the plan and were cancelled
+    pub cancelled: Vec<String>,
+    /// Tasks whose steps were removed but which were already being worked
+    /// on; they're left as they are
+    pub detached: Vec<String>,
+}
+
+struct Node {
+    step: PlanStep,
+    /// Heading level (1-6), or 10 + indent for list items
+    level: usize,
+    body: Vec<String>,
+}
+
+/// Parse a markdown plan into steps
+pub fn parse(markdown: &str) -> Vec<PlanStep> {
+    let mut roots: Vec<Node> = Vec::new();
+    // Open nodes, outermost first; finished nodes are folded into their parent
+    let mut stack: Vec<Node> = Vec::new();
+    let mut headings: Vec<(usize, String)> = Vec::new();
+    let mut fence: Option<&str> = None;
+    
+    for (i, raw) in markdown.lines().enumerate() {
+        let trimmed = raw.trim_start();
+        
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+            push_body(&mut stack, raw);
+            continue;
+        }
+        if let Some(marker) = ["```", "~~~"].into_iter().find(|m| trimmed.starts_with(m)) {
+            fence = Some(marker);
+            push_body(&mut stack, raw);
+            continue;
+        }
+        
+        let (level, title, checked, is_heading) = if let Some((level, title)) = heading(raw) {
+            headings.retain(|(l, _)| *l < level);
+            headings.push((level, title.clone()));
+            (level, title, false, true)
+        } else if let Some((indent, title, checked)) = list_step(raw) {
+            (10 + indent, title, checked, false)
+        } else {
+            push_body(&mut stack, raw);
+            continue;
+        };
+        
+        while stack.last().is_some_and(|top| top.level >= level) {
+            close(&mut stack, &mut roots);
+        }
+        let nearest = headings.last().map(|(_, h)| h.as_str()).unwrap_or_default();
+        stack.push(Node {
+            step: PlanStep {
+                title,
+                description: None,
+                section: headings.iter().map(|(_, h)| h.as_str()).collect::<Vec<_>>().join(" > "),
+                anchor: anchor(nearest),
+                line: i + 1,
+                checked,
+                heading: is_heading,
+                children: Vec::new(),
+            },
+            level,
+            body: Vec::new(),
+        });
+    }
+    while !stack.is_empty() {
+        close(&mut stack, &mut roots);
+    }
+    
+    // A lone `#` heading is the document's title
+    if let [only] = roots.as_slice() {
+        if only.step.heading && only.level == 1 && !only.step.children.is_empty() {
+            let title = roots.remove(0);
+            return finish(title.step.children);
+        }
+    }
+    finish(roots.into_iter().map(|n| n.step).collect())
+}
+
+fn finish(steps: Vec<PlanStep>) -> Vec<PlanStep> {
+    if steps.iter().any(has_list_step) {
+        prune_context(steps)
+    } else {
+        steps
+    }
+}
+
+/// Finish the innermost open node and attach it to its parent
+fn close(stack: &mut Vec<Node>, roots: &mut Vec<Node>) {
+    let Some(mut node) = stack.pop() else { return };
+    let body = node.body.join("\n");
+    let body = body.trim();
+    if !body.is_empty() {
+        node.step.description = Some(dedent(body));
+    }
+    match stack.last_mut() {
+        Some(parent) => parent.step.children.push(node.step),
+        None => roots.push(node),
+    }
+}
+
+fn push_body(stack: &mut [Node], line: &str) {
+    if let Some(top) = stack.last_mut() {
+        top.body.push(line.to_string());
+    }
+}
+
+/// Headings with no list steps under them are context, not work
+fn prune_context(steps: Vec<PlanStep>) -> Vec<PlanStep> {
+    steps.into_iter()
+        .filter(has_list_step)
+        .map(|mut step| {
+            step.children = prune_context(step.children);
+            step
+        })
+        .collect()
+}
+
+/// Whether the step is a list item or has one under it
+fn has_list_step(step: &PlanStep) -> bool {
+    !step.heading || step.children.iter().any(has_list_step)
+}
+
+/// `## Title` → (2, "Title"), dropping closing hashes and leading numbering
+fn heading(line: &str) -> Option<(usize, String)> {
+    let hashes = line.chars().take_while(|c| *c == '#').count();
+    if !(1..=6).contains(&hashes) {
+        return None;
+    }
+    let rest = &line[hashes..];
+    if !(rest.is_empty() || rest.starts_with([' ', '\t'])) {
+        return None;
+    }
+    let title = rest.trim().trim_end_matches('#').trim();
+    let title = strip_number(title).unwrap_or(title);
+    (!title.is_empty()).then(|| (hashes, title.to_string()))
+}
+
+/// `  1. Title`, `- [ ] Title` → (indent, title, checked). Plain bullets
+/// aren't steps.
+fn list_step(line: &str) -> Option<(usize, String, bool)> {
+    let indent = line.len() - line.trim_start().len();
+    let item = line.trim_start();
+    
+    let (numbered, rest) = match strip_number(item) {
+        Some(rest) => (true, rest),
+        None => match item.strip_prefix(['-', '*', '+']) {
+            Some(rest) if rest.starts_with(' ') => (false, rest.trim_start()),
+            _ => return None,
+        },
+    };
+    
+    let (checkbox, rest) = match rest.get(..3) {
+        Some("[ ]") => (Some(false), &rest[3..]),
+        Some("[x]" | "[X]") => (Some(true), &rest[3..]),
+        _ => (None, rest),
+    };
+    if !numbered && checkbox.is_none() {
+        return None;
+    }
+    
+    let title = rest.trim();
+    (!title.is_empty()).then(|| (indent, title.to_string(), checkbox.unwrap_or(false)))
+}
+
+/// The text after a leading `1.` or `1)` and its space
+fn strip_number(text: &str) -> Option<&str> {
+    let digits = text.chars().take_while(char::is_ascii_digit).count();
+    if digits == 0 {
+        return None;
+    }
+    let rest = text[digits..].strip_prefix(['.', ')'])?;
+    rest.starts_with(' ').then(|| rest.trim_start())
+}
+
+/// Remove the indentation list item bodies share
+fn dedent(text: &str) -> String {
+    let indent = text.lines()
+        .skip(1)
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.len() - l.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    text.lines()
+        .enumerate()
+        .map(|(i, l)| if i == 0 { l } else { l.get(indent..).unwrap_or(l.trim_start()) })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// GitHub's heading anchor: lowercase, punctuation dropped, spaces to `-`
+pub fn anchor(heading: &str) -> String {
+    heading.to_lowercase()
+        .chars()
+        .filter(|c| c.is_alphanumeric() || matches!(c, ' ' | '-' | '_'))
+        .map(|c| if c == ' ' { '-' } else { c })
+        .collect()
+}
+
+fn normalize(title: &str) -> String {
+    title.to_lowercase()
+        .replace(['*', '_', '`'], "")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_end_matches(['.', ':', ';', '!'])
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_parse_plan() {
+        let plan = "\
+# Auth rewrite
+
+Some background on why.
+
+## Context
+
+- Sessions are stored in Redis today
+
+## 1. Backend
+
+1. Add token table
+   Needs a migration.
+2. Issue tokens on login
+   - [x] Sign with the new key
+   - [ ] Return refresh token
+
+## Rollout
+
+- [ ] Deploy to staging
+  ```sh
+  make deploy ENV=staging
+  ```
+- [ ] Deploy to production
+";
+        let steps = parse(plan);
+        let titles: Vec<_> = steps.iter().map(|s| s.title.as_str()).collect();
+        assert_eq!(titles, vec!["Backend", "Rollout"]);
+        
+        let backend = &steps[0];
+        assert_eq!(backend.section, "Auth rewrite > Backend");
+        assert_eq!(backend.anchor, "backend");
+        assert_eq!(backend.children[0].description.as_deref(), Some("Needs a migration."));
+        let issue = &backend.children[1];
+        assert_eq!(issue.line, 13);
+        assert_eq!(issue.children.len(), 2);
+        assert!(issue.children[0].checked && !issue.children[1].checked);
+        assert_eq!(issue.children[1].key(), "return refresh token");
+        
+        let staging = &steps[1].children[0];
+        assert_eq!(staging.anchor, "rollout");
+        assert!(staging.description.as_deref().unwrap().contains("make deploy ENV=staging"));
+        assert_eq!(steps.iter().map(PlanStep::count).sum::<usize>(), 8);
+        
+        // Without list steps, headings are the steps
+        let steps = parse("## Design\nWrite it up.\n## Build\n");
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[0].description.as_deref(), Some("Write it up."));
+    }
+}
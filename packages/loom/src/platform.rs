@@ -0,0 +1,205 @@
+//! Platform Differences
+//!
+//! Path handling and process checks that differ between Unix and Windows,
+//! kept in one place so config, sync and the daemon behave the same for
+//! every agent sharing a loom. Paths in config may use `~`, `$VAR`,
+//! `${VAR}` or `%VAR%`, and either separator; paths handed to git always
+//! use `/`.
+
+use std::path::{Path, PathBuf};
+
+/// Expand `~` and environment variables in a configured path, and use the
+/// platform's separator
+pub fn expand_path(path: &str) -> PathBuf {
+    let mut expanded = expand_vars(path);
+    
+    let rest = expanded.strip_prefix('~')
+        .filter(|rest| rest.is_empty() || rest.starts_with(['/', '\\']))
+        .map(String::from);
+    if let (Some(rest), Some(home)) = (rest, dirs::home_dir()) {
+        expanded = format!("{}{}", home.to_string_lossy(), rest);
+    }
+    
+    PathBuf::from(native_separators(&expanded))
+}
+
+/// `$VAR`, `${VAR}` and `%VAR%`; unset variables are left as written
+fn expand_vars(path: &str) -> String {
+    let mut out = String::with_capacity(path.len());
+    let mut rest = path;
+    
+    while let Some(i) = rest.find(['$', '%']) {
+        out.push_str(&rest[..i]);
+        let after = &rest[i + 1..];
+        let (name, len) = match (&rest[i..i + 1], after.strip_prefix('{')) {
+            ("$", Some(braced)) => match braced.find('}') {
+                Some(end) => (&braced[..end], end + 3),
+                None => ("", 1),
+            },
+            ("$", None) => {
+                let end = after.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_')).unwrap_or(after.len());
+                (&after[..end], end + 1)
+            }
+            _ => match after.find('%') {
+                Some(end) if end > 0 && !after[..end].contains(['/', '\\']) => (&after[..end], end + 2),
+                _ => ("", 1),
+            },
+        };
+        
+        match std::env::var(name).ok().filter(|_| !name.is_empty()) {
+            Some(value) => out.push_str(&value),
+            None => out.push_str(&rest[i..i + len]),
+        }
+        rest = &rest[i + len..];
+    }
+    
+    out.push_str(rest);
+    out
+}
+
+#[cfg(windows)]
+fn native_separators(path: &str) -> String {
+    path.replace('/', "\\")
+}
+
+// A backslash is an ordinary file name character on Unix
+#[cfg(not(windows))]
+fn native_separators(path: &str) -> String {
+    path.to_string()
+}
+
+/// Resolve a path for comparison or display, or return it unchanged if it
+/// doesn't exist. On Windows the `\\?\` prefix `canonicalize` adds is
+/// dropped, since git and most tools don't accept it.
+pub fn canonical(path: impl AsRef<Path>) -> PathBuf {
+    let path = path.as_ref();
+    match std::fs::canonicalize(path) {
+        Ok(resolved) => strip_verbatim(resolved),
+        Err(_) => path.to_path_buf(),
+    }
+}
+
+#[cfg(windows)]
+fn strip_verbatim(path: PathBuf) -> PathBuf {
+    let s = path.to_string_lossy();
+    if let Some(unc) = s.strip_prefix(r"\\?\UNC\") {
+        PathBuf::from(format!(r"\\{}", unc))
+    } else if let Some(local) = s.strip_prefix(r"\\?\") {
+        PathBuf::from(local)
+    } else {
+        path
+    }
+}
+
+#[cfg(not(windows))]
+fn strip_verbatim(path: PathBuf) -> PathBuf {
+    path
+}
+
+/// Whether two paths name the same location (case-insensitively on Windows)
+pub fn same_path(a: impl AsRef<Path>, b: impl AsRef<Path>) -> bool {
+    let (a, b) = (canonical(a), canonical(b));
+    if cfg!(windows) {
+        a.to_string_lossy().eq_ignore_ascii_case(&b.to_string_lossy())
+    } else {
+        a == b
+    }
+}
+
+/// A relative path as git expects it, with `/` separators on every platform
+pub fn git_path(path: impl AsRef<Path>) -> String {
+    path.as_ref()
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// A command that runs `command` through the platform's shell
+pub fn shell_command(command: &str) -> std::process::Command {
+    let (shell, flag) = if cfg!(windows) { ("cmd", "/C") } else { ("sh", "-c") };
+    let mut cmd = std::process::Command::new(shell);
+    cmd.args([flag, command]);
+    cmd
+}
+
+/// Whether a process with this PID exists (possibly owned by another user)
+#[cfg(unix)]
+pub fn process_alive(pid: u32) -> bool {
+    let Ok(pid) = libc::pid_t::try_from(pid) else {
+        return false;
+    };
+    // Signal 0 checks for existence without delivering anything
+    let delivered = unsafe { libc::kill(pid, 0) } == 0;
+    delivered || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+/// Whether a process with this PID exists
+#[cfg(windows)]
+pub fn process_alive(pid: u32) -> bool {
+    // tasklist ships with every Windows install; a missing PID prints an
+    // "INFO:" line instead of a CSV row
+    std::process::Command::new("tasklist")
+        .args(["/FI", &format!("PID eq {}", pid), "/FO", "CSV", "/NH"])
+        .output()
+        .map(|out| String::from_utf8_lossy(&out.stdout).contains(&format!("\"{}\"", pid)))
+        .unwrap_or(false)
+}
+
+/// Start the command in a process group of its own, so `kill_tree` can
+/// reach whatever it spawns (Windows finds the tree by parent PID instead)
+pub fn own_process_group(cmd: &mut std::process::Command) {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        cmd.process_group(0);
+    }
+    #[cfg(not(unix))]
+    let _ = cmd;
+}
+
+/// Kill a child and everything it started, then reap it
+#[cfg(unix)]
+pub fn kill_tree(child: &mut std::process::Child) {
+    // A child started by `own_process_group` leads a group with its PID
+    if let Ok(pid) = libc::pid_t::try_from(child.id()) {
+        unsafe { libc::kill(-pid, libc::SIGKILL) };
+    }
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+/// Kill a child and everything it started, then reap it
+#[cfg(windows)]
+pub fn kill_tree(child: &mut std::process::Child) {
+    let _ = std::process::Command::new("taskkill")
+        .args(["/T", "/F", "/PID", &child.id().to_string()])
+        .output();
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_expand_path() {
+        std::env::set_var("LOOM_PLATFORM_TEST", "shared");
+        let home = dirs::home_dir().unwrap();
+        
+        assert_eq!(expand_path("~/code/api"), PathBuf::from(native_separators(&format!("{}/code/api", home.display()))));
+        assert_eq!(expand_path("$LOOM_PLATFORM_TEST/api"), PathBuf::from(native_separators("shared/api")));
+        assert_eq!(expand_path("${LOOM_PLATFORM_TEST}/api"), PathBuf::from(native_separators("shared/api")));
+        assert_eq!(expand_path("%LOOM_PLATFORM_TEST%/api"), PathBuf::from(native_separators("shared/api")));
+        // Unset variables, stray sigils, and ~user are left alone
+        assert_eq!(expand_path("$LOOM_UNSET_VAR/50%"), PathBuf::from(native_separators("$LOOM_UNSET_VAR/50%")));
+        assert_eq!(expand_path("~other/api"), PathBuf::from(native_separators("~other/api")));
+        
+        assert_eq!(git_path(Path::new(".loom").join("tasks.jsonl")), ".loom/tasks.jsonl");
+        
+        let dir = tempfile::tempdir().unwrap();
+        assert!(same_path(dir.path(), dir.path().join(".")));
+        assert!(process_alive(std::process::id()));
+    }
+}
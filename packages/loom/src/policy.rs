@@ -43,6 +43,9 @@ pub fn claude_code_profile() -> AgentProfile {
         active: 0,
         available: true,
         last_used: None,
+        last_heartbeat: None,
+        unresponsive: false,
+        probe: None,
     }
 }
 
@@ -76,6 +79,9 @@ pub fn cursor_profile() -> AgentProfile {
         active: 0,
         available: true,
         last_used: None,
+        last_heartbeat: None,
+        unresponsive: false,
+        probe: None,
     }
 }
 
@@ -109,6 +115,9 @@ pub fn codex_profile() -> AgentProfile {
         active: 0,
         available: true,
         last_used: None,
+        last_heartbeat: None,
+        unresponsive: false,
+        probe: None,
     }
 }
 
@@ -142,6 +151,9 @@ pub fn gemini_profile() -> AgentProfile {
         active: 0,
         available: true,
         last_used: None,
+        last_heartbeat: None,
+        unresponsive: false,
+        probe: None,
     }
 }
 
@@ -295,6 +307,77 @@ pub fn requires_verification(task: &Task) -> bool {
     task.labels.iter().any(|l| verify_labels.contains(&l.as_str()))
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// Create Something Evidence Requirements
+// ─────────────────────────────────────────────────────────────────────────────
+
+use std::collections::HashMap;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// Evidence a task with a given label must carry to be completed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EvidenceRequirement {
+    /// Regexes; completion evidence must match at least one
+    pub patterns: Vec<String>,
+    /// What the agent should provide, shown when completion is refused
+    pub expected: String,
+}
+
+/// Deploys and security fixes can't be closed on the agent's word alone
+pub fn evidence_requirements() -> HashMap<String, EvidenceRequirement> {
+    HashMap::from([
+        ("deploy".to_string(), EvidenceRequirement {
+            patterns: vec![r"https?://\S+".to_string()],
+            expected: "a deployment or CI run URL (https://...)".to_string(),
+        }),
+        ("security".to_string(), EvidenceRequirement {
+            patterns: vec![r"\b[0-9a-f]{7,40}\b".to_string()],
+            expected: "the commit SHA of the fix (7-40 hex characters)".to_string(),
+        }),
+    ])
+}
+
+/// Evidence requirements compiled for checking at completion time
+#[derive(Debug, Clone, Default)]
+pub struct EvidenceRules {
+    /// (label, patterns, expected), sorted by label
+    rules: Vec<(String, Vec<Regex>, String)>,
+}
+
+impl EvidenceRules {
+    /// Compile requirements keyed by label
+    pub fn compile(requirements: &HashMap<String, EvidenceRequirement>) -> Result<Self, regex::Error> {
+        let mut rules = requirements.iter()
+            .map(|(label, req)| {
+                let patterns = req.patterns.iter()
+                    .map(|p| Regex::new(p))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok((label.to_lowercase(), patterns, req.expected.clone()))
+            })
+            .collect::<Result<Vec<_>, regex::Error>>()?;
+        rules.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(Self { rules })
+    }
+    
+    /// Check a task's evidence against its labels' requirements.
+    /// Returns the first unmet requirement as (label, expected).
+    pub fn check(&self, labels: &[String], evidence: Option<&str>) -> Result<(), (String, String)> {
+        for (label, patterns, expected) in &self.rules {
+            if !labels.iter().any(|l| l.to_lowercase() == *label) {
+                continue;
+            }
+            let satisfied = evidence
+                .map(|e| patterns.iter().any(|p| p.is_match(e)))
+                .unwrap_or(false);
+            if !satisfied {
+                return Err((label.clone(), expected.clone()));
+            }
+        }
+        Ok(())
+    }
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Create Something Scoring Algorithm
 // ─────────────────────────────────────────────────────────────────────────────
@@ -308,7 +391,8 @@ pub fn score_agent(profile: &AgentProfile, task: &Task) -> f64 {
     let complexity = Complexity::estimate(task);
     
     // Check quality threshold - don't route complex work to weak agents
-    let quality = profile.quality.success_rate();
+    // (judged on this kind of task, where the agent has a record on it)
+    let quality = profile.quality.success_rate_for(&task.labels);
     if quality < complexity.quality_threshold() {
         return 0.0; // Disqualified
     }
@@ -397,12 +481,14 @@ pub fn cs_feature_formula() -> Formula {
                 description: "Name of the feature".to_string(),
                 required: true,
                 default: None,
+                ..Default::default()
             },
             Variable {
                 name: "package".to_string(),
                 description: "Package to implement in (io, agency, tend, etc.)".to_string(),
                 required: true,
                 default: None,
+                ..Default::default()
             },
         ],
         steps: vec![
@@ -497,12 +583,14 @@ pub fn cs_component_formula() -> Formula {
                 description: "Name of the component (PascalCase)".to_string(),
                 required: true,
                 default: None,
+                ..Default::default()
             },
             Variable {
                 name: "package".to_string(),
                 description: "Package (components, io, agency)".to_string(),
                 required: false,
                 default: Some("components".to_string()),
+                ..Default::default()
             },
         ],
         steps: vec![
@@ -567,12 +655,14 @@ pub fn cs_refactor_formula() -> Formula {
                 description: "File or directory to refactor".to_string(),
                 required: true,
                 default: None,
+                ..Default::default()
             },
             Variable {
                 name: "goal".to_string(),
                 description: "What to improve (dry, cleanup, organize)".to_string(),
                 required: true,
                 default: None,
+                ..Default::default()
             },
         ],
         steps: vec![
@@ -655,12 +745,14 @@ pub fn cs_worker_formula() -> Formula {
                 description: "Name of the worker".to_string(),
                 required: true,
                 default: None,
+                ..Default::default()
             },
             Variable {
                 name: "package".to_string(),
                 description: "Parent package".to_string(),
                 required: false,
                 default: Some("templates-platform".to_string()),
+                ..Default::default()
             },
         ],
         steps: vec![
@@ -715,7 +807,7 @@ pub fn cs_worker_formula() -> Formula {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::work::{Task, Status};
+    use crate::work::{Task, Status, TaskKind};
     use chrono::Utc;
     
     fn make_task(title: &str, labels: Vec<&str>) -> Task {
@@ -731,6 +823,8 @@ mod tests {
             evidence: None,
             actual_cost_usd: None,
             repo: None,
+            kind: TaskKind::Task,
+            rollup: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         }
@@ -771,6 +865,20 @@ mod tests {
         assert!(requires_verification(&task));
     }
     
+    #[test]
+    fn test_evidence_rules() {
+        let rules = EvidenceRules::compile(&evidence_requirements()).unwrap();
+        let labels = |ls: &[&str]| ls.iter().map(|l| l.to_string()).collect::<Vec<_>>();
+        
+        assert!(rules.check(&labels(&["ui"]), None).is_ok());
+        assert!(rules.check(&labels(&["Security"]), Some("fixed in 3f9a2c1")).is_ok());
+        assert_eq!(
+            rules.check(&labels(&["security", "deploy"]), Some("fixed in 3f9a2c1")).unwrap_err().0,
+            "deploy"
+        );
+        assert!(rules.check(&labels(&["security"]), Some("looks good")).is_err());
+    }
+    
     #[test]
     fn test_scoring() {
         let claude = claude_code_profile();
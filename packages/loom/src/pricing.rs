@@ -0,0 +1,267 @@
+//! Model Pricing
+//!
+//! Prices in models.toml go stale, and every execution recorded while they
+//! were wrong carries the wrong cost. A pricing table lists each model's
+//! price per 1K tokens with the date it took effect (bundled, or
+//! `.loom/pricing.toml`). Syncing writes the prices in effect today into
+//! models.toml; repricing recomputes recorded execution costs from their
+//! token counts at the price in effect when they ran, so monthly totals
+//! match what was actually billed.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::agents::{AgentError, AgentRegistry};
+use crate::models::ModelsConfig;
+
+/// Where a repo's own pricing table lives, relative to `.loom/`
+pub const PRICING_FILE: &str = "pricing.toml";
+
+#[derive(Error, Debug)]
+pub enum PricingError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    
+    #[error("Invalid pricing table: {0}")]
+    Toml(#[from] toml::de::Error),
+    
+    #[error("Agent error: {0}")]
+    Agent(#[from] AgentError),
+}
+
+/// A model's price from a date on
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PriceEntry {
+    pub model: String,
+    /// First UTC day the price applies
+    pub effective: NaiveDate,
+    pub input_per_1k: f64,
+    pub output_per_1k: f64,
+}
+
+impl PriceEntry {
+    /// Cost of `tokens` in total, split between input and output by `output_ratio`
+    pub fn cost(&self, tokens: u64, output_ratio: f64) -> f64 {
+        let input = tokens as f64 / (1.0 + output_ratio);
+        let output = tokens as f64 - input;
+        (input / 1000.0) * self.input_per_1k + (output / 1000.0) * self.output_per_1k
+    }
+}
+
+/// Prices by model and effective date
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PricingTable {
+    pub version: String,
+    #[serde(default)]
+    pub prices: Vec<PriceEntry>,
+}
+
+impl PricingTable {
+    /// The table shipped with Loom
+    pub fn bundled() -> Self {
+        toml::from_str(include_str!("default_pricing.toml")).expect("default_pricing.toml should be valid")
+    }
+    
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, PricingError> {
+        Ok(toml::from_str(&std::fs::read_to_string(path)?)?)
+    }
+    
+    /// `.loom/pricing.toml` if the repo has one, otherwise the bundled table
+    pub fn load_or_bundled(loom_root: impl AsRef<Path>) -> Result<Self, PricingError> {
+        let path = loom_root.as_ref().join(PRICING_FILE);
+        if path.exists() {
+            Self::load(path)
+        } else {
+            Ok(Self::bundled())
+        }
+    }
+    
+    /// The price in effect for `model` on `day`
+    pub fn price_at(&self, model: &str, day: NaiveDate) -> Option<&PriceEntry> {
+        self.prices.iter()
+            .filter(|p| p.model == model && p.effective <= day)
+            .max_by_key(|p| p.effective)
+    }
+}
+
+/// A models.toml price brought up to date
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceChange {
+    pub model: String,
+    pub effective: NaiveDate,
+    pub from_input_per_1k: f64,
+    pub from_output_per_1k: f64,
+    pub to_input_per_1k: f64,
+    pub to_output_per_1k: f64,
+}
+
+/// Models whose configured price differs from the one in effect on `day`
+pub fn price_changes(models: &ModelsConfig, table: &PricingTable, day: NaiveDate) -> Vec<PriceChange> {
+    let mut changes: Vec<PriceChange> = models.models.iter()
+        .filter_map(|(id, model)| {
+            let price = table.price_at(id, day)?;
+            let changed = (price.input_per_1k - model.input_per_1k).abs() > f64::EPSILON
+                || (price.output_per_1k - model.output_per_1k).abs() > f64::EPSILON;
+            changed.then(|| PriceChange {
+                model: id.clone(),
+                effective: price.effective,
+                from_input_per_1k: model.input_per_1k,
+                from_output_per_1k: model.output_per_1k,
+                to_input_per_1k: price.input_per_1k,
+                to_output_per_1k: price.output_per_1k,
+            })
+        })
+        .collect();
+    changes.sort_by(|a, b| a.model.cmp(&b.model));
+    changes
+}
+
+/// Rewrite the price lines of changed models in a models.toml, leaving
+/// comments and everything else as they were
+pub fn apply_to_models_toml(content: &str, changes: &[PriceChange]) -> String {
+    let mut current: Option<&PriceChange> = None;
+    let mut lines = Vec::new();
+    
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            let section = trimmed.trim_start_matches('[').trim_end_matches(']');
+            current = section.strip_prefix("models.")
+                .and_then(|id| changes.iter().find(|c| c.model == id.trim_matches('"')));
+        }
+        
+        let key = trimmed.split('=').next().unwrap_or_default().trim();
+        let value = match (current, key) {
+            (Some(change), "input_per_1k") => Some(change.to_input_per_1k),
+            (Some(change), "output_per_1k") => Some(change.to_output_per_1k),
+            _ => None,
+        };
+        match value {
+            Some(value) => {
+                let indent = &line[..line.len() - line.trim_start().len()];
+                let comment = line.find('#').map(|i| &line[line[..i].trim_end().len()..]).unwrap_or_default();
+                lines.push(format!("{}{} = {}{}", indent, key, value, comment));
+            }
+            None => lines.push(line.to_string()),
+        }
+    }
+    
+    let mut rewritten = lines.join("\n");
+    if content.ends_with('\n') {
+        rewritten.push('\n');
+    }
+    rewritten
+}
+
+/// Recorded costs before and after repricing, in one month
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MonthCost {
+    pub executions: usize,
+    pub before_usd: f64,
+    pub after_usd: f64,
+}
+
+/// Result of repricing execution history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Repricing {
+    /// Version of the pricing table used
+    pub version: String,
+    pub repriced: usize,
+    /// Executions without a token count or a price for their model and date
+    pub skipped: usize,
+    pub before_usd: f64,
+    pub after_usd: f64,
+    /// Keyed by month (`YYYY-MM`)
+    pub by_month: BTreeMap<String, MonthCost>,
+    pub dry_run: bool,
+}
+
+/// Recompute recorded execution costs at the prices in effect when they ran
+///
+/// Token counts are split into input and output by each model's configured
+/// output ratio (2.5 for models not in `models`).
+pub fn reprice(
+    registry: &mut AgentRegistry,
+    table: &PricingTable,
+    models: &ModelsConfig,
+    dry_run: bool,
+) -> Result<Repricing, PricingError> {
+    let price = |agent: &str, at: DateTime<Utc>, tokens: u64| {
+        let ratio = models.models.get(agent).map(|m| m.output_ratio).unwrap_or(2.5);
+        table.price_at(agent, at.date_naive()).map(|p| p.cost(tokens, ratio))
+    };
+    let results = registry.reprice_executions(price, &table.version, dry_run)?;
+    
+    let mut repricing = Repricing {
+        version: table.version.clone(),
+        repriced: 0,
+        skipped: 0,
+        before_usd: 0.0,
+        after_usd: 0.0,
+        by_month: BTreeMap::new(),
+        dry_run,
+    };
+    for (record, cost) in results {
+        let (Some(after), Some(at)) = (cost, record.timestamp) else {
+            repricing.skipped += 1;
+            continue;
+        };
+        let before = record.cost.unwrap_or(0.0);
+        repricing.repriced += 1;
+        repricing.before_usd += before;
+        repricing.after_usd += after;
+        
+        let month = repricing.by_month.entry(at.format("%Y-%m").to_string()).or_default();
+        month.executions += 1;
+        month.before_usd += before;
+        month.after_usd += after;
+    }
+    
+    Ok(repricing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_sync_and_reprice_use_effective_dates() {
+        let table = PricingTable::bundled();
+        let day = |s: &str| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+        assert_eq!(table.price_at("gpt-4o", day("2024-06-01")).unwrap().input_per_1k, 0.005);
+        assert_eq!(table.price_at("gpt-4o", day("2024-09-01")).unwrap().input_per_1k, 0.0025);
+        assert!(table.price_at("gpt-4o", day("2024-01-01")).is_none());
+        
+        // The bundled models already carry today's prices
+        let mut models = ModelsConfig::defaults();
+        assert!(price_changes(&models, &table, Utc::now().date_naive()).is_empty());
+        
+        // A stale price is found and rewritten in place, comments intact
+        models.models.get_mut("gpt-4o").unwrap().input_per_1k = 0.005;
+        let changes = price_changes(&models, &table, Utc::now().date_naive());
+        assert_eq!(changes.len(), 1);
+        let toml = "[models.gpt-4o]\ninput_per_1k = 0.005  # old\noutput_per_1k = 0.01\n\n[models.claude-opus]\ninput_per_1k = 0.015\n";
+        let rewritten = apply_to_models_toml(toml, &changes);
+        assert!(rewritten.contains("input_per_1k = 0.0025  # old"));
+        assert!(rewritten.contains("[models.claude-opus]\ninput_per_1k = 0.015\n"));
+        
+        // Executions are repriced at the price of their day
+        let dir = tempfile::tempdir().unwrap();
+        let mut registry = AgentRegistry::open(dir.path().join("agents.db")).unwrap();
+        registry.record_execution("gpt-4o", "lm-1", None, true, 60.0, Some(3500), Some(1.0)).unwrap();
+        registry.record_execution("gpt-4o", "lm-2", None, true, 60.0, None, Some(1.0)).unwrap();
+        
+        let preview = reprice(&mut registry, &table, &models, true).unwrap();
+        assert_eq!((preview.repriced, preview.skipped), (1, 1));
+        // 1000 input at 0.0025 + 2500 output at 0.01
+        assert!((preview.after_usd - 0.0275).abs() < 1e-9);
+        assert_eq!(registry.get_agent_history("gpt-4o", None).unwrap().iter().filter_map(|r| r.cost).sum::<f64>(), 2.0);
+        
+        reprice(&mut registry, &table, &models, false).unwrap();
+        let costs: f64 = registry.get_agent_history("gpt-4o", None).unwrap().iter().filter_map(|r| r.cost).sum();
+        assert!((costs - 1.0275).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,176 @@
+//! Agent Probing
+//!
+//! models.toml says an agent is available; probing checks. Each agent's CLI
+//! is run with `--version`:
+//!
+//! - A command that can't be found, or doesn't answer within the timeout,
+//!   marks the agent unavailable, so routing stops sending it work
+//! - So does one that exits with an error, since a CLI that can't report
+//!   its version won't run a task either
+//! - One that answers marks it available again, with the time from spawn to
+//!   exit recorded as its cold-start latency
+//!
+//! The `fastest` strategy adds cold-start latency to an agent's average
+//! duration. `lm probe` and `loom_probe_agents` probe on demand; the daemon
+//! probes every `[probe] interval-mins`.
+
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::agents::AgentProfile;
+
+/// Arguments every agent CLI is probed with
+pub const PROBE_ARGS: &[&str] = &["--version"];
+
+/// Longest version line kept
+const MAX_VERSION_LEN: usize = 200;
+
+/// What probing an agent's CLI found
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Probe {
+    pub agent_id: String,
+    /// Command as configured
+    pub command: String,
+    /// Executable it resolved to
+    pub path: Option<String>,
+    /// First line the command printed
+    pub version: Option<String>,
+    /// Time from spawn to exit (milliseconds)
+    pub cold_start_ms: Option<u64>,
+    pub available: bool,
+    /// Why the agent isn't available
+    pub error: Option<String>,
+    pub probed_at: DateTime<Utc>,
+}
+
+/// Run an agent's CLI with `PROBE_ARGS`, killing it after `timeout`
+pub fn probe(agent: &AgentProfile, timeout: Duration) -> Probe {
+    let mut probe = Probe {
+        agent_id: agent.id.clone(),
+        command: agent.cli_path.clone(),
+        path: None,
+        version: None,
+        cold_start_ms: None,
+        available: false,
+        error: None,
+        probed_at: Utc::now(),
+    };
+    
+    // The which crate also resolves PATHEXT (.exe, .cmd) on Windows
+    let Ok(path) = which::which(&agent.cli_path) else {
+        probe.error = Some(format!("{} not found", agent.cli_path));
+        return probe;
+    };
+    probe.path = Some(path.display().to_string());
+    
+    let started = Instant::now();
+    let child = Command::new(&path)
+        .args(PROBE_ARGS)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn();
+    let mut child = match child {
+        Ok(child) => child,
+        Err(e) => {
+            probe.error = Some(format!("Couldn't start {}: {}", agent.cli_path, e));
+            return probe;
+        }
+    };
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if started.elapsed() >= timeout => {
+                let _ = child.kill();
+                let _ = child.wait();
+                probe.error = Some(format!("No answer within {}s", timeout.as_secs_f64()));
+                return probe;
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(10)),
+            Err(e) => {
+                probe.error = Some(format!("Couldn't wait for {}: {}", agent.cli_path, e));
+                return probe;
+            }
+        }
+    };
+    let elapsed = started.elapsed();
+    
+    // The process has exited, so this only drains the pipes
+    if let Ok(output) = child.wait_with_output() {
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        probe.version = stdout.lines()
+            .chain(stderr.lines())
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .map(|line| line.chars().take(MAX_VERSION_LEN).collect());
+    }
+    if !status.success() {
+        probe.error = Some(match &probe.version {
+            Some(output) => format!("{} exited with {}: {}", agent.cli_path, status, output),
+            None => format!("{} exited with {}", agent.cli_path, status),
+        });
+        probe.version = None;
+        return probe;
+    }
+    probe.cold_start_ms = Some(elapsed.as_millis() as u64);
+    probe.available = true;
+    probe
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::Loom;
+    use std::os::unix::fs::PermissionsExt;
+    
+    #[test]
+    fn test_probe_updates_availability() {
+        let dir = tempfile::tempdir().unwrap();
+        let script = dir.path().join("fake-agent");
+        std::fs::write(&script, "#!/bin/sh\necho 'fake-agent 1.2.3 (build 42)'\n").unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let slow = dir.path().join("slow-agent");
+        std::fs::write(&slow, "#!/bin/sh\nsleep 5\n").unwrap();
+        std::fs::set_permissions(&slow, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let broken = dir.path().join("broken-agent");
+        std::fs::write(&broken, "#!/bin/sh\necho 'missing libagent.so' >&2\nexit 127\n").unwrap();
+        std::fs::set_permissions(&broken, std::fs::Permissions::from_mode(0o755)).unwrap();
+        
+        let mut loom = Loom::in_memory().unwrap();
+        for (id, cli) in [
+            ("fake", script.to_str().unwrap()),
+            ("slow", slow.to_str().unwrap()),
+            ("broken", broken.to_str().unwrap()),
+            ("missing", "/nonexistent/agent"),
+        ] {
+            let config: crate::ModelConfig = serde_json::from_value(serde_json::json!({
+                "family": "other", "cli": cli, "input_per_1k": 0.0, "output_per_1k": 0.0
+            })).unwrap();
+            loom.register_agent(id, &config).unwrap();
+        }
+        
+        let probes = loom.probe_agents(None, Duration::from_millis(500)).unwrap();
+        let found = |id: &str| probes.iter().find(|p| p.agent_id == id).unwrap();
+        assert!(found("fake").available);
+        assert_eq!(found("fake").version.as_deref(), Some("fake-agent 1.2.3 (build 42)"));
+        assert!(found("slow").error.as_deref().unwrap().starts_with("No answer within"));
+        assert!(found("missing").error.as_deref().unwrap().ends_with("not found"));
+        assert!(!found("broken").available);
+        assert!(found("broken").error.as_deref().unwrap().ends_with("exit status: 127: missing libagent.so"));
+        
+        // Results land on the profiles routing reads
+        let fake = loom.agent("fake").unwrap().unwrap();
+        assert!(fake.available);
+        assert!(fake.probe.unwrap().cold_start_ms.is_some());
+        assert!(!loom.agent("slow").unwrap().unwrap().available);
+        assert!(!loom.agent("missing").unwrap().unwrap().available);
+        assert!(!loom.agent("broken").unwrap().unwrap().available);
+        
+        // Probing one agent leaves the rest alone
+        assert_eq!(loom.probe_agents(Some("fake"), Duration::from_secs(5)).unwrap().len(), 1);
+        assert!(loom.probe_agents(Some("nobody"), Duration::from_secs(5)).is_err());
+    }
+}
@@ -0,0 +1,187 @@
+//! Agent Quotas
+//!
+//! Per-agent rate limits (creates and claims per minute) and a cap on the
+//! open tasks an agent has created, configured under `[limits]`. The MCP
+//! layer checks them before a tool runs and records the action after it
+//! succeeds. Activity lives in work.db, so every MCP server and the HTTP API
+//! on a repo share the same counts.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::config::AgentLimits;
+use crate::work::{WorkError, WorkStore};
+
+/// Agent that actions are charged to when a call doesn't name one
+pub const ANONYMOUS_AGENT: &str = "anonymous";
+
+#[derive(Error, Debug)]
+pub enum QuotaError {
+    #[error("Rate limit: {agent} made {count} {action}s in the last minute (limit {limit}/min); retry in {retry_after_secs}s")]
+    RateLimited {
+        agent: String,
+        action: AgentAction,
+        count: u32,
+        limit: u32,
+        retry_after_secs: i64,
+    },
+    
+    #[error("Quota: {agent} has {open} open tasks it created (limit {limit}); complete or cancel some before creating more")]
+    TooManyOpen { agent: String, open: u32, limit: u32 },
+    
+    #[error("Work store error: {0}")]
+    Work(#[from] WorkError),
+}
+
+/// A rate-limited action
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AgentAction {
+    Create,
+    Claim,
+}
+
+impl AgentAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AgentAction::Create => "create",
+            AgentAction::Claim => "claim",
+        }
+    }
+}
+
+impl std::fmt::Display for AgentAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// An agent's usage against its limits
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuotaStatus {
+    pub agent: String,
+    pub limits: AgentLimits,
+    pub creates_last_minute: u32,
+    pub claims_last_minute: u32,
+    /// Open tasks the agent created
+    pub open_tasks: u32,
+    /// Whether the next create/claim would be refused
+    pub can_create: bool,
+    pub can_claim: bool,
+}
+
+/// Report an agent's usage against its limits
+pub fn status(store: &WorkStore, agent: &str, limits: AgentLimits) -> Result<QuotaStatus, QuotaError> {
+    let now = Utc::now();
+    let creates = store.activity_since(agent, AgentAction::Create.as_str(), now - Duration::minutes(1))?.len() as u32;
+    let claims = store.activity_since(agent, AgentAction::Claim.as_str(), now - Duration::minutes(1))?.len() as u32;
+    let open_tasks = store.open_tasks_created_by(agent)?;
+    
+    Ok(QuotaStatus {
+        agent: agent.to_string(),
+        limits,
+        creates_last_minute: creates,
+        claims_last_minute: claims,
+        open_tasks,
+        can_create: limits.creates_per_minute.is_none_or(|l| creates < l)
+            && limits.max_open_tasks.is_none_or(|l| open_tasks < l),
+        can_claim: limits.claims_per_minute.is_none_or(|l| claims < l),
+    })
+}
+
+/// Check whether an agent may take `count` more of an action right now
+pub fn check(
+    store: &WorkStore,
+    agent: &str,
+    limits: AgentLimits,
+    action: AgentAction,
+    count: u32,
+) -> Result<(), QuotaError> {
+    let per_minute = match action {
+        AgentAction::Create => limits.creates_per_minute,
+        AgentAction::Claim => limits.claims_per_minute,
+    };
+    
+    if let Some(limit) = per_minute {
+        let now = Utc::now();
+        let recent = store.activity_since(agent, action.as_str(), now - Duration::minutes(1))?;
+        let used = recent.len() as u32;
+        if used + count > limit {
+            return Err(QuotaError::RateLimited {
+                agent: agent.to_string(),
+                action,
+                count: used,
+                limit,
+                retry_after_secs: retry_after(&recent, used + count - limit, now),
+            });
+        }
+    }
+    
+    if let (AgentAction::Create, Some(limit)) = (action, limits.max_open_tasks) {
+        let open = store.open_tasks_created_by(agent)?;
+        if open + count > limit {
+            return Err(QuotaError::TooManyOpen { agent: agent.to_string(), open, limit });
+        }
+    }
+    
+    Ok(())
+}
+
+/// Seconds until `excess` of the actions in the window (oldest first) age out
+fn retry_after(recent: &[DateTime<Utc>], excess: u32, now: DateTime<Utc>) -> i64 {
+    recent
+        .get(excess.saturating_sub(1) as usize)
+        .map(|at| (*at + Duration::minutes(1) - now).num_seconds().max(1))
+        .unwrap_or(60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::work::CreateTask;
+    
+    #[test]
+    fn test_limits_are_enforced_per_agent() {
+        let mut store = WorkStore::in_memory().unwrap();
+        let limits = AgentLimits {
+            creates_per_minute: Some(2),
+            claims_per_minute: None,
+            max_open_tasks: Some(3),
+        };
+        
+        let mut ids = Vec::new();
+        for title in ["a", "b"] {
+            check(&store, "looper", limits, AgentAction::Create, 1).unwrap();
+            let task = store.create(CreateTask { title: title.to_string(), ..Default::default() }).unwrap();
+            store.record_activity("looper", "create", &task.id).unwrap();
+            ids.push(task.id);
+        }
+        
+        match check(&store, "looper", limits, AgentAction::Create, 1) {
+            Err(QuotaError::RateLimited { count, limit, retry_after_secs, .. }) => {
+                assert_eq!((count, limit), (2, 2));
+                assert!((1..=60).contains(&retry_after_secs));
+            }
+            other => panic!("expected a rate limit, got {:?}", other),
+        }
+        // Other agents have their own budget, and claims aren't limited here
+        check(&store, "codex", limits, AgentAction::Create, 1).unwrap();
+        check(&store, "looper", limits, AgentAction::Claim, 10).unwrap();
+        
+        // Open-task cap counts only unfinished tasks the agent created
+        let unlimited_rate = AgentLimits { creates_per_minute: None, ..limits };
+        assert!(matches!(
+            check(&store, "looper", unlimited_rate, AgentAction::Create, 2),
+            Err(QuotaError::TooManyOpen { open: 2, limit: 3, .. })
+        ));
+        store.cancel(&ids[0]).unwrap();
+        check(&store, "looper", unlimited_rate, AgentAction::Create, 2).unwrap();
+        
+        let status = status(&store, "looper", limits).unwrap();
+        assert_eq!(status.creates_last_minute, 2);
+        assert_eq!(status.open_tasks, 1);
+        assert!(!status.can_create);
+        assert!(status.can_claim);
+    }
+}
@@ -5,11 +5,28 @@
 //! - Agent capabilities and history
 //! - Cost constraints
 //! - Availability
+//!
+//! The `Learned` strategy is an epsilon-greedy bandit over execution
+//! history: each agent's success rate on tasks sharing the task's labels,
+//! starting from its predicted success and shifting as results come in.
+//! A configurable share of decisions explores the least-tried agent instead.
+//!
+//! `LeastLoaded` and `RoundRobin` spread work by claimed tasks: agents at
+//! their concurrency limit are skipped, and the rest are taken by lowest
+//! share of their limit in use, or in turn.
+//!
+//! Affinity narrows the field before any strategy runs. The agent that did
+//! a sub-task's parent (sticky routing) takes it when eligible; failing
+//! that, preferred agents (from the request, or `[routing.affinity]` for the
+//! task's labels) are the only ones the strategy considers. Excluded agents,
+//! including `[routing.exclude]` for the task's labels, are never routed to.
 
 use crate::work::Task;
-use crate::agents::{AgentProfile, AgentRegistry, RequiredFeatures};
+use crate::agents::{AgentProfile, AgentRegistry, ExecutionRecord, RequiredFeatures};
+use crate::config::RoutingConfig;
 use crate::formulas::Formula;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Routing strategy
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -21,14 +38,20 @@ pub enum RoutingStrategy {
     Cheapest,
     /// Fastest available agent
     Fastest,
-    /// Round-robin across available agents
+    /// Round-robin across agents below their concurrency limit
     RoundRobin,
+    /// Agent using the smallest share of its concurrency limit
+    LeastLoaded,
     /// Specific agent
     Specific,
+    /// Agent with the best recorded success on this kind of task, exploring
+    /// others some of the time
+    Learned,
 }
 
 /// Routing constraints
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct RoutingConstraints {
     /// Maximum cost in dollars
     pub max_cost: Option<f64>,
@@ -36,12 +59,18 @@ pub struct RoutingConstraints {
     pub max_tokens: Option<u64>,
     /// Required features
     pub required_features: Option<RequiredFeatures>,
-    /// Preferred agents (in order)
+    /// Preferred agents (in order); when any is eligible, the strategy
+    /// picks among them alone
+    #[serde(alias = "prefer_agents")]
     pub preferred_agents: Vec<String>,
     /// Excluded agents
+    #[serde(alias = "exclude_agents")]
     pub excluded_agents: Vec<String>,
     /// Quality floor (minimum quality score)
     pub min_quality: Option<f64>,
+    /// Agent that did the task's parent, which takes the task ahead of any
+    /// preference when eligible (see `Loom::sticky_agent`)
+    pub sticky_agent: Option<String>,
 }
 
 /// Routing decision
@@ -57,16 +86,78 @@ pub struct RoutingDecision {
     pub confidence: f64,
     /// Alternative agents considered
     pub alternatives: Vec<String>,
+    /// Things to know before dispatching (e.g. budget overruns)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+    /// Tokens of task context the agent can take without truncation: its
+    /// model's window less the response and system prompt reservations
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_budget: Option<u64>,
+}
+
+/// Pseudo-observations the predicted success counts for, so an agent's
+/// first few results move its score without swamping it
+const PRIOR_WEIGHT: f64 = 4.0;
+
+/// An agent's claimed tasks against its concurrency limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentLoad {
+    pub claimed: u32,
+    pub limit: u32,
+}
+
+impl AgentLoad {
+    fn has_room(&self) -> bool {
+        self.claimed < self.limit
+    }
+    
+    /// Share of the limit in use
+    fn utilization(&self) -> f64 {
+        if self.limit == 0 {
+            1.0
+        } else {
+            self.claimed as f64 / self.limit as f64
+        }
+    }
 }
 
 /// Smart router
 pub struct Router {
-    last_robin_index: usize,
+    /// Agent the last round-robin decision went to
+    last_robin: Option<String>,
+    /// Claimed work per agent, for the load-aware strategies
+    loads: HashMap<String, AgentLoad>,
+    config: RoutingConfig,
+    /// xorshift state for exploration
+    rng: u64,
 }
 
 impl Router {
     pub fn new() -> Self {
-        Self { last_robin_index: 0 }
+        Self {
+            last_robin: None,
+            loads: HashMap::new(),
+            config: RoutingConfig::default(),
+            rng: uuid::Uuid::new_v4().as_u64_pair().0 | 1,
+        }
+    }
+    
+    /// Label affinities and exclusions, and the `Learned` strategy's
+    /// exploration rate and history window
+    pub fn set_config(&mut self, config: RoutingConfig) {
+        self.config = config;
+    }
+    
+    /// Current load per agent for `LeastLoaded` and `RoundRobin`. Agents
+    /// missing from the map count as idle, limited by their profile's
+    /// `max_concurrent`.
+    pub fn set_loads(&mut self, loads: HashMap<String, AgentLoad>) {
+        self.loads = loads;
+    }
+    
+    fn load(&self, profile: &AgentProfile) -> AgentLoad {
+        self.loads.get(&profile.id).copied()
+            .unwrap_or(AgentLoad { claimed: 0, limit: profile.max_concurrent })
     }
     
     /// Route a task to the best agent
@@ -80,6 +171,37 @@ impl Router {
         let profiles = registry.all_profiles()
             .map_err(|e| e.to_string())?;
         
+        let history = if strategy == RoutingStrategy::Learned {
+            let since = chrono::Utc::now() - chrono::Duration::days(self.config.history_days as i64);
+            registry.get_history_since(since).map_err(|e| e.to_string())?
+        } else {
+            Vec::new()
+        };
+        
+        self.route_candidates(task, profiles, strategy, constraints, &history)
+    }
+    
+    /// Route a task against an explicit set of profiles
+    /// (used for dry-runs where profiles don't come from the registry).
+    /// With no execution history, `Learned` goes on predicted success alone.
+    pub fn route_profiles(
+        &mut self,
+        task: &Task,
+        profiles: Vec<AgentProfile>,
+        strategy: RoutingStrategy,
+        constraints: &RoutingConstraints,
+    ) -> Result<RoutingDecision, String> {
+        self.route_candidates(task, profiles, strategy, constraints, &[])
+    }
+    
+    fn route_candidates(
+        &mut self,
+        task: &Task,
+        profiles: Vec<AgentProfile>,
+        strategy: RoutingStrategy,
+        constraints: &RoutingConstraints,
+        history: &[ExecutionRecord],
+    ) -> Result<RoutingDecision, String> {
         // Filter by constraints
         let candidates: Vec<_> = profiles.into_iter()
             .filter(|p| self.passes_constraints(p, task, constraints))
@@ -89,11 +211,58 @@ impl Router {
             return Err("No agents available matching constraints".to_string());
         }
         
+        // Narrow to the sticky agent, then to preferred agents, falling back
+        // to the whole field when none of a tier is eligible or the strategy
+        // turns all of them down
+        if strategy != RoutingStrategy::Specific {
+            let preferred = self.preferred_agents(task, constraints);
+            let tiers = [
+                (constraints.sticky_agent.iter().cloned().collect::<Vec<_>>(), "same agent as the parent task"),
+                (preferred, "preferred"),
+            ];
+            for (agents, why) in tiers {
+                let pinned: Vec<_> = candidates.iter()
+                    .filter(|p| agents.contains(&p.id))
+                    .cloned()
+                    .collect();
+                if pinned.is_empty() {
+                    continue;
+                }
+                if let Ok(mut decision) = self.route_strategy(task, &pinned, strategy, constraints, history) {
+                    decision.reason = format!("{} ({})", decision.reason, why);
+                    return Ok(decision);
+                }
+            }
+        }
+        
+        self.route_strategy(task, &candidates, strategy, constraints, history)
+    }
+    
+    /// Preferred agents from the request, then from `[routing.affinity]`
+    /// for the task's labels
+    fn preferred_agents(&self, task: &Task, constraints: &RoutingConstraints) -> Vec<String> {
+        let mut preferred = constraints.preferred_agents.clone();
+        for label in &task.labels {
+            preferred.extend(self.config.affinity.get(label).into_iter().flatten().cloned());
+        }
+        preferred
+    }
+    
+    fn route_strategy(
+        &mut self,
+        task: &Task,
+        candidates: &[AgentProfile],
+        strategy: RoutingStrategy,
+        constraints: &RoutingConstraints,
+        history: &[ExecutionRecord],
+    ) -> Result<RoutingDecision, String> {
         match strategy {
-            RoutingStrategy::Best => self.route_best(task, &candidates, constraints),
-            RoutingStrategy::Cheapest => self.route_cheapest(task, &candidates),
-            RoutingStrategy::Fastest => self.route_fastest(&candidates),
-            RoutingStrategy::RoundRobin => self.route_round_robin(&candidates),
+            RoutingStrategy::Best => self.route_best(task, candidates),
+            RoutingStrategy::Cheapest => self.route_cheapest(task, candidates),
+            RoutingStrategy::Fastest => self.route_fastest(task, candidates),
+            RoutingStrategy::RoundRobin => self.route_round_robin(task, candidates),
+            RoutingStrategy::LeastLoaded => self.route_least_loaded(task, candidates),
+            RoutingStrategy::Learned => self.route_learned(task, candidates, history),
             RoutingStrategy::Specific => {
                 if let Some(agent) = constraints.preferred_agents.first() {
                     candidates.iter()
@@ -104,6 +273,8 @@ impl Router {
                             estimated_cost: p.cost.estimate(self.estimate_tokens(task)),
                             confidence: 1.0,
                             alternatives: vec![],
+                            warnings: Vec::new(),
+                            context_budget: None,
                         })
                         .ok_or_else(|| format!("Agent {} not available", agent))
                 } else {
@@ -149,6 +320,8 @@ impl Router {
                 estimated_cost: agent.cost.estimate(tokens),
                 confidence: 0.9,
                 alternatives: candidates.iter().skip(1).take(2).map(|p| p.id.clone()).collect(),
+                warnings: Vec::new(),
+                context_budget: None,
             })
         } else {
             Err("No agents available".to_string())
@@ -161,10 +334,16 @@ impl Router {
             return false;
         }
         
-        // Check exclusions
+        // Check exclusions, requested and by label
         if constraints.excluded_agents.contains(&profile.id) {
             return false;
         }
+        let excluded_for_label = task.labels.iter()
+            .filter_map(|label| self.config.exclude.get(label))
+            .any(|agents| agents.contains(&profile.id));
+        if excluded_for_label {
+            return false;
+        }
         
         // Check cost constraint
         if let Some(max_cost) = constraints.max_cost {
@@ -197,7 +376,7 @@ impl Router {
         
         // Check quality floor
         if let Some(min_quality) = constraints.min_quality {
-            if profile.quality.success_rate() < min_quality {
+            if profile.quality.success_rate_for(&task.labels) < min_quality {
                 return false;
             }
         }
@@ -205,7 +384,7 @@ impl Router {
         true
     }
     
-    fn route_best(&self, task: &Task, candidates: &[AgentProfile], constraints: &RoutingConstraints) -> Result<RoutingDecision, String> {
+    fn route_best(&self, task: &Task, candidates: &[AgentProfile]) -> Result<RoutingDecision, String> {
         use crate::policy;
         
         let tokens = self.estimate_tokens(task);
@@ -219,13 +398,6 @@ impl Router {
             .filter(|(_, score)| *score > 0.0) // Filter out disqualified agents
             .collect();
         
-        // Boost preferred agents
-        for (profile, score) in &mut scored {
-            if constraints.preferred_agents.contains(&profile.id) {
-                *score *= 1.2; // 20% boost
-            }
-        }
-        
         scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
         
         if let Some((best, score)) = scored.first() {
@@ -239,6 +411,8 @@ impl Router {
                 estimated_cost: best.cost.estimate(tokens),
                 confidence: *score,
                 alternatives: scored.iter().skip(1).take(2).map(|(p, _)| p.id.clone()).collect(),
+                warnings: Vec::new(),
+                context_budget: None,
             })
         } else {
             Err("No candidates meet quality threshold for this task complexity".to_string())
@@ -261,51 +435,141 @@ impl Router {
                 estimated_cost: *cost,
                 confidence: 0.8,
                 alternatives: with_cost.iter().skip(1).take(2).map(|(p, _)| p.id.clone()).collect(),
+                warnings: Vec::new(),
+                context_budget: None,
             })
         } else {
             Err("No candidates".to_string())
         }
     }
     
-    fn route_fastest(&self, candidates: &[AgentProfile]) -> Result<RoutingDecision, String> {
-        // Sort by average duration (faster = better)
+    fn route_fastest(&self, task: &Task, candidates: &[AgentProfile]) -> Result<RoutingDecision, String> {
+        // Sort by average duration on this kind of task, plus the time to
+        // start the agent when it's been probed (faster = better)
+        let expected = |p: &AgentProfile| p.quality.avg_duration_for(&task.labels) + p.cold_start_secs();
         let mut sorted: Vec<_> = candidates.iter().collect();
         sorted.sort_by(|a, b| {
-            a.quality.avg_duration_secs
-                .partial_cmp(&b.quality.avg_duration_secs)
+            expected(a)
+                .partial_cmp(&expected(b))
                 .unwrap_or(std::cmp::Ordering::Equal)
         });
         
         if let Some(fastest) = sorted.first() {
             Ok(RoutingDecision {
                 agent_id: fastest.id.clone(),
-                reason: format!("Fastest (avg {:.0}s)", fastest.quality.avg_duration_secs),
+                reason: format!("Fastest (avg {:.0}s)", expected(fastest)),
                 estimated_cost: fastest.cost.estimate(10000),
                 confidence: 0.7,
                 alternatives: sorted.iter().skip(1).take(2).map(|p| p.id.clone()).collect(),
+                warnings: Vec::new(),
+                context_budget: None,
             })
         } else {
             Err("No candidates".to_string())
         }
     }
     
-    fn route_round_robin(&mut self, candidates: &[AgentProfile]) -> Result<RoutingDecision, String> {
-        if candidates.is_empty() {
-            return Err("No candidates".to_string());
+    fn route_round_robin(&mut self, task: &Task, candidates: &[AgentProfile]) -> Result<RoutingDecision, String> {
+        let mut open: Vec<_> = candidates.iter()
+            .map(|p| (p, self.load(p)))
+            .filter(|(_, load)| load.has_room())
+            .collect();
+        if open.is_empty() {
+            return Err("All agents are at their concurrency limit".to_string());
         }
+        open.sort_by(|a, b| a.0.id.cmp(&b.0.id));
         
-        self.last_robin_index = (self.last_robin_index + 1) % candidates.len();
-        let selected = &candidates[self.last_robin_index];
+        // The first agent after the last one picked, wrapping around
+        let next = self.last_robin.as_ref()
+            .and_then(|last| open.iter().position(|(p, _)| &p.id > last))
+            .unwrap_or(0);
+        let (selected, load) = open[next];
+        self.last_robin = Some(selected.id.clone());
         
         Ok(RoutingDecision {
             agent_id: selected.id.clone(),
-            reason: "Round-robin selection".to_string(),
-            estimated_cost: selected.cost.estimate(10000),
+            reason: format!("Round-robin selection ({}/{} claimed)", load.claimed, load.limit),
+            estimated_cost: selected.cost.estimate(self.estimate_tokens(task)),
             confidence: 0.6,
-            alternatives: vec![],
+            alternatives: open.iter().cycle().skip(next + 1).take(open.len() - 1).take(2).map(|(p, _)| p.id.clone()).collect(),
+            warnings: Vec::new(),
+            context_budget: None,
+        })
+    }
+    
+    fn route_least_loaded(&self, task: &Task, candidates: &[AgentProfile]) -> Result<RoutingDecision, String> {
+        let mut open: Vec<_> = candidates.iter()
+            .map(|p| (p, self.load(p)))
+            .filter(|(_, load)| load.has_room())
+            .collect();
+        // Lowest share in use, then most room left
+        open.sort_by(|a, b| {
+            a.1.utilization().partial_cmp(&b.1.utilization())
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then((b.1.limit - b.1.claimed).cmp(&(a.1.limit - a.1.claimed)))
+                .then(a.0.id.cmp(&b.0.id))
+        });
+        
+        let (selected, load) = open.first().ok_or("All agents are at their concurrency limit")?;
+        Ok(RoutingDecision {
+            agent_id: selected.id.clone(),
+            reason: format!("Least loaded ({}/{} claimed)", load.claimed, load.limit),
+            estimated_cost: selected.cost.estimate(self.estimate_tokens(task)),
+            confidence: 0.7,
+            alternatives: open.iter().skip(1).take(2).map(|(p, _)| p.id.clone()).collect(),
+            warnings: Vec::new(),
+            context_budget: None,
+        })
+    }
+    
+    fn route_learned(&mut self, task: &Task, candidates: &[AgentProfile], history: &[ExecutionRecord]) -> Result<RoutingDecision, String> {
+        let tokens = self.estimate_tokens(task);
+        
+        let mut arms: Vec<_> = candidates.iter()
+            .map(|p| Arm::observe(p, task, history))
+            .collect();
+        arms.sort_by(|a, b| b.mean().partial_cmp(&a.mean()).unwrap_or(std::cmp::Ordering::Equal));
+        
+        let explore = self.next_unit() < self.config.exploration_rate;
+        let chosen = if explore {
+            arms.iter().min_by_key(|a| a.trials).ok_or("No candidates")?
+        } else {
+            arms.first().ok_or("No candidates")?
+        };
+        
+        let kind = if task.labels.is_empty() { "all tasks".to_string() } else { format!("{:?}", task.labels) };
+        let reason = if explore {
+            format!("Exploring: least-tried on {} ({} runs)", kind, chosen.trials)
+        } else {
+            format!(
+                "Learned: {}/{} succeeded on {} (expected {:.0}%)",
+                chosen.successes, chosen.trials, kind, chosen.mean() * 100.0
+            )
+        };
+        
+        Ok(RoutingDecision {
+            agent_id: chosen.profile.id.clone(),
+            reason,
+            estimated_cost: chosen.profile.cost.estimate(tokens),
+            confidence: chosen.mean(),
+            alternatives: arms.iter()
+                .filter(|a| a.profile.id != chosen.profile.id)
+                .take(2)
+                .map(|a| a.profile.id.clone())
+                .collect(),
+            warnings: Vec::new(),
+            context_budget: None,
         })
     }
     
+    /// Uniform in [0, 1)
+    fn next_unit(&mut self) -> f64 {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        (self.rng >> 11) as f64 / (1u64 << 53) as f64
+    }
+    
     fn estimate_tokens(&self, task: &Task) -> u64 {
         // Rough estimate based on task content
         let title_tokens = task.title.len() as u64 * 2;
@@ -318,6 +582,171 @@ impl Router {
     }
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// Routing Simulation (dry-run of policy changes)
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// How a single task would be routed under the current and candidate configs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulatedAssignment {
+    pub task_id: String,
+    pub current_agent: Option<String>,
+    pub candidate_agent: Option<String>,
+    pub current_cost: f64,
+    pub candidate_cost: f64,
+    pub current_success: f64,
+    pub candidate_success: f64,
+}
+
+impl SimulatedAssignment {
+    /// Whether the candidate config routes this task differently
+    pub fn changed(&self) -> bool {
+        self.current_agent != self.candidate_agent
+    }
+}
+
+/// Aggregate result of replaying tasks through a candidate config
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RoutingSimulation {
+    /// Number of tasks replayed
+    pub tasks: usize,
+    /// Tasks whose assignment would change
+    pub reassigned: usize,
+    /// Total estimated cost under the current config
+    pub current_cost: f64,
+    /// Total estimated cost under the candidate config
+    pub candidate_cost: f64,
+    /// Mean predicted success rate under the current config
+    pub current_success: f64,
+    /// Mean predicted success rate under the candidate config
+    pub candidate_success: f64,
+    /// Task counts per agent under the current config
+    pub current_by_agent: HashMap<String, usize>,
+    /// Task counts per agent under the candidate config
+    pub candidate_by_agent: HashMap<String, usize>,
+    /// Per-task detail
+    pub assignments: Vec<SimulatedAssignment>,
+}
+
+impl RoutingSimulation {
+    /// Replay tasks through two profile sets with the same strategy
+    pub fn run(
+        tasks: &[Task],
+        current: &[AgentProfile],
+        candidate: &[AgentProfile],
+        strategy: RoutingStrategy,
+    ) -> Self {
+        let constraints = RoutingConstraints::default();
+        let mut current_router = Router::new();
+        let mut candidate_router = Router::new();
+        let mut sim = RoutingSimulation::default();
+        
+        for task in tasks {
+            let before = current_router.route_profiles(task, current.to_vec(), strategy, &constraints).ok();
+            let after = candidate_router.route_profiles(task, candidate.to_vec(), strategy, &constraints).ok();
+            
+            let success_for = |decision: &Option<RoutingDecision>, profiles: &[AgentProfile]| {
+                decision.as_ref()
+                    .and_then(|d| profiles.iter().find(|p| p.id == d.agent_id))
+                    .map(|p| predicted_success(p, task))
+                    .unwrap_or(0.0)
+            };
+            
+            let assignment = SimulatedAssignment {
+                task_id: task.id.clone(),
+                current_agent: before.as_ref().map(|d| d.agent_id.clone()),
+                candidate_agent: after.as_ref().map(|d| d.agent_id.clone()),
+                current_cost: before.as_ref().map(|d| d.estimated_cost).unwrap_or(0.0),
+                candidate_cost: after.as_ref().map(|d| d.estimated_cost).unwrap_or(0.0),
+                current_success: success_for(&before, current),
+                candidate_success: success_for(&after, candidate),
+            };
+            
+            if let Some(ref agent) = assignment.current_agent {
+                *sim.current_by_agent.entry(agent.clone()).or_insert(0) += 1;
+            }
+            if let Some(ref agent) = assignment.candidate_agent {
+                *sim.candidate_by_agent.entry(agent.clone()).or_insert(0) += 1;
+            }
+            if assignment.changed() {
+                sim.reassigned += 1;
+            }
+            sim.current_cost += assignment.current_cost;
+            sim.candidate_cost += assignment.candidate_cost;
+            sim.current_success += assignment.current_success;
+            sim.candidate_success += assignment.candidate_success;
+            sim.assignments.push(assignment);
+        }
+        
+        sim.tasks = tasks.len();
+        if sim.tasks > 0 {
+            sim.current_success /= sim.tasks as f64;
+            sim.candidate_success /= sim.tasks as f64;
+        }
+        sim
+    }
+    
+    /// Change in total estimated cost (candidate - current)
+    pub fn cost_delta(&self) -> f64 {
+        self.candidate_cost - self.current_cost
+    }
+    
+    /// Change in mean predicted success rate (candidate - current)
+    pub fn success_delta(&self) -> f64 {
+        self.candidate_success - self.current_success
+    }
+}
+
+/// Predicted success for an agent on a task: learned per-type quality for
+/// the task's labels when available, otherwise the overall success rate
+fn predicted_success(profile: &AgentProfile, task: &Task) -> f64 {
+    let by_type: Vec<f64> = task.labels.iter()
+        .filter_map(|l| profile.quality.by_type.get(l).map(|t| t.score as f64))
+        .collect();
+    
+    if by_type.is_empty() {
+        profile.quality.success_rate()
+    } else {
+        by_type.iter().sum::<f64>() / by_type.len() as f64
+    }
+}
+
+/// An agent's record on one kind of task, for the `Learned` strategy
+struct Arm<'a> {
+    profile: &'a AgentProfile,
+    /// Predicted success before any history
+    prior: f64,
+    trials: u32,
+    successes: u32,
+}
+
+impl<'a> Arm<'a> {
+    /// Executions by this agent on tasks sharing a label with `task` (any
+    /// execution, for unlabelled tasks)
+    fn observe(profile: &'a AgentProfile, task: &Task, history: &[ExecutionRecord]) -> Self {
+        let relevant = history.iter()
+            .filter(|r| r.agent_id == profile.id)
+            .filter(|r| task.labels.is_empty() || r.task_type.as_ref().is_some_and(|t| task.labels.contains(t)));
+        let (mut trials, mut successes) = (0, 0);
+        for record in relevant {
+            trials += 1;
+            successes += record.success as u32;
+        }
+        Self {
+            profile,
+            prior: predicted_success(profile, task).clamp(0.0, 1.0),
+            trials,
+            successes,
+        }
+    }
+    
+    /// Expected success: the prior, moved toward the observed rate as
+    /// executions accumulate
+    fn mean(&self) -> f64 {
+        (self.successes as f64 + PRIOR_WEIGHT * self.prior) / (self.trials as f64 + PRIOR_WEIGHT)
+    }
+}
+
 impl Default for Router {
     fn default() -> Self {
         Self::new()
@@ -328,8 +757,31 @@ impl Default for Router {
 mod tests {
     use super::*;
     use crate::agents::AgentRegistry;
+    use crate::work::TaskKind;
     use tempfile::tempdir;
     
+    /// A ready task with these labels
+    fn task(title: &str, labels: &[&str]) -> Task {
+        let now = chrono::Utc::now();
+        Task {
+            id: "lm-test".to_string(),
+            title: title.to_string(),
+            description: None,
+            status: crate::work::Status::Ready,
+            priority: Default::default(),
+            agent: None,
+            labels: labels.iter().map(|l| l.to_string()).collect(),
+            parent: None,
+            evidence: None,
+            actual_cost_usd: None,
+            repo: None,
+            kind: TaskKind::Task,
+            rollup: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+    
     #[test]
     fn test_routing_strategies() {
         let dir = tempdir().unwrap();
@@ -340,19 +792,8 @@ mod tests {
         let mut router = Router::new();
         
         let task = Task {
-            id: "test-1".to_string(),
-            title: "Plan authentication system".to_string(),
             description: Some("Design OAuth flow".to_string()),
-            status: crate::work::Status::Ready,
-            priority: Default::default(),
-            agent: None,
-            labels: vec!["planning".to_string(), "architecture".to_string()],
-            parent: None,
-            evidence: None,
-            actual_cost_usd: None,
-            repo: None,
-            created_at: chrono::Utc::now(),
-            updated_at: chrono::Utc::now(),
+            ..task("Plan authentication system", &["planning", "architecture"])
         };
         
         let constraints = RoutingConstraints::default();
@@ -371,4 +812,168 @@ mod tests {
         );
         assert!(cheapest.reason.contains("Cheapest"));
     }
+    
+    #[test]
+    fn test_simulation_reports_shift() {
+        let task = task("Fix typo", &["docs"]);
+        
+        let current = vec![AgentProfile::claude_code(), AgentProfile::gemini()];
+        // Candidate drops Claude entirely, so everything must move to Gemini
+        let candidate = vec![AgentProfile::gemini()];
+        
+        let sim = RoutingSimulation::run(&[task], &current, &candidate, RoutingStrategy::Cheapest);
+        assert_eq!(sim.tasks, 1);
+        assert_eq!(sim.candidate_by_agent.get("gemini"), Some(&1));
+        assert!(sim.cost_delta() <= 0.0);
+        
+        let unchanged = RoutingSimulation::run(&[], &current, &current, RoutingStrategy::Best);
+        assert_eq!(unchanged.reassigned, 0);
+    }
+    
+    #[test]
+    fn test_learned_routing_follows_history() {
+        let task = task("Update changelog", &["docs"]);
+        let mut profiles = vec![AgentProfile::claude_code(), AgentProfile::gemini(), AgentProfile::cursor()];
+        for profile in &mut profiles {
+            profile.available = true;
+        }
+        let run = |agent: &str, task_type: &str, success: bool| ExecutionRecord {
+            agent_id: agent.to_string(),
+            task_id: "lm-1".to_string(),
+            task_type: Some(task_type.to_string()),
+            success,
+            duration_secs: None,
+            tokens_used: None,
+            cost: None,
+            timestamp: None,
+        };
+        // Claude keeps failing docs tasks, Gemini keeps landing them;
+        // Claude's record on other work doesn't count
+        let mut history: Vec<_> = (0..10).map(|_| run("claude-code", "docs", false)).collect();
+        history.extend((0..10).map(|_| run("gemini", "docs", true)));
+        history.extend((0..10).map(|_| run("claude-code", "refactor", true)));
+        history.push(run("cursor", "docs", true));
+        
+        let mut router = Router::new();
+        router.set_config(RoutingConfig { exploration_rate: 0.0, ..Default::default() });
+        let constraints = RoutingConstraints::default();
+        let decision = router.route_candidates(&task, profiles.clone(), RoutingStrategy::Learned, &constraints, &history).unwrap();
+        assert_eq!(decision.agent_id, "gemini");
+        assert!(decision.reason.starts_with("Learned: 10/10"));
+        
+        // Exploring goes to the agent with the fewest runs on docs
+        router.set_config(RoutingConfig { exploration_rate: 1.0, ..Default::default() });
+        let decision = router.route_candidates(&task, profiles, RoutingStrategy::Learned, &constraints, &history).unwrap();
+        assert_eq!(decision.agent_id, "cursor");
+        assert!(decision.reason.starts_with("Exploring"));
+    }
+    
+    #[test]
+    fn test_per_type_quality_beats_overall() {
+        let task = task("Track down flaky login", &["debugging"]);
+        let agent = |id: &str| AgentProfile { id: id.to_string(), name: id.to_string(), available: true, ..AgentProfile::claude_code() };
+        
+        // The generalist is better overall, but keeps failing at debugging
+        let mut generalist = agent("generalist");
+        for _ in 0..18 {
+            generalist.quality.record_success("refactor", 600.0);
+        }
+        generalist.quality.record_failure("debugging");
+        generalist.quality.record_review(&["debugging".to_string()], false);
+        generalist.quality.record_success("debugging", 900.0);
+        let mut debugger = agent("debugger");
+        for _ in 0..6 {
+            debugger.quality.record_success("debugging", 300.0);
+        }
+        for _ in 0..4 {
+            debugger.quality.record_failure("refactor");
+        }
+        assert!(generalist.quality.success_rate() > debugger.quality.success_rate());
+        assert_eq!(generalist.quality.by_type["debugging"].runs(), 3);
+        assert!(debugger.quality.success_rate_for(&task.labels) > generalist.quality.success_rate_for(&task.labels));
+        assert_eq!(debugger.quality.avg_duration_for(&task.labels), 300.0);
+        
+        let mut router = Router::new();
+        let constraints = RoutingConstraints::default();
+        let profiles = vec![generalist, debugger];
+        for strategy in [RoutingStrategy::Best, RoutingStrategy::Fastest] {
+            let decision = router.route_candidates(&task, profiles.clone(), strategy, &constraints, &[]).unwrap();
+            assert_eq!(decision.agent_id, "debugger", "{:?}", strategy);
+        }
+        
+        // Other kinds of work still go on the overall record
+        let refactor = Task { labels: vec!["refactor".to_string()], ..task };
+        let decision = router.route_candidates(&refactor, profiles, RoutingStrategy::Best, &constraints, &[]).unwrap();
+        assert_eq!(decision.agent_id, "generalist");
+    }
+    
+    #[test]
+    fn test_load_aware_strategies_skip_full_agents() {
+        let task = task("Bump dependencies", &[]);
+        let mut profiles = vec![AgentProfile::claude_code(), AgentProfile::gemini(), AgentProfile::cursor()];
+        for profile in &mut profiles {
+            profile.available = true;
+        }
+        
+        let mut router = Router::new();
+        router.set_loads(HashMap::from([
+            ("claude-code".to_string(), AgentLoad { claimed: 2, limit: 5 }),
+            ("gemini".to_string(), AgentLoad { claimed: 3, limit: 3 }),
+            ("cursor".to_string(), AgentLoad { claimed: 1, limit: 2 }),
+        ]));
+        let constraints = RoutingConstraints::default();
+        let route = |router: &mut Router, strategy| {
+            router.route_profiles(&task, profiles.clone(), strategy, &constraints).unwrap().agent_id
+        };
+        
+        // 2/5 beats 1/2; gemini is full
+        assert_eq!(route(&mut router, RoutingStrategy::LeastLoaded), "claude-code");
+        
+        // Turns alternate between the agents with room
+        let turns: Vec<_> = (0..3).map(|_| route(&mut router, RoutingStrategy::RoundRobin)).collect();
+        assert_eq!(turns, vec!["claude-code", "cursor", "claude-code"]);
+        
+        router.set_loads(HashMap::from([
+            ("claude-code".to_string(), AgentLoad { claimed: 5, limit: 5 }),
+            ("gemini".to_string(), AgentLoad { claimed: 3, limit: 3 }),
+            ("cursor".to_string(), AgentLoad { claimed: 2, limit: 2 }),
+        ]));
+        assert!(router.route_profiles(&task, profiles.clone(), RoutingStrategy::LeastLoaded, &constraints).is_err());
+    }
+    
+    #[test]
+    fn test_affinity_exclusion_and_sticky_routing() {
+        let task = Task { parent: Some("lm-parent".to_string()), ..task("Polish the settings page", &["ui"]) };
+        let mut profiles = vec![AgentProfile::claude_code(), AgentProfile::gemini(), AgentProfile::cursor()];
+        for profile in &mut profiles {
+            profile.available = true;
+        }
+        
+        let mut router = Router::new();
+        router.set_config(RoutingConfig {
+            affinity: [("ui".to_string(), vec!["cursor".to_string()])].into(),
+            exclude: [("ui".to_string(), vec!["gemini".to_string()])].into(),
+            ..Default::default()
+        });
+        let mut route = |constraints: RoutingConstraints| {
+            router.route_profiles(&task, profiles.clone(), RoutingStrategy::Cheapest, &constraints).unwrap()
+        };
+        
+        // Gemini is cheapest but excluded for ui; cursor is pinned to ui
+        let pinned = route(RoutingConstraints::default());
+        assert_eq!(pinned.agent_id, "cursor");
+        assert!(pinned.reason.contains("preferred"));
+        
+        // Excluding the pinned agent falls back to the rest of the field
+        let fallback = route(RoutingConstraints { excluded_agents: vec!["cursor".to_string()], ..Default::default() });
+        assert_eq!(fallback.agent_id, "claude-code");
+        
+        // The parent's agent comes ahead of any preference
+        let sticky = route(RoutingConstraints { sticky_agent: Some("claude-code".to_string()), ..Default::default() });
+        assert_eq!(sticky.agent_id, "claude-code");
+        assert!(sticky.reason.contains("parent"));
+        
+        let constraints: RoutingConstraints = serde_json::from_str(r#"{"prefer_agents": ["gemini"], "exclude_agents": ["cursor"]}"#).unwrap();
+        assert_eq!((constraints.preferred_agents, constraints.excluded_agents), (vec!["gemini".to_string()], vec!["cursor".to_string()]));
+    }
 }
@@ -0,0 +1,237 @@
+//! Scheduled Tasks
+//!
+//! A schedule is a task waiting to be created: once at `due_at`, or every
+//! time a cron expression fires. The daemon checks schedules every minute
+//! and materializes due ones as ready tasks (`WorkStore::materialize_due`),
+//! so they show up in `ready()` like any other work.
+//!
+//! Cron expressions use the standard five fields (minute hour day-of-month
+//! month day-of-week) in UTC, with `*`, lists, ranges, steps, month and day
+//! names, and the `@hourly`/`@daily`/`@weekly`/`@monthly`/`@yearly` aliases.
+
+use chrono::{DateTime, Datelike, Duration, NaiveTime, TimeZone, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::work::Priority;
+
+/// When a schedule creates its task
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Recurrence {
+    /// Every time this cron expression fires
+    Cron(String),
+    /// Once, at this time
+    DueAt(DateTime<Utc>),
+}
+
+/// A task to create on a schedule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Schedule {
+    /// Unique identifier (e.g., "sch-a1b2")
+    pub id: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub priority: Priority,
+    pub labels: Vec<String>,
+    /// Parent for each created task
+    pub parent: Option<String>,
+    pub recurrence: Recurrence,
+    /// When the task is next due (None once a one-off has run)
+    pub next_run: Option<DateTime<Utc>>,
+    pub last_run: Option<DateTime<Utc>>,
+    /// Task created by the last run
+    pub last_task: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Recurrence {
+    /// First run at or after `now`; None for a one-off that's already been consumed
+    pub fn first_run(&self, now: DateTime<Utc>) -> Result<Option<DateTime<Utc>>, String> {
+        match self {
+            Recurrence::Cron(expr) => Ok(CronExpr::parse(expr)?.next_after(now - Duration::minutes(1))),
+            Recurrence::DueAt(at) => Ok(Some(*at)),
+        }
+    }
+    
+    /// Run after one at `now`
+    pub fn next_run(&self, now: DateTime<Utc>) -> Result<Option<DateTime<Utc>>, String> {
+        match self {
+            Recurrence::Cron(expr) => Ok(CronExpr::parse(expr)?.next_after(now)),
+            Recurrence::DueAt(_) => Ok(None),
+        }
+    }
+}
+
+/// A parsed five-field cron expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronExpr {
+    minutes: u64,
+    hours: u32,
+    days: u32,
+    months: u16,
+    weekdays: u8,
+    /// Whether day-of-month / day-of-week started with `*` (`*` or `*/n`);
+    /// cron ORs them only when both are restricted otherwise
+    any_day: bool,
+    any_weekday: bool,
+}
+
+const MONTHS: [&str; 12] = ["jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec"];
+const WEEKDAYS: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+impl CronExpr {
+    pub fn parse(expr: &str) -> Result<Self, String> {
+        let expanded = match expr.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            other => other,
+        };
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            return Err(format!("'{}': expected 5 fields (minute hour day month weekday)", expr));
+        };
+        
+        let weekdays = parse_field(weekday, 0, 7, &WEEKDAYS, 0).map_err(|e| format!("'{}': weekday {}", expr, e))?;
+        Ok(Self {
+            minutes: parse_field(minute, 0, 59, &[], 0).map_err(|e| format!("'{}': minute {}", expr, e))?,
+            hours: parse_field(hour, 0, 23, &[], 0).map_err(|e| format!("'{}': hour {}", expr, e))? as u32,
+            days: parse_field(day, 1, 31, &[], 0).map_err(|e| format!("'{}': day {}", expr, e))? as u32,
+            months: parse_field(month, 1, 12, &MONTHS, 1).map_err(|e| format!("'{}': month {}", expr, e))? as u16,
+            // 7 is Sunday too
+            weekdays: ((weekdays | (weekdays >> 7)) & 0x7f) as u8,
+            any_day: day.starts_with('*'),
+            any_weekday: weekday.starts_with('*'),
+        })
+    }
+    
+    /// First time strictly after `after` (to the minute) that the expression
+    /// fires, or None if it never does (e.g. February 30th)
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let start = after.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let mut date = start.date_naive();
+        
+        // Eight years covers every weekday/leap-day combination
+        for _ in 0..366 * 8 {
+            if self.matches_day(date) {
+                let from = if date == start.date_naive() { (start.hour(), start.minute()) } else { (0, 0) };
+                for hour in from.0..24 {
+                    if self.hours & (1 << hour) == 0 {
+                        continue;
+                    }
+                    let first_minute = if hour == from.0 { from.1 } else { 0 };
+                    if let Some(minute) = (first_minute..60).find(|m| self.minutes & (1 << m) != 0) {
+                        let time = NaiveTime::from_hms_opt(hour, minute, 0)?;
+                        return Some(Utc.from_utc_datetime(&date.and_time(time)));
+                    }
+                }
+            }
+            date = date.succ_opt()?;
+        }
+        None
+    }
+    
+    fn matches_day(&self, date: chrono::NaiveDate) -> bool {
+        if self.months & (1 << date.month()) == 0 {
+            return false;
+        }
+        let day = self.days & (1 << date.day()) != 0;
+        let weekday = self.weekdays & (1 << date.weekday().num_days_from_sunday()) != 0;
+        match (self.any_day, self.any_weekday) {
+            (false, false) => day || weekday,
+            _ => day && weekday,
+        }
+    }
+}
+
+/// Parse one field into a bitmask; `names` map to `first_name`, `first_name + 1`, ...
+fn parse_field(field: &str, min: u32, max: u32, names: &[&str], first_name: u32) -> Result<u64, String> {
+    let value = |s: &str| -> Result<u32, String> {
+        let lower = s.to_ascii_lowercase();
+        let n = match names.iter().position(|name| *name == lower) {
+            Some(i) => i as u32 + first_name,
+            None => s.parse().map_err(|_| format!("'{}' is not a number", s))?,
+        };
+        if n < min || n > max {
+            return Err(format!("{} is out of range {}-{}", n, min, max));
+        }
+        Ok(n)
+    };
+    
+    let mut mask = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, step.parse::<u32>().map_err(|_| format!("bad step '{}'", step))?),
+            None => (part, 1),
+        };
+        if step == 0 {
+            return Err("step can't be 0".to_string());
+        }
+        let (lo, hi) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((lo, hi)) => (value(lo)?, value(hi)?),
+                // "5/15" means from 5 to the end in steps of 15
+                None if part.contains('/') => (value(range)?, max),
+                None => {
+                    let n = value(range)?;
+                    (n, n)
+                }
+            },
+        };
+        if lo > hi {
+            return Err(format!("range {}-{} is backwards", lo, hi));
+        }
+        for n in (lo..=hi).step_by(step as usize) {
+            mask |= 1 << n;
+        }
+    }
+    Ok(mask)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn at(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+    
+    #[test]
+    fn test_cron_next_after() {
+        // Weekdays at 09:30
+        let cron = CronExpr::parse("30 9 * * mon-fri").unwrap();
+        assert_eq!(cron.next_after(at("2025-01-03T09:29:59Z")), Some(at("2025-01-03T09:30:00Z")));
+        // Friday 09:30 has passed; skips the weekend
+        assert_eq!(cron.next_after(at("2025-01-03T09:30:00Z")), Some(at("2025-01-06T09:30:00Z")));
+        
+        let cron = CronExpr::parse("*/15 * * * *").unwrap();
+        assert_eq!(cron.next_after(at("2025-01-01T10:07:00Z")), Some(at("2025-01-01T10:15:00Z")));
+        assert_eq!(cron.next_after(at("2025-01-01T23:50:00Z")), Some(at("2025-01-02T00:00:00Z")));
+        
+        // Day-of-month and day-of-week both set: either matches
+        let cron = CronExpr::parse("0 0 13 * 5").unwrap();
+        assert_eq!(cron.next_after(at("2025-06-01T00:00:00Z")), Some(at("2025-06-06T00:00:00Z")));
+        
+        assert_eq!(
+            CronExpr::parse("@yearly").unwrap().next_after(at("2025-03-01T00:00:00Z")),
+            Some(at("2026-01-01T00:00:00Z"))
+        );
+        assert_eq!(CronExpr::parse("0 0 29 feb *").unwrap().next_after(at("2025-01-01T00:00:00Z")), Some(at("2028-02-29T00:00:00Z")));
+        assert_eq!(CronExpr::parse("0 0 30 2 *").unwrap().next_after(at("2025-01-01T00:00:00Z")), None);
+        assert_eq!(CronExpr::parse("0 0 * * 7").unwrap(), CronExpr::parse("0 0 * * sun").unwrap());
+        
+        // Stepped days, and a `*/n` day-of-month only narrows the weekday
+        // rather than adding days, as in Vixie cron
+        let cron = CronExpr::parse("0 0 */2 * *").unwrap();
+        assert_eq!(cron.next_after(at("2025-01-01T12:00:00Z")), Some(at("2025-01-03T00:00:00Z")));
+        let cron = CronExpr::parse("0 0 */2 * mon").unwrap();
+        assert_eq!(cron.next_after(at("2025-01-01T12:00:00Z")), Some(at("2025-01-13T00:00:00Z")));
+        
+        assert!(CronExpr::parse("* * *").is_err());
+        assert!(CronExpr::parse("60 * * * *").is_err());
+        assert!(CronExpr::parse("*/0 * * * *").is_err());
+    }
+}
@@ -0,0 +1,183 @@
+//! GitHub Issues backend
+//!
+//! Talks to the REST API through the `gh` CLI, which already handles
+//! authentication (`gh auth login` or `GH_TOKEN`) and pagination, so Loom
+//! doesn't need an HTTP client or its own token handling.
+//!
+//! Mapping: title ↔ title, description ↔ body, labels ↔ label names,
+//! done/cancelled ↔ closed, claiming agent ↔ first assignee.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value};
+
+use super::tracker::{IssueFields, IssueState, IssueTracker, RemoteIssue};
+use super::SyncError;
+
+/// Issues in one GitHub repository
+pub struct GitHubTracker {
+    /// `owner/name`
+    repo: String,
+}
+
+impl GitHubTracker {
+    pub fn new(repo: impl Into<String>) -> Result<Self, SyncError> {
+        let repo = repo.into();
+        if repo.split('/').filter(|s| !s.is_empty()).count() != 2 {
+            return Err(SyncError::Tracker(format!("GitHub repo must be owner/name, got '{}'", repo)));
+        }
+        Ok(Self { repo })
+    }
+    
+    fn gh(&self, args: &[&str], input: Option<&Value>) -> Result<String, SyncError> {
+        let mut child = Command::new("gh")
+            .arg("api")
+            .args(["-H", "Accept: application/vnd.github+json"])
+            .args(args)
+            .args(if input.is_some() { &["--input", "-"][..] } else { &[] })
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| SyncError::Tracker(format!("Failed to run gh (is the GitHub CLI installed?): {}", e)))?;
+        
+        if let (Some(mut stdin), Some(input)) = (child.stdin.take(), input) {
+            stdin.write_all(serde_json::to_string(input)?.as_bytes())?;
+        }
+        
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(SyncError::Tracker(format!("gh api {}: {}", args.join(" "), stderr.trim())));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+}
+
+impl IssueTracker for GitHubTracker {
+    fn name(&self) -> &str {
+        "github"
+    }
+    
+    fn list_issues(&self) -> Result<Vec<RemoteIssue>, SyncError> {
+        let path = format!("repos/{}/issues?state=all&per_page=100", self.repo);
+        let output = self.gh(&["--paginate", &path, "--jq", ".[]"], None)?;
+        
+        let mut issues = Vec::new();
+        for line in output.lines().filter(|l| !l.trim().is_empty()) {
+            let value: Value = serde_json::from_str(line)?;
+            // The issues endpoint also returns pull requests
+            if value.get("pull_request").is_some() {
+                continue;
+            }
+            issues.push(parse_issue(&value)?);
+        }
+        Ok(issues)
+    }
+    
+    fn create_issue(&self, fields: &IssueFields) -> Result<RemoteIssue, SyncError> {
+        let path = format!("repos/{}/issues", self.repo);
+        let created = parse_issue(&serde_json::from_str(&self.gh(&["-X", "POST", &path], Some(&payload(fields)))?)?)?;
+        
+        // Issues are always created open
+        if fields.state == IssueState::Closed {
+            return self.update_issue(&created.id, fields);
+        }
+        Ok(created)
+    }
+    
+    fn update_issue(&self, id: &str, fields: &IssueFields) -> Result<RemoteIssue, SyncError> {
+        let path = format!("repos/{}/issues/{}", self.repo, id);
+        let mut body = payload(fields);
+        body["state"] = json!(match fields.state {
+            IssueState::Open => "open",
+            IssueState::Closed => "closed",
+        });
+        parse_issue(&serde_json::from_str(&self.gh(&["-X", "PATCH", &path], Some(&body))?)?)
+    }
+    
+    fn add_comment(&self, id: &str, body: &str) -> Result<(), SyncError> {
+        let path = format!("repos/{}/issues/{}/comments", self.repo, id);
+        self.gh(&["-X", "POST", &path], Some(&json!({ "body": body })))?;
+        Ok(())
+    }
+}
+
+/// Request body for creating or updating an issue
+fn payload(fields: &IssueFields) -> Value {
+    json!({
+        "title": fields.title,
+        "body": fields.body.clone().unwrap_or_default(),
+        "labels": fields.labels,
+        "assignees": fields.assignee.iter().collect::<Vec<_>>(),
+    })
+}
+
+/// Issue from a REST API response
+fn parse_issue(value: &Value) -> Result<RemoteIssue, SyncError> {
+    let invalid = || SyncError::Tracker(format!("Unexpected issue JSON: {}", value));
+    let number = value.get("number").and_then(|n| n.as_u64()).ok_or_else(invalid)?;
+    let labels = value.get("labels").and_then(|l| l.as_array()).into_iter().flatten()
+        .filter_map(|l| l.get("name").and_then(|n| n.as_str()).map(String::from))
+        .collect();
+    let state = match value.get("state").and_then(|s| s.as_str()) {
+        Some("closed") => IssueState::Closed,
+        _ => IssueState::Open,
+    };
+    let assignee = value.get("assignees").and_then(|a| a.as_array())
+        .and_then(|a| a.first())
+        .or_else(|| value.get("assignee").filter(|a| !a.is_null()))
+        .and_then(|a| a.get("login"))
+        .and_then(|l| l.as_str())
+        .map(String::from);
+    let updated_at = value.get("updated_at").and_then(|u| u.as_str())
+        .and_then(|u| DateTime::parse_from_rfc3339(u).ok())
+        .map(|d| d.with_timezone(&Utc))
+        .unwrap_or_else(Utc::now);
+    
+    Ok(RemoteIssue {
+        id: number.to_string(),
+        url: value.get("html_url").and_then(|u| u.as_str()).unwrap_or_default().to_string(),
+        fields: IssueFields::new(
+            value.get("title").and_then(|t| t.as_str()).unwrap_or_default(),
+            value.get("body").and_then(|b| b.as_str()).map(String::from),
+            labels,
+            state,
+            assignee,
+        ),
+        updated_at,
+        parent: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_parse_issue_and_payload() {
+        let issue = parse_issue(&json!({
+            "number": 42,
+            "html_url": "https://github.com/acme/app/issues/42",
+            "title": "Fix login",
+            "body": "",
+            "state": "closed",
+            "labels": [{ "name": "bug" }, { "name": "auth" }],
+            "assignees": [{ "login": "octocat" }],
+            "updated_at": "2024-05-01T12:00:00Z"
+        })).unwrap();
+        
+        assert_eq!(issue.id, "42");
+        assert_eq!(issue.fields.labels, vec!["auth", "bug"]);
+        assert_eq!(issue.fields.body, None);
+        assert_eq!(issue.fields.state, IssueState::Closed);
+        assert_eq!(issue.fields.assignee.as_deref(), Some("octocat"));
+        
+        let body = payload(&issue.fields);
+        assert_eq!(body["assignees"], json!(["octocat"]));
+        assert_eq!(body["labels"], json!(["auth", "bug"]));
+        
+        assert!(GitHubTracker::new("not-a-repo").is_err());
+    }
+}
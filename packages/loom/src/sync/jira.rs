@@ -0,0 +1,355 @@
+//! Jira backend
+//!
+//! Talks to the Jira REST API (v2, so descriptions stay plain text). Jira
+//! Cloud authenticates with an account email and API token;
+//! Server and Data Center with a personal access token (no email).
+//!
+//! Mapping: title ↔ summary, description ↔ description, labels ↔ labels,
+//! done/cancelled ↔ a status in the "Done" category (moved by transition),
+//! claiming agent ↔ assignee (account ID on Cloud, user name on Server),
+//! and priority ↔ priority (by name). Epics come in as parent tasks with
+//! their stories under them.
+
+use std::collections::BTreeMap;
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value};
+
+use super::tracker::{IssueFields, IssueState, IssueTracker, RemoteIssue};
+use super::{http, SyncError};
+use crate::work::Priority;
+
+/// Issue fields requested in every query
+const FIELDS: [&str; 8] = ["summary", "description", "labels", "status", "assignee", "priority", "parent", "updated"];
+
+/// Issues in one Jira project
+pub struct JiraTracker {
+    /// Site URL, e.g. `https://acme.atlassian.net`
+    base_url: String,
+    /// Project key (e.g. "ENG")
+    project: String,
+    /// Extra JQL narrowing which issues sync
+    jql: Option<String>,
+    /// Type of issues created from tasks
+    issue_type: String,
+    /// Jira priority name → Priority, checked before the defaults
+    priorities: BTreeMap<String, Priority>,
+    /// Cloud (email + API token) rather than Server/Data Center (PAT)
+    cloud: bool,
+    /// `Authorization` header value
+    auth: String,
+}
+
+impl JiraTracker {
+    /// `email` is set for Jira Cloud (basic auth with an API token) and
+    /// unset for Server/Data Center (bearer personal access token)
+    pub fn new(
+        base_url: impl Into<String>,
+        project: impl Into<String>,
+        email: Option<&str>,
+        token: &str,
+    ) -> Result<Self, SyncError> {
+        let base_url = base_url.into().trim_end_matches('/').to_string();
+        let project = project.into();
+        if !base_url.starts_with("http") {
+            return Err(SyncError::Tracker(format!("Jira URL must be http(s)://..., got '{}'", base_url)));
+        }
+        if project.trim().is_empty() || token.trim().is_empty() {
+            return Err(SyncError::Tracker("Jira project and token are required".to_string()));
+        }
+        
+        let auth = match email {
+            Some(email) => crate::net::basic_auth(email, token),
+            None => format!("Bearer {}", token),
+        };
+        Ok(Self {
+            base_url,
+            project,
+            jql: None,
+            issue_type: "Task".to_string(),
+            priorities: BTreeMap::new(),
+            cloud: email.is_some(),
+            auth,
+        })
+    }
+    
+    pub fn with_jql(mut self, jql: Option<String>) -> Self {
+        self.jql = jql.filter(|j| !j.trim().is_empty());
+        self
+    }
+    
+    pub fn with_issue_type(mut self, issue_type: impl Into<String>) -> Self {
+        self.issue_type = issue_type.into();
+        self
+    }
+    
+    pub fn with_priorities(mut self, priorities: BTreeMap<String, Priority>) -> Self {
+        self.priorities = priorities;
+        self
+    }
+    
+    /// Call the REST API and return the response JSON (null when empty)
+    fn request(&self, method: &str, path: &str, body: Option<&Value>) -> Result<Value, SyncError> {
+        let url = format!("{}/rest/api/2/{}", self.base_url, path);
+        let headers = [("Authorization", self.auth.clone()), ("Accept", "application/json".to_string())];
+        let (status, output) = http(method, &url, &headers, body)?;
+        
+        if status >= 400 {
+            return Err(SyncError::Tracker(format!("Jira {} {} ({}): {}", method, path, status, error_message(&output))));
+        }
+        if output.trim().is_empty() {
+            return Ok(Value::Null);
+        }
+        serde_json::from_str(&output)
+            .map_err(|_| SyncError::Tracker(format!("Unexpected Jira response: {}", output.trim())))
+    }
+    
+    fn get_issue(&self, key: &str) -> Result<RemoteIssue, SyncError> {
+        let issue = self.request("GET", &format!("issue/{}?fields={}", key, FIELDS.join(",")), None)?;
+        self.parse_issue(&issue)
+    }
+    
+    /// Move an issue into the Done category, or back out of it
+    fn transition(&self, key: &str, state: IssueState) -> Result<(), SyncError> {
+        let response = self.request("GET", &format!("issue/{}/transitions", key), None)?;
+        let transitions = response.get("transitions").and_then(|t| t.as_array()).cloned().unwrap_or_default();
+        let wanted: &[&str] = match state {
+            IssueState::Closed => &["done"],
+            IssueState::Open => &["new", "indeterminate"],
+        };
+        
+        let transition = wanted.iter()
+            .find_map(|category| {
+                transitions.iter().find(|t| t.pointer("/to/statusCategory/key").and_then(|k| k.as_str()) == Some(category))
+            })
+            .and_then(|t| t.get("id"))
+            .ok_or_else(|| SyncError::Tracker(format!("No transition moves {} to {:?}", key, state)))?;
+        self.request("POST", &format!("issue/{}/transitions", key), Some(&json!({ "transition": { "id": transition } })))?;
+        Ok(())
+    }
+    
+    /// Jira priority name for a Priority
+    fn priority_name(&self, priority: Priority) -> String {
+        self.priorities.iter()
+            .find(|(_, p)| **p == priority)
+            .map(|(name, _)| name.clone())
+            .unwrap_or_else(|| match priority {
+                Priority::Critical => "Highest",
+                Priority::High => "High",
+                Priority::Normal => "Medium",
+                Priority::Low => "Low",
+            }.to_string())
+    }
+    
+    /// Priority for a Jira priority name (None if it isn't mapped)
+    fn loom_priority(&self, name: &str) -> Option<Priority> {
+        if let Some(priority) = self.priorities.get(name) {
+            return Some(*priority);
+        }
+        match name.to_ascii_lowercase().as_str() {
+            "highest" | "blocker" | "critical" => Some(Priority::Critical),
+            "high" | "major" => Some(Priority::High),
+            "medium" | "normal" => Some(Priority::Normal),
+            "low" | "lowest" | "minor" | "trivial" => Some(Priority::Low),
+            _ => None,
+        }
+    }
+    
+    /// `fields` for creating or updating an issue
+    fn payload(&self, fields: &IssueFields) -> Value {
+        let mut payload = json!({
+            "summary": fields.title,
+            "description": fields.body,
+            "labels": fields.labels,
+            "assignee": fields.assignee.as_ref().map(|id| match self.cloud {
+                true => json!({ "accountId": id }),
+                false => json!({ "name": id }),
+            }),
+        });
+        if let Some(priority) = fields.priority {
+            payload["priority"] = json!({ "name": self.priority_name(priority) });
+        }
+        payload
+    }
+    
+    /// Issue from a REST API response
+    fn parse_issue(&self, value: &Value) -> Result<RemoteIssue, SyncError> {
+        let invalid = || SyncError::Tracker(format!("Unexpected Jira issue JSON: {}", value));
+        let key = value.get("key").and_then(|k| k.as_str()).ok_or_else(invalid)?;
+        let fields = value.get("fields").ok_or_else(invalid)?;
+        
+        let labels = fields.get("labels").and_then(|l| l.as_array()).into_iter().flatten()
+            .filter_map(|l| l.as_str().map(String::from))
+            .collect();
+        let state = match fields.pointer("/status/statusCategory/key").and_then(|k| k.as_str()) {
+            Some("done") => IssueState::Closed,
+            _ => IssueState::Open,
+        };
+        // Cloud identifies users by account ID, Server by name
+        let assignee = fields.get("assignee")
+            .and_then(|a| a.get("accountId").or_else(|| a.get("name")))
+            .and_then(|a| a.as_str())
+            .map(String::from);
+        let priority = fields.pointer("/priority/name").and_then(|p| p.as_str()).and_then(|p| self.loom_priority(p));
+        // Jira timestamps look like 2024-05-01T12:00:00.000+0000
+        let updated_at = fields.get("updated").and_then(|u| u.as_str())
+            .and_then(|u| DateTime::parse_from_str(u, "%Y-%m-%dT%H:%M:%S%.f%z").ok())
+            .map(|d| d.with_timezone(&Utc))
+            .unwrap_or_else(Utc::now);
+        
+        Ok(RemoteIssue {
+            id: key.to_string(),
+            url: format!("{}/browse/{}", self.base_url, key),
+            fields: IssueFields::new(
+                fields.get("summary").and_then(|s| s.as_str()).unwrap_or_default(),
+                fields.get("description").and_then(|d| d.as_str()).map(String::from),
+                labels,
+                state,
+                assignee,
+            ).with_priority(priority),
+            updated_at,
+            parent: fields.pointer("/parent/key").and_then(|k| k.as_str()).map(String::from),
+        })
+    }
+}
+
+impl IssueTracker for JiraTracker {
+    fn name(&self) -> &str {
+        "jira"
+    }
+    
+    fn syncs_priority(&self) -> bool {
+        true
+    }
+    
+    fn list_issues(&self) -> Result<Vec<RemoteIssue>, SyncError> {
+        let mut jql = format!("project = \"{}\"", self.project);
+        if let Some(extra) = &self.jql {
+            jql.push_str(&format!(" AND ({})", extra));
+        }
+        jql.push_str(" ORDER BY key");
+        
+        // Cloud pages with a token; Server with an offset
+        let mut issues = Vec::new();
+        let mut next_page: Option<String> = None;
+        loop {
+            let mut body = json!({ "jql": jql, "maxResults": 100, "fields": FIELDS });
+            if self.cloud {
+                if let Some(token) = &next_page {
+                    body["nextPageToken"] = json!(token);
+                }
+            } else {
+                body["startAt"] = json!(issues.len());
+            }
+            let page = self.request("POST", if self.cloud { "search/jql" } else { "search" }, Some(&body))?;
+            
+            let batch = page.get("issues").and_then(|i| i.as_array()).cloned().unwrap_or_default();
+            for issue in &batch {
+                issues.push(self.parse_issue(issue)?);
+            }
+            
+            let more = if self.cloud {
+                next_page = page.get("nextPageToken").and_then(|t| t.as_str()).map(String::from);
+                next_page.is_some()
+            } else {
+                (issues.len() as u64) < page.get("total").and_then(|t| t.as_u64()).unwrap_or(0)
+            };
+            if batch.is_empty() || !more {
+                break;
+            }
+        }
+        Ok(issues)
+    }
+    
+    fn create_issue(&self, fields: &IssueFields) -> Result<RemoteIssue, SyncError> {
+        let mut payload = self.payload(fields);
+        payload["project"] = json!({ "key": self.project });
+        payload["issuetype"] = json!({ "name": self.issue_type });
+        
+        let created = self.request("POST", "issue", Some(&json!({ "fields": payload })))?;
+        let key = created.get("key").and_then(|k| k.as_str())
+            .ok_or_else(|| SyncError::Tracker(format!("Jira didn't return an issue key: {}", created)))?;
+        
+        // Issues are created in the workflow's initial status
+        if fields.state == IssueState::Closed {
+            self.transition(key, IssueState::Closed)?;
+        }
+        self.get_issue(key)
+    }
+    
+    fn update_issue(&self, id: &str, fields: &IssueFields) -> Result<RemoteIssue, SyncError> {
+        self.request("PUT", &format!("issue/{}", id), Some(&json!({ "fields": self.payload(fields) })))?;
+        
+        let updated = self.get_issue(id)?;
+        if updated.fields.state == fields.state {
+            return Ok(updated);
+        }
+        self.transition(id, fields.state)?;
+        self.get_issue(id)
+    }
+    
+    fn add_comment(&self, id: &str, body: &str) -> Result<(), SyncError> {
+        self.request("POST", &format!("issue/{}/comment", id), Some(&json!({ "body": body })))?;
+        Ok(())
+    }
+}
+
+/// Error text from a Jira error response (`errorMessages` and `errors`)
+fn error_message(output: &str) -> String {
+    let Ok(value) = serde_json::from_str::<Value>(output) else {
+        return output.trim().to_string();
+    };
+    let mut messages: Vec<String> = value.get("errorMessages").and_then(|m| m.as_array()).into_iter().flatten()
+        .filter_map(|m| m.as_str().map(String::from))
+        .collect();
+    if let Some(errors) = value.get("errors").and_then(|e| e.as_object()) {
+        messages.extend(errors.iter().map(|(field, e)| format!("{}: {}", field, e.as_str().unwrap_or_default())));
+    }
+    if messages.is_empty() {
+        output.trim().to_string()
+    } else {
+        messages.join("; ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_parse_issue_and_priorities() {
+        let tracker = JiraTracker::new("https://acme.atlassian.net/", "ENG", Some("sam@acme.dev"), "token")
+            .unwrap()
+            .with_priorities(BTreeMap::from([("P0".to_string(), Priority::Critical)]));
+        
+        let issue = tracker.parse_issue(&json!({
+            "key": "ENG-12",
+            "fields": {
+                "summary": "Checkout flow",
+                "description": "Story body",
+                "labels": ["payments"],
+                "status": { "name": "In Review", "statusCategory": { "key": "indeterminate" } },
+                "assignee": { "accountId": "5b10ac8d82e05b22cc7d4ef5" },
+                "priority": { "name": "P0" },
+                "parent": { "key": "ENG-1" },
+                "updated": "2024-05-01T12:00:00.000+0200"
+            }
+        })).unwrap();
+        
+        assert_eq!(issue.url, "https://acme.atlassian.net/browse/ENG-12");
+        assert_eq!(issue.fields.state, IssueState::Open);
+        assert_eq!(issue.fields.priority, Some(Priority::Critical));
+        assert_eq!(issue.fields.assignee.as_deref(), Some("5b10ac8d82e05b22cc7d4ef5"));
+        assert_eq!(issue.parent.as_deref(), Some("ENG-1"));
+        assert_eq!(issue.updated_at.to_rfc3339(), "2024-05-01T10:00:00+00:00");
+        
+        // Configured names win; the default scheme covers the rest
+        assert_eq!(tracker.loom_priority("Blocker"), Some(Priority::Critical));
+        assert_eq!(tracker.loom_priority("Whenever"), None);
+        let payload = tracker.payload(&issue.fields.clone().with_priority(Some(Priority::Normal)));
+        assert_eq!(payload["priority"], json!({ "name": "Medium" }));
+        assert_eq!(tracker.payload(&issue.fields)["priority"], json!({ "name": "P0" }));
+        
+        assert_eq!(error_message(r#"{"errorMessages":[],"errors":{"summary":"required"}}"#), "summary: required");
+        assert!(JiraTracker::new("acme.atlassian.net", "ENG", None, "token").is_err());
+    }
+}
@@ -0,0 +1,333 @@
+//! Linear backend
+//!
+//! Talks to Linear's GraphQL API, authenticating with a personal API key.
+//!
+//! Mapping: title ↔ title, description ↔ description, labels ↔ label names
+//! (missing labels are created on the team), done/cancelled ↔ a "completed"
+//! workflow state, claiming agent ↔ assignee (by email). Issues are
+//! identified by their key (e.g. `ENG-123`).
+
+use std::cell::{OnceCell, RefCell};
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value};
+
+use super::tracker::{IssueFields, IssueState, IssueTracker, RemoteIssue};
+use super::{http, SyncError};
+
+const LINEAR_API: &str = "https://api.linear.app/graphql";
+
+/// Issue fields requested in every query
+const ISSUE_FIELDS: &str =
+    "identifier url title description updatedAt state { type } labels { nodes { name } } assignee { email }";
+
+/// Issues in one Linear team
+pub struct LinearSync {
+    /// Team key (e.g. "ENG")
+    team: String,
+    api_key: String,
+    /// Team, workflow states, labels, and users; fetched on first use
+    workspace: OnceCell<Workspace>,
+}
+
+/// What payloads need to turn names into Linear IDs
+#[derive(Debug)]
+struct Workspace {
+    team_id: String,
+    /// State new and reopened issues are put in
+    open_state: String,
+    /// State closed issues are put in
+    done_state: String,
+    /// Label name → ID (team and workspace labels)
+    labels: RefCell<HashMap<String, String>>,
+    /// Email → user ID
+    users: HashMap<String, String>,
+}
+
+impl LinearSync {
+    pub fn new(team: impl Into<String>, api_key: impl Into<String>) -> Result<Self, SyncError> {
+        let (team, api_key) = (team.into(), api_key.into());
+        if team.trim().is_empty() {
+            return Err(SyncError::Tracker("Linear team key is empty".to_string()));
+        }
+        if api_key.trim().is_empty() {
+            return Err(SyncError::Tracker("Linear API key is empty".to_string()));
+        }
+        Ok(Self { team, api_key, workspace: OnceCell::new() })
+    }
+    
+    /// Run a GraphQL request and return its `data`
+    fn graphql(&self, query: &str, variables: Value) -> Result<Value, SyncError> {
+        let body = json!({ "query": query, "variables": variables });
+        let auth = ("Authorization", self.api_key.clone());
+        let (status, output) = http("POST", LINEAR_API, &[auth], Some(&body))?;
+        
+        let response: Value = serde_json::from_str(&output)
+            .map_err(|_| SyncError::Tracker(format!("Unexpected Linear response ({}): {}", status, output.trim())))?;
+        if let Some(errors) = response.get("errors").and_then(|e| e.as_array()).filter(|e| !e.is_empty()) {
+            let messages: Vec<&str> = errors.iter().filter_map(|e| e.get("message").and_then(|m| m.as_str())).collect();
+            return Err(SyncError::Tracker(format!("Linear: {}", messages.join("; "))));
+        }
+        if status >= 400 {
+            return Err(SyncError::Tracker(format!("Linear request failed ({}): {}", status, output.trim())));
+        }
+        Ok(response.get("data").cloned().unwrap_or(Value::Null))
+    }
+    
+    fn workspace(&self) -> Result<&Workspace, SyncError> {
+        if let Some(workspace) = self.workspace.get() {
+            return Ok(workspace);
+        }
+        
+        let data = self.graphql(
+            "query($key: String!) {
+                teams(filter: { key: { eq: $key } }) { nodes { id states { nodes { id type position } } } }
+                issueLabels(first: 250) { nodes { id name team { id } } }
+                users(first: 250) { nodes { id email } }
+            }",
+            json!({ "key": self.team }),
+        )?;
+        let workspace = parse_workspace(&data)
+            .ok_or_else(|| SyncError::Tracker(format!("Linear team '{}' not found or has no workflow states", self.team)))?;
+        Ok(self.workspace.get_or_init(|| workspace))
+    }
+    
+    /// Label IDs for names, creating labels the team doesn't have yet
+    fn label_ids(&self, names: &[String]) -> Result<Vec<String>, SyncError> {
+        let workspace = self.workspace()?;
+        let mut ids = Vec::new();
+        for name in names {
+            let known = workspace.labels.borrow().get(name).cloned();
+            let id = match known {
+                Some(id) => id,
+                None => {
+                    let data = self.graphql(
+                        "mutation($input: IssueLabelCreateInput!) { issueLabelCreate(input: $input) { issueLabel { id } } }",
+                        json!({ "input": { "name": name, "teamId": workspace.team_id } }),
+                    )?;
+                    let id = data.pointer("/issueLabelCreate/issueLabel/id").and_then(|i| i.as_str())
+                        .ok_or_else(|| SyncError::Tracker(format!("Linear didn't create label '{}'", name)))?
+                        .to_string();
+                    workspace.labels.borrow_mut().insert(name.clone(), id.clone());
+                    id
+                }
+            };
+            ids.push(id);
+        }
+        Ok(ids)
+    }
+    
+    /// Mutation input for an issue's fields
+    fn input(&self, fields: &IssueFields) -> Result<Value, SyncError> {
+        let workspace = self.workspace()?;
+        Ok(json!({
+            "title": fields.title,
+            "description": fields.body.clone().unwrap_or_default(),
+            "labelIds": self.label_ids(&fields.labels)?,
+            "assigneeId": fields.assignee.as_ref().and_then(|email| workspace.users.get(email)),
+        }))
+    }
+    
+    /// Issue mutation returning the issue as it now is
+    fn mutate_issue(&self, mutation: &str, variables: Value) -> Result<RemoteIssue, SyncError> {
+        let (params, args) = if mutation == "issueCreate" {
+            ("$input: IssueCreateInput!", "input: $input")
+        } else {
+            ("$id: String!, $input: IssueUpdateInput!", "id: $id, input: $input")
+        };
+        let query = format!("mutation({}) {{ {}({}) {{ issue {{ {} }} }} }}", params, mutation, args, ISSUE_FIELDS);
+        let data = self.graphql(&query, variables)?;
+        parse_issue(data.pointer(&format!("/{}/issue", mutation)).unwrap_or(&Value::Null))
+    }
+}
+
+impl IssueTracker for LinearSync {
+    fn name(&self) -> &str {
+        "linear"
+    }
+    
+    fn list_issues(&self) -> Result<Vec<RemoteIssue>, SyncError> {
+        let team_id = self.workspace()?.team_id.clone();
+        let query = format!(
+            "query($team: ID!, $after: String) {{
+                issues(first: 100, after: $after, filter: {{ team: {{ id: {{ eq: $team }} }} }}) {{
+                    nodes {{ {} }}
+                    pageInfo {{ hasNextPage endCursor }}
+                }}
+            }}",
+            ISSUE_FIELDS
+        );
+        
+        let mut issues = Vec::new();
+        let mut after: Option<String> = None;
+        loop {
+            let data = self.graphql(&query, json!({ "team": team_id, "after": after }))?;
+            let page = data.get("issues").cloned().unwrap_or(Value::Null);
+            for node in page.get("nodes").and_then(|n| n.as_array()).into_iter().flatten() {
+                issues.push(parse_issue(node)?);
+            }
+            
+            let has_next = page.pointer("/pageInfo/hasNextPage").and_then(|h| h.as_bool()).unwrap_or(false);
+            after = page.pointer("/pageInfo/endCursor").and_then(|c| c.as_str()).map(String::from);
+            if !has_next || after.is_none() {
+                break;
+            }
+        }
+        Ok(issues)
+    }
+    
+    fn create_issue(&self, fields: &IssueFields) -> Result<RemoteIssue, SyncError> {
+        let workspace = self.workspace()?;
+        let mut input = self.input(fields)?;
+        input["teamId"] = json!(workspace.team_id);
+        input["stateId"] = json!(match fields.state {
+            IssueState::Open => &workspace.open_state,
+            IssueState::Closed => &workspace.done_state,
+        });
+        self.mutate_issue("issueCreate", json!({ "input": input }))
+    }
+    
+    fn update_issue(&self, id: &str, fields: &IssueFields) -> Result<RemoteIssue, SyncError> {
+        let workspace = self.workspace()?;
+        let mut input = self.input(fields)?;
+        
+        // Leave open issues in whatever open state they're in (backlog,
+        // started, ...); only move them across the open/closed line
+        let current = self.graphql(
+            "query($id: String!) { issue(id: $id) { state { type } } }",
+            json!({ "id": id }),
+        )?;
+        let current = state_from_type(current.pointer("/issue/state/type").and_then(|t| t.as_str()));
+        if current != fields.state {
+            input["stateId"] = json!(match fields.state {
+                IssueState::Open => &workspace.open_state,
+                IssueState::Closed => &workspace.done_state,
+            });
+        }
+        self.mutate_issue("issueUpdate", json!({ "id": id, "input": input }))
+    }
+    
+    fn add_comment(&self, id: &str, body: &str) -> Result<(), SyncError> {
+        self.graphql(
+            "mutation($input: CommentCreateInput!) { commentCreate(input: $input) { success } }",
+            json!({ "input": { "issueId": id, "body": body } }),
+        )?;
+        Ok(())
+    }
+}
+
+/// Completed and canceled issues are closed; everything else is open
+fn state_from_type(state_type: Option<&str>) -> IssueState {
+    match state_type {
+        Some("completed") | Some("canceled") => IssueState::Closed,
+        _ => IssueState::Open,
+    }
+}
+
+/// Team, states, labels, and users from the workspace query
+fn parse_workspace(data: &Value) -> Option<Workspace> {
+    let team = data.pointer("/teams/nodes/0")?;
+    let team_id = team.get("id")?.as_str()?.to_string();
+    
+    // First state (by board position) of the given type
+    let states = team.pointer("/states/nodes")?.as_array()?;
+    let first_of = |kinds: &[&str]| -> Option<String> {
+        kinds.iter().find_map(|kind| {
+            states.iter()
+                .filter(|s| s.get("type").and_then(|t| t.as_str()) == Some(kind))
+                .min_by(|a, b| {
+                    let position = |s: &Value| s.get("position").and_then(|p| p.as_f64()).unwrap_or(0.0);
+                    position(a).total_cmp(&position(b))
+                })
+                .and_then(|s| s.get("id")?.as_str().map(String::from))
+        })
+    };
+    
+    let labels = data.pointer("/issueLabels/nodes").and_then(|l| l.as_array()).into_iter().flatten()
+        .filter(|l| l.pointer("/team/id").and_then(|t| t.as_str()).is_none_or(|t| t == team_id))
+        .filter_map(|l| Some((l.get("name")?.as_str()?.to_string(), l.get("id")?.as_str()?.to_string())))
+        .collect();
+    let users = data.pointer("/users/nodes").and_then(|u| u.as_array()).into_iter().flatten()
+        .filter_map(|u| Some((u.get("email")?.as_str()?.to_string(), u.get("id")?.as_str()?.to_string())))
+        .collect();
+    
+    Some(Workspace {
+        open_state: first_of(&["unstarted", "backlog", "started"])?,
+        done_state: first_of(&["completed"])?,
+        team_id,
+        labels: RefCell::new(labels),
+        users,
+    })
+}
+
+/// Issue from a GraphQL issue node
+fn parse_issue(value: &Value) -> Result<RemoteIssue, SyncError> {
+    let invalid = || SyncError::Tracker(format!("Unexpected Linear issue JSON: {}", value));
+    let identifier = value.get("identifier").and_then(|i| i.as_str()).ok_or_else(invalid)?;
+    let labels = value.pointer("/labels/nodes").and_then(|l| l.as_array()).into_iter().flatten()
+        .filter_map(|l| l.get("name").and_then(|n| n.as_str()).map(String::from))
+        .collect();
+    let updated_at = value.get("updatedAt").and_then(|u| u.as_str())
+        .and_then(|u| DateTime::parse_from_rfc3339(u).ok())
+        .map(|d| d.with_timezone(&Utc))
+        .unwrap_or_else(Utc::now);
+    
+    Ok(RemoteIssue {
+        id: identifier.to_string(),
+        url: value.get("url").and_then(|u| u.as_str()).unwrap_or_default().to_string(),
+        fields: IssueFields::new(
+            value.get("title").and_then(|t| t.as_str()).unwrap_or_default(),
+            value.get("description").and_then(|d| d.as_str()).map(String::from),
+            labels,
+            state_from_type(value.pointer("/state/type").and_then(|t| t.as_str())),
+            value.pointer("/assignee/email").and_then(|e| e.as_str()).map(String::from),
+        ),
+        updated_at,
+        parent: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_parse_issue_and_workspace() {
+        let issue = parse_issue(&json!({
+            "identifier": "ENG-7",
+            "url": "https://linear.app/acme/issue/ENG-7",
+            "title": "Fix login",
+            "description": null,
+            "updatedAt": "2024-05-01T12:00:00.000Z",
+            "state": { "type": "canceled" },
+            "labels": { "nodes": [{ "name": "bug" }, { "name": "auth" }] },
+            "assignee": { "email": "sam@acme.dev" }
+        })).unwrap();
+        
+        assert_eq!(issue.id, "ENG-7");
+        assert_eq!(issue.fields.labels, vec!["auth", "bug"]);
+        assert_eq!(issue.fields.state, IssueState::Closed);
+        assert_eq!(issue.fields.assignee.as_deref(), Some("sam@acme.dev"));
+        
+        let workspace = parse_workspace(&json!({
+            "teams": { "nodes": [{ "id": "team-1", "states": { "nodes": [
+                { "id": "s-progress", "type": "started", "position": 2.0 },
+                { "id": "s-todo", "type": "unstarted", "position": 1.0 },
+                { "id": "s-done", "type": "completed", "position": 3.0 },
+                { "id": "s-dup", "type": "completed", "position": 4.0 }
+            ] } }] },
+            "issueLabels": { "nodes": [
+                { "id": "l-bug", "name": "bug", "team": null },
+                { "id": "l-api", "name": "api", "team": { "id": "team-1" } },
+                { "id": "l-other", "name": "design", "team": { "id": "team-2" } }
+            ] },
+            "users": { "nodes": [{ "id": "u-1", "email": "sam@acme.dev" }] }
+        })).unwrap();
+        
+        assert_eq!((workspace.open_state.as_str(), workspace.done_state.as_str()), ("s-todo", "s-done"));
+        assert_eq!(workspace.labels.borrow().len(), 2);
+        assert!(!workspace.labels.borrow().contains_key("design"));
+        assert_eq!(workspace.users["sam@acme.dev"], "u-1");
+        assert!(parse_workspace(&json!({ "teams": { "nodes": [] } })).is_none());
+    }
+}
@@ -2,6 +2,14 @@
 //!
 //! Like Beads' git-based sync, but Rust-native.
 //! Allows team collaboration on tasks via git.
+//!
+//! External issue trackers sync through `tracker` (two-way, with conflict
+//! resolution); `github`, `linear`, and `jira` are the backends.
+
+pub mod tracker;
+pub mod github;
+pub mod linear;
+pub mod jira;
 
 use std::path::{Path, PathBuf};
 use std::process::Command;
@@ -30,6 +38,9 @@ pub enum SyncError {
     
     #[error("Sync branch not configured")]
     NoBranch,
+    
+    #[error("Tracker error: {0}")]
+    Tracker(String),
 }
 
 /// Sync state tracking
@@ -133,6 +144,7 @@ impl GitSync {
                     parent: task.parent,
                     evidence: task.evidence,
                     repo: task.repo,
+                    kind: task.kind,
                 })?;
                 count += 1;
             }
@@ -247,6 +259,17 @@ pub struct SyncResult {
     pub exported: u32,
 }
 
+/// Make a tracker API request, returning the status code and body
+pub(crate) fn http(
+    method: &str,
+    url: &str,
+    headers: &[(&str, String)],
+    body: Option<&serde_json::Value>,
+) -> Result<(u16, String), SyncError> {
+    let body = body.map(serde_json::to_string).transpose()?;
+    crate::net::request(method, url, headers, body.as_deref()).map_err(SyncError::Tracker)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
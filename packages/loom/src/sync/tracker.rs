@@ -0,0 +1,674 @@
+//! Issue Tracker Sync
+//!
+//! Two-way sync between tasks and an external issue tracker. Backends only
+//! list, create, and update issues (`IssueTracker`); deciding what moves
+//! where is shared here.
+//!
+//! Each linked task remembers a hash of its synced fields as each side
+//! looked after the last sync (`RemoteLink`). On the next sync:
+//!
+//! | Local changed | Remote changed | Action                         |
+//! |---------------|----------------|--------------------------------|
+//! | no            | no             | nothing                        |
+//! | yes           | no             | push the task to the issue     |
+//! | no            | yes            | pull the issue into the task   |
+//! | yes           | yes            | conflict, resolved by strategy |
+//!
+//! Open tasks without an issue are pushed as new issues; open issues
+//! without a task are pulled as new tasks (under their parent's task, for
+//! trackers with hierarchies like Jira epics). When a push closes an issue,
+//! the task's evidence is posted as a comment so the tracker shows how it
+//! was done.
+
+use std::collections::{HashMap, HashSet};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::SyncError;
+use crate::work::{CreateTask, Priority, RemoteLink, Status, Task, TaskKind, WorkStore};
+
+/// Whether an issue is open or closed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueState {
+    Open,
+    Closed,
+}
+
+/// The fields kept in sync between a task and an issue
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IssueFields {
+    pub title: String,
+    pub body: Option<String>,
+    /// Sorted, so order differences aren't changes
+    pub labels: Vec<String>,
+    pub state: IssueState,
+    /// Tracker user the issue is assigned to
+    pub assignee: Option<String>,
+    /// Only set for trackers that sync priority (left out of the hash when
+    /// unset, so adding it didn't change existing links)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<Priority>,
+}
+
+impl IssueFields {
+    pub fn new(
+        title: impl Into<String>,
+        body: Option<String>,
+        mut labels: Vec<String>,
+        state: IssueState,
+        assignee: Option<String>,
+    ) -> Self {
+        labels.sort();
+        labels.dedup();
+        Self {
+            title: title.into(),
+            body: body.filter(|b| !b.trim().is_empty()),
+            labels,
+            state,
+            assignee,
+            priority: None,
+        }
+    }
+    
+    pub fn with_priority(mut self, priority: Option<Priority>) -> Self {
+        self.priority = priority;
+        self
+    }
+    
+    /// Stable hash of the fields, for change detection
+    pub fn hash(&self) -> String {
+        let json = serde_json::to_string(self).unwrap_or_default();
+        format!("{:x}", Sha256::digest(json.as_bytes()))[..16].to_string()
+    }
+}
+
+/// An issue as the tracker reports it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteIssue {
+    /// Tracker's identifier (issue number, key, ...)
+    pub id: String,
+    pub url: String,
+    pub fields: IssueFields,
+    pub updated_at: DateTime<Utc>,
+    /// Remote ID of the parent issue (e.g. a Jira story's epic)
+    pub parent: Option<String>,
+}
+
+/// An external issue tracker
+pub trait IssueTracker {
+    /// Name links are stored under (e.g. "github")
+    fn name(&self) -> &str;
+    
+    /// All issues in scope, open and closed
+    fn list_issues(&self) -> Result<Vec<RemoteIssue>, SyncError>;
+    
+    fn create_issue(&self, fields: &IssueFields) -> Result<RemoteIssue, SyncError>;
+    
+    fn update_issue(&self, id: &str, fields: &IssueFields) -> Result<RemoteIssue, SyncError>;
+    
+    /// Whether issues carry a priority that maps to `Priority`
+    fn syncs_priority(&self) -> bool {
+        false
+    }
+    
+    /// Comment on an issue (trackers without comments can ignore it)
+    fn add_comment(&self, _id: &str, _body: &str) -> Result<(), SyncError> {
+        Ok(())
+    }
+}
+
+/// Which side wins when a task and its issue both changed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConflictStrategy {
+    /// Whichever was edited last
+    #[default]
+    Newest,
+    Local,
+    Remote,
+}
+
+/// Options for a tracker sync
+#[derive(Debug, Clone, Default)]
+pub struct TrackerSyncOptions {
+    /// Only sync tasks and issues carrying this label
+    pub label: Option<String>,
+    /// Loom agent → tracker user; unmapped agents aren't assigned remotely
+    pub assignees: HashMap<String, String>,
+    pub conflict: ConflictStrategy,
+    /// Report what would happen without changing either side
+    pub dry_run: bool,
+}
+
+/// A task and issue that both changed since the last sync
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncConflict {
+    pub task_id: String,
+    pub remote_id: String,
+    /// "local" or "remote": the side that was kept
+    pub kept: String,
+}
+
+/// What a tracker sync did (or would do, for a dry run)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TrackerSyncReport {
+    pub tracker: String,
+    pub dry_run: bool,
+    /// Tasks pushed as new issues
+    pub created_remote: Vec<String>,
+    /// Issues pulled as new tasks (remote IDs; task IDs once created)
+    pub created_local: Vec<String>,
+    /// Tasks whose changes were pushed
+    pub pushed: Vec<String>,
+    /// Tasks updated from their issue
+    pub pulled: Vec<String>,
+    pub conflicts: Vec<SyncConflict>,
+    pub unchanged: usize,
+    /// Linked issues the tracker no longer lists
+    pub missing_remote: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+impl TrackerSyncReport {
+    pub fn summary(&self) -> String {
+        format!(
+            "{}{}: {} new issues, {} new tasks, {} pushed, {} pulled, {} conflicts, {} unchanged{}",
+            self.tracker,
+            if self.dry_run { " (dry run)" } else { "" },
+            self.created_remote.len(),
+            self.created_local.len(),
+            self.pushed.len(),
+            self.pulled.len(),
+            self.conflicts.len(),
+            self.unchanged,
+            if self.errors.is_empty() { String::new() } else { format!(", {} errors", self.errors.len()) },
+        )
+    }
+}
+
+/// Sync tasks with a tracker
+///
+/// Per-item failures (e.g. a remote close that the evidence rules won't
+/// let complete locally) are collected in `errors` rather than aborting.
+pub fn sync_tracker(
+    store: &mut WorkStore,
+    tracker: &dyn IssueTracker,
+    options: &TrackerSyncOptions,
+) -> Result<TrackerSyncReport, SyncError> {
+    let mut report = TrackerSyncReport {
+        tracker: tracker.name().to_string(),
+        dry_run: options.dry_run,
+        ..Default::default()
+    };
+    
+    let priority = tracker.syncs_priority();
+    let links = store.remote_links(tracker.name())?;
+    let in_scope = |labels: &[String]| options.label.as_ref().is_none_or(|l| labels.contains(l));
+    let issues: HashMap<String, RemoteIssue> = tracker.list_issues()?
+        .into_iter()
+        .filter(|i| in_scope(&i.fields.labels))
+        .map(|i| (i.id.clone(), i))
+        .collect();
+    
+    // Linked pairs
+    for link in &links {
+        let Some(task) = store.get(&link.task_id)? else { continue };
+        let Some(issue) = issues.get(&link.remote_id) else {
+            report.missing_remote.push(link.remote_id.clone());
+            continue;
+        };
+        
+        let local = local_fields(&task, options, priority);
+        let local_changed = local.hash() != link.local_hash;
+        let remote_changed = issue.fields.hash() != link.remote_hash;
+        
+        let push = match (local_changed, remote_changed) {
+            (false, false) => {
+                report.unchanged += 1;
+                continue;
+            }
+            (true, false) => true,
+            (false, true) => false,
+            (true, true) => {
+                let push = match options.conflict {
+                    ConflictStrategy::Local => true,
+                    ConflictStrategy::Remote => false,
+                    ConflictStrategy::Newest => task.updated_at >= issue.updated_at,
+                };
+                report.conflicts.push(SyncConflict {
+                    task_id: task.id.clone(),
+                    remote_id: issue.id.clone(),
+                    kept: if push { "local" } else { "remote" }.to_string(),
+                });
+                push
+            }
+        };
+        
+        if push {
+            report.pushed.push(task.id.clone());
+            if !options.dry_run {
+                let closing = local.state == IssueState::Closed && issue.fields.state == IssueState::Open;
+                let result = tracker.update_issue(&issue.id, &local)
+                    .and_then(|updated| {
+                        if closing {
+                            tracker.add_comment(&issue.id, &completion_comment(&task))?;
+                        }
+                        link_pair(store, tracker.name(), &task.id, &updated, options, priority)
+                    });
+                if let Err(e) = result {
+                    report.errors.push(format!("{} → {}: {}", task.id, issue.id, e));
+                }
+            }
+        } else {
+            report.pulled.push(task.id.clone());
+            if !options.dry_run {
+                let assignee_changed = issue.fields.assignee != link.remote_assignee;
+                let result = apply_remote(store, &task, issue, options, priority, assignee_changed)
+                    .and_then(|()| link_pair(store, tracker.name(), &task.id, issue, options, priority));
+                if let Err(e) = result {
+                    report.errors.push(format!("{} ← {}: {}", task.id, issue.id, e));
+                }
+            }
+        }
+    }
+    
+    // Open tasks without an issue
+    let linked_tasks: HashSet<&str> = links.iter().map(|l| l.task_id.as_str()).collect();
+    for task in store.list_all()? {
+        let open = matches!(task.status, Status::Ready | Status::Claimed | Status::Blocked);
+        if !open || task.kind != TaskKind::Task || linked_tasks.contains(task.id.as_str()) || !in_scope(&task.labels) {
+            continue;
+        }
+        
+        report.created_remote.push(task.id.clone());
+        if !options.dry_run {
+            let result = tracker.create_issue(&local_fields(&task, options, priority))
+                .and_then(|issue| link_pair(store, tracker.name(), &task.id, &issue, options, priority));
+            if let Err(e) = result {
+                report.errors.push(format!("{} → new issue: {}", task.id, e));
+            }
+        }
+    }
+    
+    // Open issues without a task, parents first so children can link to them
+    let mut task_for_issue: HashMap<String, String> = links.iter()
+        .map(|l| (l.remote_id.clone(), l.task_id.clone()))
+        .collect();
+    let mut new_issues: Vec<&RemoteIssue> = issues.values()
+        .filter(|i| i.fields.state == IssueState::Open && !task_for_issue.contains_key(&i.id))
+        .collect();
+    let depth = |issue: &RemoteIssue| {
+        let mut depth = 0;
+        let mut parent = issue.parent.as_ref();
+        while let Some(id) = parent.filter(|_| depth < 16) {
+            depth += 1;
+            parent = issues.get(id).and_then(|p| p.parent.as_ref());
+        }
+        depth
+    };
+    new_issues.sort_by_cached_key(|i| (depth(i), i.id.clone()));
+    
+    for issue in new_issues {
+        if options.dry_run {
+            report.created_local.push(issue.id.clone());
+            continue;
+        }
+        
+        let result = store.create(CreateTask {
+            title: issue.fields.title.clone(),
+            description: issue.fields.body.clone(),
+            labels: issue.fields.labels.clone(),
+            priority: issue.fields.priority.unwrap_or_default(),
+            parent: issue.parent.as_ref().and_then(|p| task_for_issue.get(p)).cloned(),
+            ..Default::default()
+        })
+        .map_err(SyncError::from)
+        .and_then(|task| {
+            apply_remote(store, &task, issue, options, priority, true)?;
+            link_pair(store, tracker.name(), &task.id, issue, options, priority)?;
+            Ok(task.id)
+        });
+        match result {
+            Ok(id) => {
+                task_for_issue.insert(issue.id.clone(), id.clone());
+                report.created_local.push(id);
+            }
+            Err(e) => report.errors.push(format!("new task ← {}: {}", issue.id, e)),
+        }
+    }
+    
+    Ok(report)
+}
+
+/// A task's synced fields, as the tracker would see them
+pub fn local_fields(task: &Task, options: &TrackerSyncOptions, priority: bool) -> IssueFields {
+    let state = match task.status {
+        Status::Done | Status::Cancelled => IssueState::Closed,
+        _ => IssueState::Open,
+    };
+    let assignee = task.agent.as_ref().and_then(|a| options.assignees.get(a)).cloned();
+    IssueFields::new(task.title.clone(), task.description.clone(), task.labels.clone(), state, assignee)
+        .with_priority(priority.then_some(task.priority))
+}
+
+/// Comment posted when a push closes an issue
+pub fn completion_comment(task: &Task) -> String {
+    let verb = if task.status == Status::Cancelled { "Cancelled" } else { "Completed" };
+    let mut comment = format!("{} in Loom ({}", verb, task.id);
+    if let Some(agent) = &task.agent {
+        comment.push_str(&format!(", by {}", agent));
+    }
+    comment.push(')');
+    if let Some(evidence) = &task.evidence {
+        comment.push_str(&format!("\n\nEvidence: {}", evidence));
+    }
+    comment
+}
+
+/// Bring a task in line with its issue
+///
+/// The claim only follows the issue's assignee when `assignee_changed`:
+/// an edit to the title shouldn't release a task held by an agent with no
+/// tracker user.
+fn apply_remote(
+    store: &mut WorkStore,
+    task: &Task,
+    issue: &RemoteIssue,
+    options: &TrackerSyncOptions,
+    priority: bool,
+    assignee_changed: bool,
+) -> Result<(), SyncError> {
+    let remote = &issue.fields;
+    let local = local_fields(task, options, priority);
+    
+    if remote.title != local.title || remote.body != local.body || remote.labels != local.labels {
+        store.update_details(&task.id, &remote.title, remote.body.as_deref(), &remote.labels)?;
+    }
+    if let Some(remote_priority) = remote.priority.filter(|p| *p != task.priority) {
+        store.update_priority(&task.id, remote_priority)?;
+    }
+    
+    let closed_locally = matches!(task.status, Status::Done | Status::Cancelled);
+    match remote.state {
+        IssueState::Closed if !closed_locally => {
+            store.complete(&task.id, Some(&issue.url))?;
+            return Ok(());
+        }
+        IssueState::Closed => return Ok(()),
+        IssueState::Open if closed_locally => store.update_status(&task.id, Status::Ready)?,
+        IssueState::Open => {}
+    }
+    if !assignee_changed {
+        return Ok(());
+    }
+    
+    // Only assignees we can map back to an agent move claims, and only on
+    // tasks that can be claimed; blocked ones wait on their dependencies
+    if !matches!(task.status, Status::Ready | Status::Claimed) {
+        return Ok(());
+    }
+    let agent = remote.assignee.as_ref().and_then(|user| {
+        options.assignees.iter().find(|(_, u)| *u == user).map(|(agent, _)| agent.clone())
+    });
+    match agent {
+        Some(agent) if task.agent.as_ref() != Some(&agent) => {
+            if task.status == Status::Claimed {
+                store.release(&task.id)?;
+            }
+            store.claim(&task.id, &agent)?;
+        }
+        None if remote.assignee.is_none() && task.status == Status::Claimed => store.release(&task.id)?,
+        _ => {}
+    }
+    
+    Ok(())
+}
+
+/// Record a task and issue as in sync
+fn link_pair(
+    store: &mut WorkStore,
+    tracker: &str,
+    task_id: &str,
+    issue: &RemoteIssue,
+    options: &TrackerSyncOptions,
+    priority: bool,
+) -> Result<(), SyncError> {
+    let task = store.get(task_id)?
+        .ok_or_else(|| SyncError::Tracker(format!("Task not found: {}", task_id)))?;
+    store.set_remote_link(&RemoteLink {
+        task_id: task_id.to_string(),
+        tracker: tracker.to_string(),
+        remote_id: issue.id.clone(),
+        url: issue.url.clone(),
+        local_hash: local_fields(&task, options, priority).hash(),
+        remote_hash: issue.fields.hash(),
+        remote_assignee: issue.fields.assignee.clone(),
+        synced_at: Utc::now(),
+    })?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    
+    /// Tracker backed by a vector of issues
+    #[derive(Default)]
+    struct FakeTracker {
+        issues: RefCell<Vec<RemoteIssue>>,
+        comments: RefCell<Vec<(String, String)>>,
+        priority: bool,
+    }
+    
+    impl FakeTracker {
+        fn edit(&self, id: &str, edit: impl FnOnce(&mut IssueFields)) {
+            let mut issues = self.issues.borrow_mut();
+            let issue = issues.iter_mut().find(|i| i.id == id).unwrap();
+            edit(&mut issue.fields);
+            issue.fields.labels.sort();
+            issue.updated_at = Utc::now() + chrono::Duration::seconds(5);
+        }
+        
+        fn get(&self, id: &str) -> RemoteIssue {
+            self.issues.borrow().iter().find(|i| i.id == id).unwrap().clone()
+        }
+    }
+    
+    impl IssueTracker for FakeTracker {
+        fn name(&self) -> &str {
+            "fake"
+        }
+        
+        fn syncs_priority(&self) -> bool {
+            self.priority
+        }
+        
+        fn list_issues(&self) -> Result<Vec<RemoteIssue>, SyncError> {
+            Ok(self.issues.borrow().clone())
+        }
+        
+        fn create_issue(&self, fields: &IssueFields) -> Result<RemoteIssue, SyncError> {
+            let mut issues = self.issues.borrow_mut();
+            let id = (issues.len() + 1).to_string();
+            let issue = RemoteIssue {
+                url: format!("https://tracker.example/{}", id),
+                id,
+                fields: fields.clone(),
+                updated_at: Utc::now(),
+                parent: None,
+            };
+            issues.push(issue.clone());
+            Ok(issue)
+        }
+        
+        fn update_issue(&self, id: &str, fields: &IssueFields) -> Result<RemoteIssue, SyncError> {
+            let mut issues = self.issues.borrow_mut();
+            let issue = issues.iter_mut().find(|i| i.id == id)
+                .ok_or_else(|| SyncError::Tracker(format!("no issue {}", id)))?;
+            issue.fields = fields.clone();
+            issue.updated_at = Utc::now();
+            Ok(issue.clone())
+        }
+        
+        fn add_comment(&self, id: &str, body: &str) -> Result<(), SyncError> {
+            self.comments.borrow_mut().push((id.to_string(), body.to_string()));
+            Ok(())
+        }
+    }
+    
+    fn remote_id(store: &WorkStore, task_id: &str) -> String {
+        store.remote_links("fake").unwrap().into_iter().find(|l| l.task_id == task_id).unwrap().remote_id
+    }
+    
+    fn options() -> TrackerSyncOptions {
+        TrackerSyncOptions {
+            assignees: HashMap::from([("claude-code".to_string(), "octocat".to_string())]),
+            ..Default::default()
+        }
+    }
+    
+    #[test]
+    fn test_sync_creates_both_ways_then_settles() {
+        let mut store = WorkStore::in_memory().unwrap();
+        let tracker = FakeTracker::default();
+        let local = store.create(CreateTask {
+            title: "Local task".to_string(),
+            labels: vec!["api".to_string()],
+            ..Default::default()
+        }).unwrap();
+        store.claim(&local.id, "claude-code").unwrap();
+        tracker.create_issue(&IssueFields::new("Remote issue", Some("From GitHub".to_string()), vec!["bug".to_string()], IssueState::Open, Some("octocat".to_string()))).unwrap();
+        tracker.create_issue(&IssueFields::new("Old closed issue", None, vec![], IssueState::Closed, None)).unwrap();
+        
+        // Dry run changes nothing
+        let dry = sync_tracker(&mut store, &tracker, &TrackerSyncOptions { dry_run: true, ..options() }).unwrap();
+        assert_eq!((dry.created_remote.len(), dry.created_local.len()), (1, 1));
+        assert_eq!(tracker.issues.borrow().len(), 2);
+        
+        let report = sync_tracker(&mut store, &tracker, &options()).unwrap();
+        assert_eq!(report.created_remote, vec![local.id.clone()]);
+        assert_eq!(report.created_local.len(), 1);
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        
+        // Pushed issue carries the mapped assignee
+        assert_eq!(tracker.get(&remote_id(&store, &local.id)).fields.assignee.as_deref(), Some("octocat"));
+        
+        // Pulled task is claimed by the agent mapped from the assignee
+        let pulled = store.get(&report.created_local[0]).unwrap().unwrap();
+        assert_eq!(pulled.title, "Remote issue");
+        assert_eq!(pulled.description.as_deref(), Some("From GitHub"));
+        assert_eq!(pulled.agent.as_deref(), Some("claude-code"));
+        
+        let again = sync_tracker(&mut store, &tracker, &options()).unwrap();
+        assert_eq!(again.unchanged, 2);
+        assert!(again.pushed.is_empty() && again.pulled.is_empty() && again.created_local.is_empty());
+    }
+    
+    #[test]
+    fn test_sync_pushes_pulls_and_resolves_conflicts() {
+        let mut store = WorkStore::in_memory().unwrap();
+        let tracker = FakeTracker::default();
+        let a = store.create(CreateTask { title: "A".to_string(), ..Default::default() }).unwrap();
+        let b = store.create(CreateTask { title: "B".to_string(), ..Default::default() }).unwrap();
+        let c = store.create(CreateTask { title: "C".to_string(), ..Default::default() }).unwrap();
+        sync_tracker(&mut store, &tracker, &options()).unwrap();
+        
+        let (issue_a, issue_b, issue_c) = (remote_id(&store, &a.id), remote_id(&store, &b.id), remote_id(&store, &c.id));
+        
+        // A changed locally, B closed remotely, C edited on both sides
+        store.update_details(&a.id, "A renamed", None, &[]).unwrap();
+        tracker.edit(&issue_b, |f| f.state = IssueState::Closed);
+        store.update_details(&c.id, "C local", None, &[]).unwrap();
+        tracker.edit(&issue_c, |f| f.title = "C remote".to_string());
+        
+        let report = sync_tracker(&mut store, &tracker, &options()).unwrap();
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        assert_eq!(tracker.get(&issue_a).fields.title, "A renamed");
+        assert_eq!(store.get(&b.id).unwrap().unwrap().status, Status::Done);
+        
+        // Remote edit is newer
+        assert_eq!(report.conflicts.len(), 1);
+        assert_eq!(report.conflicts[0].kept, "remote");
+        assert_eq!(store.get(&c.id).unwrap().unwrap().title, "C remote");
+        
+        // Reopening remotely reopens the task
+        tracker.edit(&issue_b, |f| f.state = IssueState::Open);
+        sync_tracker(&mut store, &tracker, &options()).unwrap();
+        assert_eq!(store.get(&b.id).unwrap().unwrap().status, Status::Ready);
+        
+        // Completing locally closes the issue with the evidence as a comment
+        store.claim(&a.id, "claude-code").unwrap();
+        store.complete(&a.id, Some("commit abc123")).unwrap();
+        sync_tracker(&mut store, &tracker, &options()).unwrap();
+        assert_eq!(tracker.get(&issue_a).fields.state, IssueState::Closed);
+        let comments = tracker.comments.borrow();
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].0, issue_a);
+        assert!(comments[0].1.contains("by claude-code") && comments[0].1.contains("Evidence: commit abc123"));
+    }
+    
+    #[test]
+    fn test_sync_pulls_hierarchy_and_priority() {
+        let mut store = WorkStore::in_memory().unwrap();
+        let tracker = FakeTracker { priority: true, ..Default::default() };
+        let issue = |id: &str, parent: Option<&str>, priority: Priority| RemoteIssue {
+            id: id.to_string(),
+            url: format!("https://tracker.example/{}", id),
+            fields: IssueFields::new(format!("Issue {}", id), None, vec![], IssueState::Open, None).with_priority(Some(priority)),
+            updated_at: Utc::now(),
+            parent: parent.map(String::from),
+        };
+        // Listed children first: parents must still be created before them
+        tracker.issues.borrow_mut().extend([
+            issue("ENG-3", Some("ENG-2"), Priority::Low),
+            issue("ENG-2", Some("ENG-1"), Priority::Normal),
+            issue("ENG-1", None, Priority::Critical),
+        ]);
+        
+        let report = sync_tracker(&mut store, &tracker, &options()).unwrap();
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        let task = |store: &WorkStore, remote: &str| {
+            let link = store.remote_links("fake").unwrap().into_iter().find(|l| l.remote_id == remote).unwrap();
+            store.get(&link.task_id).unwrap().unwrap()
+        };
+        let (epic, story, subtask) = (task(&store, "ENG-1"), task(&store, "ENG-2"), task(&store, "ENG-3"));
+        assert_eq!(epic.priority, Priority::Critical);
+        assert_eq!(story.parent.as_deref(), Some(epic.id.as_str()));
+        assert_eq!(subtask.parent.as_deref(), Some(story.id.as_str()));
+        
+        // Priority changes flow both ways
+        tracker.edit("ENG-2", |f| f.priority = Some(Priority::High));
+        store.update_priority(&subtask.id, Priority::Critical).unwrap();
+        let report = sync_tracker(&mut store, &tracker, &options()).unwrap();
+        assert_eq!((report.pulled.len(), report.pushed.len()), (1, 1));
+        assert_eq!(task(&store, "ENG-2").priority, Priority::High);
+        assert_eq!(tracker.get("ENG-3").fields.priority, Some(Priority::Critical));
+    }
+    
+    #[test]
+    fn test_sync_moves_claims_only_when_the_assignee_changes() {
+        let mut store = WorkStore::in_memory().unwrap();
+        let tracker = FakeTracker::default();
+        let task = store.create(CreateTask { title: "Task".to_string(), ..Default::default() }).unwrap();
+        sync_tracker(&mut store, &tracker, &options()).unwrap();
+        let issue = remote_id(&store, &task.id);
+        
+        // gemini has no tracker user, so the issue stays unassigned; a
+        // remote title edit leaves its claim alone
+        store.claim(&task.id, "gemini").unwrap();
+        sync_tracker(&mut store, &tracker, &options()).unwrap();
+        tracker.edit(&issue, |f| f.title = "Task, renamed".to_string());
+        let report = sync_tracker(&mut store, &tracker, &options()).unwrap();
+        assert_eq!(report.pulled, vec![task.id.clone()]);
+        let pulled = store.get(&task.id).unwrap().unwrap();
+        assert_eq!((pulled.title.as_str(), pulled.agent.as_deref()), ("Task, renamed", Some("gemini")));
+        
+        // Assigning the issue moves the claim
+        tracker.edit(&issue, |f| f.assignee = Some("octocat".to_string()));
+        sync_tracker(&mut store, &tracker, &options()).unwrap();
+        assert_eq!(store.get(&task.id).unwrap().unwrap().agent.as_deref(), Some("claude-code"));
+    }
+}
@@ -0,0 +1,274 @@
+//! Task Templates
+//!
+//! Reusable task trees stored as TOML in `.loom/templates/`.
+//! A template gives a title pattern, labels, default priority, and child
+//! tasks; `{{name}}` placeholders are filled in from variables when it's
+//! instantiated, and the whole tree is created at once.
+
+use std::collections::HashMap;
+use std::path::Path;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::formulas::Variable;
+use crate::work::Priority;
+
+#[derive(Error, Debug)]
+pub enum TemplateError {
+    #[error("Template not found: {0}")]
+    NotFound(String),
+    
+    #[error("Missing template variable: {0}")]
+    MissingVariable(String),
+    
+    #[error("Invalid template {name}: {reason}")]
+    Invalid { name: String, reason: String },
+    
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// A child task in a template (children can have children of their own)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateTask {
+    /// Title pattern
+    pub title: String,
+    /// Description pattern
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Defaults to the parent's priority
+    #[serde(default)]
+    pub priority: Option<Priority>,
+    #[serde(default)]
+    pub children: Vec<TemplateTask>,
+}
+
+/// A reusable task template
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskTemplate {
+    /// Template name (defaults to the file name)
+    #[serde(default)]
+    pub name: String,
+    /// What the template is for
+    #[serde(default)]
+    pub summary: Option<String>,
+    /// Title pattern for the root task
+    pub title: String,
+    /// Description pattern for the root task
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Default priority for the root task and its children
+    #[serde(default)]
+    pub priority: Priority,
+    /// Variables the patterns can reference
+    #[serde(default)]
+    pub variables: Vec<Variable>,
+    #[serde(default)]
+    pub children: Vec<TemplateTask>,
+}
+
+/// A task to create from a template, with its children
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlannedTask {
+    pub title: String,
+    pub description: Option<String>,
+    pub labels: Vec<String>,
+    pub priority: Priority,
+    pub children: Vec<PlannedTask>,
+}
+
+impl TaskTemplate {
+    /// Parse a template; `name` is used when the TOML doesn't set one
+    pub fn from_toml(content: &str, name: &str) -> Result<Self, TemplateError> {
+        let mut template: TaskTemplate = toml::from_str(content).map_err(|e| TemplateError::Invalid {
+            name: name.to_string(),
+            reason: e.to_string(),
+        })?;
+        if template.name.is_empty() {
+            template.name = name.to_string();
+        }
+        Ok(template)
+    }
+    
+    /// Load `<name>.toml` from a templates directory
+    pub fn load(dir: impl AsRef<Path>, name: &str) -> Result<Self, TemplateError> {
+        if name.is_empty() || name.starts_with('.') || name.contains(['/', '\\']) {
+            return Err(TemplateError::NotFound(name.to_string()));
+        }
+        
+        let path = dir.as_ref().join(format!("{}.toml", name));
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Err(TemplateError::NotFound(name.to_string()));
+            }
+            Err(e) => return Err(e.into()),
+        };
+        Self::from_toml(&content, name)
+    }
+    
+    /// Load every template in a directory, sorted by name
+    pub fn load_all(dir: impl AsRef<Path>) -> Result<Vec<Self>, TemplateError> {
+        let dir = dir.as_ref();
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+        
+        let mut templates = Vec::new();
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|e| e == "toml") {
+                let name = path.file_stem().unwrap_or_default().to_string_lossy();
+                templates.push(Self::from_toml(&std::fs::read_to_string(&path)?, &name)?);
+            }
+        }
+        templates.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(templates)
+    }
+    
+    /// Fill in the patterns from `vars` (falling back to variable defaults).
+    /// Fails if a required variable is missing or a placeholder has no value.
+    pub fn instantiate(&self, vars: &HashMap<String, String>) -> Result<PlannedTask, TemplateError> {
+        let mut values = vars.clone();
+        for var in &self.variables {
+            if !values.contains_key(&var.name) {
+                match &var.default {
+                    Some(default) => {
+                        values.insert(var.name.clone(), default.clone());
+                    }
+                    None if var.required => return Err(TemplateError::MissingVariable(var.name.clone())),
+                    None => {}
+                }
+            }
+        }
+        
+        let renderer = Renderer::new(values);
+        let root = TemplateTask {
+            title: self.title.clone(),
+            description: self.description.clone(),
+            labels: self.labels.clone(),
+            priority: Some(self.priority),
+            children: self.children.clone(),
+        };
+        renderer.plan(&root, self.priority)
+    }
+}
+
+impl PlannedTask {
+    /// Number of tasks in this tree, including this one
+    pub fn count(&self) -> usize {
+        1 + self.children.iter().map(PlannedTask::count).sum::<usize>()
+    }
+}
+
+/// Substitutes `{{name}}` placeholders
+struct Renderer {
+    values: HashMap<String, String>,
+    placeholder: Regex,
+}
+
+impl Renderer {
+    fn new(values: HashMap<String, String>) -> Self {
+        Self {
+            values,
+            placeholder: Regex::new(r"\{\{\s*([A-Za-z0-9_-]+)\s*\}\}").expect("valid placeholder regex"),
+        }
+    }
+    
+    fn plan(&self, task: &TemplateTask, inherited: Priority) -> Result<PlannedTask, TemplateError> {
+        let priority = task.priority.unwrap_or(inherited);
+        Ok(PlannedTask {
+            title: self.render(&task.title)?,
+            description: task.description.as_deref().map(|d| self.render(d)).transpose()?,
+            labels: task.labels.iter().map(|l| self.render(l)).collect::<Result<_, _>>()?,
+            priority,
+            children: task
+                .children
+                .iter()
+                .map(|child| self.plan(child, priority))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+    
+    fn render(&self, text: &str) -> Result<String, TemplateError> {
+        if let Some(missing) = self
+            .placeholder
+            .captures_iter(text)
+            .find(|c| !self.values.contains_key(&c[1]))
+        {
+            return Err(TemplateError::MissingVariable(missing[1].to_string()));
+        }
+        Ok(self.placeholder.replace_all(text, |c: &regex::Captures| self.values[&c[1]].clone()).into_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    const RELEASE: &str = r#"
+summary = "Cut and ship a release"
+title = "Release {{version}}"
+description = "Ship {{ version }} to {{target}}"
+labels = ["release"]
+priority = "high"
+
+[[variables]]
+name = "version"
+description = "Version to release"
+required = true
+
+[[variables]]
+name = "target"
+description = "Where it goes"
+default = "production"
+
+[[children]]
+title = "Tag {{version}}"
+labels = ["git"]
+
+[[children]]
+title = "Announce"
+priority = "low"
+
+[[children.children]]
+title = "Write changelog for {{version}}"
+"#;
+    
+    #[test]
+    fn test_instantiate_template() {
+        let template = TaskTemplate::from_toml(RELEASE, "release").unwrap();
+        assert_eq!(template.name, "release");
+        
+        assert!(matches!(
+            template.instantiate(&HashMap::new()),
+            Err(TemplateError::MissingVariable(ref v)) if v == "version"
+        ));
+        
+        let vars = HashMap::from([("version".to_string(), "1.4.0".to_string())]);
+        let plan = template.instantiate(&vars).unwrap();
+        assert_eq!(plan.title, "Release 1.4.0");
+        assert_eq!(plan.description.as_deref(), Some("Ship 1.4.0 to production"));
+        assert_eq!(plan.count(), 4);
+        
+        let (tag, announce) = (&plan.children[0], &plan.children[1]);
+        assert_eq!(tag.title, "Tag 1.4.0");
+        assert_eq!(tag.priority, Priority::High);
+        assert_eq!(announce.priority, Priority::Low);
+        // Grandchildren inherit from their own parent
+        assert_eq!(announce.children[0].priority, Priority::Low);
+        assert_eq!(announce.children[0].title, "Write changelog for 1.4.0");
+        
+        // Placeholders without a declared variable still need a value
+        let undeclared = TaskTemplate::from_toml(r#"title = "Fix {{component}}""#, "fix").unwrap();
+        assert!(matches!(
+            undeclared.instantiate(&HashMap::new()),
+            Err(TemplateError::MissingVariable(ref v)) if v == "component"
+        ));
+    }
+}
@@ -0,0 +1,183 @@
+//! Test Fixtures
+//!
+//! Builders for the state a test needs, on top of `Loom::in_memory()`.
+//! Available to this crate's tests, and to other crates with the
+//! `test-util` feature. Each builder goes through the same `Loom` calls an
+//! agent would, so the history, leases, and sessions look real.
+
+use crate::memory::{Session, SessionStatus};
+use crate::work::{CreateTask, Priority, Task, TaskKind};
+use crate::{Loom, LoomError};
+
+/// A task to create, and how far along it should be
+#[derive(Debug, Clone, Default)]
+pub struct TaskFixture {
+    params: CreateTask,
+    blocked_by: Vec<String>,
+    claimed_by: Option<String>,
+    done: Option<String>,
+}
+
+impl TaskFixture {
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            params: CreateTask { title: title.into(), ..Default::default() },
+            ..Default::default()
+        }
+    }
+    
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.params.description = Some(description.into());
+        self
+    }
+    
+    pub fn priority(mut self, priority: Priority) -> Self {
+        self.params.priority = priority;
+        self
+    }
+    
+    pub fn labels<S: Into<String>>(mut self, labels: impl IntoIterator<Item = S>) -> Self {
+        self.params.labels = labels.into_iter().map(Into::into).collect();
+        self
+    }
+    
+    pub fn parent(mut self, parent: impl Into<String>) -> Self {
+        self.params.parent = Some(parent.into());
+        self
+    }
+    
+    pub fn kind(mut self, kind: TaskKind) -> Self {
+        self.params.kind = kind;
+        self
+    }
+    
+    /// Block the task on another
+    pub fn blocked_by(mut self, task_id: impl Into<String>) -> Self {
+        self.blocked_by.push(task_id.into());
+        self
+    }
+    
+    /// Leave the task claimed by an agent
+    pub fn claimed_by(mut self, agent: impl Into<String>) -> Self {
+        self.claimed_by = Some(agent.into());
+        self
+    }
+    
+    /// Complete the task with this evidence (claiming it as "fixture" first
+    /// unless `claimed_by` says who)
+    pub fn done(mut self, evidence: impl Into<String>) -> Self {
+        self.done = Some(evidence.into());
+        self
+    }
+    
+    /// Create the task, returning it as it ends up
+    pub fn create(self, loom: &mut Loom) -> Result<Task, LoomError> {
+        let task = loom.create_task(self.params)?;
+        for blocker in &self.blocked_by {
+            loom.block(&task.id, blocker)?;
+        }
+        if self.claimed_by.is_some() || self.done.is_some() {
+            loom.claim(&task.id, self.claimed_by.as_deref().unwrap_or("fixture"))?;
+        }
+        if let Some(evidence) = &self.done {
+            loom.complete(&task.id, Some(evidence))?;
+        }
+        Ok(loom.get(&task.id)?.expect("fixture task was just created"))
+    }
+}
+
+/// A session on a task, with its checkpoints and how it ended
+#[derive(Debug, Clone)]
+pub struct SessionFixture {
+    task_id: String,
+    agent: String,
+    checkpoints: Vec<String>,
+    ended: Option<SessionStatus>,
+}
+
+impl SessionFixture {
+    pub fn new(task_id: impl Into<String>, agent: impl Into<String>) -> Self {
+        Self {
+            task_id: task_id.into(),
+            agent: agent.into(),
+            checkpoints: Vec::new(),
+            ended: None,
+        }
+    }
+    
+    /// Record a checkpoint, in order with any others
+    pub fn checkpoint(mut self, summary: impl Into<String>) -> Self {
+        self.checkpoints.push(summary.into());
+        self
+    }
+    
+    /// End the session (it stays active otherwise)
+    pub fn ended(mut self, status: SessionStatus) -> Self {
+        self.ended = Some(status);
+        self
+    }
+    
+    /// Start the session, returning it as it ends up
+    pub fn create(self, loom: &mut Loom) -> Result<Session, LoomError> {
+        let session = loom.start_session(&self.task_id, &self.agent)?;
+        for summary in &self.checkpoints {
+            loom.checkpoint(&session.id, summary)?;
+        }
+        if let Some(status) = self.ended {
+            loom.end_session(&session.id, status)?;
+        }
+        Ok(loom.get_session(&session.id)?.expect("fixture session was just started"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Status;
+    
+    #[test]
+    fn test_fixtures_build_state_in_memory() {
+        let mut loom = Loom::in_memory().unwrap();
+        
+        let schema = TaskFixture::new("Design the schema")
+            .labels(["planning"])
+            .done("Reviewed with the team")
+            .create(&mut loom)
+            .unwrap();
+        let api = TaskFixture::new("Build the API")
+            .priority(Priority::High)
+            .claimed_by("claude-sonnet")
+            .create(&mut loom)
+            .unwrap();
+        let docs = TaskFixture::new("Document the API").blocked_by(&api.id).create(&mut loom).unwrap();
+        assert_eq!(schema.status, Status::Done);
+        assert_eq!(api.status, Status::Claimed);
+        assert_eq!(docs.status, Status::Blocked);
+        
+        let session = SessionFixture::new(&api.id, "claude-sonnet")
+            .checkpoint("Routes stubbed")
+            .checkpoint("Handlers tested")
+            .ended(SessionStatus::Completed)
+            .create(&mut loom)
+            .unwrap();
+        assert_eq!(session.status, SessionStatus::Completed);
+        assert!(session.last_checkpoint.is_some());
+        
+        // Answers keep their preferences in a directory that goes away with the Loom
+        let (question, _) = loom.ask(
+            CreateTask { title: "REST or GraphQL?".into(), ..Default::default() },
+            vec!["rest".into(), "graphql".into()],
+            None,
+            None,
+            std::slice::from_ref(&docs.id),
+        ).unwrap();
+        loom.answer(&question.id, "rest", None, "alice").unwrap();
+        assert!(loom.get_preferences(&question.id).unwrap().unwrap().contains("**Decision:** rest"));
+        assert!(loom.route(&docs).is_ok());
+        
+        let root = loom.root().to_path_buf();
+        assert!(root.is_absolute() && root.exists());
+        drop(loom);
+        assert!(!root.parent().unwrap().exists());
+    }
+}
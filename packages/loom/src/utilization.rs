@@ -0,0 +1,284 @@
+//! Agent Utilization
+//!
+//! Which agent subscriptions are worth keeping? For each agent and each
+//! (UTC) day, compare time spent in sessions against the time available,
+//! alongside what that time produced (tasks completed) and what it cost.
+//!
+//! Active time comes from session spans in memory.db, merged so parallel
+//! sessions on one agent aren't double counted. Idle time is the rest of the
+//! day's window: the whole day, clipped to the report start and to now.
+//! Outcomes and cost come from execution records in agents.db.
+
+use std::collections::BTreeMap;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::agents::ExecutionRecord;
+use crate::memory::SessionSpan;
+
+type Interval = (DateTime<Utc>, DateTime<Utc>);
+
+/// One agent's day
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AgentUtilization {
+    pub agent_id: String,
+    pub date: NaiveDate,
+    /// Seconds with at least one session open
+    pub active_secs: f64,
+    /// Seconds of the day's window with no session open
+    pub idle_secs: f64,
+    /// Sessions that overlapped the day
+    pub sessions: u32,
+    /// Successful executions recorded that day
+    pub tasks_completed: u32,
+    /// Failed executions recorded that day
+    pub tasks_failed: u32,
+    /// Execution cost recorded that day (USD)
+    pub cost_usd: f64,
+}
+
+impl AgentUtilization {
+    /// Share of the day's window spent working (0.0 - 1.0)
+    pub fn utilization(&self) -> f64 {
+        let window = self.active_secs + self.idle_secs;
+        if window > 0.0 { self.active_secs / window } else { 0.0 }
+    }
+    
+    /// Cost per completed task, if anything was completed
+    pub fn cost_per_task(&self) -> Option<f64> {
+        (self.tasks_completed > 0).then(|| self.cost_usd / self.tasks_completed as f64)
+    }
+}
+
+/// Per-agent, per-day utilization over a period
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UtilizationReport {
+    pub since: DateTime<Utc>,
+    pub until: DateTime<Utc>,
+    /// Sorted by date, then agent
+    pub days: Vec<AgentUtilization>,
+}
+
+impl UtilizationReport {
+    /// Build a report from session spans (agent, started, ended) and execution records
+    pub fn build(
+        spans: &[SessionSpan],
+        records: &[ExecutionRecord],
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Self {
+        let mut days: BTreeMap<(NaiveDate, String), AgentUtilization> = BTreeMap::new();
+        
+        // Split each span at midnight, collecting per-day intervals to merge
+        let mut intervals: BTreeMap<(NaiveDate, String), Vec<Interval>> = BTreeMap::new();
+        for (agent_id, started, ended) in spans {
+            let (started, ended) = ((*started).max(since), (*ended).min(until));
+            let mut cursor = started;
+            while cursor < ended {
+                let date = cursor.date_naive();
+                let day_end = day_start(date + Duration::days(1)).min(ended);
+                intervals.entry((date, agent_id.clone())).or_default().push((cursor, day_end));
+                entry(&mut days, date, agent_id).sessions += 1;
+                cursor = day_end;
+            }
+        }
+        
+        for ((date, agent_id), mut spans) in intervals {
+            entry(&mut days, date, &agent_id).active_secs = merged_secs(&mut spans);
+        }
+        
+        for record in records {
+            let Some(timestamp) = record.timestamp else { continue };
+            if timestamp < since || timestamp > until {
+                continue;
+            }
+            let day = entry(&mut days, timestamp.date_naive(), &record.agent_id);
+            if record.success {
+                day.tasks_completed += 1;
+            } else {
+                day.tasks_failed += 1;
+            }
+            day.cost_usd += record.cost.unwrap_or(0.0);
+        }
+        
+        let days = days.into_values()
+            .map(|mut day| {
+                let window_start = day_start(day.date).max(since);
+                let window_end = day_start(day.date + Duration::days(1)).min(until);
+                let window = (window_end - window_start).num_milliseconds().max(0) as f64 / 1000.0;
+                day.idle_secs = (window - day.active_secs).max(0.0);
+                day
+            })
+            .collect();
+        
+        Self { since, until, days }
+    }
+    
+    /// Keep only one agent's days
+    pub fn for_agent(mut self, agent_id: &str) -> Self {
+        self.days.retain(|d| d.agent_id == agent_id);
+        self
+    }
+    
+    /// Totals per agent across the whole period, sorted by agent
+    pub fn by_agent(&self) -> Vec<AgentUtilization> {
+        let mut totals: BTreeMap<&str, AgentUtilization> = BTreeMap::new();
+        for day in &self.days {
+            let total = totals.entry(&day.agent_id).or_insert_with(|| AgentUtilization {
+                agent_id: day.agent_id.clone(),
+                date: day.date,
+                ..Default::default()
+            });
+            total.active_secs += day.active_secs;
+            total.idle_secs += day.idle_secs;
+            total.sessions += day.sessions;
+            total.tasks_completed += day.tasks_completed;
+            total.tasks_failed += day.tasks_failed;
+            total.cost_usd += day.cost_usd;
+        }
+        totals.into_values().collect()
+    }
+    
+    /// Format as a human-readable report
+    pub fn format_report(&self) -> String {
+        let mut output = String::new();
+        
+        output.push_str("Agent Utilization\n");
+        output.push_str("=================\n\n");
+        output.push_str(&format!("{} → {}\n\n", self.since.format("%Y-%m-%d %H:%M"), self.until.format("%Y-%m-%d %H:%M")));
+        
+        for day in &self.days {
+            output.push_str(&format!(
+                "  {} {:<13} → {:>5.1}h active ({:>3.0}%), {:>3} done, {:>2} failed, ${:.2}\n",
+                day.date,
+                day.agent_id,
+                day.active_secs / 3600.0,
+                day.utilization() * 100.0,
+                day.tasks_completed,
+                day.tasks_failed,
+                day.cost_usd,
+            ));
+        }
+        
+        output.push_str("\nTotals:\n");
+        for total in self.by_agent() {
+            let per_task = total.cost_per_task()
+                .map(|c| format!("${:.2}/task", c))
+                .unwrap_or_else(|| "no completions".to_string());
+            output.push_str(&format!(
+                "  {:<13} → {:>5.1}h active ({:>3.0}%), {:>3} done, ${:.2} ({})\n",
+                total.agent_id,
+                total.active_secs / 3600.0,
+                total.utilization() * 100.0,
+                total.tasks_completed,
+                total.cost_usd,
+                per_task,
+            ));
+        }
+        
+        output
+    }
+}
+
+fn entry<'a>(
+    days: &'a mut BTreeMap<(NaiveDate, String), AgentUtilization>,
+    date: NaiveDate,
+    agent_id: &str,
+) -> &'a mut AgentUtilization {
+    days.entry((date, agent_id.to_string())).or_insert_with(|| AgentUtilization {
+        agent_id: agent_id.to_string(),
+        date,
+        ..Default::default()
+    })
+}
+
+fn day_start(date: NaiveDate) -> DateTime<Utc> {
+    date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc()
+}
+
+/// Total seconds covered by possibly-overlapping intervals
+fn merged_secs(intervals: &mut [Interval]) -> f64 {
+    intervals.sort_by_key(|(start, _)| *start);
+    
+    let mut total = Duration::zero();
+    let mut current: Option<Interval> = None;
+    for &(start, end) in intervals.iter() {
+        current = match current {
+            Some((cur_start, cur_end)) if start <= cur_end => Some((cur_start, cur_end.max(end))),
+            Some((cur_start, cur_end)) => {
+                total += cur_end - cur_start;
+                Some((start, end))
+            }
+            None => Some((start, end)),
+        };
+    }
+    if let Some((start, end)) = current {
+        total += end - start;
+    }
+    
+    total.num_milliseconds() as f64 / 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    
+    fn at(day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 3, day, hour, 0, 0).unwrap()
+    }
+    
+    fn record(agent: &str, success: bool, cost: f64, timestamp: DateTime<Utc>) -> ExecutionRecord {
+        ExecutionRecord {
+            agent_id: agent.to_string(),
+            task_id: "lm-1".to_string(),
+            task_type: None,
+            success,
+            duration_secs: None,
+            tokens_used: None,
+            cost: Some(cost),
+            timestamp: Some(timestamp),
+        }
+    }
+    
+    #[test]
+    fn test_utilization_by_day() {
+        let spans = vec![
+            // Overlapping sessions count once; the late one spills into the 2nd
+            ("claude".to_string(), at(1, 9), at(1, 11)),
+            ("claude".to_string(), at(1, 10), at(1, 12)),
+            ("claude".to_string(), at(1, 22), at(2, 2)),
+            ("codex".to_string(), at(2, 8), at(2, 9)),
+        ];
+        let records = vec![
+            record("claude", true, 1.50, at(1, 11)),
+            record("claude", false, 0.50, at(1, 12)),
+            record("gemini", true, 0.10, at(2, 5)),
+        ];
+        
+        let report = UtilizationReport::build(&spans, &records, at(1, 0), at(2, 12));
+        let day = |date: u32, agent: &str| report.days.iter()
+            .find(|d| d.date == at(date, 0).date_naive() && d.agent_id == agent)
+            .unwrap();
+        
+        let claude = day(1, "claude");
+        assert_eq!(claude.active_secs, 5.0 * 3600.0);
+        assert_eq!(claude.idle_secs, 19.0 * 3600.0);
+        assert_eq!(claude.sessions, 3);
+        assert_eq!((claude.tasks_completed, claude.tasks_failed), (1, 1));
+        assert!((claude.cost_usd - 2.0).abs() < 1e-9);
+        
+        // The 2nd is cut off at the report end (noon)
+        assert_eq!(day(2, "claude").active_secs, 2.0 * 3600.0);
+        assert_eq!(day(2, "claude").idle_secs, 10.0 * 3600.0);
+        
+        // Executions without sessions still show up, fully idle
+        assert_eq!(day(2, "gemini").active_secs, 0.0);
+        assert_eq!(day(2, "gemini").tasks_completed, 1);
+        
+        let totals = report.by_agent();
+        assert_eq!(totals.len(), 3);
+        assert_eq!(totals[0].active_secs, 7.0 * 3600.0);
+        assert_eq!(report.for_agent("codex").days.len(), 1);
+    }
+}
@@ -70,6 +70,23 @@ pub struct VerificationResult {
     pub timestamp: chrono::DateTime<chrono::Utc>,
 }
 
+/// Entry points tried for environment checks (relative to the directory)
+const ENTRY_POINTS: &[&str] = &["src/index.ts", "src/worker.ts", "index.ts"];
+
+/// The parts of Ground's `CheckReport` JSON the verifier reads
+#[derive(Debug, Deserialize)]
+struct GroundReport {
+    passed: bool,
+    findings: Vec<GroundFinding>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GroundFinding {
+    file: String,
+    line: Option<usize>,
+    message: String,
+}
+
 /// Ground verifier
 pub struct Verifier {
     ground_path: String,
@@ -96,6 +113,9 @@ impl Verifier {
     }
     
     /// Run a verification check
+    ///
+    /// Runs `ground check run <check> --json`, the same code path as the
+    /// Ground CLI and MCP server.
     pub fn check(
         &self,
         check_type: CheckType,
@@ -103,34 +123,42 @@ impl Verifier {
     ) -> Result<VerificationResult, VerifyError> {
         let dir = directory.as_ref();
         
-        let args: Vec<&str> = match check_type {
-            CheckType::Duplicates => vec!["find", "duplicates", "--json"],
-            CheckType::DuplicateFunctions => vec!["find", "duplicate-functions", "--json"],
-            CheckType::DeadCode => vec!["find", "dead-code", "--json"],
-            CheckType::DeadExports => vec!["find", "dead-exports", "--json"],
-            CheckType::Orphans => vec!["find", "orphans", "--json"],
-            CheckType::Environment => vec!["check", "environment", "--json"],
-        };
+        let mut args = vec!["check", "run", check_type.as_str(), ".", "--json"];
+        if check_type == CheckType::Environment {
+            // Environment checks trace from an entry point
+            let Some(entry) = ENTRY_POINTS.iter().find(|e| dir.join(e).is_file()) else {
+                return Ok(VerificationResult {
+                    check_type,
+                    passed: false,
+                    issue_count: 1,
+                    issues: vec![format!("No entry point found (looked for {})", ENTRY_POINTS.join(", "))],
+                    timestamp: chrono::Utc::now(),
+                });
+            };
+            args.extend(["--file", entry]);
+        }
         
         let output = Command::new(&self.ground_path)
             .args(&args)
             .current_dir(dir)
             .output()?;
         
-        // Ground uses exit code 0 for no issues, non-zero for issues found
-        let passed = output.status.success();
         let stdout = String::from_utf8_lossy(&output.stdout);
+        if stdout.trim().is_empty() {
+            return Err(VerifyError::Failed(String::from_utf8_lossy(&output.stderr).trim().to_string()));
+        }
         
-        // Try to parse JSON output
-        let issues: Vec<String> = if !stdout.is_empty() {
-            serde_json::from_str(&stdout).unwrap_or_default()
-        } else {
-            Vec::new()
-        };
+        let report: GroundReport = serde_json::from_str(&stdout)?;
+        let issues: Vec<String> = report.findings.iter()
+            .map(|f| match f.line {
+                Some(line) => format!("{}:{}: {}", f.file, line, f.message),
+                None => format!("{}: {}", f.file, f.message),
+            })
+            .collect();
         
         Ok(VerificationResult {
             check_type,
-            passed,
+            passed: report.passed,
             issue_count: issues.len() as u32,
             issues,
             timestamp: chrono::Utc::now(),
@@ -283,4 +311,25 @@ mod tests {
         assert!(evidence.contains("dead_code"));
         assert!(evidence.contains("FAILED"));
     }
+    
+    #[test]
+    fn test_parses_ground_check_report() {
+        let json = r#"{
+            "kind": "orphans",
+            "scope": { "directory": "." },
+            "findings": [
+                { "file": "src/stray.ts", "line": null, "severity": "warning",
+                  "message": "Nothing imports this module", "symbol": null, "related": null }
+            ],
+            "files_scanned": 3,
+            "passed": false,
+            "duration_ms": 12,
+            "computed_at": "2026-01-01T00:00:00Z"
+        }"#;
+        
+        let report: GroundReport = serde_json::from_str(json).unwrap();
+        assert!(!report.passed);
+        assert_eq!(report.findings.len(), 1);
+        assert_eq!(report.findings[0].file, "src/stray.ts");
+    }
 }
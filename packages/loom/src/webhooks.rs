@@ -0,0 +1,165 @@
+//! Webhooks
+//!
+//! External systems react to Loom activity without polling: the daemon
+//! POSTs a JSON event to each `[[webhooks]]` URL when a task is created,
+//! claimed, completed or blocked. Events are read from the task history, so
+//! changes made by any process (CLI, MCP, HTTP, sync) are delivered in
+//! order, at least once. Each webhook keeps its own place in the history: a
+//! receiver that's down catches up when it comes back, and an event it fails
+//! `max-attempts` times in a row is skipped.
+
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::Sha256;
+
+use crate::config::WebhookConfig;
+use crate::work::{HistoryEntry, Task, WorkError, WorkStore};
+
+/// History entries read per webhook per delivery run
+const BATCH: usize = 100;
+
+/// What one delivery run did for one webhook
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WebhookDelivery {
+    pub url: String,
+    pub delivered: usize,
+    /// History sequence numbers of events given up on
+    pub skipped: Vec<i64>,
+    /// Why the last attempt failed, if it did
+    pub error: Option<String>,
+}
+
+/// The JSON body for a history entry; the task is included as it is now
+pub fn event_payload(entry: &HistoryEntry, task: Option<&Task>) -> Value {
+    json!({
+        "event": entry.event,
+        "seq": entry.seq,
+        "at": entry.at,
+        "task_id": entry.task_id,
+        "from": entry.from,
+        "to": entry.to,
+        "agent": entry.agent,
+        "task": task,
+    })
+}
+
+/// `sha256=<hex HMAC>` of a body
+pub fn signature(secret: &[u8], body: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts any key length");
+    mac.update(body.as_bytes());
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// POST an event to a webhook, signed when its secret is set
+pub fn post(hook: &WebhookConfig, payload: &Value) -> Result<(), String> {
+    let body = payload.to_string();
+    let mut headers = vec![("X-Loom-Event", payload["event"].as_str().unwrap_or_default().to_string())];
+    if let Some(secret) = hook.secret_env.as_deref().and_then(|var| std::env::var(var).ok()) {
+        headers.push(("X-Loom-Signature", signature(secret.as_bytes(), &body)));
+    }
+    crate::net::post_json(&hook.url, &body, &headers)
+}
+
+/// Send each webhook the events recorded since it last ran
+///
+/// A webhook seen for the first time starts from now rather than replaying
+/// the whole history. Delivery stops at the first failure, to be retried on
+/// the next run.
+pub fn deliver(
+    store: &mut WorkStore,
+    hooks: &[WebhookConfig],
+    mut send: impl FnMut(&WebhookConfig, &Value) -> Result<(), String>,
+) -> Result<Vec<WebhookDelivery>, WorkError> {
+    let mut deliveries = Vec::new();
+    
+    for hook in hooks {
+        let Some((mut seq, mut failures)) = store.webhook_cursor(&hook.url)? else {
+            let latest = store.latest_history_seq()?;
+            store.set_webhook_cursor(&hook.url, latest, 0)?;
+            continue;
+        };
+        let mut delivery = WebhookDelivery { url: hook.url.clone(), ..Default::default() };
+        
+        for entry in store.history_since(seq, BATCH)? {
+            let task = store.get(&entry.task_id)?;
+            let wanted = hook.wants(&entry.event)
+                && (hook.labels.is_empty()
+                    || task.as_ref().is_some_and(|t| t.labels.iter().any(|l| hook.labels.contains(l))));
+            if !wanted {
+                seq = entry.seq;
+                continue;
+            }
+            
+            match send(hook, &event_payload(&entry, task.as_ref())) {
+                Ok(()) => {
+                    delivery.delivered += 1;
+                    failures = 0;
+                    seq = entry.seq;
+                }
+                Err(e) => {
+                    delivery.error = Some(e);
+                    failures += 1;
+                    if failures < hook.max_attempts.max(1) {
+                        break;
+                    }
+                    delivery.skipped.push(entry.seq);
+                    failures = 0;
+                    seq = entry.seq;
+                }
+            }
+        }
+        
+        store.set_webhook_cursor(&hook.url, seq, failures)?;
+        deliveries.push(delivery);
+    }
+    
+    Ok(deliveries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::work::CreateTask;
+    
+    #[test]
+    fn test_deliver_resumes_after_failures() {
+        let mut store = WorkStore::in_memory().unwrap();
+        let hook: WebhookConfig = toml::from_str("url = \"https://example.com/hook\"\nmax-attempts = 2").unwrap();
+        let hooks = [hook];
+        
+        store.create(CreateTask { title: "Before the hook".to_string(), ..Default::default() }).unwrap();
+        let first = deliver(&mut store, &hooks, |_, _| panic!("nothing to send yet")).unwrap();
+        assert!(first.is_empty());
+        
+        let task = store.create(CreateTask { title: "Ship".to_string(), ..Default::default() }).unwrap();
+        store.claim(&task.id, "agent-1").unwrap();
+        store.update_priority(&task.id, crate::work::Priority::High).unwrap();
+        store.complete(&task.id, Some("done")).unwrap();
+        
+        // The receiver is down: nothing is lost, the first event is retried
+        let down = deliver(&mut store, &hooks, |_, _| Err("connection refused".to_string())).unwrap();
+        assert_eq!(down[0].delivered, 0);
+        assert_eq!(down[0].error.as_deref(), Some("connection refused"));
+        
+        let mut sent = Vec::new();
+        let up = deliver(&mut store, &hooks, |_, payload| {
+            sent.push(payload["event"].as_str().unwrap().to_string());
+            assert_eq!(payload["task"]["title"], "Ship");
+            Ok(())
+        }).unwrap();
+        // Priority changes aren't sent by default
+        assert_eq!(sent, vec!["created", "claimed", "completed"]);
+        assert_eq!(up[0].delivered, 3);
+        
+        // An event that keeps failing is skipped after max-attempts
+        store.cancel(&task.id).unwrap();
+        let hooks = [WebhookConfig { events: vec!["cancelled".to_string()], ..hooks[0].clone() }];
+        deliver(&mut store, &hooks, |_, _| Err("HTTP 500".to_string())).unwrap();
+        let skipped = deliver(&mut store, &hooks, |_, _| Err("HTTP 500".to_string())).unwrap();
+        assert_eq!(skipped[0].skipped.len(), 1);
+        assert!(deliver(&mut store, &hooks, |_, _| panic!("already skipped")).unwrap()[0].skipped.is_empty());
+        
+        assert!(signature(b"secret", "{}").starts_with("sha256="));
+    }
+}
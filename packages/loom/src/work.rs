@@ -3,13 +3,21 @@
 //! SQLite-backed task persistence with Yegge's beads principles.
 //! This is the external memory that agents read from and write to.
 
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use chrono::{DateTime, Utc};
-use rusqlite::{Connection, params};
+use rusqlite::{Connection, OptionalExtension, params};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use uuid::Uuid;
 
+use crate::evidence::{self, EvidenceEntry, LogIssue, LogVerification};
+use crate::outcome::Outcome;
+use crate::plan::{PlanImport, PlanLink, PlanStep};
+use crate::policy::EvidenceRules;
+use crate::schedule::{Recurrence, Schedule};
+use crate::templates::PlannedTask;
+
 #[derive(Error, Debug)]
 pub enum WorkError {
     #[error("Database error: {0}")]
@@ -24,8 +32,41 @@ pub enum WorkError {
     #[error("Cannot complete task: {reason}")]
     CannotComplete { reason: String },
     
-    #[error("Dependency cycle detected")]
-    CycleDetected,
+    #[error("Task {id} is labeled '{label}' and needs evidence to complete: provide {expected}")]
+    EvidenceRequired { id: String, label: String, expected: String },
+    
+    #[error("Task {0} is not a question")]
+    NotAQuestion(String),
+    
+    #[error("Invalid answer: {0}")]
+    InvalidAnswer(String),
+    
+    #[error("Dependency cycle detected: {}", .0.join(" → "))]
+    CycleDetected(Vec<String>),
+    
+    #[error("Task {id} is paused ({scope}); unpause it (lm unpause, loom_unpause) before claiming")]
+    Paused { id: String, scope: String },
+    
+    #[error("Schedule not found: {0}")]
+    ScheduleNotFound(String),
+    
+    #[error("Invalid schedule: {0}")]
+    InvalidSchedule(String),
+    
+    #[error("Comment body is empty")]
+    EmptyComment,
+    
+    #[error("{agent} holds no lease on task {id}; claim it again")]
+    LeaseNotHeld { id: String, agent: String },
+    
+    #[error("Task {id} is {status}, so it can't be claimed")]
+    NotClaimable { id: String, status: &'static str },
+    
+    #[error("Task {0} isn't done, so there's nothing to reopen")]
+    NotDone(String),
+    
+    #[error("Task {0} isn't done, so there's nothing to review")]
+    NotReviewable(String),
     
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
@@ -65,6 +106,17 @@ pub enum Priority {
     Low,
 }
 
+/// What kind of work item a task is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskKind {
+    /// Work for an agent
+    #[default]
+    Task,
+    /// A decision a human needs to make before dependent work can proceed
+    Question,
+}
+
 impl Status {
     pub fn as_str(&self) -> &'static str {
         match self {
@@ -88,6 +140,27 @@ impl Status {
     }
 }
 
+impl TaskKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskKind::Task => "task",
+            TaskKind::Question => "question",
+        }
+    }
+}
+
+impl std::str::FromStr for TaskKind {
+    type Err = String;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "task" => Ok(TaskKind::Task),
+            "question" => Ok(TaskKind::Question),
+            _ => Err(format!("Unknown task kind: {}", s)),
+        }
+    }
+}
+
 impl Priority {
     pub fn as_str(&self) -> &'static str {
         match self {
@@ -136,6 +209,12 @@ pub struct Task {
     /// When None, task belongs to the primary/local repository
     #[serde(default)]
     pub repo: Option<String>,
+    /// Task or question
+    #[serde(default)]
+    pub kind: TaskKind,
+    /// Progress rolled up from sub-tasks, when read via `WorkStore::children`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rollup: Option<Rollup>,
     /// When the task was created
     pub created_at: DateTime<Utc>,
     /// When the task was last updated
@@ -153,6 +232,168 @@ pub struct Dependency {
     pub created_at: DateTime<Utc>,
 }
 
+/// A task's counterpart in an external issue tracker
+///
+/// The hashes are of the synced fields as each side looked after the last
+/// sync, so the next one can tell which side changed since.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteLink {
+    pub task_id: String,
+    /// Tracker name (e.g. "github")
+    pub tracker: String,
+    /// Issue identifier in the tracker
+    pub remote_id: String,
+    pub url: String,
+    pub local_hash: String,
+    pub remote_hash: String,
+    /// Issue assignee as of the last sync
+    pub remote_assignee: Option<String>,
+    pub synced_at: DateTime<Utc>,
+}
+
+/// What a pause freezes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PauseScope {
+    /// Every task with this label
+    Label(String),
+    /// Every task in this repository
+    Repo(String),
+}
+
+impl PauseScope {
+    fn kind(&self) -> &'static str {
+        match self {
+            PauseScope::Label(_) => "label",
+            PauseScope::Repo(_) => "repo",
+        }
+    }
+    
+    fn value(&self) -> &str {
+        match self {
+            PauseScope::Label(v) | PauseScope::Repo(v) => v,
+        }
+    }
+}
+
+impl std::fmt::Display for PauseScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.kind(), self.value())
+    }
+}
+
+/// A paused label or repo: its tasks stay out of `ready()`, routing, and claims
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Pause {
+    pub scope: PauseScope,
+    pub reason: Option<String>,
+    pub paused_by: Option<String>,
+    pub paused_at: DateTime<Utc>,
+}
+
+impl Pause {
+    /// Whether this pause holds the task
+    pub fn covers(&self, task: &Task) -> bool {
+        match &self.scope {
+            PauseScope::Label(label) => task.labels.contains(label),
+            PauseScope::Repo(repo) => task.repo.as_ref() == Some(repo),
+        }
+    }
+}
+
+/// Cost recorded on a task, from the `cost_ledger` table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Spend {
+    pub task_id: String,
+    /// Agent holding the task when the cost was recorded
+    pub agent: Option<String>,
+    pub amount_usd: f64,
+    pub labels: Vec<String>,
+    pub repo: Option<String>,
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// A time-limited claim on a task. The holder renews it while working;
+/// once it expires, another agent can claim the task.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Lease {
+    pub task_id: String,
+    pub agent: String,
+    pub acquired_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub renewals: u32,
+}
+
+impl Lease {
+    pub fn is_expired(&self, at: DateTime<Utc>) -> bool {
+        self.expires_at <= at
+    }
+}
+
+/// A claim released because nothing happened on it for too long
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StaleClaim {
+    pub task_id: String,
+    pub agent: String,
+    /// Last activity on the claim: a task update, checkpoint, or heartbeat
+    pub idle_since: DateTime<Utc>,
+    /// Session flagged for recovery, if the task had an active one
+    pub session_id: Option<String>,
+}
+
+/// State carried through one plan import
+struct PlanPass {
+    import: PlanImport,
+    /// (task, blocked by before, blocked by now) for every step
+    order: Vec<(String, Option<String>, Option<String>)>,
+    /// Tasks of checked-off steps
+    checked: Vec<String>,
+}
+
+/// A note left on a task, so agents can add context without rewriting the description
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Comment {
+    pub id: i64,
+    pub task_id: String,
+    pub author: String,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// One auto-dispatch decision about a task, from the `dispatch_audit` table
+///
+/// `action` is `dispatched` or `approval_requested`; `question` is the
+/// approval question asked in the latter case.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DispatchAudit {
+    pub id: i64,
+    pub task_id: String,
+    pub agent: String,
+    pub confidence: f64,
+    pub threshold: f64,
+    pub action: String,
+    pub question: Option<String>,
+    pub at: DateTime<Utc>,
+}
+
+/// A recorded step in a task's life, from the append-only `task_history` table
+///
+/// `event` is one of `created`, `claimed`, `released`, `completed`,
+/// `cancelled`, `blocked`, `unblocked`, `reopened`, `status` (any other
+/// status change), or `priority`. `from`/`to` hold the old and new status
+/// or priority.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub seq: i64,
+    pub task_id: String,
+    pub event: String,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    /// Agent holding the task at the time
+    pub agent: Option<String>,
+    pub at: DateTime<Utc>,
+}
+
 /// Parameters for creating a new task
 #[derive(Debug, Clone, Default)]
 pub struct CreateTask {
@@ -164,6 +405,40 @@ pub struct CreateTask {
     pub evidence: Option<String>,
     /// Repository identifier (for multi-repo tracking)
     pub repo: Option<String>,
+    /// Task or question
+    pub kind: TaskKind,
+}
+
+/// Decision details attached to a question task
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Question {
+    /// The question task
+    pub task_id: String,
+    /// Allowed answers (any answer is accepted when empty)
+    pub options: Vec<String>,
+    /// When the default decision applies if nobody has answered
+    pub deadline: Option<DateTime<Utc>>,
+    /// Decision applied when the deadline passes
+    pub default_decision: Option<String>,
+    /// The recorded decision
+    pub decision: Option<String>,
+    pub rationale: Option<String>,
+    /// Who answered (or "default" when the deadline applied it)
+    pub answered_by: Option<String>,
+    pub answered_at: Option<DateTime<Utc>>,
+}
+
+impl Question {
+    pub fn is_answered(&self) -> bool {
+        self.decision.is_some()
+    }
+    
+    /// Unanswered, past its deadline, and has a default to fall back on
+    pub fn is_overdue(&self, now: DateTime<Utc>) -> bool {
+        !self.is_answered()
+            && self.default_decision.is_some()
+            && self.deadline.map(|d| d <= now).unwrap_or(false)
+    }
 }
 
 /// The work store - SQLite-backed task persistence
@@ -172,6 +447,16 @@ pub struct WorkStore {
     prefix: String,
     /// Default repository for new tasks
     default_repo: Option<String>,
+    /// Append completion evidence to the hash-chained log
+    sign_evidence: bool,
+    /// HMAC key for signing log entries
+    signing_key: Option<Vec<u8>>,
+    /// Evidence that labeled tasks must carry to complete
+    evidence_rules: EvidenceRules,
+    /// Complete a parent once its last open sub-task completes
+    auto_complete_parents: bool,
+    /// How long a claim lasts without renewal
+    lease_duration: chrono::Duration,
 }
 
 impl WorkStore {
@@ -186,6 +471,11 @@ impl WorkStore {
             conn, 
             prefix: "lm".to_string(),
             default_repo: None,
+            sign_evidence: false,
+            signing_key: None,
+            evidence_rules: EvidenceRules::default(),
+            auto_complete_parents: false,
+            lease_duration: chrono::Duration::minutes(30),
         };
         store.init_schema()?;
         Ok(store)
@@ -198,6 +488,11 @@ impl WorkStore {
             conn,
             prefix: "lm".to_string(),
             default_repo: None,
+            sign_evidence: false,
+            signing_key: None,
+            evidence_rules: EvidenceRules::default(),
+            auto_complete_parents: false,
+            lease_duration: chrono::Duration::minutes(30),
         };
         store.init_schema()?;
         Ok(store)
@@ -217,7 +512,7 @@ impl WorkStore {
     
     fn init_schema(&self) -> Result<(), WorkError> {
         // Create base tables first
-        // Column order MUST match TASK_COLUMNS: id, title, description, status, priority, agent, labels, parent, evidence, actual_cost_usd, repo, created_at, updated_at
+        // Column order MUST match TASK_COLUMNS: id, title, description, status, priority, agent, labels, parent, evidence, actual_cost_usd, repo, created_at, updated_at, kind
         self.conn.execute_batch(r#"
             CREATE TABLE IF NOT EXISTS tasks (
                 id TEXT PRIMARY KEY,
@@ -232,7 +527,8 @@ impl WorkStore {
                 actual_cost_usd REAL,
                 repo TEXT,
                 created_at TEXT NOT NULL,
-                updated_at TEXT NOT NULL
+                updated_at TEXT NOT NULL,
+                kind TEXT NOT NULL DEFAULT 'task'
             );
             
             CREATE TABLE IF NOT EXISTS dependencies (
@@ -243,6 +539,207 @@ impl WorkStore {
                 FOREIGN KEY (task_id) REFERENCES tasks(id),
                 FOREIGN KEY (depends_on) REFERENCES tasks(id)
             );
+            
+            CREATE TABLE IF NOT EXISTS subscriptions (
+                agent TEXT NOT NULL,
+                label TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                PRIMARY KEY (agent, label)
+            );
+            
+            CREATE TABLE IF NOT EXISTS questions (
+                task_id TEXT PRIMARY KEY,
+                options TEXT NOT NULL DEFAULT '[]',
+                deadline TEXT,
+                default_decision TEXT,
+                decision TEXT,
+                rationale TEXT,
+                answered_by TEXT,
+                answered_at TEXT,
+                FOREIGN KEY (task_id) REFERENCES tasks(id)
+            );
+            
+            CREATE TABLE IF NOT EXISTS evidence_log (
+                seq INTEGER PRIMARY KEY AUTOINCREMENT,
+                task_id TEXT NOT NULL,
+                evidence TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                prev_hash TEXT NOT NULL,
+                hash TEXT NOT NULL,
+                signature TEXT
+            );
+            
+            CREATE TABLE IF NOT EXISTS remote_links (
+                task_id TEXT NOT NULL,
+                tracker TEXT NOT NULL,
+                remote_id TEXT NOT NULL,
+                url TEXT NOT NULL DEFAULT '',
+                local_hash TEXT NOT NULL,
+                remote_hash TEXT NOT NULL,
+                remote_assignee TEXT,
+                synced_at TEXT NOT NULL,
+                PRIMARY KEY (task_id, tracker),
+                FOREIGN KEY (task_id) REFERENCES tasks(id)
+            );
+            
+            CREATE TABLE IF NOT EXISTS agent_activity (
+                agent TEXT NOT NULL,
+                action TEXT NOT NULL,
+                task_id TEXT NOT NULL,
+                at TEXT NOT NULL
+            );
+            
+            -- Every change to a task's recorded cost, for spend per day
+            CREATE TABLE IF NOT EXISTS cost_ledger (
+                task_id TEXT NOT NULL,
+                agent TEXT,
+                amount_usd REAL NOT NULL,
+                recorded_at TEXT NOT NULL
+            );
+            
+            CREATE INDEX IF NOT EXISTS idx_cost_ledger_recorded ON cost_ledger(recorded_at);
+            
+            CREATE TABLE IF NOT EXISTS leases (
+                task_id TEXT PRIMARY KEY,
+                agent TEXT NOT NULL,
+                acquired_at TEXT NOT NULL,
+                expires_at TEXT NOT NULL,
+                renewals INTEGER NOT NULL DEFAULT 0
+            );
+            
+            CREATE INDEX IF NOT EXISTS idx_leases_expires ON leases(expires_at);
+            
+            CREATE TABLE IF NOT EXISTS task_outcomes (
+                task_id TEXT PRIMARY KEY,
+                outcome_json TEXT NOT NULL,
+                recorded_at TEXT NOT NULL
+            );
+            
+            -- Tasks imported from a plan, and the step each came from
+            CREATE TABLE IF NOT EXISTS plan_items (
+                task_id TEXT PRIMARY KEY,
+                root_id TEXT NOT NULL,
+                parent_id TEXT NOT NULL,
+                step_key TEXT NOT NULL,
+                position INTEGER NOT NULL,
+                section TEXT NOT NULL,
+                anchor TEXT NOT NULL,
+                line INTEGER NOT NULL,
+                after_id TEXT,
+                imported_at TEXT NOT NULL
+            );
+            
+            CREATE INDEX IF NOT EXISTS idx_plan_items_parent ON plan_items(parent_id);
+            
+            -- How far each webhook has got through task_history
+            CREATE TABLE IF NOT EXISTS webhook_cursors (
+                url TEXT PRIMARY KEY,
+                seq INTEGER NOT NULL,
+                failures INTEGER NOT NULL DEFAULT 0
+            );
+            
+            -- @mentions in task text, numbered as they're first seen
+            CREATE TABLE IF NOT EXISTS mentions (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                handle TEXT NOT NULL,
+                task_id TEXT NOT NULL,
+                line TEXT NOT NULL,
+                seen_at TEXT NOT NULL,
+                UNIQUE (handle, task_id, line)
+            );
+            
+            -- Newest mention each stakeholder has been sent
+            CREATE TABLE IF NOT EXISTS mention_cursors (
+                handle TEXT PRIMARY KEY,
+                id INTEGER NOT NULL
+            );
+            
+            CREATE TABLE IF NOT EXISTS pauses (
+                kind TEXT NOT NULL,
+                value TEXT NOT NULL,
+                reason TEXT,
+                paused_by TEXT,
+                paused_at TEXT NOT NULL,
+                PRIMARY KEY (kind, value)
+            );
+            
+            CREATE TABLE IF NOT EXISTS task_history (
+                seq INTEGER PRIMARY KEY AUTOINCREMENT,
+                task_id TEXT NOT NULL,
+                event TEXT NOT NULL,
+                from_value TEXT,
+                to_value TEXT,
+                agent TEXT,
+                at TEXT NOT NULL
+            );
+            
+            CREATE TABLE IF NOT EXISTS comments (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                task_id TEXT NOT NULL,
+                author TEXT NOT NULL,
+                body TEXT NOT NULL,
+                created_at TEXT NOT NULL
+            );
+            
+            -- Reviewers who approved a task's current completion
+            CREATE TABLE IF NOT EXISTS review_approvals (
+                task_id TEXT NOT NULL,
+                reviewer TEXT NOT NULL,
+                at TEXT NOT NULL,
+                PRIMARY KEY (task_id, reviewer)
+            );
+            
+            CREATE TABLE IF NOT EXISTS dispatch_audit (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                task_id TEXT NOT NULL,
+                agent TEXT NOT NULL,
+                confidence REAL NOT NULL,
+                threshold REAL NOT NULL,
+                action TEXT NOT NULL,
+                question TEXT,
+                at TEXT NOT NULL
+            );
+            
+            CREATE TABLE IF NOT EXISTS schedules (
+                id TEXT PRIMARY KEY,
+                title TEXT NOT NULL,
+                description TEXT,
+                priority TEXT NOT NULL DEFAULT 'normal',
+                labels TEXT NOT NULL DEFAULT '[]',
+                parent TEXT,
+                cron TEXT,
+                due_at TEXT,
+                next_run TEXT,
+                last_run TEXT,
+                last_task TEXT,
+                created_at TEXT NOT NULL
+            );
+            
+            -- Task history is append-only too
+            CREATE TRIGGER IF NOT EXISTS task_history_no_update
+            BEFORE UPDATE ON task_history
+            BEGIN
+                SELECT RAISE(ABORT, 'task_history is append-only');
+            END;
+            
+            CREATE TRIGGER IF NOT EXISTS task_history_no_delete
+            BEFORE DELETE ON task_history
+            BEGIN
+                SELECT RAISE(ABORT, 'task_history is append-only');
+            END;
+            
+            -- The evidence log is append-only
+            CREATE TRIGGER IF NOT EXISTS evidence_log_no_update
+            BEFORE UPDATE ON evidence_log
+            BEGIN
+                SELECT RAISE(ABORT, 'evidence_log is append-only');
+            END;
+            
+            CREATE TRIGGER IF NOT EXISTS evidence_log_no_delete
+            BEFORE DELETE ON evidence_log
+            BEGIN
+                SELECT RAISE(ABORT, 'evidence_log is append-only');
+            END;
         "#)?;
         
         // Migration: add columns if they don't exist (for existing databases)
@@ -250,6 +747,9 @@ impl WorkStore {
         let _ = self.conn.execute("ALTER TABLE tasks ADD COLUMN priority TEXT NOT NULL DEFAULT 'normal'", []);
         let _ = self.conn.execute("ALTER TABLE tasks ADD COLUMN actual_cost_usd REAL", []);
         let _ = self.conn.execute("ALTER TABLE tasks ADD COLUMN repo TEXT", []);
+        let _ = self.conn.execute("ALTER TABLE tasks ADD COLUMN kind TEXT NOT NULL DEFAULT 'task'", []);
+        let _ = self.conn.execute("ALTER TABLE cost_ledger ADD COLUMN agent TEXT", []);
+        let _ = self.conn.execute("ALTER TABLE remote_links ADD COLUMN remote_assignee TEXT", []);
         
         // Create indexes (after columns exist)
         self.conn.execute_batch(r#"
@@ -259,6 +759,58 @@ impl WorkStore {
             CREATE INDEX IF NOT EXISTS idx_tasks_parent ON tasks(parent);
             CREATE INDEX IF NOT EXISTS idx_deps_task ON dependencies(task_id);
             CREATE INDEX IF NOT EXISTS idx_deps_depends ON dependencies(depends_on);
+            CREATE INDEX IF NOT EXISTS idx_evidence_task ON evidence_log(task_id);
+            CREATE INDEX IF NOT EXISTS idx_activity_agent ON agent_activity(agent, action, at);
+            CREATE INDEX IF NOT EXISTS idx_schedules_next ON schedules(next_run);
+            CREATE INDEX IF NOT EXISTS idx_comments_task ON comments(task_id, id);
+            CREATE INDEX IF NOT EXISTS idx_dispatch_audit_task ON dispatch_audit(task_id, id);
+            CREATE INDEX IF NOT EXISTS idx_history_task ON task_history(task_id, seq);
+        "#)?;
+        
+        // Record every transition in task_history, whichever code path (or
+        // bulk UPDATE, like recompute_blocked_status) makes it. The status
+        // trigger is recreated so older stores pick up new events.
+        self.conn.execute_batch(r#"
+            DROP TRIGGER IF EXISTS task_history_status;
+            
+            CREATE TRIGGER IF NOT EXISTS task_history_created
+            AFTER INSERT ON tasks
+            BEGIN
+                INSERT INTO task_history (task_id, event, to_value, agent, at)
+                VALUES (NEW.id, 'created', NEW.status, NEW.agent, strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now'));
+            END;
+            
+            CREATE TRIGGER IF NOT EXISTS task_history_status
+            AFTER UPDATE OF status ON tasks
+            WHEN OLD.status IS NOT NEW.status
+            BEGIN
+                INSERT INTO task_history (task_id, event, from_value, to_value, agent, at)
+                VALUES (
+                    NEW.id,
+                    CASE
+                        WHEN NEW.status = 'claimed' THEN 'claimed'
+                        WHEN NEW.status = 'done' THEN 'completed'
+                        WHEN NEW.status = 'cancelled' THEN 'cancelled'
+                        WHEN NEW.status = 'blocked' THEN 'blocked'
+                        WHEN NEW.status = 'ready' AND OLD.status = 'claimed' THEN 'released'
+                        WHEN NEW.status = 'ready' AND OLD.status = 'blocked' THEN 'unblocked'
+                        WHEN NEW.status = 'ready' AND OLD.status = 'done' THEN 'reopened'
+                        ELSE 'status'
+                    END,
+                    OLD.status,
+                    NEW.status,
+                    COALESCE(NEW.agent, OLD.agent),
+                    strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now')
+                );
+            END;
+            
+            CREATE TRIGGER IF NOT EXISTS task_history_priority
+            AFTER UPDATE OF priority ON tasks
+            WHEN OLD.priority IS NOT NEW.priority
+            BEGIN
+                INSERT INTO task_history (task_id, event, from_value, to_value, agent, at)
+                VALUES (NEW.id, 'priority', OLD.priority, NEW.priority, NEW.agent, strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now'));
+            END;
         "#)?;
         
         Ok(())
@@ -285,8 +837,8 @@ impl WorkStore {
         let repo = params.repo.or_else(|| self.default_repo.clone());
         
         self.conn.execute(
-            r#"INSERT INTO tasks (id, title, description, status, priority, labels, parent, evidence, repo, created_at, updated_at)
-               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)"#,
+            r#"INSERT INTO tasks (id, title, description, status, priority, labels, parent, evidence, repo, created_at, updated_at, kind)
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)"#,
             params![
                 id,
                 params.title,
@@ -299,6 +851,7 @@ impl WorkStore {
                 repo,
                 now.to_rfc3339(),
                 now.to_rfc3339(),
+                params.kind.as_str(),
             ],
         )?;
         
@@ -314,11 +867,238 @@ impl WorkStore {
             evidence: params.evidence,
             actual_cost_usd: None,
             repo,
+            kind: params.kind,
+            rollup: None,
             created_at: now,
             updated_at: now,
         })
     }
     
+    /// Create a task tree (from a template) under an optional parent, all or
+    /// nothing. Returns the tasks parents-first; the root is first.
+    pub fn create_tree(&mut self, plan: &PlannedTask, parent: Option<&str>) -> Result<Vec<Task>, WorkError> {
+        if let Some(parent) = parent {
+            self.get(parent)?.ok_or_else(|| WorkError::NotFound(parent.to_string()))?;
+        }
+        
+        self.conn.execute_batch("BEGIN")?;
+        let mut created = Vec::with_capacity(plan.count());
+        match self.create_subtree(plan, parent.map(str::to_string), &mut created) {
+            Ok(()) => {
+                self.conn.execute_batch("COMMIT")?;
+                Ok(created)
+            }
+            Err(e) => {
+                let _ = self.conn.execute_batch("ROLLBACK");
+                Err(e)
+            }
+        }
+    }
+    
+    fn create_subtree(&mut self, plan: &PlannedTask, parent: Option<String>, created: &mut Vec<Task>) -> Result<(), WorkError> {
+        let task = self.create(CreateTask {
+            title: plan.title.clone(),
+            description: plan.description.clone(),
+            priority: plan.priority,
+            labels: plan.labels.clone(),
+            parent,
+            ..Default::default()
+        })?;
+        let id = task.id.clone();
+        created.push(task);
+        for child in &plan.children {
+            self.create_subtree(child, Some(id.clone()), created)?;
+        }
+        Ok(())
+    }
+    
+    /// Import plan steps as sub-tasks of `root`, all or nothing
+    ///
+    /// Steps are matched to the tasks of an earlier import under the same
+    /// parent by title, then by position, so an edited step updates its task
+    /// instead of adding another. Each step is blocked by the one before it;
+    /// checked-off steps aren't blocked and are completed. Tasks whose steps
+    /// were removed are cancelled unless someone has started on them.
+    pub fn import_plan(&mut self, root: &str, steps: &[PlanStep]) -> Result<PlanImport, WorkError> {
+        self.get(root)?.ok_or_else(|| WorkError::NotFound(root.to_string()))?;
+        
+        self.conn.execute_batch("BEGIN")?;
+        let mut pass = PlanPass {
+            import: PlanImport { root_id: root.to_string(), ..Default::default() },
+            order: Vec::new(),
+            checked: Vec::new(),
+        };
+        match self.apply_plan(root, steps, &mut pass) {
+            Ok(()) => {
+                self.conn.execute_batch("COMMIT")?;
+                Ok(pass.import)
+            }
+            Err(e) => {
+                let _ = self.conn.execute_batch("ROLLBACK");
+                Err(e)
+            }
+        }
+    }
+    
+    fn apply_plan(&mut self, root: &str, steps: &[PlanStep], pass: &mut PlanPass) -> Result<(), WorkError> {
+        self.import_steps(root, root, None, steps, pass)?;
+        
+        // Drop every stale edge before adding new ones, so reordered steps
+        // don't look like a cycle halfway through
+        for (task, before, now) in &pass.order {
+            if let Some(before) = before.as_deref().filter(|b| Some(*b) != now.as_deref()) {
+                self.remove_dependency(task, before)?;
+            }
+        }
+        for (task, before, now) in &pass.order {
+            if let Some(now) = now.as_deref().filter(|n| Some(*n) != before.as_deref()) {
+                self.add_dependency(task, now)?;
+            }
+        }
+        
+        for id in std::mem::take(&mut pass.checked) {
+            let open = self.get(&id)?.is_some_and(|t| !matches!(t.status, Status::Done | Status::Cancelled));
+            if !open {
+                continue;
+            }
+            match self.complete(&id, Some("Checked off in the plan")) {
+                Ok(_) => pass.import.completed.push(id),
+                Err(WorkError::CannotComplete { .. } | WorkError::EvidenceRequired { .. }) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+    
+    /// `waits_on` blocks the first step, so the steps of a phase wait for
+    /// the phase before it
+    fn import_steps(
+        &mut self,
+        root: &str,
+        parent: &str,
+        waits_on: Option<String>,
+        steps: &[PlanStep],
+        pass: &mut PlanPass,
+    ) -> Result<(), WorkError> {
+        // (task, step key, position, blocked by) from the last import
+        let mut stmt = self.conn.prepare(
+            "SELECT task_id, step_key, position, after_id FROM plan_items WHERE root_id = ?1 AND parent_id = ?2 ORDER BY position"
+        )?;
+        let existing = stmt.query_map(params![root, parent], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, i64>(2)? as usize, row.get::<_, Option<String>>(3)?))
+        })?.collect::<Result<Vec<_>, _>>()?;
+        drop(stmt);
+        
+        // Match by title first, then whatever is left by position
+        let mut matched: Vec<Option<usize>> = vec![None; steps.len()];
+        let mut used = vec![false; existing.len()];
+        for (i, step) in steps.iter().enumerate() {
+            let key = step.key();
+            if let Some(j) = (0..existing.len()).find(|&j| !used[j] && existing[j].1 == key) {
+                matched[i] = Some(j);
+                used[j] = true;
+            }
+        }
+        for (i, slot) in matched.iter_mut().enumerate() {
+            if slot.is_none() {
+                if let Some(j) = (0..existing.len()).find(|&j| !used[j] && existing[j].2 == i) {
+                    *slot = Some(j);
+                    used[j] = true;
+                }
+            }
+        }
+        
+        let mut previous = waits_on;
+        for (i, step) in steps.iter().enumerate() {
+            let (id, before) = match matched[i] {
+                Some(j) => {
+                    let (id, _, position, before) = &existing[j];
+                    let task = self.get(id)?.ok_or_else(|| WorkError::NotFound(id.clone()))?;
+                    let edited = task.title != step.title || task.description != step.description;
+                    if edited && !matches!(task.status, Status::Done | Status::Cancelled) {
+                        self.update_details(id, &step.title, step.description.as_deref(), &task.labels)?;
+                    }
+                    if edited || *position != i {
+                        pass.import.updated.push(id.clone());
+                    } else {
+                        pass.import.unchanged += 1;
+                    }
+                    (id.clone(), before.clone())
+                }
+                None => {
+                    let task = self.create(CreateTask {
+                        title: step.title.clone(),
+                        description: step.description.clone(),
+                        parent: Some(parent.to_string()),
+                        ..Default::default()
+                    })?;
+                    pass.import.created.push(task.id.clone());
+                    (task.id, None)
+                }
+            };
+            
+            let after = if step.checked { None } else { previous.clone() };
+            self.conn.execute(
+                r#"INSERT OR REPLACE INTO plan_items
+                   (task_id, root_id, parent_id, step_key, position, section, anchor, line, after_id, imported_at)
+                   VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)"#,
+                params![id, root, parent, step.key(), i as i64, step.section, step.anchor, step.line as i64, after, Utc::now().to_rfc3339()],
+            )?;
+            pass.order.push((id.clone(), before, after.clone()));
+            if step.checked {
+                pass.checked.push(id.clone());
+            }
+            
+            self.import_steps(root, &id, after, &step.children, pass)?;
+            previous = Some(id);
+        }
+        
+        for (j, (id, ..)) in existing.iter().enumerate() {
+            if !used[j] {
+                self.remove_plan_item(id, &mut pass.import)?;
+            }
+        }
+        Ok(())
+    }
+    
+    /// Detach a task whose step left the plan, with the steps under it,
+    /// cancelling those nobody has started
+    fn remove_plan_item(&mut self, id: &str, import: &mut PlanImport) -> Result<(), WorkError> {
+        let mut stmt = self.conn.prepare("SELECT task_id FROM plan_items WHERE parent_id = ?1")?;
+        let children = stmt.query_map(params![id], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        drop(stmt);
+        for child in children {
+            self.remove_plan_item(&child, import)?;
+        }
+        
+        self.conn.execute("DELETE FROM plan_items WHERE task_id = ?1", params![id])?;
+        match self.get(id)?.map(|t| t.status) {
+            Some(Status::Ready | Status::Blocked) => {
+                self.cancel(id)?;
+                import.cancelled.push(id.to_string());
+            }
+            Some(Status::Claimed) => import.detached.push(id.to_string()),
+            _ => {}
+        }
+        Ok(())
+    }
+    
+    /// The plan step a task was imported from
+    pub fn plan_link(&self, id: &str) -> Result<Option<PlanLink>, WorkError> {
+        Ok(self.conn.query_row(
+            "SELECT task_id, root_id, section, anchor, line FROM plan_items WHERE task_id = ?1",
+            params![id],
+            |row| Ok(PlanLink {
+                task_id: row.get(0)?,
+                root_id: row.get(1)?,
+                section: row.get(2)?,
+                anchor: row.get(3)?,
+                line: row.get::<_, i64>(4)? as usize,
+            }),
+        ).optional()?)
+    }
+    
     /// Get a task by ID
     pub fn get(&self, id: &str) -> Result<Option<Task>, WorkError> {
         let sql = format!(
@@ -356,6 +1136,8 @@ impl WorkStore {
             evidence: row.get(8)?,
             actual_cost_usd: row.get(9)?,
             repo: row.get(10)?,
+            kind: row.get::<_, String>(13).unwrap_or_default().parse().unwrap_or_default(),
+            rollup: None,
             created_at: DateTime::parse_from_rfc3339(&created_str)
                 .map(|dt| dt.with_timezone(&Utc))
                 .unwrap_or_else(|_| Utc::now()),
@@ -366,7 +1148,7 @@ impl WorkStore {
     }
     
     /// Standard SELECT columns for tasks
-    const TASK_COLUMNS: &'static str = "id, title, description, status, priority, agent, labels, parent, evidence, actual_cost_usd, repo, created_at, updated_at";
+    const TASK_COLUMNS: &'static str = "id, title, description, status, priority, agent, labels, parent, evidence, actual_cost_usd, repo, created_at, updated_at, kind";
     
     /// Update a task's status
     pub fn update_status(&mut self, id: &str, status: Status) -> Result<(), WorkError> {
@@ -382,6 +1164,26 @@ impl WorkStore {
         Ok(())
     }
     
+    /// Update a task's title, description, and labels
+    pub fn update_details(
+        &mut self,
+        id: &str,
+        title: &str,
+        description: Option<&str>,
+        labels: &[String],
+    ) -> Result<(), WorkError> {
+        let now = Utc::now();
+        let rows = self.conn.execute(
+            "UPDATE tasks SET title = ?1, description = ?2, labels = ?3, updated_at = ?4 WHERE id = ?5",
+            params![title, description, serde_json::to_string(labels)?, now.to_rfc3339(), id],
+        )?;
+        
+        if rows == 0 {
+            return Err(WorkError::NotFound(id.to_string()));
+        }
+        Ok(())
+    }
+    
     /// Update a task's priority
     pub fn update_priority(&mut self, id: &str, priority: Priority) -> Result<(), WorkError> {
         let now = Utc::now();
@@ -396,42 +1198,179 @@ impl WorkStore {
         Ok(())
     }
     
-    /// Claim a task for an agent
-    pub fn claim(&mut self, id: &str, agent: &str) -> Result<Task, WorkError> {
+    /// Claim a task for an agent, returning the lease it must renew
+    ///
+    /// Only ready tasks can be claimed, or claimed ones again by their
+    /// holder. A task claimed by another agent can be taken over once that
+    /// agent's lease has expired.
+    pub fn claim(&mut self, id: &str, agent: &str) -> Result<Lease, WorkError> {
         let task = self.get(id)?.ok_or_else(|| WorkError::NotFound(id.to_string()))?;
-        
-        // Check if already claimed by someone else
-        if let Some(ref current_agent) = task.agent {
-            if current_agent != agent && task.status == Status::Claimed {
-                return Err(WorkError::AlreadyClaimed(current_agent.clone()));
-            }
+        if let Some(pause) = self.pause_covering(&task)? {
+            return Err(WorkError::Paused { id: id.to_string(), scope: pause.scope.to_string() });
         }
         
+        // The check and the update are one statement, in one transaction
+        // with the lease, so two agents racing for a task can't both win
         let now = Utc::now();
-        self.conn.execute(
-            "UPDATE tasks SET status = 'claimed', agent = ?1, updated_at = ?2 WHERE id = ?3",
+        let tx = self.conn.transaction()?;
+        let claimed = tx.execute(
+            "UPDATE tasks SET status = 'claimed', agent = ?1, updated_at = ?2
+             WHERE id = ?3 AND (status = 'ready' OR (status = 'claimed' AND (agent = ?1 OR EXISTS (
+                 SELECT 1 FROM leases l WHERE l.task_id = tasks.id AND l.agent = tasks.agent AND l.expires_at < ?2
+             ))))",
             params![agent, now.to_rfc3339(), id],
         )?;
-        
-        self.get(id)?.ok_or_else(|| WorkError::NotFound(id.to_string()))
+        if claimed != 1 {
+            drop(tx);
+            let task = self.get(id)?.ok_or_else(|| WorkError::NotFound(id.to_string()))?;
+            return Err(match (task.status, task.agent) {
+                (Status::Claimed, Some(holder)) => WorkError::AlreadyClaimed(holder),
+                (status, _) => WorkError::NotClaimable { id: id.to_string(), status: status.as_str() },
+            });
+        }
+        let lease = Self::grant_lease(&tx, self.lease_duration, id, agent, now)?;
+        tx.commit()?;
+        Ok(lease)
     }
     
-    /// Release a claimed task back to ready
-    pub fn release(&mut self, id: &str) -> Result<(), WorkError> {
+    /// Claim a task only if it is still ready
+    ///
+    /// The status check and the update happen in a single statement, so two
+    /// agents racing for the same task can never both win. Returns `None`
+    /// when another agent got there first.
+    pub fn try_claim(&mut self, id: &str, agent: &str) -> Result<Option<Task>, WorkError> {
         let now = Utc::now();
-        let rows = self.conn.execute(
-            "UPDATE tasks SET status = 'ready', agent = NULL, updated_at = ?1 WHERE id = ?2",
+        let tx = self.conn.transaction()?;
+        let rows = tx.execute(
+            "UPDATE tasks SET status = 'claimed', agent = ?1, updated_at = ?2 WHERE id = ?3 AND status = 'ready'",
+            params![agent, now.to_rfc3339(), id],
+        )?;
+        
+        if rows == 0 {
+            return Ok(None);
+        }
+        Self::grant_lease(&tx, self.lease_duration, id, agent, now)?;
+        tx.commit()?;
+        self.get(id)
+    }
+    
+    fn grant_lease(conn: &Connection, duration: chrono::Duration, id: &str, agent: &str, now: DateTime<Utc>) -> Result<Lease, WorkError> {
+        let lease = Lease {
+            task_id: id.to_string(),
+            agent: agent.to_string(),
+            acquired_at: now,
+            expires_at: now + duration,
+            renewals: 0,
+        };
+        conn.execute(
+            "INSERT OR REPLACE INTO leases (task_id, agent, acquired_at, expires_at, renewals) VALUES (?1, ?2, ?3, ?4, 0)",
+            params![id, agent, now.to_rfc3339(), lease.expires_at.to_rfc3339()],
+        )?;
+        Ok(lease)
+    }
+    
+    /// Extend `agent`'s lease on a task by the lease duration from now
+    ///
+    /// Fails if the agent doesn't hold the task, including when its lease
+    /// expired and another agent has claimed it since.
+    pub fn renew(&mut self, id: &str, agent: &str) -> Result<Lease, WorkError> {
+        let lease = self.get_lease(id)?
+            .filter(|lease| lease.agent == agent)
+            .ok_or_else(|| WorkError::LeaseNotHeld { id: id.to_string(), agent: agent.to_string() })?;
+        
+        let expires_at = Utc::now() + self.lease_duration;
+        self.conn.execute(
+            "UPDATE leases SET expires_at = ?1, renewals = renewals + 1 WHERE task_id = ?2",
+            params![expires_at.to_rfc3339(), id],
+        )?;
+        Ok(Lease { expires_at, renewals: lease.renewals + 1, ..lease })
+    }
+    
+    /// Fail unless `agent` holds the lease on a claimed task
+    pub fn check_lease(&self, id: &str, agent: &str) -> Result<(), WorkError> {
+        match self.get_lease(id)? {
+            Some(lease) if lease.agent == agent => Ok(()),
+            _ => Err(WorkError::LeaseNotHeld { id: id.to_string(), agent: agent.to_string() }),
+        }
+    }
+    
+    /// The lease on a claimed task, if it was claimed with one
+    pub fn get_lease(&self, id: &str) -> Result<Option<Lease>, WorkError> {
+        Ok(self.query_leases("AND l.task_id = ?1", params![id])?.into_iter().next())
+    }
+    
+    /// Leases on claimed tasks that expire within `within` (or already
+    /// have), soonest first. Expired ones can be claimed by another agent.
+    pub fn expiring_leases(&self, within: chrono::Duration) -> Result<Vec<Lease>, WorkError> {
+        let before = (Utc::now() + within).to_rfc3339();
+        self.query_leases("AND l.expires_at <= ?1", params![before])
+    }
+    
+    fn query_leases(&self, filter: &str, params: impl rusqlite::Params) -> Result<Vec<Lease>, WorkError> {
+        // Leases left over from claims that have since ended are ignored
+        let sql = format!(
+            "SELECT l.task_id, l.agent, l.acquired_at, l.expires_at, l.renewals FROM leases l
+             JOIN tasks t ON t.id = l.task_id AND t.agent = l.agent
+             WHERE t.status = 'claimed' {} ORDER BY l.expires_at",
+            filter
+        );
+        let parse = |s: String| DateTime::parse_from_rfc3339(&s).map(|dt| dt.with_timezone(&Utc)).unwrap_or_else(|_| Utc::now());
+        let mut stmt = self.conn.prepare(&sql)?;
+        let leases = stmt.query_map(params, |row| {
+            Ok(Lease {
+                task_id: row.get(0)?,
+                agent: row.get(1)?,
+                acquired_at: parse(row.get(2)?),
+                expires_at: parse(row.get(3)?),
+                renewals: row.get(4)?,
+            })
+        })?.collect::<Result<Vec<_>, _>>()?;
+        Ok(leases)
+    }
+    
+    /// Claimed tasks not updated since `cutoff`, oldest first
+    pub fn claims_idle_since(&self, cutoff: DateTime<Utc>) -> Result<Vec<Task>, WorkError> {
+        let sql = format!(
+            "SELECT {} FROM tasks WHERE status = 'claimed' AND updated_at < ?1 ORDER BY updated_at",
+            Self::TASK_COLUMNS
+        );
+        let mut stmt = self.conn.prepare(&sql)?;
+        let tasks = stmt.query_map(params![cutoff.to_rfc3339()], Self::row_to_task)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(tasks)
+    }
+    
+    /// Release a claimed task back to ready
+    pub fn release(&mut self, id: &str) -> Result<(), WorkError> {
+        let now = Utc::now();
+        let rows = self.conn.execute(
+            "UPDATE tasks SET status = 'ready', agent = NULL, updated_at = ?1 WHERE id = ?2",
             params![now.to_rfc3339(), id],
         )?;
         
         if rows == 0 {
             return Err(WorkError::NotFound(id.to_string()));
         }
+        self.conn.execute("DELETE FROM leases WHERE task_id = ?1", params![id])?;
         Ok(())
     }
     
     /// Complete a task with optional evidence, returns list of newly unblocked task IDs
     pub fn complete(&mut self, id: &str, evidence: Option<&str>) -> Result<Vec<String>, WorkError> {
+        self.finish(id, None, evidence)
+    }
+    
+    /// `complete` on behalf of the agent working on a task, which fails
+    /// unless that agent still holds its lease
+    pub fn complete_as(&mut self, id: &str, agent: &str, evidence: Option<&str>) -> Result<Vec<String>, WorkError> {
+        self.finish(id, Some(agent), evidence)
+    }
+    
+    fn finish(&mut self, id: &str, holder: Option<&str>, evidence: Option<&str>) -> Result<Vec<String>, WorkError> {
+        if let Some(agent) = holder {
+            self.check_lease(id, agent)?;
+        }
+        
         // Check that all dependencies are satisfied
         let blocking = self.get_blocking_tasks(id)?;
         let incomplete: Vec<_> = blocking.iter()
@@ -445,18 +1384,98 @@ impl WorkStore {
             });
         }
         
+        // Labeled tasks (deploy, security, ...) must carry matching evidence;
+        // evidence recorded earlier on the task counts too
+        let task = self.get(id)?.ok_or_else(|| WorkError::NotFound(id.to_string()))?;
+        let evidence_to_check = evidence.or(task.evidence.as_deref());
+        if let Err((label, expected)) = self.evidence_rules.check(&task.labels, evidence_to_check) {
+            return Err(WorkError::EvidenceRequired { id: id.to_string(), label, expected });
+        }
+        
+        // The holder is checked again in the update, in case the lease was
+        // taken over meanwhile
         let now = Utc::now();
-        self.conn.execute(
-            "UPDATE tasks SET status = 'done', evidence = COALESCE(?1, evidence), updated_at = ?2 WHERE id = ?3",
-            params![evidence, now.to_rfc3339(), id],
+        let completed = self.conn.execute(
+            "UPDATE tasks SET status = 'done', evidence = COALESCE(?1, evidence), updated_at = ?2
+             WHERE id = ?3 AND (?4 IS NULL OR (status = 'claimed' AND agent = ?4))",
+            params![evidence, now.to_rfc3339(), id, holder],
         )?;
+        if completed == 0 {
+            return Err(WorkError::LeaseNotHeld { id: id.to_string(), agent: holder.unwrap_or_default().to_string() });
+        }
+        
+        if self.sign_evidence {
+            if let Some(evidence) = evidence {
+                self.append_evidence(id, evidence, now)?;
+            }
+        }
         
         // Auto-unblock: find tasks that were blocked by this one and are now ready
-        let unblocked = self.auto_unblock(id)?;
+        let mut unblocked = self.auto_unblock(id)?;
+        
+        if self.auto_complete_parents {
+            if let Some(parent) = task.parent.as_deref() {
+                unblocked.extend(self.complete_parent_if_done(parent)?);
+            }
+        }
         
         Ok(unblocked)
     }
     
+    /// Put a done task back in the queue, unclaimed, returning it
+    ///
+    /// Its evidence is kept for the record. Dependents that haven't been
+    /// claimed yet are blocked on it again.
+    pub fn reopen(&mut self, id: &str) -> Result<Task, WorkError> {
+        let task = self.get(id)?.ok_or_else(|| WorkError::NotFound(id.to_string()))?;
+        if task.status != Status::Done {
+            return Err(WorkError::NotDone(id.to_string()));
+        }
+        
+        self.conn.execute(
+            "UPDATE tasks SET status = 'ready', agent = NULL, updated_at = ?1 WHERE id = ?2",
+            params![Utc::now().to_rfc3339(), id],
+        )?;
+        // The next completion is reviewed afresh
+        self.conn.execute("DELETE FROM review_approvals WHERE task_id = ?1", params![id])?;
+        self.recompute_blocked_status()?;
+        self.get(id)?.ok_or_else(|| WorkError::NotFound(id.to_string()))
+    }
+    
+    /// Record a reviewer's approval of a task's current completion,
+    /// returning false if they had already approved it
+    pub fn record_approval(&mut self, id: &str, reviewer: &str) -> Result<bool, WorkError> {
+        let added = self.conn.execute(
+            "INSERT OR IGNORE INTO review_approvals (task_id, reviewer, at) VALUES (?1, ?2, ?3)",
+            params![id, reviewer, Utc::now().to_rfc3339()],
+        )?;
+        Ok(added == 1)
+    }
+    
+    /// Complete `parent` if every sub-task that isn't cancelled is done,
+    /// cascading upwards. A parent that's blocked or needs evidence of its
+    /// own stays open for someone to complete by hand.
+    fn complete_parent_if_done(&mut self, parent: &str) -> Result<Vec<String>, WorkError> {
+        let Some(task) = self.get(parent)? else {
+            return Ok(Vec::new());
+        };
+        if matches!(task.status, Status::Done | Status::Cancelled) {
+            return Ok(Vec::new());
+        }
+        
+        let children = self.children(parent)?;
+        let live: Vec<&Task> = children.iter().filter(|t| t.status != Status::Cancelled).collect();
+        if live.is_empty() || live.iter().any(|t| t.status != Status::Done) {
+            return Ok(Vec::new());
+        }
+        
+        match self.complete(parent, None) {
+            Ok(unblocked) => Ok(unblocked),
+            Err(WorkError::CannotComplete { .. } | WorkError::EvidenceRequired { .. }) => Ok(Vec::new()),
+            Err(e) => Err(e),
+        }
+    }
+    
     /// Complete a task with cost tracking
     pub fn complete_with_cost(
         &mut self, 
@@ -471,19 +1490,98 @@ impl WorkStore {
     }
     
     /// Record actual cost for a task
+    ///
+    /// Replaces any earlier cost; the ledger gets the difference, so spend
+    /// isn't counted twice. The ledger row keeps the agent holding the task
+    /// now, so a later reassignment doesn't move the spend.
     pub fn record_cost(&mut self, id: &str, cost_usd: f64) -> Result<(), WorkError> {
         let now = Utc::now();
-        let rows = self.conn.execute(
+        let task = self.get(id)?.ok_or_else(|| WorkError::NotFound(id.to_string()))?;
+        let previous = task.actual_cost_usd.unwrap_or(0.0);
+        
+        let tx = self.conn.transaction()?;
+        tx.execute(
             "UPDATE tasks SET actual_cost_usd = ?1, updated_at = ?2 WHERE id = ?3",
             params![cost_usd, now.to_rfc3339(), id],
         )?;
-        
-        if rows == 0 {
-            return Err(WorkError::NotFound(id.to_string()));
+        if cost_usd != previous {
+            tx.execute(
+                "INSERT INTO cost_ledger (task_id, agent, amount_usd, recorded_at) VALUES (?1, ?2, ?3, ?4)",
+                params![id, task.agent, cost_usd - previous, now.to_rfc3339()],
+            )?;
         }
+        tx.commit()?;
         Ok(())
     }
     
+    /// Cost recorded since `since`, with the labels and repo of the task it
+    /// was recorded on
+    pub fn spend_since(&self, since: DateTime<Utc>) -> Result<Vec<Spend>, WorkError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT l.task_id, l.agent, l.amount_usd, t.labels, t.repo, l.recorded_at FROM cost_ledger l JOIN tasks t ON t.id = l.task_id
+             WHERE l.recorded_at >= ?1 ORDER BY l.recorded_at"
+        )?;
+        let spend = stmt.query_map(params![since.to_rfc3339()], |row| {
+            Ok(Spend {
+                task_id: row.get(0)?,
+                agent: row.get(1)?,
+                amount_usd: row.get(2)?,
+                labels: serde_json::from_str(&row.get::<_, String>(3)?).unwrap_or_default(),
+                repo: row.get(4)?,
+                recorded_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(5)?)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .unwrap_or_else(|_| Utc::now()),
+            })
+        })?.collect::<Result<Vec<_>, _>>()?;
+        Ok(spend)
+    }
+    
+    /// Of `ids`, the tasks with any cost in the ledger, however long ago
+    pub fn ledgered_tasks<'a>(&self, ids: impl IntoIterator<Item = &'a str>) -> Result<HashSet<String>, WorkError> {
+        let mut stmt = self.conn.prepare("SELECT 1 FROM cost_ledger WHERE task_id = ?1 LIMIT 1")?;
+        let mut ledgered = HashSet::new();
+        for id in ids {
+            if stmt.exists(params![id])? {
+                ledgered.insert(id.to_string());
+            }
+        }
+        Ok(ledgered)
+    }
+    
+    /// Store the outcome of a task, replacing any recorded before
+    pub fn set_outcome(&mut self, id: &str, outcome: &Outcome) -> Result<Outcome, WorkError> {
+        self.get(id)?.ok_or_else(|| WorkError::NotFound(id.to_string()))?;
+        let now = Utc::now();
+        let outcome = Outcome { recorded_at: Some(now), ..outcome.clone() };
+        self.conn.execute(
+            "INSERT OR REPLACE INTO task_outcomes (task_id, outcome_json, recorded_at) VALUES (?1, ?2, ?3)",
+            params![id, serde_json::to_string(&outcome)?, now.to_rfc3339()],
+        )?;
+        Ok(outcome)
+    }
+    
+    pub fn get_outcome(&self, id: &str) -> Result<Option<Outcome>, WorkError> {
+        let json: Option<String> = self.conn.query_row(
+            "SELECT outcome_json FROM task_outcomes WHERE task_id = ?1",
+            params![id],
+            |row| row.get(0),
+        ).optional()?;
+        Ok(json.and_then(|j| serde_json::from_str(&j).ok()))
+    }
+    
+    /// Outcomes recorded since `since`, by task ID
+    pub fn outcomes_since(&self, since: DateTime<Utc>) -> Result<Vec<(String, Outcome)>, WorkError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT task_id, outcome_json FROM task_outcomes WHERE recorded_at >= ?1 ORDER BY recorded_at"
+        )?;
+        let rows = stmt.query_map(params![since.to_rfc3339()], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?.collect::<Result<Vec<_>, _>>()?;
+        Ok(rows.into_iter()
+            .filter_map(|(id, json)| serde_json::from_str(&json).ok().map(|o| (id, o)))
+            .collect())
+    }
+    
     /// Auto-unblock tasks that were blocked by a completed task
     /// Returns the IDs of tasks that are now ready
     fn auto_unblock(&self, completed_id: &str) -> Result<Vec<String>, WorkError> {
@@ -539,10 +1637,11 @@ impl WorkStore {
         self.get(task_id)?.ok_or_else(|| WorkError::NotFound(task_id.to_string()))?;
         self.get(depends_on)?.ok_or_else(|| WorkError::NotFound(depends_on.to_string()))?;
         
-        // Check for cycles (simple check: depends_on can't depend on task_id)
-        let reverse_deps = self.get_all_dependencies(depends_on)?;
-        if reverse_deps.iter().any(|d| d.depends_on == task_id) {
-            return Err(WorkError::CycleDetected);
+        // Reject the edge if depends_on already waits on task_id, however indirectly
+        if let Some(path) = self.dependency_path(depends_on, task_id)? {
+            let mut cycle = vec![task_id.to_string()];
+            cycle.extend(path);
+            return Err(WorkError::CycleDetected(cycle));
         }
         
         let now = Utc::now();
@@ -580,8 +1679,88 @@ impl WorkStore {
         Ok(tasks)
     }
     
+    /// Dependency cycles already in the store (e.g. added before cycles were
+    /// rejected). Each is a path of task IDs that starts and ends on the same
+    /// task; removing the last dependency of every cycle reported breaks them all.
+    pub fn dependency_cycles(&self) -> Result<Vec<Vec<String>>, WorkError> {
+        let graph = self.dependency_graph()?;
+        let mut roots: Vec<&String> = graph.keys().collect();
+        roots.sort();
+        
+        let mut finished = HashSet::new();
+        let mut cycles = Vec::new();
+        for root in roots {
+            Self::collect_cycles(&graph, root, &mut Vec::new(), &mut finished, &mut cycles);
+        }
+        Ok(cycles)
+    }
+    
+    /// Depth-first search that records a cycle for every edge back into the current path
+    fn collect_cycles<'a>(
+        graph: &'a HashMap<String, Vec<String>>,
+        node: &'a str,
+        path: &mut Vec<&'a str>,
+        finished: &mut HashSet<&'a str>,
+        cycles: &mut Vec<Vec<String>>,
+    ) {
+        if finished.contains(node) {
+            return;
+        }
+        if let Some(start) = path.iter().position(|n| *n == node) {
+            let mut cycle: Vec<String> = path[start..].iter().map(|n| n.to_string()).collect();
+            cycle.push(node.to_string());
+            cycles.push(cycle);
+            return;
+        }
+        
+        path.push(node);
+        for next in graph.get(node).into_iter().flatten() {
+            Self::collect_cycles(graph, next, path, finished, cycles);
+        }
+        path.pop();
+        finished.insert(node);
+    }
+    
+    /// Shortest chain of dependencies leading from `from` to `to`, inclusive
+    fn dependency_path(&self, from: &str, to: &str) -> Result<Option<Vec<String>>, WorkError> {
+        let graph = self.dependency_graph()?;
+        let mut came_from: HashMap<&str, &str> = HashMap::new();
+        let mut queue = std::collections::VecDeque::from([from]);
+        
+        while let Some(node) = queue.pop_front() {
+            if node == to {
+                let mut path = vec![node.to_string()];
+                let mut at = node;
+                while let Some(prev) = came_from.get(at) {
+                    path.push(prev.to_string());
+                    at = prev;
+                }
+                path.reverse();
+                return Ok(Some(path));
+            }
+            for next in graph.get(node).into_iter().flatten() {
+                if next != from && !came_from.contains_key(next.as_str()) {
+                    came_from.insert(next, node);
+                    queue.push_back(next);
+                }
+            }
+        }
+        Ok(None)
+    }
+    
+    /// Every dependency edge, as task -> the tasks it waits on
+    fn dependency_graph(&self) -> Result<HashMap<String, Vec<String>>, WorkError> {
+        let mut stmt = self.conn.prepare("SELECT task_id, depends_on FROM dependencies ORDER BY task_id, depends_on")?;
+        let mut graph: HashMap<String, Vec<String>> = HashMap::new();
+        for edge in stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))? {
+            let (task_id, depends_on) = edge?;
+            graph.entry(task_id).or_default().push(depends_on);
+        }
+        Ok(graph)
+    }
+    
     /// Get all dependencies for a task
-    fn get_all_dependencies(&self, task_id: &str) -> Result<Vec<Dependency>, WorkError> {
+    pub fn get_all_dependencies(&self, task_id: &str) -> Result<Vec<Dependency>, WorkError> {
         let mut stmt = self.conn.prepare(
             "SELECT task_id, depends_on, created_at FROM dependencies WHERE task_id = ?1"
         )?;
@@ -625,13 +1804,593 @@ impl WorkStore {
         Ok(())
     }
     
+    // ─────────────────────────────────────────────────────────────────────
+    // Subscriptions (labels as queue topics)
+    // ─────────────────────────────────────────────────────────────────────
+    
+    /// Subscribe an agent to one or more label topics
+    pub fn subscribe(&mut self, agent: &str, labels: &[String]) -> Result<(), WorkError> {
+        let now = Utc::now();
+        for label in labels {
+            self.conn.execute(
+                "INSERT OR IGNORE INTO subscriptions (agent, label, created_at) VALUES (?1, ?2, ?3)",
+                params![agent, label, now.to_rfc3339()],
+            )?;
+        }
+        Ok(())
+    }
+    
+    /// Unsubscribe an agent from label topics (all topics when `labels` is empty)
+    pub fn unsubscribe(&mut self, agent: &str, labels: &[String]) -> Result<(), WorkError> {
+        if labels.is_empty() {
+            self.conn.execute("DELETE FROM subscriptions WHERE agent = ?1", params![agent])?;
+        } else {
+            for label in labels {
+                self.conn.execute(
+                    "DELETE FROM subscriptions WHERE agent = ?1 AND label = ?2",
+                    params![agent, label],
+                )?;
+            }
+        }
+        Ok(())
+    }
+    
+    /// Get the label topics an agent is subscribed to
+    pub fn subscriptions(&self, agent: &str) -> Result<Vec<String>, WorkError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT label FROM subscriptions WHERE agent = ?1 ORDER BY label"
+        )?;
+        let labels = stmt.query_map(params![agent], |row| row.get(0))?
+            .collect::<Result<Vec<String>, _>>()?;
+        Ok(labels)
+    }
+    
+    // ─────────────────────────────────────────────────────────────────────
+    // Agent Activity (rate limits and quotas)
+    // ─────────────────────────────────────────────────────────────────────
+    
+    /// Record that an agent acted on a task ("create", "claim", ...).
+    /// Creates are kept as authorship; other actions are dropped after an hour.
+    pub fn record_activity(&mut self, agent: &str, action: &str, task_id: &str) -> Result<(), WorkError> {
+        let now = Utc::now();
+        self.conn.execute(
+            "INSERT INTO agent_activity (agent, action, task_id, at) VALUES (?1, ?2, ?3, ?4)",
+            params![agent, action, task_id, now.to_rfc3339()],
+        )?;
+        self.conn.execute(
+            "DELETE FROM agent_activity WHERE action != 'create' AND at < ?1",
+            params![(now - chrono::Duration::hours(1)).to_rfc3339()],
+        )?;
+        Ok(())
+    }
+    
+    /// When an agent took an action since `since`, oldest first
+    pub fn activity_since(&self, agent: &str, action: &str, since: DateTime<Utc>) -> Result<Vec<DateTime<Utc>>, WorkError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT at FROM agent_activity WHERE agent = ?1 AND action = ?2 AND at >= ?3 ORDER BY at"
+        )?;
+        let times = stmt.query_map(params![agent, action, since.to_rfc3339()], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(times
+            .iter()
+            .filter_map(|at| DateTime::parse_from_rfc3339(at).ok())
+            .map(|at| at.with_timezone(&Utc))
+            .collect())
+    }
+    
+    /// Number of open (not done or cancelled) tasks an agent created
+    pub fn open_tasks_created_by(&self, agent: &str) -> Result<u32, WorkError> {
+        Ok(self.conn.query_row(
+            "SELECT COUNT(DISTINCT a.task_id) FROM agent_activity a JOIN tasks t ON t.id = a.task_id
+             WHERE a.agent = ?1 AND a.action = 'create' AND t.status NOT IN ('done', 'cancelled')",
+            params![agent],
+            |row| row.get(0),
+        )?)
+    }
+    
+    // ─────────────────────────────────────────────────────────────────────
+    // Pauses (freeze a label or repo)
+    // ─────────────────────────────────────────────────────────────────────
+    
+    /// Pause a label or repo (pausing it again updates the reason)
+    pub fn pause(&mut self, scope: PauseScope, reason: Option<&str>, paused_by: Option<&str>) -> Result<Pause, WorkError> {
+        let pause = Pause {
+            scope,
+            reason: reason.map(String::from),
+            paused_by: paused_by.map(String::from),
+            paused_at: Utc::now(),
+        };
+        self.conn.execute(
+            "INSERT OR REPLACE INTO pauses (kind, value, reason, paused_by, paused_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                pause.scope.kind(),
+                pause.scope.value(),
+                pause.reason,
+                pause.paused_by,
+                pause.paused_at.to_rfc3339(),
+            ],
+        )?;
+        Ok(pause)
+    }
+    
+    /// Lift a pause; false if the scope wasn't paused
+    pub fn unpause(&mut self, scope: &PauseScope) -> Result<bool, WorkError> {
+        let removed = self.conn.execute(
+            "DELETE FROM pauses WHERE kind = ?1 AND value = ?2",
+            params![scope.kind(), scope.value()],
+        )?;
+        Ok(removed > 0)
+    }
+    
+    /// Active pauses, oldest first
+    pub fn pauses(&self) -> Result<Vec<Pause>, WorkError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT kind, value, reason, paused_by, paused_at FROM pauses ORDER BY paused_at"
+        )?;
+        let pauses = stmt.query_map([], |row| {
+            let kind: String = row.get(0)?;
+            let value: String = row.get(1)?;
+            let paused_at: String = row.get(4)?;
+            Ok(Pause {
+                scope: match kind.as_str() {
+                    "repo" => PauseScope::Repo(value),
+                    _ => PauseScope::Label(value),
+                },
+                reason: row.get(2)?,
+                paused_by: row.get(3)?,
+                paused_at: DateTime::parse_from_rfc3339(&paused_at)
+                    .map(|d| d.with_timezone(&Utc))
+                    .unwrap_or_else(|_| Utc::now()),
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+        Ok(pauses)
+    }
+    
+    /// The pause holding a task, if any
+    pub fn pause_covering(&self, task: &Task) -> Result<Option<Pause>, WorkError> {
+        Ok(self.pauses()?.into_iter().find(|p| p.covers(task)))
+    }
+    
+    // ─────────────────────────────────────────────────────────────────────
+    // Comments (discussion threads on tasks)
+    // ─────────────────────────────────────────────────────────────────────
+    
+    /// Add a comment to a task's thread
+    pub fn add_comment(&mut self, task_id: &str, author: &str, body: &str) -> Result<Comment, WorkError> {
+        let body = body.trim();
+        if body.is_empty() {
+            return Err(WorkError::EmptyComment);
+        }
+        if self.get(task_id)?.is_none() {
+            return Err(WorkError::NotFound(task_id.to_string()));
+        }
+        
+        let created_at = Utc::now();
+        self.conn.execute(
+            "INSERT INTO comments (task_id, author, body, created_at) VALUES (?1, ?2, ?3, ?4)",
+            params![task_id, author, body, created_at.to_rfc3339()],
+        )?;
+        Ok(Comment {
+            id: self.conn.last_insert_rowid(),
+            task_id: task_id.to_string(),
+            author: author.to_string(),
+            body: body.to_string(),
+            created_at,
+        })
+    }
+    
+    /// A task's thread, oldest first
+    pub fn comments(&self, task_id: &str) -> Result<Vec<Comment>, WorkError> {
+        self.query_comments(
+            "SELECT id, task_id, author, body, created_at FROM comments WHERE task_id = ?1 ORDER BY id",
+            params![task_id],
+        )
+    }
+    
+    /// The latest comments across all tasks, newest first
+    pub fn recent_comments(&self, limit: usize) -> Result<Vec<Comment>, WorkError> {
+        self.query_comments(
+            "SELECT id, task_id, author, body, created_at FROM comments ORDER BY id DESC LIMIT ?1",
+            params![limit as i64],
+        )
+    }
+    
+    fn query_comments(&self, sql: &str, params: impl rusqlite::Params) -> Result<Vec<Comment>, WorkError> {
+        let mut stmt = self.conn.prepare(sql)?;
+        let comments = stmt.query_map(params, |row| {
+            let created_at: String = row.get(4)?;
+            Ok(Comment {
+                id: row.get(0)?,
+                task_id: row.get(1)?,
+                author: row.get(2)?,
+                body: row.get(3)?,
+                created_at: DateTime::parse_from_rfc3339(&created_at)
+                    .map(|d| d.with_timezone(&Utc))
+                    .unwrap_or_else(|_| Utc::now()),
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+        Ok(comments)
+    }
+    
+    // ─────────────────────────────────────────────────────────────────────
+    // Dispatch audit (why auto-dispatch did what it did)
+    // ─────────────────────────────────────────────────────────────────────
+    
+    /// Record an auto-dispatch decision
+    pub fn record_dispatch(
+        &mut self,
+        task_id: &str,
+        agent: &str,
+        confidence: f64,
+        threshold: f64,
+        action: &str,
+        question: Option<&str>,
+    ) -> Result<DispatchAudit, WorkError> {
+        let at = Utc::now();
+        self.conn.execute(
+            "INSERT INTO dispatch_audit (task_id, agent, confidence, threshold, action, question, at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![task_id, agent, confidence, threshold, action, question, at.to_rfc3339()],
+        )?;
+        Ok(DispatchAudit {
+            id: self.conn.last_insert_rowid(),
+            task_id: task_id.to_string(),
+            agent: agent.to_string(),
+            confidence,
+            threshold,
+            action: action.to_string(),
+            question: question.map(String::from),
+            at,
+        })
+    }
+    
+    /// Question from the latest approval auto-dispatch asked for on a task
+    pub fn latest_approval_request(&self, task_id: &str) -> Result<Option<String>, WorkError> {
+        Ok(self.conn.query_row(
+            "SELECT question FROM dispatch_audit WHERE task_id = ?1 AND action = 'approval_requested'
+             ORDER BY id DESC LIMIT 1",
+            params![task_id],
+            |row| row.get(0),
+        ).optional()?.flatten())
+    }
+    
+    /// Auto-dispatch decisions, oldest first; all tasks' when `task_id` is None
+    pub fn dispatch_audit(&self, task_id: Option<&str>) -> Result<Vec<DispatchAudit>, WorkError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, task_id, agent, confidence, threshold, action, question, at FROM dispatch_audit
+             WHERE ?1 IS NULL OR task_id = ?1 ORDER BY id"
+        )?;
+        let entries = stmt.query_map(params![task_id], |row| {
+            let at: String = row.get(7)?;
+            Ok(DispatchAudit {
+                id: row.get(0)?,
+                task_id: row.get(1)?,
+                agent: row.get(2)?,
+                confidence: row.get(3)?,
+                threshold: row.get(4)?,
+                action: row.get(5)?,
+                question: row.get(6)?,
+                at: DateTime::parse_from_rfc3339(&at)
+                    .map(|d| d.with_timezone(&Utc))
+                    .unwrap_or_else(|_| Utc::now()),
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+        Ok(entries)
+    }
+    
+    // ─────────────────────────────────────────────────────────────────────
+    // History (append-only audit log)
+    // ─────────────────────────────────────────────────────────────────────
+    
+    /// Every recorded transition of a task, oldest first
+    pub fn history(&self, task_id: &str) -> Result<Vec<HistoryEntry>, WorkError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT seq, task_id, event, from_value, to_value, agent, at FROM task_history
+             WHERE task_id = ?1 ORDER BY seq"
+        )?;
+        let entries = stmt.query_map(params![task_id], Self::row_to_history)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(entries)
+    }
+    
+    /// History entries across all tasks after `seq`, oldest first
+    pub fn history_since(&self, seq: i64, limit: usize) -> Result<Vec<HistoryEntry>, WorkError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT seq, task_id, event, from_value, to_value, agent, at FROM task_history
+             WHERE seq > ?1 ORDER BY seq LIMIT ?2"
+        )?;
+        let entries = stmt.query_map(params![seq, limit as i64], Self::row_to_history)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(entries)
+    }
+    
+    /// Sequence number of the newest history entry (0 when there are none)
+    pub fn latest_history_seq(&self) -> Result<i64, WorkError> {
+        Ok(self.conn.query_row("SELECT COALESCE(MAX(seq), 0) FROM task_history", [], |row| row.get(0))?)
+    }
+    
+    fn row_to_history(row: &rusqlite::Row) -> rusqlite::Result<HistoryEntry> {
+        let at: String = row.get(6)?;
+        Ok(HistoryEntry {
+            seq: row.get(0)?,
+            task_id: row.get(1)?,
+            event: row.get(2)?,
+            from: row.get(3)?,
+            to: row.get(4)?,
+            agent: row.get(5)?,
+            at: DateTime::parse_from_rfc3339(&at)
+                .map(|d| d.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now()),
+        })
+    }
+    
+    /// Last history entry handled for a webhook, and failed attempts at the next
+    pub fn webhook_cursor(&self, url: &str) -> Result<Option<(i64, u32)>, WorkError> {
+        Ok(self.conn.query_row(
+            "SELECT seq, failures FROM webhook_cursors WHERE url = ?1",
+            params![url],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        ).optional()?)
+    }
+    
+    pub fn set_webhook_cursor(&mut self, url: &str, seq: i64, failures: u32) -> Result<(), WorkError> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO webhook_cursors (url, seq, failures) VALUES (?1, ?2, ?3)",
+            params![url, seq, failures],
+        )?;
+        Ok(())
+    }
+    
+    // ─────────────────────────────────────────────────────────────────────
+    // Mentions
+    // ─────────────────────────────────────────────────────────────────────
+    
+    /// Number a mention of `handle` on `line` of a task's text, unless it
+    /// has been seen before
+    pub fn record_mention(&mut self, handle: &str, task_id: &str, line: &str) -> Result<(), WorkError> {
+        self.conn.execute(
+            "INSERT OR IGNORE INTO mentions (handle, task_id, line, seen_at) VALUES (?1, ?2, ?3, ?4)",
+            params![handle, task_id, line, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+    
+    /// Mentions of `handle` numbered after `id`, oldest first, as
+    /// `(id, task_id, seen_at)`
+    pub fn mentions_since(&self, handle: &str, id: i64) -> Result<Vec<(i64, String, DateTime<Utc>)>, WorkError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, task_id, seen_at FROM mentions WHERE handle = ?1 AND id > ?2 ORDER BY id"
+        )?;
+        let mentions = stmt.query_map(params![handle, id], |row| {
+            let seen_at: String = row.get(2)?;
+            Ok((row.get(0)?, row.get(1)?, DateTime::parse_from_rfc3339(&seen_at)
+                .map(|d| d.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now())))
+        })?.collect::<Result<Vec<_>, _>>()?;
+        Ok(mentions)
+    }
+    
+    /// Number of the newest mention (0 when there are none)
+    pub fn latest_mention_id(&self) -> Result<i64, WorkError> {
+        Ok(self.conn.query_row("SELECT COALESCE(MAX(id), 0) FROM mentions", [], |row| row.get(0))?)
+    }
+    
+    /// Newest mention sent to a stakeholder
+    pub fn mention_cursor(&self, handle: &str) -> Result<Option<i64>, WorkError> {
+        Ok(self.conn.query_row(
+            "SELECT id FROM mention_cursors WHERE handle = ?1",
+            params![handle],
+            |row| row.get(0),
+        ).optional()?)
+    }
+    
+    /// Record the newest mention sent to a stakeholder
+    pub fn set_mention_cursor(&mut self, handle: &str, id: i64) -> Result<(), WorkError> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO mention_cursors (handle, id) VALUES (?1, ?2)",
+            params![handle, id],
+        )?;
+        Ok(())
+    }
+    
+    // ─────────────────────────────────────────────────────────────────────
+    // Schedules (recurring and deferred tasks)
+    // ─────────────────────────────────────────────────────────────────────
+    
+    /// Schedule a task to be created once at a time, or on every cron firing
+    pub fn create_schedule(&mut self, params: CreateTask, recurrence: Recurrence) -> Result<Schedule, WorkError> {
+        let now = Utc::now();
+        let next_run = recurrence.first_run(now).map_err(WorkError::InvalidSchedule)?;
+        if next_run.is_none() {
+            return Err(WorkError::InvalidSchedule(format!("{:?} never fires", recurrence)));
+        }
+        
+        let schedule = Schedule {
+            id: format!("sch-{}", &Uuid::new_v4().to_string()[..4]),
+            title: params.title,
+            description: params.description,
+            priority: params.priority,
+            labels: params.labels,
+            parent: params.parent,
+            recurrence,
+            next_run,
+            last_run: None,
+            last_task: None,
+            created_at: now,
+        };
+        let (cron, due_at) = match &schedule.recurrence {
+            Recurrence::Cron(expr) => (Some(expr.clone()), None),
+            Recurrence::DueAt(at) => (None, Some(at.to_rfc3339())),
+        };
+        self.conn.execute(
+            r#"INSERT INTO schedules (id, title, description, priority, labels, parent, cron, due_at, next_run, created_at)
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)"#,
+            params![
+                schedule.id,
+                schedule.title,
+                schedule.description,
+                schedule.priority.as_str(),
+                serde_json::to_string(&schedule.labels)?,
+                schedule.parent,
+                cron,
+                due_at,
+                next_run.map(|t| t.to_rfc3339()),
+                now.to_rfc3339(),
+            ],
+        )?;
+        Ok(schedule)
+    }
+    
+    /// All schedules, soonest first (finished one-offs last)
+    pub fn schedules(&self) -> Result<Vec<Schedule>, WorkError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, title, description, priority, labels, parent, cron, due_at, next_run, last_run, last_task, created_at
+             FROM schedules ORDER BY next_run IS NULL, next_run, created_at"
+        )?;
+        let time = |s: Option<String>| {
+            s.and_then(|s| DateTime::parse_from_rfc3339(&s).ok()).map(|t| t.with_timezone(&Utc))
+        };
+        let schedules = stmt.query_map([], |row| {
+            let priority: String = row.get(3)?;
+            let labels: String = row.get(4)?;
+            let cron: Option<String> = row.get(6)?;
+            let due_at = time(row.get(7)?);
+            Ok(Schedule {
+                id: row.get(0)?,
+                title: row.get(1)?,
+                description: row.get(2)?,
+                priority: Priority::from_str(&priority).unwrap_or_default(),
+                labels: serde_json::from_str(&labels).unwrap_or_default(),
+                parent: row.get(5)?,
+                recurrence: match cron {
+                    Some(expr) => Recurrence::Cron(expr),
+                    None => Recurrence::DueAt(due_at.unwrap_or_else(Utc::now)),
+                },
+                next_run: time(row.get(8)?),
+                last_run: time(row.get(9)?),
+                last_task: row.get(10)?,
+                created_at: time(row.get(11)?).unwrap_or_else(Utc::now),
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+        Ok(schedules)
+    }
+    
+    /// Remove a schedule (tasks it already created are kept)
+    pub fn delete_schedule(&mut self, id: &str) -> Result<(), WorkError> {
+        if self.conn.execute("DELETE FROM schedules WHERE id = ?1", params![id])? == 0 {
+            return Err(WorkError::ScheduleNotFound(id.to_string()));
+        }
+        Ok(())
+    }
+    
+    /// Create a ready task for every schedule due at `now`, returning them.
+    ///
+    /// Missed cron firings collapse into one run, and a recurring task
+    /// doesn't pile up: while the task from its last run is still open, the
+    /// run is skipped.
+    pub fn materialize_due(&mut self, now: DateTime<Utc>) -> Result<Vec<Task>, WorkError> {
+        let mut created = Vec::new();
+        for schedule in self.schedules()? {
+            if schedule.next_run.is_none_or(|at| at > now) {
+                continue;
+            }
+            
+            let pending = match &schedule.last_task {
+                Some(id) => self.get(id)?.is_some_and(|t| !matches!(t.status, Status::Done | Status::Cancelled)),
+                None => false,
+            };
+            let (mut last_run, mut last_task) = (schedule.last_run, schedule.last_task.clone());
+            if !pending {
+                let task = self.create(CreateTask {
+                    title: schedule.title.clone(),
+                    description: schedule.description.clone(),
+                    priority: schedule.priority,
+                    labels: schedule.labels.clone(),
+                    parent: schedule.parent.clone(),
+                    ..Default::default()
+                })?;
+                last_run = Some(now);
+                last_task = Some(task.id.clone());
+                created.push(task);
+            }
+            
+            let next_run = schedule.recurrence.next_run(now).map_err(WorkError::InvalidSchedule)?;
+            self.conn.execute(
+                "UPDATE schedules SET next_run = ?2, last_run = ?3, last_task = ?4 WHERE id = ?1",
+                params![
+                    schedule.id,
+                    next_run.map(|t| t.to_rfc3339()),
+                    last_run.map(|t| t.to_rfc3339()),
+                    last_task,
+                ],
+            )?;
+        }
+        Ok(created)
+    }
+    
+    // ─────────────────────────────────────────────────────────────────────
+    // Remote Links (external issue trackers)
+    // ─────────────────────────────────────────────────────────────────────
+    
+    /// Links between tasks and issues in a tracker (e.g. "github")
+    pub fn remote_links(&self, tracker: &str) -> Result<Vec<RemoteLink>, WorkError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT task_id, tracker, remote_id, url, local_hash, remote_hash, synced_at, remote_assignee
+             FROM remote_links WHERE tracker = ?1 ORDER BY task_id"
+        )?;
+        let links = stmt.query_map(params![tracker], |row| {
+            let synced_at: String = row.get(6)?;
+            Ok(RemoteLink {
+                task_id: row.get(0)?,
+                tracker: row.get(1)?,
+                remote_id: row.get(2)?,
+                url: row.get(3)?,
+                local_hash: row.get(4)?,
+                remote_hash: row.get(5)?,
+                remote_assignee: row.get(7)?,
+                synced_at: DateTime::parse_from_rfc3339(&synced_at)
+                    .map(|d| d.with_timezone(&Utc))
+                    .unwrap_or_else(|_| Utc::now()),
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+        Ok(links)
+    }
+    
+    /// Record (or replace) the link between a task and a remote issue
+    pub fn set_remote_link(&mut self, link: &RemoteLink) -> Result<(), WorkError> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO remote_links (task_id, tracker, remote_id, url, local_hash, remote_hash, synced_at, remote_assignee)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                link.task_id,
+                link.tracker,
+                link.remote_id,
+                link.url,
+                link.local_hash,
+                link.remote_hash,
+                link.synced_at.to_rfc3339(),
+                link.remote_assignee,
+            ],
+        )?;
+        Ok(())
+    }
+    
     // ─────────────────────────────────────────────────────────────────────
     // Queries
     // ─────────────────────────────────────────────────────────────────────
     
-    /// Get all tasks that are ready to be worked on
+    /// Get all tasks that are ready to be worked on (paused ones are held back)
     pub fn ready(&self) -> Result<Vec<Task>, WorkError> {
-        self.list_by_status(Status::Ready)
+        let pauses = self.pauses()?;
+        Ok(self.list_by_status(Status::Ready)?
+            .into_iter()
+            .filter(|t| !pauses.iter().any(|p| p.covers(t)))
+            .collect())
     }
     
     /// Get all tasks claimed by a specific agent
@@ -688,18 +2447,58 @@ impl WorkStore {
         Ok(tasks)
     }
     
-    /// Get sub-tasks of a parent task
+    /// Complete parents automatically once all their sub-tasks are done
+    pub fn set_auto_complete_parents(&mut self, enabled: bool) {
+        self.auto_complete_parents = enabled;
+    }
+    
+    /// How long claims last before they must be renewed (default: 30 minutes)
+    pub fn set_lease_duration(&mut self, duration: chrono::Duration) {
+        self.lease_duration = duration;
+    }
+    
+    /// Get sub-tasks of a parent task, each carrying the rollup of its own
+    /// sub-tasks (if it has any)
     pub fn children(&self, parent_id: &str) -> Result<Vec<Task>, WorkError> {
+        let subtree = self.subtree(parent_id)?;
+        let mut rollups = Rollup::compute(&subtree);
+        let mut tasks: Vec<Task> = subtree.into_iter()
+            .filter(|t| t.parent.as_deref() == Some(parent_id) && t.id != parent_id)
+            .map(|mut t| {
+                t.rollup = rollups.remove(&t.id);
+                t
+            })
+            .collect();
+        tasks.sort_by_key(|t| t.created_at);
+        Ok(tasks)
+    }
+    
+    /// Progress rolled up from a task's sub-tasks, or None if it has none
+    pub fn rollup(&self, id: &str) -> Result<Option<Rollup>, WorkError> {
+        Ok(Rollup::compute(&self.subtree(id)?).remove(id))
+    }
+    
+    /// A task and everything beneath it
+    fn subtree(&self, id: &str) -> Result<Vec<Task>, WorkError> {
+        // UNION also stops on parent cycles
         let sql = format!(
-            "SELECT {} FROM tasks WHERE parent = ?1 ORDER BY created_at ASC",
+            "WITH RECURSIVE subtree(id) AS (
+                SELECT ?1 UNION SELECT t.id FROM tasks t JOIN subtree s ON t.parent = s.id
+             )
+             SELECT {} FROM tasks WHERE id IN (SELECT id FROM subtree)",
             Self::TASK_COLUMNS
         );
         let mut stmt = self.conn.prepare(&sql)?;
-        let tasks = stmt.query_map(params![parent_id], |row| Self::row_to_task(row))?
+        let tasks = stmt.query_map(params![id], Self::row_to_task)?
             .collect::<Result<Vec<_>, _>>()?;
         Ok(tasks)
     }
     
+    /// Rollups for every task that has sub-tasks, keyed by task ID
+    pub fn rollups(&self) -> Result<HashMap<String, Rollup>, WorkError> {
+        Ok(Rollup::compute(&self.list_all()?))
+    }
+    
     /// List tasks by repository
     pub fn list_by_repo(&self, repo: &str) -> Result<Vec<Task>, WorkError> {
         let sql = format!(
@@ -727,6 +2526,275 @@ impl WorkStore {
         Ok(repos)
     }
     
+    // ─────────────────────────────────────────────────────────────────────
+    // Questions
+    // ─────────────────────────────────────────────────────────────────────
+    
+    /// Create a question task that dependent work can block on
+    pub fn create_question(
+        &mut self,
+        params: CreateTask,
+        options: Vec<String>,
+        deadline: Option<DateTime<Utc>>,
+        default_decision: Option<String>,
+    ) -> Result<(Task, Question), WorkError> {
+        if let Some(ref default) = default_decision {
+            if !options.is_empty() && !options.contains(default) {
+                return Err(WorkError::InvalidAnswer(format!(
+                    "default '{}' is not one of the options", default
+                )));
+            }
+        }
+        
+        let task = self.create(CreateTask { kind: TaskKind::Question, ..params })?;
+        self.conn.execute(
+            "INSERT INTO questions (task_id, options, deadline, default_decision) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                task.id,
+                serde_json::to_string(&options)?,
+                deadline.map(|d| d.to_rfc3339()),
+                default_decision,
+            ],
+        )?;
+        
+        let question = Question {
+            task_id: task.id.clone(),
+            options,
+            deadline,
+            default_decision,
+            decision: None,
+            rationale: None,
+            answered_by: None,
+            answered_at: None,
+        };
+        Ok((task, question))
+    }
+    
+    /// Get the question details for a question task
+    pub fn question(&self, task_id: &str) -> Result<Option<Question>, WorkError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT task_id, options, deadline, default_decision, decision, rationale, answered_by, answered_at
+             FROM questions WHERE task_id = ?1"
+        )?;
+        let mut rows = stmt.query(params![task_id])?;
+        match rows.next()? {
+            Some(row) => Ok(Some(Self::row_to_question(row)?)),
+            None => Ok(None),
+        }
+    }
+    
+    fn row_to_question(row: &rusqlite::Row) -> rusqlite::Result<Question> {
+        let options_json: String = row.get(1)?;
+        let parse = |s: Option<String>| s.and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+            .map(|dt| dt.with_timezone(&Utc));
+        
+        Ok(Question {
+            task_id: row.get(0)?,
+            options: serde_json::from_str(&options_json).unwrap_or_default(),
+            deadline: parse(row.get(2)?),
+            default_decision: row.get(3)?,
+            decision: row.get(4)?,
+            rationale: row.get(5)?,
+            answered_by: row.get(6)?,
+            answered_at: parse(row.get(7)?),
+        })
+    }
+    
+    /// Unanswered questions
+    pub fn open_questions(&self) -> Result<Vec<Question>, WorkError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT q.task_id, q.options, q.deadline, q.default_decision, q.decision, q.rationale, q.answered_by, q.answered_at
+             FROM questions q JOIN tasks t ON t.id = q.task_id
+             WHERE q.decision IS NULL AND t.status NOT IN ('done', 'cancelled')
+             ORDER BY q.deadline IS NULL, q.deadline"
+        )?;
+        let questions = stmt.query_map([], Self::row_to_question)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(questions)
+    }
+    
+    /// Answer a question, completing its task
+    ///
+    /// The decision becomes the task's evidence, and tasks blocked on the
+    /// question are unblocked. Returns the newly unblocked task IDs.
+    pub fn answer(
+        &mut self,
+        task_id: &str,
+        decision: &str,
+        rationale: Option<&str>,
+        answered_by: &str,
+    ) -> Result<Vec<String>, WorkError> {
+        let question = self.question(task_id)?
+            .ok_or_else(|| WorkError::NotAQuestion(task_id.to_string()))?;
+        
+        if question.is_answered() {
+            return Err(WorkError::InvalidAnswer(format!("{} was already answered", task_id)));
+        }
+        if !question.options.is_empty() && !question.options.iter().any(|o| o == decision) {
+            return Err(WorkError::InvalidAnswer(format!(
+                "'{}' is not one of: {}", decision, question.options.join(", ")
+            )));
+        }
+        
+        self.conn.execute(
+            "UPDATE questions SET decision = ?1, rationale = ?2, answered_by = ?3, answered_at = ?4 WHERE task_id = ?5",
+            params![decision, rationale, answered_by, Utc::now().to_rfc3339(), task_id],
+        )?;
+        
+        self.complete(task_id, Some(&format!("Decision: {}", decision)))
+    }
+    
+    /// IDs of tasks that depend on the given task
+    pub fn dependents(&self, task_id: &str) -> Result<Vec<String>, WorkError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT task_id FROM dependencies WHERE depends_on = ?1 ORDER BY created_at"
+        )?;
+        let ids = stmt.query_map(params![task_id], |row| row.get(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(ids)
+    }
+    
+    // ─────────────────────────────────────────────────────────────────────
+    // Evidence Log
+    // ─────────────────────────────────────────────────────────────────────
+    
+    /// Enable the hash-chained evidence log, optionally HMAC-signing entries
+    pub fn set_evidence_signing(&mut self, enabled: bool, key: Option<Vec<u8>>) {
+        self.sign_evidence = enabled;
+        self.signing_key = key;
+    }
+    
+    /// Require evidence matching these rules before labeled tasks complete
+    pub fn set_evidence_rules(&mut self, rules: EvidenceRules) {
+        self.evidence_rules = rules;
+    }
+    
+    /// Append evidence to the log, chained to the current head
+    fn append_evidence(&self, task_id: &str, evidence: &str, at: DateTime<Utc>) -> Result<EvidenceEntry, WorkError> {
+        let prev_hash: String = self.conn.query_row(
+            "SELECT hash FROM evidence_log ORDER BY seq DESC LIMIT 1",
+            [],
+            |row| row.get(0),
+        ).optional()?.unwrap_or_else(|| evidence::GENESIS_HASH.to_string());
+        
+        let created_at = at.to_rfc3339();
+        let hash = evidence::entry_hash(&prev_hash, task_id, evidence, &created_at);
+        let signature = self.signing_key.as_deref().map(|key| evidence::sign(key, &hash));
+        
+        self.conn.execute(
+            "INSERT INTO evidence_log (task_id, evidence, created_at, prev_hash, hash, signature) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![task_id, evidence, created_at, prev_hash, hash, signature],
+        )?;
+        
+        Ok(EvidenceEntry {
+            seq: self.conn.last_insert_rowid(),
+            task_id: task_id.to_string(),
+            evidence: evidence.to_string(),
+            created_at: at,
+            prev_hash,
+            hash,
+            signature,
+        })
+    }
+    
+    /// Get evidence log entries, optionally for a single task
+    pub fn evidence_log(&self, task_id: Option<&str>) -> Result<Vec<EvidenceEntry>, WorkError> {
+        Ok(self.evidence_rows(task_id)?.into_iter().map(|(entry, _)| entry).collect())
+    }
+    
+    /// Log entries paired with their created_at exactly as stored (and hashed)
+    fn evidence_rows(&self, task_id: Option<&str>) -> Result<Vec<(EvidenceEntry, String)>, WorkError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT seq, task_id, evidence, created_at, prev_hash, hash, signature FROM evidence_log
+             WHERE ?1 IS NULL OR task_id = ?1 ORDER BY seq"
+        )?;
+        let entries = stmt.query_map(params![task_id], |row| {
+            let created_at: String = row.get(3)?;
+            Ok((EvidenceEntry {
+                seq: row.get(0)?,
+                task_id: row.get(1)?,
+                evidence: row.get(2)?,
+                created_at: DateTime::parse_from_rfc3339(&created_at)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .unwrap_or_else(|_| Utc::now()),
+                prev_hash: row.get(4)?,
+                hash: row.get(5)?,
+                signature: row.get(6)?,
+            }, created_at))
+        })?.collect::<Result<Vec<_>, _>>()?;
+        Ok(entries)
+    }
+    
+    /// Walk the evidence log and report any tampering
+    ///
+    /// Recomputes every hash, checks each entry links to its predecessor,
+    /// checks signatures when a key is configured, and compares each task's
+    /// current evidence against its most recent log entry.
+    pub fn verify_evidence_log(&self) -> Result<LogVerification, WorkError> {
+        let entries = self.evidence_rows(None)?;
+        let mut issues = Vec::new();
+        let mut signatures_checked = 0;
+        let mut expected_prev = evidence::GENESIS_HASH.to_string();
+        let mut latest: HashMap<String, (i64, String)> = HashMap::new();
+        
+        for (entry, created_at) in &entries {
+            if entry.prev_hash != expected_prev {
+                issues.push(LogIssue::BrokenChain {
+                    seq: entry.seq,
+                    expected_prev: expected_prev.clone(),
+                    found_prev: entry.prev_hash.clone(),
+                });
+            }
+            
+            let recomputed = evidence::entry_hash(&entry.prev_hash, &entry.task_id, &entry.evidence, created_at);
+            if recomputed != entry.hash {
+                issues.push(LogIssue::HashMismatch { seq: entry.seq, task_id: entry.task_id.clone() });
+            }
+            
+            if let Some(ref key) = self.signing_key {
+                signatures_checked += 1;
+                let valid = entry.signature.as_deref()
+                    .map(|sig| evidence::verify_signature(key, &entry.hash, sig))
+                    .unwrap_or(false);
+                if !valid {
+                    issues.push(LogIssue::BadSignature { seq: entry.seq, task_id: entry.task_id.clone() });
+                }
+            }
+            
+            latest.insert(entry.task_id.clone(), (entry.seq, entry.evidence.clone()));
+            expected_prev = entry.hash.clone();
+        }
+        
+        let mut logged: Vec<_> = latest.into_iter().collect();
+        logged.sort_by_key(|(_, (seq, _))| *seq);
+        for (task_id, (seq, logged_evidence)) in logged {
+            let current = self.get(&task_id)?.and_then(|t| t.evidence);
+            if current.as_deref() != Some(logged_evidence.as_str()) {
+                issues.push(LogIssue::EvidenceEdited { task_id, seq });
+            }
+        }
+        
+        Ok(LogVerification {
+            entries: entries.len() as u32,
+            signatures_checked,
+            head: expected_prev,
+            issues,
+        })
+    }
+    
+    /// Get the IDs of every task in the store
+    pub fn all_ids(&self) -> Result<std::collections::HashSet<String>, WorkError> {
+        let mut stmt = self.conn.prepare("SELECT id FROM tasks")?;
+        let ids = stmt.query_map([], |row| row.get(0))?
+            .collect::<Result<std::collections::HashSet<String>, _>>()?;
+        Ok(ids)
+    }
+    
+    /// VACUUM the database, returning bytes reclaimed
+    pub fn vacuum(&self) -> Result<u64, WorkError> {
+        Ok(crate::maintenance::vacuum(&self.conn)?)
+    }
+    
     /// Get summary statistics
     pub fn summary(&self) -> Result<WorkSummary, WorkError> {
         self.summary_with_filter(None)
@@ -780,6 +2848,22 @@ impl WorkStore {
             }
         };
         
+        // Open parents in scope; their children may not carry the label
+        let mut rollups = self.rollups()?;
+        let scope = match label {
+            Some(l) => self.list_by_label(l)?,
+            None => self.list_all()?,
+        };
+        let parents = scope.iter()
+            .filter(|t| matches!(t.status, Status::Ready | Status::Claimed | Status::Blocked))
+            .filter_map(|t| rollups.remove(&t.id))
+            .collect();
+        let pauses = self.pauses()?;
+        let paused = scope.iter()
+            .filter(|t| matches!(t.status, Status::Ready | Status::Claimed | Status::Blocked))
+            .filter(|t| pauses.iter().any(|p| p.covers(t)))
+            .count() as u32;
+        
         Ok(WorkSummary {
             ready: count_by_status("ready")? as u32,
             claimed: count_by_status("claimed")? as u32,
@@ -788,6 +2872,9 @@ impl WorkStore {
             cancelled: count_by_status("cancelled")? as u32,
             total_cost_usd: total_cost.unwrap_or(0.0),
             label: label.map(|s| s.to_string()),
+            parents,
+            paused,
+            pauses,
         })
     }
 }
@@ -805,6 +2892,15 @@ pub struct WorkSummary {
     /// Label filter applied (if any)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub label: Option<String>,
+    /// Progress of open parent tasks, rolled up from their sub-tasks
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parents: Vec<Rollup>,
+    /// Open tasks held by a pause (also counted under their status)
+    #[serde(default)]
+    pub paused: u32,
+    /// Active pauses
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pauses: Vec<Pause>,
 }
 
 impl WorkSummary {
@@ -823,6 +2919,82 @@ impl WorkSummary {
     }
 }
 
+/// A parent task's progress rolled up from its sub-tasks (computed on read)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Rollup {
+    pub task_id: String,
+    /// Direct sub-tasks, not counting cancelled ones
+    pub children_total: u32,
+    /// Direct sub-tasks that are done
+    pub children_done: u32,
+    /// Cost of the task and everything beneath it (USD)
+    pub total_cost_usd: f64,
+    /// Share of leaf tasks beneath this one that are done (0-100), so a child
+    /// with its own sub-tasks weighs as much as its subtree
+    pub percent_complete: f64,
+}
+
+impl Rollup {
+    /// Rollups for every task in `tasks` that has sub-tasks among them
+    pub fn compute(tasks: &[Task]) -> HashMap<String, Rollup> {
+        let by_id: HashMap<&str, &Task> = tasks.iter().map(|t| (t.id.as_str(), t)).collect();
+        let mut children: HashMap<&str, Vec<&Task>> = HashMap::new();
+        for task in tasks {
+            if let Some(parent) = task.parent.as_deref().filter(|p| by_id.contains_key(p)) {
+                children.entry(parent).or_default().push(task);
+            }
+        }
+        
+        let mut rollups = HashMap::new();
+        for parent in children.keys() {
+            let mut visiting = Vec::new();
+            let (done, total, cost) = Self::walk(by_id[parent], &children, &mut visiting);
+            let kids = &children[parent];
+            let live = kids.iter().filter(|t| t.status != Status::Cancelled);
+            rollups.insert(parent.to_string(), Rollup {
+                task_id: parent.to_string(),
+                children_total: live.clone().count() as u32,
+                children_done: live.filter(|t| t.status == Status::Done).count() as u32,
+                total_cost_usd: cost,
+                percent_complete: if total > 0.0 { done / total * 100.0 } else { 0.0 },
+            });
+        }
+        rollups
+    }
+    
+    /// (done leaves, counted leaves, cost) for a subtree
+    fn walk<'a>(
+        task: &'a Task,
+        children: &HashMap<&str, Vec<&'a Task>>,
+        visiting: &mut Vec<&'a str>,
+    ) -> (f64, f64, f64) {
+        let own_cost = task.actual_cost_usd.unwrap_or(0.0);
+        if visiting.contains(&task.id.as_str()) {
+            return (0.0, 0.0, 0.0);
+        }
+        visiting.push(&task.id);
+        
+        let (mut done, mut total, mut cost) = (0.0, 0.0, own_cost);
+        for child in children.get(task.id.as_str()).into_iter().flatten() {
+            let (d, t, c) = Self::walk(child, children, visiting);
+            done += d;
+            total += t;
+            cost += c;
+        }
+        visiting.pop();
+        
+        // Leaves (or parents whose children were all cancelled) count as one unit;
+        // a finished parent is finished regardless of stragglers
+        match task.status {
+            Status::Cancelled if total == 0.0 => (0.0, 0.0, cost),
+            Status::Done if total == 0.0 => (1.0, 1.0, cost),
+            _ if total == 0.0 => (0.0, 1.0, cost),
+            Status::Done => (total, total, cost),
+            _ => (done, total, cost),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -853,7 +3025,8 @@ mod tests {
         }).unwrap();
         
         // Claim it
-        let claimed = store.claim(&task.id, "agent-1").unwrap();
+        store.claim(&task.id, "agent-1").unwrap();
+        let claimed = store.get(&task.id).unwrap().unwrap();
         assert_eq!(claimed.status, Status::Claimed);
         assert_eq!(claimed.agent, Some("agent-1".to_string()));
         
@@ -918,4 +3091,402 @@ mod tests {
         let ready = store.ready().unwrap();
         assert_eq!(ready.len(), 2);
     }
+    
+    #[test]
+    fn test_try_claim_only_wins_once() {
+        let mut store = WorkStore::in_memory().unwrap();
+        
+        let task = store.create(CreateTask {
+            title: "Contended".to_string(),
+            ..Default::default()
+        }).unwrap();
+        
+        let first = store.try_claim(&task.id, "agent-1").unwrap();
+        assert_eq!(first.unwrap().agent, Some("agent-1".to_string()));
+        
+        // Second claimant loses the race
+        assert!(store.try_claim(&task.id, "agent-2").unwrap().is_none());
+    }
+    
+    #[test]
+    fn test_expired_lease_can_be_taken_over() {
+        let mut store = WorkStore::in_memory().unwrap();
+        let task = store.create(CreateTask { title: "Leased".to_string(), ..Default::default() }).unwrap();
+        
+        let lease = store.claim(&task.id, "agent-1").unwrap();
+        assert!(lease.expires_at > Utc::now());
+        assert!(store.expiring_leases(chrono::Duration::zero()).unwrap().is_empty());
+        assert!(matches!(store.claim(&task.id, "agent-2"), Err(WorkError::AlreadyClaimed(_))));
+        assert_eq!(store.renew(&task.id, "agent-1").unwrap().renewals, 1);
+        assert!(matches!(store.renew(&task.id, "agent-2"), Err(WorkError::LeaseNotHeld { .. })));
+        
+        // Let the lease lapse
+        store.conn.execute("UPDATE leases SET expires_at = ?1", params![(Utc::now() - chrono::Duration::minutes(1)).to_rfc3339()]).unwrap();
+        assert_eq!(store.expiring_leases(chrono::Duration::zero()).unwrap().len(), 1);
+        
+        store.claim(&task.id, "agent-2").unwrap();
+        assert_eq!(store.get(&task.id).unwrap().unwrap().agent.as_deref(), Some("agent-2"));
+        // The original holder finds out when it next renews, or completes
+        assert!(matches!(store.renew(&task.id, "agent-1"), Err(WorkError::LeaseNotHeld { .. })));
+        assert!(matches!(store.complete_as(&task.id, "agent-1", None), Err(WorkError::LeaseNotHeld { .. })));
+        // Claiming again only renews the holder's own lease
+        store.claim(&task.id, "agent-2").unwrap();
+        assert!(matches!(store.claim(&task.id, "agent-1"), Err(WorkError::AlreadyClaimed(_))));
+        
+        store.release(&task.id).unwrap();
+        assert!(store.get_lease(&task.id).unwrap().is_none());
+        
+        // Only ready tasks can be claimed afresh, and only holders complete
+        store.claim(&task.id, "agent-1").unwrap();
+        assert!(matches!(store.complete_as(&task.id, "agent-2", None), Err(WorkError::LeaseNotHeld { .. })));
+        store.complete_as(&task.id, "agent-1", Some("Done")).unwrap();
+        assert!(matches!(store.claim(&task.id, "agent-1"), Err(WorkError::NotClaimable { status: "done", .. })));
+    }
+    
+    #[test]
+    fn test_plan_reimport_updates_same_tasks() {
+        let mut store = WorkStore::in_memory().unwrap();
+        let root = store.create(CreateTask { title: "Auth rewrite".to_string(), ..Default::default() }).unwrap();
+        
+        let plan = "1. Add token table\n2. Issue tokens\n3. Drop sessions\n";
+        let first = store.import_plan(&root.id, &crate::plan::parse(plan)).unwrap();
+        assert_eq!(first.created.len(), 3);
+        let [add, issue, drop] = [0, 1, 2].map(|i| first.created[i].clone());
+        assert_eq!(store.get(&issue).unwrap().unwrap().status, Status::Blocked);
+        assert_eq!(store.plan_link(&drop).unwrap().unwrap().line, 3);
+        
+        // Same plan again: nothing changes
+        let again = store.import_plan(&root.id, &crate::plan::parse(plan)).unwrap();
+        assert!(again.created.is_empty() && again.updated.is_empty());
+        assert_eq!(again.unchanged, 3);
+        
+        // Reword one step, tick one off, drop one and add one
+        let edited = "1. [x] Add token table\n2. Issue signed tokens\n3. Announce\n";
+        let second = store.import_plan(&root.id, &crate::plan::parse(edited)).unwrap();
+        assert_eq!(second.updated, vec![issue.clone(), drop.clone()]);
+        assert_eq!(second.completed, vec![add.clone()]);
+        assert!(second.created.is_empty() && second.cancelled.is_empty());
+        assert_eq!(store.get(&issue).unwrap().unwrap().title, "Issue signed tokens");
+        assert_eq!(store.get(&issue).unwrap().unwrap().status, Status::Ready);
+        assert_eq!(store.children(&root.id).unwrap().len(), 3);
+        
+        let third = store.import_plan(&root.id, &crate::plan::parse("1. [x] Add token table\n")).unwrap();
+        assert_eq!(third.cancelled, vec![issue.clone(), drop.clone()]);
+        assert!(store.plan_link(&issue).unwrap().is_none());
+    }
+    
+    #[test]
+    fn test_subscriptions() {
+        let mut store = WorkStore::in_memory().unwrap();
+        
+        store.subscribe("agent-1", &["ui".to_string(), "docs".to_string()]).unwrap();
+        store.subscribe("agent-1", &["ui".to_string()]).unwrap();
+        assert_eq!(store.subscriptions("agent-1").unwrap(), vec!["docs", "ui"]);
+        
+        store.unsubscribe("agent-1", &["docs".to_string()]).unwrap();
+        assert_eq!(store.subscriptions("agent-1").unwrap(), vec!["ui"]);
+        
+        store.unsubscribe("agent-1", &[]).unwrap();
+        assert!(store.subscriptions("agent-1").unwrap().is_empty());
+    }
+    
+    #[test]
+    fn test_evidence_log_detects_tampering() {
+        let mut store = WorkStore::in_memory().unwrap();
+        store.set_evidence_signing(true, Some(b"secret".to_vec()));
+        
+        for title in ["A", "B", "C"] {
+            let task = store.create(CreateTask { title: title.to_string(), ..Default::default() }).unwrap();
+            store.complete(&task.id, Some(&format!("{} verified", title))).unwrap();
+        }
+        
+        let verification = store.verify_evidence_log().unwrap();
+        assert_eq!(verification.entries, 3);
+        assert_eq!(verification.signatures_checked, 3);
+        assert!(verification.is_valid());
+        
+        // The log itself refuses edits
+        assert!(store.conn.execute("UPDATE evidence_log SET evidence = 'x'", []).is_err());
+        
+        // Editing evidence on the task is caught against the log
+        store.conn.execute("UPDATE tasks SET evidence = 'edited' WHERE title = 'B'", []).unwrap();
+        let verification = store.verify_evidence_log().unwrap();
+        assert!(matches!(verification.issues.as_slice(), [LogIssue::EvidenceEdited { .. }]));
+        
+        // Bypassing the trigger to rewrite a log entry breaks its hash
+        store.conn.execute_batch("
+            DROP TRIGGER evidence_log_no_update;
+            UPDATE evidence_log SET evidence = 'edited' WHERE seq = 2;
+        ").unwrap();
+        let verification = store.verify_evidence_log().unwrap();
+        assert!(verification.issues.iter().any(|i| matches!(i, LogIssue::HashMismatch { seq: 2, .. })));
+    }
+    
+    #[test]
+    fn test_subtask_rollup() {
+        let mut store = WorkStore::in_memory().unwrap();
+        let parent = store.create(CreateTask { title: "Epic".to_string(), ..Default::default() }).unwrap();
+        let child = |store: &mut WorkStore, title: &str, parent: &str| store.create(CreateTask {
+            title: title.to_string(),
+            parent: Some(parent.to_string()),
+            ..Default::default()
+        }).unwrap();
+        
+        let a = child(&mut store, "A", &parent.id);
+        let b = child(&mut store, "B", &parent.id);
+        let b1 = child(&mut store, "B1", &b.id);
+        let b2 = child(&mut store, "B2", &b.id);
+        let b3 = child(&mut store, "B3", &b.id);
+        let c = child(&mut store, "C", &parent.id);
+        
+        store.complete_with_cost(&a.id, None, 1.25).unwrap();
+        store.complete_with_cost(&b1.id, None, 0.75).unwrap();
+        store.cancel(&b2.id).unwrap();
+        store.cancel(&c.id).unwrap();
+        
+        // A is done; B is half done (B1 done, B2 cancelled, B3 open)
+        let rollup = store.rollup(&parent.id).unwrap().unwrap();
+        assert_eq!((rollup.children_done, rollup.children_total), (1, 2));
+        assert!((rollup.total_cost_usd - 2.0).abs() < 1e-9);
+        assert!((rollup.percent_complete - 200.0 / 3.0).abs() < 1e-9);
+        
+        assert_eq!(store.rollup(&b.id).unwrap().unwrap().percent_complete, 50.0);
+        assert!(store.rollup(&b3.id).unwrap().is_none());
+        
+        let summary = store.summary().unwrap();
+        assert_eq!(summary.parents.len(), 2);
+        
+        // Children carry their own rollups
+        let children = store.children(&parent.id).unwrap();
+        assert_eq!(children.iter().map(|t| t.id.as_str()).collect::<Vec<_>>(), vec![&a.id, &b.id, &c.id]);
+        assert!(children[0].rollup.is_none());
+        assert_eq!(children[1].rollup.as_ref().unwrap().children_done, 1);
+        
+        // Off by default: finishing the last sub-task leaves the parents open
+        store.complete(&b3.id, None).unwrap();
+        assert_eq!(store.get(&b.id).unwrap().unwrap().status, Status::Ready);
+        
+        // On: B4 finishes B, which finishes the epic
+        store.set_auto_complete_parents(true);
+        let b4 = child(&mut store, "B4", &b.id);
+        store.complete(&b4.id, None).unwrap();
+        assert_eq!(store.get(&b.id).unwrap().unwrap().status, Status::Done);
+        assert_eq!(store.get(&parent.id).unwrap().unwrap().status, Status::Done);
+    }
+    
+    #[test]
+    fn test_labeled_tasks_require_evidence() {
+        let mut store = WorkStore::in_memory().unwrap();
+        store.set_evidence_rules(EvidenceRules::compile(&crate::policy::evidence_requirements()).unwrap());
+        
+        let task = store.create(CreateTask {
+            title: "Ship billing worker".to_string(),
+            labels: vec!["deploy".to_string()],
+            ..Default::default()
+        }).unwrap();
+        
+        let err = store.complete(&task.id, Some("done")).unwrap_err();
+        assert!(matches!(err, WorkError::EvidenceRequired { ref label, .. } if label == "deploy"));
+        assert!(err.to_string().contains("URL"));
+        assert_eq!(store.get(&task.id).unwrap().unwrap().status, Status::Ready);
+        
+        store.complete(&task.id, Some("https://billing.example.workers.dev")).unwrap();
+        
+        // Unlabeled tasks are unaffected
+        let plain = store.create(CreateTask { title: "Tidy".to_string(), ..Default::default() }).unwrap();
+        store.complete(&plain.id, None).unwrap();
+    }
+    
+    #[test]
+    fn test_answering_question_unblocks_dependents() {
+        let mut store = WorkStore::in_memory().unwrap();
+        
+        let (question, _) = store.create_question(
+            CreateTask { title: "Which auth provider?".to_string(), ..Default::default() },
+            vec!["clerk".to_string(), "lucia".to_string()],
+            None,
+            Some("lucia".to_string()),
+        ).unwrap();
+        assert_eq!(question.kind, TaskKind::Question);
+        
+        let work = store.create(CreateTask { title: "Add login".to_string(), ..Default::default() }).unwrap();
+        store.add_dependency(&work.id, &question.id).unwrap();
+        assert_eq!(store.get(&work.id).unwrap().unwrap().status, Status::Blocked);
+        
+        assert!(matches!(
+            store.answer(&question.id, "auth0", None, "sam"),
+            Err(WorkError::InvalidAnswer(_))
+        ));
+        assert!(matches!(
+            store.answer(&work.id, "clerk", None, "sam"),
+            Err(WorkError::NotAQuestion(_))
+        ));
+        
+        let unblocked = store.answer(&question.id, "clerk", Some("SSO support"), "sam").unwrap();
+        assert_eq!(unblocked, vec![work.id.clone()]);
+        
+        let answered = store.question(&question.id).unwrap().unwrap();
+        assert_eq!(answered.decision.as_deref(), Some("clerk"));
+        assert!(store.open_questions().unwrap().is_empty());
+    }
+    
+    #[test]
+    fn test_schedules_materialize_into_ready() {
+        let mut store = WorkStore::in_memory().unwrap();
+        let now = Utc::now();
+        
+        let standup = store.create_schedule(
+            CreateTask { title: "Standup notes".to_string(), labels: vec!["ops".to_string()], ..Default::default() },
+            Recurrence::Cron("* * * * *".to_string()),
+        ).unwrap();
+        let once = store.create_schedule(
+            CreateTask { title: "Renew cert".to_string(), ..Default::default() },
+            Recurrence::DueAt(now + chrono::Duration::hours(1)),
+        ).unwrap();
+        assert!(store.create_schedule(CreateTask::default(), Recurrence::Cron("0 0 31 2 *".to_string())).is_err());
+        
+        // Nothing is due until the schedule's time comes
+        assert!(store.materialize_due(now - chrono::Duration::minutes(5)).unwrap().is_empty());
+        
+        let later = now + chrono::Duration::minutes(2);
+        let created = store.materialize_due(later).unwrap();
+        assert_eq!(created.len(), 1);
+        assert_eq!(created[0].labels, vec!["ops"]);
+        assert!(store.ready().unwrap().iter().any(|t| t.id == created[0].id));
+        
+        // The last run is still open, so the next firing is skipped
+        assert!(store.materialize_due(later + chrono::Duration::minutes(1)).unwrap().is_empty());
+        store.complete(&created[0].id, None).unwrap();
+        assert_eq!(store.materialize_due(later + chrono::Duration::minutes(2)).unwrap().len(), 1);
+        
+        // One-offs run once
+        let due = now + chrono::Duration::hours(2);
+        let created = store.materialize_due(due).unwrap();
+        assert_eq!(created.iter().filter(|t| t.title == "Renew cert").count(), 1);
+        let once = store.schedules().unwrap().into_iter().find(|s| s.id == once.id).unwrap();
+        assert_eq!(once.next_run, None);
+        assert!(store.materialize_due(due + chrono::Duration::hours(1)).unwrap().iter().all(|t| t.title != "Renew cert"));
+        
+        store.delete_schedule(&standup.id).unwrap();
+        assert!(matches!(store.delete_schedule(&standup.id), Err(WorkError::ScheduleNotFound(_))));
+    }
+    
+    #[test]
+    fn test_dependency_cycles() {
+        let mut store = WorkStore::in_memory().unwrap();
+        let a = store.create(CreateTask { title: "A".to_string(), ..Default::default() }).unwrap();
+        let b = store.create(CreateTask { title: "B".to_string(), ..Default::default() }).unwrap();
+        let c = store.create(CreateTask { title: "C".to_string(), ..Default::default() }).unwrap();
+        
+        store.add_dependency(&a.id, &b.id).unwrap();
+        store.add_dependency(&b.id, &c.id).unwrap();
+        
+        // Indirect cycles are rejected too, with the path that closes them
+        match store.add_dependency(&c.id, &a.id) {
+            Err(WorkError::CycleDetected(path)) => assert_eq!(path, vec![c.id.clone(), a.id.clone(), b.id.clone(), c.id.clone()]),
+            other => panic!("expected a cycle, got {:?}", other),
+        }
+        assert!(matches!(store.add_dependency(&a.id, &a.id), Err(WorkError::CycleDetected(_))));
+        // Diamonds aren't cycles
+        store.add_dependency(&a.id, &c.id).unwrap();
+        assert!(store.dependency_cycles().unwrap().is_empty());
+        
+        // Cycles that predate the check are still found
+        store.conn.execute(
+            "INSERT INTO dependencies (task_id, depends_on, created_at) VALUES (?1, ?2, ?3)",
+            params![c.id, a.id, Utc::now().to_rfc3339()],
+        ).unwrap();
+        let cycles = store.dependency_cycles().unwrap();
+        assert!(!cycles.is_empty());
+        assert!(cycles.iter().all(|cycle| cycle.len() > 2 && cycle.first() == cycle.last()));
+        store.remove_dependency(&c.id, &a.id).unwrap();
+        assert!(store.dependency_cycles().unwrap().is_empty());
+    }
+    
+    #[test]
+    fn test_history_records_transitions() {
+        let mut store = WorkStore::in_memory().unwrap();
+        let first = store.create(CreateTask { title: "Schema".to_string(), ..Default::default() }).unwrap();
+        let task = store.create(CreateTask { title: "Migrate".to_string(), ..Default::default() }).unwrap();
+        store.add_dependency(&task.id, &first.id).unwrap();
+        
+        store.claim(&first.id, "claude-code").unwrap();
+        store.release(&first.id).unwrap();
+        store.update_priority(&first.id, Priority::High).unwrap();
+        store.claim(&first.id, "codex").unwrap();
+        store.complete(&first.id, Some("commit abc")).unwrap();
+        
+        let events = |id: &str| -> Vec<(String, Option<String>)> {
+            store.history(id).unwrap().into_iter().map(|e| (e.event, e.agent)).collect()
+        };
+        let some = |s: &str| Some(s.to_string());
+        assert_eq!(events(&first.id), vec![
+            ("created".to_string(), None),
+            ("claimed".to_string(), some("claude-code")),
+            ("released".to_string(), some("claude-code")),
+            ("priority".to_string(), None),
+            ("claimed".to_string(), some("codex")),
+            ("completed".to_string(), some("codex")),
+        ]);
+        // Bulk status updates are recorded too
+        let names: Vec<String> = events(&task.id).into_iter().map(|(e, _)| e).collect();
+        assert_eq!(names, vec!["created", "blocked", "unblocked"]);
+        
+        let priority = &store.history(&first.id).unwrap()[3];
+        assert_eq!((priority.from.as_deref(), priority.to.as_deref()), (Some("normal"), Some("high")));
+        
+        assert!(store.conn.execute("UPDATE task_history SET event = 'created'", []).is_err());
+        assert!(store.conn.execute("DELETE FROM task_history", []).is_err());
+    }
+    
+    #[test]
+    fn test_comments() {
+        let mut store = WorkStore::in_memory().unwrap();
+        let task = store.create(CreateTask { title: "Flaky login test".to_string(), ..Default::default() }).unwrap();
+        let other = store.create(CreateTask { title: "Docs".to_string(), ..Default::default() }).unwrap();
+        
+        store.add_comment(&task.id, "claude-code", "Fails only on CI; suspect timezone").unwrap();
+        store.add_comment(&other.id, "codex", "Started on the API section").unwrap();
+        let reply = store.add_comment(&task.id, "codex", "  Confirmed: TZ=UTC fixes it  ").unwrap();
+        assert_eq!(reply.body, "Confirmed: TZ=UTC fixes it");
+        
+        let thread = store.comments(&task.id).unwrap();
+        let authors: Vec<&str> = thread.iter().map(|c| c.author.as_str()).collect();
+        assert_eq!(authors, vec!["claude-code", "codex"]);
+        // The description is untouched
+        assert_eq!(store.get(&task.id).unwrap().unwrap().description, None);
+        
+        let recent = store.recent_comments(2).unwrap();
+        assert_eq!(recent[0].id, reply.id);
+        assert_eq!(recent[1].task_id, other.id);
+        
+        assert!(matches!(store.add_comment(&task.id, "codex", " "), Err(WorkError::EmptyComment)));
+        assert!(matches!(store.add_comment("lm-none", "codex", "hi"), Err(WorkError::NotFound(_))));
+    }
+    
+    #[test]
+    fn test_paused_label_and_repo() {
+        let mut store = WorkStore::in_memory().unwrap().with_repo("web");
+        let deploy = store.create(CreateTask { title: "Ship".to_string(), labels: vec!["deploy".to_string()], ..Default::default() }).unwrap();
+        let docs = store.create(CreateTask { title: "Docs".to_string(), ..Default::default() }).unwrap();
+        
+        store.pause(PauseScope::Label("deploy".to_string()), Some("incident"), Some("oncall")).unwrap();
+        let ready: Vec<String> = store.ready().unwrap().into_iter().map(|t| t.id).collect();
+        assert_eq!(ready, vec![docs.id.clone()]);
+        assert!(matches!(store.claim(&deploy.id, "agent"), Err(WorkError::Paused { .. })));
+        
+        let summary = store.summary().unwrap();
+        assert_eq!((summary.ready, summary.paused), (2, 1));
+        assert_eq!(summary.pauses[0].reason.as_deref(), Some("incident"));
+        
+        // A repo pause holds everything in it
+        store.pause(PauseScope::Repo("web".to_string()), None, None).unwrap();
+        assert!(store.ready().unwrap().is_empty());
+        
+        assert!(store.unpause(&PauseScope::Label("deploy".to_string())).unwrap());
+        assert!(store.unpause(&PauseScope::Repo("web".to_string())).unwrap());
+        assert!(!store.unpause(&PauseScope::Repo("web".to_string())).unwrap());
+        assert_eq!(store.ready().unwrap().len(), 2);
+        store.claim(&deploy.id, "agent").unwrap();
+    }
 }
@@ -0,0 +1,456 @@
+//! Formula Runs
+//!
+//! Running a formula turns it into tasks: one root task for the run and a
+//! sub-task per step, each blocked by the step before it. Consecutive
+//! `parallel` steps wait on the same step and don't block each other; the
+//! step after them waits for all of them. The root is blocked by the last
+//! steps, so it only becomes ready once the whole workflow is done.
+//!
+//! The orchestrator then works through the run (`Orchestrator::drive_formula`):
+//! each ready step is claimed for its agent, executed, verified with the
+//! step's `verify` command, and completed. Steps marked `checkpoint = true`
+//! record a checkpoint on the session that ran them. A step that fails stops
+//! the run and stays claimed, like any other failed orchestrator task.
+//!
+//! `plan` previews a run without touching the work store: the task tree with
+//! variables filled in, which agent each step would go to, and what it
+//! should cost.
+//!
+//! A fan-out (`Orchestrator::fan_out`) is the unordered counterpart: a task's
+//! ready sub-tasks are routed to different agents and run at once. It ends
+//! when all of them complete or one fails; the failed task stays claimed and
+//! the rest are stopped and released back to the queue.
+
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::agents::AgentProfile;
+use crate::formulas::{Formula, Step};
+use crate::routing::{Router, RoutingConstraints, RoutingDecision, RoutingStrategy};
+use crate::templates::PlannedTask;
+use crate::work::{Priority, Status, Task, TaskKind};
+
+/// Where a formula run got to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunStatus {
+    /// Tasks created, steps still to run
+    Running,
+    /// Every step completed
+    Completed,
+    /// A step failed
+    Failed,
+    /// Steps remain but none is ready (paused, or blocked outside the run)
+    Stalled,
+}
+
+impl RunStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RunStatus::Running => "running",
+            RunStatus::Completed => "completed",
+            RunStatus::Failed => "failed",
+            RunStatus::Stalled => "stalled",
+        }
+    }
+}
+
+/// Where one step of a run got to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StepStatus {
+    Pending,
+    Completed,
+    Failed,
+}
+
+/// A formula step and the task created for it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StepRun {
+    pub step_id: String,
+    pub task_id: String,
+    /// Agent the step runs on (the step's, else the formula's)
+    pub agent: String,
+    pub verify: Option<String>,
+    pub checkpoint: bool,
+    pub status: StepStatus,
+    /// Checkpoint recorded when the step completed
+    pub checkpoint_id: Option<String>,
+    pub duration_secs: Option<f64>,
+    pub error: Option<String>,
+}
+
+/// A formula turned into tasks, and how far it has run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FormulaRun {
+    pub formula: String,
+    /// Task for the run as a whole; the steps are its sub-tasks
+    pub root_id: String,
+    pub steps: Vec<StepRun>,
+    pub status: RunStatus,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+}
+
+impl FormulaRun {
+    /// A run over the tasks created from [`formula_tasks`]
+    pub fn new(formula: &Formula, root: &Task, step_tasks: &[Task]) -> Self {
+        let steps = formula.steps.iter().zip(step_tasks)
+            .map(|(step, task)| StepRun {
+                step_id: step.id.clone(),
+                task_id: task.id.clone(),
+                agent: formula.agent_for_step(step),
+                verify: step.verify.clone(),
+                checkpoint: step.checkpoint,
+                status: StepStatus::Pending,
+                checkpoint_id: None,
+                duration_secs: None,
+                error: None,
+            })
+            .collect();
+        
+        Self {
+            formula: formula.name.clone(),
+            root_id: root.id.clone(),
+            steps,
+            status: RunStatus::Running,
+            started_at: Utc::now(),
+            ended_at: None,
+        }
+    }
+    
+    pub fn step(&self, step_id: &str) -> Option<&StepRun> {
+        self.steps.iter().find(|s| s.step_id == step_id)
+    }
+    
+    pub fn completed_steps(&self) -> usize {
+        self.steps.iter().filter(|s| s.status == StepStatus::Completed).count()
+    }
+    
+    /// Mark the run finished
+    pub fn finish(&mut self, status: RunStatus) {
+        self.status = status;
+        self.ended_at = Some(Utc::now());
+    }
+}
+
+/// A sub-task dispatched as part of a fan-out
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FanOutMember {
+    pub task_id: String,
+    /// Agent the task was routed to
+    pub agent: String,
+    /// Pending until it finishes; stays pending when stopped because a
+    /// sibling failed
+    pub status: StepStatus,
+    pub duration_secs: Option<f64>,
+    pub error: Option<String>,
+}
+
+/// A task's ready sub-tasks dispatched together, and how far they got
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FanOut {
+    pub parent_id: String,
+    pub members: Vec<FanOutMember>,
+    pub status: RunStatus,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+}
+
+impl FanOut {
+    /// A fan-out over `(task, agent)` assignments
+    pub fn new(parent_id: &str, assignments: impl IntoIterator<Item = (String, String)>) -> Self {
+        let members = assignments.into_iter()
+            .map(|(task_id, agent)| FanOutMember {
+                task_id,
+                agent,
+                status: StepStatus::Pending,
+                duration_secs: None,
+                error: None,
+            })
+            .collect();
+        
+        Self {
+            parent_id: parent_id.to_string(),
+            members,
+            status: RunStatus::Running,
+            started_at: Utc::now(),
+            ended_at: None,
+        }
+    }
+    
+    pub fn completed(&self) -> usize {
+        self.members.iter().filter(|m| m.status == StepStatus::Completed).count()
+    }
+    
+    /// Mark the fan-out finished
+    pub fn finish(&mut self, status: RunStatus) {
+        self.status = status;
+        self.ended_at = Some(Utc::now());
+    }
+}
+
+/// The task tree for a run: a root for the formula with a child per step,
+/// variables filled in. Step tasks carry the formula's labels and their own.
+pub fn formula_tasks(formula: &Formula, vars: &HashMap<String, String>) -> PlannedTask {
+    let children = formula.steps.iter()
+        .map(|step| {
+            let mut labels = formula.labels.clone();
+            labels.extend(step.labels.iter().filter(|l| !formula.labels.contains(l)).cloned());
+            let prompt = step.prompt.as_deref().unwrap_or(&step.description);
+            PlannedTask {
+                title: formula.expand_template(&step.title, vars),
+                description: Some(formula.expand_template(prompt, vars)),
+                labels,
+                priority: Priority::Normal,
+                children: Vec::new(),
+            }
+        })
+        .collect();
+    
+    let mut variables: Vec<String> = formula.variables.iter()
+        .filter_map(|v| vars.get(&v.name).or(v.default.as_ref()).map(|value| format!("- {}: {}", v.name, value)))
+        .collect();
+    variables.sort();
+    
+    PlannedTask {
+        title: format!("[{}] {}", formula.name, formula.expand_template(&formula.description, vars)),
+        description: (!variables.is_empty()).then(|| format!("Variables:\n{}", variables.join("\n"))),
+        labels: formula.labels.clone(),
+        priority: Priority::Normal,
+        children,
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Dry Runs
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// A step as it would run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StepPlan {
+    pub step_id: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub labels: Vec<String>,
+    /// Steps this one would be blocked by
+    pub waits_on: Vec<String>,
+    /// Agent the step asks for (the step's, else the formula's)
+    pub agent: String,
+    /// Where the router would send it; a different agent than `agent` means
+    /// the requested one isn't registered or has no capacity
+    pub routing: Option<RoutingDecision>,
+    /// The formula's per-step estimate
+    pub estimated_tokens: u64,
+    /// At the routed agent's prices
+    pub estimated_cost: Option<f64>,
+    pub verify: Option<String>,
+    pub checkpoint: bool,
+}
+
+/// What running a formula would create and cost, with nothing written
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FormulaPlan {
+    pub formula: String,
+    /// The task tree a run would create: the root and a child per step
+    pub tasks: PlannedTask,
+    pub steps: Vec<StepPlan>,
+    pub estimated_tokens: u64,
+    /// Sum over the steps that could be costed
+    pub estimated_cost: f64,
+    /// Steps that couldn't be routed, or went to another agent
+    pub warnings: Vec<String>,
+}
+
+/// Plan a formula run against a set of agent profiles. Variables are not
+/// validated here; see `FormulaRegistry::plan`.
+pub fn plan(formula: &Formula, vars: &HashMap<String, String>, profiles: &[AgentProfile]) -> FormulaPlan {
+    let tasks = formula_tasks(formula, vars);
+    let dependencies = step_dependencies(&formula.steps);
+    let mut router = Router::new();
+    let mut warnings = Vec::new();
+    
+    let steps: Vec<StepPlan> = formula.steps.iter().zip(&tasks.children).zip(&dependencies)
+        .map(|((step, planned), waits_on)| {
+            let agent = formula.agent_for_step(step);
+            let task = preview_task(step, planned);
+            let requested = RoutingConstraints {
+                preferred_agents: vec![agent.clone()],
+                ..Default::default()
+            };
+            let routing = match router.route_profiles(&task, profiles.to_vec(), RoutingStrategy::Specific, &requested) {
+                Ok(decision) => Some(decision),
+                Err(_) => {
+                    let fallback = router.route_profiles(&task, profiles.to_vec(), RoutingStrategy::Best, &RoutingConstraints::default()).ok();
+                    warnings.push(match &fallback {
+                        Some(decision) => format!("{}: {} is unavailable, would route to {}", step.id, agent, decision.agent_id),
+                        None => format!("{}: no agent available", step.id),
+                    });
+                    fallback
+                }
+            };
+            let estimated_cost = routing.as_ref()
+                .and_then(|decision| profiles.iter().find(|p| p.id == decision.agent_id))
+                .map(|profile| profile.cost.estimate(formula.estimated_tokens));
+            
+            StepPlan {
+                step_id: step.id.clone(),
+                title: planned.title.clone(),
+                description: planned.description.clone(),
+                labels: planned.labels.clone(),
+                waits_on: waits_on.iter().map(|&i| formula.steps[i].id.clone()).collect(),
+                agent,
+                routing,
+                estimated_tokens: formula.estimated_tokens,
+                estimated_cost,
+                verify: step.verify.clone(),
+                checkpoint: step.checkpoint,
+            }
+        })
+        .collect();
+    
+    FormulaPlan {
+        formula: formula.name.clone(),
+        estimated_tokens: formula.estimated_tokens * steps.len() as u64,
+        estimated_cost: steps.iter().filter_map(|s| s.estimated_cost).sum(),
+        tasks,
+        steps,
+        warnings,
+    }
+}
+
+/// A stand-in task for routing a step that hasn't been created
+fn preview_task(step: &Step, planned: &PlannedTask) -> Task {
+    Task {
+        id: format!("plan-{}", step.id),
+        title: planned.title.clone(),
+        description: planned.description.clone(),
+        status: Status::Ready,
+        priority: planned.priority,
+        agent: None,
+        labels: planned.labels.clone(),
+        parent: None,
+        evidence: None,
+        actual_cost_usd: None,
+        repo: None,
+        kind: TaskKind::Task,
+        rollup: None,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+/// For each step, the indices of the steps it waits on
+pub fn step_dependencies(steps: &[Step]) -> Vec<Vec<usize>> {
+    let mut dependencies = Vec::with_capacity(steps.len());
+    // What the next sequential step waits on
+    let mut before: Vec<usize> = Vec::new();
+    // The run of parallel steps in progress, and what they all wait on
+    let mut group: Vec<usize> = Vec::new();
+    let mut group_before: Vec<usize> = Vec::new();
+    
+    for (i, step) in steps.iter().enumerate() {
+        if step.parallel {
+            if group.is_empty() {
+                group_before = before.clone();
+            }
+            dependencies.push(group_before.clone());
+            group.push(i);
+        } else {
+            if !group.is_empty() {
+                before = std::mem::take(&mut group);
+            }
+            dependencies.push(before.clone());
+            before = vec![i];
+        }
+    }
+    
+    dependencies
+}
+
+/// Steps nothing else waits on: the ones the run's root is blocked by
+pub fn final_steps(dependencies: &[Vec<usize>]) -> Vec<usize> {
+    (0..dependencies.len())
+        .filter(|i| !dependencies.iter().any(|deps| deps.contains(i)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_step_dependencies_with_parallel_group() {
+        let step = |id: &str, parallel: bool| Step {
+            id: id.to_string(),
+            title: format!("Do {{{{target}}}} {}", id),
+            description: id.to_string(),
+            agent: None,
+            labels: vec![id.to_string()],
+            prompt: None,
+            verify: None,
+            checkpoint: false,
+            parallel,
+        };
+        let steps = vec![
+            step("plan", false),
+            step("api", true),
+            step("ui", true),
+            step("review", false),
+        ];
+        
+        let dependencies = step_dependencies(&steps);
+        assert_eq!(dependencies, vec![vec![], vec![0], vec![0], vec![1, 2]]);
+        assert_eq!(final_steps(&dependencies), vec![3]);
+        
+        // A workflow ending in a parallel group finishes with all of it
+        assert_eq!(final_steps(&step_dependencies(&steps[..3])), vec![1, 2]);
+        
+        let mut formula = Formula::basic_task();
+        formula.steps = steps;
+        let vars = HashMap::from([("target".to_string(), "search".to_string())]);
+        formula.variables.push(crate::formulas::Variable {
+            name: "target".to_string(),
+            description: "What to work on".to_string(),
+            required: true,
+            default: None,
+            ..Default::default()
+        });
+        let plan = formula_tasks(&formula, &vars);
+        assert_eq!(plan.children.len(), 4);
+        assert_eq!(plan.children[1].title, "Do search api");
+        assert!(plan.children[1].labels.contains(&"api".to_string()));
+        assert!(plan.description.unwrap().contains("target: search"));
+    }
+    
+    #[test]
+    fn test_plan_routes_and_costs_each_step() {
+        let mut profiles: Vec<AgentProfile> = crate::models::ModelsConfig::defaults().profiles();
+        for profile in &mut profiles {
+            profile.available = true;
+        }
+        let mut formula = Formula::feature();
+        formula.agent = Some(profiles[0].id.clone());
+        for step in &mut formula.steps {
+            step.agent = None;
+        }
+        formula.steps[1].agent = Some("nobody".to_string());
+        let vars = HashMap::from([
+            ("feature_name".to_string(), "dark mode".to_string()),
+            ("package".to_string(), "io".to_string()),
+        ]);
+        
+        let plan = plan(&formula, &vars, &profiles);
+        assert_eq!(plan.tasks.count(), formula.steps.len() + 1);
+        assert_eq!(plan.steps[0].routing.as_ref().unwrap().agent_id, profiles[0].id);
+        assert!(plan.steps[0].estimated_cost.unwrap() > 0.0);
+        assert_eq!(plan.steps[1].waits_on, vec![formula.steps[0].id.clone()]);
+        
+        // An agent that isn't registered falls back to the router's pick
+        assert_eq!(plan.warnings.len(), 1);
+        assert!(plan.warnings[0].starts_with(&format!("{}: nobody is unavailable", formula.steps[1].id)));
+        assert_ne!(plan.steps[1].routing.as_ref().unwrap().agent_id, "nobody");
+        assert!((plan.estimated_cost - plan.steps.iter().filter_map(|s| s.estimated_cost).sum::<f64>()).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,91 @@
+//! Drives `loom-mcp` over pipes with the recorded transcripts in
+//! `tests/fixtures/mcp/`.
+//!
+//! Transcript lines starting with `>` are written to the server's stdin
+//! verbatim; `<` lines are patterns the next stdout line must match, where
+//! `"*"` matches any value and objects may carry extra keys. After the last
+//! line stdin is closed, and the server must exit cleanly without writing
+//! anything else (so notifications really got no response).
+
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::time::Duration;
+use serde_json::Value;
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Whether `actual` matches the `expected` pattern
+fn matches(expected: &Value, actual: &Value) -> bool {
+    match (expected, actual) {
+        (Value::String(s), _) if s == "*" => true,
+        (Value::Object(expected), Value::Object(actual)) => {
+            expected.iter().all(|(key, value)| actual.get(key).is_some_and(|a| matches(value, a)))
+        }
+        (Value::Array(expected), Value::Array(actual)) => {
+            expected.len() == actual.len() && expected.iter().zip(actual).all(|(e, a)| matches(e, a))
+        }
+        _ => expected == actual,
+    }
+}
+
+fn run_transcript(name: &str) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/mcp").join(name);
+    let transcript = std::fs::read_to_string(&path).unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    
+    let mut server = Command::new(env!("CARGO_BIN_EXE_loom-mcp"))
+        .arg("--path")
+        .arg(dir.path())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+    let mut stdin = server.stdin.take().unwrap();
+    
+    // Read on a thread so a missing response fails the test instead of hanging it
+    let stdout = server.stdout.take().unwrap();
+    let (lines, responses) = mpsc::channel();
+    std::thread::spawn(move || {
+        for line in BufReader::new(stdout).lines() {
+            if lines.send(line.unwrap()).is_err() {
+                break;
+            }
+        }
+    });
+    
+    for (number, line) in transcript.lines().enumerate() {
+        let at = format!("{}:{}", name, number + 1);
+        if let Some(request) = line.strip_prefix("> ") {
+            writeln!(stdin, "{}", request).unwrap();
+            stdin.flush().unwrap();
+        } else if let Some(pattern) = line.strip_prefix("< ") {
+            let expected: Value = serde_json::from_str(pattern).unwrap_or_else(|e| panic!("{}: bad pattern: {}", at, e));
+            let response = responses.recv_timeout(TIMEOUT).unwrap_or_else(|_| panic!("{}: no response", at));
+            let actual: Value = serde_json::from_str(&response).unwrap_or_else(|e| panic!("{}: response isn't JSON ({}): {}", at, e, response));
+            assert!(matches(&expected, &actual), "{}: expected {}\n  got {}", at, expected, actual);
+        }
+    }
+    
+    drop(stdin);
+    assert!(server.wait().unwrap().success(), "{}: server exited with an error", name);
+    let extra: Vec<String> = responses.try_iter().collect();
+    assert!(extra.is_empty(), "{}: unexpected output {:?}", name, extra);
+}
+
+#[test]
+fn test_handshake_transcript() {
+    run_transcript("handshake.transcript");
+}
+
+#[test]
+fn test_negotiation_transcript() {
+    run_transcript("negotiation.transcript");
+}
+
+#[test]
+fn test_malformed_frames_transcript() {
+    run_transcript("malformed.transcript");
+}
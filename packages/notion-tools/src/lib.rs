@@ -12,9 +12,15 @@ pub mod mcp;
 use wasm_bindgen::prelude::*;
 
 // Re-export processor functions for WASM
+pub use processors::cross_database::find_cross_database_duplicates_impl;
 pub use processors::duplicates::find_duplicates_impl;
-pub use processors::pages::simplify_pages_impl;
+pub use processors::formula::evaluate_formula_impl;
+pub use processors::pages::{simplified_pages_schema, simplify_pages_impl, SIMPLIFIED_PAGES_SCHEMA_VERSION};
+pub use processors::profiles::{DatabaseProfile, ProcessorConfig};
+pub use processors::quality::score_pages_impl;
+pub use processors::sync_state::sync_diff_impl;
 pub use processors::schema::format_schema_impl;
+pub use processors::write::validate_write_impl;
 
 /// Maximum input size in bytes (10MB) to prevent OOM
 const MAX_INPUT_SIZE: usize = 10 * 1024 * 1024;
@@ -42,31 +48,109 @@ pub fn init() {
 /// Format a Notion database schema for LLM context.
 ///
 /// Takes JSON string of database properties, returns formatted string.
+/// With a config and the database's ID, its profile selects and renames
+/// properties; both may be omitted.
 /// Max input: 10MB
 #[wasm_bindgen]
-pub fn format_schema(properties_json: &str) -> Result<String, JsValue> {
+pub fn format_schema(
+    properties_json: &str,
+    config_json: Option<String>,
+    database_id: Option<String>,
+) -> Result<String, JsValue> {
     check_input_size(properties_json, "format_schema").map_err(|e| JsValue::from_str(&e))?;
-    format_schema_impl(properties_json).map_err(|e| JsValue::from_str(&e))
+    format_schema_impl(properties_json, config_json.as_deref().unwrap_or(""), database_id.as_deref().unwrap_or(""))
+        .map_err(|e| JsValue::from_str(&e))
 }
 
 /// Simplify Notion page results for agent processing.
 ///
-/// Extracts titles and key metadata from page objects.
+/// Extracts titles and key metadata from page objects, plus the properties
+/// each page's database profile selects when a config is given.
+/// Returns `{schema_version, pages}`; see `simplified_pages_schema`.
 /// Max input: 10MB
 #[wasm_bindgen]
-pub fn simplify_pages(pages_json: &str) -> Result<String, JsValue> {
+pub fn simplify_pages(pages_json: &str, config_json: Option<String>) -> Result<String, JsValue> {
     check_input_size(pages_json, "simplify_pages").map_err(|e| JsValue::from_str(&e))?;
-    simplify_pages_impl(pages_json).map_err(|e| JsValue::from_str(&e))
+    simplify_pages_impl(pages_json, config_json.as_deref().unwrap_or("")).map_err(|e| JsValue::from_str(&e))
+}
+
+/// JSON Schema for the `simplify_pages` output.
+///
+/// Lets Worker consumers validate payloads and check `schema_version`.
+#[wasm_bindgen(js_name = simplified_pages_schema)]
+pub fn simplified_pages_schema_json() -> String {
+    simplified_pages_schema().to_string()
 }
 
 /// Find duplicate pages by title.
 ///
 /// Returns JSON with page IDs to archive based on keep_strategy ("oldest" or "newest").
+/// An optional config's profiles set each database's title property and
+/// the properties that must also match.
 /// Max input: 10MB
 #[wasm_bindgen]
-pub fn find_duplicates(pages_json: &str, keep_strategy: &str) -> Result<String, JsValue> {
+pub fn find_duplicates(pages_json: &str, keep_strategy: &str, config_json: Option<String>) -> Result<String, JsValue> {
     check_input_size(pages_json, "find_duplicates").map_err(|e| JsValue::from_str(&e))?;
-    find_duplicates_impl(pages_json, keep_strategy).map_err(|e| JsValue::from_str(&e))
+    find_duplicates_impl(pages_json, keep_strategy, config_json.as_deref().unwrap_or("")).map_err(|e| JsValue::from_str(&e))
+}
+
+/// Find records duplicated across databases.
+///
+/// Takes pages grouped by database and a mapping of which properties
+/// represent the same concept in each database.
+/// Max input: 10MB
+#[wasm_bindgen]
+pub fn find_cross_database_duplicates(databases_json: &str, mapping_json: &str) -> Result<String, JsValue> {
+    check_input_size(databases_json, "find_cross_database_duplicates").map_err(|e| JsValue::from_str(&e))?;
+    find_cross_database_duplicates_impl(databases_json, mapping_json).map_err(|e| JsValue::from_str(&e))
+}
+
+/// Classify a batch of pages against previous sync state.
+///
+/// Returns created/updated/unchanged/deleted page IDs, the next cursor, and
+/// the serialized state to pass in next time (empty string on first sync).
+/// Set `full_scan` when the batch is every page, so missing pages count as deleted.
+/// Max input: 10MB
+#[wasm_bindgen]
+pub fn sync_diff(previous_state: &str, pages_json: &str, full_scan: bool) -> Result<String, JsValue> {
+    check_input_size(pages_json, "sync_diff").map_err(|e| JsValue::from_str(&e))?;
+    check_input_size(previous_state, "sync_diff").map_err(|e| JsValue::from_str(&e))?;
+    sync_diff_impl(previous_state, pages_json, full_scan).map_err(|e| JsValue::from_str(&e))
+}
+
+/// Evaluate a Notion formula on each page.
+///
+/// Options: `{overrides?, page_overrides?, now?}` property values to
+/// substitute for "what if" analysis; pass an empty string for none.
+/// Max input: 10MB
+#[wasm_bindgen]
+pub fn evaluate_formula(formula: &str, pages_json: &str, options_json: &str) -> Result<String, JsValue> {
+    check_input_size(pages_json, "evaluate_formula").map_err(|e| JsValue::from_str(&e))?;
+    evaluate_formula_impl(formula, pages_json, options_json).map_err(|e| JsValue::from_str(&e))
+}
+
+/// Score pages on completeness.
+///
+/// Rates empty required properties, stub bodies, and stale edits; returns
+/// per-page scores (lowest first) and aggregate stats.
+/// Max input: 10MB
+#[wasm_bindgen]
+pub fn score_pages(pages_json: &str, options_json: &str) -> Result<String, JsValue> {
+    check_input_size(pages_json, "score_pages").map_err(|e| JsValue::from_str(&e))?;
+    score_pages_impl(pages_json, options_json).map_err(|e| JsValue::from_str(&e))
+}
+
+/// Check an agent-built create/update payload against a database schema.
+///
+/// Reports unknown and read-only properties, values of the wrong type, and
+/// select, multi-select and status options the schema doesn't have, so a
+/// bad write never reaches the API. Returns `{valid, checked, errors}`.
+/// Max input: 10MB
+#[wasm_bindgen]
+pub fn validate_write(payload_json: &str, schema_json: &str) -> Result<String, JsValue> {
+    check_input_size(payload_json, "validate_write").map_err(|e| JsValue::from_str(&e))?;
+    check_input_size(schema_json, "validate_write").map_err(|e| JsValue::from_str(&e))?;
+    validate_write_impl(payload_json, schema_json).map_err(|e| JsValue::from_str(&e))
 }
 
 /// Estimate token count for text.
@@ -1,6 +1,6 @@
 //! MCP tool definitions and implementations
 
-use crate::processors::{duplicates, pages, schema};
+use crate::processors::{cross_database, duplicates, formula, pages, quality, schema, sync_state, write};
 use serde_json::Value;
 
 /// Get all available tool definitions
@@ -15,6 +15,14 @@ pub fn get_tool_definitions() -> Vec<super::ToolDefinition> {
                     "properties_json": {
                         "type": "string",
                         "description": "JSON string of Notion database properties object"
+                    },
+                    "database_id": {
+                        "type": "string",
+                        "description": "Optional: the database's ID, to apply its profile from config_json"
+                    },
+                    "config_json": {
+                        "type": "string",
+                        "description": "Optional JSON object {databases: {<database id>: profile}, default?: profile} where a profile is {title_property?, properties?: string[], rename?: {property: name}, date_format?: strftime, match_on?: string[]}"
                     }
                 },
                 "required": ["properties_json"]
@@ -28,31 +36,137 @@ pub fn get_tool_definitions() -> Vec<super::ToolDefinition> {
                 "properties": {
                     "pages_json": {
                         "type": "string",
-                        "description": "JSON array of pages with id, title, and created_time fields"
+                        "description": "JSON array of pages with id, title, and created_time fields, or Notion page objects"
                     },
                     "keep_strategy": {
                         "type": "string",
                         "enum": ["oldest", "newest"],
                         "description": "Which duplicate to keep: 'oldest' or 'newest'"
+                    },
+                    "config_json": {
+                        "type": "string",
+                        "description": "Optional JSON object {databases: {<database id>: profile}, default?: profile} where a profile is {title_property?, properties?: string[], rename?: {property: name}, date_format?: strftime, match_on?: string[]}"
                     }
                 },
                 "required": ["pages_json"]
             }),
         },
+        super::ToolDefinition {
+            name: "notion_find_cross_database_duplicates".to_string(),
+            description: "Find the same record in more than one Notion database (e.g. a customer in both CRM and Onboarding). Takes pages from each database plus a mapping of which properties represent the same concept.".to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "databases_json": {
+                        "type": "string",
+                        "description": "JSON array of {name, pages} where pages are Notion page objects"
+                    },
+                    "mapping_json": {
+                        "type": "string",
+                        "description": "JSON object {concepts: [{concept, properties: {<database name>: <property name>}, normalize?: 'text'|'digits'}], match?: 'all'|'any'}. 'all' (default) requires every concept to match; 'any' links pages sharing any concept value."
+                    }
+                },
+                "required": ["databases_json", "mapping_json"]
+            }),
+        },
         super::ToolDefinition {
             name: "notion_simplify_pages".to_string(),
-            description: "Simplify Notion page objects by extracting titles and key metadata for easier processing.".to_string(),
+            description: "Simplify Notion page objects by extracting titles and key metadata for easier processing. Returns {schema_version, pages}.".to_string(),
             input_schema: serde_json::json!({
                 "type": "object",
                 "properties": {
                     "pages_json": {
                         "type": "string",
                         "description": "JSON array of Notion page objects"
+                    },
+                    "config_json": {
+                        "type": "string",
+                        "description": "Optional JSON object {databases: {<database id>: profile}, default?: profile} where a profile is {title_property?, properties?: string[], rename?: {property: name}, date_format?: strftime, match_on?: string[]}"
                     }
                 },
                 "required": ["pages_json"]
             }),
         },
+        super::ToolDefinition {
+            name: "notion_score_pages".to_string(),
+            description: "Score Notion pages on completeness (empty required properties, stub bodies, stale edits). Returns per-page scores, lowest first, with the issues found and aggregate stats, so cleanup can target the worst pages.".to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "pages_json": {
+                        "type": "string",
+                        "description": "JSON array of Notion page objects, each optionally with a 'content' string of body text"
+                    },
+                    "options_json": {
+                        "type": "string",
+                        "description": "JSON object {required_properties?: string[], min_body_words?: number (default 50), stale_after_days?: number (default 90), worst?: number (default 10)}"
+                    }
+                },
+                "required": ["pages_json"]
+            }),
+        },
+        super::ToolDefinition {
+            name: "notion_evaluate_formula".to_string(),
+            description: "Evaluate a Notion formula (numbers, text, booleans, dates; prop(), if/ifs, dateAdd/dateBetween/formatDate, ...) on each page, optionally with substituted property values for what-if analysis, without writing to Notion.".to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "formula": {
+                        "type": "string",
+                        "description": "Formula source, e.g. dateBetween(prop(\"Due\"), now(), \"days\")"
+                    },
+                    "pages_json": {
+                        "type": "string",
+                        "description": "JSON array of Notion page objects (or {id, properties} with plain values)"
+                    },
+                    "options_json": {
+                        "type": "string",
+                        "description": "JSON object {overrides?: {property: value} for every page, page_overrides?: {page_id: {property: value}}, now?: RFC 3339 time}"
+                    }
+                },
+                "required": ["formula", "pages_json"]
+            }),
+        },
+        super::ToolDefinition {
+            name: "notion_sync_diff".to_string(),
+            description: "Classify a batch of Notion pages against previous sync state (per-page last_edited_time and content hash) into created/updated/unchanged/deleted, and return the new serialized state and query cursor.".to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "previous_state": {
+                        "type": "string",
+                        "description": "State string from the previous call (omit or empty on first sync)"
+                    },
+                    "pages_json": {
+                        "type": "string",
+                        "description": "JSON array of Notion page objects, each optionally with a 'content' string of body text"
+                    },
+                    "full_scan": {
+                        "type": "boolean",
+                        "description": "The batch is every page, so tracked pages missing from it are deleted (default false)"
+                    }
+                },
+                "required": ["pages_json"]
+            }),
+        },
+        super::ToolDefinition {
+            name: "notion_validate_write".to_string(),
+            description: "Check a page create/update payload against the database schema before sending it: unknown properties (with the closest name suggested), read-only properties such as formulas and rollups, values of the wrong type, and select/multi-select/status options that don't exist. Returns {valid, checked, errors: [{property, kind, message}]}.".to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "payload_json": {
+                        "type": "string",
+                        "description": "JSON request body for creating or updating a page, with a 'properties' object keyed by property name or ID"
+                    },
+                    "schema_json": {
+                        "type": "string",
+                        "description": "JSON database object, or its properties object"
+                    }
+                },
+                "required": ["payload_json", "schema_json"]
+            }),
+        },
         super::ToolDefinition {
             name: "notion_suggest_cleanup".to_string(),
             description: "Analyze pages and suggest cleanup actions including duplicate removal and incomplete entry detection.".to_string(),
@@ -82,7 +196,15 @@ pub fn call_tool(name: &str, arguments: Value) -> Result<String, String> {
                 .get("properties_json")
                 .and_then(|v| v.as_str())
                 .ok_or("Missing properties_json argument")?;
-            schema::format_schema_impl(props_json)
+            let config_json = arguments
+                .get("config_json")
+                .and_then(|v| v.as_str())
+                .unwrap_or("");
+            let database_id = arguments
+                .get("database_id")
+                .and_then(|v| v.as_str())
+                .unwrap_or("");
+            schema::format_schema_impl(props_json, config_json, database_id)
         }
         "notion_find_duplicates" => {
             let pages_json = arguments
@@ -93,14 +215,85 @@ pub fn call_tool(name: &str, arguments: Value) -> Result<String, String> {
                 .get("keep_strategy")
                 .and_then(|v| v.as_str())
                 .unwrap_or("oldest");
-            duplicates::find_duplicates_impl(pages_json, keep_strategy)
+            let config_json = arguments
+                .get("config_json")
+                .and_then(|v| v.as_str())
+                .unwrap_or("");
+            duplicates::find_duplicates_impl(pages_json, keep_strategy, config_json)
+        }
+        "notion_find_cross_database_duplicates" => {
+            let databases_json = arguments
+                .get("databases_json")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing databases_json argument")?;
+            let mapping_json = arguments
+                .get("mapping_json")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing mapping_json argument")?;
+            cross_database::find_cross_database_duplicates_impl(databases_json, mapping_json)
         }
         "notion_simplify_pages" => {
             let pages_json = arguments
                 .get("pages_json")
                 .and_then(|v| v.as_str())
                 .ok_or("Missing pages_json argument")?;
-            pages::simplify_pages_impl(pages_json)
+            let config_json = arguments
+                .get("config_json")
+                .and_then(|v| v.as_str())
+                .unwrap_or("");
+            pages::simplify_pages_impl(pages_json, config_json)
+        }
+        "notion_score_pages" => {
+            let pages_json = arguments
+                .get("pages_json")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing pages_json argument")?;
+            let options_json = arguments
+                .get("options_json")
+                .and_then(|v| v.as_str())
+                .unwrap_or("");
+            quality::score_pages_impl(pages_json, options_json)
+        }
+        "notion_evaluate_formula" => {
+            let formula = arguments
+                .get("formula")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing formula argument")?;
+            let pages_json = arguments
+                .get("pages_json")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing pages_json argument")?;
+            let options_json = arguments
+                .get("options_json")
+                .and_then(|v| v.as_str())
+                .unwrap_or("");
+            formula::evaluate_formula_impl(formula, pages_json, options_json)
+        }
+        "notion_sync_diff" => {
+            let pages_json = arguments
+                .get("pages_json")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing pages_json argument")?;
+            let previous_state = arguments
+                .get("previous_state")
+                .and_then(|v| v.as_str())
+                .unwrap_or("");
+            let full_scan = arguments
+                .get("full_scan")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            sync_state::sync_diff_impl(previous_state, pages_json, full_scan)
+        }
+        "notion_validate_write" => {
+            let payload_json = arguments
+                .get("payload_json")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing payload_json argument")?;
+            let schema_json = arguments
+                .get("schema_json")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing schema_json argument")?;
+            write::validate_write_impl(payload_json, schema_json)
         }
         "notion_suggest_cleanup" => {
             let pages_json = arguments
@@ -118,7 +311,7 @@ pub fn call_tool(name: &str, arguments: Value) -> Result<String, String> {
 /// Analyze pages and suggest cleanup actions
 fn suggest_cleanup(pages_json: &str) -> Result<String, String> {
     // First, find duplicates
-    let dup_result = duplicates::find_duplicates_impl(pages_json, "oldest")?;
+    let dup_result = duplicates::find_duplicates_impl(pages_json, "oldest", "")?;
     let dup_data: duplicates::DuplicateResult =
         serde_json::from_str(&dup_result).map_err(|e| e.to_string())?;
 
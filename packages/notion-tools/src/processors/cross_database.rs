@@ -0,0 +1,358 @@
+//! Duplicate detection across Notion databases
+//!
+//! `find_duplicates` compares titles within one database. The same record
+//! often lives in several databases under different property names (a
+//! customer in both CRM and Onboarding), so this takes pages from many
+//! databases plus a mapping of which properties mean the same thing, and
+//! reports records that appear in more than one database.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+
+use super::duplicates::normalize_title;
+use super::pages::{extract_title, property_plain_text, NotionPage};
+
+/// Pages from one database
+#[derive(Debug, Deserialize)]
+pub struct DatabasePages {
+    /// Name used in the mapping (e.g. "CRM")
+    pub name: String,
+    pub pages: Vec<NotionPage>,
+}
+
+/// Which properties represent the same concept in each database
+#[derive(Debug, Deserialize)]
+pub struct ConceptMapping {
+    /// Concept name for reporting (e.g. "email")
+    pub concept: String,
+    /// Database name → property name
+    pub properties: HashMap<String, String>,
+    /// How values are compared: "text" (default) or "digits" (phone numbers, IDs)
+    #[serde(default)]
+    pub normalize: Normalize,
+}
+
+#[derive(Debug, Default, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Normalize {
+    /// Case, whitespace, and Unicode form don't matter
+    #[default]
+    Text,
+    /// Only digits matter
+    Digits,
+}
+
+/// How concepts combine into a match
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MatchMode {
+    /// Every mapped concept must be present and equal
+    #[default]
+    All,
+    /// Any one shared concept value links pages
+    Any,
+}
+
+/// Mapping spec for a cross-database scan
+#[derive(Debug, Deserialize)]
+pub struct MappingSpec {
+    pub concepts: Vec<ConceptMapping>,
+    #[serde(default, rename = "match")]
+    pub match_mode: MatchMode,
+}
+
+/// A page in a cross-database duplicate group
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GroupMember {
+    pub database: String,
+    pub id: String,
+    pub title: String,
+    pub url: String,
+}
+
+/// One record found in more than one database
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CrossDatabaseGroup {
+    /// Concepts whose values matched
+    pub matched_on: Vec<String>,
+    /// Databases the record appears in
+    pub databases: Vec<String>,
+    pub pages: Vec<GroupMember>,
+}
+
+/// Result of a cross-database scan
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CrossDatabaseResult {
+    pub total_pages: usize,
+    pub databases_scanned: usize,
+    /// Pages skipped because a mapped property was empty (match "all")
+    /// or every mapped property was empty (match "any")
+    pub pages_without_keys: usize,
+    pub duplicate_groups: Vec<CrossDatabaseGroup>,
+    pub summary: String,
+}
+
+/// Find records duplicated across databases.
+///
+/// Input: JSON array of `{name, pages}` and a mapping spec
+/// `{concepts: [{concept, properties: {database: property}, normalize?}], match?}`
+/// Output: JSON with groups of pages that represent the same record
+pub fn find_cross_database_duplicates_impl(databases_json: &str, mapping_json: &str) -> Result<String, String> {
+    let databases: Vec<DatabasePages> =
+        serde_json::from_str(databases_json).map_err(|e| format!("JSON parse error: {}", e))?;
+    let spec: MappingSpec =
+        serde_json::from_str(mapping_json).map_err(|e| format!("Mapping parse error: {}", e))?;
+
+    if spec.concepts.is_empty() {
+        return Err("Mapping has no concepts".to_string());
+    }
+    for database in &databases {
+        if !spec.concepts.iter().any(|c| c.properties.contains_key(&database.name)) {
+            return Err(format!("Mapping has no properties for database '{}'", database.name));
+        }
+    }
+
+    let result = scan(&databases, &spec);
+    serde_json::to_string(&result).map_err(|e| format!("JSON serialize error: {}", e))
+}
+
+fn scan(databases: &[DatabasePages], spec: &MappingSpec) -> CrossDatabaseResult {
+    let members: Vec<(GroupMember, Vec<Option<String>>)> = databases
+        .iter()
+        .flat_map(|db| {
+            db.pages.iter().map(move |page| {
+                let keys = spec.concepts.iter()
+                    .map(|c| {
+                        c.properties.get(&db.name)
+                            .and_then(|prop| page.properties.get(prop))
+                            .and_then(property_plain_text)
+                            .map(|v| normalize_value(&v, c.normalize))
+                            .filter(|v| !v.is_empty())
+                    })
+                    .collect();
+                let member = GroupMember {
+                    database: db.name.clone(),
+                    id: page.id.clone(),
+                    title: extract_title(&page.properties).0,
+                    url: page.url.clone(),
+                };
+                (member, keys)
+            })
+        })
+        .collect();
+
+    // Union pages that share a key; "all" uses one composite key per page
+    let mut parent: Vec<usize> = (0..members.len()).collect();
+    let mut matched: Vec<BTreeSet<usize>> = vec![BTreeSet::new(); members.len()];
+    let mut first_with_key: HashMap<(usize, String), usize> = HashMap::new();
+    let mut pages_without_keys = 0;
+
+    for (i, (_, keys)) in members.iter().enumerate() {
+        let page_keys: Vec<(usize, String)> = match spec.match_mode {
+            MatchMode::All if keys.iter().all(|k| k.is_some()) => {
+                let composite = keys.iter().flatten().cloned().collect::<Vec<_>>().join("\u{1f}");
+                vec![(usize::MAX, composite)]
+            }
+            MatchMode::All => Vec::new(),
+            MatchMode::Any => keys.iter()
+                .enumerate()
+                .filter_map(|(c, k)| k.clone().map(|k| (c, k)))
+                .collect(),
+        };
+        if page_keys.is_empty() {
+            pages_without_keys += 1;
+        }
+
+        for key in page_keys {
+            let concept = key.0;
+            match first_with_key.get(&key) {
+                Some(&j) => {
+                    union(&mut parent, i, j);
+                    matched[i].insert(concept);
+                    matched[j].insert(concept);
+                }
+                None => {
+                    first_with_key.insert(key, i);
+                }
+            }
+        }
+    }
+
+    let mut groups: HashMap<usize, Vec<usize>> = HashMap::new();
+    for i in 0..members.len() {
+        let root = find(&mut parent, i);
+        groups.entry(root).or_default().push(i);
+    }
+
+    let mut members: Vec<Option<GroupMember>> = members.into_iter().map(|(m, _)| Some(m)).collect();
+    let mut duplicate_groups: Vec<CrossDatabaseGroup> = groups
+        .into_values()
+        .filter_map(|indices| {
+            let databases: BTreeSet<String> = indices.iter()
+                .filter_map(|&i| members[i].as_ref().map(|m| m.database.clone()))
+                .collect();
+            if databases.len() < 2 {
+                return None;
+            }
+
+            let concepts: BTreeSet<usize> = indices.iter().flat_map(|&i| matched[i].iter().copied()).collect();
+            let matched_on = if concepts.contains(&usize::MAX) {
+                spec.concepts.iter().map(|c| c.concept.clone()).collect()
+            } else {
+                concepts.iter().map(|&c| spec.concepts[c].concept.clone()).collect()
+            };
+
+            let mut pages: Vec<GroupMember> = indices.iter().filter_map(|&i| members[i].take()).collect();
+            pages.sort_by(|a, b| (&a.database, &a.id).cmp(&(&b.database, &b.id)));
+            Some(CrossDatabaseGroup {
+                matched_on,
+                databases: databases.into_iter().collect(),
+                pages,
+            })
+        })
+        .collect();
+    duplicate_groups.sort_by(|a, b| a.pages[0].id.cmp(&b.pages[0].id));
+
+    let total_pages = members.len();
+    let summary = format!(
+        "Scanned {} pages across {} databases. Found {} records in more than one database.",
+        total_pages,
+        databases.len(),
+        duplicate_groups.len()
+    );
+
+    CrossDatabaseResult {
+        total_pages,
+        databases_scanned: databases.len(),
+        pages_without_keys,
+        duplicate_groups,
+        summary,
+    }
+}
+
+fn normalize_value(value: &str, normalize: Normalize) -> String {
+    match normalize {
+        Normalize::Text => normalize_title(value),
+        Normalize::Digits => value.chars().filter(|c| c.is_ascii_digit()).collect(),
+    }
+}
+
+fn find(parent: &mut [usize], i: usize) -> usize {
+    let mut root = i;
+    while parent[root] != root {
+        root = parent[root];
+    }
+    // Path compression
+    let mut node = i;
+    while parent[node] != root {
+        let next = parent[node];
+        parent[node] = root;
+        node = next;
+    }
+    root
+}
+
+fn union(parent: &mut [usize], a: usize, b: usize) {
+    let (ra, rb) = (find(parent, a), find(parent, b));
+    if ra != rb {
+        parent[ra] = rb;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(id: &str, props: serde_json::Value) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "created_time": "2024-01-01T00:00:00.000Z",
+            "last_edited_time": "2024-01-01T00:00:00.000Z",
+            "properties": props
+        })
+    }
+
+    fn databases() -> String {
+        serde_json::json!([
+            {
+                "name": "CRM",
+                "pages": [
+                    page("crm-1", serde_json::json!({
+                        "Company": {"type": "title", "title": [{"plain_text": "Acme Corp"}]},
+                        "Email": {"type": "email", "email": "Ops@Acme.com"},
+                        "Phone": {"type": "phone_number", "phone_number": "+1 (555) 010-2000"}
+                    })),
+                    page("crm-2", serde_json::json!({
+                        "Company": {"type": "title", "title": [{"plain_text": "Globex"}]},
+                        "Email": {"type": "email", "email": "hi@globex.com"},
+                        "Phone": {"type": "phone_number", "phone_number": null}
+                    }))
+                ]
+            },
+            {
+                "name": "Onboarding",
+                "pages": [
+                    page("onb-1", serde_json::json!({
+                        "Client": {"type": "title", "title": [{"plain_text": "ACME corp "}]},
+                        "Contact Email": {"type": "rich_text", "rich_text": [{"plain_text": "ops@acme.com"}]},
+                        "Phone": {"type": "rich_text", "rich_text": [{"plain_text": "15550102000"}]}
+                    })),
+                    page("onb-2", serde_json::json!({
+                        "Client": {"type": "title", "title": [{"plain_text": "Initech"}]},
+                        "Contact Email": {"type": "rich_text", "rich_text": [{"plain_text": "hi@globex.com"}]},
+                        "Phone": {"type": "rich_text", "rich_text": []}
+                    }))
+                ]
+            }
+        ])
+        .to_string()
+    }
+
+    #[test]
+    fn test_all_concepts_must_match() {
+        let mapping = r#"{
+            "concepts": [
+                {"concept": "name", "properties": {"CRM": "Company", "Onboarding": "Client"}},
+                {"concept": "email", "properties": {"CRM": "Email", "Onboarding": "Contact Email"}}
+            ]
+        }"#;
+
+        let result = find_cross_database_duplicates_impl(&databases(), mapping).unwrap();
+        let parsed: CrossDatabaseResult = serde_json::from_str(&result).unwrap();
+
+        assert_eq!(parsed.total_pages, 4);
+        assert_eq!(parsed.duplicate_groups.len(), 1);
+        let group = &parsed.duplicate_groups[0];
+        assert_eq!(group.databases, vec!["CRM", "Onboarding"]);
+        assert_eq!(group.matched_on, vec!["name", "email"]);
+        assert_eq!(group.pages[0].id, "crm-1");
+        assert_eq!(group.pages[1].title, "ACME corp ");
+    }
+
+    #[test]
+    fn test_any_concept_links_pages() {
+        let mapping = r#"{
+            "match": "any",
+            "concepts": [
+                {"concept": "email", "properties": {"CRM": "Email", "Onboarding": "Contact Email"}},
+                {"concept": "phone", "properties": {"CRM": "Phone", "Onboarding": "Phone"}, "normalize": "digits"}
+            ]
+        }"#;
+
+        let result = find_cross_database_duplicates_impl(&databases(), mapping).unwrap();
+        let parsed: CrossDatabaseResult = serde_json::from_str(&result).unwrap();
+
+        // Acme matches on both; Globex/Initech share only an email
+        assert_eq!(parsed.duplicate_groups.len(), 2);
+        assert_eq!(parsed.duplicate_groups[0].matched_on, vec!["email", "phone"]);
+        assert_eq!(parsed.duplicate_groups[1].matched_on, vec!["email"]);
+        assert_eq!(parsed.pages_without_keys, 0);
+    }
+
+    #[test]
+    fn test_unmapped_database_is_an_error() {
+        let mapping = r#"{"concepts": [{"concept": "name", "properties": {"CRM": "Company"}}]}"#;
+        let err = find_cross_database_duplicates_impl(&databases(), mapping).unwrap_err();
+        assert!(err.contains("Onboarding"));
+    }
+}
@@ -1,15 +1,25 @@
 //! Duplicate detection for Notion pages
 
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::collections::HashMap;
 use unicode_normalization::UnicodeNormalization;
 
-/// Input page for duplicate detection
+use super::pages::extract_title;
+use super::profiles::{normalize_id, parent_database_id, ProcessorConfig};
+
+/// Input page for duplicate detection: `{id, title, created_time}`, or a
+/// Notion page object (title taken from its properties)
 #[derive(Debug, Deserialize, Clone)]
 pub struct PageForDuplicates {
     pub id: String,
+    #[serde(default)]
     pub title: String,
     pub created_time: String,
+    #[serde(default)]
+    pub properties: Value,
+    #[serde(default)]
+    pub parent: Value,
 }
 
 /// Result of duplicate detection
@@ -23,27 +33,50 @@ pub struct DuplicateResult {
 
 /// Find duplicate pages by title.
 ///
-/// Input: JSON array of pages with id, title, created_time
+/// Input: JSON array of pages with id, title, created_time, and a
+/// `ProcessorConfig` JSON ("" for none). A page's database profile can
+/// name its title property and properties that must also match
+/// (`match_on`); pages only duplicate others in the same database.
 /// Output: JSON with pages to archive based on keep_strategy
-pub fn find_duplicates_impl(pages_json: &str, keep_strategy: &str) -> Result<String, String> {
+pub fn find_duplicates_impl(pages_json: &str, keep_strategy: &str, config_json: &str) -> Result<String, String> {
     let pages: Vec<PageForDuplicates> =
         serde_json::from_str(pages_json).map_err(|e| format!("JSON parse error: {}", e))?;
+    let config = ProcessorConfig::parse(config_json)?;
 
     let total_pages = pages.len();
 
-    // Normalize and group by title
-    let mut groups: HashMap<String, Vec<PageForDuplicates>> = HashMap::new();
+    // Normalize and group by database, title, and any match_on values
+    let mut groups: HashMap<(Option<String>, String, Vec<String>), Vec<PageForDuplicates>> = HashMap::new();
 
     for page in pages {
-        let normalized = normalize_title(&page.title);
-        groups.entry(normalized).or_default().push(page);
+        let database = parent_database_id(&page.parent);
+        let profile = config.profile_for(database);
+        let title = match profile {
+            _ if !page.properties.is_object() => page.title.clone(),
+            Some(profile) => profile.title(&page.properties).0,
+            None if page.title.is_empty() => extract_title(&page.properties).0,
+            None => page.title.clone(),
+        };
+        let matched: Vec<String> = profile
+            .map(|p| {
+                p.match_on
+                    .iter()
+                    .map(|name| {
+                        let value = page.properties.get(name).and_then(|property| p.value(property));
+                        value.map(|v| normalize_title(&v)).unwrap_or_default()
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let key = (database.map(normalize_id), normalize_title(&title), matched);
+        groups.entry(key).or_default().push(page);
     }
 
     // Find groups with duplicates and determine which to archive
     let mut pages_to_archive: Vec<String> = Vec::new();
     let mut duplicate_groups = 0;
 
-    for (_title, mut group) in groups {
+    for (_key, mut group) in groups {
         if group.len() > 1 {
             duplicate_groups += 1;
 
@@ -93,7 +126,7 @@ pub fn find_duplicates_impl(pages_json: &str, keep_strategy: &str) -> Result<Str
 /// - Trim whitespace
 /// - Unicode NFC normalization
 /// - Collapse multiple spaces
-fn normalize_title(title: &str) -> String {
+pub(crate) fn normalize_title(title: &str) -> String {
     let normalized: String = title
         .nfc() // Unicode normalization
         .collect::<String>()
@@ -118,7 +151,7 @@ mod tests {
             {"id": "page-3", "title": "Task B", "created_time": "2024-01-03T00:00:00Z"}
         ]"#;
 
-        let result = find_duplicates_impl(input, "oldest").unwrap();
+        let result = find_duplicates_impl(input, "oldest", "").unwrap();
         let parsed: DuplicateResult = serde_json::from_str(&result).unwrap();
 
         assert_eq!(parsed.total_pages, 3);
@@ -134,7 +167,7 @@ mod tests {
             {"id": "page-2", "title": "Task A", "created_time": "2024-01-02T00:00:00Z"}
         ]"#;
 
-        let result = find_duplicates_impl(input, "newest").unwrap();
+        let result = find_duplicates_impl(input, "newest", "").unwrap();
         let parsed: DuplicateResult = serde_json::from_str(&result).unwrap();
 
         assert_eq!(parsed.pages_to_archive.len(), 1);
@@ -163,10 +196,44 @@ mod tests {
             {"id": "page-2", "title": "Task B", "created_time": "2024-01-02T00:00:00Z"}
         ]"#;
 
-        let result = find_duplicates_impl(input, "oldest").unwrap();
+        let result = find_duplicates_impl(input, "oldest", "").unwrap();
         let parsed: DuplicateResult = serde_json::from_str(&result).unwrap();
 
         assert_eq!(parsed.duplicate_groups, 0);
         assert!(parsed.pages_to_archive.is_empty());
     }
+    
+    #[test]
+    fn test_find_duplicates_with_profile() {
+        // Notion pages from a CRM whose "Name" is an import ID; the company is what repeats
+        let page = |id: &str, created: &str, company: &str, domain: &str| {
+            serde_json::json!({
+                "id": id,
+                "created_time": created,
+                "parent": { "type": "database_id", "database_id": "crm-db" },
+                "properties": {
+                    "Name": { "type": "title", "title": [{ "plain_text": format!("row-{}", id) }] },
+                    "Company": { "type": "rich_text", "rich_text": [{ "plain_text": company }] },
+                    "Domain": { "type": "url", "url": domain }
+                }
+            })
+        };
+        let input = serde_json::json!([
+            page("page-1", "2024-01-01T00:00:00Z", "Acme", "acme.com"),
+            page("page-2", "2024-01-02T00:00:00Z", "ACME ", "acme.com"),
+            page("page-3", "2024-01-03T00:00:00Z", "Acme", "acme.co.uk"),
+        ])
+        .to_string();
+        let config = r#"{"databases": {"crm-db": {"title_property": "Company", "match_on": ["Domain"]}}}"#;
+        
+        let parsed: DuplicateResult =
+            serde_json::from_str(&find_duplicates_impl(&input, "oldest", config).unwrap()).unwrap();
+        assert_eq!(parsed.duplicate_groups, 1);
+        assert_eq!(parsed.pages_to_archive, vec!["page-2"]);
+        
+        // Without the profile every import ID is distinct
+        let parsed: DuplicateResult =
+            serde_json::from_str(&find_duplicates_impl(&input, "oldest", "").unwrap()).unwrap();
+        assert_eq!(parsed.duplicate_groups, 0);
+    }
 }
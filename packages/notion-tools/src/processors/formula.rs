@@ -0,0 +1,1003 @@
+//! Notion formula evaluation
+//!
+//! Re-evaluates a formula over page properties so agents can ask "what if"
+//! (what would `Days left` be if the due date moved?) without writing to
+//! Notion and reading the formula property back.
+//!
+//! Supports a useful subset of the formula language:
+//!
+//! - **Values**: numbers, text, booleans, dates, and empty
+//! - **Operators**: `+ - * / % ^`, comparisons, `and`/`or`/`not` (or
+//!   `&& || !`), and `cond ? a : b`
+//! - **Functions**: `prop`, logic (`if`, `ifs`, `empty`, ...), text
+//!   (`length`, `contains`, `replaceAll`, `format`, ...), numbers (`round`,
+//!   `min`, `max`, ...), and dates (`now`, `dateAdd`, `dateBetween`,
+//!   `formatDate`, ...); see `call` for the full list
+//! - **Method calls**: `prop("Name").length()` is `length(prop("Name"))`
+//!
+//! Lists, regular expressions (`replace` and `test` match literally), and
+//! `let` aren't supported.
+
+use chrono::{DateTime, Datelike, Duration, Months, NaiveDate, NaiveDateTime, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+use super::pages::{extract_title, property_plain_text};
+
+/// Longest text `repeat` builds, in bytes
+const MAX_REPEAT_LEN: usize = 1024 * 1024;
+
+/// Deepest a formula may nest (parentheses, operators, calls), so parsing
+/// and evaluating it can't overflow the stack
+const MAX_DEPTH: usize = 256;
+
+/// A formula value
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormulaValue {
+    Empty,
+    Number(f64),
+    Text(String),
+    Bool(bool),
+    Date(DateTime<Utc>),
+}
+
+impl FormulaValue {
+    fn type_name(&self) -> &'static str {
+        match self {
+            FormulaValue::Empty => "empty",
+            FormulaValue::Number(_) => "number",
+            FormulaValue::Text(_) => "text",
+            FormulaValue::Bool(_) => "boolean",
+            FormulaValue::Date(_) => "date",
+        }
+    }
+
+    /// JSON form: dates as RFC 3339, empty as null
+    fn to_json(&self) -> Value {
+        match self {
+            FormulaValue::Empty => Value::Null,
+            FormulaValue::Number(n) => serde_json::Number::from_f64(*n).map(Value::Number).unwrap_or(Value::Null),
+            FormulaValue::Text(s) => Value::String(s.clone()),
+            FormulaValue::Bool(b) => Value::Bool(*b),
+            FormulaValue::Date(d) => Value::String(d.to_rfc3339()),
+        }
+    }
+
+    /// Text as Notion would display it
+    fn display(&self) -> String {
+        match self {
+            FormulaValue::Empty => String::new(),
+            FormulaValue::Number(n) if n.fract() == 0.0 && n.abs() < 1e15 => format!("{}", *n as i64),
+            FormulaValue::Number(n) => n.to_string(),
+            FormulaValue::Text(s) => s.clone(),
+            FormulaValue::Bool(b) => b.to_string(),
+            FormulaValue::Date(d) if d.time() == chrono::NaiveTime::MIN => format_date(d, "MMMM D, YYYY"),
+            FormulaValue::Date(d) => format_date(d, "MMMM D, YYYY h:mm A"),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        match self {
+            FormulaValue::Empty => true,
+            FormulaValue::Text(s) => s.is_empty(),
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Text(String),
+    Ident(String),
+    Symbol(&'static str),
+}
+
+/// Parsed formula
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Literal(FormulaValue),
+    Call(String, Vec<Expr>),
+    Negate(Box<Expr>),
+    Not(Box<Expr>),
+    Binary(&'static str, Box<Expr>, Box<Expr>),
+    Conditional(Box<Expr>, Box<Expr>, Box<Expr>),
+}
+
+const SYMBOLS: [&str; 21] = [
+    "==", "!=", ">=", "<=", "&&", "||", "+", "-", "*", "/", "%", "^", ">", "<", "!", "(", ")", ",", ".", "?", ":",
+];
+
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let chars: Vec<char> = source.chars().collect();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_ascii_digit() || (c == '.' && chars.get(i + 1).is_some_and(|d| d.is_ascii_digit())) {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            tokens.push(Token::Number(text.parse().map_err(|_| format!("Invalid number '{}'", text))?));
+        } else if c == '"' {
+            let mut text = String::new();
+            i += 1;
+            loop {
+                match chars.get(i) {
+                    None => return Err("Unterminated string".to_string()),
+                    Some('"') => break,
+                    Some('\\') => {
+                        match chars.get(i + 1) {
+                            Some('n') => text.push('\n'),
+                            Some('t') => text.push('\t'),
+                            Some(&other) => text.push(other),
+                            None => return Err("Unterminated string".to_string()),
+                        }
+                        i += 1;
+                    }
+                    Some(&other) => text.push(other),
+                }
+                i += 1;
+            }
+            i += 1;
+            tokens.push(Token::Text(text));
+        } else if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push(Token::Ident(chars[start..i].iter().collect()));
+        } else {
+            let rest: String = chars[i..chars.len().min(i + 2)].iter().collect();
+            let symbol = SYMBOLS
+                .iter()
+                .find(|s| rest.starts_with(*s))
+                .ok_or_else(|| format!("Unexpected character '{}'", c))?;
+            i += symbol.len();
+            tokens.push(Token::Symbol(symbol));
+        }
+    }
+    Ok(tokens)
+}
+
+/// Binary operator, its precedence, and whether it's right-associative
+fn binary_operator(token: Option<&Token>) -> Option<(&'static str, u8, bool)> {
+    let op = match token? {
+        Token::Symbol(s) => *s,
+        Token::Ident(name) if name == "or" => "||",
+        Token::Ident(name) if name == "and" => "&&",
+        _ => return None,
+    };
+    let (precedence, right) = match op {
+        "||" => (1, false),
+        "&&" => (2, false),
+        "==" | "!=" => (3, false),
+        ">" | ">=" | "<" | "<=" => (4, false),
+        "+" | "-" => (5, false),
+        "*" | "/" | "%" => (6, false),
+        "^" => (7, true),
+        _ => return None,
+    };
+    Some((op, precedence, right))
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    /// Nesting of the expression being parsed; operator and method chains
+    /// count each link, since they nest in the tree too
+    depth: usize,
+}
+
+impl Parser {
+    fn descend(&mut self) -> Result<(), String> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err("Formula nested too deeply".to_string());
+        }
+        Ok(())
+    }
+    
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn eat(&mut self, symbol: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Symbol(s)) if *s == symbol) {
+            self.pos += 1;
+            return true;
+        }
+        false
+    }
+
+    fn expect(&mut self, symbol: &str) -> Result<(), String> {
+        if self.eat(symbol) {
+            Ok(())
+        } else {
+            Err(format!("Expected '{}'", symbol))
+        }
+    }
+
+    fn expression(&mut self) -> Result<Expr, String> {
+        let depth = self.depth;
+        self.descend()?;
+        let mut expr = self.binary(1)?;
+        if self.eat("?") {
+            let then = self.expression()?;
+            self.expect(":")?;
+            let otherwise = self.expression()?;
+            expr = Expr::Conditional(Box::new(expr), Box::new(then), Box::new(otherwise));
+        }
+        self.depth = depth;
+        Ok(expr)
+    }
+
+    fn binary(&mut self, min_precedence: u8) -> Result<Expr, String> {
+        let depth = self.depth;
+        let mut left = self.unary()?;
+        while let Some((op, precedence, right_assoc)) = binary_operator(self.peek()) {
+            if precedence < min_precedence {
+                break;
+            }
+            self.pos += 1;
+            self.descend()?;
+            let right = self.binary(if right_assoc { precedence } else { precedence + 1 })?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+        }
+        self.depth = depth;
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        let depth = self.depth;
+        // Binds looser than ^, so -2^2 is -(2^2)
+        if self.eat("-") {
+            self.descend()?;
+            let expr = Expr::Negate(Box::new(self.binary(7)?));
+            self.depth = depth;
+            return Ok(expr);
+        }
+        // `not x` as an operator; `not(x)` is the function
+        let not_keyword = matches!(self.peek(), Some(Token::Ident(name)) if name == "not")
+            && self.tokens.get(self.pos + 1) != Some(&Token::Symbol("("));
+        if not_keyword {
+            self.pos += 1;
+        }
+        if not_keyword || self.eat("!") {
+            self.descend()?;
+            let expr = Expr::Not(Box::new(self.binary(3)?));
+            self.depth = depth;
+            return Ok(expr);
+        }
+        self.postfix()
+    }
+
+    fn postfix(&mut self) -> Result<Expr, String> {
+        let depth = self.depth;
+        let mut expr = self.primary()?;
+        while self.eat(".") {
+            self.descend()?;
+            let Some(Token::Ident(name)) = self.next() else {
+                return Err("Expected a function name after '.'".to_string());
+            };
+            self.expect("(")?;
+            let mut args = vec![expr];
+            args.extend(self.arguments()?);
+            expr = Expr::Call(name, args);
+        }
+        self.depth = depth;
+        Ok(expr)
+    }
+
+    /// Comma-separated arguments up to the closing parenthesis
+    fn arguments(&mut self) -> Result<Vec<Expr>, String> {
+        let mut args = Vec::new();
+        if self.eat(")") {
+            return Ok(args);
+        }
+        loop {
+            args.push(self.expression()?);
+            if self.eat(")") {
+                return Ok(args);
+            }
+            self.expect(",")?;
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expr, String> {
+        match self.next() {
+            Some(Token::Number(n)) => Ok(Expr::Literal(FormulaValue::Number(n))),
+            Some(Token::Text(s)) => Ok(Expr::Literal(FormulaValue::Text(s))),
+            Some(Token::Ident(name)) if name == "true" => Ok(Expr::Literal(FormulaValue::Bool(true))),
+            Some(Token::Ident(name)) if name == "false" => Ok(Expr::Literal(FormulaValue::Bool(false))),
+            Some(Token::Ident(name)) => {
+                if !self.eat("(") {
+                    return Err(format!("Unknown name '{}' (properties are read with prop(\"{}\"))", name, name));
+                }
+                Ok(Expr::Call(name, self.arguments()?))
+            }
+            Some(Token::Symbol("(")) => {
+                let expr = self.expression()?;
+                self.expect(")")?;
+                Ok(expr)
+            }
+            Some(Token::Symbol(s)) => Err(format!("Unexpected '{}'", s)),
+            None => Err("Unexpected end of formula".to_string()),
+        }
+    }
+}
+
+/// Parse a formula
+pub fn parse_formula(source: &str) -> Result<Expr, String> {
+    let mut parser = Parser { tokens: tokenize(source)?, pos: 0, depth: 0 };
+    let expr = parser.expression()?;
+    if parser.pos < parser.tokens.len() {
+        return Err(format!("Unexpected {:?} after end of formula", parser.tokens[parser.pos]));
+    }
+    Ok(expr)
+}
+
+/// What a formula can see: one page's properties and the current time
+pub struct Context<'a> {
+    pub properties: &'a HashMap<String, FormulaValue>,
+    pub now: DateTime<Utc>,
+}
+
+/// Evaluate a parsed formula
+pub fn evaluate(expr: &Expr, ctx: &Context) -> Result<FormulaValue, String> {
+    use FormulaValue::*;
+    match expr {
+        Expr::Literal(value) => Ok(value.clone()),
+        Expr::Call(name, args) => call(name, args, ctx),
+        Expr::Negate(inner) => Ok(Number(-number(&evaluate(inner, ctx)?)?)),
+        Expr::Not(inner) => Ok(Bool(!truthy(&evaluate(inner, ctx)?))),
+        Expr::Conditional(condition, then, otherwise) => {
+            if truthy(&evaluate(condition, ctx)?) {
+                evaluate(then, ctx)
+            } else {
+                evaluate(otherwise, ctx)
+            }
+        }
+        Expr::Binary("&&", left, right) => Ok(Bool(truthy(&evaluate(left, ctx)?) && truthy(&evaluate(right, ctx)?))),
+        Expr::Binary("||", left, right) => Ok(Bool(truthy(&evaluate(left, ctx)?) || truthy(&evaluate(right, ctx)?))),
+        Expr::Binary(op, left, right) => {
+            let (left, right) = (evaluate(left, ctx)?, evaluate(right, ctx)?);
+            match *op {
+                "==" => Ok(Bool(equal(&left, &right))),
+                "!=" => Ok(Bool(!equal(&left, &right))),
+                ">" | ">=" | "<" | "<=" => {
+                    let ordering = compare(&left, &right)?;
+                    Ok(Bool(match *op {
+                        ">" => ordering.is_gt(),
+                        ">=" => ordering.is_ge(),
+                        "<" => ordering.is_lt(),
+                        _ => ordering.is_le(),
+                    }))
+                }
+                "+" if matches!(left, Text(_)) || matches!(right, Text(_)) => {
+                    Ok(Text(format!("{}{}", left.display(), right.display())))
+                }
+                _ => {
+                    let (a, b) = (number(&left)?, number(&right)?);
+                    match *op {
+                        "+" => Ok(Number(a + b)),
+                        "-" => Ok(Number(a - b)),
+                        "*" => Ok(Number(a * b)),
+                        "/" if b == 0.0 => Err("Division by zero".to_string()),
+                        "/" => Ok(Number(a / b)),
+                        "%" if b == 0.0 => Err("Division by zero".to_string()),
+                        "%" => Ok(Number(a % b)),
+                        _ => Ok(Number(a.powf(b))),
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Numbers as numbers; empty is 0 and booleans are 0/1
+fn number(value: &FormulaValue) -> Result<f64, String> {
+    match value {
+        FormulaValue::Number(n) => Ok(*n),
+        FormulaValue::Empty => Ok(0.0),
+        FormulaValue::Bool(b) => Ok(if *b { 1.0 } else { 0.0 }),
+        other => Err(format!("Expected a number, got {}", other.type_name())),
+    }
+}
+
+fn date(value: &FormulaValue) -> Result<DateTime<Utc>, String> {
+    match value {
+        FormulaValue::Date(d) => Ok(*d),
+        other => Err(format!("Expected a date, got {}", other.type_name())),
+    }
+}
+
+fn text(value: &FormulaValue) -> String {
+    value.display()
+}
+
+/// Conditions: false, empty, 0, and "" are false
+fn truthy(value: &FormulaValue) -> bool {
+    match value {
+        FormulaValue::Empty => false,
+        FormulaValue::Bool(b) => *b,
+        FormulaValue::Number(n) => *n != 0.0,
+        FormulaValue::Text(s) => !s.is_empty(),
+        FormulaValue::Date(_) => true,
+    }
+}
+
+fn equal(left: &FormulaValue, right: &FormulaValue) -> bool {
+    match (left, right) {
+        (FormulaValue::Number(a), FormulaValue::Number(b)) => (a - b).abs() < 1e-9,
+        (a, b) if a.is_empty() || b.is_empty() => a.is_empty() && b.is_empty(),
+        (a, b) => a == b,
+    }
+}
+
+fn compare(left: &FormulaValue, right: &FormulaValue) -> Result<std::cmp::Ordering, String> {
+    match (left, right) {
+        (FormulaValue::Text(a), FormulaValue::Text(b)) => Ok(a.cmp(b)),
+        (FormulaValue::Date(a), FormulaValue::Date(b)) => Ok(a.cmp(b)),
+        (a, b) => Ok(number(a)?.total_cmp(&number(b)?)),
+    }
+}
+
+fn arity(name: &str, args: &[Expr], min: usize, max: usize) -> Result<(), String> {
+    if args.len() < min || args.len() > max {
+        let expected = if min == max { min.to_string() } else if max == usize::MAX { format!("at least {}", min) } else { format!("{}-{}", min, max) };
+        return Err(format!("{}() takes {} arguments, got {}", name, expected, args.len()));
+    }
+    Ok(())
+}
+
+/// Call a function
+fn call(name: &str, args: &[Expr], ctx: &Context) -> Result<FormulaValue, String> {
+    use FormulaValue::*;
+
+    // Lazily evaluated
+    match name {
+        "if" => {
+            arity(name, args, 3, 3)?;
+            let branch = if truthy(&evaluate(&args[0], ctx)?) { &args[1] } else { &args[2] };
+            return evaluate(branch, ctx);
+        }
+        "ifs" => {
+            arity(name, args, 3, usize::MAX)?;
+            for pair in args.chunks(2) {
+                match pair {
+                    [condition, value] if truthy(&evaluate(condition, ctx)?) => return evaluate(value, ctx),
+                    [otherwise] => return evaluate(otherwise, ctx),
+                    _ => {}
+                }
+            }
+            return Ok(Empty);
+        }
+        "and" | "or" => {
+            arity(name, args, 2, usize::MAX)?;
+            for arg in args {
+                let value = truthy(&evaluate(arg, ctx)?);
+                if value == (name == "or") {
+                    return Ok(Bool(value));
+                }
+            }
+            return Ok(Bool(name == "and"));
+        }
+        _ => {}
+    }
+
+    let values = args.iter().map(|a| evaluate(a, ctx)).collect::<Result<Vec<_>, _>>()?;
+    let arg = |i: usize| values.get(i).cloned().unwrap_or(Empty);
+    let (min, max) = match name {
+        "now" | "today" => (0, 0),
+        "prop" | "empty" | "not" | "length" | "lower" | "upper" | "trim" | "format" | "toNumber" | "abs" | "ceil"
+        | "floor" | "sqrt" | "sign" | "parseDate" | "year" | "month" | "date" | "day" | "hour" | "minute"
+        | "timestamp" => (1, 1),
+        "contains" | "startsWith" | "endsWith" | "test" | "repeat" | "pow" | "mod" => (2, 2),
+        "round" | "formatDate" => (1, 2),
+        "substring" | "replace" | "replaceAll" | "dateAdd" | "dateSubtract" | "dateBetween" => (2, 3),
+        "concat" | "min" | "max" | "sum" => (1, usize::MAX),
+        _ => return Err(format!("Unknown function '{}'", name)),
+    };
+    arity(name, args, min, max)?;
+
+    match name {
+        "prop" => {
+            let property = text(&arg(0));
+            ctx.properties.get(&property).cloned().ok_or_else(|| format!("Unknown property '{}'", property))
+        }
+
+        // Logic
+        "empty" => Ok(Bool(arg(0).is_empty() || arg(0) == Number(0.0) || arg(0) == Bool(false))),
+        "not" => Ok(Bool(!truthy(&arg(0)))),
+
+        // Text
+        "length" => Ok(Number(text(&arg(0)).chars().count() as f64)),
+        "lower" => Ok(Text(text(&arg(0)).to_lowercase())),
+        "upper" => Ok(Text(text(&arg(0)).to_uppercase())),
+        "trim" => Ok(Text(text(&arg(0)).trim().to_string())),
+        "format" => Ok(Text(text(&arg(0)))),
+        "concat" => Ok(Text(values.iter().map(text).collect())),
+        "contains" | "test" => Ok(Bool(text(&arg(0)).contains(&text(&arg(1))))),
+        "startsWith" => Ok(Bool(text(&arg(0)).starts_with(&text(&arg(1))))),
+        "endsWith" => Ok(Bool(text(&arg(0)).ends_with(&text(&arg(1))))),
+        "repeat" => {
+            let text = text(&arg(0));
+            let count = number(&arg(1))?.max(0.0) as usize;
+            if text.len().saturating_mul(count) > MAX_REPEAT_LEN {
+                return Err(format!("repeat would make more than {} bytes of text", MAX_REPEAT_LEN));
+            }
+            Ok(Text(text.repeat(count)))
+        }
+        "replace" => Ok(Text(text(&arg(0)).replacen(&text(&arg(1)), &text(&arg(2)), 1))),
+        "replaceAll" => Ok(Text(text(&arg(0)).replace(&text(&arg(1)), &text(&arg(2))))),
+        "substring" => {
+            let chars: Vec<char> = text(&arg(0)).chars().collect();
+            let clamp = |n: f64| (n.max(0.0) as usize).min(chars.len());
+            let start = clamp(number(&arg(1))?);
+            let end = if values.len() > 2 { clamp(number(&arg(2))?) } else { chars.len() };
+            Ok(Text(chars[start..end.max(start)].iter().collect()))
+        }
+        "toNumber" => match arg(0) {
+            Text(s) => Ok(s.trim().parse().map(Number).unwrap_or(Empty)),
+            Date(d) => Ok(Number(d.timestamp_millis() as f64)),
+            other => number(&other).map(Number),
+        },
+
+        // Numbers
+        "abs" => Ok(Number(number(&arg(0))?.abs())),
+        "ceil" => Ok(Number(number(&arg(0))?.ceil())),
+        "floor" => Ok(Number(number(&arg(0))?.floor())),
+        "sqrt" => Ok(Number(number(&arg(0))?.sqrt())),
+        "sign" => Ok(Number(match number(&arg(0))? {
+            n if n > 0.0 => 1.0,
+            n if n < 0.0 => -1.0,
+            _ => 0.0,
+        })),
+        "round" => {
+            let factor = 10f64.powi(if values.len() > 1 { number(&arg(1))? as i32 } else { 0 });
+            Ok(Number((number(&arg(0))? * factor).round() / factor))
+        }
+        "pow" => Ok(Number(number(&arg(0))?.powf(number(&arg(1))?))),
+        "mod" => {
+            let divisor = number(&arg(1))?;
+            if divisor == 0.0 {
+                return Err("Division by zero".to_string());
+            }
+            Ok(Number(number(&arg(0))? % divisor))
+        }
+        "min" | "max" | "sum" => {
+            let numbers = values.iter().map(number).collect::<Result<Vec<_>, _>>()?;
+            Ok(Number(match name {
+                "min" => numbers.iter().copied().fold(f64::INFINITY, f64::min),
+                "max" => numbers.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+                _ => numbers.iter().sum(),
+            }))
+        }
+
+        // Dates
+        "now" => Ok(Date(ctx.now)),
+        "today" => Ok(Date(ctx.now.date_naive().and_time(chrono::NaiveTime::MIN).and_utc())),
+        "parseDate" => Ok(parse_date_text(&text(&arg(0))).map(Date).unwrap_or(Empty)),
+        "year" => Ok(Number(date(&arg(0))?.year() as f64)),
+        "month" => Ok(Number(date(&arg(0))?.month() as f64)),
+        "date" => Ok(Number(date(&arg(0))?.day() as f64)),
+        // 1 = Monday ... 7 = Sunday
+        "day" => Ok(Number(date(&arg(0))?.weekday().number_from_monday() as f64)),
+        "hour" => Ok(Number(date(&arg(0))?.hour() as f64)),
+        "minute" => Ok(Number(date(&arg(0))?.minute() as f64)),
+        "timestamp" => Ok(Number(date(&arg(0))?.timestamp_millis() as f64)),
+        "formatDate" => {
+            let pattern = if values.len() > 1 { text(&arg(1)) } else { "MMMM D, YYYY".to_string() };
+            Ok(Text(format_date(&date(&arg(0))?, &pattern)))
+        }
+        "dateAdd" | "dateSubtract" => {
+            let amount = number(&arg(1))?;
+            let unit = if values.len() > 2 { text(&arg(2)) } else { "days".to_string() };
+            let amount = if name == "dateSubtract" { -amount } else { amount };
+            add_to_date(date(&arg(0))?, amount as i64, &unit).map(Date)
+        }
+        "dateBetween" => {
+            let unit = if values.len() > 2 { text(&arg(2)) } else { "days".to_string() };
+            date_between(date(&arg(0))?, date(&arg(1))?, &unit).map(|n| Number(n as f64))
+        }
+        _ => unreachable!("arity table covers every function"),
+    }
+}
+
+/// Unit name without a trailing "s"
+fn unit_name(unit: &str) -> &str {
+    unit.strip_suffix('s').unwrap_or(unit)
+}
+
+/// `date` moved by `amount` units; an error rather than a panic when the
+/// result is out of range
+fn add_to_date(date: DateTime<Utc>, amount: i64, unit: &str) -> Result<DateTime<Utc>, String> {
+    let months = |n: Option<i64>| {
+        let n = n?;
+        let count = Months::new(u32::try_from(n.unsigned_abs()).ok()?);
+        if n >= 0 { date.checked_add_months(count) } else { date.checked_sub_months(count) }
+    };
+    let shifted = match unit_name(unit) {
+        "year" => months(amount.checked_mul(12)),
+        "quarter" => months(amount.checked_mul(3)),
+        "month" => months(Some(amount)),
+        "week" => Duration::try_weeks(amount).and_then(|d| date.checked_add_signed(d)),
+        "day" => Duration::try_days(amount).and_then(|d| date.checked_add_signed(d)),
+        "hour" => Duration::try_hours(amount).and_then(|d| date.checked_add_signed(d)),
+        "minute" => Duration::try_minutes(amount).and_then(|d| date.checked_add_signed(d)),
+        "second" => Duration::try_seconds(amount).and_then(|d| date.checked_add_signed(d)),
+        other => return Err(format!("Unknown date unit '{}'", other)),
+    };
+    shifted.ok_or_else(|| "Date out of range".to_string())
+}
+
+/// Whole units from `end` to `start` (positive when `start` is later)
+fn date_between(start: DateTime<Utc>, end: DateTime<Utc>, unit: &str) -> Result<i64, String> {
+    let months = || {
+        let mut months = (start.year() - end.year()) as i64 * 12 + start.month() as i64 - end.month() as i64;
+        // Only count whole months
+        if months > 0 && add_to_date(end, months, "months").is_ok_and(|d| d > start) {
+            months -= 1;
+        } else if months < 0 && add_to_date(end, months, "months").is_ok_and(|d| d < start) {
+            months += 1;
+        }
+        months
+    };
+    let elapsed = start - end;
+    match unit_name(unit) {
+        "year" => Ok(months() / 12),
+        "quarter" => Ok(months() / 3),
+        "month" => Ok(months()),
+        "week" => Ok(elapsed.num_weeks()),
+        "day" => Ok(elapsed.num_days()),
+        "hour" => Ok(elapsed.num_hours()),
+        "minute" => Ok(elapsed.num_minutes()),
+        "second" => Ok(elapsed.num_seconds()),
+        other => Err(format!("Unknown date unit '{}'", other)),
+    }
+}
+
+/// Format a date with Notion's (Moment-style) tokens; `[text]` is literal
+fn format_date(date: &DateTime<Utc>, pattern: &str) -> String {
+    const TOKENS: [&str; 19] = [
+        "YYYY", "YY", "MMMM", "MMM", "MM", "M", "Do", "DD", "D", "dddd", "ddd", "HH", "H", "hh", "h", "mm", "ss", "A", "a",
+    ];
+    let hour12 = match date.hour() % 12 {
+        0 => 12,
+        h => h,
+    };
+
+    let mut out = String::new();
+    let mut rest = pattern;
+    while let Some(c) = rest.chars().next() {
+        if let Some((literal, after)) = rest.strip_prefix('[').and_then(|r| r.split_once(']')) {
+            out.push_str(literal);
+            rest = after;
+            continue;
+        }
+        let Some(token) = TOKENS.iter().find(|t| rest.starts_with(*t)) else {
+            out.push(c);
+            rest = &rest[c.len_utf8()..];
+            continue;
+        };
+        let rendered = match *token {
+            "YYYY" => date.year().to_string(),
+            "YY" => format!("{:02}", date.year() % 100),
+            "MMMM" => date.format("%B").to_string(),
+            "MMM" => date.format("%b").to_string(),
+            "MM" => format!("{:02}", date.month()),
+            "M" => date.month().to_string(),
+            "Do" => {
+                let day = date.day();
+                let suffix = match (day % 10, day % 100) {
+                    (_, 11..=13) => "th",
+                    (1, _) => "st",
+                    (2, _) => "nd",
+                    (3, _) => "rd",
+                    _ => "th",
+                };
+                format!("{}{}", day, suffix)
+            }
+            "DD" => format!("{:02}", date.day()),
+            "D" => date.day().to_string(),
+            "dddd" => date.format("%A").to_string(),
+            "ddd" => date.format("%a").to_string(),
+            "HH" => format!("{:02}", date.hour()),
+            "H" => date.hour().to_string(),
+            "hh" => format!("{:02}", hour12),
+            "h" => hour12.to_string(),
+            "mm" => format!("{:02}", date.minute()),
+            "ss" => format!("{:02}", date.second()),
+            "A" => if date.hour() < 12 { "AM" } else { "PM" }.to_string(),
+            _ => if date.hour() < 12 { "am" } else { "pm" }.to_string(),
+        };
+        out.push_str(&rendered);
+        rest = &rest[token.len()..];
+    }
+    out
+}
+
+/// RFC 3339, `YYYY-MM-DDTHH:MM[:SS]`, or `YYYY-MM-DD` (as UTC)
+fn parse_date_text(text: &str) -> Option<DateTime<Utc>> {
+    let text = text.trim();
+    if let Ok(d) = DateTime::parse_from_rfc3339(text) {
+        return Some(d.with_timezone(&Utc));
+    }
+    ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M"]
+        .iter()
+        .find_map(|f| NaiveDateTime::parse_from_str(text, f).ok())
+        .or_else(|| NaiveDate::parse_from_str(text, "%Y-%m-%d").ok().map(|d| d.and_time(chrono::NaiveTime::MIN)))
+        .map(|d| d.and_utc())
+}
+
+/// Formula value of a Notion property object, or of a plain JSON value
+/// (strings that look like dates become dates)
+pub fn property_value(property: &Value) -> FormulaValue {
+    let typed = property
+        .get("type")
+        .and_then(|t| t.as_str())
+        .filter(|kind| property.get(*kind).is_some());
+    let Some(kind) = typed else {
+        return match property {
+            Value::Null => FormulaValue::Empty,
+            Value::Bool(b) => FormulaValue::Bool(*b),
+            Value::Number(n) => n.as_f64().map(FormulaValue::Number).unwrap_or(FormulaValue::Empty),
+            Value::String(s) => parse_date_text(s).map(FormulaValue::Date).unwrap_or_else(|| FormulaValue::Text(s.clone())),
+            other => FormulaValue::Text(other.to_string()),
+        };
+    };
+
+    let value = &property[kind];
+    match kind {
+        "number" | "checkbox" => property_value(value),
+        "date" => value
+            .get("start")
+            .and_then(|s| s.as_str())
+            .and_then(parse_date_text)
+            .map(FormulaValue::Date)
+            .unwrap_or(FormulaValue::Empty),
+        "created_time" | "last_edited_time" => value
+            .as_str()
+            .and_then(parse_date_text)
+            .map(FormulaValue::Date)
+            .unwrap_or(FormulaValue::Empty),
+        "formula" | "rollup" if matches!(value.get("type").and_then(|t| t.as_str()), Some("number" | "boolean" | "date")) => {
+            let inner = value["type"].as_str().unwrap_or_default();
+            let inner_kind = if inner == "boolean" { "checkbox" } else { inner };
+            property_value(&serde_json::json!({ "type": inner_kind, inner_kind: value[inner] }))
+        }
+        _ => property_plain_text(property).map(FormulaValue::Text).unwrap_or(FormulaValue::Empty),
+    }
+}
+
+/// Input page (a Notion page object, or `{id, properties}` with plain values)
+#[derive(Debug, Deserialize)]
+pub struct PageForFormula {
+    pub id: String,
+    #[serde(default)]
+    pub properties: Map<String, Value>,
+}
+
+/// Evaluation options
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct FormulaOptions {
+    /// Property values to substitute on every page (the "what if")
+    pub overrides: Map<String, Value>,
+    /// Per-page substitutions, by page ID (applied after `overrides`)
+    pub page_overrides: HashMap<String, Map<String, Value>>,
+    /// Reference time for now() and today() (defaults to now)
+    pub now: Option<DateTime<Utc>>,
+}
+
+/// A formula's value on one page
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FormulaResult {
+    pub id: String,
+    pub title: String,
+    pub value: Value,
+    /// "number", "text", "boolean", "date", or "empty"
+    #[serde(rename = "type")]
+    pub value_type: String,
+    pub error: Option<String>,
+}
+
+/// Formula results for a batch of pages
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FormulaEvaluation {
+    pub formula: String,
+    pub results: Vec<FormulaResult>,
+    pub errors: usize,
+    pub summary: String,
+}
+
+/// Evaluate a formula on each page.
+///
+/// Input: formula source, JSON array of pages, and options JSON
+/// (`{overrides, page_overrides, now}`, or empty for defaults)
+/// Output: JSON with each page's value, or its error
+pub fn evaluate_formula_impl(formula: &str, pages_json: &str, options_json: &str) -> Result<String, String> {
+    let expr = parse_formula(formula).map_err(|e| format!("Formula error: {}", e))?;
+    let pages: Vec<PageForFormula> =
+        serde_json::from_str(pages_json).map_err(|e| format!("JSON parse error: {}", e))?;
+    let options: FormulaOptions = if options_json.trim().is_empty() {
+        FormulaOptions::default()
+    } else {
+        serde_json::from_str(options_json).map_err(|e| format!("Options parse error: {}", e))?
+    };
+    let now = options.now.unwrap_or_else(Utc::now);
+
+    let results: Vec<FormulaResult> = pages
+        .iter()
+        .map(|page| {
+            let mut properties: HashMap<String, FormulaValue> =
+                page.properties.iter().map(|(name, value)| (name.clone(), property_value(value))).collect();
+            let page_overrides = options.page_overrides.get(&page.id).into_iter().flatten();
+            for (name, value) in options.overrides.iter().chain(page_overrides) {
+                properties.insert(name.clone(), property_value(value));
+            }
+
+            let (title, _) = extract_title(&Value::Object(page.properties.clone()));
+            match evaluate(&expr, &Context { properties: &properties, now }) {
+                Ok(value) => FormulaResult {
+                    id: page.id.clone(),
+                    title,
+                    value: value.to_json(),
+                    value_type: value.type_name().to_string(),
+                    error: None,
+                },
+                Err(e) => FormulaResult {
+                    id: page.id.clone(),
+                    title,
+                    value: Value::Null,
+                    value_type: "empty".to_string(),
+                    error: Some(e),
+                },
+            }
+        })
+        .collect();
+
+    let errors = results.iter().filter(|r| r.error.is_some()).count();
+    let result = FormulaEvaluation {
+        formula: formula.to_string(),
+        summary: format!("Evaluated on {} pages: {} ok, {} errors.", results.len(), results.len() - errors, errors),
+        results,
+        errors,
+    };
+
+    serde_json::to_string(&result).map_err(|e| format!("JSON serialize error: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(source: &str, properties: &[(&str, FormulaValue)]) -> Result<FormulaValue, String> {
+        let properties: HashMap<String, FormulaValue> =
+            properties.iter().map(|(k, v)| (k.to_string(), v.clone())).collect();
+        let now = parse_date_text("2024-06-15T09:30:00Z").unwrap();
+        evaluate(&parse_formula(source)?, &Context { properties: &properties, now })
+    }
+
+    #[test]
+    fn test_evaluate() {
+        use FormulaValue::*;
+        let props = [
+            ("Price", Number(12.5)),
+            ("Qty", Number(4.0)),
+            ("Name", Text("Widget".to_string())),
+            ("Due", Date(parse_date_text("2024-07-01").unwrap())),
+            ("Done", Bool(false)),
+            ("Notes", Empty),
+        ];
+
+        assert_eq!(eval("1 + 2 * 3 ^ 2", &props), Ok(Number(19.0)));
+        assert_eq!(eval("-2 ^ 2", &props), Ok(Number(-4.0)));
+        assert_eq!(eval(r#"prop("Price") * prop("Qty")"#, &props), Ok(Number(50.0)));
+        assert_eq!(eval(r#""Total: " + prop("Price") * prop("Qty")"#, &props), Ok(Text("Total: 50".to_string())));
+        assert_eq!(eval(r#"prop("Name").upper().length()"#, &props), Ok(Number(6.0)));
+        assert_eq!(eval(r#"if(prop("Done"), "✅", prop("Qty") > 3 and not prop("Done") ? "Big" : "Small")"#, &props), Ok(Text("Big".to_string())));
+        assert_eq!(eval(r#"ifs(prop("Qty") < 2, "few", prop("Qty") < 10, "some", "many")"#, &props), Ok(Text("some".to_string())));
+        assert_eq!(eval(r#"empty(prop("Notes")) && !empty(prop("Name"))"#, &props), Ok(Bool(true)));
+        assert_eq!(eval("round(10 / 3, 2)", &props), Ok(Number(3.33)));
+
+        // Dates
+        assert_eq!(eval(r#"dateBetween(prop("Due"), now(), "days")"#, &props), Ok(Number(15.0)));
+        assert_eq!(eval(r#"dateBetween(dateAdd(prop("Due"), 1, "months"), prop("Due"), "weeks")"#, &props), Ok(Number(4.0)));
+        assert_eq!(eval(r#"formatDate(prop("Due"), "ddd, MMM Do YYYY [at] h A")"#, &props), Ok(Text("Mon, Jul 1st 2024 at 12 AM".to_string())));
+        assert_eq!(eval(r#"month(dateSubtract(prop("Due"), 2, "quarters"))"#, &props), Ok(Number(1.0)));
+        assert_eq!(eval("format(today())", &props), Ok(Text("June 15, 2024".to_string())));
+        assert_eq!(eval(r#"month(dateAdd(prop("Due"), -13, "months"))"#, &props), Ok(Number(6.0)));
+        for unit in ["years", "quarters", "months", "weeks", "days", "hours", "minutes", "seconds"] {
+            for amount in ["10 ^ 18", "-(10 ^ 18)", "10 ^ 300"] {
+                let source = format!(r#"dateAdd(prop("Due"), {}, "{}")"#, amount, unit);
+                assert_eq!(eval(&source, &props), Err("Date out of range".to_string()), "{}", source);
+            }
+        }
+
+        // Errors
+        assert!(eval(r#"prop("Missing")"#, &props).unwrap_err().contains("Unknown property"));
+        assert!(eval(r#"prop("Name") * 2"#, &props).unwrap_err().contains("Expected a number"));
+        assert!(eval("1 / 0", &props).is_err());
+        assert!(eval(r#"repeat("ab", 10 ^ 12)"#, &props).unwrap_err().contains("more than"));
+        assert_eq!(eval(r#"repeat("ab", 3)"#, &props), Ok(Text("ababab".to_string())));
+        assert!(parse_formula("if(true, 1").is_err());
+        assert!(parse_formula("Price * 2").unwrap_err().contains("prop(\"Price\")"));
+        
+        // Deep nesting is an error, not a stack overflow
+        let nested = "(".repeat(MAX_DEPTH / 2) + "1" + &")".repeat(MAX_DEPTH / 2);
+        assert_eq!(eval(&nested, &props), Ok(Number(1.0)));
+        for source in [
+            "(".repeat(100_000) + "1" + &")".repeat(100_000),
+            "-".repeat(100_000) + "1",
+            "!".repeat(100_000) + "true",
+            vec!["1"; 100_000].join(" + "),
+            vec!["2"; 100_000].join(" ^ "),
+            format!(r#""a"{}"#, ".lower()".repeat(100_000)),
+            "true ? 1 : ".repeat(100_000) + "0",
+        ] {
+            assert_eq!(parse_formula(&source).unwrap_err(), "Formula nested too deeply");
+        }
+    }
+
+    #[test]
+    fn test_evaluate_formula_on_pages() {
+        let pages = serde_json::json!([
+            {
+                "id": "p1",
+                "properties": {
+                    "Name": { "type": "title", "title": [{ "plain_text": "Launch" }] },
+                    "Due": { "type": "date", "date": { "start": "2024-06-20" } },
+                    "Status": { "type": "status", "status": { "name": "In progress" } },
+                    "Points": { "type": "number", "number": 3 }
+                }
+            },
+            {
+                "id": "p2",
+                "properties": {
+                    "Name": { "type": "title", "title": [{ "plain_text": "Retro" }] },
+                    "Due": { "type": "date", "date": null },
+                    "Status": { "type": "status", "status": { "name": "Done" } },
+                    "Points": { "type": "number", "number": null }
+                }
+            }
+        ]);
+        let formula = r#"if(prop("Status") == "Done", "done", dateBetween(prop("Due"), now(), "days") + " days left")"#;
+        let options = serde_json::json!({
+            "now": "2024-06-15T00:00:00Z",
+            "page_overrides": { "p1": { "Due": "2024-06-25" } }
+        });
+
+        let result: FormulaEvaluation = serde_json::from_str(
+            &evaluate_formula_impl(formula, &pages.to_string(), &options.to_string()).unwrap(),
+        ).unwrap();
+        assert_eq!(result.errors, 0);
+        assert_eq!(result.results[0].title, "Launch");
+        assert_eq!(result.results[0].value, Value::String("10 days left".to_string()));
+        assert_eq!(result.results[1].value, Value::String("done".to_string()));
+
+        // What if everything were still open?
+        let options = serde_json::json!({ "now": "2024-06-15T00:00:00Z", "overrides": { "Status": "Todo" } });
+        let result: FormulaEvaluation = serde_json::from_str(
+            &evaluate_formula_impl(formula, &pages.to_string(), &options.to_string()).unwrap(),
+        ).unwrap();
+        assert_eq!(result.results[0].value, Value::String("5 days left".to_string()));
+        assert_eq!(result.errors, 1);
+        assert!(result.results[1].error.as_deref().unwrap().contains("Expected a date"));
+
+        assert!(evaluate_formula_impl("1 +", "[]", "").unwrap_err().starts_with("Formula error"));
+    }
+}
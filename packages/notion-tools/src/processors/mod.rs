@@ -1,5 +1,11 @@
 //! Data processing modules for Notion operations
 
+pub mod cross_database;
 pub mod duplicates;
+pub mod formula;
 pub mod pages;
+pub mod profiles;
+pub mod quality;
 pub mod schema;
+pub mod sync_state;
+pub mod write;
@@ -1,7 +1,16 @@
 //! Page processing and simplification
 
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+use super::profiles::{parent_database_id, ProcessorConfig};
+
+/// Version of the `simplify_pages` output shape.
+///
+/// Bump whenever a field is added, removed, renamed, or changes type, and
+/// update `simplified_pages_schema()` and the fixtures with it.
+pub const SIMPLIFIED_PAGES_SCHEMA_VERSION: u32 = 2;
 
 /// Input page from Notion API
 #[derive(Debug, Deserialize)]
@@ -12,6 +21,8 @@ pub struct NotionPage {
     #[serde(default)]
     pub url: String,
     pub properties: Value,
+    #[serde(default)]
+    pub parent: Value,
 }
 
 /// Simplified page output for agent processing
@@ -23,20 +34,78 @@ pub struct SimplifiedPage {
     pub created_time: String,
     pub last_edited_time: String,
     pub url: String,
+    /// Properties the database's profile selects, by output name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub properties: Option<BTreeMap<String, String>>,
+}
+
+/// Versioned `simplify_pages` payload
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SimplifiedPages {
+    pub schema_version: u32,
+    pub pages: Vec<SimplifiedPage>,
+}
+
+/// JSON Schema (draft 2020-12) for the `simplify_pages` output.
+pub fn simplified_pages_schema() -> Value {
+    let timestamp = json!({ "type": "string", "format": "date-time" });
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "$id": format!("urn:create-something:notion-tools:simplified-pages:{}", SIMPLIFIED_PAGES_SCHEMA_VERSION),
+        "title": "SimplifiedPages",
+        "type": "object",
+        "required": ["schema_version", "pages"],
+        "additionalProperties": false,
+        "properties": {
+            "schema_version": { "const": SIMPLIFIED_PAGES_SCHEMA_VERSION },
+            "pages": { "type": "array", "items": { "$ref": "#/$defs/SimplifiedPage" } }
+        },
+        "$defs": {
+            "SimplifiedPage": {
+                "type": "object",
+                "required": ["id", "title", "title_property_name", "created_time", "last_edited_time", "url"],
+                "additionalProperties": false,
+                "properties": {
+                    "id": { "type": "string" },
+                    "title": { "type": "string", "description": "Plain text of the title property; empty if the page has none" },
+                    "title_property_name": { "type": "string", "description": "Name of the title property; empty if the page has none" },
+                    "created_time": timestamp,
+                    "last_edited_time": timestamp,
+                    "url": { "type": "string" },
+                    "properties": {
+                        "type": "object",
+                        "description": "Plain text of the properties the database's profile selects, keyed by output name; absent without a profile that selects any",
+                        "additionalProperties": { "type": "string" }
+                    }
+                }
+            }
+        }
+    })
 }
 
 /// Simplify Notion pages for agent consumption.
 ///
-/// Input: JSON array of Notion page objects
-/// Output: JSON array of simplified pages with extracted titles
-pub fn simplify_pages_impl(pages_json: &str) -> Result<String, String> {
+/// Input: JSON array of Notion page objects, and a `ProcessorConfig` JSON
+/// ("" for none) whose per-database profiles pick the title property and
+/// the properties to include
+/// Output: JSON `{schema_version, pages}` with extracted titles (shape
+/// described by `simplified_pages_schema()`)
+pub fn simplify_pages_impl(pages_json: &str, config_json: &str) -> Result<String, String> {
     let pages: Vec<NotionPage> =
         serde_json::from_str(pages_json).map_err(|e| format!("JSON parse error: {}", e))?;
+    let config = ProcessorConfig::parse(config_json)?;
 
     let simplified: Vec<SimplifiedPage> = pages
         .into_iter()
         .map(|page| {
-            let (title, title_property_name) = extract_title(&page.properties);
+            let profile = config.profile_for(parent_database_id(&page.parent));
+            let (title, title_property_name) = match profile {
+                Some(profile) => profile.title(&page.properties),
+                None => extract_title(&page.properties),
+            };
+            let properties = profile
+                .filter(|p| !p.properties.is_empty())
+                .map(|p| p.select(&page.properties));
             SimplifiedPage {
                 id: page.id,
                 title,
@@ -44,17 +113,22 @@ pub fn simplify_pages_impl(pages_json: &str) -> Result<String, String> {
                 created_time: page.created_time,
                 last_edited_time: page.last_edited_time,
                 url: page.url,
+                properties,
             }
         })
         .collect();
 
-    serde_json::to_string(&simplified).map_err(|e| format!("JSON serialize error: {}", e))
+    let payload = SimplifiedPages {
+        schema_version: SIMPLIFIED_PAGES_SCHEMA_VERSION,
+        pages: simplified,
+    };
+    serde_json::to_string(&payload).map_err(|e| format!("JSON serialize error: {}", e))
 }
 
 /// Extract title from page properties.
 ///
 /// Searches for the property with type "title" and extracts the plain text.
-fn extract_title(properties: &Value) -> (String, String) {
+pub(crate) fn extract_title(properties: &Value) -> (String, String) {
     if let Value::Object(props) = properties {
         for (name, value) in props {
             if let Value::Object(prop) = value {
@@ -78,6 +152,58 @@ fn extract_title(properties: &Value) -> (String, String) {
     (String::new(), String::new())
 }
 
+/// Plain text value of a page property, whatever its type.
+///
+/// Returns None for empty values (no text, no selection, null).
+pub(crate) fn property_plain_text(property: &Value) -> Option<String> {
+    let kind = property.get("type")?.as_str()?;
+    let value = property.get(kind)?;
+    let text = match (kind, value) {
+        (_, Value::Null) => return None,
+        ("title" | "rich_text", Value::Array(parts)) => parts
+            .iter()
+            .filter_map(|t| t.get("plain_text").and_then(|v| v.as_str()))
+            .collect::<Vec<_>>()
+            .join(""),
+        ("select" | "status", v) => v.get("name")?.as_str()?.to_string(),
+        ("multi_select" | "people" | "files", Value::Array(items)) => {
+            let mut names: Vec<&str> = items
+                .iter()
+                .filter_map(|i| i.get("name").and_then(|n| n.as_str()))
+                .collect();
+            names.sort_unstable();
+            names.join(", ")
+        }
+        ("relation", Value::Array(items)) => items
+            .iter()
+            .filter_map(|i| i.get("id").and_then(|n| n.as_str()))
+            .collect::<Vec<_>>()
+            .join(", "),
+        ("date", v) => v.get("start")?.as_str()?.to_string(),
+        ("formula", v) => {
+            let inner = v.get("type")?.as_str()?;
+            return property_plain_text(&serde_json::json!({ "type": inner, inner: v.get(inner)? }));
+        }
+        ("rollup", v) => {
+            let inner = v.get("type")?.as_str()?;
+            match v.get(inner)? {
+                Value::Array(items) => items
+                    .iter()
+                    .filter_map(property_plain_text)
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                other => return property_plain_text(&serde_json::json!({ "type": inner, inner: other })),
+            }
+        }
+        (_, Value::String(s)) => s.clone(),
+        (_, Value::Number(n)) => n.to_string(),
+        (_, Value::Bool(b)) => b.to_string(),
+        _ => return None,
+    };
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -102,8 +228,8 @@ mod tests {
             }
         ]"#;
 
-        let result = simplify_pages_impl(input).unwrap();
-        let pages: Vec<SimplifiedPage> = serde_json::from_str(&result).unwrap();
+        let result = simplify_pages_impl(input, "").unwrap();
+        let pages = serde_json::from_str::<SimplifiedPages>(&result).unwrap().pages;
 
         assert_eq!(pages.len(), 1);
         assert_eq!(pages[0].id, "page-1");
@@ -111,6 +237,56 @@ mod tests {
         assert_eq!(pages[0].title_property_name, "Name");
     }
 
+    /// Check a value against the subset of JSON Schema the output schema uses
+    fn conforms(schema: &Value, value: &Value, root: &Value) -> Result<(), String> {
+        if let Some(reference) = schema.get("$ref").and_then(|r| r.as_str()) {
+            let name = reference.trim_start_matches("#/$defs/");
+            return conforms(&root["$defs"][name], value, root);
+        }
+        if let Some(expected) = schema.get("const") {
+            return (value == expected).then_some(()).ok_or(format!("expected {}, got {}", expected, value));
+        }
+        match (schema["type"].as_str(), value) {
+            (Some("string"), Value::String(_)) => Ok(()),
+            (Some("array"), Value::Array(items)) => items.iter().try_for_each(|i| conforms(&schema["items"], i, root)),
+            (Some("object"), Value::Object(fields)) => {
+                for required in schema["required"].as_array().into_iter().flatten() {
+                    if !fields.contains_key(required.as_str().unwrap()) {
+                        return Err(format!("missing field {}", required));
+                    }
+                }
+                for (name, field) in fields {
+                    let field_schema = schema["properties"]
+                        .get(name)
+                        .or(schema.get("additionalProperties").filter(|s| s.is_object()))
+                        .ok_or(format!("unexpected field {}", name))?;
+                    conforms(field_schema, field, root)?;
+                }
+                Ok(())
+            }
+            (kind, _) => Err(format!("expected {:?}, got {}", kind, value)),
+        }
+    }
+
+    #[test]
+    fn test_simplified_pages_fixtures() {
+        let input = include_str!("../../tests/fixtures/simplify_pages/input.json");
+        let expected: Value =
+            serde_json::from_str(include_str!("../../tests/fixtures/simplify_pages/output.v2.json")).unwrap();
+        let pinned_schema: Value =
+            serde_json::from_str(include_str!("../../tests/fixtures/simplify_pages/schema.v2.json")).unwrap();
+        let config = include_str!("../../tests/fixtures/simplify_pages/config.json");
+
+        // Output and schema are pinned: changing either means a new version and new fixtures
+        let output: Value = serde_json::from_str(&simplify_pages_impl(input, config).unwrap()).unwrap();
+        assert_eq!(output, expected);
+        assert_eq!(simplified_pages_schema(), pinned_schema);
+
+        let schema = simplified_pages_schema();
+        conforms(&schema, &output, &schema).unwrap();
+        assert!(conforms(&schema, &json!({ "schema_version": 1, "pages": [{ "id": "x" }] }), &schema).is_err());
+    }
+
     #[test]
     fn test_extract_title_empty() {
         let props = serde_json::json!({});
@@ -0,0 +1,213 @@
+//! Per-database processing profiles
+//!
+//! Databases differ in what matters: a CRM's title property is "Company",
+//! a content calendar's dates should read "Mar 4", a tracker has forty
+//! properties of which three are worth an agent's context. A config maps
+//! database IDs to profiles that `simplify_pages`, `format_schema` and
+//! `find_duplicates` apply:
+//!
+//! ```json
+//! {
+//!   "databases": {
+//!     "1a2b3c4d-...": {
+//!       "title_property": "Company",
+//!       "properties": ["Status", "Renewal"],
+//!       "rename": { "Renewal": "renews_on" },
+//!       "date_format": "%b %-d",
+//!       "match_on": ["Domain"]
+//!     }
+//!   },
+//!   "default": { "properties": ["Status"] }
+//! }
+//! ```
+//!
+//! Database IDs match with or without dashes, in any case. Pages find their
+//! database through `parent.database_id`.
+
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, NaiveDate};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+
+use super::pages::{extract_title, property_plain_text};
+
+/// Profiles by database, with a fallback
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ProcessorConfig {
+    /// Database ID → profile
+    pub databases: HashMap<String, DatabaseProfile>,
+    /// Used for pages (or schemas) whose database has no profile
+    pub default: Option<DatabaseProfile>,
+}
+
+/// How to process one database's pages
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct DatabaseProfile {
+    /// Property holding the title, when it isn't the title-typed one
+    pub title_property: Option<String>,
+    /// Properties to include in simplified pages and the formatted schema
+    /// (the schema keeps all of them when this is empty)
+    pub properties: Vec<String>,
+    /// Output names for properties
+    pub rename: HashMap<String, String>,
+    /// strftime format for date values (e.g. "%Y-%m-%d", "%b %-d")
+    pub date_format: Option<String>,
+    /// Properties that must also match for pages to count as duplicates
+    pub match_on: Vec<String>,
+}
+
+impl ProcessorConfig {
+    /// Parse a config; an empty string means no profiles
+    pub fn parse(config_json: &str) -> Result<Self, String> {
+        if config_json.trim().is_empty() {
+            return Ok(Self::default());
+        }
+        let config: Self =
+            serde_json::from_str(config_json).map_err(|e| format!("Config parse error: {}", e))?;
+        for profile in config.databases.values().chain(&config.default) {
+            if let Some(format) = &profile.date_format {
+                if StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+                    return Err(format!("Invalid date_format: {}", format));
+                }
+            }
+        }
+        Ok(config)
+    }
+    
+    /// The profile for a database, or the default
+    pub fn profile_for(&self, database_id: Option<&str>) -> Option<&DatabaseProfile> {
+        database_id
+            .and_then(|id| {
+                let id = normalize_id(id);
+                self.databases
+                    .iter()
+                    .find(|(key, _)| normalize_id(key) == id)
+                    .map(|(_, profile)| profile)
+            })
+            .or(self.default.as_ref())
+    }
+}
+
+impl DatabaseProfile {
+    /// Title text and the property it came from
+    pub fn title(&self, properties: &Value) -> (String, String) {
+        match &self.title_property {
+            Some(name) if properties.get(name).is_some() => (
+                properties.get(name).and_then(property_plain_text).unwrap_or_default(),
+                name.clone(),
+            ),
+            _ => extract_title(properties),
+        }
+    }
+    
+    /// Whether a property is included
+    pub fn includes(&self, name: &str) -> bool {
+        self.properties.is_empty() || self.properties.iter().any(|p| p == name)
+    }
+    
+    /// Name a property goes by in output
+    pub fn output_name<'a>(&'a self, name: &'a str) -> &'a str {
+        self.rename.get(name).map(String::as_str).unwrap_or(name)
+    }
+    
+    /// Selected properties, by output name, as plain text (dates formatted);
+    /// empty properties are left out
+    pub fn select(&self, properties: &Value) -> BTreeMap<String, String> {
+        self.properties
+            .iter()
+            .filter_map(|name| {
+                let value = self.value(properties.get(name)?)?;
+                Some((self.output_name(name).to_string(), value))
+            })
+            .collect()
+    }
+    
+    /// Plain text of a property, with dates in `date_format`
+    pub fn value(&self, property: &Value) -> Option<String> {
+        let text = property_plain_text(property)?;
+        let is_date = matches!(
+            property.get("type").and_then(|t| t.as_str()),
+            Some("date" | "created_time" | "last_edited_time")
+        );
+        match &self.date_format {
+            Some(format) if is_date => Some(format_date(&text, format)),
+            _ => Some(text),
+        }
+    }
+}
+
+/// The database a page belongs to, from its `parent`
+pub(crate) fn parent_database_id(parent: &Value) -> Option<&str> {
+    parent.get("database_id").and_then(|id| id.as_str())
+}
+
+pub(crate) fn normalize_id(id: &str) -> String {
+    id.chars().filter(|c| *c != '-').collect::<String>().to_lowercase()
+}
+
+/// Reformat an ISO 8601 date or date-time; anything else is left as is
+fn format_date(value: &str, format: &str) -> String {
+    let mut out = String::new();
+    let written = if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        write!(out, "{}", time.format(format))
+    } else if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        write!(out, "{}", date.format(format))
+    } else {
+        return value.to_string();
+    };
+    // Date-only values can't fill time fields
+    match written {
+        Ok(()) => out,
+        Err(_) => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    
+    #[test]
+    fn test_profile_selects_renames_and_formats() {
+        let config = ProcessorConfig::parse(
+            r#"{
+                "databases": {
+                    "1A2B3C4D00004000800000000000000A": {
+                        "title_property": "Company",
+                        "properties": ["Status", "Renewal", "Missing"],
+                        "rename": { "Renewal": "renews_on" },
+                        "date_format": "%d/%m/%Y"
+                    }
+                },
+                "default": { "properties": ["Status"] }
+            }"#,
+        )
+        .unwrap();
+        let properties = json!({
+            "Name": { "type": "title", "title": [{ "plain_text": "acme-import-row" }] },
+            "Company": { "type": "rich_text", "rich_text": [{ "plain_text": "Acme" }] },
+            "Status": { "type": "status", "status": { "name": "Active" } },
+            "Renewal": { "type": "date", "date": { "start": "2024-07-01" } }
+        });
+        
+        let profile = config.profile_for(Some("1a2b3c4d-0000-4000-8000-00000000000a")).unwrap();
+        assert_eq!(profile.title(&properties), ("Acme".to_string(), "Company".to_string()));
+        let selected = profile.select(&properties);
+        assert_eq!(selected.len(), 2);
+        assert_eq!(selected["renews_on"], "01/07/2024");
+        assert_eq!(selected["Status"], "Active");
+        
+        // Other databases fall back to the default
+        let fallback = config.profile_for(Some("ffff")).unwrap();
+        assert_eq!(fallback.title(&properties).0, "acme-import-row");
+        assert_eq!(fallback.select(&properties).len(), 1);
+        
+        assert!(ProcessorConfig::parse("").unwrap().profile_for(Some("ffff")).is_none());
+        assert!(ProcessorConfig::parse(r#"{"default": {"date_format": "%Q"}}"#).is_err());
+        assert_eq!(format_date("2024-07-01", "%H:%M"), "2024-07-01");
+    }
+}
@@ -0,0 +1,389 @@
+//! Content quality scoring for Notion pages
+//!
+//! Rates each page on completeness so agents can target cleanup work
+//! directly instead of asking an LLM to "find low quality pages":
+//!
+//! - **Properties**: share of required properties that have a value
+//! - **Body**: word count against a minimum (skipped when no content is given)
+//! - **Freshness**: full marks until `stale_after_days`, then decaying to zero
+//!   at twice that age
+//!
+//! The page score is a weighted mean of whichever parts apply.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+use super::pages::{extract_title, property_plain_text};
+
+const PROPERTIES_WEIGHT: f64 = 0.5;
+const BODY_WEIGHT: f64 = 0.3;
+const FRESHNESS_WEIGHT: f64 = 0.2;
+
+/// Pages scoring below this are "poor"; at or above `GOOD_SCORE` "good"
+const POOR_SCORE: f64 = 0.5;
+const GOOD_SCORE: f64 = 0.8;
+
+/// Input page for scoring (a Notion page object, plus optional body text)
+#[derive(Debug, Deserialize)]
+pub struct PageForScoring {
+    pub id: String,
+    pub last_edited_time: String,
+    #[serde(default)]
+    pub url: String,
+    pub properties: Value,
+    /// Plain text of the page body, if the caller fetched it
+    #[serde(default)]
+    pub content: Option<String>,
+}
+
+/// Scoring options
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct ScoreOptions {
+    /// Properties that should have a value (the title is always required)
+    pub required_properties: Vec<String>,
+    /// Bodies shorter than this are stubs
+    pub min_body_words: usize,
+    /// Pages not edited for this long start losing freshness
+    pub stale_after_days: i64,
+    /// Reference time (defaults to now)
+    pub now: Option<DateTime<Utc>>,
+    /// How many of the lowest-scoring pages to list in the stats
+    pub worst: usize,
+}
+
+impl Default for ScoreOptions {
+    fn default() -> Self {
+        Self {
+            required_properties: Vec::new(),
+            min_body_words: 50,
+            stale_after_days: 90,
+            now: None,
+            worst: 10,
+        }
+    }
+}
+
+/// One page's score and what pulled it down
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PageScore {
+    pub id: String,
+    pub title: String,
+    pub url: String,
+    /// Overall score (0.0 - 1.0)
+    pub score: f64,
+    pub properties_score: f64,
+    /// None when no body content was given
+    pub body_score: Option<f64>,
+    pub freshness_score: f64,
+    pub empty_properties: Vec<String>,
+    pub body_words: Option<usize>,
+    pub days_since_edit: Option<i64>,
+    /// Human-readable problems ("empty: Status", "stub body (12 words)", ...)
+    pub issues: Vec<String>,
+}
+
+/// Aggregate stats across all scored pages
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QualityStats {
+    pub mean_score: f64,
+    pub median_score: f64,
+    /// Pages scoring at least 0.8
+    pub good: usize,
+    /// Pages scoring 0.5 - 0.8
+    pub fair: usize,
+    /// Pages scoring below 0.5
+    pub poor: usize,
+    pub untitled: usize,
+    pub stub_bodies: usize,
+    pub stale: usize,
+    /// Required property → pages where it's empty
+    pub empty_by_property: BTreeMap<String, usize>,
+    /// Lowest-scoring page IDs, worst first
+    pub worst_pages: Vec<String>,
+}
+
+/// Result of scoring pages
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ScoreResult {
+    pub total_pages: usize,
+    /// Sorted by score, lowest first
+    pub pages: Vec<PageScore>,
+    pub stats: QualityStats,
+    pub summary: String,
+}
+
+/// Score pages on completeness.
+///
+/// Input: JSON array of Notion page objects (optionally with a `content`
+/// string holding body text) and JSON options (empty string for defaults)
+/// Output: JSON with per-page scores, lowest first, and aggregate stats
+pub fn score_pages_impl(pages_json: &str, options_json: &str) -> Result<String, String> {
+    let pages: Vec<PageForScoring> =
+        serde_json::from_str(pages_json).map_err(|e| format!("JSON parse error: {}", e))?;
+    let options: ScoreOptions = if options_json.trim().is_empty() {
+        ScoreOptions::default()
+    } else {
+        serde_json::from_str(options_json).map_err(|e| format!("Options parse error: {}", e))?
+    };
+
+    let now = options.now.unwrap_or_else(Utc::now);
+    let mut scores: Vec<PageScore> = pages.iter().map(|p| score_page(p, &options, now)).collect();
+    scores.sort_by(|a, b| a.score.total_cmp(&b.score).then_with(|| a.id.cmp(&b.id)));
+
+    let stats = aggregate(&scores, &options);
+    let summary = format!(
+        "Scored {} pages: {} good, {} fair, {} poor (mean {:.2}).",
+        scores.len(),
+        stats.good,
+        stats.fair,
+        stats.poor,
+        stats.mean_score
+    );
+
+    let result = ScoreResult {
+        total_pages: scores.len(),
+        pages: scores,
+        stats,
+        summary,
+    };
+
+    serde_json::to_string(&result).map_err(|e| format!("JSON serialize error: {}", e))
+}
+
+fn score_page(page: &PageForScoring, options: &ScoreOptions, now: DateTime<Utc>) -> PageScore {
+    let (title, title_property) = extract_title(&page.properties);
+    let mut issues = Vec::new();
+
+    // Required properties, title first
+    let mut required: Vec<&str> = Vec::new();
+    if !title_property.is_empty() {
+        required.push(&title_property);
+    }
+    required.extend(
+        options
+            .required_properties
+            .iter()
+            .map(|p| p.as_str())
+            .filter(|p| *p != title_property),
+    );
+
+    let empty_properties: Vec<String> = required
+        .iter()
+        .filter(|name| {
+            page.properties
+                .get(**name)
+                .and_then(property_plain_text)
+                .is_none()
+        })
+        .map(|name| name.to_string())
+        .collect();
+    if title.trim().is_empty() {
+        issues.push("untitled".to_string());
+    }
+    for name in empty_properties.iter().filter(|n| **n != title_property) {
+        issues.push(format!("empty: {}", name));
+    }
+    let properties_score = if required.is_empty() {
+        1.0
+    } else {
+        1.0 - empty_properties.len() as f64 / required.len() as f64
+    };
+
+    // Body
+    let body_words = page.content.as_ref().map(|c| c.split_whitespace().count());
+    let body_score = body_words.map(|words| {
+        if options.min_body_words == 0 {
+            1.0
+        } else {
+            (words as f64 / options.min_body_words as f64).min(1.0)
+        }
+    });
+    if let Some(words) = body_words.filter(|w| *w < options.min_body_words) {
+        issues.push(format!("stub body ({} words)", words));
+    }
+
+    // Freshness
+    let days_since_edit = DateTime::parse_from_rfc3339(&page.last_edited_time)
+        .ok()
+        .map(|edited| (now - edited.with_timezone(&Utc)).num_days());
+    let freshness_score = match days_since_edit {
+        Some(days) if days > options.stale_after_days => {
+            issues.push(format!("stale ({} days since edit)", days));
+            let over = (days - options.stale_after_days) as f64;
+            (1.0 - over / options.stale_after_days.max(1) as f64).max(0.0)
+        }
+        Some(_) => 1.0,
+        None => 0.0,
+    };
+
+    let (weighted, weights) = match body_score {
+        Some(body) => (
+            properties_score * PROPERTIES_WEIGHT
+                + body * BODY_WEIGHT
+                + freshness_score * FRESHNESS_WEIGHT,
+            PROPERTIES_WEIGHT + BODY_WEIGHT + FRESHNESS_WEIGHT,
+        ),
+        None => (
+            properties_score * PROPERTIES_WEIGHT + freshness_score * FRESHNESS_WEIGHT,
+            PROPERTIES_WEIGHT + FRESHNESS_WEIGHT,
+        ),
+    };
+
+    PageScore {
+        id: page.id.clone(),
+        title,
+        url: page.url.clone(),
+        score: weighted / weights,
+        properties_score,
+        body_score,
+        freshness_score,
+        empty_properties,
+        body_words,
+        days_since_edit,
+        issues,
+    }
+}
+
+fn aggregate(scores: &[PageScore], options: &ScoreOptions) -> QualityStats {
+    let mut empty_by_property = BTreeMap::new();
+    for score in scores {
+        for name in &score.empty_properties {
+            *empty_by_property.entry(name.clone()).or_insert(0) += 1;
+        }
+    }
+
+    // `scores` is already sorted ascending
+    let median_score = match scores.len() {
+        0 => 0.0,
+        n if n % 2 == 1 => scores[n / 2].score,
+        n => (scores[n / 2 - 1].score + scores[n / 2].score) / 2.0,
+    };
+    let mean_score = if scores.is_empty() {
+        0.0
+    } else {
+        scores.iter().map(|s| s.score).sum::<f64>() / scores.len() as f64
+    };
+
+    QualityStats {
+        mean_score,
+        median_score,
+        good: scores.iter().filter(|s| s.score >= GOOD_SCORE).count(),
+        fair: scores
+            .iter()
+            .filter(|s| (POOR_SCORE..GOOD_SCORE).contains(&s.score))
+            .count(),
+        poor: scores.iter().filter(|s| s.score < POOR_SCORE).count(),
+        untitled: scores.iter().filter(|s| s.title.trim().is_empty()).count(),
+        stub_bodies: scores
+            .iter()
+            .filter(|s| s.body_words.is_some_and(|w| w < options.min_body_words))
+            .count(),
+        stale: scores
+            .iter()
+            .filter(|s| {
+                s.days_since_edit
+                    .is_some_and(|d| d > options.stale_after_days)
+            })
+            .count(),
+        empty_by_property,
+        worst_pages: scores
+            .iter()
+            .take(options.worst)
+            .map(|s| s.id.clone())
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score_pages() {
+        let input = r#"[
+            {
+                "id": "complete",
+                "last_edited_time": "2024-06-01T00:00:00.000Z",
+                "properties": {
+                    "Name": {"type": "title", "title": [{"plain_text": "Launch plan"}]},
+                    "Status": {"type": "status", "status": {"name": "Done"}},
+                    "Owner": {"type": "people", "people": [{"name": "Sam"}]}
+                },
+                "content": "one two three four five"
+            },
+            {
+                "id": "neglected",
+                "last_edited_time": "2024-01-01T00:00:00.000Z",
+                "properties": {
+                    "Name": {"type": "title", "title": []},
+                    "Status": {"type": "status", "status": null},
+                    "Owner": {"type": "people", "people": [{"name": "Sam"}]}
+                },
+                "content": "todo"
+            },
+            {
+                "id": "no-body",
+                "last_edited_time": "2024-06-10T00:00:00.000Z",
+                "properties": {
+                    "Name": {"type": "title", "title": [{"plain_text": "Notes"}]},
+                    "Status": {"type": "status", "status": {"name": "Draft"}}
+                }
+            }
+        ]"#;
+        let options = r#"{
+            "required_properties": ["Status", "Owner"],
+            "min_body_words": 5,
+            "stale_after_days": 60,
+            "now": "2024-06-15T00:00:00Z"
+        }"#;
+
+        let result = score_pages_impl(input, options).unwrap();
+        let parsed: ScoreResult = serde_json::from_str(&result).unwrap();
+
+        assert_eq!(parsed.total_pages, 3);
+
+        // Worst first: 2 of 3 required empty, 1/5 words, 166 days old (fully stale)
+        let neglected = &parsed.pages[0];
+        assert_eq!(neglected.id, "neglected");
+        assert_eq!(neglected.empty_properties, vec!["Name", "Status"]);
+        assert_eq!(
+            neglected.issues,
+            vec![
+                "untitled",
+                "empty: Status",
+                "stub body (1 words)",
+                "stale (166 days since edit)"
+            ]
+        );
+        assert!((neglected.score - (0.5 / 3.0 + 0.3 * 0.2)).abs() < 1e-9);
+
+        // Missing Owner; body not scored, so weights renormalize
+        let no_body = &parsed.pages[1];
+        assert_eq!(no_body.id, "no-body");
+        assert_eq!(no_body.body_score, None);
+        assert!((no_body.score - (0.5 * 2.0 / 3.0 + 0.2) / 0.7).abs() < 1e-9);
+
+        assert_eq!(parsed.pages[2].score, 1.0);
+
+        let stats = &parsed.stats;
+        assert_eq!((stats.good, stats.fair, stats.poor), (1, 1, 1));
+        assert_eq!((stats.untitled, stats.stub_bodies, stats.stale), (1, 1, 1));
+        assert_eq!(stats.empty_by_property.get("Status"), Some(&1));
+        assert_eq!(stats.empty_by_property.get("Owner"), Some(&1));
+        assert_eq!(stats.worst_pages[0], "neglected");
+    }
+
+    #[test]
+    fn test_default_options() {
+        let input = r#"[{"id": "p", "last_edited_time": "not a date", "properties": {}}]"#;
+        let result = score_pages_impl(input, "").unwrap();
+        let parsed: ScoreResult = serde_json::from_str(&result).unwrap();
+
+        // Nothing required, unknown edit time counts as stale
+        assert_eq!(parsed.pages[0].properties_score, 1.0);
+        assert_eq!(parsed.pages[0].freshness_score, 0.0);
+    }
+}
@@ -3,6 +3,8 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use super::profiles::ProcessorConfig;
+
 /// Notion property types we care about
 #[derive(Debug, Deserialize)]
 #[serde(tag = "type")]
@@ -94,14 +96,19 @@ pub struct FormattedProperty {
 
 /// Format database properties into a structured schema.
 ///
-/// Input: JSON object of Notion database properties
+/// Input: JSON object of Notion database properties, a `ProcessorConfig`
+/// JSON ("" for none), and the database's ID. The database's profile
+/// picks which properties to keep and renames them.
 /// Output: JSON array of formatted properties with options extracted
-pub fn format_schema_impl(properties_json: &str) -> Result<String, String> {
+pub fn format_schema_impl(properties_json: &str, config_json: &str, database_id: &str) -> Result<String, String> {
     let properties: HashMap<String, PropertyValue> =
         serde_json::from_str(properties_json).map_err(|e| format!("JSON parse error: {}", e))?;
+    let config = ProcessorConfig::parse(config_json)?;
+    let profile = config.profile_for(Some(database_id).filter(|id| !id.is_empty()));
 
     let mut formatted: Vec<FormattedProperty> = properties
         .into_iter()
+        .filter(|(name, _)| profile.is_none_or(|p| p.includes(name)))
         .map(|(name, prop)| {
             let (prop_type, options) = match prop {
                 PropertyValue::Title { .. } => ("title".to_string(), None),
@@ -137,6 +144,11 @@ pub fn format_schema_impl(properties_json: &str) -> Result<String, String> {
                 PropertyValue::Unknown => ("unknown".to_string(), None),
             };
 
+            let name = match profile {
+                Some(p) => p.output_name(&name).to_string(),
+                None => name,
+            };
+            
             FormattedProperty {
                 name,
                 prop_type,
@@ -232,7 +244,7 @@ mod tests {
             }
         }"#;
 
-        let result = format_schema_impl(input).unwrap();
+        let result = format_schema_impl(input, "", "").unwrap();
         let parsed: FormattedSchema = serde_json::from_str(&result).unwrap();
 
         assert_eq!(parsed.properties.len(), 2);
@@ -242,5 +254,14 @@ mod tests {
         assert!(status.options.is_some());
         let opts = status.options.as_ref().unwrap();
         assert!(opts.contains(&"Done".to_string()));
+        
+        // A profile keeps and renames properties for its database only
+        let config = r#"{"databases": {"db-1": {"properties": ["Status"], "rename": {"Status": "stage"}}}}"#;
+        let parsed: FormattedSchema = serde_json::from_str(&format_schema_impl(input, config, "DB-1").unwrap()).unwrap();
+        assert_eq!(parsed.properties.len(), 1);
+        assert_eq!(parsed.properties[0].name, "stage");
+        assert_eq!(parsed.properties[0].prop_type, "status");
+        let parsed: FormattedSchema = serde_json::from_str(&format_schema_impl(input, config, "db-2").unwrap()).unwrap();
+        assert_eq!(parsed.properties.len(), 2);
     }
 }
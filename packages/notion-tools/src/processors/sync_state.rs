@@ -0,0 +1,243 @@
+//! Incremental sync state for Notion → database syncs
+//!
+//! Every Notion → D1 sync Worker runs the same loop: query pages edited
+//! since a cursor, work out which rows to insert, update, or delete, and
+//! remember what it has seen. `sync_diff_impl` is that loop's core:
+//!
+//! - **State** maps page ID → last_edited_time and a content hash, plus the
+//!   cursor (latest last_edited_time seen) for the next query
+//! - **Created**: not in the previous state
+//! - **Updated**: content hash changed (an edit that leaves properties and
+//!   content as they were is unchanged, so no write)
+//! - **Deleted**: archived or trashed pages, and with `full_scan`, every
+//!   tracked page missing from the batch
+//!
+//! Notion's last_edited_time is rounded to the minute, so query with
+//! `on_or_after` the cursor rather than `after`; re-seen pages come back
+//! unchanged.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Version of the serialized state; older states are rejected
+pub const SYNC_STATE_VERSION: u32 = 1;
+
+/// Input page (a Notion page object, plus optional body text)
+#[derive(Debug, Deserialize)]
+pub struct PageForSync {
+    pub id: String,
+    pub last_edited_time: String,
+    #[serde(default)]
+    pub properties: Value,
+    #[serde(default)]
+    pub archived: bool,
+    #[serde(default)]
+    pub in_trash: bool,
+    /// Body text, if the caller fetched it (included in the hash)
+    #[serde(default)]
+    pub content: Option<String>,
+}
+
+/// What was last synced for one page
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PageSyncState {
+    pub last_edited_time: String,
+    pub hash: String,
+}
+
+/// Persisted sync state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncState {
+    pub version: u32,
+    /// Latest last_edited_time seen; query `on_or_after` this next time
+    pub cursor: Option<String>,
+    pub pages: BTreeMap<String, PageSyncState>,
+}
+
+impl Default for SyncState {
+    fn default() -> Self {
+        Self {
+            version: SYNC_STATE_VERSION,
+            cursor: None,
+            pages: BTreeMap::new(),
+        }
+    }
+}
+
+/// Classified batch plus the state to persist
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SyncDiff {
+    pub created: Vec<String>,
+    pub updated: Vec<String>,
+    pub unchanged: Vec<String>,
+    pub deleted: Vec<String>,
+    pub cursor: Option<String>,
+    /// Serialized `SyncState`; pass back in as `previous_state` next time
+    pub state: String,
+    pub summary: String,
+}
+
+/// Classify a batch of pages against the previous sync state.
+///
+/// Input: previous state (empty string on first sync), JSON array of Notion
+/// page objects, and whether the batch is every page (so absent pages count
+/// as deleted) rather than an incremental query
+/// Output: JSON with created/updated/unchanged/deleted page IDs and new state
+pub fn sync_diff_impl(previous_state: &str, pages_json: &str, full_scan: bool) -> Result<String, String> {
+    let previous: SyncState = if previous_state.trim().is_empty() {
+        SyncState::default()
+    } else {
+        serde_json::from_str(previous_state).map_err(|e| format!("State parse error: {}", e))?
+    };
+    if previous.version != SYNC_STATE_VERSION {
+        return Err(format!(
+            "Unsupported sync state version {} (expected {}); start a fresh sync",
+            previous.version, SYNC_STATE_VERSION
+        ));
+    }
+    let pages: Vec<PageForSync> =
+        serde_json::from_str(pages_json).map_err(|e| format!("JSON parse error: {}", e))?;
+
+    let mut state = SyncState {
+        cursor: previous.cursor.clone(),
+        pages: if full_scan { BTreeMap::new() } else { previous.pages.clone() },
+        ..Default::default()
+    };
+    let (mut created, mut updated, mut unchanged, mut deleted) = (Vec::new(), Vec::new(), Vec::new(), Vec::new());
+
+    for page in &pages {
+        // Timestamps are RFC 3339 in UTC, so they order as strings
+        if state.cursor.as_deref().is_none_or(|c| page.last_edited_time.as_str() > c) {
+            state.cursor = Some(page.last_edited_time.clone());
+        }
+
+        let known = previous.pages.get(&page.id);
+        if page.archived || page.in_trash {
+            state.pages.remove(&page.id);
+            if known.is_some() {
+                deleted.push(page.id.clone());
+            }
+            continue;
+        }
+
+        let hash = content_hash(page);
+        match known {
+            None => created.push(page.id.clone()),
+            Some(prev) if prev.hash != hash => updated.push(page.id.clone()),
+            Some(_) => unchanged.push(page.id.clone()),
+        }
+        state.pages.insert(page.id.clone(), PageSyncState {
+            last_edited_time: page.last_edited_time.clone(),
+            hash,
+        });
+    }
+
+    if full_scan {
+        let missing: Vec<String> = previous
+            .pages
+            .keys()
+            .filter(|id| !state.pages.contains_key(*id) && !deleted.contains(id))
+            .cloned()
+            .collect();
+        deleted.extend(missing);
+    }
+
+    let summary = format!(
+        "{} created, {} updated, {} unchanged, {} deleted ({} pages tracked).",
+        created.len(),
+        updated.len(),
+        unchanged.len(),
+        deleted.len(),
+        state.pages.len()
+    );
+    let result = SyncDiff {
+        created,
+        updated,
+        unchanged,
+        deleted,
+        cursor: state.cursor.clone(),
+        state: serde_json::to_string(&state).map_err(|e| format!("JSON serialize error: {}", e))?,
+        summary,
+    };
+
+    serde_json::to_string(&result).map_err(|e| format!("JSON serialize error: {}", e))
+}
+
+/// Hash of a page's properties and content.
+///
+/// FNV-1a over canonical JSON (object keys sorted): stable across builds and
+/// runtimes, which a persisted hash needs and `DefaultHasher` doesn't promise.
+fn content_hash(page: &PageForSync) -> String {
+    let canonical = serde_json::json!({ "properties": page.properties, "content": page.content }).to_string();
+    let hash = canonical.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("{:016x}", hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(id: &str, edited: &str, status: &str) -> Value {
+        serde_json::json!({
+            "id": id,
+            "last_edited_time": edited,
+            "properties": { "Status": { "type": "status", "status": { "name": status } } }
+        })
+    }
+
+    #[test]
+    fn test_incremental_sync() {
+        let first = serde_json::json!([
+            page("a", "2024-01-01T10:00:00.000Z", "Todo"),
+            page("b", "2024-01-01T11:00:00.000Z", "Todo"),
+            page("c", "2024-01-01T09:00:00.000Z", "Done"),
+        ]);
+        let diff: SyncDiff = serde_json::from_str(&sync_diff_impl("", &first.to_string(), false).unwrap()).unwrap();
+        assert_eq!(diff.created, vec!["a", "b", "c"]);
+        assert_eq!(diff.cursor.as_deref(), Some("2024-01-01T11:00:00.000Z"));
+
+        // b edited, a touched without a content change, c archived, d new
+        let mut archived = page("c", "2024-01-02T09:00:00.000Z", "Done");
+        archived["archived"] = Value::Bool(true);
+        let second = serde_json::json!([
+            page("a", "2024-01-02T08:00:00.000Z", "Todo"),
+            page("b", "2024-01-02T08:00:00.000Z", "Doing"),
+            archived,
+            page("d", "2024-01-02T10:00:00.000Z", "Todo"),
+        ]);
+        let diff: SyncDiff = serde_json::from_str(&sync_diff_impl(&diff.state, &second.to_string(), false).unwrap()).unwrap();
+        assert_eq!(diff.created, vec!["d"]);
+        assert_eq!(diff.updated, vec!["b"]);
+        assert_eq!(diff.unchanged, vec!["a"]);
+        assert_eq!(diff.deleted, vec!["c"]);
+        assert_eq!(diff.cursor.as_deref(), Some("2024-01-02T10:00:00.000Z"));
+
+        let state: SyncState = serde_json::from_str(&diff.state).unwrap();
+        assert_eq!(state.pages.keys().collect::<Vec<_>>(), vec!["a", "b", "d"]);
+        assert_eq!(state.pages["a"].last_edited_time, "2024-01-02T08:00:00.000Z");
+
+        // Empty incremental batch keeps everything
+        let diff: SyncDiff = serde_json::from_str(&sync_diff_impl(&diff.state, "[]", false).unwrap()).unwrap();
+        assert!(diff.deleted.is_empty());
+
+        // Full scan: pages not returned were deleted
+        let full = serde_json::json!([page("a", "2024-01-02T08:00:00.000Z", "Todo")]);
+        let diff: SyncDiff = serde_json::from_str(&sync_diff_impl(&diff.state, &full.to_string(), true).unwrap()).unwrap();
+        assert_eq!(diff.unchanged, vec!["a"]);
+        assert_eq!(diff.deleted, vec!["b", "d"]);
+    }
+
+    #[test]
+    fn test_content_hash_is_stable() {
+        let a: PageForSync = serde_json::from_str(r#"{"id": "x", "last_edited_time": "t", "properties": {"B": 1, "A": 2}}"#).unwrap();
+        let b: PageForSync = serde_json::from_str(r#"{"id": "x", "last_edited_time": "u", "properties": {"A": 2, "B": 1}}"#).unwrap();
+        // Pinned: persisted state from earlier builds must keep matching
+        assert_eq!(content_hash(&a), "6422b55cc8aa4e24");
+        assert_eq!(content_hash(&a), content_hash(&b));
+
+        assert!(sync_diff_impl(r#"{"version": 0, "cursor": null, "pages": {}}"#, "[]", false).is_err());
+    }
+}
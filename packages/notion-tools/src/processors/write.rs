@@ -0,0 +1,329 @@
+//! Guardrails for agent-built Notion writes
+//!
+//! An agent assembling a create or update payload by hand gets property
+//! names, value shapes and select options wrong in ways Notion only reports
+//! one at a time, after the request. Checking the payload against the
+//! database schema first catches all of them at once:
+//!
+//! - **Unknown properties**: names (or IDs) the database doesn't have,
+//!   with the closest match suggested
+//! - **Read-only properties**: formulas, rollups, timestamps and the like
+//! - **Wrong types**: a `select` value for a `status` property, a string
+//!   where a number goes, a relation that isn't a list of `{id}`
+//! - **Unknown options**: select, multi-select and status values the schema
+//!   doesn't list (Notion would fail, or silently add a select option)
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Property types Notion computes, which a write can't set
+const READ_ONLY_TYPES: &[&str] = &[
+    "formula",
+    "rollup",
+    "created_time",
+    "created_by",
+    "last_edited_time",
+    "last_edited_by",
+    "unique_id",
+    "button",
+    "verification",
+];
+
+/// Property value types a write can set
+const WRITABLE_TYPES: &[&str] = &[
+    "title",
+    "rich_text",
+    "number",
+    "select",
+    "multi_select",
+    "status",
+    "date",
+    "checkbox",
+    "url",
+    "email",
+    "phone_number",
+    "relation",
+    "people",
+    "files",
+];
+
+/// What's wrong with a property in the payload
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WriteErrorKind {
+    UnknownProperty,
+    ReadOnly,
+    WrongType,
+    InvalidValue,
+    UnknownOption,
+}
+
+/// One problem with the payload, and how to fix it
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WriteError {
+    /// Property as named in the payload
+    pub property: String,
+    pub kind: WriteErrorKind,
+    pub message: String,
+}
+
+/// Result of validating a payload
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WriteValidation {
+    pub valid: bool,
+    /// Properties in the payload
+    pub checked: usize,
+    pub errors: Vec<WriteError>,
+}
+
+/// Check a create/update page payload against a database schema.
+///
+/// Input: the request body (`{properties: {...}, ...}`) and the database
+/// object or its `properties` map. Properties may be keyed by name or ID.
+/// Output: JSON `{valid, checked, errors: [{property, kind, message}]}`
+pub fn validate_write_impl(payload_json: &str, schema_json: &str) -> Result<String, String> {
+    let payload: Value =
+        serde_json::from_str(payload_json).map_err(|e| format!("Payload parse error: {}", e))?;
+    let schema: Value =
+        serde_json::from_str(schema_json).map_err(|e| format!("Schema parse error: {}", e))?;
+    
+    let schema = match schema.get("object").and_then(|o| o.as_str()) {
+        Some("database") => schema.get("properties"),
+        _ => Some(&schema),
+    }
+    .and_then(|p| p.as_object())
+    .ok_or("Schema must be a database object or its properties map")?;
+    let properties = match payload.get("properties") {
+        None => Map::new(),
+        Some(Value::Object(properties)) => properties.clone(),
+        Some(_) => return Err("Payload properties must be an object".to_string()),
+    };
+    
+    let mut errors = Vec::new();
+    for (key, value) in &properties {
+        let Some((name, property)) = find_property(schema, key) else {
+            let message = match closest(key, schema.keys()) {
+                Some(name) => format!("No property named \"{}\"; did you mean \"{}\"?", key, name),
+                None => format!("No property named \"{}\"", key),
+            };
+            errors.push(error(key, WriteErrorKind::UnknownProperty, message));
+            continue;
+        };
+        let expected = property.get("type").and_then(|t| t.as_str()).unwrap_or("unknown");
+        if READ_ONLY_TYPES.contains(&expected) {
+            let message = format!("\"{}\" is a {} property, which Notion computes; leave it out", name, expected);
+            errors.push(error(key, WriteErrorKind::ReadOnly, message));
+            continue;
+        }
+        errors.extend(check_value(key, expected, property, value));
+    }
+    
+    let result = WriteValidation {
+        valid: errors.is_empty(),
+        checked: properties.len(),
+        errors,
+    };
+    serde_json::to_string(&result).map_err(|e| format!("JSON serialize error: {}", e))
+}
+
+/// A schema property by name, or by its ID
+fn find_property<'a>(schema: &'a Map<String, Value>, key: &str) -> Option<(&'a str, &'a Value)> {
+    schema.get_key_value(key).or_else(|| {
+        schema.iter().find(|(_, p)| p.get("id").and_then(|id| id.as_str()) == Some(key))
+    })
+    .map(|(name, property)| (name.as_str(), property))
+}
+
+fn check_value(key: &str, expected: &str, property: &Value, value: &Value) -> Vec<WriteError> {
+    // A value is {"<type>": ...}, optionally with a matching "type"
+    let given = value
+        .get("type")
+        .and_then(|t| t.as_str())
+        .or_else(|| {
+            value.as_object()?.keys().map(String::as_str).find(|k| WRITABLE_TYPES.contains(k) || READ_ONLY_TYPES.contains(k))
+        });
+    let Some(given) = given else {
+        let message = format!("Expected {{\"{}\": ...}} for a {} property", expected, expected);
+        return vec![error(key, WriteErrorKind::WrongType, message)];
+    };
+    if given != expected {
+        let message = format!("\"{}\" is a {} property, but the value is written as {}", key, expected, given);
+        return vec![error(key, WriteErrorKind::WrongType, message)];
+    }
+    let inner = value.get(expected).unwrap_or(&Value::Null);
+    
+    let shape_ok = match expected {
+        "title" | "rich_text" => inner.as_array().is_some_and(|items| items.iter().all(|i| i.is_object())),
+        "number" => inner.is_number() || inner.is_null(),
+        "checkbox" => inner.is_boolean(),
+        "url" | "email" | "phone_number" => inner.is_string() || inner.is_null(),
+        "date" => inner.is_null() || inner.get("start").is_some_and(|s| s.is_string()),
+        "select" => inner.is_null() || is_option(inner),
+        "status" => is_option(inner),
+        "multi_select" => inner.as_array().is_some_and(|items| items.iter().all(is_option)),
+        "relation" | "people" => inner.as_array().is_some_and(|items| items.iter().all(|i| i.get("id").is_some_and(|id| id.is_string()))),
+        "files" => inner.is_array(),
+        _ => true,
+    };
+    if !shape_ok {
+        let message = format!("Invalid {} value for \"{}\": expected {}", expected, key, expected_shape(expected));
+        return vec![error(key, WriteErrorKind::InvalidValue, message)];
+    }
+    
+    // Options have to exist already
+    let chosen: Vec<&Value> = match expected {
+        "select" | "status" if !inner.is_null() => vec![inner],
+        "multi_select" => inner.as_array().map(|items| items.iter().collect()).unwrap_or_default(),
+        _ => return Vec::new(),
+    };
+    let options: Vec<&Value> = property
+        .get(expected)
+        .and_then(|config| config.get("options"))
+        .and_then(|options| options.as_array())
+        .map(|options| options.iter().collect())
+        .unwrap_or_default();
+    let option_names = options.iter().filter_map(|o| o.get("name").and_then(|n| n.as_str()));
+    let option_names: Vec<&str> = option_names.collect();
+    
+    chosen
+        .into_iter()
+        .filter(|choice| {
+            !options.iter().any(|option| {
+                ["id", "name"].iter().any(|field| {
+                    choice.get(field).is_some_and(|v| option.get(field) == Some(v))
+                })
+            })
+        })
+        .map(|choice| {
+            let label = choice.get("name").or_else(|| choice.get("id")).and_then(|v| v.as_str()).unwrap_or("");
+            let message = match closest(label, option_names.iter().copied()) {
+                Some(option) => format!("\"{}\" has no option \"{}\"; did you mean \"{}\"?", key, label, option),
+                None => format!("\"{}\" has no option \"{}\" (options: {})", key, label, option_names.join(", ")),
+            };
+            error(key, WriteErrorKind::UnknownOption, message)
+        })
+        .collect()
+}
+
+/// `{name}` or `{id}`
+fn is_option(value: &Value) -> bool {
+    value.get("name").is_some_and(|n| n.is_string()) || value.get("id").is_some_and(|id| id.is_string())
+}
+
+fn expected_shape(property_type: &str) -> &'static str {
+    match property_type {
+        "title" | "rich_text" => "an array of rich text objects",
+        "number" => "a number or null",
+        "checkbox" => "true or false",
+        "url" | "email" | "phone_number" => "a string or null",
+        "date" => "{start, end?} or null",
+        "select" => "{name} or {id}, or null",
+        "status" => "{name} or {id}",
+        "multi_select" => "an array of {name} or {id}",
+        "relation" | "people" => "an array of {id}",
+        "files" => "an array of file objects",
+        _ => "a valid value",
+    }
+}
+
+fn error(property: &str, kind: WriteErrorKind, message: String) -> WriteError {
+    WriteError { property: property.to_string(), kind, message }
+}
+
+/// The candidate closest to `name`: the same ignoring case and spacing, or
+/// within a third of its length in edits
+fn closest<'a, S: AsRef<str> + ?Sized + 'a>(name: &str, candidates: impl Iterator<Item = &'a S>) -> Option<&'a str> {
+    let normalize = |s: &str| s.to_lowercase().split_whitespace().collect::<String>();
+    let target = normalize(name);
+    let limit = (target.chars().count() / 3).max(1);
+    candidates
+        .map(|c| c.as_ref())
+        .map(|c| (c, edit_distance(&target, &normalize(c))))
+        .filter(|(_, distance)| *distance <= limit)
+        .min_by_key(|(_, distance)| *distance)
+        .map(|(c, _)| c)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous + usize::from(ca != *cb);
+            previous = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(previous + 1);
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_validate_write_reports_every_problem() {
+        let schema = r#"{
+            "object": "database",
+            "properties": {
+                "Name": { "id": "title", "type": "title", "title": {} },
+                "Status": { "id": "s%3Ax", "type": "status", "status": { "options": [
+                    { "id": "1", "name": "Not started" }, { "id": "2", "name": "Done" }
+                ] } },
+                "Tags": { "id": "t%3Ay", "type": "multi_select", "multi_select": { "options": [
+                    { "id": "3", "name": "bug" }, { "id": "4", "name": "feature" }
+                ] } },
+                "Estimate": { "id": "e%3Az", "type": "number", "number": {} },
+                "Days left": { "id": "d%3Aw", "type": "formula", "formula": {} }
+            }
+        }"#;
+        
+        let valid = r#"{
+            "parent": { "database_id": "abc" },
+            "properties": {
+                "Name": { "title": [{ "text": { "content": "Fix login" } }] },
+                "s%3Ax": { "status": { "name": "Done" } },
+                "Tags": { "multi_select": [{ "name": "bug" }, { "id": "4" }] },
+                "Estimate": { "type": "number", "number": null }
+            }
+        }"#;
+        let result: WriteValidation = serde_json::from_str(&validate_write_impl(valid, schema).unwrap()).unwrap();
+        assert!(result.valid, "{:?}", result.errors);
+        assert_eq!(result.checked, 4);
+        
+        let invalid = r#"{
+            "properties": {
+                "status": { "select": { "name": "Done" } },
+                "Status": { "select": { "name": "Done" } },
+                "Tags": { "multi_select": [{ "name": "bugs" }, { "name": "chore" }] },
+                "Estimate": { "number": "3" },
+                "Days left": { "formula": { "number": 2 } },
+                "Owner": { "people": [] }
+            }
+        }"#;
+        let result: WriteValidation = serde_json::from_str(&validate_write_impl(invalid, schema).unwrap()).unwrap();
+        assert!(!result.valid);
+        let kinds: Vec<(&str, WriteErrorKind)> = result.errors.iter().map(|e| (e.property.as_str(), e.kind)).collect();
+        assert_eq!(kinds, vec![
+            ("Days left", WriteErrorKind::ReadOnly),
+            ("Estimate", WriteErrorKind::InvalidValue),
+            ("Owner", WriteErrorKind::UnknownProperty),
+            ("Status", WriteErrorKind::WrongType),
+            ("Tags", WriteErrorKind::UnknownOption),
+            ("Tags", WriteErrorKind::UnknownOption),
+            ("status", WriteErrorKind::UnknownProperty),
+        ]);
+        assert!(result.errors[4].message.contains("did you mean \"bug\""));
+        assert!(result.errors[5].message.contains("options: bug, feature"));
+        assert!(result.errors[6].message.contains("did you mean \"Status\""));
+        
+        // A bare properties map works as the schema too
+        let properties = r#"{ "Done": { "id": "x", "type": "checkbox", "checkbox": {} } }"#;
+        let payload = r#"{ "properties": { "Done": { "checkbox": "yes" } } }"#;
+        let result: WriteValidation = serde_json::from_str(&validate_write_impl(payload, properties).unwrap()).unwrap();
+        assert_eq!(result.errors[0].kind, WriteErrorKind::InvalidValue);
+        assert!(validate_write_impl("{}", "[]").is_err());
+    }
+}
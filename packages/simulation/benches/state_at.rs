@@ -0,0 +1,32 @@
+//! State generation per frame
+//!
+//! `cold` generates a new minute each iteration, the cost of the first frame
+//! in a minute; `cached` asks for the same minute again, the cost of every
+//! other frame. Both should stay well under the 2ms `stateAt` budget on
+//! Workers.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use simulation::Simulation;
+
+/// Tuesday 2024-01-23, 10:00 CST
+const MORNING: i64 = 1706025600000;
+
+fn state_at(c: &mut Criterion) {
+    for scenario in ["dental", "devops/agents"] {
+        let sim = Simulation::new(4101, scenario);
+        
+        let mut minute = 0;
+        c.bench_function(&format!("{scenario}/cold"), |b| b.iter(|| {
+            minute = (minute + 1) % 1440;
+            black_box(sim.state(MORNING + minute * 60_000))
+        }));
+        
+        sim.state(MORNING);
+        c.bench_function(&format!("{scenario}/cached"), |b| b.iter(|| {
+            black_box(sim.state(black_box(MORNING + 1_000)))
+        }));
+    }
+}
+
+criterion_group!(benches, state_at);
+criterion_main!(benches);
@@ -0,0 +1,475 @@
+//! Agent Activity Scenario (devops/agents)
+//!
+//! Simulates a multi-agent engineering org, for demoing Loom itself.
+//! Tasks are filed through the working day, claimed by agents, and either
+//! completed or failed, accruing cost as they run.
+//!
+//! Unlike the dental scenario, the whole day's schedule is generated up
+//! front from the day seed and then evaluated at the requested time, so a
+//! task stays the same task as it moves ready → claimed → done.
+//!
+//! The schedule is the expensive part, so the last one generated is kept
+//! and each call only projects it to the requested minute.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use serde_json::json;
+
+use crate::{Rng, SimBeat, SimItem, SimLogEntry, SimMetrics, SimState};
+use crate::scenario::{Outage, Scenario, SimOptions, SimTargets, SimTime};
+
+/// Agents and their cost per working minute (USD)
+const AGENTS: &[(&str, f64)] = &[
+    ("claude", 0.045),
+    ("codex", 0.030),
+    ("gemini", 0.020),
+    ("local-llama", 0.002),
+];
+
+/// Task templates by kind
+const TASKS: &[(&str, &[&str])] = &[
+    ("feature", &[
+        "Add rate limiting to public API",
+        "Support CSV export on reports page",
+        "Add dark mode toggle to settings",
+        "Implement webhook retries",
+        "Add search to audit log",
+        "Paginate the admin user list",
+    ]),
+    ("bugfix", &[
+        "Fix race in session refresh",
+        "Fix timezone drift in scheduler",
+        "Handle empty cart on checkout",
+        "Fix flaky login redirect",
+        "Stop double-sending invite emails",
+    ]),
+    ("test", &[
+        "Cover billing edge cases",
+        "Add integration tests for sync worker",
+        "Snapshot tests for email templates",
+    ]),
+    ("refactor", &[
+        "Extract shared date helpers",
+        "Split monolithic API router",
+        "Remove deprecated feature flags",
+    ]),
+    ("docs", &[
+        "Document deploy runbook",
+        "Update API reference for v2",
+    ]),
+];
+
+/// Why tasks fail (becomes the human's decision)
+const FAILURES: &[&str] = &[
+    "tests failing after change",
+    "merge conflict with main",
+    "ambiguous acceptance criteria",
+    "hit token budget",
+];
+
+/// Chance a finished task failed instead of completing
+const FAILURE_RATE: f64 = 0.08;
+
+/// Tasks filed on a typical weekday (baseline for `tasksPerDay`)
+const TYPICAL_TASKS: f64 = 55.0;
+
+/// Longest a task waits to be claimed (unless `maxWaitMinutes` is set)
+const MAX_CLAIM_WAIT: u32 = 30;
+
+/// Why work in flight fails when an outage starts
+const OUTAGE_FAILURE: &str = "agent pool unreachable";
+
+/// A task's lifecycle through the day (minutes since local midnight)
+struct SimTask {
+    id: String,
+    title: &'static str,
+    kind: &'static str,
+    agent: &'static str,
+    rate: f64,
+    created: u32,
+    claimed: u32,
+    finished: u32,
+    failure: Option<&'static str>,
+}
+
+/// A task's state at a point in time
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum TaskStatus {
+    Ready,
+    Claimed,
+    Done,
+    Failed,
+}
+
+impl TaskStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            TaskStatus::Ready => "ready",
+            TaskStatus::Claimed => "claimed",
+            TaskStatus::Done => "done",
+            TaskStatus::Failed => "failed",
+        }
+    }
+}
+
+impl SimTask {
+    fn status_at(&self, minute: u32) -> TaskStatus {
+        if minute < self.claimed {
+            TaskStatus::Ready
+        } else if minute < self.finished {
+            TaskStatus::Claimed
+        } else if self.failure.is_some() {
+            TaskStatus::Failed
+        } else {
+            TaskStatus::Done
+        }
+    }
+    
+    /// Cost accrued by `minute` (agents bill while working)
+    fn cost_at(&self, minute: u32) -> f64 {
+        let worked = minute.clamp(self.claimed, self.finished) - self.claimed;
+        worked as f64 * self.rate
+    }
+}
+
+pub struct AgentsScenario;
+
+impl Scenario for AgentsScenario {
+    // Engineering orgs mostly work in English; task text isn't localized
+    fn generate_with(seed: u64, timestamp_ms: i64, options: &SimOptions) -> SimState {
+        let time = SimTime::from_timestamp_ms(timestamp_ms);
+        let now = time.minute_of_day;
+        let schedule = day_schedule(seed, &time, options);
+        let tasks = filed_by(&schedule, now);
+        
+        SimState {
+            items: generate_items(tasks, now),
+            activity_log: generate_activity_log(tasks, now),
+            metrics: generate_metrics(tasks, now),
+            time_of_day: time.time_of_day.as_str().to_string(),
+            simulation_time: timestamp_ms,
+        }
+    }
+    
+    fn narrate_with(seed: u64, timestamp_ms: i64, options: &SimOptions) -> Vec<SimBeat> {
+        let time = SimTime::from_timestamp_ms(timestamp_ms);
+        let schedule = day_schedule(seed, &time, options);
+        narrate(filed_by(&schedule, time.minute_of_day), time.minute_of_day)
+    }
+}
+
+/// A day's schedule and what it was generated from
+struct CachedSchedule {
+    seed: u64,
+    day: i64,
+    targets: SimTargets,
+    outage: Option<Outage>,
+    tasks: Rc<[SimTask]>,
+}
+
+thread_local! {
+    /// The last schedule generated. Callers ask for the same day frame after
+    /// frame, so one entry is enough and memory stays at a single day.
+    static SCHEDULE: RefCell<Option<CachedSchedule>> = const { RefCell::new(None) };
+}
+
+/// The whole day's tasks, sorted by when they're filed
+fn day_schedule(seed: u64, time: &SimTime, options: &SimOptions) -> Rc<[SimTask]> {
+    SCHEDULE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        if let Some(cached) = cache.as_ref() {
+            if cached.seed == seed && cached.day == time.day
+                && cached.targets == options.targets && cached.outage == options.outage
+            {
+                return Rc::clone(&cached.tasks);
+            }
+        }
+        
+        // Same schedule all day; only the evaluation time moves
+        let mut rng = Rng::seeded(seed, time.day as u64);
+        let mut tasks = generate_schedule(&mut rng, time, &options.targets);
+        if let Some(outage) = options.outage {
+            apply_outage(&mut tasks, outage);
+        }
+        let tasks: Rc<[SimTask]> = tasks.into();
+        *cache = Some(CachedSchedule {
+            seed,
+            day: time.day,
+            targets: options.targets.clone(),
+            outage: options.outage,
+            tasks: Rc::clone(&tasks),
+        });
+        tasks
+    })
+}
+
+/// Tasks filed so far today; the outage only moves claims, so the schedule
+/// stays sorted by filing time
+fn filed_by(schedule: &[SimTask], minute: u32) -> &[SimTask] {
+    &schedule[..schedule.partition_point(|t| t.created <= minute)]
+}
+
+/// Generate the day's tasks, filed mostly during working hours
+fn generate_schedule(rng: &mut Rng, time: &SimTime, targets: &SimTargets) -> Vec<SimTask> {
+    let count = if time.is_weekday() {
+        40 + rng.next_range(30)
+    } else {
+        8 + rng.next_range(8)
+    };
+    let count = (count as f64 * SimTargets::scale(targets.tasks_per_day, TYPICAL_TASKS)).round() as u64;
+    let max_wait = targets.max_wait_minutes.unwrap_or(MAX_CLAIM_WAIT).max(1);
+    
+    let mut tasks = Vec::new();
+    for _ in 0..count {
+        let (kind, titles) = TASKS[rng.next_range(TASKS.len() as u64) as usize];
+        let (agent, rate) = AGENTS[rng.next_range(AGENTS.len() as u64) as usize];
+        
+        // Filed between 7am and 7pm, weighted toward the morning
+        let spread = rng.next_f64().powf(1.3);
+        let created = 420 + (spread * 720.0) as u32;
+        let claimed = created + 1 + rng.next_range(max_wait as u64) as u32;
+        let finished = claimed + 5 + rng.next_range(85) as u32;
+        let failure = if rng.next_f64() < FAILURE_RATE {
+            rng.pick(FAILURES).copied()
+        } else {
+            None
+        };
+        
+        tasks.push(SimTask {
+            id: format!("lm-{:04x}", rng.next_u32() & 0xffff),
+            title: titles[rng.next_range(titles.len() as u64) as usize],
+            kind,
+            agent,
+            rate,
+            created,
+            claimed,
+            finished,
+            failure,
+        });
+    }
+    
+    tasks.sort_by_key(|t| t.created);
+    tasks
+}
+
+/// Fail work in flight when the outage starts and hold claims until it
+/// ends. Applied after generation so the RNG, and the rest of the day,
+/// match the same seed without an outage.
+fn apply_outage(tasks: &mut [SimTask], outage: Outage) {
+    for task in tasks.iter_mut() {
+        if task.claimed < outage.start_minute && outage.contains(task.finished) {
+            task.finished = outage.start_minute;
+            task.failure = Some(OUTAGE_FAILURE);
+        } else if outage.contains(task.claimed) {
+            // Queued work is picked up in order once the pool is back
+            let delay = outage.end_minute - task.claimed + (task.claimed - outage.start_minute) / 4;
+            task.claimed += delay;
+            task.finished += delay;
+        }
+    }
+}
+
+fn generate_items(tasks: &[SimTask], now: u32) -> Vec<SimItem> {
+    let mut items: Vec<SimItem> = tasks.iter()
+        .map(|task| {
+            let status = task.status_at(now);
+            let cost = task.cost_at(now);
+            
+            let (body, item_status, score, since) = match status {
+                TaskStatus::Ready => (
+                    format!("Waiting for an agent ({} min in queue).", now - task.created),
+                    "snoozed",
+                    0.40,
+                    task.created,
+                ),
+                TaskStatus::Claimed => (
+                    format!("{} working for {} min - ${:.2} so far.", task.agent, now - task.claimed, cost),
+                    "snoozed",
+                    0.55,
+                    task.claimed,
+                ),
+                TaskStatus::Done => (
+                    format!("{} finished in {} min for ${:.2}.", task.agent, task.finished - task.claimed, cost),
+                    "approved",
+                    0.30,
+                    task.finished,
+                ),
+                TaskStatus::Failed => (
+                    format!("{} stopped: {}. Retry or reassign?", task.agent, task.failure.unwrap_or("unknown error")),
+                    "inbox",
+                    0.90,
+                    task.finished,
+                ),
+            };
+            
+            SimItem {
+                id: task.id.clone(),
+                title: task.title.to_string(),
+                body,
+                source_type: "loom".to_string(),
+                category: task.kind.to_string(),
+                score,
+                status: item_status.to_string(),
+                minutes_ago: (now - since) as i64,
+                metadata: Some(json!({
+                    "loomStatus": status.as_str(),
+                    "agent": task.agent,
+                    "costUsd": (cost * 100.0).round() / 100.0,
+                })),
+            }
+        })
+        .collect();
+    
+    items.sort_by(|a, b| {
+        b.score.partial_cmp(&a.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(a.minutes_ago.cmp(&b.minutes_ago))
+    });
+    items
+}
+
+fn generate_activity_log(tasks: &[SimTask], now: u32) -> Vec<SimLogEntry> {
+    let mut log = Vec::new();
+    
+    for task in tasks {
+        log.push(SimLogEntry {
+            minutes_ago: (now - task.created) as i64,
+            text: format!("Filed {}: {}", task.id, task.title),
+            entry_type: "create".to_string(),
+        });
+        if now >= task.claimed {
+            log.push(SimLogEntry {
+                minutes_ago: (now - task.claimed) as i64,
+                text: format!("{} claimed {}", task.agent, task.id),
+                entry_type: "claim".to_string(),
+            });
+        }
+        if now >= task.finished {
+            let cost = task.cost_at(now);
+            let (text, entry_type) = match task.failure {
+                Some(reason) => (format!("{} failed {}: {}", task.agent, task.id, reason), "fail"),
+                None => (format!("{} completed {} (${:.2})", task.agent, task.id, cost), "complete"),
+            };
+            log.push(SimLogEntry {
+                minutes_ago: (now - task.finished) as i64,
+                text,
+                entry_type: entry_type.to_string(),
+            });
+        }
+    }
+    
+    log.sort_by_key(|e| e.minutes_ago);
+    log.truncate(60);
+    log
+}
+
+fn generate_metrics(tasks: &[SimTask], now: u32) -> SimMetrics {
+    let count = |status: TaskStatus| tasks.iter().filter(|t| t.status_at(now) == status).count() as u32;
+    let (ready, claimed, done, failed) = (
+        count(TaskStatus::Ready),
+        count(TaskStatus::Claimed),
+        count(TaskStatus::Done),
+        count(TaskStatus::Failed),
+    );
+    
+    let cost: f64 = tasks.iter().map(|t| t.cost_at(now)).sum();
+    let active_agents = AGENTS.iter()
+        .filter(|(agent, _)| tasks.iter().any(|t| t.agent == *agent && t.status_at(now) == TaskStatus::Claimed))
+        .count();
+    let total_wait: u32 = tasks.iter()
+        .filter(|t| now >= t.claimed)
+        .map(|t| t.claimed - t.created)
+        .sum();
+    let avg_wait = total_wait.checked_div(claimed + done + failed).unwrap_or(0);
+    let success_rate = (done * 100).checked_div(done + failed).unwrap_or(100);
+    
+    SimMetrics {
+        waiting_room: ready,
+        avg_wait_minutes: avg_wait,
+        on_time_rate: success_rate,
+        no_show_rate: 100 - success_rate,
+        appointments_total: tasks.len() as u32,
+        appointments_completed: done,
+        automations_today: done,
+        calls_processed: 0,
+        confirmations_sent: 0,
+        eligibility_checked: 0,
+        recalls_contacted: 0,
+        agents_completed: done,
+        agents_awaiting: claimed,
+        human_decisions: failed,
+        extra: Some(json!({
+            "tasksReady": ready,
+            "tasksClaimed": claimed,
+            "tasksDone": done,
+            "tasksFailed": failed,
+            "activeAgents": active_agents,
+            "costTodayUsd": (cost * 100.0).round() / 100.0,
+        })),
+    }
+}
+
+/// Story beats: throughput, spend, the failure needing a human, then activity
+fn narrate(tasks: &[SimTask], now: u32) -> Vec<SimBeat> {
+    let beat = |minutes_ago: u32, text: String, beat_type: &str| SimBeat {
+        minutes_ago: minutes_ago as i64,
+        text,
+        beat_type: beat_type.to_string(),
+    };
+    let in_status = |status: TaskStatus| tasks.iter().filter(move |t| t.status_at(now) == status);
+    
+    let mut beats = vec![beat(0, format!(
+        "{} tasks shipped today, {} in flight, {} waiting for an agent.",
+        in_status(TaskStatus::Done).count(),
+        in_status(TaskStatus::Claimed).count(),
+        in_status(TaskStatus::Ready).count(),
+    ), "overview")];
+    
+    let busiest = AGENTS.iter()
+        .map(|(agent, _)| (agent, tasks.iter().filter(|t| t.agent == *agent && now >= t.claimed).count()))
+        .max_by_key(|(_, n)| *n)
+        .filter(|(_, n)| *n > 0);
+    if let Some((agent, n)) = busiest {
+        let cost: f64 = tasks.iter().map(|t| t.cost_at(now)).sum();
+        beats.push(beat(0, format!("Agents have spent ${:.2} so far; {} is the busiest with {} tasks.", cost, agent, n), "automation"));
+    }
+    
+    if let Some(task) = in_status(TaskStatus::Failed).max_by_key(|t| t.finished) {
+        beats.push(beat(now - task.finished, format!(
+            "{} hit \"{}\" on {} - a human needs to retry or reassign it.",
+            task.agent, task.failure.unwrap_or("an error"), task.title,
+        ), "attention"));
+    }
+    
+    beats.extend(generate_activity_log(tasks, now).into_iter().map(|e| SimBeat {
+        minutes_ago: e.minutes_ago,
+        text: e.text,
+        beat_type: "activity".to_string(),
+    }));
+    beats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_tasks_progress_through_the_day() {
+        // 10am and 11:30pm CST on a Monday
+        let morning = AgentsScenario::generate(7, 1705939200000);
+        let evening = AgentsScenario::generate(7, 1705987800000);
+        
+        let extra = |s: &SimState, key: &str| s.metrics.extra.as_ref().unwrap()[key].as_f64().unwrap();
+        assert!(extra(&evening, "tasksDone") > extra(&morning, "tasksDone"));
+        assert!(extra(&evening, "costTodayUsd") >= extra(&morning, "costTodayUsd"));
+        assert_eq!(extra(&evening, "tasksReady") + extra(&evening, "tasksClaimed"), 0.0);
+        
+        // A task keeps its identity as it moves through statuses
+        let id = &morning.items.iter().find(|i| i.status == "snoozed").unwrap().id;
+        assert!(evening.items.iter().any(|i| &i.id == id));
+        
+        let again = AgentsScenario::generate(7, 1705939200000);
+        assert_eq!(morning.items[0].id, again.items[0].id);
+    }
+}
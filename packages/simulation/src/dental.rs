@@ -8,29 +8,14 @@
 //! - Recall → Contact → Book → Visit
 //! - Review → Response → Posted
 
-use crate::{Rng, SimItem, SimLogEntry, SimMetrics, SimState};
-use crate::scenario::{Scenario, SimTime, TimeOfDay};
+use std::fmt::Display;
 
-/// Patient name pools for realistic generation
-const FIRST_NAMES: &[&str] = &[
-    "James", "Sarah", "Michael", "Jennifer", "David", "Emily", "Robert", "Maria",
-    "William", "Lisa", "Carlos", "Amanda", "Kevin", "Jessica", "Thomas", "Ashley",
-    "Daniel", "Michelle", "Brian", "Nicole", "Steven", "Rachel", "Andrew", "Laura",
-];
-
-const LAST_NAMES: &[&str] = &[
-    "Thompson", "Williams", "Johnson", "Morrison", "Chen", "Garcia", "Martinez",
-    "Smith", "Brown", "Davis", "Wilson", "Anderson", "Taylor", "Thomas", "Moore",
-    "Jackson", "White", "Harris", "Martin", "Lee", "Walker", "Hall", "Young",
-];
+use crate::{Rng, SimBeat, SimItem, SimLogEntry, SimMetrics, SimState};
+use crate::dental_text::{self, Text};
+use crate::locale::{fill, Locale};
+use crate::scenario::{Scenario, SimOptions, SimTargets, SimTime, TimeOfDay};
 
-/// Insurance payers
-const PAYERS: &[&str] = &[
-    "Delta Dental", "Aetna", "Cigna", "MetLife", "Guardian", "United Healthcare",
-    "Blue Cross", "Humana", "Principal",
-];
-
-/// Dental procedure types
+/// Dental procedure types (English names key the translations)
 const PROCEDURES: &[(&str, u32)] = &[
     ("Cleaning", 150),
     ("Crown", 1200),
@@ -44,6 +29,13 @@ const PROCEDURES: &[(&str, u32)] = &[
     ("Bridge", 2500),
 ];
 
+/// Default baselines that `SimTargets` rescale
+const TYPICAL_APPOINTMENTS: f64 = 32.0; // Typical weekday
+const TYPICAL_AUTOMATIONS: f64 = 825.0; // By end of day
+const TYPICAL_HUMAN_DECISIONS: f64 = 18.5; // By end of day
+const TYPICAL_ON_TIME_RATE: u32 = 97;
+const TYPICAL_NO_SHOW_RATE: u32 = 2;
+
 /// Patient journey stages - events flow through these logically
 #[derive(Clone, Copy)]
 enum JourneyStage {
@@ -72,6 +64,7 @@ enum JourneyStage {
 
 /// A patient journey tracks one patient through their care cycle
 struct PatientJourney {
+    locale: Locale,
     first_name: &'static str,
     last_name: &'static str,
     payer: &'static str,
@@ -82,11 +75,13 @@ struct PatientJourney {
 }
 
 impl PatientJourney {
-    fn new(rng: &mut Rng, stage: JourneyStage) -> Option<Self> {
+    fn new(rng: &mut Rng, locale: Locale, stage: JourneyStage) -> Option<Self> {
+        let names = dental_text::names(locale);
         Some(Self {
-            first_name: *rng.pick(FIRST_NAMES)?,
-            last_name: *rng.pick(LAST_NAMES)?,
-            payer: *rng.pick(PAYERS)?,
+            locale,
+            first_name: *rng.pick(names.first)?,
+            last_name: *rng.pick(names.last)?,
+            payer: *rng.pick(names.payers)?,
             procedure: rng.pick(PROCEDURES).map(|(p, _)| *p)?,
             amount: rng.pick(PROCEDURES).map(|(_, a)| *a)?,
             stage,
@@ -95,23 +90,38 @@ impl PatientJourney {
     }
     
     fn full_name(&self) -> String {
-        format!("{} {}", self.first_name, self.last_name)
+        self.locale.full_name(self.first_name, self.last_name)
+    }
+    
+    /// Render a template with this journey's name, payer, procedure, and amount
+    /// (`args` are filled first, so they can override `{amount}`)
+    fn say(&self, key: Text, args: &[(&str, &dyn Display)]) -> String {
+        let procedure = dental_text::procedure(self.locale, self.procedure);
+        let text = fill(dental_text::text(self.locale, key), args);
+        fill(&text, &[
+            ("name", &self.full_name()),
+            ("last", &self.last_name),
+            ("payer", &self.payer),
+            ("amount", &self.locale.money(self.amount)),
+            ("procedure", &self.locale.in_sentence(procedure)),
+            ("Procedure", &procedure),
+        ])
     }
 }
 
 pub struct DentalScenario;
 
 impl Scenario for DentalScenario {
-    fn generate(seed: u64, timestamp_ms: i64) -> SimState {
+    fn generate_with(seed: u64, timestamp_ms: i64, options: &SimOptions) -> SimState {
         let time = SimTime::from_timestamp_ms(timestamp_ms);
         let mut rng = Rng::seeded(seed, (timestamp_ms / 60000) as u64); // Changes every minute
         
         // Generate patient journeys at various stages
-        let journeys = generate_journeys(&mut rng, &time);
+        let journeys = generate_journeys(&mut rng, &time, options);
         
-        let metrics = generate_metrics(&mut rng, &time);
-        let items = generate_items_from_journeys(&mut rng, &time, &journeys);
-        let activity_log = generate_activity_log_from_journeys(&mut rng, &time, &journeys);
+        let metrics = generate_metrics(&mut rng, &time, &options.targets);
+        let items = generate_items_from_journeys(&mut rng, &time, &journeys, options);
+        let activity_log = generate_activity_log_from_journeys(&mut rng, &time, &journeys, options.locale);
         
         SimState {
             items,
@@ -121,96 +131,103 @@ impl Scenario for DentalScenario {
             simulation_time: timestamp_ms,
         }
     }
+    
+    fn narrate_with(seed: u64, timestamp_ms: i64, options: &SimOptions) -> Vec<SimBeat> {
+        let state = Self::generate_with(seed, timestamp_ms, options);
+        narrate(seed, &SimTime::from_timestamp_ms(timestamp_ms), &state, options.locale)
+    }
 }
 
 /// Generate a realistic set of patient journeys at various stages
-fn generate_journeys(rng: &mut Rng, time: &SimTime) -> Vec<PatientJourney> {
+fn generate_journeys(rng: &mut Rng, time: &SimTime, options: &SimOptions) -> Vec<PatientJourney> {
+    let locale = options.locale;
+    let volume = item_volume(&options.targets);
     let mut journeys = Vec::new();
     
     // New patient calls (today) - become inbox items needing callback
-    for _ in 0..(3 + rng.next_range(4)) {
-        if let Some(j) = PatientJourney::new(rng, JourneyStage::NewCall) {
+    for _ in 0..scaled(3 + rng.next_range(4), volume) {
+        if let Some(j) = PatientJourney::new(rng, locale, JourneyStage::NewCall) {
             journeys.push(j);
         }
     }
     
     // Recently booked (1-2 days ago) - need confirmation
-    for _ in 0..(4 + rng.next_range(5)) {
-        if let Some(j) = PatientJourney::new(rng, JourneyStage::Booked) {
+    for _ in 0..scaled(4 + rng.next_range(5), volume) {
+        if let Some(j) = PatientJourney::new(rng, locale, JourneyStage::Booked) {
             journeys.push(j);
         }
     }
     
     // Confirmed for today/tomorrow - in progress
-    for _ in 0..(6 + rng.next_range(8)) {
-        if let Some(j) = PatientJourney::new(rng, JourneyStage::Confirmed) {
+    for _ in 0..scaled(6 + rng.next_range(8), volume) {
+        if let Some(j) = PatientJourney::new(rng, locale, JourneyStage::Confirmed) {
             journeys.push(j);
         }
     }
     
     // Visited recently - treatment plans being created
-    for _ in 0..(5 + rng.next_range(6)) {
-        if let Some(j) = PatientJourney::new(rng, JourneyStage::Visited) {
+    for _ in 0..scaled(5 + rng.next_range(6), volume) {
+        if let Some(j) = PatientJourney::new(rng, locale, JourneyStage::Visited) {
             journeys.push(j);
         }
     }
     
     // Treatment plans ready - need approval
-    for _ in 0..(3 + rng.next_range(4)) {
-        if let Some(j) = PatientJourney::new(rng, JourneyStage::TreatmentPlan) {
+    for _ in 0..scaled(3 + rng.next_range(4), volume) {
+        if let Some(j) = PatientJourney::new(rng, locale, JourneyStage::TreatmentPlan) {
             journeys.push(j);
         }
     }
     
     // Insurance being checked
-    for _ in 0..(4 + rng.next_range(5)) {
-        if let Some(j) = PatientJourney::new(rng, JourneyStage::InsuranceCheck) {
+    for _ in 0..scaled(4 + rng.next_range(5), volume) {
+        if let Some(j) = PatientJourney::new(rng, locale, JourneyStage::InsuranceCheck) {
             journeys.push(j);
         }
     }
     
     // Claims filed - waiting
-    for _ in 0..(8 + rng.next_range(10)) {
-        if let Some(j) = PatientJourney::new(rng, JourneyStage::ClaimFiled) {
+    for _ in 0..scaled(8 + rng.next_range(10), volume) {
+        if let Some(j) = PatientJourney::new(rng, locale, JourneyStage::ClaimFiled) {
             journeys.push(j);
         }
     }
     
     // Claims pending (older) - some need appeals
-    for _ in 0..(5 + rng.next_range(8)) {
-        if let Some(j) = PatientJourney::new(rng, JourneyStage::ClaimPending) {
+    for _ in 0..scaled(5 + rng.next_range(8), volume) {
+        if let Some(j) = PatientJourney::new(rng, locale, JourneyStage::ClaimPending) {
             journeys.push(j);
         }
     }
     
     // Payments received today
-    for _ in 0..(4 + rng.next_range(6)) {
-        if let Some(j) = PatientJourney::new(rng, JourneyStage::PaymentReceived) {
+    for _ in 0..scaled(4 + rng.next_range(6), volume) {
+        if let Some(j) = PatientJourney::new(rng, locale, JourneyStage::PaymentReceived) {
             journeys.push(j);
         }
     }
     
     // Recall patients at various stages
-    for _ in 0..(10 + rng.next_range(15)) {
+    for _ in 0..scaled(10 + rng.next_range(15), volume) {
         let stage = match rng.next_range(4) {
             0 => JourneyStage::RecallDue,
             1 => JourneyStage::RecallContacted,
             2 => JourneyStage::RecallResponded,
             _ => JourneyStage::RecallBooked,
         };
-        if let Some(j) = PatientJourney::new(rng, stage) {
+        if let Some(j) = PatientJourney::new(rng, locale, stage) {
             journeys.push(j);
         }
     }
     
     // Reviews at various stages
-    for _ in 0..(2 + rng.next_range(4)) {
+    for _ in 0..scaled(2 + rng.next_range(4), volume) {
         let stage = match rng.next_range(3) {
             0 => JourneyStage::ReviewReceived,
             1 => JourneyStage::ResponseDrafted,
             _ => JourneyStage::ResponsePosted,
         };
-        if let Some(j) = PatientJourney::new(rng, stage) {
+        if let Some(j) = PatientJourney::new(rng, locale, stage) {
             journeys.push(j);
         }
     }
@@ -218,8 +235,18 @@ fn generate_journeys(rng: &mut Rng, time: &SimTime) -> Vec<PatientJourney> {
     journeys
 }
 
-fn generate_metrics(rng: &mut Rng, time: &SimTime) -> SimMetrics {
+/// Item volume follows appointment volume
+fn item_volume(targets: &SimTargets) -> f64 {
+    SimTargets::scale(targets.appointments_per_day, TYPICAL_APPOINTMENTS)
+}
+
+fn scaled(count: u64, factor: f64) -> u64 {
+    (count as f64 * factor).round() as u64
+}
+
+fn generate_metrics(rng: &mut Rng, time: &SimTime, targets: &SimTargets) -> SimMetrics {
     let progress = time.business_progress;
+    let volume = item_volume(targets);
     let is_busy = matches!(time.time_of_day, TimeOfDay::Morning | TimeOfDay::Afternoon);
     
     // Total appointments scales with day of week (busier midweek)
@@ -232,7 +259,7 @@ fn generate_metrics(rng: &mut Rng, time: &SimTime) -> SimMetrics {
         _ => 8,  // Weekend (emergency only)
     };
     
-    let appointments_total = base_appointments + rng.next_range(6) as u32;
+    let appointments_total = scaled(base_appointments + rng.next_range(6), volume) as u32;
     
     // Completed follows progress through the day
     let expected_completed = (appointments_total as f64 * progress) as u32;
@@ -261,6 +288,7 @@ fn generate_metrics(rng: &mut Rng, time: &SimTime) -> SimMetrics {
         },
         _ => 0, // Early morning/evening: no one waiting
     };
+    let waiting_room = scaled(waiting_room as u64, volume) as u32;
     
     // Wait time correlates with waiting room but stays low
     let avg_wait = match waiting_room {
@@ -269,25 +297,29 @@ fn generate_metrics(rng: &mut Rng, time: &SimTime) -> SimMetrics {
         2 => 2 + rng.next_range(3) as u32,  // 2-4 min
         _ => 3 + rng.next_range(4) as u32,  // 3-6 min (rare)
     };
+    let avg_wait = avg_wait.min(targets.max_wait_minutes.unwrap_or(u32::MAX));
     
     // On-time rate: 94-99% - excellent because of all the automation
     let on_time_base = if matches!(time.time_of_day, TimeOfDay::Afternoon) { 97 } else { 95 };
     let on_time_rate = on_time_base + rng.next_range(3) as u32;
+    let on_time_rate = SimTargets::shift_rate(on_time_rate, targets.on_time_rate, TYPICAL_ON_TIME_RATE);
     
     // No-show rate: very low 1-3% because of aggressive confirmation
     let no_show_rate = 1 + rng.next_range(3) as u32;
+    let no_show_rate = SimTargets::shift_rate(no_show_rate, targets.no_show_rate, TYPICAL_NO_SHOW_RATE);
     
     // FULL BLAST: Automations are hundreds per day
     // This is the "volume" that would overwhelm a human but the system handles calmly
     let automation_multiplier = (progress * 10.0).max(1.0);
     let base_automations = (80.0 * automation_multiplier) as u32; // 80-800 automations/day
-    let automations_today = base_automations + rng.next_range(50) as u32;
+    let automation_scale = SimTargets::scale(targets.automations_per_day, TYPICAL_AUTOMATIONS);
+    let automations_today = scaled(base_automations as u64 + rng.next_range(50), automation_scale) as u32;
     
     // High-volume breakdown of automations
     let calls_processed = (automations_today as f64 * 0.08) as u32 + rng.next_range(15) as u32; // 50-80 calls
     let confirmations_sent = appointments_total * 3 + rng.next_range(20) as u32; // 3x because reminder sequences
     let eligibility_checked = appointments_total + 20 + rng.next_range(30) as u32; // All appts + walk-ins + tomorrow
-    let recalls_contacted = (25.0 * automation_multiplier * 0.4) as u32 + rng.next_range(15) as u32; // 30-100 recalls
+    let recalls_contacted = scaled((25.0 * automation_multiplier * 0.4) as u64 + rng.next_range(15), automation_scale) as u32; // 30-100 recalls
     
     // Agents doing heavy reasoning work in the background
     let agents_completed = (progress * 45.0) as u32 + rng.next_range(12) as u32; // 40-55 agent tasks
@@ -296,6 +328,7 @@ fn generate_metrics(rng: &mut Rng, time: &SimTime) -> SimMetrics {
     // Human decisions stay LOW despite high volume - this is the calm
     // The ratio of automations:human is ~100:1
     let human_decisions = 5 + (progress * 12.0) as u32 + rng.next_range(4) as u32; // 5-20 decisions/day
+    let human_decisions = scaled(human_decisions as u64, SimTargets::scale(targets.human_decisions_per_day, TYPICAL_HUMAN_DECISIONS)) as u32;
     
     SimMetrics {
         waiting_room,
@@ -312,11 +345,12 @@ fn generate_metrics(rng: &mut Rng, time: &SimTime) -> SimMetrics {
         agents_completed,
         agents_awaiting,
         human_decisions,
+        extra: None,
     }
 }
 
 /// Generate items from patient journeys - creates logical flow
-fn generate_items_from_journeys(rng: &mut Rng, time: &SimTime, journeys: &[PatientJourney]) -> Vec<SimItem> {
+fn generate_items_from_journeys(rng: &mut Rng, time: &SimTime, journeys: &[PatientJourney], options: &SimOptions) -> Vec<SimItem> {
     let mut items = Vec::new();
     
     for journey in journeys {
@@ -326,9 +360,9 @@ fn generate_items_from_journeys(rng: &mut Rng, time: &SimTime, journeys: &[Patie
     }
     
     // Add some noise items (dismissed)
-    let noise_count = 8 + rng.next_range(12) as usize;
+    let noise_count = scaled(8 + rng.next_range(12), item_volume(&options.targets)) as usize;
     for i in 0..noise_count {
-        if let Some(item) = generate_noise_item(rng, i) {
+        if let Some(item) = generate_noise_item(rng, i, options.locale) {
             items.push(item);
         }
     }
@@ -341,17 +375,11 @@ fn generate_items_from_journeys(rng: &mut Rng, time: &SimTime, journeys: &[Patie
 
 /// Convert a patient journey stage to an item
 fn journey_to_item(rng: &mut Rng, time: &SimTime, journey: &PatientJourney) -> Option<SimItem> {
-    let name = journey.full_name();
-    let last = journey.last_name;
-    let payer = journey.payer;
-    let procedure = journey.procedure;
-    let amount = journey.amount;
-    
     let (title, body, source_type, category, score, status, minutes_ago) = match journey.stage {
         // === INBOX: Needs human attention ===
         JourneyStage::NewCall => (
-            format!("New patient worth calling back"),
-            format!("{} called about {}. Has PPO, scored {}/10.", name, procedure.to_lowercase(), 7 + rng.next_range(4)),
+            journey.say(Text::NewCallTitle, &[]),
+            journey.say(Text::NewCallBody, &[("score", &(7 + rng.next_range(4)))]),
             "phone",
             "call",
             0.80 + rng.next_f64() * 0.15,
@@ -359,8 +387,8 @@ fn journey_to_item(rng: &mut Rng, time: &SimTime, journey: &PatientJourney) -> O
             5 + rng.next_range(60) as i64,
         ),
         JourneyStage::TreatmentPlan => (
-            format!("{}'s treatment plan ready", last),
-            format!("${} {} - waiting for your review before presenting.", amount, procedure.to_lowercase()),
+            journey.say(Text::TreatmentPlanTitle, &[]),
+            journey.say(Text::TreatmentPlanBody, &[]),
             "pms",
             "treatment_plan",
             0.75 + rng.next_f64() * 0.15,
@@ -371,8 +399,8 @@ fn journey_to_item(rng: &mut Rng, time: &SimTime, journey: &PatientJourney) -> O
             // Some claims need appeals
             if rng.next_f64() > 0.6 {
                 (
-                    format!("Appeal ready for {}'s {}", last, procedure.to_lowercase()),
-                    format!("{} denied it. Drafted the letter - deadline in {} days.", payer, 3 + rng.next_range(7)),
+                    journey.say(Text::AppealTitle, &[]),
+                    journey.say(Text::AppealBody, &[("days", &(3 + rng.next_range(7)))]),
                     "claims",
                     "claim",
                     0.88 + rng.next_f64() * 0.10,
@@ -381,8 +409,8 @@ fn journey_to_item(rng: &mut Rng, time: &SimTime, journey: &PatientJourney) -> O
                 )
             } else {
                 (
-                    format!("{} claim pending", last),
-                    format!("${} {} submitted to {} - {} days ago.", amount, procedure.to_lowercase(), payer, 5 + rng.next_range(15)),
+                    journey.say(Text::ClaimPendingTitle, &[]),
+                    journey.say(Text::ClaimPendingBody, &[("days", &(5 + rng.next_range(15)))]),
                     "claims",
                     "claim",
                     0.30 + rng.next_f64() * 0.20,
@@ -394,9 +422,9 @@ fn journey_to_item(rng: &mut Rng, time: &SimTime, journey: &PatientJourney) -> O
         JourneyStage::ResponseDrafted => {
             let rating = 2 + rng.next_range(3) as u32;
             (
-                format!("Response ready for {}-star review", rating),
-                if rating <= 2 { "Drafted an apology. Review before posting?".to_string() } 
-                else { "Drafted a thank-you. Ready to post.".to_string() },
+                journey.say(Text::ResponseReadyTitle, &[("rating", &rating)]),
+                if rating <= 2 { journey.say(Text::ApologyBody, &[]) } 
+                else { journey.say(Text::ThankYouBody, &[]) },
                 "reviews",
                 "review",
                 if rating <= 2 { 0.85 + rng.next_f64() * 0.10 } else { 0.40 + rng.next_f64() * 0.15 },
@@ -405,8 +433,8 @@ fn journey_to_item(rng: &mut Rng, time: &SimTime, journey: &PatientJourney) -> O
             )
         },
         JourneyStage::RecallResponded => (
-            format!("{} wants to schedule", last),
-            format!("Responded to recall text. Was {} months overdue.", 6 + rng.next_range(18)),
+            journey.say(Text::RecallRespondedTitle, &[]),
+            journey.say(Text::RecallRespondedBody, &[("months", &(6 + rng.next_range(18)))]),
             "pms",
             "recall",
             0.70 + rng.next_f64() * 0.15,
@@ -416,8 +444,8 @@ fn journey_to_item(rng: &mut Rng, time: &SimTime, journey: &PatientJourney) -> O
         
         // === APPROVED: Completed successfully ===
         JourneyStage::Booked => (
-            format!("{} booked", last),
-            format!("{} scheduled for {}.", name, procedure.to_lowercase()),
+            journey.say(Text::BookedTitle, &[]),
+            journey.say(Text::BookedBody, &[]),
             "phone",
             "call",
             0.60 + rng.next_f64() * 0.20,
@@ -425,8 +453,8 @@ fn journey_to_item(rng: &mut Rng, time: &SimTime, journey: &PatientJourney) -> O
             60 + rng.next_range(240) as i64,
         ),
         JourneyStage::Confirmed => (
-            format!("{} confirmed", last),
-            format!("Replied to reminder - {} tomorrow.", procedure.to_lowercase()),
+            journey.say(Text::ConfirmedTitle, &[]),
+            journey.say(Text::ConfirmedBody, &[]),
             "pms",
             "appointment",
             0.50 + rng.next_f64() * 0.20,
@@ -434,8 +462,8 @@ fn journey_to_item(rng: &mut Rng, time: &SimTime, journey: &PatientJourney) -> O
             30 + rng.next_range(180) as i64,
         ),
         JourneyStage::Visited => (
-            format!("{} visit completed", last),
-            format!("{} done. Creating treatment plan.", procedure),
+            journey.say(Text::VisitedTitle, &[]),
+            journey.say(Text::VisitedBody, &[]),
             "pms",
             "appointment",
             0.55 + rng.next_f64() * 0.20,
@@ -443,8 +471,8 @@ fn journey_to_item(rng: &mut Rng, time: &SimTime, journey: &PatientJourney) -> O
             120 + rng.next_range(360) as i64,
         ),
         JourneyStage::InsuranceCheck => (
-            format!("{} verified", last),
-            format!("{} confirmed coverage for {}.", payer, procedure.to_lowercase()),
+            journey.say(Text::VerifiedTitle, &[]),
+            journey.say(Text::VerifiedBody, &[]),
             "insurance",
             "eligibility",
             0.45 + rng.next_f64() * 0.25,
@@ -452,8 +480,8 @@ fn journey_to_item(rng: &mut Rng, time: &SimTime, journey: &PatientJourney) -> O
             45 + rng.next_range(120) as i64,
         ),
         JourneyStage::ClaimFiled => (
-            format!("Claim filed - {}", last),
-            format!("${} {} submitted to {}.", amount, procedure.to_lowercase(), payer),
+            journey.say(Text::ClaimFiledTitle, &[]),
+            journey.say(Text::ClaimFiledBody, &[]),
             "claims",
             "claim",
             0.50 + rng.next_f64() * 0.20,
@@ -461,8 +489,8 @@ fn journey_to_item(rng: &mut Rng, time: &SimTime, journey: &PatientJourney) -> O
             180 + rng.next_range(480) as i64,
         ),
         JourneyStage::PaymentReceived => (
-            format!("{} ${}", payer, amount - rng.next_range(200) as u32),
-            format!("Posted for {}'s {}.", last, procedure.to_lowercase()),
+            journey.say(Text::PaymentTitle, &[("amount", &journey.locale.money(journey.amount.saturating_sub(rng.next_range(200) as u32)))]),
+            journey.say(Text::PaymentBody, &[]),
             "accounting",
             "payment",
             0.55 + rng.next_f64() * 0.25,
@@ -470,8 +498,8 @@ fn journey_to_item(rng: &mut Rng, time: &SimTime, journey: &PatientJourney) -> O
             30 + rng.next_range(240) as i64,
         ),
         JourneyStage::RecallBooked => (
-            format!("{} rescheduled", last),
-            format!("Was overdue - now booked for {}.", procedure.to_lowercase()),
+            journey.say(Text::RecallBookedTitle, &[]),
+            journey.say(Text::RecallBookedBody, &[]),
             "pms",
             "recall",
             0.60 + rng.next_f64() * 0.20,
@@ -479,8 +507,8 @@ fn journey_to_item(rng: &mut Rng, time: &SimTime, journey: &PatientJourney) -> O
             60 + rng.next_range(180) as i64,
         ),
         JourneyStage::ResponsePosted => (
-            format!("Replied to {}-star review", 3 + rng.next_range(3)),
-            "Response posted.".to_string(),
+            journey.say(Text::ReviewRepliedTitle, &[("rating", &(3 + rng.next_range(3)))]),
+            journey.say(Text::ReviewRepliedBody, &[]),
             "reviews",
             "review",
             0.40 + rng.next_f64() * 0.20,
@@ -490,8 +518,8 @@ fn journey_to_item(rng: &mut Rng, time: &SimTime, journey: &PatientJourney) -> O
         
         // === SNOOZED: Waiting for something ===
         JourneyStage::RecallDue => (
-            format!("{} - {} months overdue", last, 6 + rng.next_range(24)),
-            format!("Last {} was a while ago. In recall queue.", procedure.to_lowercase()),
+            journey.say(Text::RecallDueTitle, &[("months", &(6 + rng.next_range(24)))]),
+            journey.say(Text::RecallDueBody, &[]),
             "pms",
             "recall",
             0.35 + rng.next_f64() * 0.20,
@@ -499,8 +527,8 @@ fn journey_to_item(rng: &mut Rng, time: &SimTime, journey: &PatientJourney) -> O
             2880 + rng.next_range(10080) as i64,
         ),
         JourneyStage::RecallContacted => (
-            format!("Waiting on {} for recall", last),
-            format!("Sent {} messages. No response yet.", 2 + rng.next_range(3)),
+            journey.say(Text::RecallContactedTitle, &[]),
+            journey.say(Text::RecallContactedBody, &[("count", &(2 + rng.next_range(3)))]),
             "pms",
             "recall",
             0.30 + rng.next_f64() * 0.15,
@@ -508,8 +536,8 @@ fn journey_to_item(rng: &mut Rng, time: &SimTime, journey: &PatientJourney) -> O
             1440 + rng.next_range(4320) as i64,
         ),
         JourneyStage::ReviewReceived => (
-            format!("New {}-star review", 1 + rng.next_range(5)),
-            "Agent analyzing for response.".to_string(),
+            journey.say(Text::ReviewReceivedTitle, &[("rating", &(1 + rng.next_range(5)))]),
+            journey.say(Text::ReviewReceivedBody, &[]),
             "reviews",
             "review",
             0.50 + rng.next_f64() * 0.20,
@@ -532,25 +560,25 @@ fn journey_to_item(rng: &mut Rng, time: &SimTime, journey: &PatientJourney) -> O
 }
 
 /// Generate noise items that get auto-dismissed
-fn generate_noise_item(rng: &mut Rng, index: usize) -> Option<SimItem> {
-    let last = *rng.pick(LAST_NAMES)?;
+fn generate_noise_item(rng: &mut Rng, index: usize, locale: Locale) -> Option<SimItem> {
+    let last = *rng.pick(dental_text::names(locale).last)?;
     
     let noise_types = &[
-        ("Spam blocked", "Marketing call auto-blocked.", "phone", "call"),
-        ("Wrong number", "Misdial, ended politely.", "phone", "call"),
-        ("Rx refill", "Routed to clinical queue.", "phone", "call"),
-        ("Duplicate merged", &format!("Merged duplicate {} record.", last), "pms", "patient"),
-        ("FYI only", &format!("{} confirmed via portal.", last), "pms", "appointment"),
-        ("Auto-handled", "Routine follow-up sent.", "automation", "task"),
-        ("Test record", "System test, auto-cleaned.", "system", "maintenance"),
+        (Text::SpamTitle, Text::SpamBody, "phone", "call"),
+        (Text::WrongNumberTitle, Text::WrongNumberBody, "phone", "call"),
+        (Text::RefillTitle, Text::RefillBody, "phone", "call"),
+        (Text::DuplicateTitle, Text::DuplicateBody, "pms", "patient"),
+        (Text::FyiTitle, Text::FyiBody, "pms", "appointment"),
+        (Text::AutoHandledTitle, Text::AutoHandledBody, "automation", "task"),
+        (Text::TestRecordTitle, Text::TestRecordBody, "system", "maintenance"),
     ];
     
     let (title, body, source_type, category) = *rng.pick(noise_types)?;
     
     Some(SimItem {
         id: rng.random_id("sim"),
-        title: title.to_string(),
-        body: body.to_string(),
+        title: dental_text::text(locale, title).to_string(),
+        body: fill(dental_text::text(locale, body), &[("last", &last)]),
         source_type: source_type.to_string(),
         category: category.to_string(),
         score: 0.02 + rng.next_f64() * 0.10,
@@ -561,7 +589,7 @@ fn generate_noise_item(rng: &mut Rng, index: usize) -> Option<SimItem> {
 }
 
 /// Generate activity log from patient journeys - shows logical flow
-fn generate_activity_log_from_journeys(rng: &mut Rng, time: &SimTime, journeys: &[PatientJourney]) -> Vec<SimLogEntry> {
+fn generate_activity_log_from_journeys(rng: &mut Rng, time: &SimTime, journeys: &[PatientJourney], locale: Locale) -> Vec<SimLogEntry> {
     let mut log = Vec::new();
     
     // Generate log entries from journeys - shows the narrative
@@ -580,15 +608,19 @@ fn generate_activity_log_from_journeys(rng: &mut Rng, time: &SimTime, journeys:
     // Add batch operation entries
     let batch_count = 10 + rng.next_range(15) as usize;
     for i in 0..batch_count {
+        let say = |key: Text, args: &[(&str, &dyn Display)]| fill(dental_text::text(locale, key), args);
         let batch_templates = &[
-            ("batch", format!("Batch verified {} patients for tomorrow", 8 + rng.next_range(20))),
-            ("batch", format!("Sent {} confirmation texts", 15 + rng.next_range(30))),
-            ("batch", format!("Texted {} overdue patients", 5 + rng.next_range(25))),
-            ("batch", format!("Synced {} records from PMS", 20 + rng.next_range(50))),
-            ("batch", format!("Posted {} payments totaling ${}", 3 + rng.next_range(8), 2000 + rng.next_range(15000))),
-            ("agent", "Completed: Schedule optimization".to_string()),
-            ("agent", "Completed: Recall prioritization".to_string()),
-            ("agent", "Completed: Claims review".to_string()),
+            ("batch", say(Text::BatchVerified, &[("count", &(8 + rng.next_range(20)))])),
+            ("batch", say(Text::BatchConfirmations, &[("count", &(15 + rng.next_range(30)))])),
+            ("batch", say(Text::BatchRecalls, &[("count", &(5 + rng.next_range(25)))])),
+            ("batch", say(Text::BatchSynced, &[("count", &(20 + rng.next_range(50)))])),
+            ("batch", say(Text::BatchPayments, &[
+                ("count", &(3 + rng.next_range(8))),
+                ("amount", &locale.money(2000 + rng.next_range(15000) as u32)),
+            ])),
+            ("agent", say(Text::AgentScheduling, &[])),
+            ("agent", say(Text::AgentRecalls, &[])),
+            ("agent", say(Text::AgentClaims, &[])),
         ];
         
         if let Some((entry_type, text)) = rng.pick(batch_templates) {
@@ -609,90 +641,85 @@ fn generate_activity_log_from_journeys(rng: &mut Rng, time: &SimTime, journeys:
 
 /// Convert a journey to its current activity log entry
 fn journey_to_log_entry(rng: &mut Rng, journey: &PatientJourney) -> Option<SimLogEntry> {
-    let name = journey.full_name();
-    let procedure = journey.procedure;
-    let payer = journey.payer;
-    let amount = journey.amount;
-    
     let (entry_type, text, base_minutes) = match journey.stage {
         JourneyStage::NewCall => (
             "call",
-            format!("{} called about {} - scored {}/10", name, procedure.to_lowercase(), 7 + rng.next_range(4)),
+            journey.say(Text::LogNewCall, &[("score", &(7 + rng.next_range(4)))]),
             5 + rng.next_range(30) as i64,
         ),
         JourneyStage::Booked => (
             "booking",
-            format!("{} booked for {}", name, procedure.to_lowercase()),
+            journey.say(Text::LogBooked, &[]),
             15 + rng.next_range(60) as i64,
         ),
         JourneyStage::Confirmed => (
             "confirmation",
-            format!("{} confirmed tomorrow's {}", name, procedure.to_lowercase()),
+            journey.say(Text::LogConfirmed, &[]),
             10 + rng.next_range(45) as i64,
         ),
         JourneyStage::Visited => (
             "visit",
-            format!("{} checked out - {} complete", name, procedure),
+            journey.say(Text::LogVisited, &[]),
             30 + rng.next_range(120) as i64,
         ),
         JourneyStage::TreatmentPlan => (
             "treatment",
-            format!("Created treatment plan for {} - ${}", name, amount),
+            journey.say(Text::LogTreatmentPlan, &[]),
             20 + rng.next_range(90) as i64,
         ),
         JourneyStage::InsuranceCheck => (
             "eligibility",
-            format!("Verified {} - {} active", name, payer),
+            journey.say(Text::LogInsuranceCheck, &[]),
             8 + rng.next_range(30) as i64,
         ),
         JourneyStage::ClaimFiled => (
             "claim",
-            format!("Filed ${} claim for {} to {}", amount, name, payer),
+            journey.say(Text::LogClaimFiled, &[]),
             25 + rng.next_range(90) as i64,
         ),
         JourneyStage::ClaimPending => (
             "claim",
-            format!("Drafted appeal for {}'s denied {}", name, procedure.to_lowercase()),
+            journey.say(Text::LogClaimPending, &[]),
             45 + rng.next_range(180) as i64,
         ),
         JourneyStage::PaymentReceived => (
             "payment",
-            format!("Posted {} ${} for {}", payer, amount - rng.next_range(200) as u32, name),
+            journey.say(Text::LogPayment, &[("amount", &journey.locale.money(journey.amount.saturating_sub(rng.next_range(200) as u32)))]),
             12 + rng.next_range(60) as i64,
         ),
         JourneyStage::RecallDue => (
             "recall",
-            format!("Added {} to recall queue - {} months overdue", name, 6 + rng.next_range(18)),
+            journey.say(Text::LogRecallDue, &[("months", &(6 + rng.next_range(18)))]),
             60 + rng.next_range(240) as i64,
         ),
         JourneyStage::RecallContacted => (
             "recall",
-            format!("Sent recall to {} - last visit {} months ago", name, 8 + rng.next_range(16)),
+            journey.say(Text::LogRecallContacted, &[("months", &(8 + rng.next_range(16)))]),
             30 + rng.next_range(120) as i64,
         ),
         JourneyStage::RecallResponded => (
             "recall",
-            format!("{} responded to recall - wants to schedule", name),
+            journey.say(Text::LogRecallResponded, &[]),
             5 + rng.next_range(30) as i64,
         ),
         JourneyStage::RecallBooked => (
             "recall",
-            format!("{} rescheduled after {} month gap", name, 8 + rng.next_range(14)),
+            journey.say(Text::LogRecallBooked, &[("months", &(8 + rng.next_range(14)))]),
             15 + rng.next_range(60) as i64,
         ),
         JourneyStage::ReviewReceived => (
             "review",
-            format!("New {}-star review received", 1 + rng.next_range(5)),
+            journey.say(Text::LogReviewReceived, &[("rating", &(1 + rng.next_range(5)))]),
             20 + rng.next_range(90) as i64,
         ),
         JourneyStage::ResponseDrafted => (
             "review",
-            format!("Agent drafted response - ready for review"),
+            journey.say(Text::LogResponseDrafted, &[]),
             10 + rng.next_range(45) as i64,
         ),
         JourneyStage::ResponsePosted => (
             "review",
-            format!("Posted response to {}-star review", 3 + rng.next_range(3)),
+            journey.say(Text::LogResponsePosted, &[("rating", &(3 + rng.next_range(3)))]),
             25 + rng.next_range(120) as i64,
         ),
     };
@@ -706,50 +733,46 @@ fn journey_to_log_entry(rng: &mut Rng, journey: &PatientJourney) -> Option<SimLo
 
 /// Generate a "previous step" log entry to show journey progression
 fn journey_previous_step_log(rng: &mut Rng, journey: &PatientJourney) -> Option<SimLogEntry> {
-    let name = journey.full_name();
-    let procedure = journey.procedure;
-    let payer = journey.payer;
-    
     // Show what happened before the current stage
     let (entry_type, text, minutes_ago) = match journey.stage {
         JourneyStage::Booked => (
             "call",
-            format!("{} called earlier about {}", name, procedure.to_lowercase()),
+            journey.say(Text::PrevCalled, &[]),
             120 + rng.next_range(240) as i64,
         ),
         JourneyStage::Confirmed => (
             "booking",
-            format!("{} was booked yesterday", name),
+            journey.say(Text::PrevBooked, &[]),
             720 + rng.next_range(720) as i64,
         ),
         JourneyStage::Visited => (
             "confirmation",
-            format!("{} confirmed this morning", name),
+            journey.say(Text::PrevConfirmed, &[]),
             180 + rng.next_range(300) as i64,
         ),
         JourneyStage::TreatmentPlan => (
             "visit",
-            format!("{}'s {} completed", name, procedure),
+            journey.say(Text::PrevVisited, &[]),
             240 + rng.next_range(480) as i64,
         ),
         JourneyStage::ClaimFiled => (
             "eligibility",
-            format!("{}'s {} coverage verified", name, payer),
+            journey.say(Text::PrevVerified, &[]),
             180 + rng.next_range(360) as i64,
         ),
         JourneyStage::PaymentReceived => (
             "claim",
-            format!("{} claim was filed 2 weeks ago", name),
+            journey.say(Text::PrevClaimFiled, &[]),
             10080 + rng.next_range(10080) as i64,
         ),
         JourneyStage::RecallBooked => (
             "recall",
-            format!("{} responded to recall text", name),
+            journey.say(Text::PrevRecallResponded, &[]),
             60 + rng.next_range(180) as i64,
         ),
         JourneyStage::ResponsePosted => (
             "review",
-            format!("Agent drafted response earlier"),
+            journey.say(Text::PrevResponseDrafted, &[]),
             60 + rng.next_range(180) as i64,
         ),
         _ => return None,
@@ -762,6 +785,79 @@ fn journey_previous_step_log(rng: &mut Rng, journey: &PatientJourney) -> Option<
     })
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// Narration
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Separates the narration RNG stream from the state stream
+const NARRATION_STREAM: u64 = 0x6e61_7272;
+
+/// Story beats drawn from the same state the dashboard shows
+///
+/// Overview, then the day's schedule hiccup (once it has happened), the one
+/// thing needing a human, the automation ratio, and recent activity.
+fn narrate(seed: u64, time: &SimTime, state: &SimState, locale: Locale) -> Vec<SimBeat> {
+    let say = |key: Text, args: &[(&str, &dyn Display)]| fill(dental_text::text(locale, key), args);
+    let beat = |minutes_ago: i64, text: String, beat_type: &str| SimBeat {
+        minutes_ago,
+        text,
+        beat_type: beat_type.to_string(),
+    };
+    let metrics = &state.metrics;
+    let now = time.minute_of_day;
+    
+    let when = match time.time_of_day {
+        TimeOfDay::EarlyMorning => Text::WhenEarlyMorning,
+        TimeOfDay::Morning => Text::WhenMorning,
+        TimeOfDay::Midday => Text::WhenMidday,
+        TimeOfDay::Afternoon => Text::WhenAfternoon,
+        TimeOfDay::LateAfternoon => Text::WhenLateAfternoon,
+        TimeOfDay::Evening => Text::WhenEvening,
+    };
+    let mut beats = vec![beat(0, say(Text::NarrateOverview, &[
+        ("when", &dental_text::text(locale, when)),
+        ("done", &metrics.appointments_completed),
+        ("total", &metrics.appointments_total),
+        ("waiting", &metrics.waiting_room),
+    ]), "overview")];
+    
+    // One appointment overruns each weekday; seeded by day so the story
+    // doesn't change between calls once it has happened
+    if time.is_weekday() {
+        let mut rng = Rng::seeded(seed ^ NARRATION_STREAM, time.day as u64);
+        let hour = 9 + rng.next_range(7) as u32;
+        let resolved_at = hour * 60 + 60 + 20 + rng.next_range(40) as u32;
+        let doctor = rng.pick(dental_text::names(locale).doctors).copied().unwrap_or_default();
+        let procedure = rng.pick(PROCEDURES).map(|(p, _)| dental_text::procedure(locale, p)).unwrap_or_default();
+        let count = 1 + rng.next_range(3);
+        
+        if now >= resolved_at {
+            beats.push(beat((now - resolved_at) as i64, say(Text::NarrateIncident, &[
+                ("doctor", &doctor),
+                ("time", &locale.clock(hour)),
+                ("count", &count),
+                ("procedure", &locale.in_sentence(procedure)),
+            ]), "incident"));
+        }
+    }
+    
+    if let Some(item) = state.items.iter().find(|i| i.status == "inbox") {
+        beats.push(beat(item.minutes_ago, say(Text::NarrateAttention, &[
+            ("title", &item.title),
+            ("body", &item.body),
+        ]), "attention"));
+    }
+    
+    beats.push(beat(0, say(Text::NarrateAutomation, &[
+        ("automations", &metrics.automations_today),
+        ("decisions", &metrics.human_decisions),
+        ("ratio", &(metrics.automations_today / metrics.human_decisions.max(1))),
+    ]), "automation"));
+    
+    beats.extend(state.activity_log.iter().map(|e| beat(e.minutes_ago, e.text.clone(), "activity")));
+    beats
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -785,4 +881,60 @@ mod tests {
         // Evening should have more appointments completed
         assert!(evening.metrics.appointments_completed >= morning.metrics.appointments_completed);
     }
+    
+    #[test]
+    fn test_locale_tells_same_story() {
+        let ts = 1705849200000;
+        let options = SimOptions { locale: Locale::Ja, ..Default::default() };
+        let english = DentalScenario::generate(12345, ts);
+        let japanese = DentalScenario::generate_with(12345, ts, &options);
+        
+        // Same seed, same shape - only the words change
+        assert_eq!(english.items.len(), japanese.items.len());
+        assert_eq!(english.metrics.appointments_total, japanese.metrics.appointments_total);
+        assert_eq!(english.items[0].category, japanese.items[0].category);
+        assert_ne!(english.items[0].title, japanese.items[0].title);
+        assert!(japanese.activity_log.iter().all(|e| !e.text.contains('{')));
+        
+        let again = DentalScenario::generate_with(12345, ts, &options);
+        assert_eq!(japanese.items[0].title, again.items[0].title);
+    }
+    
+    #[test]
+    fn test_targets_scale_volume_and_keep_shape() {
+        let targets = SimTargets::from_json(r#"{"appointmentsPerDay": 128, "onTimeRate": 90, "maxWaitMinutes": 2}"#).unwrap();
+        let options = SimOptions { targets, ..Default::default() };
+        
+        let default = DentalScenario::generate(12345, 1705849200000);
+        let scaled = DentalScenario::generate_with(12345, 1705849200000, &options);
+        assert!(scaled.metrics.appointments_total >= default.metrics.appointments_total * 3);
+        assert!(scaled.items.len() > default.items.len() * 2);
+        assert!((88..=92).contains(&scaled.metrics.on_time_rate));
+        assert!(scaled.metrics.avg_wait_minutes <= 2);
+        
+        // Still follows the day: more done by 5pm than at 9am
+        let morning = DentalScenario::generate_with(12345, 1705831200000, &options);
+        let evening = DentalScenario::generate_with(12345, 1705860000000, &options);
+        assert!(evening.metrics.appointments_completed > morning.metrics.appointments_completed);
+        
+        assert!(SimTargets::from_json(r#"{"appointmentsPerDya": 120}"#).is_err());
+    }
+    
+    #[test]
+    fn test_narration_follows_the_day() {
+        // Monday 8am vs 5pm CST
+        let morning = DentalScenario::narrate(12345, 1705932000000);
+        let evening = DentalScenario::narrate(12345, 1705957200000);
+        
+        assert_eq!(morning[0].beat_type, "overview");
+        assert!(!morning.iter().any(|b| b.beat_type == "incident"));
+        let incident = evening.iter().find(|b| b.beat_type == "incident").unwrap();
+        assert!(incident.text.contains("ran long; the agent rescheduled"));
+        
+        // Same incident later in the day, just further in the past
+        let later = DentalScenario::narrate(12345, 1705957200000 + 1800000);
+        let again = later.iter().find(|b| b.beat_type == "incident").unwrap();
+        assert_eq!(incident.text, again.text);
+        assert_eq!(again.minutes_ago, incident.minutes_ago + 30);
+    }
 }
@@ -0,0 +1,641 @@
+//! Dental Scenario Text
+//!
+//! Name pools and templates for each locale. Every locale matches every
+//! `Text` variant, so adding a template without translating it won't compile.
+//!
+//! Placeholders: `{name}`, `{last}`, `{payer}`, `{amount}` (already in local
+//! currency), `{procedure}` (mid-sentence), `{Procedure}` (sentence start),
+//! and counts like `{score}`, `{days}`, `{months}`, `{rating}`, `{count}`.
+
+use crate::locale::Locale;
+
+/// Name pools for one locale
+pub struct NamePool {
+    pub first: &'static [&'static str],
+    pub last: &'static [&'static str],
+    pub payers: &'static [&'static str],
+    pub doctors: &'static [&'static str],
+}
+
+const EN_NAMES: NamePool = NamePool {
+    first: &[
+        "James", "Sarah", "Michael", "Jennifer", "David", "Emily", "Robert", "Maria",
+        "William", "Lisa", "Carlos", "Amanda", "Kevin", "Jessica", "Thomas", "Ashley",
+        "Daniel", "Michelle", "Brian", "Nicole", "Steven", "Rachel", "Andrew", "Laura",
+    ],
+    last: &[
+        "Thompson", "Williams", "Johnson", "Morrison", "Chen", "Garcia", "Martinez",
+        "Smith", "Brown", "Davis", "Wilson", "Anderson", "Taylor", "Thomas", "Moore",
+        "Jackson", "White", "Harris", "Martin", "Lee", "Walker", "Hall", "Young",
+    ],
+    payers: &[
+        "Delta Dental", "Aetna", "Cigna", "MetLife", "Guardian", "United Healthcare",
+        "Blue Cross", "Humana", "Principal",
+    ],
+    doctors: &["Dr. Lee", "Dr. Patel", "Dr. Okafor", "Dr. Nguyen", "Dr. Reyes"],
+};
+
+const ES_NAMES: NamePool = NamePool {
+    first: &[
+        "Sofía", "Mateo", "Valentina", "Santiago", "Camila", "Diego", "Lucía", "Javier",
+        "Isabella", "Alejandro", "Gabriela", "Andrés", "Mariana", "Carlos", "Daniela", "Luis",
+    ],
+    last: &[
+        "García", "Rodríguez", "Martínez", "Hernández", "López", "González", "Pérez",
+        "Sánchez", "Ramírez", "Torres", "Flores", "Rivera", "Gómez", "Díaz", "Morales", "Ortiz",
+    ],
+    payers: &[
+        "GNP Seguros", "AXA", "MetLife", "Seguros Monterrey", "Allianz", "Mapfre",
+        "BBVA Seguros", "Sanitas", "Zurich",
+    ],
+    doctors: &["la Dra. Morales", "el Dr. Herrera", "la Dra. Castillo", "el Dr. Vargas"],
+};
+
+const DE_NAMES: NamePool = NamePool {
+    first: &[
+        "Lukas", "Anna", "Jonas", "Lena", "Felix", "Marie", "Leon", "Sophie",
+        "Paul", "Laura", "Maximilian", "Julia", "Tobias", "Katharina", "Niklas", "Hannah",
+    ],
+    last: &[
+        "Müller", "Schmidt", "Schneider", "Fischer", "Weber", "Meyer", "Wagner", "Becker",
+        "Schulz", "Hoffmann", "Koch", "Richter", "Klein", "Wolf", "Neumann", "Braun",
+    ],
+    payers: &[
+        "AOK", "TK", "Barmer", "DAK", "IKK", "KKH", "HEK", "Allianz", "Debeka",
+    ],
+    doctors: &["Dr. Weber", "Dr. Krüger", "Dr. Lehmann", "Dr. Hartmann"],
+};
+
+const JA_NAMES: NamePool = NamePool {
+    first: &[
+        "花子", "太郎", "陽菜", "蓮", "美咲", "大翔", "結衣", "悠真",
+        "さくら", "翔太", "葵", "健太", "愛", "拓海", "由美", "直樹",
+    ],
+    last: &[
+        "佐藤", "鈴木", "高橋", "田中", "伊藤", "渡辺", "山本", "中村",
+        "小林", "加藤", "吉田", "山田", "松本", "井上", "木村", "林",
+    ],
+    payers: &[
+        "協会けんぽ", "国民健康保険", "組合健保", "共済組合", "後期高齢者医療",
+    ],
+    doctors: &["山田先生", "佐々木先生", "石井先生", "森先生"],
+};
+
+pub fn names(locale: Locale) -> &'static NamePool {
+    match locale {
+        Locale::En => &EN_NAMES,
+        Locale::Es => &ES_NAMES,
+        Locale::De => &DE_NAMES,
+        Locale::Ja => &JA_NAMES,
+    }
+}
+
+/// Localized procedure name (procedures are keyed by their English name)
+pub fn procedure(locale: Locale, english: &'static str) -> &'static str {
+    let translations: &[(&str, &str, &str, &str)] = &[
+        ("Cleaning", "Limpieza", "Zahnreinigung", "クリーニング"),
+        ("Crown", "Corona", "Krone", "クラウン"),
+        ("Filling", "Empaste", "Füllung", "詰め物"),
+        ("Root Canal", "Endodoncia", "Wurzelbehandlung", "根管治療"),
+        ("Extraction", "Extracción", "Extraktion", "抜歯"),
+        ("Implant Consult", "Consulta de implante", "Implantatberatung", "インプラント相談"),
+        ("Veneer Consult", "Consulta de carillas", "Veneer-Beratung", "ベニア相談"),
+        ("Whitening", "Blanqueamiento", "Bleaching", "ホワイトニング"),
+        ("Deep Cleaning", "Limpieza profunda", "Tiefenreinigung", "歯周クリーニング"),
+        ("Bridge", "Puente", "Brücke", "ブリッジ"),
+    ];
+    
+    let Some(&(_, es, de, ja)) = translations.iter().find(|(en, ..)| *en == english) else {
+        return english;
+    };
+    match locale {
+        Locale::En => english,
+        Locale::Es => es,
+        Locale::De => de,
+        Locale::Ja => ja,
+    }
+}
+
+/// Every user-visible string the dental scenario renders
+#[derive(Debug, Clone, Copy)]
+pub enum Text {
+    // Items
+    NewCallTitle,
+    NewCallBody,
+    TreatmentPlanTitle,
+    TreatmentPlanBody,
+    AppealTitle,
+    AppealBody,
+    ClaimPendingTitle,
+    ClaimPendingBody,
+    ResponseReadyTitle,
+    ApologyBody,
+    ThankYouBody,
+    RecallRespondedTitle,
+    RecallRespondedBody,
+    BookedTitle,
+    BookedBody,
+    ConfirmedTitle,
+    ConfirmedBody,
+    VisitedTitle,
+    VisitedBody,
+    VerifiedTitle,
+    VerifiedBody,
+    ClaimFiledTitle,
+    ClaimFiledBody,
+    PaymentTitle,
+    PaymentBody,
+    RecallBookedTitle,
+    RecallBookedBody,
+    ReviewRepliedTitle,
+    ReviewRepliedBody,
+    RecallDueTitle,
+    RecallDueBody,
+    RecallContactedTitle,
+    RecallContactedBody,
+    ReviewReceivedTitle,
+    ReviewReceivedBody,
+    
+    // Noise items
+    SpamTitle,
+    SpamBody,
+    WrongNumberTitle,
+    WrongNumberBody,
+    RefillTitle,
+    RefillBody,
+    DuplicateTitle,
+    DuplicateBody,
+    FyiTitle,
+    FyiBody,
+    AutoHandledTitle,
+    AutoHandledBody,
+    TestRecordTitle,
+    TestRecordBody,
+    
+    // Activity log
+    LogNewCall,
+    LogBooked,
+    LogConfirmed,
+    LogVisited,
+    LogTreatmentPlan,
+    LogInsuranceCheck,
+    LogClaimFiled,
+    LogClaimPending,
+    LogPayment,
+    LogRecallDue,
+    LogRecallContacted,
+    LogRecallResponded,
+    LogRecallBooked,
+    LogReviewReceived,
+    LogResponseDrafted,
+    LogResponsePosted,
+    
+    // Activity log (previous step)
+    PrevCalled,
+    PrevBooked,
+    PrevConfirmed,
+    PrevVisited,
+    PrevVerified,
+    PrevClaimFiled,
+    PrevRecallResponded,
+    PrevResponseDrafted,
+    
+    // Activity log (batch operations)
+    BatchVerified,
+    BatchConfirmations,
+    BatchRecalls,
+    BatchSynced,
+    BatchPayments,
+    AgentScheduling,
+    AgentRecalls,
+    AgentClaims,
+    
+    // Narration
+    NarrateOverview,
+    NarrateIncident,
+    NarrateAttention,
+    NarrateAutomation,
+    WhenEarlyMorning,
+    WhenMorning,
+    WhenMidday,
+    WhenAfternoon,
+    WhenLateAfternoon,
+    WhenEvening,
+}
+
+pub fn text(locale: Locale, key: Text) -> &'static str {
+    match locale {
+        Locale::En => en(key),
+        Locale::Es => es(key),
+        Locale::De => de(key),
+        Locale::Ja => ja(key),
+    }
+}
+
+fn en(key: Text) -> &'static str {
+    use Text::*;
+    match key {
+        NewCallTitle => "New patient worth calling back",
+        NewCallBody => "{name} called about {procedure}. Has PPO, scored {score}/10.",
+        TreatmentPlanTitle => "{last}'s treatment plan ready",
+        TreatmentPlanBody => "{amount} {procedure} - waiting for your review before presenting.",
+        AppealTitle => "Appeal ready for {last}'s {procedure}",
+        AppealBody => "{payer} denied it. Drafted the letter - deadline in {days} days.",
+        ClaimPendingTitle => "{last} claim pending",
+        ClaimPendingBody => "{amount} {procedure} submitted to {payer} - {days} days ago.",
+        ResponseReadyTitle => "Response ready for {rating}-star review",
+        ApologyBody => "Drafted an apology. Review before posting?",
+        ThankYouBody => "Drafted a thank-you. Ready to post.",
+        RecallRespondedTitle => "{last} wants to schedule",
+        RecallRespondedBody => "Responded to recall text. Was {months} months overdue.",
+        BookedTitle => "{last} booked",
+        BookedBody => "{name} scheduled for {procedure}.",
+        ConfirmedTitle => "{last} confirmed",
+        ConfirmedBody => "Replied to reminder - {procedure} tomorrow.",
+        VisitedTitle => "{last} visit completed",
+        VisitedBody => "{Procedure} done. Creating treatment plan.",
+        VerifiedTitle => "{last} verified",
+        VerifiedBody => "{payer} confirmed coverage for {procedure}.",
+        ClaimFiledTitle => "Claim filed - {last}",
+        ClaimFiledBody => "{amount} {procedure} submitted to {payer}.",
+        PaymentTitle => "{payer} {amount}",
+        PaymentBody => "Posted for {last}'s {procedure}.",
+        RecallBookedTitle => "{last} rescheduled",
+        RecallBookedBody => "Was overdue - now booked for {procedure}.",
+        ReviewRepliedTitle => "Replied to {rating}-star review",
+        ReviewRepliedBody => "Response posted.",
+        RecallDueTitle => "{last} - {months} months overdue",
+        RecallDueBody => "Last {procedure} was a while ago. In recall queue.",
+        RecallContactedTitle => "Waiting on {last} for recall",
+        RecallContactedBody => "Sent {count} messages. No response yet.",
+        ReviewReceivedTitle => "New {rating}-star review",
+        ReviewReceivedBody => "Agent analyzing for response.",
+        
+        SpamTitle => "Spam blocked",
+        SpamBody => "Marketing call auto-blocked.",
+        WrongNumberTitle => "Wrong number",
+        WrongNumberBody => "Misdial, ended politely.",
+        RefillTitle => "Rx refill",
+        RefillBody => "Routed to clinical queue.",
+        DuplicateTitle => "Duplicate merged",
+        DuplicateBody => "Merged duplicate {last} record.",
+        FyiTitle => "FYI only",
+        FyiBody => "{last} confirmed via portal.",
+        AutoHandledTitle => "Auto-handled",
+        AutoHandledBody => "Routine follow-up sent.",
+        TestRecordTitle => "Test record",
+        TestRecordBody => "System test, auto-cleaned.",
+        
+        LogNewCall => "{name} called about {procedure} - scored {score}/10",
+        LogBooked => "{name} booked for {procedure}",
+        LogConfirmed => "{name} confirmed tomorrow's {procedure}",
+        LogVisited => "{name} checked out - {Procedure} complete",
+        LogTreatmentPlan => "Created treatment plan for {name} - {amount}",
+        LogInsuranceCheck => "Verified {name} - {payer} active",
+        LogClaimFiled => "Filed {amount} claim for {name} to {payer}",
+        LogClaimPending => "Drafted appeal for {name}'s denied {procedure}",
+        LogPayment => "Posted {payer} {amount} for {name}",
+        LogRecallDue => "Added {name} to recall queue - {months} months overdue",
+        LogRecallContacted => "Sent recall to {name} - last visit {months} months ago",
+        LogRecallResponded => "{name} responded to recall - wants to schedule",
+        LogRecallBooked => "{name} rescheduled after {months} month gap",
+        LogReviewReceived => "New {rating}-star review received",
+        LogResponseDrafted => "Agent drafted response - ready for review",
+        LogResponsePosted => "Posted response to {rating}-star review",
+        
+        PrevCalled => "{name} called earlier about {procedure}",
+        PrevBooked => "{name} was booked yesterday",
+        PrevConfirmed => "{name} confirmed this morning",
+        PrevVisited => "{name}'s {Procedure} completed",
+        PrevVerified => "{name}'s {payer} coverage verified",
+        PrevClaimFiled => "{name} claim was filed 2 weeks ago",
+        PrevRecallResponded => "{name} responded to recall text",
+        PrevResponseDrafted => "Agent drafted response earlier",
+        
+        BatchVerified => "Batch verified {count} patients for tomorrow",
+        BatchConfirmations => "Sent {count} confirmation texts",
+        BatchRecalls => "Texted {count} overdue patients",
+        BatchSynced => "Synced {count} records from PMS",
+        BatchPayments => "Posted {count} payments totaling {amount}",
+        AgentScheduling => "Completed: Schedule optimization",
+        AgentRecalls => "Completed: Recall prioritization",
+        AgentClaims => "Completed: Claims review",
+        
+        NarrateOverview => "{when}: {done} of {total} appointments are done and {waiting} waiting.",
+        NarrateIncident => "{doctor}'s {time} appointment ran long; the agent rescheduled {count} {procedure} appointments.",
+        NarrateAttention => "Top of your inbox: {title}. {body}",
+        NarrateAutomation => "{automations} automations so far against {decisions} human decisions - about {ratio} to 1.",
+        WhenEarlyMorning => "Before opening",
+        WhenMorning => "Mid-morning",
+        WhenMidday => "Around lunch",
+        WhenAfternoon => "This afternoon",
+        WhenLateAfternoon => "Near closing",
+        WhenEvening => "After hours",
+    }
+}
+
+fn es(key: Text) -> &'static str {
+    use Text::*;
+    match key {
+        NewCallTitle => "Paciente nuevo para devolver la llamada",
+        NewCallBody => "{name} llamó por {procedure}. Tiene seguro, puntuación {score}/10.",
+        TreatmentPlanTitle => "Plan de tratamiento de {last} listo",
+        TreatmentPlanBody => "{procedure} de {amount} - pendiente de tu revisión antes de presentarlo.",
+        AppealTitle => "Apelación lista para {procedure} de {last}",
+        AppealBody => "{payer} lo rechazó. Carta redactada - vence en {days} días.",
+        ClaimPendingTitle => "Reclamación de {last} pendiente",
+        ClaimPendingBody => "{procedure} de {amount} enviado a {payer} - hace {days} días.",
+        ResponseReadyTitle => "Respuesta lista para reseña de {rating} estrellas",
+        ApologyBody => "Disculpa redactada. ¿Revisar antes de publicar?",
+        ThankYouBody => "Agradecimiento redactado. Listo para publicar.",
+        RecallRespondedTitle => "{last} quiere agendar",
+        RecallRespondedBody => "Respondió al mensaje de revisión. Llevaba {months} meses de retraso.",
+        BookedTitle => "{last} agendado",
+        BookedBody => "{name} agendado para {procedure}.",
+        ConfirmedTitle => "{last} confirmó",
+        ConfirmedBody => "Respondió al recordatorio - {procedure} mañana.",
+        VisitedTitle => "Visita de {last} completada",
+        VisitedBody => "{Procedure} terminado. Creando plan de tratamiento.",
+        VerifiedTitle => "{last} verificado",
+        VerifiedBody => "{payer} confirmó la cobertura de {procedure}.",
+        ClaimFiledTitle => "Reclamación enviada - {last}",
+        ClaimFiledBody => "{procedure} de {amount} enviado a {payer}.",
+        PaymentTitle => "{payer} {amount}",
+        PaymentBody => "Registrado para {procedure} de {last}.",
+        RecallBookedTitle => "{last} reprogramado",
+        RecallBookedBody => "Tenía retraso - ahora agendado para {procedure}.",
+        ReviewRepliedTitle => "Respuesta a reseña de {rating} estrellas",
+        ReviewRepliedBody => "Respuesta publicada.",
+        RecallDueTitle => "{last} - {months} meses de retraso",
+        RecallDueBody => "Su última {procedure} fue hace tiempo. En cola de revisión.",
+        RecallContactedTitle => "Esperando a {last} para revisión",
+        RecallContactedBody => "{count} mensajes enviados. Sin respuesta aún.",
+        ReviewReceivedTitle => "Nueva reseña de {rating} estrellas",
+        ReviewReceivedBody => "El agente está analizando la respuesta.",
+        
+        SpamTitle => "Spam bloqueado",
+        SpamBody => "Llamada comercial bloqueada automáticamente.",
+        WrongNumberTitle => "Número equivocado",
+        WrongNumberBody => "Marcación errónea, finalizada con cortesía.",
+        RefillTitle => "Receta",
+        RefillBody => "Enviada a la cola clínica.",
+        DuplicateTitle => "Duplicado fusionado",
+        DuplicateBody => "Registro duplicado de {last} fusionado.",
+        FyiTitle => "Solo informativo",
+        FyiBody => "{last} confirmó por el portal.",
+        AutoHandledTitle => "Gestionado automáticamente",
+        AutoHandledBody => "Seguimiento de rutina enviado.",
+        TestRecordTitle => "Registro de prueba",
+        TestRecordBody => "Prueba del sistema, limpiada automáticamente.",
+        
+        LogNewCall => "{name} llamó por {procedure} - puntuación {score}/10",
+        LogBooked => "{name} agendado para {procedure}",
+        LogConfirmed => "{name} confirmó {procedure} de mañana",
+        LogVisited => "{name} salió - {procedure} completado",
+        LogTreatmentPlan => "Plan de tratamiento creado para {name} - {amount}",
+        LogInsuranceCheck => "{name} verificado - {payer} activo",
+        LogClaimFiled => "Reclamación de {amount} de {name} enviada a {payer}",
+        LogClaimPending => "Apelación redactada para {procedure} rechazado de {name}",
+        LogPayment => "Registrado {payer} {amount} para {name}",
+        LogRecallDue => "{name} añadido a la cola de revisión - {months} meses de retraso",
+        LogRecallContacted => "Recordatorio enviado a {name} - última visita hace {months} meses",
+        LogRecallResponded => "{name} respondió al recordatorio - quiere agendar",
+        LogRecallBooked => "{name} reprogramado tras {months} meses",
+        LogReviewReceived => "Nueva reseña de {rating} estrellas recibida",
+        LogResponseDrafted => "El agente redactó una respuesta - lista para revisar",
+        LogResponsePosted => "Respuesta publicada a reseña de {rating} estrellas",
+        
+        PrevCalled => "{name} llamó antes por {procedure}",
+        PrevBooked => "{name} fue agendado ayer",
+        PrevConfirmed => "{name} confirmó esta mañana",
+        PrevVisited => "{Procedure} de {name} completado",
+        PrevVerified => "Cobertura de {payer} de {name} verificada",
+        PrevClaimFiled => "La reclamación de {name} se envió hace 2 semanas",
+        PrevRecallResponded => "{name} respondió al mensaje de revisión",
+        PrevResponseDrafted => "El agente redactó una respuesta antes",
+        
+        BatchVerified => "Verificados {count} pacientes para mañana",
+        BatchConfirmations => "Enviados {count} mensajes de confirmación",
+        BatchRecalls => "Mensajes a {count} pacientes con retraso",
+        BatchSynced => "Sincronizados {count} registros del sistema de gestión",
+        BatchPayments => "Registrados {count} pagos por un total de {amount}",
+        AgentScheduling => "Completado: Optimización de agenda",
+        AgentRecalls => "Completado: Priorización de revisiones",
+        AgentClaims => "Completado: Revisión de reclamaciones",
+        
+        NarrateOverview => "{when}: {done} de {total} citas terminadas y {waiting} en espera.",
+        NarrateIncident => "La cita de las {time} con {doctor} se alargó; el agente reprogramó {count} citas de {procedure}.",
+        NarrateAttention => "Lo primero en tu bandeja: {title}. {body}",
+        NarrateAutomation => "{automations} automatizaciones hasta ahora frente a {decisions} decisiones humanas - unas {ratio} a 1.",
+        WhenEarlyMorning => "Antes de abrir",
+        WhenMorning => "A media mañana",
+        WhenMidday => "A la hora de comer",
+        WhenAfternoon => "Esta tarde",
+        WhenLateAfternoon => "Casi al cierre",
+        WhenEvening => "Fuera de horario",
+    }
+}
+
+fn de(key: Text) -> &'static str {
+    use Text::*;
+    match key {
+        NewCallTitle => "Neuer Patient - Rückruf lohnt sich",
+        NewCallBody => "{name} hat wegen {procedure} angerufen. Versichert, Bewertung {score}/10.",
+        TreatmentPlanTitle => "Behandlungsplan für {last} fertig",
+        TreatmentPlanBody => "{procedure} für {amount} - wartet auf deine Prüfung vor der Vorstellung.",
+        AppealTitle => "Widerspruch für {procedure} von {last} bereit",
+        AppealBody => "{payer} hat abgelehnt. Schreiben entworfen - Frist in {days} Tagen.",
+        ClaimPendingTitle => "Abrechnung {last} ausstehend",
+        ClaimPendingBody => "{procedure} für {amount} bei {payer} eingereicht - vor {days} Tagen.",
+        ResponseReadyTitle => "Antwort auf {rating}-Sterne-Bewertung bereit",
+        ApologyBody => "Entschuldigung entworfen. Vor dem Veröffentlichen prüfen?",
+        ThankYouBody => "Dankeschön entworfen. Bereit zum Veröffentlichen.",
+        RecallRespondedTitle => "{last} möchte einen Termin",
+        RecallRespondedBody => "Auf Recall-Nachricht geantwortet. {months} Monate überfällig.",
+        BookedTitle => "{last} gebucht",
+        BookedBody => "{name} für {procedure} eingeplant.",
+        ConfirmedTitle => "{last} bestätigt",
+        ConfirmedBody => "Auf Erinnerung geantwortet - {procedure} morgen.",
+        VisitedTitle => "Besuch von {last} abgeschlossen",
+        VisitedBody => "{Procedure} erledigt. Behandlungsplan wird erstellt.",
+        VerifiedTitle => "{last} geprüft",
+        VerifiedBody => "{payer} hat die Kostenübernahme für {procedure} bestätigt.",
+        ClaimFiledTitle => "Abrechnung eingereicht - {last}",
+        ClaimFiledBody => "{procedure} für {amount} bei {payer} eingereicht.",
+        PaymentTitle => "{payer} {amount}",
+        PaymentBody => "Verbucht für {procedure} von {last}.",
+        RecallBookedTitle => "{last} neu terminiert",
+        RecallBookedBody => "War überfällig - jetzt für {procedure} gebucht.",
+        ReviewRepliedTitle => "{rating}-Sterne-Bewertung beantwortet",
+        ReviewRepliedBody => "Antwort veröffentlicht.",
+        RecallDueTitle => "{last} - {months} Monate überfällig",
+        RecallDueBody => "Letzte {procedure} ist eine Weile her. In der Recall-Liste.",
+        RecallContactedTitle => "Warten auf {last} (Recall)",
+        RecallContactedBody => "{count} Nachrichten gesendet. Noch keine Antwort.",
+        ReviewReceivedTitle => "Neue {rating}-Sterne-Bewertung",
+        ReviewReceivedBody => "Agent analysiert für eine Antwort.",
+        
+        SpamTitle => "Spam blockiert",
+        SpamBody => "Werbeanruf automatisch blockiert.",
+        WrongNumberTitle => "Falsch verbunden",
+        WrongNumberBody => "Verwählt, höflich beendet.",
+        RefillTitle => "Rezept",
+        RefillBody => "An die klinische Warteschlange weitergeleitet.",
+        DuplicateTitle => "Duplikat zusammengeführt",
+        DuplicateBody => "Doppelten Datensatz von {last} zusammengeführt.",
+        FyiTitle => "Nur zur Info",
+        FyiBody => "{last} hat über das Portal bestätigt.",
+        AutoHandledTitle => "Automatisch erledigt",
+        AutoHandledBody => "Routine-Nachfrage gesendet.",
+        TestRecordTitle => "Testdatensatz",
+        TestRecordBody => "Systemtest, automatisch bereinigt.",
+        
+        LogNewCall => "{name} hat wegen {procedure} angerufen - Bewertung {score}/10",
+        LogBooked => "{name} für {procedure} gebucht",
+        LogConfirmed => "{name} hat {procedure} morgen bestätigt",
+        LogVisited => "{name} ausgecheckt - {procedure} abgeschlossen",
+        LogTreatmentPlan => "Behandlungsplan für {name} erstellt - {amount}",
+        LogInsuranceCheck => "{name} geprüft - {payer} aktiv",
+        LogClaimFiled => "Abrechnung über {amount} für {name} bei {payer} eingereicht",
+        LogClaimPending => "Widerspruch für abgelehnte {procedure} von {name} entworfen",
+        LogPayment => "{payer} {amount} für {name} verbucht",
+        LogRecallDue => "{name} zur Recall-Liste hinzugefügt - {months} Monate überfällig",
+        LogRecallContacted => "Recall an {name} gesendet - letzter Besuch vor {months} Monaten",
+        LogRecallResponded => "{name} hat auf Recall geantwortet - möchte einen Termin",
+        LogRecallBooked => "{name} nach {months} Monaten Pause neu terminiert",
+        LogReviewReceived => "Neue {rating}-Sterne-Bewertung erhalten",
+        LogResponseDrafted => "Agent hat Antwort entworfen - bereit zur Prüfung",
+        LogResponsePosted => "Antwort auf {rating}-Sterne-Bewertung veröffentlicht",
+        
+        PrevCalled => "{name} hat vorhin wegen {procedure} angerufen",
+        PrevBooked => "{name} wurde gestern gebucht",
+        PrevConfirmed => "{name} hat heute Morgen bestätigt",
+        PrevVisited => "{Procedure} von {name} abgeschlossen",
+        PrevVerified => "{payer}-Deckung von {name} geprüft",
+        PrevClaimFiled => "Abrechnung für {name} vor 2 Wochen eingereicht",
+        PrevRecallResponded => "{name} hat auf Recall-Nachricht geantwortet",
+        PrevResponseDrafted => "Agent hat vorhin eine Antwort entworfen",
+        
+        BatchVerified => "{count} Patienten für morgen geprüft",
+        BatchConfirmations => "{count} Bestätigungsnachrichten gesendet",
+        BatchRecalls => "{count} überfällige Patienten angeschrieben",
+        BatchSynced => "{count} Datensätze aus der Praxissoftware synchronisiert",
+        BatchPayments => "{count} Zahlungen über insgesamt {amount} verbucht",
+        AgentScheduling => "Abgeschlossen: Terminoptimierung",
+        AgentRecalls => "Abgeschlossen: Recall-Priorisierung",
+        AgentClaims => "Abgeschlossen: Abrechnungsprüfung",
+        
+        NarrateOverview => "{when}: {done} von {total} Terminen erledigt, {waiting} warten.",
+        NarrateIncident => "Der {time}-Termin bei {doctor} dauerte länger; der Agent hat {count} {procedure}-Termine verschoben.",
+        NarrateAttention => "Ganz oben im Posteingang: {title}. {body}",
+        NarrateAutomation => "{automations} Automatisierungen bisher gegenüber {decisions} menschlichen Entscheidungen - etwa {ratio} zu 1.",
+        WhenEarlyMorning => "Vor der Öffnung",
+        WhenMorning => "Am Vormittag",
+        WhenMidday => "Zur Mittagszeit",
+        WhenAfternoon => "Heute Nachmittag",
+        WhenLateAfternoon => "Kurz vor Feierabend",
+        WhenEvening => "Nach Praxisschluss",
+    }
+}
+
+fn ja(key: Text) -> &'static str {
+    use Text::*;
+    match key {
+        NewCallTitle => "折り返すべき新規患者",
+        NewCallBody => "{name}様から{procedure}について問い合わせ。保険加入済み、スコア{score}/10。",
+        TreatmentPlanTitle => "{last}様の治療計画が完成",
+        TreatmentPlanBody => "{procedure} {amount} - 提示前に確認をお願いします。",
+        AppealTitle => "{last}様の{procedure}の再審査請求を準備済み",
+        AppealBody => "{payer}が却下。文書を作成済み - 期限まであと{days}日。",
+        ClaimPendingTitle => "{last}様の請求が保留中",
+        ClaimPendingBody => "{procedure} {amount}を{payer}に提出 - {days}日前。",
+        ResponseReadyTitle => "星{rating}つのレビューへの返信を準備済み",
+        ApologyBody => "お詫び文を作成しました。投稿前に確認しますか？",
+        ThankYouBody => "お礼文を作成しました。投稿できます。",
+        RecallRespondedTitle => "{last}様が予約を希望",
+        RecallRespondedBody => "リコール連絡に返信。{months}か月経過していました。",
+        BookedTitle => "{last}様 予約済み",
+        BookedBody => "{name}様の{procedure}を予約しました。",
+        ConfirmedTitle => "{last}様 確認済み",
+        ConfirmedBody => "リマインダーに返信 - 明日{procedure}。",
+        VisitedTitle => "{last}様の来院完了",
+        VisitedBody => "{Procedure}が完了。治療計画を作成中。",
+        VerifiedTitle => "{last}様 確認済み",
+        VerifiedBody => "{payer}が{procedure}の適用を確認しました。",
+        ClaimFiledTitle => "請求済み - {last}様",
+        ClaimFiledBody => "{procedure} {amount}を{payer}に提出しました。",
+        PaymentTitle => "{payer} {amount}",
+        PaymentBody => "{last}様の{procedure}分を計上しました。",
+        RecallBookedTitle => "{last}様 再予約",
+        RecallBookedBody => "期限切れでしたが、{procedure}を予約しました。",
+        ReviewRepliedTitle => "星{rating}つのレビューに返信",
+        ReviewRepliedBody => "返信を投稿しました。",
+        RecallDueTitle => "{last}様 - {months}か月経過",
+        RecallDueBody => "前回の{procedure}から時間が経っています。リコール待ち。",
+        RecallContactedTitle => "{last}様のリコール返信待ち",
+        RecallContactedBody => "{count}件のメッセージを送信。まだ返信なし。",
+        ReviewReceivedTitle => "星{rating}つの新しいレビュー",
+        ReviewReceivedBody => "エージェントが返信内容を分析中。",
+        
+        SpamTitle => "迷惑電話をブロック",
+        SpamBody => "営業電話を自動ブロックしました。",
+        WrongNumberTitle => "間違い電話",
+        WrongNumberBody => "番号違いのため丁寧に終了。",
+        RefillTitle => "処方の依頼",
+        RefillBody => "診療チームのキューに転送しました。",
+        DuplicateTitle => "重複を統合",
+        DuplicateBody => "{last}様の重複レコードを統合しました。",
+        FyiTitle => "参考情報",
+        FyiBody => "{last}様がポータルで確認しました。",
+        AutoHandledTitle => "自動対応済み",
+        AutoHandledBody => "定期フォローアップを送信しました。",
+        TestRecordTitle => "テストレコード",
+        TestRecordBody => "システムテスト、自動削除済み。",
+        
+        LogNewCall => "{name}様から{procedure}の問い合わせ - スコア{score}/10",
+        LogBooked => "{name}様の{procedure}を予約",
+        LogConfirmed => "{name}様が明日の{procedure}を確認",
+        LogVisited => "{name}様 会計完了 - {procedure}終了",
+        LogTreatmentPlan => "{name}様の治療計画を作成 - {amount}",
+        LogInsuranceCheck => "{name}様を確認 - {payer} 有効",
+        LogClaimFiled => "{name}様の{amount}の請求を{payer}に提出",
+        LogClaimPending => "{name}様の{procedure}却下に対する再審査請求を作成",
+        LogPayment => "{name}様の{payer} {amount}を計上",
+        LogRecallDue => "{name}様をリコール対象に追加 - {months}か月経過",
+        LogRecallContacted => "{name}様にリコール送信 - 前回来院は{months}か月前",
+        LogRecallResponded => "{name}様がリコールに返信 - 予約希望",
+        LogRecallBooked => "{name}様が{months}か月ぶりに再予約",
+        LogReviewReceived => "星{rating}つの新しいレビューを受信",
+        LogResponseDrafted => "エージェントが返信を作成 - 確認待ち",
+        LogResponsePosted => "星{rating}つのレビューに返信を投稿",
+        
+        PrevCalled => "{name}様から先ほど{procedure}の問い合わせ",
+        PrevBooked => "{name}様は昨日予約済み",
+        PrevConfirmed => "{name}様が今朝確認",
+        PrevVisited => "{name}様の{Procedure}が完了",
+        PrevVerified => "{name}様の{payer}適用を確認",
+        PrevClaimFiled => "{name}様の請求は2週間前に提出済み",
+        PrevRecallResponded => "{name}様がリコール連絡に返信",
+        PrevResponseDrafted => "エージェントが先ほど返信を作成",
+        
+        BatchVerified => "明日の患者{count}名を一括確認",
+        BatchConfirmations => "確認メッセージを{count}件送信",
+        BatchRecalls => "期限切れの患者{count}名に連絡",
+        BatchSynced => "レセコンから{count}件のレコードを同期",
+        BatchPayments => "{count}件の入金、合計{amount}を計上",
+        AgentScheduling => "完了: スケジュール最適化",
+        AgentRecalls => "完了: リコール優先順位付け",
+        AgentClaims => "完了: 請求レビュー",
+        
+        NarrateOverview => "{when}: 予約{total}件中{done}件が完了、{waiting}名が待合室にいます。",
+        NarrateIncident => "{doctor}の{time}の診療が長引いたため、エージェントが{procedure}の予約を{count}件変更しました。",
+        NarrateAttention => "受信トレイの最優先: {title}。{body}",
+        NarrateAutomation => "これまでに自動処理{automations}件、人の判断は{decisions}件 - 約{ratio}対1です。",
+        WhenEarlyMorning => "開院前",
+        WhenMorning => "午前中",
+        WhenMidday => "お昼どき",
+        WhenAfternoon => "午後",
+        WhenLateAfternoon => "閉院間際",
+        WhenEvening => "診療時間外",
+    }
+}
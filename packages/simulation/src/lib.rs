@@ -5,17 +5,33 @@
 //!
 //! Philosophy: A demo should feel alive. Same seed = same story, but
 //! the story unfolds naturally with time.
+//!
+//! Budget: `stateAt` is called on every animation frame, so it should stay
+//! under 2ms on Workers. State only changes minute to minute, so each
+//! simulation keeps the last minute's state and re-stamps it for frames
+//! within that minute; scenarios with a per-day skeleton (devops/agents)
+//! also cache the day and project it to each minute. `cargo bench` tracks
+//! both the cold and cached paths.
 
 mod rng;
 mod scenario;
+mod locale;
 mod dental;
+mod dental_text;
+mod agents;
+mod presets;
+
+use std::cell::RefCell;
 
 use wasm_bindgen::prelude::*;
 use serde::{Deserialize, Serialize};
 
 pub use rng::Rng;
-pub use scenario::{Scenario, TimeOfDay};
+pub use scenario::{Outage, Scenario, SimOptions, SimTargets, TimeOfDay};
+pub use locale::Locale;
 pub use dental::DentalScenario;
+pub use agents::AgentsScenario;
+pub use presets::{Preset, PresetExport, PRESET_VERSION};
 
 /// A simulated data item
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -44,6 +60,16 @@ pub struct SimLogEntry {
     pub entry_type: String,
 }
 
+/// A story beat for voice-over or TTS
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimBeat {
+    pub minutes_ago: i64,
+    pub text: String,
+    /// overview, incident, attention, automation, or activity
+    pub beat_type: String,
+}
+
 /// Real-time metrics snapshot
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -62,6 +88,9 @@ pub struct SimMetrics {
     pub agents_completed: u32,
     pub agents_awaiting: u32,
     pub human_decisions: u32,
+    /// Scenario-specific metrics
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extra: Option<serde_json::Value>,
 }
 
 /// Complete simulation state at a point in time
@@ -80,6 +109,9 @@ pub struct SimState {
 pub struct Simulation {
     seed: u64,
     scenario: String,
+    options: SimOptions,
+    /// The last state generated, by minute since the epoch
+    last_state: RefCell<Option<(i64, SimState)>>,
 }
 
 #[wasm_bindgen]
@@ -87,10 +119,7 @@ impl Simulation {
     /// Create a new simulation with a seed
     #[wasm_bindgen(constructor)]
     pub fn new(seed: u64, scenario: &str) -> Simulation {
-        Simulation {
-            seed,
-            scenario: scenario.to_string(),
-        }
+        Simulation::with_options(seed, scenario, SimOptions::default())
     }
 
     /// Create a simulation with the current timestamp as seed
@@ -98,19 +127,56 @@ impl Simulation {
     pub fn from_timestamp(timestamp: i64, scenario: &str) -> Simulation {
         // Use day-level seed so demos are consistent within a day
         let day_seed = (timestamp / 86400) as u64;
-        Simulation {
-            seed: day_seed,
-            scenario: scenario.to_string(),
-        }
+        Simulation::with_options(day_seed, scenario, SimOptions::default())
     }
 
+    /// A fixed QA day by name, optionally pinned to a version
+    /// (`"system-recovery"`, `"fully-booked@1"`)
+    #[wasm_bindgen(js_name = fromPreset)]
+    pub fn from_preset(name: &str) -> Result<Simulation, JsValue> {
+        Preset::find(name)
+            .map(|preset| preset.simulation())
+            .map_err(|e| JsValue::from_str(&e))
+    }
+    
+    /// A preset's whole day as JSON: the state every `stepMinutes` from
+    /// local midnight, with the preset and its version
+    #[wasm_bindgen(js_name = exportPreset)]
+    pub fn export_preset(name: &str, step_minutes: u32) -> Result<String, JsValue> {
+        let export = Preset::find(name)
+            .map_err(|e| JsValue::from_str(&e))?
+            .export(step_minutes);
+        serde_json::to_string(&export).map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+    
+    /// Names of the available presets
+    #[wasm_bindgen(js_name = presetNames)]
+    pub fn preset_names() -> Vec<String> {
+        Preset::all().into_iter().map(|p| p.name).collect()
+    }
+    
+    /// Render names and text in a locale ("en", "es", "de", "ja"; others fall back to English)
+    #[wasm_bindgen(js_name = withLocale)]
+    pub fn with_locale(mut self, locale: &str) -> Simulation {
+        self.options.locale = Locale::parse(locale);
+        self.last_state = RefCell::default();
+        self
+    }
+    
+    /// Scale volumes and metrics to caller-provided targets, e.g.
+    /// `{"appointmentsPerDay": 120, "onTimeRate": 92, "maxWaitMinutes": 5}`
+    #[wasm_bindgen(js_name = withTargets)]
+    pub fn with_targets(mut self, targets: &str) -> Result<Simulation, JsValue> {
+        self.options.targets = SimTargets::from_json(targets)
+            .map_err(|e| JsValue::from_str(&format!("Invalid targets: {}", e)))?;
+        self.last_state = RefCell::default();
+        Ok(self)
+    }
+    
     /// Get the complete simulation state at a given timestamp
     #[wasm_bindgen(js_name = stateAt)]
     pub fn state_at(&self, timestamp_ms: i64) -> JsValue {
-        let state = match self.scenario.as_str() {
-            "dental" => DentalScenario::generate(self.seed, timestamp_ms),
-            _ => DentalScenario::generate(self.seed, timestamp_ms), // Default to dental
-        };
+        let state = self.state(timestamp_ms);
         
         serde_wasm_bindgen::to_value(&state).unwrap_or(JsValue::NULL)
     }
@@ -118,10 +184,7 @@ impl Simulation {
     /// Get just the items at a given timestamp
     #[wasm_bindgen(js_name = itemsAt)]
     pub fn items_at(&self, timestamp_ms: i64) -> JsValue {
-        let state = match self.scenario.as_str() {
-            "dental" => DentalScenario::generate(self.seed, timestamp_ms),
-            _ => DentalScenario::generate(self.seed, timestamp_ms),
-        };
+        let state = self.state(timestamp_ms);
         
         serde_wasm_bindgen::to_value(&state.items).unwrap_or(JsValue::NULL)
     }
@@ -129,10 +192,7 @@ impl Simulation {
     /// Get just the metrics at a given timestamp
     #[wasm_bindgen(js_name = metricsAt)]
     pub fn metrics_at(&self, timestamp_ms: i64) -> JsValue {
-        let state = match self.scenario.as_str() {
-            "dental" => DentalScenario::generate(self.seed, timestamp_ms),
-            _ => DentalScenario::generate(self.seed, timestamp_ms),
-        };
+        let state = self.state(timestamp_ms);
         
         serde_wasm_bindgen::to_value(&state.metrics).unwrap_or(JsValue::NULL)
     }
@@ -140,15 +200,19 @@ impl Simulation {
     /// Get the activity log at a given timestamp
     #[wasm_bindgen(js_name = activityLogAt)]
     pub fn activity_log_at(&self, timestamp_ms: i64, count: usize) -> JsValue {
-        let state = match self.scenario.as_str() {
-            "dental" => DentalScenario::generate(self.seed, timestamp_ms),
-            _ => DentalScenario::generate(self.seed, timestamp_ms),
-        };
+        let state = self.state(timestamp_ms);
         
         let log: Vec<_> = state.activity_log.into_iter().take(count).collect();
         serde_wasm_bindgen::to_value(&log).unwrap_or(JsValue::NULL)
     }
 
+    /// Get narration beats at a given timestamp, in speaking order
+    #[wasm_bindgen(js_name = narrationAt)]
+    pub fn narration_at(&self, timestamp_ms: i64, count: usize) -> JsValue {
+        let beats: Vec<_> = self.narrate(timestamp_ms).into_iter().take(count).collect();
+        serde_wasm_bindgen::to_value(&beats).unwrap_or(JsValue::NULL)
+    }
+    
     /// Get the seed for debugging
     #[wasm_bindgen(getter)]
     pub fn seed(&self) -> u64 {
@@ -156,6 +220,46 @@ impl Simulation {
     }
 }
 
+impl Simulation {
+    pub fn with_options(seed: u64, scenario: &str, options: SimOptions) -> Simulation {
+        Simulation {
+            seed,
+            scenario: scenario.to_string(),
+            options,
+            last_state: RefCell::default(),
+        }
+    }
+    
+    /// The state at a timestamp, reusing the last one generated when it's
+    /// for the same minute
+    pub fn state(&self, timestamp_ms: i64) -> SimState {
+        let minute = timestamp_ms.div_euclid(60_000);
+        if let Some((cached, state)) = self.last_state.borrow().as_ref() {
+            if *cached == minute {
+                return SimState { simulation_time: timestamp_ms, ..state.clone() };
+            }
+        }
+        
+        let state = self.generate(timestamp_ms);
+        *self.last_state.borrow_mut() = Some((minute, state.clone()));
+        state
+    }
+    
+    fn generate(&self, timestamp_ms: i64) -> SimState {
+        match self.scenario.as_str() {
+            "devops/agents" | "agents" => AgentsScenario::generate_with(self.seed, timestamp_ms, &self.options),
+            _ => DentalScenario::generate_with(self.seed, timestamp_ms, &self.options), // Default to dental
+        }
+    }
+    
+    fn narrate(&self, timestamp_ms: i64) -> Vec<SimBeat> {
+        match self.scenario.as_str() {
+            "devops/agents" | "agents" => AgentsScenario::narrate_with(self.seed, timestamp_ms, &self.options),
+            _ => DentalScenario::narrate_with(self.seed, timestamp_ms, &self.options),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -184,4 +288,27 @@ mod tests {
         // Metrics should differ by time of day
         assert!(morning.metrics.appointments_completed < afternoon.metrics.appointments_completed);
     }
+    
+    #[test]
+    fn test_cached_state_matches_fresh() {
+        let json = |state: &SimState| serde_json::to_string(state).unwrap();
+        for scenario in ["dental", "devops/agents"] {
+            let sim = Simulation::new(12345, scenario);
+            let ts = 1705849200000i64;
+            
+            // Later in the same minute: reused, with the new timestamp
+            sim.state(ts);
+            let cached = sim.state(ts + 30_000);
+            assert_eq!(cached.simulation_time, ts + 30_000);
+            assert_eq!(json(&cached), json(&sim.generate(ts + 30_000)));
+            
+            // A different minute, or a cached day under other options, is
+            // generated afresh
+            let next = sim.state(ts + 60_000);
+            assert_eq!(json(&next), json(&Simulation::new(12345, scenario).generate(ts + 60_000)));
+            let busy = Simulation::new(12345, scenario)
+                .with_targets(r#"{"tasksPerDay": 120, "appointmentsPerDay": 120}"#).unwrap();
+            assert_ne!(json(&busy.state(ts + 60_000)), json(&next));
+        }
+    }
 }
@@ -0,0 +1,109 @@
+//! Localization
+//!
+//! Demos for international prospects shouldn't be full of "Sarah Johnson"
+//! and "$150". A locale picks the name pools, currency formatting, and
+//! templates a scenario renders with. Templates are plain strings with
+//! `{name}` placeholders so translations can reorder them freely.
+//!
+//! The RNG is consumed the same way in every locale, so a seed tells the
+//! same story in each language.
+
+use std::fmt::Display;
+
+/// Supported demo locales
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    #[default]
+    En,
+    Es,
+    De,
+    Ja,
+}
+
+impl Locale {
+    /// Parse a language tag ("es", "de-DE", "ja_JP"); unknown tags fall back to English
+    pub fn parse(tag: &str) -> Self {
+        let language = tag.split(['-', '_']).next().unwrap_or("").to_lowercase();
+        match language.as_str() {
+            "es" => Locale::Es,
+            "de" => Locale::De,
+            "ja" => Locale::Ja,
+            _ => Locale::En,
+        }
+    }
+    
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Es => "es",
+            Locale::De => "de",
+            Locale::Ja => "ja",
+        }
+    }
+    
+    /// Full name in local order (family name first in Japanese)
+    pub fn full_name(&self, first: &str, last: &str) -> String {
+        match self {
+            Locale::Ja => format!("{} {}", last, first),
+            _ => format!("{} {}", first, last),
+        }
+    }
+    
+    /// A noun as it appears mid-sentence (German and Japanese keep their casing)
+    pub fn in_sentence(&self, noun: &str) -> String {
+        match self {
+            Locale::En | Locale::Es => noun.to_lowercase(),
+            Locale::De | Locale::Ja => noun.to_string(),
+        }
+    }
+    
+    /// Clock time for an hour of the day ("10am", "10:00", "10時")
+    pub fn clock(&self, hour: u32) -> String {
+        match self {
+            Locale::En => match hour {
+                0 => "12am".to_string(),
+                12 => "noon".to_string(),
+                1..=11 => format!("{}am", hour),
+                _ => format!("{}pm", hour - 12),
+            },
+            Locale::Es | Locale::De => format!("{}:00", hour),
+            Locale::Ja => format!("{}時", hour),
+        }
+    }
+    
+    /// Format a USD amount in the local currency
+    pub fn money(&self, usd: u32) -> String {
+        match self {
+            Locale::En | Locale::Es => format!("${}", usd),
+            Locale::De => format!("{} €", usd),
+            // Yen amounts are ~150x; round to the nearest ¥100 so they look priced
+            Locale::Ja => format!("¥{}", usd as u64 * 150 / 100 * 100),
+        }
+    }
+}
+
+/// Fill `{key}` placeholders in a template
+pub fn fill(template: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut out = template.to_string();
+    for (key, value) in args {
+        out = out.replace(&format!("{{{}}}", key), &value.to_string());
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_parse_and_fill() {
+        assert_eq!(Locale::parse("es-MX"), Locale::Es);
+        assert_eq!(Locale::parse("de_DE"), Locale::De);
+        assert_eq!(Locale::parse("JA"), Locale::Ja);
+        assert_eq!(Locale::parse("fr"), Locale::En);
+        
+        assert_eq!(Locale::Ja.full_name("花子", "田中"), "田中 花子");
+        assert_eq!(Locale::Ja.money(150), "¥22500");
+        assert_eq!(fill("{b} then {a}", &[("a", &1), ("b", &"two")]), "two then 1");
+    }
+}
@@ -0,0 +1,179 @@
+//! Incident Day Presets
+//!
+//! Fixed datasets for QA: each preset pins a scenario, seed, day, and the
+//! targets or outage that make the day an edge case. Exporting a preset
+//! dumps the state at every step through the day, so tests can assert on
+//! a known day instead of whatever today's seed produces.
+//!
+//! Presets are versioned together. Any change to generation that alters a
+//! preset's output bumps `PRESET_VERSION`, and a request pinned to an older
+//! version (`"fully-booked@1"`) fails rather than quietly returning
+//! different data.
+//!
+//! | Preset | Scenario | Day | Characteristics |
+//! |--------|----------|-----|-----------------|
+//! | `fully-booked` | dental | Tue 2024-01-23 | ~60 appointments (Tuesday on a 52-a-day practice), up to 5 in the waiting room during the morning rush, waits capped at 12 min, on-time rate around 90% |
+//! | `mass-cancellations` | dental | Mon 2024-01-22 | ~20 appointments, no-show rate around 28% |
+//! | `system-recovery` | devops/agents | Wed 2024-01-24 | Agent pool down 9:30-11:00; work in flight fails with "agent pool unreachable", tasks queue through the outage and drain by ~11:25 |
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Simulation, SimState};
+use crate::scenario::{Outage, SimOptions, SimTargets};
+
+/// Version of every preset's output
+pub const PRESET_VERSION: u32 = 1;
+
+/// Minutes in a day
+const DAY_MINUTES: u32 = 1440;
+
+/// A named, fixed simulation day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Preset {
+    pub name: String,
+    pub description: String,
+    pub scenario: String,
+    pub seed: u64,
+    /// Local (CST) midnight of the simulated day, in milliseconds
+    pub day_start_ms: i64,
+    pub targets: SimTargets,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outage: Option<Outage>,
+}
+
+/// A preset's state series
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PresetExport {
+    pub version: u32,
+    pub preset: Preset,
+    pub step_minutes: u32,
+    /// The state at midnight, then every `step_minutes` through the day
+    pub states: Vec<SimState>,
+}
+
+impl Preset {
+    /// Every preset, in the order of the module table
+    pub fn all() -> Vec<Preset> {
+        vec![
+            Preset {
+                name: "fully-booked".to_string(),
+                description: "Dental practice booked well past capacity on its busiest weekday".to_string(),
+                scenario: "dental".to_string(),
+                seed: 4101,
+                day_start_ms: 1705989600000,
+                targets: SimTargets {
+                    appointments_per_day: Some(52),
+                    on_time_rate: Some(90),
+                    max_wait_minutes: Some(12),
+                    ..Default::default()
+                },
+                outage: None,
+            },
+            Preset {
+                name: "mass-cancellations".to_string(),
+                description: "Dental practice losing over a quarter of a thin schedule to cancellations and no-shows".to_string(),
+                scenario: "dental".to_string(),
+                seed: 4102,
+                day_start_ms: 1705903200000,
+                targets: SimTargets {
+                    appointments_per_day: Some(20),
+                    no_show_rate: Some(28),
+                    ..Default::default()
+                },
+                outage: None,
+            },
+            Preset {
+                name: "system-recovery".to_string(),
+                description: "Agent pool outage mid-morning, failed work to triage, and the backlog draining afterwards".to_string(),
+                scenario: "devops/agents".to_string(),
+                seed: 4103,
+                day_start_ms: 1706076000000,
+                targets: SimTargets::default(),
+                outage: Some(Outage { start_minute: 570, end_minute: 660 }),
+            },
+        ]
+    }
+    
+    /// Look up `name` or `name@version`; a version other than the current
+    /// one is an error
+    pub fn find(spec: &str) -> Result<Preset, String> {
+        let (name, version) = match spec.split_once('@') {
+            Some((name, version)) => {
+                let version: u32 = version.parse()
+                    .map_err(|_| format!("Invalid preset version: {}", version))?;
+                (name, Some(version))
+            }
+            None => (spec, None),
+        };
+        if let Some(version) = version.filter(|v| *v != PRESET_VERSION) {
+            return Err(format!(
+                "Preset {} is at version {}, not {}; regenerate fixtures pinned to the old version",
+                name, PRESET_VERSION, version
+            ));
+        }
+        
+        Self::all().into_iter()
+            .find(|p| p.name == name)
+            .ok_or_else(|| format!("Unknown preset: {}", name))
+    }
+    
+    pub fn simulation(&self) -> Simulation {
+        Simulation::with_options(self.seed, &self.scenario, SimOptions {
+            targets: self.targets.clone(),
+            outage: self.outage,
+            ..Default::default()
+        })
+    }
+    
+    /// The whole day's states, every `step_minutes` (at least 1)
+    pub fn export(&self, step_minutes: u32) -> PresetExport {
+        let step = step_minutes.max(1);
+        let simulation = self.simulation();
+        let states = (0..DAY_MINUTES).step_by(step as usize)
+            .map(|minute| simulation.generate(self.day_start_ms + minute as i64 * 60_000))
+            .collect();
+        
+        PresetExport {
+            version: PRESET_VERSION,
+            preset: self.clone(),
+            step_minutes: step,
+            states,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_presets_export_their_characteristics() {
+        let at = |export: &PresetExport, hour: u32| -> SimState {
+            export.states[(hour * 60 / export.step_minutes) as usize].clone()
+        };
+        
+        let booked = Preset::find("fully-booked@1").unwrap().export(30);
+        assert_eq!(booked.states.len(), 48);
+        assert!(at(&booked, 12).metrics.appointments_total >= 45);
+        assert!(booked.states.iter().all(|s| s.metrics.avg_wait_minutes <= 12));
+        
+        let cancelled = Preset::find("mass-cancellations").unwrap().export(60);
+        assert!(at(&cancelled, 12).metrics.no_show_rate >= 25);
+        
+        let recovery = Preset::find("system-recovery").unwrap().export(15);
+        let extra = |s: &SimState, key: &str| s.metrics.extra.as_ref().unwrap()[key].as_u64().unwrap();
+        let during = at(&recovery, 10);
+        assert_eq!(extra(&during, "tasksClaimed"), 0);
+        assert!(extra(&during, "tasksReady") > 0);
+        assert!(during.items.iter().any(|i| i.body.contains("agent pool unreachable")));
+        assert_eq!(extra(&at(&recovery, 23), "tasksReady"), 0);
+        
+        // Same preset, same data; other versions are refused
+        let again = Preset::find("system-recovery").unwrap().export(15);
+        assert_eq!(serde_json::to_string(&recovery).unwrap(), serde_json::to_string(&again).unwrap());
+        assert!(Preset::find("fully-booked@0").is_err());
+        assert!(Preset::find("quiet-day").is_err());
+    }
+}
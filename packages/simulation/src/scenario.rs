@@ -3,7 +3,10 @@
 //! Provides time-aware simulation scaffolding.
 //! Each vertical (dental, writer, agency) implements Scenario.
 
-use crate::{SimState, Rng};
+use serde::{Deserialize, Serialize};
+
+use crate::{SimBeat, SimState, Rng};
+use crate::locale::Locale;
 
 /// Time of day affects behavior patterns
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -72,6 +75,8 @@ pub struct SimTime {
     pub minute_of_day: u32,
     /// Day of week (0 = Sunday, 6 = Saturday)
     pub day_of_week: u32,
+    /// Local days since epoch (stable for the whole simulated day)
+    pub day: i64,
     /// Time of day enum
     pub time_of_day: TimeOfDay,
     /// Business progress (0.0 - 1.0)
@@ -106,6 +111,7 @@ impl SimTime {
             hour,
             minute_of_day,
             day_of_week,
+            day: days_since_epoch,
             time_of_day,
             business_progress,
         }
@@ -122,10 +128,94 @@ impl SimTime {
     }
 }
 
+/// Caller-provided targets ("we do 120 appointments/day")
+///
+/// Each target rescales its metric's default baseline, so the time-of-day
+/// and day-of-week shape is preserved. Unset targets keep the defaults.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SimTargets {
+    /// Appointments on a typical weekday (dental); also scales item volume
+    pub appointments_per_day: Option<u32>,
+    /// Tasks filed on a typical weekday (devops/agents); also scales item volume
+    pub tasks_per_day: Option<u32>,
+    /// Automations by end of day
+    pub automations_per_day: Option<u32>,
+    /// Human decisions by end of day
+    pub human_decisions_per_day: Option<u32>,
+    /// On-time rate SLA (%) - the metric hovers around it
+    pub on_time_rate: Option<u32>,
+    /// No-show rate (%) - the metric hovers around it
+    pub no_show_rate: Option<u32>,
+    /// Wait-time SLA - waits never exceed it
+    pub max_wait_minutes: Option<u32>,
+}
+
+impl SimTargets {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+    
+    /// Factor that takes a default baseline to the target (1.0 when unset)
+    pub fn scale(target: Option<u32>, baseline: f64) -> f64 {
+        target.map(|t| t as f64 / baseline).unwrap_or(1.0)
+    }
+    
+    /// Shift a percentage so it hovers around `target` instead of `baseline`
+    pub fn shift_rate(value: u32, target: Option<u32>, baseline: u32) -> u32 {
+        match target {
+            Some(t) => (value as i64 + t as i64 - baseline as i64).clamp(0, 100) as u32,
+            None => value,
+        }
+    }
+}
+
+/// A window when the agent pool is down (devops/agents)
+///
+/// Work in flight when it starts fails, nothing is claimed until it ends,
+/// and the backlog drains over the following quarter of its length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Outage {
+    /// Minutes since local midnight
+    pub start_minute: u32,
+    pub end_minute: u32,
+}
+
+impl Outage {
+    pub fn contains(&self, minute: u32) -> bool {
+        minute >= self.start_minute && minute < self.end_minute
+    }
+}
+
+/// Per-demo generation options
+#[derive(Debug, Clone, Default)]
+pub struct SimOptions {
+    /// Language for names and generated text
+    pub locale: Locale,
+    /// Metric targets and SLA thresholds
+    pub targets: SimTargets,
+    /// Agent pool outage to recover from
+    pub outage: Option<Outage>,
+}
+
 /// Trait for scenario implementations
 pub trait Scenario {
     /// Generate the complete state at a given time
-    fn generate(seed: u64, timestamp_ms: i64) -> SimState;
+    fn generate(seed: u64, timestamp_ms: i64) -> SimState {
+        Self::generate_with(seed, timestamp_ms, &SimOptions::default())
+    }
+    
+    /// Generate the complete state at a given time with demo options
+    fn generate_with(seed: u64, timestamp_ms: i64, options: &SimOptions) -> SimState;
+    
+    /// Story beats describing the state at a given time, in speaking order
+    fn narrate(seed: u64, timestamp_ms: i64) -> Vec<SimBeat> {
+        Self::narrate_with(seed, timestamp_ms, &SimOptions::default())
+    }
+    
+    /// Story beats with demo options
+    fn narrate_with(seed: u64, timestamp_ms: i64, options: &SimOptions) -> Vec<SimBeat>;
 }
 
 #[cfg(test)]
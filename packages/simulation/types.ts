@@ -26,6 +26,13 @@ export interface SimLogEntry {
   entryType: string;
 }
 
+/** A story beat for voice-over or TTS */
+export interface SimBeat {
+  minutesAgo: number;
+  text: string;
+  beatType: 'overview' | 'incident' | 'attention' | 'automation' | 'activity';
+}
+
 /** Real-time metrics snapshot */
 export interface SimMetrics {
   waitingRoom: number;
@@ -42,6 +49,26 @@ export interface SimMetrics {
   agentsCompleted: number;
   agentsAwaiting: number;
   humanDecisions: number;
+  /** Scenario-specific metrics (e.g. tasksReady, costTodayUsd for devops/agents) */
+  extra?: Record<string, number>;
+}
+
+/** Metric targets and SLA thresholds (all optional; unset keeps the defaults) */
+export interface SimTargets {
+  /** Appointments on a typical weekday (dental); also scales item volume */
+  appointmentsPerDay?: number;
+  /** Tasks filed on a typical weekday (devops/agents); also scales item volume */
+  tasksPerDay?: number;
+  /** Automations by end of day */
+  automationsPerDay?: number;
+  /** Human decisions by end of day */
+  humanDecisionsPerDay?: number;
+  /** On-time rate SLA (%) */
+  onTimeRate?: number;
+  /** No-show rate (%) */
+  noShowRate?: number;
+  /** Wait-time SLA; waits never exceed it */
+  maxWaitMinutes?: number;
 }
 
 /** Complete simulation state at a point in time */
@@ -53,11 +80,45 @@ export interface SimState {
   simulationTime: number;
 }
 
+/** Agent pool outage window (devops/agents), in minutes since local midnight */
+export interface Outage {
+  startMinute: number;
+  endMinute: number;
+}
+
+/** A named, fixed QA day */
+export interface Preset {
+  name: 'fully-booked' | 'mass-cancellations' | 'system-recovery' | string;
+  description: string;
+  scenario: string;
+  seed: number;
+  /** Local (CST) midnight of the simulated day */
+  dayStartMs: number;
+  targets: SimTargets;
+  outage?: Outage;
+}
+
+/** A preset's whole day, from Simulation.exportPreset */
+export interface PresetExport {
+  /** Preset version; pin with 'name@version' */
+  version: number;
+  preset: Preset;
+  stepMinutes: number;
+  /** State at local midnight, then every stepMinutes */
+  states: SimState[];
+}
+
 /** Simulation class interface (matches WASM exports) */
 export interface Simulation {
   /** Create from explicit seed */
   new(seed: bigint, scenario: string): Simulation;
   
+  /** Render names and text in a locale ('en' | 'es' | 'de' | 'ja'; others fall back to English) */
+  withLocale(locale: string): Simulation;
+  
+  /** Scale volumes and metrics to caller-provided targets (JSON-encoded SimTargets); throws on unknown keys */
+  withTargets(targetsJson: string): Simulation;
+  
   /** Get complete state at timestamp */
   stateAt(timestampMs: number): SimState;
   
@@ -70,6 +131,9 @@ export interface Simulation {
   /** Get activity log at timestamp */
   activityLogAt(timestampMs: number, count: number): SimLogEntry[];
   
+  /** Get narration beats at timestamp, in speaking order (for voice-over or TTS) */
+  narrationAt(timestampMs: number, count: number): SimBeat[];
+  
   /** Current seed (for debugging) */
   readonly seed: bigint;
 }
@@ -79,6 +143,11 @@ export interface SimulationModule {
   Simulation: {
     new(seed: bigint, scenario: string): Simulation;
     fromTimestamp(timestampMs: number, scenario: string): Simulation;
+    /** A preset day by name, optionally pinned ('fully-booked@1'); throws on unknown names or versions */
+    fromPreset(name: string): Simulation;
+    /** JSON-encoded PresetExport for a preset's whole day */
+    exportPreset(name: string, stepMinutes: number): string;
+    presetNames(): string[];
   };
 }
 